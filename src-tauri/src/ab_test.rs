@@ -0,0 +1,131 @@
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager, State};
+use uuid::Uuid;
+
+use crate::backend::ab_test::{AbVariant, AbVariantRun};
+use crate::backend::rpc_response::{response_error, response_result};
+use crate::backend::turn_limiter::TurnLane;
+use crate::codex::{self, SendOptions};
+use crate::state::AppState;
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestStarted {
+    pub(crate) test_id: String,
+    pub(crate) variants: Vec<AbVariantRun>,
+}
+
+/// Completes A/B tests from app-server notifications; called for every
+/// `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let completed = match state.ab_tests.lock() {
+        Ok(mut tests) => tests.observe(workspace_id, message),
+        Err(_) => None,
+    };
+    if let Some(completed) = completed {
+        let _ = app.emit("ab-test-completed", completed);
+    }
+}
+
+async fn start_variant(
+    workspace_id: &str,
+    variant: AbVariant,
+    fallback_label: &str,
+    state: &State<'_, AppState>,
+    app: &AppHandle,
+) -> Result<AbVariantRun, String> {
+    let response =
+        codex::start_thread(workspace_id.to_string(), state.clone(), app.clone()).await?;
+    if let Some(error) = response_error(&response) {
+        return Err(error);
+    }
+    let thread_id = response_result(&response)
+        .get("thread")
+        .and_then(|thread| thread.get("id"))
+        .and_then(|value| value.as_str())
+        .ok_or("thread/start did not return a thread id")?
+        .to_string();
+    Ok(AbVariantRun {
+        label: variant
+            .label
+            .filter(|label| !label.trim().is_empty())
+            .unwrap_or_else(|| fallback_label.to_string()),
+        model: variant.model,
+        effort: variant.effort,
+        thread_id,
+        turn_id: None,
+    })
+}
+
+/// Starts `prompt` in two fresh threads with different model/effort profiles.
+/// An `ab-test-completed` event with per-variant metrics follows once both
+/// turns have finished.
+#[tauri::command]
+pub(crate) async fn run_ab_test(
+    workspace_id: String,
+    prompt: String,
+    variant_a: AbVariant,
+    variant_b: AbVariant,
+    access_mode: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<AbTestStarted, String> {
+    if prompt.trim().is_empty() {
+        return Err("empty user message".to_string());
+    }
+    let test_id = format!("ab-{}", Uuid::new_v4().simple());
+    let mut runs = Vec::with_capacity(2);
+    for (variant, label) in [(variant_a, "A"), (variant_b, "B")] {
+        runs.push(start_variant(&workspace_id, variant, label, &state, &app).await?);
+    }
+    state
+        .ab_tests
+        .lock()
+        .map_err(|err| err.to_string())?
+        .register(&test_id, &workspace_id, &prompt, runs.clone());
+
+    for run in runs.iter_mut() {
+        let response = codex::send_message(
+            workspace_id.clone(),
+            run.thread_id.clone(),
+            prompt.clone(),
+            SendOptions {
+                model: run.model.clone(),
+                effort: run.effort.clone(),
+                access_mode: access_mode.clone(),
+                lane: TurnLane::Batch,
+                ..SendOptions::default()
+            },
+            state.clone(),
+            app.clone(),
+        )
+        .await
+        .and_then(|response| match response_error(&response) {
+            Some(error) => Err(error),
+            None => Ok(response),
+        });
+        match response {
+            Ok(response) => {
+                run.turn_id = response_result(&response)
+                    .get("turn")
+                    .and_then(|turn| turn.get("id"))
+                    .and_then(|value| value.as_str())
+                    .map(|value| value.to_string());
+            }
+            Err(error) => {
+                if let Ok(mut tests) = state.ab_tests.lock() {
+                    tests.cancel(&test_id);
+                }
+                return Err(error);
+            }
+        }
+    }
+    Ok(AbTestStarted {
+        test_id,
+        variants: runs,
+    })
+}
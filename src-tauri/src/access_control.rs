@@ -0,0 +1,363 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use subtle::ConstantTimeEq;
+
+use tauri::State;
+use uuid::Uuid;
+
+use crate::backend::i18n::{localize, MessageCode};
+use crate::state::AppState;
+use crate::types::WorkspaceEntry;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+struct AccessControlConfig {
+    #[serde(default)]
+    passphrase_hash: Option<String>,
+    #[serde(default)]
+    observer_mode: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AccessControlStatus {
+    pub(crate) passphrase_set: bool,
+    pub(crate) observer_mode: bool,
+    pub(crate) unlocked_workspace_ids: Vec<String>,
+}
+
+/// Passphrase and observer-mode state for restricted workspaces. Kept out of
+/// `settings.json` so that settings updates from the UI cannot clear it.
+pub(crate) struct AccessControl {
+    path: PathBuf,
+    config: AccessControlConfig,
+    unlocked: HashSet<String>,
+}
+
+pub(crate) fn access_control_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("access_control.json")
+}
+
+const PASSPHRASE_SCHEME: &str = "pbkdf2-sha256";
+/// PBKDF2-HMAC-SHA256 rounds for new hashes, as for share bundles.
+const PASSPHRASE_ITERATIONS: u32 = 210_000;
+const MAX_PASSPHRASE_ITERATIONS: u32 = 5_000_000;
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn pbkdf2_hex(passphrase: &str, salt: &str, iterations: u32) -> String {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt.as_bytes(), iterations, &mut key);
+    to_hex(&key)
+}
+
+/// Stored as `pbkdf2-sha256$<iterations>$<salt>$<hex>`.
+fn hash_passphrase_with(passphrase: &str, salt: &str, iterations: u32) -> String {
+    format!(
+        "{PASSPHRASE_SCHEME}${iterations}${salt}${}",
+        pbkdf2_hex(passphrase, salt, iterations)
+    )
+}
+
+fn hash_passphrase(passphrase: &str, salt: &str) -> String {
+    hash_passphrase_with(passphrase, salt, PASSPHRASE_ITERATIONS)
+}
+
+fn new_salt() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// Hashes written before PBKDF2, `<salt>$<hex>` of one salted SHA-256 round.
+/// They still verify, and are replaced on the next successful unlock.
+fn is_legacy_hash(stored: &str) -> bool {
+    !stored.starts_with(&format!("{PASSPHRASE_SCHEME}$"))
+}
+
+fn verify_passphrase(stored: &str, passphrase: &str) -> bool {
+    let parts: Vec<&str> = stored.split('$').collect();
+    let (expected, hex) = match parts.as_slice() {
+        [PASSPHRASE_SCHEME, iterations, salt, hex] => match iterations.parse::<u32>() {
+            Ok(iterations) if (1..=MAX_PASSPHRASE_ITERATIONS).contains(&iterations) => {
+                (pbkdf2_hex(passphrase, salt, iterations), *hex)
+            }
+            _ => return false,
+        },
+        [salt, hex] => {
+            let mut hasher = Sha256::new();
+            hasher.update(salt.as_bytes());
+            hasher.update(passphrase.as_bytes());
+            (to_hex(&hasher.finalize()), *hex)
+        }
+        _ => return false,
+    };
+    expected.as_bytes().ct_eq(hex.as_bytes()).into()
+}
+
+impl AccessControl {
+    pub(crate) fn load(path: PathBuf) -> Self {
+        let config = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            config,
+            unlocked: HashSet::new(),
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let data = serde_json::to_string_pretty(&self.config).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, data).map_err(|e| e.to_string())
+    }
+
+    pub(crate) fn passphrase_set(&self) -> bool {
+        self.config.passphrase_hash.is_some()
+    }
+
+    pub(crate) fn check_passphrase(&self, passphrase: Option<&str>) -> Result<(), String> {
+        let Some(stored) = self.config.passphrase_hash.as_deref() else {
+            return Ok(());
+        };
+        match passphrase {
+            Some(passphrase) if verify_passphrase(stored, passphrase) => Ok(()),
+            _ => Err("Incorrect app passphrase.".to_string()),
+        }
+    }
+
+    /// Replaces a legacy hash once `passphrase` has been checked against it.
+    fn upgrade_hash(&mut self, passphrase: &str) {
+        if !self
+            .config
+            .passphrase_hash
+            .as_deref()
+            .is_some_and(is_legacy_hash)
+        {
+            return;
+        }
+        self.config.passphrase_hash = Some(hash_passphrase(passphrase, &new_salt()));
+        if let Err(err) = self.save() {
+            eprintln!("Failed to upgrade the app passphrase hash: {err}");
+        }
+    }
+
+    fn status(&self) -> AccessControlStatus {
+        let mut unlocked_workspace_ids: Vec<String> = self.unlocked.iter().cloned().collect();
+        unlocked_workspace_ids.sort();
+        AccessControlStatus {
+            passphrase_set: self.passphrase_set(),
+            observer_mode: self.config.observer_mode,
+            unlocked_workspace_ids,
+        }
+    }
+
+    /// Restricted workspaces are hidden entirely while observer mode is on.
+    pub(crate) fn is_visible(&self, entry: &WorkspaceEntry) -> bool {
+        !(entry.settings.restricted && self.config.observer_mode)
+    }
+
+    pub(crate) fn ensure_visible(&self, entry: &WorkspaceEntry) -> Result<(), String> {
+        if self.is_visible(entry) {
+            Ok(())
+        } else {
+            Err(localize(MessageCode::WorkspaceNotFound))
+        }
+    }
+
+    /// Starting turns (and with them granting full access) on a restricted
+    /// workspace requires it to be unlocked with the app passphrase.
+    pub(crate) fn ensure_unlocked(&self, entry: &WorkspaceEntry) -> Result<(), String> {
+        self.ensure_visible(entry)?;
+        if entry.settings.restricted && !self.unlocked.contains(&entry.id) {
+            return Err(
+                "Workspace is restricted. Unlock it with the app passphrase first.".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Looks up the workspace and checks it can run turns.
+pub(crate) async fn ensure_workspace_unlocked(
+    state: &AppState,
+    workspace_id: &str,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let Some(entry) = workspaces.get(workspace_id) else {
+        return Ok(());
+    };
+    state.access_control.lock().await.ensure_unlocked(entry)
+}
+
+pub(crate) async fn ensure_workspace_visible(
+    state: &AppState,
+    workspace_id: &str,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let Some(entry) = workspaces.get(workspace_id) else {
+        return Ok(());
+    };
+    state.access_control.lock().await.ensure_visible(entry)
+}
+
+#[tauri::command]
+pub(crate) async fn get_access_control_status(
+    state: State<'_, AppState>,
+) -> Result<AccessControlStatus, String> {
+    Ok(state.access_control.lock().await.status())
+}
+
+#[tauri::command]
+pub(crate) async fn set_app_passphrase(
+    current_passphrase: Option<String>,
+    passphrase: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<AccessControlStatus, String> {
+    let mut access = state.access_control.lock().await;
+    access.check_passphrase(current_passphrase.as_deref())?;
+    let passphrase = passphrase
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if passphrase.is_none() {
+        let workspaces = state.workspaces.lock().await;
+        if workspaces.values().any(|entry| entry.settings.restricted) {
+            return Err(
+                "Remove the restriction from all workspaces before clearing the passphrase."
+                    .to_string(),
+            );
+        }
+        access.config.observer_mode = false;
+    }
+    access.config.passphrase_hash = passphrase.map(|value| hash_passphrase(&value, &new_salt()));
+    access.unlocked.clear();
+    access.save()?;
+    Ok(access.status())
+}
+
+#[tauri::command]
+pub(crate) async fn unlock_workspace(
+    workspace_id: String,
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<AccessControlStatus, String> {
+    let mut access = state.access_control.lock().await;
+    if access.config.observer_mode {
+        return Err("Leave observer mode before unlocking workspaces.".to_string());
+    }
+    access.check_passphrase(Some(&passphrase))?;
+    access.upgrade_hash(&passphrase);
+    access.unlocked.insert(workspace_id);
+    Ok(access.status())
+}
+
+#[tauri::command]
+pub(crate) async fn lock_workspace(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<AccessControlStatus, String> {
+    let mut access = state.access_control.lock().await;
+    match workspace_id {
+        Some(id) => {
+            access.unlocked.remove(&id);
+        }
+        None => access.unlocked.clear(),
+    }
+    Ok(access.status())
+}
+
+#[tauri::command]
+pub(crate) async fn set_observer_mode(
+    enabled: bool,
+    passphrase: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<AccessControlStatus, String> {
+    let mut access = state.access_control.lock().await;
+    if !access.passphrase_set() {
+        return Err("Set an app passphrase before using observer mode.".to_string());
+    }
+    if !enabled {
+        access.check_passphrase(passphrase.as_deref())?;
+        access.upgrade_hash(passphrase.as_deref().unwrap_or_default());
+    }
+    access.config.observer_mode = enabled;
+    access.unlocked.clear();
+    access.save()?;
+    Ok(access.status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{WorkspaceKind, WorkspaceSettings};
+
+    fn workspace(id: &str, restricted: bool) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: "/tmp".to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings {
+                restricted,
+                ..WorkspaceSettings::default()
+            },
+            icon: None,
+            color: None,
+        }
+    }
+
+    fn access_control() -> AccessControl {
+        let path = std::env::temp_dir()
+            .join(format!("codex-monitor-test-{}", Uuid::new_v4()))
+            .join("access_control.json");
+        AccessControl::load(path)
+    }
+
+    #[test]
+    fn passphrase_hash_is_salted_and_verifiable() {
+        assert!(hash_passphrase("hunter2", "salt-a").starts_with("pbkdf2-sha256$210000$salt-a$"));
+        // Few rounds keep debug-build tests fast; the format records them.
+        let first = hash_passphrase_with("hunter2", "salt-a", 1_000);
+        assert_ne!(first, hash_passphrase_with("hunter2", "salt-b", 1_000));
+        assert!(verify_passphrase(&first, "hunter2"));
+        assert!(!verify_passphrase(&first, "hunter3"));
+        assert!(!verify_passphrase("garbage", "hunter2"));
+
+        // One salted SHA-256 round, as written before PBKDF2.
+        let legacy = "salt$6efb3088c88aa6ff4ec91880bd1a4953fb0713dbc0229960fde3a8f053b76843";
+        assert!(is_legacy_hash(legacy));
+        assert!(verify_passphrase(legacy, "hunter2"));
+        assert!(!verify_passphrase(legacy, "hunter3"));
+        assert!(!is_legacy_hash(&first));
+        assert!(!verify_passphrase("pbkdf2-sha256$0$salt$00", "hunter2"));
+    }
+
+    #[test]
+    fn restricted_workspaces_require_unlock_and_hide_in_observer_mode() {
+        let mut access = access_control();
+        access.config.passphrase_hash = Some(hash_passphrase_with("secret", "salt", 1_000));
+        let open = workspace("open", false);
+        let restricted = workspace("restricted", true);
+
+        assert!(access.ensure_unlocked(&open).is_ok());
+        assert!(access.ensure_unlocked(&restricted).is_err());
+        assert!(access.check_passphrase(Some("wrong")).is_err());
+        access.check_passphrase(Some("secret")).expect("passphrase");
+        access.unlocked.insert(restricted.id.clone());
+        assert!(access.ensure_unlocked(&restricted).is_ok());
+
+        access.config.observer_mode = true;
+        assert!(access.is_visible(&open));
+        assert!(!access.is_visible(&restricted));
+        assert!(access.ensure_unlocked(&restricted).is_err());
+    }
+}
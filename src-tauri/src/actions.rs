@@ -0,0 +1,317 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tauri::{AppHandle, State};
+
+use crate::app_lock;
+use crate::codex;
+use crate::git;
+use crate::protocol_compat;
+use crate::report;
+use crate::share_bundle;
+use crate::state::AppState;
+use crate::workspace_doctor;
+use crate::workspaces;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ActionArgumentKind {
+    WorkspaceId,
+    ThreadId,
+    String,
+    /// Any JSON value, passed through to the command.
+    Json,
+}
+
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ActionArgument {
+    pub(crate) name: &'static str,
+    pub(crate) kind: ActionArgumentKind,
+    pub(crate) required: bool,
+}
+
+/// A backend capability the command palette (and later plugins) can list
+/// and run through `invoke_action`.
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ActionDefinition {
+    pub(crate) id: &'static str,
+    pub(crate) title: &'static str,
+    pub(crate) category: &'static str,
+    pub(crate) arguments: &'static [ActionArgument],
+}
+
+const fn required(name: &'static str, kind: ActionArgumentKind) -> ActionArgument {
+    ActionArgument {
+        name,
+        kind,
+        required: true,
+    }
+}
+
+const fn optional(name: &'static str, kind: ActionArgumentKind) -> ActionArgument {
+    ActionArgument {
+        name,
+        kind,
+        required: false,
+    }
+}
+
+const WORKSPACE: ActionArgument = required("workspaceId", ActionArgumentKind::WorkspaceId);
+const THREAD: ActionArgument = required("threadId", ActionArgumentKind::ThreadId);
+
+/// Every action `invoke_action` can dispatch. Add the dispatch arm in
+/// `dispatch` together with the entry here.
+const ACTIONS: &[ActionDefinition] = &[
+    ActionDefinition {
+        id: "workspace.connect",
+        title: "Connect workspace",
+        category: "workspace",
+        arguments: &[WORKSPACE],
+    },
+    ActionDefinition {
+        id: "workspace.doctor",
+        title: "Run workspace doctor",
+        category: "workspace",
+        arguments: &[WORKSPACE],
+    },
+    ActionDefinition {
+        id: "workspace.exportReport",
+        title: "Export workspace report",
+        category: "workspace",
+        arguments: &[WORKSPACE, optional("outputDir", ActionArgumentKind::String)],
+    },
+    ActionDefinition {
+        id: "workspace.gitStatus",
+        title: "Show git status",
+        category: "git",
+        arguments: &[WORKSPACE],
+    },
+    ActionDefinition {
+        id: "thread.start",
+        title: "Start new agent",
+        category: "thread",
+        arguments: &[WORKSPACE],
+    },
+    ActionDefinition {
+        id: "thread.archive",
+        title: "Archive thread",
+        category: "thread",
+        arguments: &[WORKSPACE, THREAD],
+    },
+    ActionDefinition {
+        id: "thread.export",
+        title: "Export thread as share bundle",
+        category: "thread",
+        arguments: &[
+            WORKSPACE,
+            THREAD,
+            required("passphrase", ActionArgumentKind::String),
+            optional("outputPath", ActionArgumentKind::String),
+        ],
+    },
+    ActionDefinition {
+        id: "review.start",
+        title: "Start review",
+        category: "thread",
+        arguments: &[
+            WORKSPACE,
+            THREAD,
+            required("target", ActionArgumentKind::Json),
+            optional("delivery", ActionArgumentKind::String),
+        ],
+    },
+    ActionDefinition {
+        id: "codex.doctor",
+        title: "Run Codex doctor",
+        category: "app",
+        arguments: &[optional("codexBin", ActionArgumentKind::String)],
+    },
+    ActionDefinition {
+        id: "codex.protocolReport",
+        title: "Check app-server protocol compatibility",
+        category: "app",
+        arguments: &[],
+    },
+    ActionDefinition {
+        id: "app.lock",
+        title: "Lock app",
+        category: "app",
+        arguments: &[],
+    },
+];
+
+fn find_action(action_id: &str) -> Result<&'static ActionDefinition, String> {
+    ACTIONS
+        .iter()
+        .find(|action| action.id == action_id)
+        .ok_or_else(|| format!("Unknown action: {action_id}"))
+}
+
+/// Checks the arguments against the action's definition: required ones are
+/// present, none are unknown and each has the declared type.
+fn validate_arguments(action: &ActionDefinition, args: &Map<String, Value>) -> Result<(), String> {
+    if let Some(unknown) = args
+        .keys()
+        .find(|name| !action.arguments.iter().any(|arg| arg.name == name.as_str()))
+    {
+        return Err(format!("Unknown argument for {}: {unknown}", action.id));
+    }
+    for argument in action.arguments {
+        let value = args.get(argument.name).filter(|value| !value.is_null());
+        let Some(value) = value else {
+            if argument.required {
+                return Err(format!(
+                    "Missing argument for {}: {}",
+                    action.id, argument.name
+                ));
+            }
+            continue;
+        };
+        let valid = match argument.kind {
+            ActionArgumentKind::WorkspaceId | ActionArgumentKind::ThreadId => {
+                value.as_str().is_some_and(|value| !value.trim().is_empty())
+            }
+            ActionArgumentKind::String => value.is_string(),
+            ActionArgumentKind::Json => true,
+        };
+        if !valid {
+            return Err(format!(
+                "Invalid argument for {}: {}",
+                action.id, argument.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn string_arg(args: &Map<String, Value>, name: &str) -> String {
+    optional_string_arg(args, name).unwrap_or_default()
+}
+
+fn optional_string_arg(args: &Map<String, Value>, name: &str) -> Option<String> {
+    args.get(name)
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+}
+
+fn to_value<T: Serialize>(result: Result<T, String>) -> Result<Value, String> {
+    serde_json::to_value(result?).map_err(|err| err.to_string())
+}
+
+async fn dispatch(
+    action: &ActionDefinition,
+    args: Map<String, Value>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    let workspace_id = || string_arg(&args, "workspaceId");
+    let thread_id = || string_arg(&args, "threadId");
+    match action.id {
+        "workspace.connect" => {
+            to_value(workspaces::connect_workspace(workspace_id(), state, app).await)
+        }
+        "workspace.doctor" => {
+            to_value(workspace_doctor::workspace_doctor(workspace_id(), state).await)
+        }
+        "workspace.exportReport" => to_value(
+            report::export_workspace_report(
+                workspace_id(),
+                optional_string_arg(&args, "outputDir"),
+                state,
+            )
+            .await,
+        ),
+        "workspace.gitStatus" => git::get_git_status(workspace_id(), state).await,
+        "thread.start" => codex::start_thread(workspace_id(), state, app).await,
+        "thread.archive" => {
+            codex::archive_thread(workspace_id(), thread_id(), None, state, app).await
+        }
+        "thread.export" => to_value(
+            share_bundle::create_share_bundle(
+                workspace_id(),
+                thread_id(),
+                string_arg(&args, "passphrase"),
+                optional_string_arg(&args, "outputPath"),
+                state,
+                app,
+            )
+            .await,
+        ),
+        "review.start" => {
+            codex::start_review(
+                workspace_id(),
+                thread_id(),
+                args.get("target").cloned().unwrap_or(Value::Null),
+                optional_string_arg(&args, "delivery"),
+                state,
+                app,
+            )
+            .await
+        }
+        "codex.doctor" => codex::codex_doctor(optional_string_arg(&args, "codexBin"), state).await,
+        "codex.protocolReport" => {
+            to_value(protocol_compat::get_protocol_compat_report(state, app).await)
+        }
+        "app.lock" => to_value(app_lock::lock_app(state, app).await),
+        other => Err(format!("Action {other} has no handler.")),
+    }
+}
+
+/// Lists the actions `invoke_action` can run, with their arguments.
+#[tauri::command]
+pub(crate) async fn list_actions() -> Result<Vec<ActionDefinition>, String> {
+    Ok(ACTIONS.to_vec())
+}
+
+/// Runs a registered action with named arguments and returns the result of
+/// the command behind it.
+#[tauri::command]
+pub(crate) async fn invoke_action(
+    action_id: String,
+    args: Option<Map<String, Value>>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    let action = find_action(&action_id)?;
+    let args = args.unwrap_or_default();
+    validate_arguments(action, &args)?;
+    dispatch(action, args, state, app).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn args(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().expect("object")
+    }
+
+    #[test]
+    fn action_ids_are_unique() {
+        let mut ids: Vec<&str> = ACTIONS.iter().map(|action| action.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), ACTIONS.len());
+        assert!(find_action("workspace.teleport").is_err());
+    }
+
+    #[test]
+    fn validates_arguments_against_definition() {
+        let export = find_action("thread.export").expect("action");
+        let valid = args(json!({ "workspaceId": "ws", "threadId": "t1", "passphrase": "secret" }));
+        assert!(validate_arguments(export, &valid).is_ok());
+
+        let missing = args(json!({ "workspaceId": "ws", "threadId": "t1" }));
+        assert!(validate_arguments(export, &missing)
+            .unwrap_err()
+            .contains("passphrase"));
+        let unknown = args(
+            json!({ "workspaceId": "ws", "threadId": "t1", "passphrase": "x", "force": true }),
+        );
+        assert!(validate_arguments(export, &unknown).is_err());
+        let wrong_type = args(json!({ "workspaceId": 3, "threadId": "t1", "passphrase": "x" }));
+        assert!(validate_arguments(export, &wrong_type).is_err());
+    }
+}
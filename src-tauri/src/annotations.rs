@@ -0,0 +1,88 @@
+use std::collections::{BTreeSet, HashMap};
+
+use tauri::State;
+
+use crate::backend::annotations::{self, Annotation, ModelFeedback};
+use crate::state::AppState;
+use crate::turn_manifest;
+
+/// Annotations of one workspace, optionally narrowed to a thread; used by
+/// exports.
+pub(crate) async fn thread_annotations(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: Option<&str>,
+) -> Vec<Annotation> {
+    let entries = match state.data_cipher().await {
+        Ok(cipher) => {
+            annotations::read_annotations(&annotations::annotations_path(&state.data_dir), &cipher)
+                .unwrap_or_default()
+        }
+        Err(_) => Vec::new(),
+    };
+    entries
+        .into_iter()
+        .filter(|entry| entry.workspace_id == workspace_id)
+        .filter(|entry| thread_id.is_none_or(|thread_id| entry.thread_id == thread_id))
+        .collect()
+}
+
+/// Sets the reaction, comment and follow-up flag of a turn, or of one of its
+/// items when `itemId` is given. Clearing all three removes the annotation.
+#[tauri::command]
+pub(crate) async fn annotate_item(
+    mut annotation: Annotation,
+    state: State<'_, AppState>,
+) -> Result<Annotation, String> {
+    annotation.updated_at = chrono::Utc::now().timestamp_millis();
+    let cipher = state.data_cipher().await?;
+    let _guard = state.annotation_writes.lock().await;
+    annotations::annotate(
+        &annotations::annotations_path(&state.data_dir),
+        &cipher,
+        annotation,
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn list_annotations(
+    workspace_id: Option<String>,
+    thread_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Annotation>, String> {
+    let cipher = state.data_cipher().await?;
+    let mut entries =
+        annotations::read_annotations(&annotations::annotations_path(&state.data_dir), &cipher)?;
+    entries.retain(|entry| {
+        workspace_id
+            .as_deref()
+            .is_none_or(|workspace_id| entry.workspace_id == workspace_id)
+            && thread_id
+                .as_deref()
+                .is_none_or(|thread_id| entry.thread_id == thread_id)
+    });
+    Ok(entries)
+}
+
+/// Thumbs up/down success rate and follow-up flags per model, using the
+/// model recorded in each annotated turn's manifest.
+#[tauri::command]
+pub(crate) async fn get_annotation_feedback(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ModelFeedback>, String> {
+    let entries = list_annotations(workspace_id, None, state.clone()).await?;
+    let workspace_ids: BTreeSet<&str> = entries
+        .iter()
+        .map(|entry| entry.workspace_id.as_str())
+        .collect();
+    let mut turn_models = HashMap::new();
+    for workspace_id in workspace_ids {
+        for manifest in turn_manifest::workspace_manifests(&state, workspace_id).await {
+            if let Some(model) = manifest.model {
+                turn_models.insert(manifest.turn_id, model);
+            }
+        }
+    }
+    Ok(annotations::feedback_by_model(&entries, &turn_models))
+}
@@ -0,0 +1,265 @@
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+use tauri::ipc::Invoke;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+use crate::state::AppState;
+
+/// How often the idle timeout is checked so the UI learns it was locked.
+const LOCK_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Commands that keep working while the app is locked: the lock itself,
+/// window plumbing and read-only queries. Every other command is rejected.
+const ALLOWED_WHILE_LOCKED: &[&str] = &[
+    "report_user_activity",
+    "lock_app",
+    "unlock_app",
+    "codex_doctor",
+    "menu_set_accelerators",
+    "set_locale",
+    "quick_search",
+    "focus_workspace_window",
+    "subscribe_events",
+    "unsubscribe_events",
+    "set_thread_event_filter",
+    "model_list",
+    "account_rate_limits",
+    "skills_list",
+    "prompts_list",
+    "collaboration_mode_list",
+    "local_usage_snapshot",
+    "dictation_model_status",
+    "check_prompt_size",
+    "estimate_turn",
+    "check_app_update",
+    "compare_turns",
+    "search_command_outputs",
+    "semantic_search",
+    "suggest_context_files",
+    "workspace_doctor",
+];
+const READ_ONLY_PREFIXES: &[&str] = &["get_", "list_"];
+
+pub(crate) const LOCKED_MESSAGE: &str =
+    "The app is locked after inactivity. Unlock it to continue.";
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AppLockStatus {
+    pub(crate) locked: bool,
+    pub(crate) auto_lock_minutes: Option<u32>,
+    /// Whether unlocking asks for the app passphrase.
+    pub(crate) passphrase_required: bool,
+}
+
+/// Inactivity lock. The frontend reports user interaction; once the idle
+/// timeout passes, mutating commands are rejected until `unlock_app`.
+pub(crate) struct AppLock {
+    timeout: Option<Duration>,
+    last_activity: Instant,
+    locked: bool,
+}
+
+fn timeout_from_minutes(minutes: Option<u32>) -> Option<Duration> {
+    minutes
+        .filter(|minutes| *minutes > 0)
+        .map(|minutes| Duration::from_secs(u64::from(minutes) * 60))
+}
+
+impl AppLock {
+    pub(crate) fn new(auto_lock_minutes: Option<u32>) -> Self {
+        Self {
+            timeout: timeout_from_minutes(auto_lock_minutes),
+            last_activity: Instant::now(),
+            locked: false,
+        }
+    }
+
+    /// Applies a changed `autoLockMinutes` setting; the idle time counts
+    /// from now.
+    pub(crate) fn set_timeout(&mut self, auto_lock_minutes: Option<u32>) {
+        self.timeout = timeout_from_minutes(auto_lock_minutes);
+        self.last_activity = Instant::now();
+        if self.timeout.is_none() {
+            self.locked = false;
+        }
+    }
+
+    fn is_locked(&mut self, now: Instant) -> bool {
+        if !self.locked
+            && self
+                .timeout
+                .is_some_and(|timeout| now.saturating_duration_since(self.last_activity) >= timeout)
+        {
+            self.locked = true;
+        }
+        self.locked
+    }
+
+    /// Activity reported after the timeout passed does not unlock the app.
+    fn record_activity(&mut self, now: Instant) {
+        if !self.is_locked(now) {
+            self.last_activity = now;
+        }
+    }
+
+    fn unlock(&mut self, now: Instant) {
+        self.locked = false;
+        self.last_activity = now;
+    }
+}
+
+fn allowed_while_locked(command: &str) -> bool {
+    ALLOWED_WHILE_LOCKED.contains(&command)
+        || READ_ONLY_PREFIXES
+            .iter()
+            .any(|prefix| command.starts_with(prefix))
+}
+
+/// Whether the app is locked, e.g. for entry points outside the command
+/// handler such as the webhook listener.
+pub(crate) fn is_locked(state: &AppState) -> bool {
+    state
+        .app_lock
+        .lock()
+        .map(|mut lock| lock.is_locked(Instant::now()))
+        .unwrap_or(false)
+}
+
+/// Rejects mutating commands while locked.
+fn check_command<R: Runtime>(app: &AppHandle<R>, command: &str) -> Result<(), String> {
+    if allowed_while_locked(command) {
+        return Ok(());
+    }
+    let Some(state) = app.try_state::<AppState>() else {
+        return Ok(());
+    };
+    if is_locked(&state) {
+        Err(LOCKED_MESSAGE.to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Wraps the command handler so every command passes the lock check first.
+pub(crate) fn guard<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        let app = invoke.message.webview_ref().app_handle();
+        if let Err(error) = check_command(app, invoke.message.command()) {
+            invoke.resolver.reject(error);
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+async fn status(state: &AppState) -> AppLockStatus {
+    AppLockStatus {
+        locked: is_locked(state),
+        auto_lock_minutes: state.app_settings.lock().await.auto_lock_minutes,
+        passphrase_required: state.access_control.lock().await.passphrase_set(),
+    }
+}
+
+/// Emits `app-lock-changed` when the idle timeout locks the app.
+pub(crate) fn spawn_lock_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut was_locked = false;
+        loop {
+            tokio::time::sleep(LOCK_CHECK_INTERVAL).await;
+            let state = app.state::<AppState>();
+            let locked = is_locked(&state);
+            if locked && !was_locked {
+                let _ = app.emit("app-lock-changed", status(&state).await);
+            }
+            was_locked = locked;
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) async fn get_app_lock_status(
+    state: State<'_, AppState>,
+) -> Result<AppLockStatus, String> {
+    Ok(status(&state).await)
+}
+
+/// Called by the frontend on user interaction, throttled on its side.
+#[tauri::command]
+pub(crate) async fn report_user_activity(
+    state: State<'_, AppState>,
+) -> Result<AppLockStatus, String> {
+    if let Ok(mut lock) = state.app_lock.lock() {
+        lock.record_activity(Instant::now());
+    }
+    Ok(status(&state).await)
+}
+
+#[tauri::command]
+pub(crate) async fn lock_app(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<AppLockStatus, String> {
+    if let Ok(mut lock) = state.app_lock.lock() {
+        lock.locked = true;
+    }
+    let status = status(&state).await;
+    let _ = app.emit("app-lock-changed", status.clone());
+    Ok(status)
+}
+
+/// Unlocks the app; asks for the app passphrase when one is set.
+#[tauri::command]
+pub(crate) async fn unlock_app(
+    passphrase: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<AppLockStatus, String> {
+    state
+        .access_control
+        .lock()
+        .await
+        .check_passphrase(passphrase.as_deref())?;
+    if let Ok(mut lock) = state.app_lock.lock() {
+        lock.unlock(Instant::now());
+    }
+    let status = status(&state).await;
+    let _ = app.emit("app-lock-changed", status.clone());
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locks_after_idle_timeout_until_unlocked() {
+        let mut lock = AppLock::new(Some(5));
+        let start = lock.last_activity;
+        assert!(!lock.is_locked(start + Duration::from_secs(60)));
+        lock.record_activity(start + Duration::from_secs(120));
+        assert!(!lock.is_locked(start + Duration::from_secs(400)));
+        assert!(lock.is_locked(start + Duration::from_secs(420)));
+
+        lock.record_activity(start + Duration::from_secs(430));
+        assert!(lock.is_locked(start + Duration::from_secs(431)));
+        lock.unlock(start + Duration::from_secs(440));
+        assert!(!lock.is_locked(start + Duration::from_secs(441)));
+
+        let mut disabled = AppLock::new(Some(0));
+        assert!(!disabled.is_locked(start + Duration::from_secs(100_000)));
+    }
+
+    #[test]
+    fn only_read_only_commands_pass_while_locked() {
+        assert!(allowed_while_locked("get_git_status"));
+        assert!(allowed_while_locked("list_workspaces"));
+        assert!(allowed_while_locked("unlock_app"));
+        assert!(!allowed_while_locked("send_user_message"));
+        assert!(!allowed_while_locked("apply_staged_patch"));
+        assert!(!allowed_while_locked("terminal_write"));
+    }
+}
@@ -0,0 +1,247 @@
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+use tauri::{AppHandle, State};
+
+use crate::state::AppState;
+use crate::types::UpdateChannel;
+
+const STABLE_FEED_URL: &str =
+    "https://github.com/Dimillian/CodexMonitor/releases/latest/download/latest.json";
+const BETA_FEED_URL: &str =
+    "https://github.com/Dimillian/CodexMonitor/releases/download/beta/latest.json";
+const INSTALL_ID_FILE: &str = "install_id";
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AppUpdateInfo {
+    pub(crate) available: bool,
+    pub(crate) channel: UpdateChannel,
+    pub(crate) current_version: String,
+    pub(crate) version: Option<String>,
+    pub(crate) notes: Option<String>,
+    pub(crate) date: Option<String>,
+    /// True when a newer release exists but this install is outside its rollout.
+    pub(crate) held_back: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub(crate) enum AppUpdateProgress {
+    #[serde(rename_all = "camelCase")]
+    Started { content_length: Option<u64> },
+    #[serde(rename_all = "camelCase")]
+    Progress {
+        downloaded: u64,
+        content_length: Option<u64>,
+    },
+    Finished,
+    Installed,
+}
+
+fn feed_url(channel: UpdateChannel) -> &'static str {
+    match channel {
+        UpdateChannel::Stable => STABLE_FEED_URL,
+        UpdateChannel::Beta => BETA_FEED_URL,
+    }
+}
+
+/// Stable per-install identifier used to bucket staged rollouts.
+fn load_install_id(data_dir: &Path) -> String {
+    let path = data_dir.join(INSTALL_ID_FILE);
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        let trimmed = existing.trim();
+        if !trimmed.is_empty() {
+            return trimmed.to_string();
+        }
+    }
+    let id = uuid::Uuid::new_v4().to_string();
+    let _ = std::fs::create_dir_all(data_dir);
+    let _ = std::fs::write(&path, &id);
+    id
+}
+
+/// Reads the optional `rollout` percentage (0-100) from a release manifest.
+fn rollout_percentage(manifest: &Value) -> Option<u8> {
+    manifest
+        .get("rollout")
+        .and_then(|value| value.as_f64())
+        .map(|value| value.clamp(0.0, 100.0) as u8)
+}
+
+/// Deterministically places the install in a bucket (0-99) per release, so
+/// each rollout picks a fresh but stable subset of installs.
+fn in_rollout(install_id: &str, version: &str, percentage: Option<u8>) -> bool {
+    let Some(percentage) = percentage else {
+        return true;
+    };
+    let digest = Sha256::digest(format!("{install_id}:{version}").as_bytes());
+    let bucket = u16::from_be_bytes([digest[0], digest[1]]) % 100;
+    bucket < u16::from(percentage)
+}
+
+#[cfg(desktop)]
+mod desktop {
+    use super::*;
+    use tauri::{Emitter, Manager};
+    use tauri_plugin_updater::{Update, UpdaterExt};
+    use tokio::sync::Mutex;
+
+    /// Update found by the last `check_app_update`, waiting to be installed.
+    #[derive(Default)]
+    pub(crate) struct PendingAppUpdate(Mutex<Option<Update>>);
+
+    pub(super) async fn check(
+        app: &AppHandle,
+        data_dir: &Path,
+        channel: UpdateChannel,
+    ) -> Result<AppUpdateInfo, String> {
+        let endpoint = tauri::Url::parse(feed_url(channel)).map_err(|err| err.to_string())?;
+        let updater = app
+            .updater_builder()
+            .endpoints(vec![endpoint])
+            .and_then(|builder| builder.build())
+            .map_err(|err| format!("Failed to configure updater: {err}"))?;
+        let update = updater
+            .check()
+            .await
+            .map_err(|err| format!("Failed to check for updates: {err}"))?;
+        let current_version = app.package_info().version.to_string();
+        let pending = app.state::<PendingAppUpdate>();
+        let Some(update) = update else {
+            *pending.0.lock().await = None;
+            return Ok(AppUpdateInfo {
+                available: false,
+                channel,
+                current_version,
+                version: None,
+                notes: None,
+                date: None,
+                held_back: false,
+            });
+        };
+        let install_id = load_install_id(data_dir);
+        let eligible = in_rollout(
+            &install_id,
+            &update.version,
+            rollout_percentage(&update.raw_json),
+        );
+        let info = AppUpdateInfo {
+            available: eligible,
+            channel,
+            current_version,
+            version: Some(update.version.clone()),
+            notes: update.body.clone(),
+            date: update.date.map(|date| date.to_string()),
+            held_back: !eligible,
+        };
+        *pending.0.lock().await = eligible.then_some(update);
+        Ok(info)
+    }
+
+    pub(super) async fn install(app: &AppHandle) -> Result<(), String> {
+        let update = app
+            .state::<PendingAppUpdate>()
+            .0
+            .lock()
+            .await
+            .take()
+            .ok_or("No update available. Check for updates first.")?;
+        let mut downloaded: u64 = 0;
+        let mut started = false;
+        let progress_app = app.clone();
+        let finished_app = app.clone();
+        update
+            .download_and_install(
+                move |chunk, content_length| {
+                    if !started {
+                        started = true;
+                        let _ = progress_app.emit(
+                            "app-update-progress",
+                            AppUpdateProgress::Started { content_length },
+                        );
+                    }
+                    downloaded += chunk as u64;
+                    let _ = progress_app.emit(
+                        "app-update-progress",
+                        AppUpdateProgress::Progress {
+                            downloaded,
+                            content_length,
+                        },
+                    );
+                },
+                move || {
+                    let _ = finished_app.emit("app-update-progress", AppUpdateProgress::Finished);
+                },
+            )
+            .await
+            .map_err(|err| format!("Failed to install update: {err}"))?;
+        let _ = app.emit("app-update-progress", AppUpdateProgress::Installed);
+        Ok(())
+    }
+}
+
+#[cfg(desktop)]
+pub(crate) use desktop::PendingAppUpdate;
+
+#[tauri::command]
+pub(crate) async fn check_app_update(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<AppUpdateInfo, String> {
+    let channel = state.app_settings.lock().await.update_channel;
+    #[cfg(desktop)]
+    {
+        desktop::check(&app, &state.data_dir, channel).await
+    }
+    #[cfg(not(desktop))]
+    {
+        let _ = (app, channel);
+        Err("App updates are not supported on this platform.".to_string())
+    }
+}
+
+/// Downloads, verifies and installs the update found by `check_app_update`,
+/// emitting `app-update-progress` events. The app must be restarted afterwards.
+#[tauri::command]
+pub(crate) async fn install_app_update(app: AppHandle) -> Result<(), String> {
+    #[cfg(desktop)]
+    {
+        desktop::install(&app).await
+    }
+    #[cfg(not(desktop))]
+    {
+        let _ = app;
+        Err("App updates are not supported on this platform.".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rollout_buckets_are_stable_and_bounded() {
+        assert!(in_rollout("install-a", "1.2.0", None));
+        assert!(in_rollout("install-a", "1.2.0", Some(100)));
+        assert!(!in_rollout("install-a", "1.2.0", Some(0)));
+        let first = in_rollout("install-a", "1.2.0", Some(50));
+        assert_eq!(first, in_rollout("install-a", "1.2.0", Some(50)));
+
+        let included = (0..1000)
+            .filter(|index| in_rollout(&format!("install-{index}"), "1.2.0", Some(25)))
+            .count();
+        assert!((150..350).contains(&included), "included {included}");
+    }
+
+    #[test]
+    fn reads_rollout_percentage_from_manifest() {
+        assert_eq!(rollout_percentage(&json!({ "rollout": 25 })), Some(25));
+        assert_eq!(rollout_percentage(&json!({ "rollout": 250 })), Some(100));
+        assert_eq!(rollout_percentage(&json!({ "version": "1.0.0" })), None);
+        assert_eq!(feed_url(UpdateChannel::Beta), BETA_FEED_URL);
+    }
+}
@@ -0,0 +1,538 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
+
+use crate::backend::approval_audit::{
+    self, ApprovalAuditAction, ApprovalAuditEntry, AuditChainReport, ChainedAuditEntry,
+};
+use crate::backend::hmac_signature;
+use crate::backend::request_id::RequestId;
+use crate::state::AppState;
+use crate::types::ApprovalDelegationSettings;
+
+/// Header carrying the HMAC-SHA256 signature of forwarded approvals.
+const SIGNATURE_HEADER: &str = "X-CodexMonitor-Signature";
+/// Signed decisions older than this are refused.
+const MAX_DECISION_AGE_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// An approval request that was forwarded and awaits the approver.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PendingDelegation {
+    pub(crate) delegation_id: String,
+    pub(crate) method: String,
+    /// The request's params, kept so forwarding can be retried.
+    pub(crate) params: Value,
+    /// Why the last forward failed. The approval stays pending, and blocked
+    /// when sign-off is required, until a retry reaches the approver.
+    pub(crate) forward_error: Option<String>,
+}
+
+/// Delegated approvals keyed by workspace and app-server request id.
+#[derive(Debug, Default)]
+pub(crate) struct PendingDelegations {
+    by_request: HashMap<(String, RequestId), PendingDelegation>,
+}
+
+impl PendingDelegations {
+    fn get(&self, workspace_id: &str, request_id: &RequestId) -> Option<&PendingDelegation> {
+        self.by_request
+            .get(&(workspace_id.to_string(), request_id.clone()))
+    }
+
+    fn insert(&mut self, workspace_id: &str, request_id: RequestId, pending: PendingDelegation) {
+        self.by_request
+            .insert((workspace_id.to_string(), request_id), pending);
+    }
+
+    fn set_forward_error(
+        &mut self,
+        workspace_id: &str,
+        request_id: &RequestId,
+        delegation_id: &str,
+        error: Option<String>,
+    ) {
+        if let Some(pending) = self
+            .by_request
+            .get_mut(&(workspace_id.to_string(), request_id.clone()))
+            .filter(|pending| pending.delegation_id == delegation_id)
+        {
+            pending.forward_error = error;
+        }
+    }
+
+    fn remove(&mut self, workspace_id: &str, request_id: &RequestId) -> Option<PendingDelegation> {
+        self.by_request
+            .remove(&(workspace_id.to_string(), request_id.clone()))
+    }
+}
+
+/// The approver's signature over a decision, passed to
+/// `respond_to_server_request`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DelegatedDecision {
+    pub(crate) delegation_id: String,
+    pub(crate) approver: String,
+    pub(crate) signed_at: i64,
+    /// Hex HMAC-SHA256 of `signing_payload` with the shared secret.
+    pub(crate) signature: String,
+}
+
+/// Canonical text the approver signs. `serde_json` sorts object keys, so
+/// both sides serialize `result` identically.
+pub(crate) fn signing_payload(
+    delegation_id: &str,
+    workspace_id: &str,
+    request_id: &RequestId,
+    result: &Value,
+    approver: &str,
+    signed_at: i64,
+) -> String {
+    format!("{delegation_id}\n{workspace_id}\n{request_id}\n{approver}\n{signed_at}\n{result}")
+}
+
+fn secret(settings: &ApprovalDelegationSettings) -> Option<&str> {
+    settings
+        .secret
+        .as_deref()
+        .filter(|secret| !secret.trim().is_empty())
+}
+
+/// The approver endpoint and shared secret, when delegation is enabled and
+/// fully configured.
+fn forward_target(settings: &ApprovalDelegationSettings) -> Option<(&str, &str)> {
+    let url = settings
+        .forward_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())?;
+    settings.enabled.then_some((url, secret(settings)?))
+}
+
+/// Whether `next` changes delegation settings that currently require
+/// sign-off, which needs the app passphrase.
+pub(crate) fn loosens_signoff(
+    current: &ApprovalDelegationSettings,
+    next: &ApprovalDelegationSettings,
+) -> bool {
+    current.enabled && current.require_signoff && current != next
+}
+
+/// Delegation settings in effect, with the organization policy applied.
+async fn delegation_settings(state: &AppState) -> ApprovalDelegationSettings {
+    let settings = state.app_settings.lock().await.approval_delegation.clone();
+    state.policy.policy.approval_delegation(&settings)
+}
+
+/// Checks a signed decision against the pending delegation and settings.
+fn verify_decision(
+    settings: &ApprovalDelegationSettings,
+    pending: Option<&PendingDelegation>,
+    workspace_id: &str,
+    request_id: &RequestId,
+    result: &Value,
+    decision: &DelegatedDecision,
+    now_ms: i64,
+) -> Result<(), String> {
+    if !settings.enabled {
+        return Err("Approval delegation is disabled.".to_string());
+    }
+    let secret = secret(settings).ok_or("Approval delegation has no shared secret.")?;
+    let pending = pending.ok_or("This approval was not delegated or was already answered.")?;
+    if pending.delegation_id != decision.delegation_id {
+        return Err("The signed decision is for a different delegation.".to_string());
+    }
+    if !settings.approver.trim().is_empty() && settings.approver.trim() != decision.approver {
+        return Err(format!(
+            "Only {} may sign off delegated approvals.",
+            settings.approver.trim()
+        ));
+    }
+    if (now_ms - decision.signed_at).abs() > MAX_DECISION_AGE_MS {
+        return Err("The signed decision has expired.".to_string());
+    }
+    let payload = signing_payload(
+        &decision.delegation_id,
+        workspace_id,
+        request_id,
+        result,
+        &decision.approver,
+        decision.signed_at,
+    );
+    if !hmac_signature::verify_hex(secret, payload.as_bytes(), &decision.signature) {
+        return Err("Invalid approval signature.".to_string());
+    }
+    Ok(())
+}
+
+/// Appends to the approval audit log, logging rather than failing on errors.
+pub(crate) async fn record(state: &AppState, entry: ApprovalAuditEntry) {
+    let result = match state.data_cipher().await {
+        Ok(cipher) => {
+            let _guard = state.audit_writes.lock().await;
+            approval_audit::append_entry(&state.data_dir, &cipher, entry)
+        }
+        Err(err) => Err(err),
+    };
+    if let Err(err) = result {
+        eprintln!("Failed to write approval audit log: {err}");
+    }
+}
+
+fn is_approval_request(message: &Value) -> Option<(RequestId, String)> {
+    let id = RequestId::from_message(message)?;
+    let method = message.get("method").and_then(|value| value.as_str())?;
+    method
+        .ends_with("requestApproval")
+        .then(|| (id, method.to_string()))
+}
+
+/// Sends a pending delegation to the approver endpoint, signed with the
+/// shared secret.
+async fn forward(
+    settings: &ApprovalDelegationSettings,
+    workspace_id: &str,
+    request_id: &RequestId,
+    pending: &PendingDelegation,
+) -> Result<(), String> {
+    let (forward_url, secret) =
+        forward_target(settings).ok_or("Approval delegation is not configured.")?;
+    let body = json!({
+        "delegationId": pending.delegation_id,
+        "workspaceId": workspace_id,
+        "requestId": request_id,
+        "method": pending.method,
+        "params": pending.params,
+        "approver": settings.approver,
+        "forwardedAt": Utc::now().timestamp_millis(),
+    })
+    .to_string();
+    let signature = hmac_signature::sign_hex(secret, body.as_bytes())
+        .ok_or("Invalid approval delegation secret.")?;
+    let response = reqwest::Client::new()
+        .post(forward_url)
+        .header("Content-Type", "application/json")
+        .header(SIGNATURE_HEADER, format!("sha256={signature}"))
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| format!("Failed to reach approver endpoint: {err}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Approver endpoint returned {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Forwards `pending` and records the attempt, keeping the delegation
+/// pending with its error when forwarding fails.
+async fn forward_and_record(
+    state: &AppState,
+    settings: ApprovalDelegationSettings,
+    workspace_id: String,
+    request_id: RequestId,
+    pending: PendingDelegation,
+) -> Result<(), String> {
+    let result = forward(&settings, &workspace_id, &request_id, &pending).await;
+    if let Ok(mut delegations) = state.delegations.lock() {
+        delegations.set_forward_error(
+            &workspace_id,
+            &request_id,
+            &pending.delegation_id,
+            result.as_ref().err().cloned(),
+        );
+    };
+    record(
+        state,
+        ApprovalAuditEntry {
+            recorded_at: Utc::now().timestamp_millis(),
+            workspace_id,
+            request_id: Some(request_id),
+            delegation_id: Some(pending.delegation_id),
+            action: ApprovalAuditAction::Forwarded,
+            actor: settings.approver,
+            method: Some(pending.method),
+            decision: None,
+            detail: result.as_ref().err().cloned(),
+        },
+    )
+    .await;
+    result
+}
+
+/// Forwards app-server approval requests to the delegated approver; called
+/// for every `app-server-event`, local or forwarded from a remote daemon.
+/// The delegation is registered before this returns, so a local answer
+/// can't slip in ahead of it while the settings are read.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some((request_id, method)) = is_approval_request(message) else {
+        return;
+    };
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let pending = PendingDelegation {
+        delegation_id: Uuid::new_v4().to_string(),
+        method,
+        params: message.get("params").cloned().unwrap_or(Value::Null),
+        forward_error: None,
+    };
+    if let Ok(mut delegations) = state.delegations.lock() {
+        delegations.insert(workspace_id, request_id.clone(), pending.clone());
+    };
+    let app = app.clone();
+    let workspace_id = workspace_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let settings = delegation_settings(&state).await;
+        if forward_target(&settings).is_none() {
+            // Delegation is off, so the request was never delegated.
+            if let Ok(mut delegations) = state.delegations.lock() {
+                delegations.remove(&workspace_id, &request_id);
+            };
+            return;
+        }
+        let _ = forward_and_record(&state, settings, workspace_id, request_id, pending).await;
+    });
+}
+
+/// Forwards a delegated approval whose earlier forward failed again.
+#[tauri::command]
+pub(crate) async fn retry_approval_delegation(
+    workspace_id: String,
+    request_id: RequestId,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let settings = delegation_settings(&state).await;
+    let pending = state
+        .delegations
+        .lock()
+        .map_err(|err| err.to_string())?
+        .get(&workspace_id, &request_id)
+        .cloned()
+        .ok_or("This approval was not delegated or was already answered.")?;
+    forward_and_record(&state, settings, workspace_id, request_id, pending).await
+}
+
+/// Decides whether a response to an app-server request may be sent, and
+/// records delegated approvals in the audit log. Called by
+/// `respond_to_server_request` before the response goes out.
+pub(crate) async fn authorize_response(
+    state: &AppState,
+    workspace_id: &str,
+    request_id: &RequestId,
+    result: &Value,
+    decision: Option<&DelegatedDecision>,
+) -> Result<(), String> {
+    let settings = delegation_settings(state).await;
+    let pending = state
+        .delegations
+        .lock()
+        .map_err(|err| err.to_string())?
+        .get(workspace_id, request_id)
+        .cloned();
+    let now_ms = Utc::now().timestamp_millis();
+    let mut entry = ApprovalAuditEntry {
+        recorded_at: now_ms,
+        workspace_id: workspace_id.to_string(),
+        request_id: Some(request_id.clone()),
+        delegation_id: pending
+            .as_ref()
+            .map(|pending| pending.delegation_id.clone()),
+        action: ApprovalAuditAction::AnsweredLocally,
+        actor: "local".to_string(),
+        method: pending.as_ref().map(|pending| pending.method.clone()),
+        decision: Some(result.clone()),
+        detail: None,
+    };
+    match decision {
+        Some(decision) => {
+            entry.actor = decision.approver.clone();
+            if let Err(err) = verify_decision(
+                &settings,
+                pending.as_ref(),
+                workspace_id,
+                request_id,
+                result,
+                decision,
+                now_ms,
+            ) {
+                entry.action = ApprovalAuditAction::Rejected;
+                entry.detail = Some(err.clone());
+                record(state, entry).await;
+                return Err(err);
+            }
+            entry.action = ApprovalAuditAction::Delegated;
+        }
+        None => {
+            let Some(pending) = pending.as_ref() else {
+                return Ok(());
+            };
+            if settings.enabled && settings.require_signoff {
+                let approver = if settings.approver.trim().is_empty() {
+                    "the delegated approver"
+                } else {
+                    settings.approver.trim()
+                };
+                let mut message = format!(
+                    "This approval needs sign-off from {approver} (delegation {}).",
+                    pending.delegation_id
+                );
+                if let Some(error) = &pending.forward_error {
+                    message.push_str(&format!(
+                        " Forwarding it failed ({error}); retry the delegation."
+                    ));
+                }
+                return Err(message);
+            }
+        }
+    }
+    if let Ok(mut pending) = state.delegations.lock() {
+        pending.remove(workspace_id, request_id);
+    }
+    record(state, entry).await;
+    Ok(())
+}
+
+/// Returns the approval audit log of one day (`YYYY-MM-DD`, default today).
+#[tauri::command]
+pub(crate) async fn get_approval_audit_log(
+    date: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ChainedAuditEntry>, String> {
+    let day = match date {
+        Some(date) => NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map_err(|err| format!("Invalid date {date}: {err}"))?,
+        None => Utc::now().date_naive(),
+    };
+    let cipher = state.data_cipher().await?;
+    approval_audit::read_entries(&state.data_dir, &cipher, day)
+}
+
+/// Checks the approval audit log's hash chain for edited, removed or
+/// truncated entries.
+#[tauri::command]
+pub(crate) async fn verify_audit_chain(
+    state: State<'_, AppState>,
+) -> Result<AuditChainReport, String> {
+    let cipher = state.data_cipher().await?;
+    let _guard = state.audit_writes.lock().await;
+    approval_audit::verify_chain(&state.data_dir, &cipher)
+}
+
+/// Writes the whole approval audit log to `path` as JSON, with its chain
+/// report, so it can be verified away from this machine.
+#[tauri::command]
+pub(crate) async fn export_approval_audit_log(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<AuditChainReport, String> {
+    let cipher = state.data_cipher().await?;
+    let (entries, report) = {
+        let _guard = state.audit_writes.lock().await;
+        (
+            approval_audit::read_all_entries(&state.data_dir, &cipher)?,
+            approval_audit::verify_chain(&state.data_dir, &cipher)?,
+        )
+    };
+    let export = json!({
+        "exportedAt": Utc::now().timestamp_millis(),
+        "chain": report,
+        "entries": entries,
+    });
+    let path = PathBuf::from(path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(&export).map_err(|err| err.to_string())?;
+    std::fs::write(&path, data).map_err(|err| err.to_string())?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed(secret: &str, result: &Value, signed_at: i64) -> DelegatedDecision {
+        let payload = signing_payload(
+            "d-1",
+            "ws-1",
+            &RequestId::Number(7),
+            result,
+            "senior",
+            signed_at,
+        );
+        DelegatedDecision {
+            delegation_id: "d-1".to_string(),
+            approver: "senior".to_string(),
+            signed_at,
+            signature: hmac_signature::sign_hex(secret, payload.as_bytes()).expect("signature"),
+        }
+    }
+
+    #[test]
+    fn verifies_signed_decisions() {
+        let settings = ApprovalDelegationSettings {
+            enabled: true,
+            approver: "senior".to_string(),
+            forward_url: None,
+            secret: Some("s3cret".to_string()),
+            require_signoff: true,
+            inbox_token: None,
+        };
+        let pending = PendingDelegation {
+            delegation_id: "d-1".to_string(),
+            method: "item/commandExecution/requestApproval".to_string(),
+            params: Value::Null,
+            forward_error: None,
+        };
+        let result = json!({ "decision": "accept" });
+        let now = 1_000_000;
+        let decision = signed("s3cret", &result, now);
+        let verify = |decision: &DelegatedDecision, result: &Value| {
+            verify_decision(
+                &settings,
+                Some(&pending),
+                "ws-1",
+                &RequestId::Number(7),
+                result,
+                decision,
+                now,
+            )
+        };
+        assert!(verify(&decision, &result).is_ok());
+        assert!(verify(&decision, &json!({ "decision": "decline" })).is_err());
+        assert!(verify(&signed("other", &result, now), &result).is_err());
+        let stale = signed("s3cret", &result, now - MAX_DECISION_AGE_MS - 1);
+        assert!(verify(&stale, &result).is_err());
+        let request_id = RequestId::Number(7);
+        assert!(verify_decision(
+            &settings,
+            None,
+            "ws-1",
+            &request_id,
+            &result,
+            &decision,
+            now
+        )
+        .is_err());
+        assert_eq!(
+            is_approval_request(&json!({
+                "id": 7,
+                "method": "item/fileChange/requestApproval"
+            })),
+            Some((request_id, "item/fileChange/requestApproval".to_string()))
+        );
+        assert!(is_approval_request(&json!({ "method": "turn/started" })).is_none());
+
+        let mut relaxed = settings.clone();
+        relaxed.require_signoff = false;
+        assert!(loosens_signoff(&settings, &relaxed));
+        assert!(!loosens_signoff(&settings, &settings.clone()));
+        assert!(!loosens_signoff(&relaxed, &settings));
+    }
+}
@@ -0,0 +1,77 @@
+use chrono::Utc;
+use tauri::{AppHandle, State};
+
+use crate::approval_delegation;
+use crate::backend::approval_audit::{ApprovalAuditAction, ApprovalAuditEntry};
+use crate::backend::approval_inbox::{self, InboxApproval};
+use crate::codex;
+use crate::state::AppState;
+
+/// Remembers the workspace shown in the main window, so the approval
+/// shortcuts answer its requests without the UI passing request ids.
+#[tauri::command]
+pub(crate) async fn set_focused_workspace(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut focused = state
+        .focused_workspace
+        .lock()
+        .map_err(|err| err.to_string())?;
+    *focused = workspace_id;
+    Ok(())
+}
+
+/// Answers the focused workspace's oldest pending approval with `accept` or
+/// `decline` and records it in the approval audit log. Returns the answered
+/// request, or `None` when nothing is waiting.
+#[tauri::command]
+pub(crate) async fn respond_to_next_approval(
+    decision: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Option<InboxApproval>, String> {
+    let result = approval_inbox::decision_value(&decision)?;
+    let Some(workspace_id) = state
+        .focused_workspace
+        .lock()
+        .map_err(|err| err.to_string())?
+        .clone()
+    else {
+        return Ok(None);
+    };
+    let approval = state
+        .approval_inbox
+        .lock()
+        .map_err(|err| err.to_string())?
+        .oldest(&workspace_id)
+        .cloned();
+    let Some(approval) = approval else {
+        return Ok(None);
+    };
+    codex::respond_to_server_request(
+        approval.workspace_id.clone(),
+        approval.request_id.clone(),
+        result.clone(),
+        None,
+        state.clone(),
+        app,
+    )
+    .await?;
+    approval_delegation::record(
+        &state,
+        ApprovalAuditEntry {
+            recorded_at: Utc::now().timestamp_millis(),
+            workspace_id: approval.workspace_id.clone(),
+            request_id: Some(approval.request_id.clone()),
+            delegation_id: None,
+            action: ApprovalAuditAction::AnsweredByShortcut,
+            actor: "local".to_string(),
+            method: Some(approval.method.clone()),
+            decision: Some(result),
+            detail: approval.command.clone(),
+        },
+    )
+    .await;
+    Ok(Some(approval))
+}
@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::backend::auth_expiry;
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::turn_limiter::TurnLane;
+use crate::offline_queue;
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// Rechecks at least this often while a turn waits for a new login.
+const MAX_WAIT: Duration = Duration::from_secs(15 * 60);
+
+/// Payload of `account/authExpired` and `account/authRestored`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct AuthChanged {
+    workspace_ids: Vec<String>,
+}
+
+/// The workspace and every connected one signed in through the same
+/// `CODEX_HOME`, which share its credentials.
+async fn sharing_credentials(state: &AppState, workspace_id: &str) -> Vec<String> {
+    let sessions = state.sessions.lock().await;
+    let Some(codex_home) = sessions
+        .get(workspace_id)
+        .map(|session| session.codex_home.clone())
+    else {
+        return vec![workspace_id.to_string()];
+    };
+    let mut workspace_ids: Vec<String> = sessions
+        .iter()
+        .filter(|(_, session)| session.codex_home == codex_home)
+        .map(|(id, _)| id.clone())
+        .collect();
+    workspace_ids.sort();
+    workspace_ids
+}
+
+/// Pauses the turns of workspaces whose sign-in expired and emits
+/// `account/authExpired` with them; on a completed login, resumes them and
+/// emits `account/authRestored`. Called for every `app-server-event`, local
+/// or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let expired = auth_expiry::is_auth_expired(message);
+    if !expired && !auth_expiry::is_auth_restored(message) {
+        return;
+    }
+    let app = app.clone();
+    let workspace_id = workspace_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let affected = sharing_credentials(&state, &workspace_id).await;
+        if expired {
+            let newly_expired = state.auth_expiry.mark_expired(&affected);
+            if !newly_expired.is_empty() {
+                let _ = app.emit(
+                    "account/authExpired",
+                    AuthChanged {
+                        workspace_ids: newly_expired,
+                    },
+                );
+            }
+            return;
+        }
+        let restored = state.auth_expiry.clear(&affected);
+        if restored.is_empty() {
+            return;
+        }
+        for workspace_id in &restored {
+            offline_queue::resume_dispatch(&app, workspace_id);
+        }
+        let _ = app.emit(
+            "account/authRestored",
+            AuthChanged {
+                workspace_ids: restored,
+            },
+        );
+    });
+}
+
+/// Holds back scheduled and batch turns of a workspace whose sign-in
+/// expired until a login completes. Interactive turns fail right away with
+/// an error asking to log in again, instead of the server's own.
+pub(crate) async fn wait_for_auth(
+    state: &AppState,
+    workspace_id: &str,
+    lane: TurnLane,
+) -> Result<(), String> {
+    while state.auth_expiry.is_expired(workspace_id) {
+        if lane == TurnLane::Interactive {
+            return Err(
+                "The Codex sign-in for this workspace expired. Log in again to send messages."
+                    .to_string(),
+            );
+        }
+        let _ = tokio::time::timeout(MAX_WAIT, state.auth_expiry.restored()).await;
+    }
+    Ok(())
+}
+
+/// Workspaces whose sign-in expired and that wait for a new login.
+#[tauri::command]
+pub(crate) async fn get_auth_expired_workspaces(
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    Ok(state.auth_expiry.expired_workspaces())
+}
+
+/// Starts a ChatGPT login through the workspace's app-server and opens the
+/// sign-in page in the browser. Paused turns resume once the server reports
+/// `account/login/completed`.
+#[tauri::command]
+pub(crate) async fn start_account_login(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Log in again on the machine running the remote daemon.".to_string());
+    }
+    let session = state
+        .sessions
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
+    let response = session
+        .send_request("account/login/start", json!({ "type": "chatgpt" }))
+        .await?;
+    if let Some(error) = response.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|value| value.as_str())
+            .unwrap_or("Failed to start login.");
+        return Err(message.to_string());
+    }
+    let result = response.get("result").unwrap_or(&response);
+    if let Some(url) = result.get("authUrl").and_then(|value| value.as_str()) {
+        tauri_plugin_opener::open_url(url, None::<&str>)
+            .map_err(|err| format!("Failed to open the sign-in page: {err}"))?;
+    }
+    Ok(result.clone())
+}
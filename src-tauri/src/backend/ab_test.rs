@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::backend::turn_summary::{params_thread_id, params_turn_id};
+
+/// Model/effort profile for one side of an A/B run.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbVariant {
+    #[serde(default)]
+    pub(crate) label: Option<String>,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) effort: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbVariantRun {
+    pub(crate) label: String,
+    pub(crate) model: Option<String>,
+    pub(crate) effort: Option<String>,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbVariantMetrics {
+    pub(crate) status: Option<String>,
+    pub(crate) duration_ms: Option<u64>,
+    pub(crate) total_tokens: u64,
+    pub(crate) files_changed: usize,
+    pub(crate) commands_run: usize,
+    pub(crate) last_agent_message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbVariantResult {
+    #[serde(flatten)]
+    pub(crate) run: AbVariantRun,
+    #[serde(flatten)]
+    pub(crate) metrics: AbVariantMetrics,
+}
+
+/// Emitted as `ab-test-completed` once every variant's turn has finished.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AbTestCompleted {
+    pub(crate) test_id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) prompt: String,
+    pub(crate) variants: Vec<AbVariantResult>,
+}
+
+struct PendingAbTest {
+    workspace_id: String,
+    prompt: String,
+    runs: Vec<AbVariantRun>,
+    metrics: Vec<Option<AbVariantMetrics>>,
+}
+
+/// Tracks running A/B tests and completes them from `turn/completed`
+/// notifications (which carry the summary attached by the app-server reader).
+#[derive(Default)]
+pub(crate) struct AbTestTracker {
+    tests: HashMap<String, PendingAbTest>,
+    by_thread: HashMap<(String, String), String>,
+}
+
+fn summary_metrics(params: &Value) -> AbVariantMetrics {
+    let summary = params.get("summary");
+    let field = |key: &str| summary.and_then(|summary| summary.get(key));
+    AbVariantMetrics {
+        status: field("status")
+            .or_else(|| params.get("turn").and_then(|turn| turn.get("status")))
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string()),
+        duration_ms: field("durationMs").and_then(|value| value.as_u64()),
+        total_tokens: field("totalTokens")
+            .and_then(|value| value.as_u64())
+            .unwrap_or(0),
+        files_changed: field("filesChanged")
+            .and_then(|value| value.as_u64())
+            .unwrap_or(0) as usize,
+        commands_run: field("commandsRun")
+            .and_then(|value| value.as_u64())
+            .unwrap_or(0) as usize,
+        last_agent_message: field("lastAgentMessage")
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string()),
+    }
+}
+
+impl AbTestTracker {
+    pub(crate) fn register(
+        &mut self,
+        test_id: &str,
+        workspace_id: &str,
+        prompt: &str,
+        runs: Vec<AbVariantRun>,
+    ) {
+        for run in &runs {
+            self.by_thread.insert(
+                (workspace_id.to_string(), run.thread_id.clone()),
+                test_id.to_string(),
+            );
+        }
+        self.tests.insert(
+            test_id.to_string(),
+            PendingAbTest {
+                workspace_id: workspace_id.to_string(),
+                prompt: prompt.to_string(),
+                metrics: vec![None; runs.len()],
+                runs,
+            },
+        );
+    }
+
+    /// Drops a test whose turns could not all be started.
+    pub(crate) fn cancel(&mut self, test_id: &str) {
+        if self.tests.remove(test_id).is_some() {
+            self.by_thread.retain(|_, id| id != test_id);
+        }
+    }
+
+    pub(crate) fn observe(&mut self, workspace_id: &str, message: &Value) -> Option<AbTestCompleted> {
+        if message.get("method").and_then(|value| value.as_str()) != Some("turn/completed") {
+            return None;
+        }
+        let params = message.get("params")?;
+        let thread_id = params_thread_id(params)?;
+        let key = (workspace_id.to_string(), thread_id.clone());
+        let test_id = self.by_thread.get(&key)?.clone();
+        let test = self.tests.get_mut(&test_id)?;
+        let index = test.runs.iter().position(|run| run.thread_id == thread_id)?;
+        if let Some(turn_id) = params_turn_id(params) {
+            test.runs[index].turn_id = Some(turn_id);
+        }
+        test.metrics[index] = Some(summary_metrics(params));
+        self.by_thread.remove(&key);
+        if test.metrics.iter().any(|metrics| metrics.is_none()) {
+            return None;
+        }
+        let test = self.tests.remove(&test_id)?;
+        Some(AbTestCompleted {
+            test_id,
+            workspace_id: test.workspace_id,
+            prompt: test.prompt,
+            variants: test
+                .runs
+                .into_iter()
+                .zip(test.metrics)
+                .map(|(run, metrics)| AbVariantResult {
+                    run,
+                    metrics: metrics.unwrap_or_default(),
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn run(label: &str, thread_id: &str) -> AbVariantRun {
+        AbVariantRun {
+            label: label.to_string(),
+            model: Some(format!("model-{label}")),
+            effort: None,
+            thread_id: thread_id.to_string(),
+            turn_id: None,
+        }
+    }
+
+    fn completed(thread_id: &str, tokens: u64) -> Value {
+        json!({
+            "method": "turn/completed",
+            "params": {
+                "threadId": thread_id,
+                "turn": { "id": "turn", "status": "completed" },
+                "summary": { "durationMs": 1200, "totalTokens": tokens, "filesChanged": 2, "commandsRun": 1 },
+            },
+        })
+    }
+
+    #[test]
+    fn completes_after_every_variant_finishes() {
+        let mut tracker = AbTestTracker::default();
+        tracker.register("ab-1", "ws", "Fix it", vec![run("a", "t-a"), run("b", "t-b")]);
+
+        assert!(tracker.observe("ws", &completed("t-b", 50)).is_none());
+        assert!(tracker.observe("other", &completed("t-a", 10)).is_none());
+        let done = tracker.observe("ws", &completed("t-a", 80)).expect("completed");
+        assert_eq!(done.test_id, "ab-1");
+        assert_eq!(done.variants[0].run.label, "a");
+        assert_eq!(done.variants[0].run.turn_id.as_deref(), Some("turn"));
+        assert_eq!(done.variants[0].metrics.total_tokens, 80);
+        assert_eq!(done.variants[1].metrics.total_tokens, 50);
+        assert_eq!(done.variants[1].metrics.duration_ms, Some(1200));
+        assert_eq!(done.variants[1].metrics.status.as_deref(), Some("completed"));
+        assert!(tracker.observe("ws", &completed("t-a", 80)).is_none());
+    }
+
+    #[test]
+    fn cancel_forgets_threads() {
+        let mut tracker = AbTestTracker::default();
+        tracker.register("ab-1", "ws", "Fix it", vec![run("a", "t-a")]);
+        tracker.cancel("ab-1");
+        assert!(tracker.observe("ws", &completed("t-a", 1)).is_none());
+        assert!(tracker.by_thread.is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+
+/// Turns without a recorded manifest are grouped under this model.
+const UNKNOWN_MODEL: &str = "unknown";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Reaction {
+    ThumbsUp,
+    ThumbsDown,
+}
+
+/// A user's note on a turn, or on one item of it when `item_id` is set.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Annotation {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    #[serde(default)]
+    pub(crate) item_id: Option<String>,
+    #[serde(default)]
+    pub(crate) reaction: Option<Reaction>,
+    #[serde(default)]
+    pub(crate) comment: Option<String>,
+    #[serde(default)]
+    pub(crate) needs_follow_up: bool,
+    #[serde(default)]
+    pub(crate) updated_at: i64,
+}
+
+impl Annotation {
+    fn is_empty(&self) -> bool {
+        self.reaction.is_none() && self.comment.is_none() && !self.needs_follow_up
+    }
+
+    fn same_target(&self, other: &Annotation) -> bool {
+        self.workspace_id == other.workspace_id
+            && self.thread_id == other.thread_id
+            && self.turn_id == other.turn_id
+            && self.item_id == other.item_id
+    }
+}
+
+/// Reactions and follow-up flags on the turns of one model.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelFeedback {
+    pub(crate) model: String,
+    pub(crate) thumbs_up: u32,
+    pub(crate) thumbs_down: u32,
+    /// Share of reactions that are thumbs up; `None` without reactions.
+    pub(crate) success_rate: Option<f64>,
+    pub(crate) follow_ups: u32,
+}
+
+pub(crate) fn annotations_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("annotations.json")
+}
+
+pub(crate) fn read_annotations(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<Annotation>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+/// Replaces the annotation of a turn or item; one without a reaction,
+/// comment or follow-up flag removes it.
+pub(crate) fn annotate(
+    path: &Path,
+    cipher: &DataCipher,
+    mut annotation: Annotation,
+) -> Result<Annotation, String> {
+    if annotation.turn_id.trim().is_empty() {
+        return Err("Turn id is required.".to_string());
+    }
+    annotation.item_id = annotation
+        .item_id
+        .filter(|item_id| !item_id.trim().is_empty());
+    annotation.comment = annotation
+        .comment
+        .map(|comment| comment.trim().to_string())
+        .filter(|comment| !comment.is_empty());
+    let mut entries = read_annotations(path, cipher)?;
+    entries.retain(|entry| !entry.same_target(&annotation));
+    if !annotation.is_empty() {
+        entries.push(annotation.clone());
+    }
+    let data = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)?;
+    Ok(annotation)
+}
+
+/// Aggregates annotations per model; `turn_models` maps turn ids to the
+/// model they ran with.
+pub(crate) fn feedback_by_model(
+    annotations: &[Annotation],
+    turn_models: &HashMap<String, String>,
+) -> Vec<ModelFeedback> {
+    let mut models: BTreeMap<&str, ModelFeedback> = BTreeMap::new();
+    for annotation in annotations {
+        let model = turn_models
+            .get(&annotation.turn_id)
+            .map(String::as_str)
+            .unwrap_or(UNKNOWN_MODEL);
+        let feedback = models.entry(model).or_insert_with(|| ModelFeedback {
+            model: model.to_string(),
+            thumbs_up: 0,
+            thumbs_down: 0,
+            success_rate: None,
+            follow_ups: 0,
+        });
+        match annotation.reaction {
+            Some(Reaction::ThumbsUp) => feedback.thumbs_up += 1,
+            Some(Reaction::ThumbsDown) => feedback.thumbs_down += 1,
+            None => {}
+        }
+        if annotation.needs_follow_up {
+            feedback.follow_ups += 1;
+        }
+    }
+    models
+        .into_values()
+        .map(|mut feedback| {
+            let reactions = feedback.thumbs_up + feedback.thumbs_down;
+            if reactions > 0 {
+                feedback.success_rate = Some(f64::from(feedback.thumbs_up) / f64::from(reactions));
+            }
+            feedback
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn annotation(turn_id: &str, reaction: Option<Reaction>) -> Annotation {
+        Annotation {
+            workspace_id: "ws".to_string(),
+            thread_id: "t1".to_string(),
+            turn_id: turn_id.to_string(),
+            item_id: None,
+            reaction,
+            comment: None,
+            needs_follow_up: false,
+            updated_at: 1,
+        }
+    }
+
+    #[test]
+    fn replaces_and_clears_annotations_per_target() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = annotations_path(&dir);
+        let cipher = DataCipher::plaintext();
+        annotate(
+            &path,
+            &cipher,
+            annotation("turn-1", Some(Reaction::ThumbsUp)),
+        )
+        .unwrap();
+        let mut item = annotation("turn-1", None);
+        item.item_id = Some("item-1".to_string());
+        item.comment = Some("  check this  ".to_string());
+        let stored = annotate(&path, &cipher, item).unwrap();
+        assert_eq!(stored.comment.as_deref(), Some("check this"));
+        annotate(
+            &path,
+            &cipher,
+            annotation("turn-1", Some(Reaction::ThumbsDown)),
+        )
+        .unwrap();
+        let entries = read_annotations(&path, &cipher).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries
+            .iter()
+            .any(|entry| entry.item_id.is_none() && entry.reaction == Some(Reaction::ThumbsDown)));
+
+        annotate(&path, &cipher, annotation("turn-1", None)).unwrap();
+        assert_eq!(read_annotations(&path, &cipher).unwrap().len(), 1);
+        assert!(annotate(&path, &cipher, annotation(" ", None)).is_err());
+    }
+
+    #[test]
+    fn computes_success_rate_by_model() {
+        let mut flagged = annotation("turn-3", Some(Reaction::ThumbsDown));
+        flagged.needs_follow_up = true;
+        let annotations = vec![
+            annotation("turn-1", Some(Reaction::ThumbsUp)),
+            annotation("turn-2", Some(Reaction::ThumbsUp)),
+            flagged,
+            annotation("turn-4", None),
+        ];
+        let turn_models = HashMap::from([
+            ("turn-1".to_string(), "gpt-5-codex".to_string()),
+            ("turn-2".to_string(), "gpt-5-codex".to_string()),
+            ("turn-3".to_string(), "gpt-5-codex".to_string()),
+        ]);
+        let feedback = feedback_by_model(&annotations, &turn_models);
+        assert_eq!(feedback.len(), 2);
+        assert_eq!(feedback[0].model, "gpt-5-codex");
+        assert_eq!(feedback[0].thumbs_up, 2);
+        assert_eq!(feedback[0].follow_ups, 1);
+        assert_eq!(feedback[0].success_rate, Some(2.0 / 3.0));
+        assert_eq!(feedback[1].model, UNKNOWN_MODEL);
+        assert_eq!(feedback[1].success_rate, None);
+    }
+}
@@ -1,20 +1,45 @@
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStdin, Command};
 use tokio::sync::{mpsc, oneshot, Mutex};
 use tokio::time::timeout;
 
+use crate::backend::command_guard;
+#[cfg(debug_assertions)]
+use crate::backend::connection_sim;
 use crate::backend::events::{AppServerEvent, EventSink};
+use crate::backend::framing::{read_frame, Framing, SessionFraming};
+use crate::backend::i18n::{localize, localize_detail, MessageCode};
+use crate::backend::narration::Narrator;
+use crate::backend::network::apply_network_env;
+use crate::backend::protocol_compat::{ProtocolStats, SessionProtocolReport};
+use crate::backend::request_id::RequestId;
+use crate::backend::resource_limits;
+use crate::backend::session_env::{self, SessionEnvironment};
+use crate::backend::session_output::{OutputStream, SessionOutputBuffer, SessionOutputLine};
+use crate::backend::stderr_filter::StderrFilter;
+use crate::backend::turn_summary::TurnSummaryTracker;
 use crate::types::WorkspaceEntry;
 
+/// How long suppressed stderr lines may accumulate before their summary is
+/// emitted without waiting for the next forwarded line.
+const STDERR_SUMMARY_INTERVAL: Duration = Duration::from_secs(10);
+/// Raw output lines `tail_session_output` returns by default.
+const DEFAULT_TAIL_LINES: usize = 200;
+/// Total time the app-server has to answer `initialize`.
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(15);
+/// Time to answer a newline-framed `initialize` before the handshake is
+/// retried with `Content-Length` framing.
+const INITIALIZE_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
 fn extract_thread_id(value: &Value) -> Option<String> {
     value
         .get("params")
@@ -27,21 +52,71 @@ pub(crate) struct WorkspaceSession {
     pub(crate) entry: WorkspaceEntry,
     pub(crate) child: Mutex<Child>,
     pub(crate) stdin: Mutex<ChildStdin>,
+    /// Our requests awaiting a response, by the ids we allocated. Kept apart
+    /// from `server_requests`, since both sides may use the same ids.
     pub(crate) pending: Mutex<HashMap<u64, oneshot::Sender<Value>>>,
     pub(crate) next_id: AtomicU64,
+    /// Requests from the app-server we have not answered yet.
+    pub(crate) server_requests: std::sync::Mutex<HashSet<RequestId>>,
     /// Callbacks for background threads - events for these threadIds are sent through the channel
     pub(crate) background_thread_callbacks: Mutex<HashMap<String, mpsc::UnboundedSender<Value>>>,
+    /// Binary, `--version` output and `CODEX_HOME` the app-server was started with.
+    pub(crate) codex_bin: Option<String>,
+    pub(crate) codex_version: Option<String>,
+    pub(crate) codex_home: Option<PathBuf>,
+    /// App-server methods seen in this session, by protocol registry status.
+    pub(crate) protocol_stats: std::sync::Mutex<ProtocolStats>,
+    /// Message framing on stdio, switched to match what the app-server sends.
+    pub(crate) framing: SessionFraming,
+    /// Command line, cwd and environment the app-server was launched with.
+    pub(crate) environment: SessionEnvironment,
+    /// Raw stdout and stderr lines, for `tail_session_output`.
+    pub(crate) raw_output: std::sync::Mutex<SessionOutputBuffer>,
 }
 
 impl WorkspaceSession {
+    /// Buffers a raw line and streams it when live output is on.
+    fn record_output<E: EventSink>(&self, event_sink: &E, stream: OutputStream, line: &str) {
+        let live = match self.raw_output.lock() {
+            Ok(mut buffer) => buffer.push(
+                &self.entry.id,
+                stream,
+                line,
+                chrono::Utc::now().timestamp_millis(),
+            ),
+            Err(_) => None,
+        };
+        if let Some(line) = live {
+            event_sink.emit_session_output(line);
+        }
+    }
+
+    /// The last `lines` raw output lines, oldest first.
+    pub(crate) fn tail_output(&self, lines: Option<u32>) -> Vec<SessionOutputLine> {
+        let count = lines.map_or(DEFAULT_TAIL_LINES, |lines| lines as usize);
+        match self.raw_output.lock() {
+            Ok(buffer) => buffer.tail(count),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Turns streaming of raw output as `session-output` on or off.
+    pub(crate) fn set_output_live(&self, live: bool) {
+        if let Ok(mut buffer) = self.raw_output.lock() {
+            buffer.set_live(live);
+        }
+    }
+
     async fn write_message(&self, value: Value) -> Result<(), String> {
+        let message = serde_json::to_string(&value).map_err(|e| e.to_string())?;
+        let frame = self.framing.get().encode(&message);
+        // Delayed before taking stdin, so other writers are not held up.
+        #[cfg(debug_assertions)]
+        if !connection_sim::transmit(frame.len()).await {
+            return Ok(());
+        }
         let mut stdin = self.stdin.lock().await;
-        let mut line = serde_json::to_string(&value).map_err(|e| e.to_string())?;
-        line.push('\n');
-        stdin
-            .write_all(line.as_bytes())
-            .await
-            .map_err(|e| e.to_string())
+        stdin.write_all(&frame).await.map_err(|e| e.to_string())
     }
 
     pub(crate) async fn send_request(&self, method: &str, params: Value) -> Result<Value, String> {
@@ -53,6 +128,27 @@ impl WorkspaceSession {
         rx.await.map_err(|_| "request canceled".to_string())
     }
 
+    /// Sends a request whose reply goes to `reply` instead of being awaited,
+    /// and returns its id.
+    async fn send_request_with_reply(
+        &self,
+        method: &str,
+        params: Value,
+        reply: mpsc::UnboundedSender<Value>,
+    ) -> Result<u64, String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(id, tx);
+        tokio::spawn(async move {
+            if let Ok(value) = rx.await {
+                let _ = reply.send(value);
+            }
+        });
+        self.write_message(json!({ "id": id, "method": method, "params": params }))
+            .await?;
+        Ok(id)
+    }
+
     pub(crate) async fn send_notification(
         &self,
         method: &str,
@@ -66,12 +162,58 @@ impl WorkspaceSession {
         self.write_message(value).await
     }
 
-    pub(crate) async fn send_response(&self, id: u64, result: Value) -> Result<(), String> {
+    pub(crate) fn protocol_report(&self) -> SessionProtocolReport {
+        let codex_version = self.codex_version.as_deref();
+        match self.protocol_stats.lock() {
+            Ok(stats) => stats.report(&self.entry.id, codex_version),
+            Err(_) => ProtocolStats::default().report(&self.entry.id, codex_version),
+        }
+    }
+
+    /// Answers a request the app-server sent; fails for ids it did not send
+    /// or that were already answered.
+    pub(crate) async fn send_response(&self, id: &RequestId, result: Value) -> Result<(), String> {
+        let outstanding = self
+            .server_requests
+            .lock()
+            .map(|mut requests| requests.remove(id))
+            .unwrap_or(false);
+        if !outstanding {
+            return Err(format!("No pending app-server request with id {id}"));
+        }
         self.write_message(json!({ "id": id, "result": result }))
             .await
     }
 }
 
+/// Sends `initialize` newline-framed and, when that gets no answer in
+/// time, again under the same id with `Content-Length` framing for builds
+/// that only read those frames. Whichever answer arrives first is used;
+/// `None` means neither did.
+async fn initialize(session: &WorkspaceSession, params: Value) -> Result<Option<Value>, String> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let id = session
+        .send_request_with_reply("initialize", params.clone(), tx)
+        .await?;
+    if let Ok(response) = timeout(INITIALIZE_PROBE_TIMEOUT, rx.recv()).await {
+        return Ok(response);
+    }
+    session.framing.set(Framing::ContentLength);
+    session
+        .write_message(json!({ "id": id, "method": "initialize", "params": params }))
+        .await?;
+    let response = timeout(INITIALIZE_TIMEOUT - INITIALIZE_PROBE_TIMEOUT, rx.recv())
+        .await
+        .ok()
+        .flatten();
+    if response.is_some() && session.framing.get() == Framing::NewlineDelimited {
+        // A slow newline-framed answer won; end the line the unframed
+        // `Content-Length` retry left open so the next message parses.
+        let _ = session.stdin.lock().await.write_all(b"\n").await;
+    }
+    Ok(response)
+}
+
 pub(crate) fn build_codex_path_env(codex_bin: Option<&str>) -> Option<String> {
     let mut paths: Vec<String> = env::var("PATH")
         .unwrap_or_default()
@@ -132,6 +274,7 @@ pub(crate) fn build_codex_command_with_bin(codex_bin: Option<String>) -> Command
     if let Some(path_env) = build_codex_path_env(codex_bin.as_deref()) {
         command.env("PATH", path_env);
     }
+    apply_network_env(&mut command);
     command
 }
 
@@ -146,17 +289,13 @@ pub(crate) async fn check_codex_installation(
     let output = match timeout(Duration::from_secs(5), command.output()).await {
         Ok(result) => result.map_err(|e| {
             if e.kind() == ErrorKind::NotFound {
-                "Codex CLI not found. Install Codex and ensure `codex` is on your PATH."
-                    .to_string()
+                localize(MessageCode::CodexNotFound)
             } else {
                 e.to_string()
             }
         })?,
         Err(_) => {
-            return Err(
-                "Timed out while checking Codex CLI. Make sure `codex --version` runs in Terminal."
-                    .to_string(),
-            );
+            return Err(localize(MessageCode::CodexCheckTimedOut));
         }
     };
 
@@ -169,14 +308,9 @@ pub(crate) async fn check_codex_installation(
             stderr.trim()
         };
         if detail.is_empty() {
-            return Err(
-                "Codex CLI failed to start. Try running `codex --version` in Terminal."
-                    .to_string(),
-            );
+            return Err(localize(MessageCode::CodexFailedToStart));
         }
-        return Err(format!(
-            "Codex CLI failed to start: {detail}. Try running `codex --version` in Terminal."
-        ));
+        return Err(localize_detail(MessageCode::CodexFailedToStartDetail, detail));
     }
 
     let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -195,17 +329,61 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
         .clone()
         .filter(|value| !value.trim().is_empty())
         .or(default_codex_bin);
-    let _ = check_codex_installation(codex_bin.clone()).await?;
+    let codex_version = check_codex_installation(codex_bin.clone()).await?;
 
-    let mut command = build_codex_command_with_bin(codex_bin);
+    let mut command = build_codex_command_with_bin(codex_bin.clone());
     command.current_dir(&entry.path);
     command.arg("app-server");
-    if let Some(codex_home) = codex_home {
+    if let Some(codex_home) = &codex_home {
         command.env("CODEX_HOME", codex_home);
     }
+    if let Some(limits) = &entry.settings.resource_limits {
+        let unsupported = resource_limits::apply(&mut command, limits);
+        if !unsupported.is_empty() {
+            eprintln!(
+                "Resource limits not supported on this platform for {}: {}",
+                entry.name,
+                unsupported.join(", ")
+            );
+        }
+    }
+    let launch = SessionLaunch {
+        codex_bin,
+        codex_version,
+        codex_home,
+    };
+    start_session(entry, command, launch, client_version, event_sink).await
+}
+
+/// Binary details a session records about its app-server.
+pub(crate) struct SessionLaunch {
+    pub(crate) codex_bin: Option<String>,
+    pub(crate) codex_version: Option<String>,
+    pub(crate) codex_home: Option<PathBuf>,
+}
+
+/// Spawns `command` as the app-server of `entry`, wires up its output and
+/// runs the `initialize` handshake.
+pub(crate) async fn start_session<E: EventSink>(
+    entry: WorkspaceEntry,
+    mut command: Command,
+    launch: SessionLaunch,
+    client_version: String,
+    event_sink: E,
+) -> Result<Arc<WorkspaceSession>, String> {
+    let SessionLaunch {
+        codex_bin,
+        codex_version,
+        codex_home,
+    } = launch;
     command.stdin(std::process::Stdio::piped());
     command.stdout(std::process::Stdio::piped());
     command.stderr(std::process::Stdio::piped());
+    let environment = session_env::capture(
+        command.as_std(),
+        env::vars(),
+        chrono::Utc::now().timestamp_millis(),
+    );
 
     let mut child = command.spawn().map_err(|e| e.to_string())?;
     let stdin = child.stdin.take().ok_or("missing stdin")?;
@@ -218,19 +396,35 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
         stdin: Mutex::new(stdin),
         pending: Mutex::new(HashMap::new()),
         next_id: AtomicU64::new(1),
+        server_requests: std::sync::Mutex::new(HashSet::new()),
         background_thread_callbacks: Mutex::new(HashMap::new()),
+        codex_bin,
+        codex_version,
+        codex_home,
+        protocol_stats: std::sync::Mutex::new(ProtocolStats::default()),
+        framing: SessionFraming::default(),
+        environment,
+        raw_output: std::sync::Mutex::new(SessionOutputBuffer::default()),
     });
 
     let session_clone = Arc::clone(&session);
     let workspace_id = entry.id.clone();
     let event_sink_clone = event_sink.clone();
     tokio::spawn(async move {
-        let mut lines = BufReader::new(stdout).lines();
-        while let Ok(Some(line)) = lines.next_line().await {
+        let mut reader = BufReader::new(stdout);
+        let mut turn_summaries = TurnSummaryTracker::default();
+        let mut narrator = Narrator::default();
+        while let Ok(Some((line, framing))) = read_frame(&mut reader).await {
+            session_clone.framing.set(framing);
             if line.trim().is_empty() {
                 continue;
             }
-            let value: Value = match serde_json::from_str(&line) {
+            #[cfg(debug_assertions)]
+            if !connection_sim::transmit(line.len()).await {
+                continue;
+            }
+            session_clone.record_output(&event_sink_clone, OutputStream::Stdout, &line);
+            let mut value: Value = match serde_json::from_str(&line) {
                 Ok(value) => value,
                 Err(err) => {
                     let payload = AppServerEvent {
@@ -245,19 +439,45 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                 }
             };
 
-            let maybe_id = value.get("id").and_then(|id| id.as_u64());
+            let maybe_id = RequestId::from_message(&value);
             let has_method = value.get("method").is_some();
+            if let Some(method) = value.get("method").and_then(|method| method.as_str()) {
+                if let Ok(mut stats) = session_clone.protocol_stats.lock() {
+                    stats.record(method, chrono::Utc::now().timestamp_millis());
+                }
+            }
             let has_result_or_error = value.get("result").is_some() || value.get("error").is_some();
 
             // Check if this event is for a background thread
             let thread_id = extract_thread_id(&value);
 
             if let Some(id) = maybe_id {
-                if has_result_or_error {
-                    if let Some(tx) = session_clone.pending.lock().await.remove(&id) {
-                        let _ = tx.send(value);
+                if has_result_or_error || !has_method {
+                    // A response can only be to one of our requests, whose ids
+                    // are always numbers.
+                    if let RequestId::Number(id) = id {
+                        if let Some(tx) = session_clone.pending.lock().await.remove(&id) {
+                            let _ = tx.send(value);
+                        }
+                    }
+                } else {
+                    if let Ok(mut requests) = session_clone.server_requests.lock() {
+                        requests.insert(id.clone());
+                    }
+                    if let Some(blocked) = command_guard::check_message(&value) {
+                        // Declined here so the request never reaches an approver.
+                        if let Err(err) = session_clone
+                            .send_response(&id, json!({ "decision": "decline" }))
+                            .await
+                        {
+                            eprintln!("Failed to decline blocked command: {err}");
+                        }
+                        event_sink_clone.emit_app_server_event(AppServerEvent {
+                            workspace_id: workspace_id.clone(),
+                            message: blocked.to_event(),
+                        });
+                        continue;
                     }
-                } else if has_method {
                     // Check for background thread callback
                     let mut sent_to_background = false;
                     if let Some(ref tid) = thread_id {
@@ -269,16 +489,45 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                     }
                     // Don't emit to frontend if this is a background thread event
                     if !sent_to_background {
+                        if let Some(narration) =
+                            narrator.observe(&workspace_id, &value, Instant::now())
+                        {
+                            event_sink_clone.emit_narration(narration);
+                        }
                         let payload = AppServerEvent {
                             workspace_id: workspace_id.clone(),
                             message: value,
                         };
                         event_sink_clone.emit_app_server_event(payload);
                     }
-                } else if let Some(tx) = session_clone.pending.lock().await.remove(&id) {
-                    let _ = tx.send(value);
                 }
             } else if has_method {
+                if let Some(blocked) = command_guard::check_message(&value) {
+                    if let (Some(thread_id), Some(turn_id)) =
+                        (blocked.thread_id.clone(), blocked.turn_id.clone())
+                    {
+                        // The reader task delivers the response, so it must not wait for it.
+                        let session = Arc::clone(&session_clone);
+                        tokio::spawn(async move {
+                            let params = json!({ "threadId": thread_id, "turnId": turn_id });
+                            if let Err(err) = session.send_request("turn/interrupt", params).await {
+                                eprintln!("Failed to interrupt blocked command: {err}");
+                            }
+                        });
+                    }
+                    event_sink_clone.emit_app_server_event(AppServerEvent {
+                        workspace_id: workspace_id.clone(),
+                        message: blocked.to_event(),
+                    });
+                }
+                if let Some(summary) = turn_summaries.observe(&value) {
+                    if let (Some(params), Ok(summary)) = (
+                        value.get_mut("params").and_then(|params| params.as_object_mut()),
+                        serde_json::to_value(summary),
+                    ) {
+                        params.insert("summary".to_string(), summary);
+                    }
+                }
                 // Check for background thread callback
                 let mut sent_to_background = false;
                 if let Some(ref tid) = thread_id {
@@ -290,6 +539,10 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                 }
                 // Don't emit to frontend if this is a background thread event
                 if !sent_to_background {
+                    if let Some(narration) = narrator.observe(&workspace_id, &value, Instant::now())
+                    {
+                        event_sink_clone.emit_narration(narration);
+                    }
                     let payload = AppServerEvent {
                         workspace_id: workspace_id.clone(),
                         message: value,
@@ -300,14 +553,39 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
         }
     });
 
+    let session_clone = Arc::clone(&session);
     let workspace_id = entry.id.clone();
     let event_sink_clone = event_sink.clone();
+    let mut stderr_filter = StderrFilter::new(entry.settings.stderr_filter.as_ref());
     tokio::spawn(async move {
         let mut lines = BufReader::new(stderr).lines();
-        while let Ok(Some(line)) = lines.next_line().await {
-            if line.trim().is_empty() {
-                continue;
+        loop {
+            // `next_line` is cancel safe, so the timeout only flushes the summary.
+            let line = match timeout(STDERR_SUMMARY_INTERVAL, lines.next_line()).await {
+                Ok(Ok(Some(line))) => Some(line),
+                Ok(_) => break,
+                Err(_) => None,
+            };
+            if let Some(line) = line.as_deref() {
+                if !line.trim().is_empty() {
+                    session_clone.record_output(&event_sink_clone, OutputStream::Stderr, line);
+                }
+                if line.trim().is_empty() || !stderr_filter.accept(line) {
+                    continue;
+                }
+            }
+            if let Some(summary) = stderr_filter.take_summary() {
+                event_sink_clone.emit_app_server_event(AppServerEvent {
+                    workspace_id: workspace_id.clone(),
+                    message: json!({
+                        "method": "codex/stderrSuppressed",
+                        "params": summary,
+                    }),
+                });
             }
+            let Some(line) = line else {
+                continue;
+            };
             let payload = AppServerEvent {
                 workspace_id: workspace_id.clone(),
                 message: json!({
@@ -317,6 +595,15 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
             };
             event_sink_clone.emit_app_server_event(payload);
         }
+        if let Some(summary) = stderr_filter.take_summary() {
+            event_sink_clone.emit_app_server_event(AppServerEvent {
+                workspace_id,
+                message: json!({
+                    "method": "codex/stderrSuppressed",
+                    "params": summary,
+                }),
+            });
+        }
     });
 
     let init_params = json!({
@@ -326,23 +613,11 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
             "version": client_version
         }
     });
-    let init_result = timeout(
-        Duration::from_secs(15),
-        session.send_request("initialize", init_params),
-    )
-    .await;
-    let init_response = match init_result {
-        Ok(response) => response,
-        Err(_) => {
-            let mut child = session.child.lock().await;
-            let _ = child.kill().await;
-            return Err(
-                "Codex app-server did not respond to initialize. Check that `codex app-server` works in Terminal."
-                    .to_string(),
-            );
-        }
-    };
-    init_response?;
+    if initialize(&session, init_params).await?.is_none() {
+        let mut child = session.child.lock().await;
+        let _ = child.kill().await;
+        return Err(localize(MessageCode::AppServerInitializeTimedOut));
+    }
     session.send_notification("initialized", None).await?;
 
     let payload = AppServerEvent {
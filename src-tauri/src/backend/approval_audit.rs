@@ -0,0 +1,396 @@
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use crate::backend::request_id::RequestId;
+use crate::backend::secure_storage::DataCipher;
+
+const FILE_PREFIX: &str = "audit-";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ApprovalAuditAction {
+    /// The pending approval was sent to the delegated approver.
+    Forwarded,
+    /// A decision signed by the approver was applied on their behalf.
+    Delegated,
+    /// A delegated approval was answered locally instead.
+    AnsweredLocally,
+    /// A decision with a missing or invalid signature was refused.
+    Rejected,
+    /// A thread was allowed to write to a directory outside its sandbox.
+    DirectoryGranted,
+    /// A request to write outside the thread's sandbox was declined.
+    DirectoryDenied,
+    /// A thread's turns were given full access for a limited time.
+    FullAccessGranted,
+    /// A full access grant expired or was revoked.
+    FullAccessReverted,
+    /// The oldest pending approval was answered with a keyboard shortcut.
+    AnsweredByShortcut,
+    /// A pending approval was answered through the `/approvals` inbox.
+    AnsweredFromInbox,
+}
+
+/// One entry of the approval audit log.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalAuditEntry {
+    pub(crate) recorded_at: i64,
+    pub(crate) workspace_id: String,
+    /// `None` for entries with no app-server request behind them, like
+    /// full access grants.
+    #[serde(default)]
+    pub(crate) request_id: Option<RequestId>,
+    pub(crate) delegation_id: Option<String>,
+    pub(crate) action: ApprovalAuditAction,
+    /// Approver name for delegated decisions, "local" otherwise.
+    pub(crate) actor: String,
+    pub(crate) method: Option<String>,
+    pub(crate) decision: Option<Value>,
+    pub(crate) detail: Option<String>,
+}
+
+/// An audit entry as stored: chained to the entry before it by hash, so an
+/// edited, removed or reordered entry breaks the chain.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ChainedAuditEntry {
+    #[serde(flatten)]
+    pub(crate) entry: ApprovalAuditEntry,
+    /// `None` for the first entry, and for entries written before chaining.
+    #[serde(default)]
+    pub(crate) prev_hash: Option<String>,
+    #[serde(default)]
+    pub(crate) hash: Option<String>,
+}
+
+/// Hash of the newest entry, kept apart from the daily files so removing
+/// entries from the end of the log is detected too.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct ChainHead {
+    hash: String,
+    recorded_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AuditChainBreak {
+    /// Day file (`YYYY-MM-DD`) of the first entry that does not verify.
+    pub(crate) day: String,
+    pub(crate) index: usize,
+    pub(crate) reason: String,
+}
+
+/// Result of checking the audit chain from its oldest entry on disk.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AuditChainReport {
+    pub(crate) entries: usize,
+    /// Entries written before chaining was introduced; not verifiable.
+    pub(crate) unchained: usize,
+    /// Hash the oldest chained entry on disk links to; older entries were
+    /// removed by the retention window.
+    pub(crate) anchor: Option<String>,
+    pub(crate) head: Option<String>,
+    pub(crate) intact: bool,
+    pub(crate) broken_at: Option<AuditChainBreak>,
+}
+
+/// The approval logs data class directory, subject to its retention window.
+pub(crate) fn approvals_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("approvals")
+}
+
+fn day_file(data_dir: &Path, day: NaiveDate) -> PathBuf {
+    approvals_dir(data_dir).join(format!("{FILE_PREFIX}{}.json", day.format("%Y-%m-%d")))
+}
+
+/// Audit log files, which follow the encryption setting.
+pub(crate) fn audit_files(data_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(approvals_dir(data_dir)) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(FILE_PREFIX))
+        })
+        .collect()
+}
+
+pub(crate) fn chain_head_path(data_dir: &Path) -> PathBuf {
+    approvals_dir(data_dir).join("chain-head.json")
+}
+
+/// Audit log files sorted oldest day first, with their day.
+fn sorted_audit_files(data_dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = audit_files(data_dir)
+        .into_iter()
+        .filter_map(|path| {
+            let day = path
+                .file_stem()?
+                .to_str()?
+                .strip_prefix(FILE_PREFIX)?
+                .to_string();
+            Some((day, path))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn read_day(path: &Path, cipher: &DataCipher) -> Result<Vec<ChainedAuditEntry>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+fn read_head(data_dir: &Path, cipher: &DataCipher) -> Result<Option<ChainHead>, String> {
+    let path = chain_head_path(data_dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = cipher.read_to_string(&path)?;
+    serde_json::from_str(&data)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+fn entry_hash(prev_hash: Option<&str>, entry: &ApprovalAuditEntry) -> Result<String, String> {
+    let data = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash.unwrap_or_default().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(data.as_bytes());
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Appends an entry linked to the newest one. Callers serialize appends,
+/// since two appends reading the same head would fork the chain.
+pub(crate) fn append_entry(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    entry: ApprovalAuditEntry,
+) -> Result<(), String> {
+    let prev_hash = match read_head(data_dir, cipher)? {
+        Some(head) => Some(head.hash),
+        // Logs written before the head file existed end in their newest entry.
+        None => match sorted_audit_files(data_dir).last() {
+            Some((_, path)) => read_day(path, cipher)?
+                .into_iter()
+                .rev()
+                .find_map(|entry| entry.hash),
+            None => None,
+        },
+    };
+    let hash = entry_hash(prev_hash.as_deref(), &entry)?;
+    let day = chrono::DateTime::from_timestamp_millis(entry.recorded_at)
+        .unwrap_or_else(Utc::now)
+        .date_naive();
+    let head = ChainHead {
+        hash: hash.clone(),
+        recorded_at: entry.recorded_at,
+    };
+    let path = day_file(data_dir, day);
+    let mut entries = read_day(&path, cipher)?;
+    entries.push(ChainedAuditEntry {
+        entry,
+        prev_hash,
+        hash: Some(hash),
+    });
+    let data = serde_json::to_string(&entries).map_err(|e| e.to_string())?;
+    cipher.write(&path, &data)?;
+    let data = serde_json::to_string(&head).map_err(|e| e.to_string())?;
+    cipher.write(&chain_head_path(data_dir), &data)
+}
+
+/// Audit entries of one day, oldest first.
+pub(crate) fn read_entries(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    day: NaiveDate,
+) -> Result<Vec<ChainedAuditEntry>, String> {
+    read_day(&day_file(data_dir, day), cipher)
+}
+
+/// Every audit entry on disk, oldest first.
+pub(crate) fn read_all_entries(
+    data_dir: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<ChainedAuditEntry>, String> {
+    let mut entries = Vec::new();
+    for (_, path) in sorted_audit_files(data_dir) {
+        entries.extend(read_day(&path, cipher)?);
+    }
+    Ok(entries)
+}
+
+/// Recomputes every hash and link from the oldest entry on disk and checks
+/// that the newest one is the recorded head, so edited, inserted, removed or
+/// truncated entries are reported.
+pub(crate) fn verify_chain(
+    data_dir: &Path,
+    cipher: &DataCipher,
+) -> Result<AuditChainReport, String> {
+    let head = read_head(data_dir, cipher)?.map(|head| head.hash);
+    let mut report = AuditChainReport {
+        entries: 0,
+        unchained: 0,
+        anchor: None,
+        head: head.clone(),
+        intact: true,
+        broken_at: None,
+    };
+    let mut last: Option<(String, usize, Option<String>)> = None;
+    let mut chained = false;
+    for (day, path) in sorted_audit_files(data_dir) {
+        for (index, stored) in read_day(&path, cipher)?.into_iter().enumerate() {
+            report.entries += 1;
+            let problem = match &stored.hash {
+                None if !chained => {
+                    report.unchained += 1;
+                    None
+                }
+                None => Some("entry has no hash".to_string()),
+                Some(hash) => {
+                    let links = if chained {
+                        stored.prev_hash == last.as_ref().and_then(|(_, _, hash)| hash.clone())
+                    } else {
+                        // Older entries may have been removed by retention.
+                        chained = true;
+                        report.anchor = stored.prev_hash.clone();
+                        true
+                    };
+                    if !links {
+                        Some("entry does not link to the one before it".to_string())
+                    } else if entry_hash(stored.prev_hash.as_deref(), &stored.entry)? != *hash {
+                        Some("entry was modified".to_string())
+                    } else {
+                        None
+                    }
+                }
+            };
+            if let Some(reason) = problem {
+                report.intact = false;
+                report.broken_at = Some(AuditChainBreak { day, index, reason });
+                return Ok(report);
+            }
+            last = Some((day.clone(), index, stored.hash));
+        }
+    }
+    let newest = last.as_ref().and_then(|(_, _, hash)| hash.clone());
+    if head.is_some() && newest != head {
+        report.intact = false;
+        report.broken_at = Some(match last {
+            Some((day, index, _)) => AuditChainBreak {
+                day,
+                index,
+                reason: "entries after this one were removed".to_string(),
+            },
+            None => AuditChainBreak {
+                day: String::new(),
+                index: 0,
+                reason: "all entries were removed".to_string(),
+            },
+        });
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn appends_entries_to_daily_files() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        let now = Utc::now();
+        for action in [
+            ApprovalAuditAction::Forwarded,
+            ApprovalAuditAction::Delegated,
+        ] {
+            let entry = ApprovalAuditEntry {
+                recorded_at: now.timestamp_millis(),
+                workspace_id: "ws-1".to_string(),
+                request_id: Some(RequestId::Number(7)),
+                delegation_id: Some("d-1".to_string()),
+                action,
+                actor: "senior".to_string(),
+                method: Some("item/commandExecution/requestApproval".to_string()),
+                decision: Some(json!({ "decision": "accept" })),
+                detail: None,
+            };
+            append_entry(&dir, &cipher, entry).expect("append");
+        }
+        let entries = read_entries(&dir, &cipher, now.date_naive()).expect("read");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].entry.action, ApprovalAuditAction::Delegated);
+        assert_eq!(audit_files(&dir).len(), 1);
+        assert_eq!(entries[1].prev_hash, entries[0].hash);
+        assert!(verify_chain(&dir, &cipher).expect("verify").intact);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn detects_edited_and_truncated_entries() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        let now = Utc::now();
+        for request_id in 1..=3 {
+            let entry = ApprovalAuditEntry {
+                recorded_at: now.timestamp_millis(),
+                workspace_id: "ws-1".to_string(),
+                request_id: Some(RequestId::Number(request_id)),
+                delegation_id: None,
+                action: ApprovalAuditAction::DirectoryGranted,
+                actor: "local".to_string(),
+                method: None,
+                decision: None,
+                detail: None,
+            };
+            append_entry(&dir, &cipher, entry).expect("append");
+        }
+        let path = day_file(&dir, now.date_naive());
+        let original = read_day(&path, &cipher).expect("read");
+        let write = |entries: &[ChainedAuditEntry]| {
+            cipher
+                .write(&path, &serde_json::to_string(entries).unwrap())
+                .unwrap();
+        };
+
+        let mut edited = original.clone();
+        edited[1].entry.actor = "someone".to_string();
+        write(&edited);
+        let report = verify_chain(&dir, &cipher).expect("verify");
+        assert!(!report.intact);
+        assert_eq!(report.broken_at.map(|at| at.index), Some(1));
+
+        write(&original[..2]);
+        let report = verify_chain(&dir, &cipher).expect("verify");
+        assert!(!report.intact);
+        assert_eq!(report.entries, 2);
+
+        // Entries removed from the start by retention leave an anchor.
+        write(&original[1..]);
+        let report = verify_chain(&dir, &cipher).expect("verify");
+        assert!(report.intact);
+        assert_eq!(report.anchor, original[0].hash);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use subtle::ConstantTimeEq;
+
+use crate::backend::local_http::HttpRequest;
+use crate::backend::request_id::RequestId;
+use crate::backend::turn_summary::{params_thread_id, params_turn_id};
+
+/// Decisions a bot may send; anything broader stays with the app.
+const DECISIONS: [&str; 2] = ["accept", "decline"];
+
+/// An approval request the app-server is waiting on, as listed by
+/// `GET /approvals`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InboxApproval {
+    /// `<workspaceId>:<requestId>`, used in `/approvals/{id}/respond`.
+    pub(crate) id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) request_id: RequestId,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: Option<String>,
+    pub(crate) method: String,
+    pub(crate) command: Option<String>,
+    pub(crate) cwd: Option<String>,
+    pub(crate) reason: Option<String>,
+    pub(crate) received_at: i64,
+}
+
+/// Body of `POST /approvals/{id}/respond`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RespondBody {
+    decision: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum InboxRoute {
+    List,
+    Respond(String),
+    MethodNotAllowed,
+    NotFound,
+}
+
+pub(crate) fn approval_id(workspace_id: &str, request_id: &RequestId) -> String {
+    format!("{workspace_id}:{request_id}")
+}
+
+fn command_text(params: &Value) -> Option<String> {
+    match params.get("command")? {
+        Value::String(command) => Some(command.clone()),
+        Value::Array(parts) => Some(
+            parts
+                .iter()
+                .filter_map(|part| part.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        _ => None,
+    }
+}
+
+fn str_param(params: &Value, key: &str) -> Option<String> {
+    params
+        .get(key)
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+}
+
+/// Unanswered approval requests of every workspace, oldest first.
+#[derive(Debug, Default)]
+pub(crate) struct ApprovalInbox {
+    approvals: BTreeMap<(i64, String), InboxApproval>,
+}
+
+impl ApprovalInbox {
+    pub(crate) fn observe(&mut self, workspace_id: &str, message: &Value, now: i64) {
+        let Some(method) = message.get("method").and_then(|value| value.as_str()) else {
+            return;
+        };
+        let Some(params) = message.get("params") else {
+            return;
+        };
+        if method.ends_with("requestApproval") {
+            let (Some(request_id), Some(thread_id)) =
+                (RequestId::from_message(message), params_thread_id(params))
+            else {
+                return;
+            };
+            self.resolve(workspace_id, &request_id);
+            let approval = InboxApproval {
+                id: approval_id(workspace_id, &request_id),
+                workspace_id: workspace_id.to_string(),
+                request_id,
+                thread_id,
+                turn_id: params_turn_id(params),
+                method: method.to_string(),
+                command: command_text(params),
+                cwd: str_param(params, "cwd"),
+                reason: str_param(params, "reason"),
+                received_at: now,
+            };
+            self.approvals.insert((now, approval.id.clone()), approval);
+        } else if method == "turn/completed" {
+            // Requests of a finished turn can no longer be answered.
+            let Some(thread_id) = params_thread_id(params) else {
+                return;
+            };
+            self.approvals.retain(|_, approval| {
+                !(approval.workspace_id == workspace_id && approval.thread_id == thread_id)
+            });
+        }
+    }
+
+    /// Forgets an answered request, returning when it was received.
+    pub(crate) fn resolve(&mut self, workspace_id: &str, request_id: &RequestId) -> Option<i64> {
+        let id = approval_id(workspace_id, request_id);
+        let key = self
+            .approvals
+            .iter()
+            .find(|(_, approval)| approval.id == id)
+            .map(|(key, _)| key.clone())?;
+        self.approvals
+            .remove(&key)
+            .map(|approval| approval.received_at)
+    }
+
+    pub(crate) fn get(&self, id: &str) -> Option<&InboxApproval> {
+        self.approvals.values().find(|approval| approval.id == id)
+    }
+
+    pub(crate) fn list(&self) -> Vec<InboxApproval> {
+        self.approvals.values().cloned().collect()
+    }
+
+    /// The workspace's request that has waited longest.
+    pub(crate) fn oldest(&self, workspace_id: &str) -> Option<&InboxApproval> {
+        self.approvals
+            .values()
+            .find(|approval| approval.workspace_id == workspace_id)
+    }
+}
+
+pub(crate) fn route(method: &str, path: &str) -> InboxRoute {
+    let path = path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["approvals"] if method == "GET" => InboxRoute::List,
+        ["approvals", id, "respond"] if !id.is_empty() && method == "POST" => {
+            InboxRoute::Respond(id.replace("%3A", ":").replace("%3a", ":"))
+        }
+        ["approvals"] | ["approvals", _, "respond"] => InboxRoute::MethodNotAllowed,
+        _ => InboxRoute::NotFound,
+    }
+}
+
+/// Checks `Authorization: Bearer <token>` without short-circuiting on the
+/// first differing byte.
+pub(crate) fn authorized(request: &HttpRequest, token: &str) -> bool {
+    let Some(provided) = request
+        .header("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    provided.trim().as_bytes().ct_eq(token.as_bytes()).into()
+}
+
+/// The app-server response for an `accept` or `decline` decision.
+pub(crate) fn decision_value(decision: &str) -> Result<Value, String> {
+    if !DECISIONS.contains(&decision) {
+        return Err(format!(
+            "Unsupported decision `{decision}`; use {}.",
+            DECISIONS.join(" or ")
+        ));
+    }
+    Ok(json!({ "decision": decision }))
+}
+
+/// The app-server response for a `{ "decision": ... }` body.
+pub(crate) fn decision_result(body: &[u8]) -> Result<Value, String> {
+    let body: RespondBody =
+        serde_json::from_slice(body).map_err(|err| format!("Invalid respond payload: {err}"))?;
+    decision_value(&body.decision)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(thread_id: &str, request_id: u64) -> Value {
+        json!({
+            "id": request_id,
+            "method": "item/commandExecution/requestApproval",
+            "params": {
+                "threadId": thread_id,
+                "turnId": "turn-1",
+                "command": ["cargo", "publish"],
+                "cwd": "/work/app",
+                "reason": "needs network"
+            }
+        })
+    }
+
+    #[test]
+    fn tracks_pending_approvals() {
+        let mut inbox = ApprovalInbox::default();
+        inbox.observe("ws", &request("t-1", 7), 1);
+        inbox.observe("ws", &request("t-2", 8), 2);
+        inbox.observe("ws", &json!({ "method": "turn/started", "params": {} }), 3);
+        let approvals = inbox.list();
+        assert_eq!(approvals.len(), 2);
+        assert_eq!(approvals[0].id, "ws:7");
+        assert_eq!(approvals[0].command.as_deref(), Some("cargo publish"));
+        assert_eq!(
+            inbox
+                .get("ws:8")
+                .map(|approval| approval.request_id.clone()),
+            Some(RequestId::Number(8))
+        );
+
+        inbox.observe("other", &request("t-3", 2), 3);
+        assert_eq!(
+            inbox
+                .oldest("ws")
+                .map(|approval| approval.request_id.clone()),
+            Some(RequestId::Number(7))
+        );
+        assert_eq!(
+            inbox
+                .oldest("other")
+                .map(|approval| approval.request_id.clone()),
+            Some(RequestId::Number(2))
+        );
+
+        inbox.resolve("ws", &RequestId::Number(7));
+        inbox.resolve("other", &RequestId::Number(2));
+        inbox.observe(
+            "ws",
+            &json!({ "method": "turn/completed", "params": { "threadId": "t-2" } }),
+            4,
+        );
+        assert!(inbox.list().is_empty());
+    }
+
+    #[test]
+    fn routes_authorizes_and_parses_decisions() {
+        assert_eq!(route("GET", "/approvals"), InboxRoute::List);
+        assert_eq!(
+            route("POST", "/approvals/ws:7/respond"),
+            InboxRoute::Respond("ws:7".to_string())
+        );
+        assert_eq!(
+            route("POST", "/approvals/ws%3A7/respond?source=slack"),
+            InboxRoute::Respond("ws:7".to_string())
+        );
+        assert_eq!(
+            route("GET", "/approvals/ws:7/respond"),
+            InboxRoute::MethodNotAllowed
+        );
+        assert_eq!(route("GET", "/workspaces"), InboxRoute::NotFound);
+
+        let mut request = HttpRequest {
+            method: "GET".to_string(),
+            path: "/approvals".to_string(),
+            headers: vec![("Authorization".to_string(), "Bearer s3cret".to_string())],
+            body: Vec::new(),
+        };
+        assert!(authorized(&request, "s3cret"));
+        assert!(!authorized(&request, "s3cret2"));
+        request.headers.clear();
+        assert!(!authorized(&request, "s3cret"));
+
+        assert_eq!(
+            decision_result(br#"{"decision":"accept"}"#).unwrap(),
+            json!({ "decision": "accept" })
+        );
+        assert!(decision_result(br#"{"decision":"acceptForSession"}"#).is_err());
+        assert!(decision_result(b"nope").is_err());
+    }
+}
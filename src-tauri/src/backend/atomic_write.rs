@@ -0,0 +1,215 @@
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+/// A change to one workspace file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FileOperation {
+    Write(String),
+    Remove,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum FileWriteStage {
+    Backup,
+    Write,
+    Remove,
+    /// Undoing an earlier file after a later one failed.
+    Restore,
+}
+
+/// Why one file of a batch could not be written.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileWriteError {
+    pub(crate) path: String,
+    pub(crate) stage: FileWriteStage,
+    pub(crate) message: String,
+}
+
+/// Original copies of files changed this session, removed on the next start.
+pub(crate) fn backups_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("patch_backups")
+}
+
+pub(crate) fn clear_backups(data_dir: &Path) {
+    let dir = backups_dir(data_dir);
+    if dir.exists() {
+        if let Err(err) = fs::remove_dir_all(&dir) {
+            eprintln!("Failed to clear patch backups: {err}");
+        }
+    }
+}
+
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        File::open(dir)?.sync_all()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = dir;
+        Ok(())
+    }
+}
+
+/// Writes `contents` to a temp file next to `path`, syncs it and renames it
+/// over `path`, so readers see either the old or the new file. Existing
+/// permissions are kept.
+pub(crate) fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp = dir.join(format!(".{name}.{}.tmp", Uuid::new_v4()));
+    let result = (|| {
+        let mut file = File::create(&temp)?;
+        file.write_all(contents)?;
+        if let Ok(metadata) = fs::metadata(path) {
+            file.set_permissions(metadata.permissions())?;
+        }
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp, path)?;
+        sync_dir(dir)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+fn remove_synced(path: &Path) -> io::Result<()> {
+    fs::remove_file(path)?;
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => sync_dir(dir),
+        _ => Ok(()),
+    }
+}
+
+fn error(path: &str, stage: FileWriteStage, err: impl ToString) -> FileWriteError {
+    FileWriteError {
+        path: path.to_string(),
+        stage,
+        message: err.to_string(),
+    }
+}
+
+/// Applies file operations as one unit. Every original is first copied to
+/// `backup_dir` as `<path>.orig` (the first copy of the session is kept);
+/// if any write fails, files already changed are restored, so the tree is
+/// never left half-written. `files` pairs a display path, relative to the
+/// workspace, with the absolute path and operation.
+pub(crate) fn apply_operations(
+    files: &[(String, PathBuf, FileOperation)],
+    backup_dir: &Path,
+) -> Result<(), Vec<FileWriteError>> {
+    let mut originals: Vec<Option<Vec<u8>>> = Vec::with_capacity(files.len());
+    for (display, path, _) in files {
+        let original = match fs::read(path) {
+            Ok(bytes) => Some(bytes),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(vec![error(display, FileWriteStage::Backup, err)]),
+        };
+        if let Some(bytes) = &original {
+            let backup = backup_dir.join(format!("{display}.orig"));
+            if !backup.exists() {
+                write_atomic(&backup, bytes)
+                    .map_err(|err| vec![error(display, FileWriteStage::Backup, err)])?;
+            }
+        }
+        originals.push(original);
+    }
+
+    for (index, (display, path, operation)) in files.iter().enumerate() {
+        let result = match operation {
+            FileOperation::Write(contents) => write_atomic(path, contents.as_bytes())
+                .map_err(|err| error(display, FileWriteStage::Write, err)),
+            FileOperation::Remove => {
+                remove_synced(path).map_err(|err| error(display, FileWriteStage::Remove, err))
+            }
+        };
+        let Err(failure) = result else {
+            continue;
+        };
+        let mut errors = vec![failure];
+        for ((display, path, _), original) in files[..index].iter().zip(&originals).rev() {
+            let restored = match original {
+                Some(bytes) => write_atomic(path, bytes),
+                None => remove_synced(path),
+            };
+            if let Err(err) = restored {
+                errors.push(error(display, FileWriteStage::Restore, err));
+            }
+        }
+        return Err(errors);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backs_up_originals_and_rolls_back_on_failure() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let backups = dir.join("backups");
+        let root = dir.join("ws");
+        fs::create_dir_all(&root).expect("root");
+        fs::write(root.join("a.txt"), "old a\n").expect("a");
+
+        let ok = vec![
+            (
+                "a.txt".to_string(),
+                root.join("a.txt"),
+                FileOperation::Write("new a\n".to_string()),
+            ),
+            (
+                "src/b.txt".to_string(),
+                root.join("src/b.txt"),
+                FileOperation::Write("b\n".to_string()),
+            ),
+        ];
+        apply_operations(&ok, &backups).expect("apply");
+        assert_eq!(fs::read_to_string(root.join("a.txt")).unwrap(), "new a\n");
+        assert_eq!(fs::read_to_string(root.join("src/b.txt")).unwrap(), "b\n");
+        assert_eq!(
+            fs::read_to_string(backups.join("a.txt.orig")).unwrap(),
+            "old a\n"
+        );
+
+        // Removing a missing file fails after `a.txt` was rewritten.
+        let failing = vec![
+            (
+                "a.txt".to_string(),
+                root.join("a.txt"),
+                FileOperation::Write("newer a\n".to_string()),
+            ),
+            (
+                "missing.txt".to_string(),
+                root.join("missing.txt"),
+                FileOperation::Remove,
+            ),
+        ];
+        let errors = apply_operations(&failing, &backups).expect_err("fails");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "missing.txt");
+        assert_eq!(errors[0].stage, FileWriteStage::Remove);
+        assert_eq!(fs::read_to_string(root.join("a.txt")).unwrap(), "new a\n");
+        assert_eq!(
+            fs::read_to_string(backups.join("a.txt.orig")).unwrap(),
+            "old a\n"
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,184 @@
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+/// Error texts of expired or revoked ChatGPT sign-ins, lowercase, for
+/// servers that do not report `codexErrorInfo`.
+const AUTH_EXPIRED_PATTERNS: [&str; 7] = [
+    "401 unauthorized",
+    "token_expired",
+    "token is expired",
+    "token has expired",
+    "refresh token",
+    "log in again",
+    "sign in again",
+];
+
+fn error_payload(message: &Value) -> Option<&Value> {
+    let params = message.get("params")?;
+    match message.get("method").and_then(|value| value.as_str())? {
+        "error" => params.get("error"),
+        "turn/completed" => params.get("turn").and_then(|turn| turn.get("error")),
+        "codex/event/error" | "codex/event/stream_error" => params.get("msg"),
+        _ => None,
+    }
+}
+
+fn is_unauthorized(info: &Value) -> bool {
+    match info {
+        Value::String(kind) => kind == "unauthorized",
+        Value::Object(kinds) => kinds.contains_key("unauthorized"),
+        _ => false,
+    }
+}
+
+/// Whether an app-server notification reports that the account's sign-in
+/// expired, from `codexErrorInfo` or else the error text.
+pub(crate) fn is_auth_expired(message: &Value) -> bool {
+    let Some(error) = error_payload(message) else {
+        return false;
+    };
+    if error.get("codexErrorInfo").is_some_and(is_unauthorized) {
+        return true;
+    }
+    let text = error
+        .get("message")
+        .and_then(|value| value.as_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    AUTH_EXPIRED_PATTERNS
+        .iter()
+        .any(|pattern| text.contains(pattern))
+}
+
+/// Whether a notification reports a completed login or a signed-in account.
+pub(crate) fn is_auth_restored(message: &Value) -> bool {
+    let params = message.get("params");
+    match message.get("method").and_then(|value| value.as_str()) {
+        Some("account/login/completed") => params
+            .and_then(|params| params.get("success"))
+            .and_then(|value| value.as_bool())
+            .unwrap_or(true),
+        Some("account/updated") => params
+            .and_then(|params| params.get("authMode"))
+            .is_some_and(|mode| !mode.is_null()),
+        _ => false,
+    }
+}
+
+/// Workspaces whose sign-in expired.
+#[derive(Default)]
+pub(crate) struct AuthExpiryTracker {
+    expired: Mutex<HashSet<String>>,
+    restored: Notify,
+}
+
+impl AuthExpiryTracker {
+    /// Returns the workspaces not already marked.
+    pub(crate) fn mark_expired(&self, workspace_ids: &[String]) -> Vec<String> {
+        let Ok(mut expired) = self.expired.lock() else {
+            return Vec::new();
+        };
+        workspace_ids
+            .iter()
+            .filter(|workspace_id| expired.insert((*workspace_id).clone()))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the workspaces that were marked.
+    pub(crate) fn clear(&self, workspace_ids: &[String]) -> Vec<String> {
+        let cleared: Vec<String> = match self.expired.lock() {
+            Ok(mut expired) => workspace_ids
+                .iter()
+                .filter(|workspace_id| expired.remove(*workspace_id))
+                .cloned()
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        if !cleared.is_empty() {
+            self.restored.notify_waiters();
+        }
+        cleared
+    }
+
+    pub(crate) fn is_expired(&self, workspace_id: &str) -> bool {
+        self.expired
+            .lock()
+            .is_ok_and(|expired| expired.contains(workspace_id))
+    }
+
+    pub(crate) fn expired_workspaces(&self) -> Vec<String> {
+        let mut workspace_ids: Vec<String> = self
+            .expired
+            .lock()
+            .map(|expired| expired.iter().cloned().collect())
+            .unwrap_or_default();
+        workspace_ids.sort();
+        workspace_ids
+    }
+
+    /// Resolves when any workspace's sign-in is restored.
+    pub(crate) async fn restored(&self) {
+        self.restored.notified().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn detects_expired_and_restored_sign_ins() {
+        assert!(is_auth_expired(&json!({
+            "method": "error",
+            "params": { "error": { "message": "stream failed", "codexErrorInfo": "unauthorized" } }
+        })));
+        assert!(is_auth_expired(&json!({
+            "method": "turn/completed",
+            "params": { "turn": { "status": "failed", "error": {
+                "message": "Your access token could not be refreshed. Please log in again."
+            } } }
+        })));
+        assert!(!is_auth_expired(&json!({
+            "method": "error",
+            "params": { "error": { "message": "stream disconnected", "codexErrorInfo": {
+                "httpConnectionFailed": { "httpStatusCode": 502 }
+            } } }
+        })));
+        assert!(!is_auth_expired(&json!({
+            "method": "item/completed",
+            "params": { "error": { "message": "401 Unauthorized" } }
+        })));
+
+        assert!(is_auth_restored(&json!({
+            "method": "account/login/completed",
+            "params": { "loginId": "a", "success": true }
+        })));
+        assert!(!is_auth_restored(&json!({
+            "method": "account/login/completed",
+            "params": { "loginId": "a", "success": false }
+        })));
+        assert!(!is_auth_restored(&json!({
+            "method": "account/updated",
+            "params": { "authMode": null }
+        })));
+    }
+
+    #[test]
+    fn tracks_each_workspace_once() {
+        let tracker = AuthExpiryTracker::default();
+        let both = ["a".to_string(), "b".to_string()];
+        assert_eq!(tracker.mark_expired(&both[..1]), vec!["a".to_string()]);
+        assert_eq!(tracker.mark_expired(&both), vec!["b".to_string()]);
+        assert!(tracker.is_expired("a"));
+        assert_eq!(tracker.expired_workspaces(), both.to_vec());
+        assert_eq!(
+            tracker.clear(&["b".to_string(), "c".to_string()]),
+            vec!["b".to_string()]
+        );
+        assert_eq!(tracker.expired_workspaces(), vec!["a".to_string()]);
+    }
+}
@@ -0,0 +1,87 @@
+use serde_json::Value;
+
+/// Follows the events of a background turn to its final agent message.
+#[derive(Debug, Default)]
+pub(crate) struct AnswerCollector {
+    streamed: String,
+    final_message: Option<String>,
+}
+
+impl AnswerCollector {
+    /// Returns the answer, or the error, once the turn is over.
+    pub(crate) fn observe(&mut self, event: &Value) -> Option<Result<String, String>> {
+        let method = event.get("method").and_then(|value| value.as_str())?;
+        let params = event.get("params");
+        match method {
+            "item/agentMessage/delta" => {
+                if let Some(delta) = params
+                    .and_then(|params| params.get("delta"))
+                    .and_then(|value| value.as_str())
+                {
+                    self.streamed.push_str(delta);
+                }
+                None
+            }
+            "item/completed" => {
+                let item = params.and_then(|params| params.get("item"))?;
+                if item.get("type").and_then(|value| value.as_str()) == Some("agentMessage") {
+                    if let Some(text) = item.get("text").and_then(|value| value.as_str()) {
+                        self.final_message = Some(text.to_string());
+                        self.streamed.clear();
+                    }
+                }
+                None
+            }
+            "turn/completed" => {
+                let answer = self
+                    .final_message
+                    .take()
+                    .unwrap_or_else(|| std::mem::take(&mut self.streamed));
+                let answer = answer.trim();
+                Some(if answer.is_empty() {
+                    Err("No answer was returned.".to_string())
+                } else {
+                    Ok(answer.to_string())
+                })
+            }
+            "turn/error" => Some(Err(params
+                .and_then(|params| params.get("error"))
+                .and_then(|value| value.as_str())
+                .unwrap_or("The background turn failed.")
+                .to_string())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn collects_the_final_agent_message() {
+        let mut collector = AnswerCollector::default();
+        let events = [
+            json!({ "method": "turn/started", "params": { "threadId": "t" } }),
+            json!({ "method": "item/agentMessage/delta", "params": { "delta": "Looking" } }),
+            json!({
+                "method": "item/completed",
+                "params": { "item": { "type": "agentMessage", "text": " It reads the file. " } }
+            }),
+        ];
+        for event in &events {
+            assert_eq!(collector.observe(event), None);
+        }
+        assert_eq!(
+            collector.observe(&json!({ "method": "turn/completed", "params": {} })),
+            Some(Ok("It reads the file.".to_string()))
+        );
+
+        let mut collector = AnswerCollector::default();
+        assert_eq!(
+            collector.observe(&json!({ "method": "turn/completed", "params": {} })),
+            Some(Err("No answer was returned.".to_string()))
+        );
+    }
+}
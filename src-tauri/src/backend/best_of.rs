@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::backend::dry_run::ProposedPatch;
+use crate::backend::verification::VerificationOutcome;
+
+/// Upper bound on parallel attempts of one best-of run.
+pub(crate) const MAX_ATTEMPTS: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BestOfAttempt {
+    pub(crate) index: usize,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BestOfResult {
+    #[serde(flatten)]
+    pub(crate) attempt: BestOfAttempt,
+    /// 1 for the best attempt.
+    pub(crate) rank: usize,
+    pub(crate) files_changed: usize,
+    pub(crate) lines_added: usize,
+    pub(crate) lines_removed: usize,
+    pub(crate) patch: String,
+    pub(crate) verification: Option<VerificationOutcome>,
+}
+
+/// Emitted as `best-of-completed` once every attempt finished and was verified.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BestOfCompleted {
+    pub(crate) run_id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) prompt: String,
+    pub(crate) verify_command: Option<String>,
+    /// Ordered by rank.
+    pub(crate) results: Vec<BestOfResult>,
+}
+
+/// A run whose attempts have all finished, ready to be verified and ranked.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FinishedBestOf {
+    pub(crate) run_id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) prompt: String,
+    pub(crate) verify_command: Option<String>,
+    pub(crate) attempts: Vec<(BestOfAttempt, ProposedPatch)>,
+}
+
+struct PendingBestOf {
+    workspace_id: String,
+    prompt: String,
+    verify_command: Option<String>,
+    attempts: Vec<BestOfAttempt>,
+    patches: Vec<Option<ProposedPatch>>,
+}
+
+/// Tracks running best-of runs and completes them from the dry-run patches
+/// of their threads.
+#[derive(Default)]
+pub(crate) struct BestOfTracker {
+    runs: HashMap<String, PendingBestOf>,
+    by_thread: HashMap<(String, String), String>,
+}
+
+impl BestOfTracker {
+    pub(crate) fn register(
+        &mut self,
+        run_id: &str,
+        workspace_id: &str,
+        prompt: &str,
+        verify_command: Option<String>,
+        attempts: Vec<BestOfAttempt>,
+    ) {
+        for attempt in &attempts {
+            self.by_thread.insert(
+                (workspace_id.to_string(), attempt.thread_id.clone()),
+                run_id.to_string(),
+            );
+        }
+        self.runs.insert(
+            run_id.to_string(),
+            PendingBestOf {
+                workspace_id: workspace_id.to_string(),
+                prompt: prompt.to_string(),
+                verify_command,
+                patches: vec![None; attempts.len()],
+                attempts,
+            },
+        );
+    }
+
+    /// Drops a run whose turns could not all be started.
+    pub(crate) fn cancel(&mut self, run_id: &str) {
+        if self.runs.remove(run_id).is_some() {
+            self.by_thread.retain(|_, id| id != run_id);
+        }
+    }
+
+    /// Records the completed dry-run patch of an attempt; returns the run once
+    /// its last attempt finished.
+    pub(crate) fn observe_patch(&mut self, patch: &ProposedPatch) -> Option<FinishedBestOf> {
+        let key = (patch.workspace_id.clone(), patch.thread_id.clone());
+        let run_id = self.by_thread.get(&key)?.clone();
+        let run = self.runs.get_mut(&run_id)?;
+        let index = run
+            .attempts
+            .iter()
+            .position(|attempt| attempt.thread_id == patch.thread_id)?;
+        run.attempts[index].turn_id = Some(patch.turn_id.clone());
+        run.patches[index] = Some(patch.clone());
+        self.by_thread.remove(&key);
+        if run.patches.iter().any(|patch| patch.is_none()) {
+            return None;
+        }
+        let run = self.runs.remove(&run_id)?;
+        Some(FinishedBestOf {
+            run_id,
+            workspace_id: run.workspace_id,
+            prompt: run.prompt,
+            verify_command: run.verify_command,
+            attempts: run
+                .attempts
+                .into_iter()
+                .zip(run.patches.into_iter().flatten())
+                .collect(),
+        })
+    }
+}
+
+/// Counts added and removed lines of a unified diff.
+pub(crate) fn diff_line_counts(patch: &str) -> (usize, usize) {
+    patch.lines().fold((0, 0), |(added, removed), line| {
+        if line.starts_with("+++") || line.starts_with("---") {
+            (added, removed)
+        } else if line.starts_with('+') {
+            (added + 1, removed)
+        } else if line.starts_with('-') {
+            (added, removed + 1)
+        } else {
+            (added, removed)
+        }
+    })
+}
+
+pub(crate) fn build_result(
+    attempt: BestOfAttempt,
+    patch: &ProposedPatch,
+    verification: Option<VerificationOutcome>,
+) -> BestOfResult {
+    let (lines_added, lines_removed) = diff_line_counts(&patch.patch);
+    BestOfResult {
+        attempt,
+        rank: 0,
+        files_changed: patch.files.len(),
+        lines_added,
+        lines_removed,
+        patch: patch.patch.clone(),
+        verification,
+    }
+}
+
+/// Orders attempts best first: passing verification beats unverified beats
+/// failing, an attempt that changed something beats one that did not, and
+/// smaller diffs win ties.
+pub(crate) fn rank_results(results: &mut [BestOfResult]) {
+    results.sort_by_key(|result| {
+        let verification = match &result.verification {
+            Some(outcome) if outcome.passed => 0,
+            None => 1,
+            Some(_) => 2,
+        };
+        (
+            verification,
+            result.files_changed == 0,
+            result.lines_added + result.lines_removed,
+            result.attempt.index,
+        )
+    });
+    for (position, result) in results.iter_mut().enumerate() {
+        result.rank = position + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::dry_run::ProposedFileChange;
+
+    fn attempt(index: usize) -> BestOfAttempt {
+        BestOfAttempt {
+            index,
+            thread_id: format!("t-{index}"),
+            turn_id: None,
+        }
+    }
+
+    fn patch(thread_id: &str, diff: &str) -> ProposedPatch {
+        ProposedPatch {
+            workspace_id: "ws".to_string(),
+            thread_id: thread_id.to_string(),
+            turn_id: format!("turn-{thread_id}"),
+            files: if diff.is_empty() {
+                Vec::new()
+            } else {
+                vec![ProposedFileChange {
+                    item_id: "item".to_string(),
+                    path: "src/lib.rs".to_string(),
+                    kind: None,
+                    diff: diff.to_string(),
+                }]
+            },
+            completed: true,
+            patch: diff.to_string(),
+        }
+    }
+
+    fn outcome(passed: bool) -> Option<VerificationOutcome> {
+        Some(VerificationOutcome {
+            passed,
+            exit_code: Some(if passed { 0 } else { 1 }),
+            timed_out: false,
+            output: String::new(),
+        })
+    }
+
+    #[test]
+    fn completes_once_every_attempt_has_a_patch() {
+        let mut tracker = BestOfTracker::default();
+        tracker.register("best-1", "ws", "Fix it", None, vec![attempt(0), attempt(1)]);
+        assert!(tracker.observe_patch(&patch("t-1", "+a\n")).is_none());
+        assert!(tracker.observe_patch(&patch("t-9", "+a\n")).is_none());
+        let done = tracker
+            .observe_patch(&patch("t-0", "+b\n"))
+            .expect("finished");
+        assert_eq!(done.run_id, "best-1");
+        assert_eq!(done.attempts[0].0.turn_id.as_deref(), Some("turn-t-0"));
+        assert_eq!(done.attempts[1].1.patch, "+a\n");
+        assert!(tracker.observe_patch(&patch("t-0", "+b\n")).is_none());
+
+        tracker.register("best-2", "ws", "Fix it", None, vec![attempt(0)]);
+        tracker.cancel("best-2");
+        assert!(tracker.by_thread.is_empty());
+    }
+
+    #[test]
+    fn ranks_passing_and_smaller_attempts_first() {
+        let mut results = vec![
+            build_result(attempt(0), &patch("t-0", "+a\n+b\n-c\n"), outcome(true)),
+            build_result(attempt(1), &patch("t-1", "+a\n"), outcome(false)),
+            build_result(attempt(2), &patch("t-2", "+a\n"), outcome(true)),
+            build_result(attempt(3), &patch("t-3", ""), outcome(true)),
+        ];
+        assert_eq!((results[0].lines_added, results[0].lines_removed), (2, 1));
+        rank_results(&mut results);
+        let order: Vec<usize> = results.iter().map(|result| result.attempt.index).collect();
+        assert_eq!(order, vec![2, 0, 3, 1]);
+        assert_eq!(results[0].rank, 1);
+        assert_eq!(results[3].rank, 4);
+    }
+}
@@ -0,0 +1,367 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Whole-file blames kept per workspace, least recently used dropped first.
+const MAX_CACHED_FILES: usize = 32;
+/// Commits listed per region, most recent first.
+const MAX_COMMITS: usize = 10;
+
+/// Lines `start` to `end`, 1-based and inclusive.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LineRange {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CommitInfo {
+    pub(crate) sha: String,
+    pub(crate) author: String,
+    /// Seconds since the epoch, like `GitLogEntry`.
+    pub(crate) timestamp: i64,
+    pub(crate) message: String,
+}
+
+/// Blame of a file's working copy: the commit of each line, `None` for
+/// lines with uncommitted changes.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct FileBlame {
+    pub(crate) lines: Vec<Option<String>>,
+    pub(crate) commits: HashMap<String, CommitInfo>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BlameCommit {
+    pub(crate) sha: String,
+    pub(crate) author: String,
+    pub(crate) timestamp: i64,
+    pub(crate) summary: String,
+    pub(crate) message: String,
+    /// Lines of the region this commit last changed.
+    pub(crate) lines: Vec<LineRange>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BlameContext {
+    pub(crate) path: String,
+    /// The requested range, clamped to the file.
+    pub(crate) range: LineRange,
+    pub(crate) head: Option<String>,
+    pub(crate) commits: Vec<BlameCommit>,
+    pub(crate) uncommitted_lines: Vec<LineRange>,
+    pub(crate) cached: bool,
+    /// The history as plain text, ready to add to a prompt.
+    pub(crate) text: String,
+}
+
+/// What a cached blame is valid for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BlameFingerprint {
+    pub(crate) head: Option<String>,
+    pub(crate) content_hash: String,
+}
+
+impl BlameFingerprint {
+    pub(crate) fn new(head: Option<String>, content: &[u8]) -> Self {
+        let content_hash = Sha256::digest(content)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        Self { head, content_hash }
+    }
+}
+
+#[derive(Debug)]
+struct CachedBlame {
+    path: String,
+    fingerprint: BlameFingerprint,
+    blame: FileBlame,
+}
+
+/// Blames by workspace and repository-relative path, reused while `HEAD`
+/// and the file's content are unchanged.
+#[derive(Debug, Default)]
+pub(crate) struct BlameCache {
+    workspaces: HashMap<String, Vec<CachedBlame>>,
+}
+
+impl BlameCache {
+    pub(crate) fn get(
+        &mut self,
+        workspace_id: &str,
+        path: &str,
+        fingerprint: &BlameFingerprint,
+    ) -> Option<&FileBlame> {
+        let cached = self.workspaces.get_mut(workspace_id)?;
+        let index = cached
+            .iter()
+            .position(|entry| entry.path == path && &entry.fingerprint == fingerprint)?;
+        let entry = cached.remove(index);
+        cached.push(entry);
+        cached.last().map(|entry| &entry.blame)
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        workspace_id: &str,
+        path: &str,
+        fingerprint: BlameFingerprint,
+        blame: FileBlame,
+    ) {
+        let cached = self.workspaces.entry(workspace_id.to_string()).or_default();
+        cached.retain(|entry| entry.path != path);
+        cached.push(CachedBlame {
+            path: path.to_string(),
+            fingerprint,
+            blame,
+        });
+        if cached.len() > MAX_CACHED_FILES {
+            cached.remove(0);
+        }
+    }
+}
+
+/// Collapses ascending line numbers into ranges.
+fn line_ranges(lines: &[usize]) -> Vec<LineRange> {
+    let mut ranges: Vec<LineRange> = Vec::new();
+    for &line in lines {
+        match ranges.last_mut() {
+            Some(range) if range.end + 1 == line => range.end = line,
+            _ => ranges.push(LineRange {
+                start: line,
+                end: line,
+            }),
+        }
+    }
+    ranges
+}
+
+fn format_ranges(ranges: &[LineRange]) -> String {
+    ranges
+        .iter()
+        .map(|range| {
+            if range.start == range.end {
+                range.start.to_string()
+            } else {
+                format!("{}-{}", range.start, range.end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn context_text(
+    path: &str,
+    range: LineRange,
+    commits: &[BlameCommit],
+    uncommitted: &[LineRange],
+) -> String {
+    let mut text = format!(
+        "Git history of {path}, lines {}:\n",
+        format_ranges(&[range])
+    );
+    for commit in commits {
+        let day = chrono::DateTime::from_timestamp(commit.timestamp, 0)
+            .map(|time| time.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let short: String = commit.sha.chars().take(8).collect();
+        text.push_str(&format!(
+            "- {short} by {} on {day}: {}\n",
+            commit.author, commit.summary
+        ));
+        let body = commit
+            .message
+            .lines()
+            .skip(1)
+            .skip_while(|line| line.trim().is_empty());
+        for line in body {
+            text.push_str(&format!("  {}\n", line.trim_end()));
+        }
+        text.push_str(&format!("  Lines {}\n", format_ranges(&commit.lines)));
+    }
+    if !uncommitted.is_empty() {
+        text.push_str(&format!(
+            "- Uncommitted changes: lines {}\n",
+            format_ranges(uncommitted)
+        ));
+    }
+    text
+}
+
+/// The commits behind lines `range` of `blame`, most recent first.
+pub(crate) fn context(
+    path: &str,
+    blame: &FileBlame,
+    range: LineRange,
+    head: Option<String>,
+    cached: bool,
+) -> Result<BlameContext, String> {
+    let line_count = blame.lines.len();
+    if range.start == 0 || range.end < range.start || range.start > line_count {
+        return Err(format!(
+            "Lines {} are outside {path}, which has {line_count} lines.",
+            format_ranges(&[range])
+        ));
+    }
+    let range = LineRange {
+        start: range.start,
+        end: range.end.min(line_count),
+    };
+    let mut by_commit: HashMap<&str, Vec<usize>> = HashMap::new();
+    let mut uncommitted = Vec::new();
+    for line in range.start..=range.end {
+        match &blame.lines[line - 1] {
+            Some(sha) => by_commit.entry(sha.as_str()).or_default().push(line),
+            None => uncommitted.push(line),
+        }
+    }
+    let mut commits: Vec<BlameCommit> = by_commit
+        .into_iter()
+        .filter_map(|(sha, lines)| {
+            let info = blame.commits.get(sha)?;
+            Some(BlameCommit {
+                sha: info.sha.clone(),
+                author: info.author.clone(),
+                timestamp: info.timestamp,
+                summary: info
+                    .message
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+                message: info.message.trim().to_string(),
+                lines: line_ranges(&lines),
+            })
+        })
+        .collect();
+    commits.sort_by(|a, b| {
+        b.timestamp
+            .cmp(&a.timestamp)
+            .then_with(|| a.sha.cmp(&b.sha))
+    });
+    commits.truncate(MAX_COMMITS);
+    let uncommitted_lines = line_ranges(&uncommitted);
+    let text = context_text(path, range, &commits, &uncommitted_lines);
+    Ok(BlameContext {
+        path: path.to_string(),
+        range,
+        head,
+        commits,
+        uncommitted_lines,
+        cached,
+        text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blame() -> FileBlame {
+        let commit = |sha: &str, timestamp: i64, message: &str| CommitInfo {
+            sha: sha.to_string(),
+            author: "Ada".to_string(),
+            timestamp,
+            message: message.to_string(),
+        };
+        FileBlame {
+            lines: vec![
+                Some("aaaa1111".to_string()),
+                Some("bbbb2222".to_string()),
+                Some("bbbb2222".to_string()),
+                None,
+                Some("aaaa1111".to_string()),
+            ],
+            commits: HashMap::from([
+                (
+                    "aaaa1111".to_string(),
+                    commit("aaaa1111", 0, "Add parser\n"),
+                ),
+                (
+                    "bbbb2222".to_string(),
+                    commit(
+                        "bbbb2222",
+                        86_400,
+                        "Retry reads\n\nThe disk is flaky on CI.",
+                    ),
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn describes_the_commits_behind_a_region() {
+        let context = context(
+            "src/io.rs",
+            &blame(),
+            LineRange { start: 2, end: 9 },
+            Some("head".to_string()),
+            false,
+        )
+        .unwrap();
+        assert_eq!(context.range, LineRange { start: 2, end: 5 });
+        let shas: Vec<_> = context
+            .commits
+            .iter()
+            .map(|commit| commit.sha.as_str())
+            .collect();
+        assert_eq!(shas, ["bbbb2222", "aaaa1111"]);
+        assert_eq!(context.commits[0].summary, "Retry reads");
+        assert_eq!(context.commits[0].lines, [LineRange { start: 2, end: 3 }]);
+        assert_eq!(context.uncommitted_lines, [LineRange { start: 4, end: 4 }]);
+        assert_eq!(
+            context.text,
+            "Git history of src/io.rs, lines 2-5:\n\
+- bbbb2222 by Ada on 1970-01-02: Retry reads\n  The disk is flaky on CI.\n  Lines 2-3\n\
+- aaaa1111 by Ada on 1970-01-01: Add parser\n  Lines 5\n\
+- Uncommitted changes: lines 4\n"
+        );
+
+        assert!(super::context(
+            "src/io.rs",
+            &blame(),
+            LineRange { start: 6, end: 7 },
+            None,
+            false
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn cache_follows_head_and_content() {
+        let mut cache = BlameCache::default();
+        let fingerprint = BlameFingerprint::new(Some("head".to_string()), b"fn main() {}");
+        cache.insert("ws", "src/main.rs", fingerprint.clone(), blame());
+        assert!(cache.get("ws", "src/main.rs", &fingerprint).is_some());
+        assert!(cache
+            .get(
+                "ws",
+                "src/main.rs",
+                &BlameFingerprint::new(Some("next".to_string()), b"fn main() {}")
+            )
+            .is_none());
+        assert!(cache
+            .get(
+                "ws",
+                "src/main.rs",
+                &BlameFingerprint::new(Some("head".to_string()), b"edited")
+            )
+            .is_none());
+        assert!(cache.get("other", "src/main.rs", &fingerprint).is_none());
+
+        for index in 0..MAX_CACHED_FILES {
+            cache.insert(
+                "ws",
+                &format!("f{index}.rs"),
+                fingerprint.clone(),
+                FileBlame::default(),
+            );
+        }
+        assert!(cache.get("ws", "src/main.rs", &fingerprint).is_none());
+    }
+}
@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::backend::secure_storage::DataCipher;
+
+/// Drift between wall-clock and monotonic elapsed time above which the
+/// system clock is considered to have jumped.
+const JUMP_THRESHOLD_MS: i64 = 2_000;
+/// Clock jumps kept on disk, oldest dropped first.
+const MAX_CLOCK_JUMPS: usize = 500;
+
+/// A moment as both UTC wall-clock time and monotonic time. `mono_ms`
+/// counts from app start, so only pairs from the same run compare.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Timestamp {
+    pub(crate) utc_ms: i64,
+    pub(crate) mono_ms: u64,
+    /// Local UTC offset when stamped, for displaying the time as the user
+    /// saw it.
+    pub(crate) offset_minutes: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum JumpKind {
+    /// Wall-clock time moved ahead of monotonic time: the machine slept or
+    /// the clock was set forward.
+    Forward,
+    /// The clock was set back, e.g. by an NTP correction.
+    Backward,
+}
+
+/// A wall-clock jump between two consecutive stamps. Durations measured
+/// across `[before, after]` must not trust wall-clock time.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ClockJump {
+    pub(crate) before: Timestamp,
+    pub(crate) after: Timestamp,
+    pub(crate) kind: JumpKind,
+    /// Wall-clock minus monotonic elapsed time.
+    pub(crate) skew_ms: i64,
+}
+
+/// Time between two stamps of the same run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Interval {
+    /// Monotonic time, which does not advance while the machine sleeps.
+    pub(crate) elapsed_ms: u64,
+    /// Wall-clock minus monotonic time; non-zero beyond the threshold when
+    /// the clock jumped during the interval.
+    pub(crate) skew_ms: i64,
+}
+
+impl Interval {
+    pub(crate) fn between(start: Timestamp, end: Timestamp) -> Self {
+        let elapsed_ms = end.mono_ms.saturating_sub(start.mono_ms);
+        let wall_ms = end.utc_ms - start.utc_ms;
+        Self {
+            elapsed_ms,
+            skew_ms: wall_ms - i64::try_from(elapsed_ms).unwrap_or(i64::MAX),
+        }
+    }
+
+    pub(crate) fn clock_jumped(&self) -> bool {
+        self.skew_ms.abs() > JUMP_THRESHOLD_MS
+    }
+}
+
+/// Stamps moments and notices when the wall clock jumps between stamps.
+#[derive(Debug)]
+pub(crate) struct Clock {
+    origin: Instant,
+    last: Option<Timestamp>,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+            last: None,
+        }
+    }
+}
+
+impl Clock {
+    pub(crate) fn now(&mut self) -> (Timestamp, Option<ClockJump>) {
+        let local = chrono::Local::now();
+        let mono_ms = u64::try_from(self.origin.elapsed().as_millis()).unwrap_or(u64::MAX);
+        self.stamp(Timestamp {
+            utc_ms: local.timestamp_millis(),
+            mono_ms,
+            offset_minutes: local.offset().local_minus_utc() / 60,
+        })
+    }
+
+    /// Records `now` as the latest stamp; returns the jump since the
+    /// previous one, if any.
+    pub(crate) fn stamp(&mut self, now: Timestamp) -> (Timestamp, Option<ClockJump>) {
+        let jump = self.last.and_then(|before| {
+            let interval = Interval::between(before, now);
+            interval.clock_jumped().then_some(ClockJump {
+                before,
+                after: now,
+                kind: if interval.skew_ms > 0 {
+                    JumpKind::Forward
+                } else {
+                    JumpKind::Backward
+                },
+                skew_ms: interval.skew_ms,
+            })
+        });
+        self.last = Some(now);
+        (now, jump)
+    }
+}
+
+pub(crate) fn clock_jumps_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("clock_jumps.json")
+}
+
+pub(crate) fn read_clock_jumps(path: &Path, cipher: &DataCipher) -> Result<Vec<ClockJump>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn record_clock_jump(
+    path: &Path,
+    cipher: &DataCipher,
+    jump: ClockJump,
+) -> Result<(), String> {
+    let mut jumps = read_clock_jumps(path, cipher)?;
+    jumps.push(jump);
+    if jumps.len() > MAX_CLOCK_JUMPS {
+        let excess = jumps.len() - MAX_CLOCK_JUMPS;
+        jumps.drain(..excess);
+    }
+    let data = serde_json::to_string_pretty(&jumps).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(utc_ms: i64, mono_ms: u64) -> Timestamp {
+        Timestamp {
+            utc_ms,
+            mono_ms,
+            offset_minutes: 0,
+        }
+    }
+
+    #[test]
+    fn detects_sleep_and_backward_corrections() {
+        let mut clock = Clock::default();
+        assert_eq!(clock.stamp(at(10_000, 0)).1, None);
+        assert_eq!(clock.stamp(at(15_500, 5_000)).1, None);
+
+        // Eight hours of sleep while monotonic time stood still.
+        let (_, jump) = clock.stamp(at(15_500 + 8 * 3_600_000, 6_000));
+        let jump = jump.expect("sleep");
+        assert_eq!(jump.kind, JumpKind::Forward);
+        assert_eq!(jump.skew_ms, 8 * 3_600_000 - 1_000);
+
+        let (_, jump) = clock.stamp(at(15_500 + 8 * 3_600_000 - 60_000, 7_000));
+        assert_eq!(jump.expect("correction").kind, JumpKind::Backward);
+    }
+
+    #[test]
+    fn intervals_use_monotonic_time() {
+        let interval = Interval::between(at(0, 1_000), at(3_600_000, 61_000));
+        assert_eq!(interval.elapsed_ms, 60_000);
+        assert!(interval.clock_jumped());
+        assert!(!Interval::between(at(0, 0), at(60_500, 60_000)).clock_jumped());
+    }
+}
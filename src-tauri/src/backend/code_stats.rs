@@ -0,0 +1,313 @@
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+use crate::backend::turn_compare::diff_line_counts;
+
+/// Daily code stats files inside the analytics data class directory.
+const FILE_PREFIX: &str = "code-stats-";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LanguageStats {
+    pub(crate) files: usize,
+    pub(crate) added: usize,
+    pub(crate) removed: usize,
+}
+
+/// Lines changed by a turn, broken down by language and test vs. non-test code.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodeStats {
+    pub(crate) by_language: BTreeMap<String, LanguageStats>,
+    pub(crate) added: usize,
+    pub(crate) removed: usize,
+    pub(crate) test_files: usize,
+    pub(crate) test_lines_added: usize,
+    pub(crate) non_test_lines_added: usize,
+}
+
+/// Maps a path to a language name by file name or extension.
+pub(crate) fn language_for_path(path: &str) -> &'static str {
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    match file_name {
+        "Dockerfile" => return "Dockerfile",
+        "Makefile" | "makefile" => return "Makefile",
+        "Cargo.lock" | "package-lock.json" | "yarn.lock" | "pnpm-lock.yaml" => return "Lockfile",
+        _ => {}
+    }
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "rs" => "Rust",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "py" | "pyi" => "Python",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "swift" => "Swift",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "C++",
+        "cs" => "C#",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "scala" => "Scala",
+        "sh" | "bash" | "zsh" => "Shell",
+        "sql" => "SQL",
+        "html" | "htm" => "HTML",
+        "css" | "scss" | "sass" | "less" => "CSS",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "md" | "mdx" | "rst" => "Markdown",
+        "json" => "JSON",
+        "yaml" | "yml" => "YAML",
+        "toml" => "TOML",
+        "xml" => "XML",
+        _ => "Other",
+    }
+}
+
+/// Heuristic for test code: test directories and the usual test file names.
+pub(crate) fn is_test_path(path: &str) -> bool {
+    let normalized = path.replace('\\', "/").to_ascii_lowercase();
+    let in_test_dir = normalized.split('/').any(|segment| {
+        matches!(
+            segment,
+            "test" | "tests" | "__tests__" | "spec" | "specs" | "testdata" | "fixtures"
+        )
+    });
+    if in_test_dir {
+        return true;
+    }
+    let file_name = normalized.rsplit('/').next().unwrap_or(&normalized);
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    file_name.contains(".test.")
+        || file_name.contains(".spec.")
+        || stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with("_spec")
+        || (stem.ends_with("test") && file_name.ends_with(".java"))
+}
+
+impl CodeStats {
+    /// Builds stats from `(path, unified diff)` pairs; a path changed several
+    /// times counts as one file.
+    pub(crate) fn from_changes<'a>(changes: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut stats = CodeStats::default();
+        let mut seen: HashSet<&str> = HashSet::new();
+        for (path, diff) in changes {
+            let (added, removed) = diff_line_counts(diff);
+            let first_time = seen.insert(path);
+            let language = stats
+                .by_language
+                .entry(language_for_path(path).to_string())
+                .or_default();
+            if first_time {
+                language.files += 1;
+            }
+            language.added += added;
+            language.removed += removed;
+            stats.added += added;
+            stats.removed += removed;
+            if is_test_path(path) {
+                if first_time {
+                    stats.test_files += 1;
+                }
+                stats.test_lines_added += added;
+            } else {
+                stats.non_test_lines_added += added;
+            }
+        }
+        stats
+    }
+
+    /// Folds another turn's stats into this one.
+    pub(crate) fn merge(&mut self, other: &CodeStats) {
+        for (language, stats) in &other.by_language {
+            let entry = self.by_language.entry(language.clone()).or_default();
+            entry.files += stats.files;
+            entry.added += stats.added;
+            entry.removed += stats.removed;
+        }
+        self.added += other.added;
+        self.removed += other.removed;
+        self.test_files += other.test_files;
+        self.test_lines_added += other.test_lines_added;
+        self.non_test_lines_added += other.non_test_lines_added;
+    }
+}
+
+/// Code stats of one completed turn, stored in the analytics data class.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnCodeStats {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: Option<String>,
+    pub(crate) recorded_at: i64,
+    pub(crate) stats: CodeStats,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodeStatsReport {
+    pub(crate) days: u32,
+    pub(crate) turns: usize,
+    pub(crate) stats: CodeStats,
+}
+
+pub(crate) fn analytics_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("analytics")
+}
+
+/// One file per day so the analytics retention window applies per day.
+fn day_file(data_dir: &Path, day: NaiveDate) -> PathBuf {
+    analytics_dir(data_dir).join(format!("{FILE_PREFIX}{}.json", day.format("%Y-%m-%d")))
+}
+
+/// Code stats files, which follow the encryption setting.
+pub(crate) fn code_stats_files(data_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(analytics_dir(data_dir)) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(FILE_PREFIX))
+        })
+        .collect()
+}
+
+fn read_day(path: &Path, cipher: &DataCipher) -> Result<Vec<TurnCodeStats>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn record_turn_stats(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    entry: TurnCodeStats,
+) -> Result<(), String> {
+    let day = chrono::DateTime::from_timestamp_millis(entry.recorded_at)
+        .unwrap_or_else(Utc::now)
+        .date_naive();
+    let path = day_file(data_dir, day);
+    let mut entries = read_day(&path, cipher)?;
+    entries.push(entry);
+    let data = serde_json::to_string(&entries).map_err(|e| e.to_string())?;
+    cipher.write(&path, &data)
+}
+
+/// Sums the stats of the last `days` days, optionally only those of one
+/// workspace and of the given turns.
+pub(crate) fn build_report(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: Option<&str>,
+    turn_ids: Option<&HashSet<String>>,
+    days: u32,
+    today: NaiveDate,
+) -> Result<CodeStatsReport, String> {
+    let mut report = CodeStatsReport {
+        days,
+        turns: 0,
+        stats: CodeStats::default(),
+    };
+    for offset in 0..days {
+        let day = today - Duration::days(i64::from(offset));
+        for entry in read_day(&day_file(data_dir, day), cipher)? {
+            if workspace_id.is_some_and(|workspace_id| entry.workspace_id != workspace_id) {
+                continue;
+            }
+            if turn_ids.is_some_and(|turn_ids| {
+                entry
+                    .turn_id
+                    .as_ref()
+                    .is_none_or(|turn_id| !turn_ids.contains(turn_id))
+            }) {
+                continue;
+            }
+            report.turns += 1;
+            report.stats.merge(&entry.stats);
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn detects_languages_and_test_files() {
+        assert_eq!(language_for_path("src/main.rs"), "Rust");
+        assert_eq!(language_for_path("web/App.TSX"), "TypeScript");
+        assert_eq!(language_for_path("Dockerfile"), "Dockerfile");
+        assert_eq!(language_for_path("LICENSE"), "Other");
+        assert!(is_test_path("src/utils.test.ts"));
+        assert!(is_test_path("tests/integration.rs"));
+        assert!(is_test_path("pkg/server_test.go"));
+        assert!(is_test_path("test_api.py"));
+        assert!(!is_test_path("src/contest.rs"));
+    }
+
+    #[test]
+    fn aggregates_lines_per_language_and_test_share() {
+        let stats = CodeStats::from_changes([
+            ("src/lib.rs", "@@\n+a\n+b\n-c\n"),
+            ("src/lib.rs", "@@\n+d\n"),
+            ("tests/lib.rs", "@@\n+e\n"),
+            ("web/app.ts", "--- a\n+++ b\n-x\n"),
+        ]);
+        let rust = &stats.by_language["Rust"];
+        assert_eq!((rust.files, rust.added, rust.removed), (2, 4, 1));
+        assert_eq!(stats.by_language["TypeScript"].removed, 1);
+        assert_eq!((stats.added, stats.removed), (4, 2));
+        assert_eq!(stats.test_files, 1);
+        assert_eq!((stats.test_lines_added, stats.non_test_lines_added), (1, 3));
+
+        let mut total = CodeStats::default();
+        total.merge(&stats);
+        total.merge(&stats);
+        assert_eq!(total.by_language["Rust"].added, 8);
+    }
+
+    #[test]
+    fn reports_stats_within_window_per_workspace() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        let stats = CodeStats::from_changes([("src/lib.rs", "+a\n+b\n")]);
+        let now = Utc::now();
+        for (workspace_id, age_days) in [("ws-1", 0), ("ws-2", 0), ("ws-1", 10)] {
+            let entry = TurnCodeStats {
+                workspace_id: workspace_id.to_string(),
+                thread_id: "t".to_string(),
+                turn_id: None,
+                recorded_at: (now - Duration::days(age_days)).timestamp_millis(),
+                stats: stats.clone(),
+            };
+            record_turn_stats(&dir, &cipher, entry).expect("record");
+        }
+        assert_eq!(code_stats_files(&dir).len(), 2);
+
+        let today = now.date_naive();
+        let all = build_report(&dir, &cipher, None, None, 30, today).expect("report");
+        assert_eq!(all.turns, 3);
+        assert_eq!(all.stats.by_language["Rust"].added, 6);
+
+        let recent = build_report(&dir, &cipher, Some("ws-1"), None, 7, today).expect("report");
+        assert_eq!(recent.turns, 1);
+    }
+}
@@ -0,0 +1,220 @@
+use std::sync::RwLock;
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::backend::command_output::command_text;
+
+/// Prefix marking a blocklist entry as a regular expression; other entries
+/// match as a run of whole command words.
+const REGEX_PREFIX: &str = "re:";
+
+static BLOCKLIST: RwLock<Vec<BlockRule>> = RwLock::new(Vec::new());
+
+enum Matcher {
+    Words(Vec<String>),
+    Regex(Regex),
+}
+
+struct BlockRule {
+    pattern: String,
+    matcher: Matcher,
+}
+
+/// Payload of the `codex/blockedCommand` event.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BlockedCommand {
+    pub(crate) thread_id: Option<String>,
+    pub(crate) turn_id: Option<String>,
+    pub(crate) item_id: Option<String>,
+    pub(crate) command: String,
+    /// Blocklist entry that matched.
+    pub(crate) pattern: String,
+    /// True when the command started without asking for approval and the
+    /// turn was interrupted instead of the request being declined.
+    pub(crate) interrupted: bool,
+    pub(crate) reason: String,
+}
+
+impl BlockedCommand {
+    pub(crate) fn to_event(&self) -> Value {
+        json!({
+            "method": "codex/blockedCommand",
+            "params": self,
+        })
+    }
+}
+
+/// Splits a command into words, dropping quotes and shell separators so
+/// `bash -lc "git push --force"` still reads as `git push --force`.
+fn command_words(command: &str) -> Vec<String> {
+    command
+        .split(|ch: char| ch.is_whitespace() || matches!(ch, ';' | '&' | '|' | '(' | ')'))
+        .map(|word| word.trim_matches(|ch| ch == '"' || ch == '\''))
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn compile(pattern: &str) -> Option<BlockRule> {
+    let trimmed = pattern.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let matcher = match trimmed.strip_prefix(REGEX_PREFIX) {
+        Some(expression) => match Regex::new(expression.trim()) {
+            Ok(regex) => Matcher::Regex(regex),
+            Err(err) => {
+                eprintln!("Ignoring invalid command blocklist pattern {pattern:?}: {err}");
+                return None;
+            }
+        },
+        None => Matcher::Words(command_words(trimmed)),
+    };
+    Some(BlockRule {
+        pattern: trimmed.to_string(),
+        matcher,
+    })
+}
+
+impl BlockRule {
+    fn matches(&self, command: &str, words: &[String]) -> bool {
+        match &self.matcher {
+            Matcher::Regex(regex) => regex.is_match(command),
+            Matcher::Words(pattern) => {
+                !pattern.is_empty()
+                    && words
+                        .windows(pattern.len())
+                        .any(|window| window == pattern.as_slice())
+            }
+        }
+    }
+}
+
+/// Replaces the blocklist; called when settings load or change.
+pub(crate) fn set_blocklist(patterns: &[String]) {
+    let rules = patterns
+        .iter()
+        .filter_map(|pattern| compile(pattern))
+        .collect();
+    if let Ok(mut current) = BLOCKLIST.write() {
+        *current = rules;
+    }
+}
+
+/// Returns the blocklist entry matching a command, if any.
+pub(crate) fn blocking_pattern(command: &str) -> Option<String> {
+    let rules = BLOCKLIST.read().ok()?;
+    let words = command_words(command);
+    rules
+        .iter()
+        .find(|rule| rule.matches(command, &words))
+        .map(|rule| rule.pattern.clone())
+}
+
+fn str_field(value: Option<&Value>, keys: &[&str]) -> Option<String> {
+    let value = value?;
+    keys.iter()
+        .find_map(|key| value.get(*key).and_then(|value| value.as_str()))
+        .map(str::to_string)
+}
+
+fn blocked(
+    params: Option<&Value>,
+    item: Option<&Value>,
+    interrupted: bool,
+) -> Option<BlockedCommand> {
+    let command = command_text(item.or(params).and_then(|value| value.get("command")));
+    let pattern = blocking_pattern(&command)?;
+    let reason = if interrupted {
+        format!("Stopped the turn: `{command}` matches the command blocklist entry `{pattern}`.")
+    } else {
+        format!("Declined `{command}`: it matches the command blocklist entry `{pattern}`.")
+    };
+    Some(BlockedCommand {
+        thread_id: str_field(params, &["threadId", "thread_id"]),
+        turn_id: str_field(params, &["turnId", "turn_id"])
+            .or_else(|| str_field(params.and_then(|params| params.get("turn")), &["id"])),
+        item_id: str_field(item, &["id"]).or_else(|| str_field(params, &["itemId", "item_id"])),
+        command,
+        pattern,
+        interrupted,
+        reason,
+    })
+}
+
+/// Checks an app-server message against the blocklist. Exec approval
+/// requests are declined before they reach the UI. Commands that start
+/// without asking (for example in full-access mode) can only be stopped by
+/// interrupting their turn.
+pub(crate) fn check_message(message: &Value) -> Option<BlockedCommand> {
+    let method = message.get("method").and_then(|value| value.as_str())?;
+    let params = message.get("params");
+    match method {
+        "item/commandExecution/requestApproval" if message.get("id").is_some() => {
+            blocked(params, None, false)
+        }
+        "item/started" => {
+            let item = params.and_then(|params| params.get("item"))?;
+            if item.get("type").and_then(|value| value.as_str()) != Some("commandExecution") {
+                return None;
+            }
+            blocked(params, Some(item), true)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_word_runs_and_regex_entries() {
+        set_blocklist(&[
+            "git push --force".to_string(),
+            r"re:kubectl\s+delete\s+(ns|namespace)\b".to_string(),
+            "re:(".to_string(),
+        ]);
+        assert_eq!(
+            blocking_pattern("bash -lc \"git push --force origin main\"").as_deref(),
+            Some("git push --force")
+        );
+        assert_eq!(blocking_pattern("git push origin main"), None);
+        assert_eq!(blocking_pattern("git push --force-with-lease"), None);
+        assert_eq!(
+            blocking_pattern("kubectl delete ns staging").as_deref(),
+            Some(r"re:kubectl\s+delete\s+(ns|namespace)\b")
+        );
+
+        let request = json!({
+            "id": 4,
+            "method": "item/commandExecution/requestApproval",
+            "params": {
+                "threadId": "t-1",
+                "turnId": "turn-1",
+                "itemId": "item-1",
+                "command": ["git", "push", "--force"],
+            }
+        });
+        let blocked = check_message(&request).expect("blocked");
+        assert!(!blocked.interrupted);
+        assert_eq!(blocked.thread_id.as_deref(), Some("t-1"));
+        assert_eq!(blocked.item_id.as_deref(), Some("item-1"));
+
+        let started = json!({
+            "method": "item/started",
+            "params": {
+                "threadId": "t-1",
+                "turnId": "turn-2",
+                "item": { "id": "item-2", "type": "commandExecution", "command": "ls" },
+            }
+        });
+        assert_eq!(check_message(&started), None);
+
+        set_blocklist(&[]);
+        assert_eq!(check_message(&request), None);
+    }
+}
@@ -0,0 +1,507 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+use crate::backend::turn_summary::{params_thread_id, params_turn_id};
+
+/// Kept from the start of an oversized output.
+const OUTPUT_HEAD_CHARS: usize = 8 * 1024;
+/// Kept from the end of an oversized output; test summaries live there.
+const OUTPUT_TAIL_CHARS: usize = 24 * 1024;
+/// Captured commands kept per workspace, oldest dropped first.
+const MAX_COMMANDS_PER_WORKSPACE: usize = 300;
+/// Deltas buffered (and full output kept on disk) for a single command.
+const MAX_FULL_OUTPUT_BYTES: usize = 32 * 1024 * 1024;
+/// `aggregatedOutput` larger than this is cut down before reaching the webview.
+const EVENT_OUTPUT_MAX_CHARS: usize = 64 * 1024;
+
+/// Output of a command the agent ran, as captured from its exec item.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CapturedCommand {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: Option<String>,
+    pub(crate) item_id: String,
+    pub(crate) command: String,
+    pub(crate) cwd: Option<String>,
+    pub(crate) exit_code: Option<i64>,
+    pub(crate) output: String,
+    /// Output size before ANSI stripping and truncation.
+    pub(crate) original_len: usize,
+    pub(crate) truncated: bool,
+    pub(crate) captured_at: i64,
+    /// Complete ANSI-stripped output of a truncated command; stored in its own
+    /// file and served in pages by `read_output_page`.
+    #[serde(skip)]
+    pub(crate) full_output: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CommandOutputMatch {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: Option<String>,
+    pub(crate) item_id: String,
+    pub(crate) command: String,
+    pub(crate) exit_code: Option<i64>,
+    pub(crate) captured_at: i64,
+    /// 1-based line number within the stored output.
+    pub(crate) line_number: usize,
+    pub(crate) line: String,
+}
+
+/// Slice of a command's complete output.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ItemOutputPage {
+    pub(crate) item_id: String,
+    /// Offset and sizes are in characters.
+    pub(crate) offset: usize,
+    pub(crate) content: String,
+    pub(crate) total_chars: usize,
+    pub(crate) has_more: bool,
+    pub(crate) next_offset: Option<usize>,
+}
+
+/// Removes ANSI escape sequences and resolves carriage-return progress
+/// redraws so only the final state of each line remains.
+pub(crate) fn strip_ansi(input: &str) -> String {
+    let mut plain = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '\u{1b}' {
+            plain.push(ch);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameters and intermediates up to a final byte in @..~.
+            Some('[') => {
+                for next in chars.by_ref() {
+                    if ('@'..='~').contains(&next) {
+                        break;
+                    }
+                }
+            }
+            // OSC: terminated by BEL or ESC \.
+            Some(']') => {
+                while let Some(next) = chars.next() {
+                    if next == '\u{7}' {
+                        break;
+                    }
+                    if next == '\u{1b}' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    plain
+        .replace("\r\n", "\n")
+        .split('\n')
+        .map(|line| {
+            line.rsplit('\r')
+                .find(|part| !part.is_empty())
+                .unwrap_or("")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Caps output to its head and tail, marking what was dropped in between.
+pub(crate) fn cap_output(output: &str) -> (String, bool) {
+    let total = output.chars().count();
+    if total <= OUTPUT_HEAD_CHARS + OUTPUT_TAIL_CHARS {
+        return (output.to_string(), false);
+    }
+    let head: String = output.chars().take(OUTPUT_HEAD_CHARS).collect();
+    let tail: String = output.chars().skip(total - OUTPUT_TAIL_CHARS).collect();
+    let omitted = total - OUTPUT_HEAD_CHARS - OUTPUT_TAIL_CHARS;
+    (
+        format!("{head}\n... [{omitted} characters omitted] ...\n{tail}"),
+        true,
+    )
+}
+
+pub(crate) fn command_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| part.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        Some(Value::String(text)) => text.clone(),
+        _ => String::new(),
+    }
+}
+
+/// Buffers `item/commandExecution/outputDelta` chunks until the command's
+/// item completes.
+#[derive(Default)]
+pub(crate) struct CommandOutputCollector {
+    pending: HashMap<(String, String), String>,
+}
+
+impl CommandOutputCollector {
+    /// Feeds a notification; returns the captured command once its exec item
+    /// has completed.
+    pub(crate) fn observe(
+        &mut self,
+        workspace_id: &str,
+        message: &Value,
+        now_ms: i64,
+    ) -> Option<CapturedCommand> {
+        let method = message.get("method").and_then(|value| value.as_str())?;
+        let params = message.get("params")?;
+        match method {
+            "item/commandExecution/outputDelta" => {
+                let item_id = params.get("itemId").and_then(|value| value.as_str())?;
+                let delta = params.get("delta").and_then(|value| value.as_str())?;
+                let buffer = self
+                    .pending
+                    .entry((workspace_id.to_string(), item_id.to_string()))
+                    .or_default();
+                if buffer.len() + delta.len() <= MAX_FULL_OUTPUT_BYTES {
+                    buffer.push_str(delta);
+                }
+                None
+            }
+            "item/completed" => {
+                let item = params.get("item")?;
+                if item.get("type").and_then(|value| value.as_str()) != Some("commandExecution") {
+                    return None;
+                }
+                let item_id = item.get("id").and_then(|value| value.as_str())?.to_string();
+                let buffered = self
+                    .pending
+                    .remove(&(workspace_id.to_string(), item_id.clone()));
+                let raw = item
+                    .get("aggregatedOutput")
+                    .and_then(|value| value.as_str())
+                    .map(|value| value.to_string())
+                    .or(buffered)
+                    .unwrap_or_default();
+                let plain = strip_ansi(&raw);
+                let (output, truncated) = cap_output(&plain);
+                Some(CapturedCommand {
+                    workspace_id: workspace_id.to_string(),
+                    thread_id: params_thread_id(params).unwrap_or_default(),
+                    turn_id: params_turn_id(params),
+                    item_id,
+                    command: command_text(item.get("command")),
+                    cwd: item
+                        .get("cwd")
+                        .and_then(|value| value.as_str())
+                        .map(|value| value.to_string()),
+                    exit_code: item.get("exitCode").and_then(|value| value.as_i64()),
+                    output,
+                    original_len: raw.len(),
+                    truncated,
+                    captured_at: now_ms,
+                    full_output: truncated.then_some(plain),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn command_outputs_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("command_outputs")
+}
+
+pub(crate) fn safe_file_name(value: &str) -> String {
+    value
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn workspace_file(data_dir: &Path, workspace_id: &str) -> PathBuf {
+    command_outputs_dir(data_dir).join(format!("{}.json", safe_file_name(workspace_id)))
+}
+
+fn full_output_file(data_dir: &Path, workspace_id: &str, item_id: &str) -> PathBuf {
+    let name = format!(
+        "{}_{}",
+        safe_file_name(workspace_id),
+        safe_file_name(item_id)
+    );
+    full_outputs_dir(data_dir).join(format!("{name}.txt"))
+}
+
+/// Complete outputs of truncated commands, one file per command.
+pub(crate) fn full_outputs_dir(data_dir: &Path) -> PathBuf {
+    command_outputs_dir(data_dir).join("full")
+}
+
+fn read_file(path: &Path, cipher: &DataCipher) -> Result<Vec<CapturedCommand>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn record_command(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    mut command: CapturedCommand,
+) -> Result<(), String> {
+    if let Some(full_output) = command.full_output.take() {
+        cipher.write(
+            &full_output_file(data_dir, &command.workspace_id, &command.item_id),
+            &full_output,
+        )?;
+    }
+    let path = workspace_file(data_dir, &command.workspace_id);
+    let mut commands = read_file(&path, cipher)?;
+    commands.retain(|existing| existing.item_id != command.item_id);
+    commands.push(command);
+    if commands.len() > MAX_COMMANDS_PER_WORKSPACE {
+        let excess = commands.len() - MAX_COMMANDS_PER_WORKSPACE;
+        for dropped in commands.drain(..excess) {
+            let _ = std::fs::remove_file(full_output_file(
+                data_dir,
+                &dropped.workspace_id,
+                &dropped.item_id,
+            ));
+        }
+    }
+    let data = serde_json::to_string(&commands).map_err(|e| e.to_string())?;
+    cipher.write(&path, &data)
+}
+
+/// Captured commands of one workspace, or of every workspace.
+pub(crate) fn read_commands(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: Option<&str>,
+) -> Result<Vec<CapturedCommand>, String> {
+    if let Some(workspace_id) = workspace_id {
+        return read_file(&workspace_file(data_dir, workspace_id), cipher);
+    }
+    let Ok(entries) = std::fs::read_dir(command_outputs_dir(data_dir)) else {
+        return Ok(Vec::new());
+    };
+    let mut commands = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            commands.extend(read_file(&path, cipher)?);
+        }
+    }
+    Ok(commands)
+}
+
+/// Returns up to `limit` characters of a command's complete output starting
+/// at `offset`. Outputs that were never truncated are served from the index.
+pub(crate) fn read_output_page(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    item_id: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<ItemOutputPage, String> {
+    let full_path = full_output_file(data_dir, workspace_id, item_id);
+    let output = if full_path.exists() {
+        cipher.read_to_string(&full_path)?
+    } else {
+        read_file(&workspace_file(data_dir, workspace_id), cipher)?
+            .into_iter()
+            .find(|command| command.item_id == item_id)
+            .map(|command| command.output)
+            .ok_or("No captured output for this item.")?
+    };
+    let total_chars = output.chars().count();
+    let offset = offset.min(total_chars);
+    let content: String = output.chars().skip(offset).take(limit).collect();
+    let end = offset + content.chars().count();
+    Ok(ItemOutputPage {
+        item_id: item_id.to_string(),
+        offset,
+        content,
+        total_chars,
+        has_more: end < total_chars,
+        next_offset: (end < total_chars).then_some(end),
+    })
+}
+
+/// Replaces an oversized `aggregatedOutput` in an `item/completed`
+/// notification with its tail and marks it so the UI can page the rest in
+/// with `get_item_output`.
+pub(crate) fn trim_event_output(message: &mut Value) {
+    if message.get("method").and_then(|value| value.as_str()) != Some("item/completed") {
+        return;
+    }
+    let Some(item) = message
+        .get_mut("params")
+        .and_then(|params| params.get_mut("item"))
+        .and_then(|item| item.as_object_mut())
+    else {
+        return;
+    };
+    let Some(output) = item
+        .get("aggregatedOutput")
+        .and_then(|value| value.as_str())
+    else {
+        return;
+    };
+    let total_chars = output.chars().count();
+    if total_chars <= EVENT_OUTPUT_MAX_CHARS {
+        return;
+    }
+    let tail: String = output
+        .chars()
+        .skip(total_chars - EVENT_OUTPUT_MAX_CHARS)
+        .collect();
+    item.insert("aggregatedOutput".to_string(), Value::String(tail));
+    item.insert("outputTruncated".to_string(), Value::Bool(true));
+    item.insert("outputTotalChars".to_string(), Value::from(total_chars));
+}
+
+/// Case-insensitive line search over captured outputs and command lines,
+/// newest commands first.
+pub(crate) fn search_commands(
+    mut commands: Vec<CapturedCommand>,
+    query: &str,
+    limit: usize,
+) -> Vec<CommandOutputMatch> {
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    commands.sort_by_key(|command| std::cmp::Reverse(command.captured_at));
+    let mut matches = Vec::new();
+    for command in &commands {
+        let command_line = format!("$ {}", command.command);
+        let lines = std::iter::once((0, command_line.as_str())).chain(
+            command
+                .output
+                .lines()
+                .enumerate()
+                .map(|(index, line)| (index + 1, line)),
+        );
+        for (line_number, line) in lines {
+            if !line.to_lowercase().contains(&needle) {
+                continue;
+            }
+            matches.push(CommandOutputMatch {
+                workspace_id: command.workspace_id.clone(),
+                thread_id: command.thread_id.clone(),
+                turn_id: command.turn_id.clone(),
+                item_id: command.item_id.clone(),
+                command: command.command.clone(),
+                exit_code: command.exit_code,
+                captured_at: command.captured_at,
+                line_number,
+                line: line.to_string(),
+            });
+            if matches.len() >= limit {
+                return matches;
+            }
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn strips_ansi_and_progress_redraws() {
+        let raw = "\u{1b}[32mok\u{1b}[0m 3 tests\r\n10%\r50%\r100%\n\u{1b}]0;title\u{7}done";
+        assert_eq!(strip_ansi(raw), "ok 3 tests\n100%\ndone");
+    }
+
+    #[test]
+    fn captures_completed_commands_and_searches_them() {
+        let mut collector = CommandOutputCollector::default();
+        let delta = json!({
+            "method": "item/commandExecution/outputDelta",
+            "params": { "threadId": "t1", "turnId": "turn-1", "itemId": "c1", "delta": "running 2 tests\ntest a ... \u{1b}[31mFAILED\u{1b}[0m\n" }
+        });
+        assert!(collector.observe("ws", &delta, 1).is_none());
+        let completed = json!({
+            "method": "item/completed",
+            "params": {
+                "threadId": "t1",
+                "turnId": "turn-1",
+                "item": { "type": "commandExecution", "id": "c1", "command": ["cargo", "test"], "exitCode": 101 }
+            }
+        });
+        let captured = collector.observe("ws", &completed, 5).expect("captured");
+        assert_eq!(captured.command, "cargo test");
+        assert_eq!(captured.output, "running 2 tests\ntest a ... FAILED\n");
+
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        record_command(&dir, &cipher, captured).expect("record");
+        let commands = read_commands(&dir, &cipher, None).expect("read");
+        let matches = search_commands(commands, "failed", 10);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line_number, 2);
+        assert_eq!(matches[0].exit_code, Some(101));
+
+        let mut event = json!({
+            "method": "item/completed",
+            "params": { "item": { "type": "commandExecution", "aggregatedOutput": "y".repeat(EVENT_OUTPUT_MAX_CHARS + 5) } }
+        });
+        trim_event_output(&mut event);
+        let item = &event["params"]["item"];
+        assert_eq!(item["outputTruncated"], json!(true));
+        assert_eq!(item["outputTotalChars"], json!(EVENT_OUTPUT_MAX_CHARS + 5));
+        assert_eq!(
+            item["aggregatedOutput"].as_str().unwrap().len(),
+            EVENT_OUTPUT_MAX_CHARS
+        );
+
+        let (capped, truncated) =
+            cap_output(&"x".repeat(OUTPUT_HEAD_CHARS + OUTPUT_TAIL_CHARS + 10));
+        assert!(truncated);
+        assert!(capped.contains("[10 characters omitted]"));
+    }
+    #[test]
+    fn pages_full_output_of_truncated_commands() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        let mut collector = CommandOutputCollector::default();
+        let output = "0123456789".repeat((OUTPUT_HEAD_CHARS + OUTPUT_TAIL_CHARS) / 10 + 1);
+        let completed = json!({
+            "method": "item/completed",
+            "params": {
+                "threadId": "t1",
+                "item": { "type": "commandExecution", "id": "big", "command": "cat log", "aggregatedOutput": output }
+            }
+        });
+        let captured = collector.observe("ws", &completed, 1).expect("captured");
+        assert!(captured.truncated);
+        record_command(&dir, &cipher, captured).expect("record");
+
+        let page = read_output_page(&dir, &cipher, "ws", "big", 5, 10).expect("page");
+        assert_eq!(page.content, "5678901234");
+        assert_eq!(page.total_chars, output.len());
+        assert_eq!(page.next_offset, Some(15));
+
+        let last =
+            read_output_page(&dir, &cipher, "ws", "big", output.len() - 3, 10).expect("page");
+        assert_eq!(last.content, "789");
+        assert!(!last.has_more);
+        assert!(read_output_page(&dir, &cipher, "ws", "missing", 0, 10).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+/// Artificial latency, bandwidth limit and message loss applied to the
+/// stdin/stdout of app-server sessions, for testing how the app behaves on
+/// bad connections. Debug builds only; not persisted, so it resets when the
+/// app restarts.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConnectionSimulation {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// Added to every message, in each direction.
+    #[serde(default)]
+    pub(crate) latency_ms: u64,
+    /// Up to this much extra latency, chosen at random per message.
+    #[serde(default)]
+    pub(crate) jitter_ms: u64,
+    /// Share of messages silently dropped, from 0 to 100. Dropped requests
+    /// never receive a response.
+    #[serde(default)]
+    pub(crate) drop_percent: f64,
+    /// Transfer rate limit; `None` for unlimited.
+    #[serde(default)]
+    pub(crate) bandwidth_kbps: Option<u64>,
+}
+
+static SIMULATION: RwLock<Option<ConnectionSimulation>> = RwLock::new(None);
+
+pub(crate) fn set_simulation(simulation: ConnectionSimulation) {
+    if let Ok(mut current) = SIMULATION.write() {
+        *current = Some(simulation).filter(|simulation| simulation.enabled);
+    }
+}
+
+pub(crate) fn simulation() -> ConnectionSimulation {
+    SIMULATION
+        .read()
+        .ok()
+        .and_then(|current| current.clone())
+        .unwrap_or_default()
+}
+
+/// A random number in `0..1`.
+fn roll() -> f64 {
+    (Uuid::new_v4().as_u128() % 1_000_000) as f64 / 1_000_000.0
+}
+
+/// Delay for a message of `bytes`, or `None` when it is dropped.
+fn plan(
+    simulation: &ConnectionSimulation,
+    bytes: usize,
+    drop_roll: f64,
+    jitter_roll: f64,
+) -> Option<Duration> {
+    if drop_roll * 100.0 < simulation.drop_percent {
+        return None;
+    }
+    let jitter = (simulation.jitter_ms as f64 * jitter_roll) as u64;
+    let transfer = simulation
+        .bandwidth_kbps
+        .filter(|kbps| *kbps > 0)
+        .map(|kbps| bytes as u64 * 8 / kbps)
+        .unwrap_or(0);
+    Some(Duration::from_millis(
+        simulation.latency_ms + jitter + transfer,
+    ))
+}
+
+/// Delays a message of `bytes` according to the active simulation; returns
+/// `false` when the message should be dropped.
+pub(crate) async fn transmit(bytes: usize) -> bool {
+    let Some(simulation) = SIMULATION.read().ok().and_then(|current| current.clone()) else {
+        return true;
+    };
+    match plan(&simulation, bytes, roll(), roll()) {
+        Some(delay) => {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plans_latency_bandwidth_and_drops() {
+        let simulation = ConnectionSimulation {
+            enabled: true,
+            latency_ms: 200,
+            jitter_ms: 100,
+            drop_percent: 10.0,
+            bandwidth_kbps: Some(8),
+        };
+        assert_eq!(
+            plan(&simulation, 1_000, 0.5, 0.5),
+            Some(Duration::from_millis(200 + 50 + 1_000))
+        );
+        assert_eq!(plan(&simulation, 1_000, 0.05, 0.5), None);
+        assert_eq!(
+            plan(&ConnectionSimulation::default(), 1_000, 0.0, 1.0),
+            Some(Duration::ZERO)
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+use crate::backend::code_stats::language_for_path;
+
+pub(crate) const DEFAULT_SUGGESTION_LIMIT: usize = 8;
+
+/// Words too common in prompts to say anything about a file.
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "from", "into", "when", "then", "than", "make",
+    "add", "fix", "use", "should", "please", "can", "not", "are", "was", "all", "new", "file",
+    "files", "code", "some", "also", "there", "where", "which", "what", "have", "does", "them",
+];
+
+/// Keywords that introduce a definition in the languages we commonly see.
+const DEFINITION_KEYWORDS: &[&str] = &[
+    "fn",
+    "struct",
+    "enum",
+    "trait",
+    "type",
+    "impl",
+    "mod",
+    "class",
+    "interface",
+    "def",
+    "function",
+    "const",
+    "let",
+    "var",
+    "func",
+];
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ContextReason {
+    PathMention,
+    FileName,
+    PathSegment,
+    Symbol,
+    CurrentDiff,
+    RecentlyChanged,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ContextFileSuggestion {
+    pub(crate) path: String,
+    pub(crate) score: i64,
+    pub(crate) reasons: Vec<ContextReason>,
+    /// Symbols from the prompt that the file defines.
+    pub(crate) symbols: Vec<String>,
+}
+
+/// What the prompt text says about files: explicit paths, plain words and
+/// identifier-like symbols.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct PromptTerms {
+    pub(crate) mentions: Vec<String>,
+    pub(crate) words: HashSet<String>,
+    pub(crate) symbols: Vec<String>,
+}
+
+/// Workspace state gathered alongside the file list.
+#[derive(Debug, Default)]
+pub(crate) struct ContextSignals {
+    pub(crate) diff_paths: HashSet<String>,
+    /// Path to the index of the most recent commit touching it (0 = newest).
+    pub(crate) recent_paths: HashMap<String, usize>,
+    pub(crate) symbol_paths: HashMap<String, Vec<String>>,
+}
+
+/// Splits `camelCase`, `PascalCase`, `snake_case` and `kebab-case` into
+/// lowercase words.
+fn split_words(value: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for ch in value.chars() {
+        if !ch.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if ch.is_ascii_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = ch.is_ascii_lowercase() || ch.is_ascii_digit();
+        current.push(ch.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn is_symbol(token: &str) -> bool {
+    let mut chars = token.chars();
+    let starts_ok = chars
+        .next()
+        .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_');
+    let identifier = token
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+    let has_lower = token.chars().any(|ch| ch.is_ascii_lowercase());
+    let mixed_case = has_lower && token.chars().skip(1).any(|ch| ch.is_ascii_uppercase());
+    starts_ok && identifier && token.len() >= 4 && (token.contains('_') || mixed_case)
+}
+
+pub(crate) fn prompt_terms(prompt: &str) -> PromptTerms {
+    let mut terms = PromptTerms::default();
+    let tokens = prompt.split(|ch: char| {
+        !(ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '/' | '-' | ':'))
+    });
+    for token in tokens {
+        let token = token.trim_matches(|ch: char| matches!(ch, '.' | ':' | '-' | '/'));
+        if token.is_empty() {
+            continue;
+        }
+        let has_extension = token
+            .rsplit_once('.')
+            .is_some_and(|(stem, _)| !stem.is_empty() && language_for_path(token) != "Other");
+        let is_path = token.contains('/') || has_extension;
+        if is_path && !terms.mentions.iter().any(|mention| mention == token) {
+            terms.mentions.push(token.to_string());
+        }
+        for part in token.split(['.', '/', ':', '-']) {
+            if is_symbol(part) && !terms.symbols.iter().any(|symbol| symbol == part) {
+                terms.symbols.push(part.to_string());
+            }
+        }
+        for word in split_words(token) {
+            if word.len() >= 3 && !STOP_WORDS.contains(&word.as_str()) {
+                terms.words.insert(word);
+            }
+        }
+    }
+    terms
+}
+
+/// Whether `content` contains a definition of `symbol`, such as `fn symbol`
+/// or `class Symbol`.
+pub(crate) fn defines_symbol(content: &str, symbol: &str) -> bool {
+    content.lines().any(|line| {
+        line.match_indices(symbol).any(|(start, _)| {
+            let end = start + symbol.len();
+            let ends_cleanly = line[end..]
+                .chars()
+                .next()
+                .is_none_or(|ch| !(ch.is_ascii_alphanumeric() || ch == '_'));
+            let keyword = line[..start]
+                .trim_end()
+                .rsplit(|ch: char| !ch.is_ascii_alphanumeric())
+                .next()
+                .unwrap_or("");
+            ends_cleanly && DEFINITION_KEYWORDS.contains(&keyword)
+        })
+    })
+}
+
+fn score_file(
+    path: &str,
+    terms: &PromptTerms,
+    signals: &ContextSignals,
+) -> Option<ContextFileSuggestion> {
+    let lower = path.to_ascii_lowercase();
+    let mut score = 0i64;
+    let mut reasons = Vec::new();
+
+    if terms
+        .mentions
+        .iter()
+        .any(|mention| lower.ends_with(&mention.to_ascii_lowercase()))
+    {
+        score += 100;
+        reasons.push(ContextReason::PathMention);
+    }
+
+    let (directory, file_name) = lower.rsplit_once('/').unwrap_or(("", &lower));
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    let stem_words = split_words(stem);
+    let matched_stem_words = stem_words
+        .iter()
+        .filter(|word| terms.words.contains(*word))
+        .count();
+    if matched_stem_words > 0 {
+        score += if matched_stem_words == stem_words.len() {
+            40
+        } else {
+            15 * matched_stem_words as i64
+        };
+        reasons.push(ContextReason::FileName);
+    }
+
+    let matched_segments = directory
+        .split('/')
+        .filter(|segment| terms.words.contains(*segment))
+        .count()
+        .min(3);
+    if matched_segments > 0 {
+        score += 8 * matched_segments as i64;
+        reasons.push(ContextReason::PathSegment);
+    }
+
+    let symbols = signals.symbol_paths.get(path).cloned().unwrap_or_default();
+    if !symbols.is_empty() {
+        score += 30 * symbols.len() as i64;
+        reasons.push(ContextReason::Symbol);
+    }
+
+    if signals.diff_paths.contains(path) {
+        score += 20;
+        reasons.push(ContextReason::CurrentDiff);
+    }
+    if let Some(rank) = signals.recent_paths.get(path) {
+        score += 10_i64.saturating_sub(*rank as i64).max(2);
+        reasons.push(ContextReason::RecentlyChanged);
+    }
+
+    (score > 0).then(|| ContextFileSuggestion {
+        path: path.to_string(),
+        score,
+        reasons,
+        symbols,
+    })
+}
+
+/// Ranks workspace files by how likely they are to matter for the prompt.
+pub(crate) fn rank_files(
+    files: &[String],
+    terms: &PromptTerms,
+    signals: &ContextSignals,
+    limit: usize,
+) -> Vec<ContextFileSuggestion> {
+    let mut suggestions: Vec<ContextFileSuggestion> = files
+        .iter()
+        .filter_map(|path| score_file(path, terms, signals))
+        .collect();
+    suggestions.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
+    suggestions.truncate(limit);
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_mentions_words_and_symbols() {
+        let terms = prompt_terms(
+            "Fix the retry logic in src/backend/app_server.rs and rename spawnSession.",
+        );
+        assert_eq!(terms.mentions, vec!["src/backend/app_server.rs"]);
+        assert!(terms.words.contains("retry"));
+        assert!(terms.words.contains("server"));
+        assert!(!terms.words.contains("the"));
+        assert_eq!(terms.symbols, vec!["app_server", "spawnSession"]);
+
+        assert!(defines_symbol(
+            "pub(crate) fn spawnSession() {}",
+            "spawnSession"
+        ));
+        assert!(defines_symbol("export class AppState {", "AppState"));
+        assert!(!defines_symbol("spawnSession();", "spawnSession"));
+        assert!(!defines_symbol("fn spawnSessionLater()", "spawnSession"));
+    }
+
+    #[test]
+    fn ranks_mentions_names_symbols_and_git_signals() {
+        let files: Vec<String> = [
+            "src/retry.rs",
+            "src/backend/app_server.rs",
+            "src/session.rs",
+            "src/unrelated.rs",
+            "README.md",
+        ]
+        .iter()
+        .map(|path| path.to_string())
+        .collect();
+        let terms = prompt_terms("Make retry work in app_server.rs, see spawnSession");
+        let signals = ContextSignals {
+            diff_paths: HashSet::from(["README.md".to_string()]),
+            recent_paths: HashMap::from([("src/retry.rs".to_string(), 0)]),
+            symbol_paths: HashMap::from([(
+                "src/session.rs".to_string(),
+                vec!["spawnSession".to_string()],
+            )]),
+        };
+        let ranked = rank_files(&files, &terms, &signals, 10);
+        let paths: Vec<&str> = ranked.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "src/backend/app_server.rs",
+                "src/session.rs",
+                "src/retry.rs",
+                "README.md"
+            ]
+        );
+        assert_eq!(
+            ranked[2].reasons,
+            vec![ContextReason::FileName, ContextReason::RecentlyChanged]
+        );
+        assert_eq!(rank_files(&files, &terms, &signals, 1).len(), 1);
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use crate::backend::request_id::RequestId;
+use crate::backend::secure_storage::DataCipher;
+use crate::backend::turn_summary::params_thread_id;
+
+/// A directory outside the workspace a thread's turns may write to.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DirectoryGrant {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) path: String,
+    pub(crate) granted_at: i64,
+}
+
+/// Payload of `directory-access-requested`: an approval request asking to
+/// write under a directory the thread's sandbox does not cover.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DirectoryAccessRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) request_id: RequestId,
+    pub(crate) path: String,
+    pub(crate) reason: Option<String>,
+}
+
+/// The root an approval request asks to be allowed to write under, from
+/// its `grantRoot`.
+pub(crate) fn requested_root(
+    workspace_id: &str,
+    message: &Value,
+) -> Option<DirectoryAccessRequest> {
+    let request_id = RequestId::from_message(message)?;
+    if !message
+        .get("method")?
+        .as_str()?
+        .ends_with("requestApproval")
+    {
+        return None;
+    }
+    let params = message.get("params")?;
+    let path = params
+        .get("grantRoot")
+        .or_else(|| params.get("grant_root"))
+        .and_then(|value| value.as_str())
+        .filter(|value| !value.trim().is_empty())?;
+    Some(DirectoryAccessRequest {
+        workspace_id: workspace_id.to_string(),
+        thread_id: params_thread_id(params)?,
+        request_id,
+        path: path.to_string(),
+        reason: params
+            .get("reason")
+            .and_then(|value| value.as_str())
+            .map(str::to_string),
+    })
+}
+
+/// Whether `path` lies under one of `roots`.
+pub(crate) fn covered_by(roots: &[String], path: &str) -> bool {
+    let path = Path::new(path);
+    roots.iter().any(|root| path.starts_with(root))
+}
+
+pub(crate) fn directory_grants_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("directory_grants.json")
+}
+
+pub(crate) fn read_directory_grants(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<DirectoryGrant>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn add_directory_grant(
+    path: &Path,
+    cipher: &DataCipher,
+    grant: DirectoryGrant,
+) -> Result<(), String> {
+    let mut grants = read_directory_grants(path, cipher)?;
+    if grants.iter().any(|existing| {
+        existing.workspace_id == grant.workspace_id
+            && existing.thread_id == grant.thread_id
+            && existing.path == grant.path
+    }) {
+        return Ok(());
+    }
+    grants.push(grant);
+    let data = serde_json::to_string_pretty(&grants).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+/// Directories granted to a thread, in the order they were granted.
+pub(crate) fn thread_grants(
+    grants: &[DirectoryGrant],
+    workspace_id: &str,
+    thread_id: &str,
+) -> Vec<String> {
+    grants
+        .iter()
+        .filter(|grant| grant.workspace_id == workspace_id && grant.thread_id == thread_id)
+        .map(|grant| grant.path.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn reads_grant_root_from_approval_requests() {
+        let message = json!({
+            "id": 12,
+            "method": "item/fileChange/requestApproval",
+            "params": { "threadId": "t-1", "grantRoot": "/home/me/notes", "reason": "save notes" }
+        });
+        let request = requested_root("ws", &message).expect("request");
+        assert_eq!(request.request_id, RequestId::Number(12));
+        assert_eq!(request.path, "/home/me/notes");
+        assert_eq!(request.reason.as_deref(), Some("save notes"));
+
+        let no_root = json!({
+            "id": 13,
+            "method": "item/fileChange/requestApproval",
+            "params": { "threadId": "t-1" }
+        });
+        assert_eq!(requested_root("ws", &no_root), None);
+
+        let roots = vec!["/work/app".to_string()];
+        assert!(covered_by(&roots, "/work/app/docs"));
+        assert!(!covered_by(&roots, "/work/application"));
+    }
+
+    #[test]
+    fn stores_each_grant_once_per_thread() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = directory_grants_path(&dir);
+        let cipher = DataCipher::plaintext();
+        let grant = |thread_id: &str, root: &str| DirectoryGrant {
+            workspace_id: "ws".to_string(),
+            thread_id: thread_id.to_string(),
+            path: root.to_string(),
+            granted_at: 1,
+        };
+        add_directory_grant(&path, &cipher, grant("t-1", "/notes")).unwrap();
+        add_directory_grant(&path, &cipher, grant("t-1", "/notes")).unwrap();
+        add_directory_grant(&path, &cipher, grant("t-2", "/tmp/out")).unwrap();
+
+        let grants = read_directory_grants(&path, &cipher).unwrap();
+        assert_eq!(grants.len(), 2);
+        assert_eq!(thread_grants(&grants, "ws", "t-1"), vec!["/notes"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,253 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{HashSet, VecDeque};
+
+/// Dry-run turns run read-only and never get asked about mutations; the
+/// sandbox rejects them and the proposed changes are captured instead.
+pub(crate) const DRY_RUN_ACCESS_MODE: &str = "read-only";
+pub(crate) const DRY_RUN_APPROVAL_POLICY: &str = "never";
+/// Proposed patches kept per session, oldest dropped first.
+const MAX_PATCHES: usize = 20;
+
+/// Prepended to the user's input so the agent still writes out its changes
+/// even though they cannot be applied.
+const DRY_RUN_NOTE: &str = "This is a dry run in a read-only workspace. Make every change you \
+would make as a patch anyway; the patches are collected for review and nothing is applied.";
+
+pub(crate) fn dry_run_note() -> Value {
+    json!({ "type": "text", "text": DRY_RUN_NOTE })
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProposedFileChange {
+    pub(crate) item_id: String,
+    pub(crate) path: String,
+    pub(crate) kind: Option<String>,
+    pub(crate) diff: String,
+}
+
+/// Changes a dry-run turn proposed without applying them.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProposedPatch {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) files: Vec<ProposedFileChange>,
+    /// False while the turn is still running.
+    pub(crate) completed: bool,
+    /// All file changes as one unified diff.
+    pub(crate) patch: String,
+}
+
+fn str_param<'a>(params: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|key| params.get(*key).and_then(|value| value.as_str()))
+}
+
+fn change_kind(change: &Value) -> Option<String> {
+    let kind = change.get("kind")?;
+    kind.as_str()
+        .or_else(|| kind.get("type").and_then(|value| value.as_str()))
+        .map(|value| value.to_string())
+}
+
+fn file_patch(change: &ProposedFileChange) -> String {
+    let mut text = String::new();
+    let has_headers = change.diff.starts_with("---") || change.diff.starts_with("diff ");
+    if !has_headers {
+        let (old, new) = match change.kind.as_deref() {
+            Some("add") => ("/dev/null".to_string(), format!("b/{}", change.path)),
+            Some("delete") => (format!("a/{}", change.path), "/dev/null".to_string()),
+            _ => (format!("a/{}", change.path), format!("b/{}", change.path)),
+        };
+        text.push_str(&format!("--- {old}\n+++ {new}\n"));
+    }
+    text.push_str(&change.diff);
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
+
+impl ProposedPatch {
+    fn record_item(&mut self, item: &Value) {
+        let item_id = item
+            .get("id")
+            .and_then(|value| value.as_str())
+            .unwrap_or_default()
+            .to_string();
+        // Later events for the same item carry the final change list.
+        self.files
+            .retain(|file| item_id.is_empty() || file.item_id != item_id);
+        let changes = item.get("changes").and_then(|value| value.as_array());
+        for change in changes.into_iter().flatten() {
+            let Some(path) = change.get("path").and_then(|value| value.as_str()) else {
+                continue;
+            };
+            self.files.push(ProposedFileChange {
+                item_id: item_id.clone(),
+                path: path.to_string(),
+                kind: change_kind(change),
+                diff: change
+                    .get("diff")
+                    .and_then(|value| value.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+            });
+        }
+        self.patch = self.files.iter().map(file_patch).collect();
+    }
+}
+
+/// Marks threads whose next turn is a dry run and collects the file changes
+/// those turns propose.
+#[derive(Default)]
+pub(crate) struct DryRunTracker {
+    pending: HashSet<(String, String)>,
+    patches: VecDeque<ProposedPatch>,
+}
+
+impl DryRunTracker {
+    /// Annotates the next turn started in a thread as a dry run.
+    pub(crate) fn begin(&mut self, workspace_id: &str, thread_id: &str) {
+        self.pending
+            .insert((workspace_id.to_string(), thread_id.to_string()));
+    }
+
+    /// Drops the annotation when `turn/start` failed.
+    pub(crate) fn cancel(&mut self, workspace_id: &str, thread_id: &str) {
+        self.pending
+            .remove(&(workspace_id.to_string(), thread_id.to_string()));
+    }
+
+    fn running(&mut self, workspace_id: &str, thread_id: &str) -> Option<&mut ProposedPatch> {
+        self.patches.iter_mut().rev().find(|patch| {
+            !patch.completed && patch.workspace_id == workspace_id && patch.thread_id == thread_id
+        })
+    }
+
+    /// True while a dry-run turn of the thread is running.
+    pub(crate) fn is_running(&self, workspace_id: &str, thread_id: &str) -> bool {
+        self.patches.iter().any(|patch| {
+            !patch.completed && patch.workspace_id == workspace_id && patch.thread_id == thread_id
+        })
+    }
+
+    /// Applies a notification; returns the proposed patch of a dry-run turn
+    /// once it completes.
+    pub(crate) fn observe(&mut self, workspace_id: &str, message: &Value) -> Option<ProposedPatch> {
+        let method = message.get("method").and_then(|value| value.as_str())?;
+        let params = message.get("params")?;
+        let thread_id = str_param(params, &["threadId", "thread_id"])?;
+        match method {
+            "turn/started" => {
+                let key = (workspace_id.to_string(), thread_id.to_string());
+                if !self.pending.remove(&key) {
+                    return None;
+                }
+                let turn_id = params
+                    .get("turn")
+                    .and_then(|turn| turn.get("id"))
+                    .and_then(|value| value.as_str())
+                    .or_else(|| str_param(params, &["turnId", "turn_id"]))?;
+                if self.patches.len() >= MAX_PATCHES {
+                    self.patches.pop_front();
+                }
+                self.patches.push_back(ProposedPatch {
+                    workspace_id: workspace_id.to_string(),
+                    thread_id: thread_id.to_string(),
+                    turn_id: turn_id.to_string(),
+                    files: Vec::new(),
+                    completed: false,
+                    patch: String::new(),
+                });
+                None
+            }
+            "item/started" | "item/completed" => {
+                let item = params.get("item")?;
+                if item.get("type").and_then(|value| value.as_str()) != Some("fileChange") {
+                    return None;
+                }
+                if let Some(patch) = self.running(workspace_id, thread_id) {
+                    patch.record_item(item);
+                }
+                None
+            }
+            "turn/completed" => {
+                let patch = self.running(workspace_id, thread_id)?;
+                patch.completed = true;
+                Some(patch.clone())
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn get(&self, workspace_id: &str, turn_id: &str) -> Option<ProposedPatch> {
+        self.patches
+            .iter()
+            .find(|patch| patch.workspace_id == workspace_id && patch.turn_id == turn_id)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_change(status: &str, diff: &str) -> Value {
+        json!({
+            "method": format!("item/{status}"),
+            "params": {
+                "threadId": "thread-1",
+                "item": {
+                    "id": "item-1",
+                    "type": "fileChange",
+                    "changes": [
+                        { "path": "src/lib.rs", "kind": { "type": "update" }, "diff": diff },
+                        { "path": "NOTES.md", "kind": "add", "diff": "+hello" },
+                    ],
+                },
+            }
+        })
+    }
+
+    #[test]
+    fn captures_file_changes_of_dry_run_turns_only() {
+        let mut tracker = DryRunTracker::default();
+        let started = |turn_id: &str| {
+            json!({
+                "method": "turn/started",
+                "params": { "threadId": "thread-1", "turn": { "id": turn_id } },
+            })
+        };
+        tracker.observe("ws", &started("turn-0"));
+        tracker.observe("ws", &file_change("completed", "@@ -1 +1 @@\n-a\n+b"));
+        assert!(tracker.get("ws", "turn-0").is_none());
+
+        tracker.begin("ws", "thread-1");
+        tracker.observe("ws", &started("turn-1"));
+        tracker.observe("ws", &file_change("started", "@@ -1 +1 @@\n-a"));
+        tracker.observe("ws", &file_change("completed", "@@ -1 +1 @@\n-a\n+b"));
+        let patch = tracker.get("ws", "turn-1").expect("patch");
+        assert!(!patch.completed);
+        assert_eq!(patch.files.len(), 2);
+        assert_eq!(
+            patch.patch,
+            "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-a\n+b\n\
+             --- /dev/null\n+++ b/NOTES.md\n+hello\n"
+        );
+
+        tracker.observe(
+            "ws",
+            &json!({
+                "method": "turn/completed",
+                "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } },
+            }),
+        );
+        assert!(tracker.get("ws", "turn-1").expect("patch").completed);
+        tracker.observe("ws", &started("turn-2"));
+        assert!(tracker.get("ws", "turn-2").is_none());
+    }
+}
@@ -0,0 +1,181 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+
+use crate::backend::clock::Timestamp;
+
+/// Events kept per workspace for backfilling a reloaded webview.
+const BUFFER_CAPACITY: usize = 2000;
+
+#[derive(Debug)]
+struct BufferedEvent {
+    seq: u64,
+    payload: Value,
+    /// Approval requests outlive the capacity until acked.
+    pinned: bool,
+}
+
+#[derive(Debug, Default)]
+struct WorkspaceEvents {
+    latest_seq: u64,
+    acked_seq: u64,
+    /// Highest sequence id dropped before being acked.
+    evicted_seq: u64,
+    events: VecDeque<BufferedEvent>,
+}
+
+impl WorkspaceEvents {
+    fn trim(&mut self) {
+        while self.events.len() > BUFFER_CAPACITY {
+            let acked_seq = self.acked_seq;
+            let Some(index) = self
+                .events
+                .iter()
+                .position(|event| !event.pinned || event.seq <= acked_seq)
+            else {
+                return;
+            };
+            if let Some(event) = self.events.remove(index) {
+                if event.seq > acked_seq {
+                    self.evicted_seq = self.evicted_seq.max(event.seq);
+                }
+            }
+        }
+    }
+}
+
+/// Result of `get_events_since`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EventBackfill {
+    pub(crate) events: Vec<Value>,
+    pub(crate) latest_seq: u64,
+    /// `false` when events after the requested id were already dropped;
+    /// the frontend should then reload thread state instead.
+    pub(crate) complete: bool,
+}
+
+/// Sequence-numbered `app-server-event` payloads, per workspace.
+#[derive(Debug, Default)]
+pub(crate) struct EventBuffer {
+    workspaces: HashMap<String, WorkspaceEvents>,
+}
+
+fn is_approval_request(payload: &Value) -> bool {
+    payload
+        .pointer("/message/method")
+        .and_then(|method| method.as_str())
+        .is_some_and(|method| method.ends_with("requestApproval"))
+}
+
+impl EventBuffer {
+    /// Stamps `payload` with the workspace's next `seq` and the time it was
+    /// received, and keeps a copy.
+    pub(crate) fn record(
+        &mut self,
+        workspace_id: &str,
+        payload: &mut Value,
+        received_at: Timestamp,
+    ) -> u64 {
+        let workspace = self.workspaces.entry(workspace_id.to_string()).or_default();
+        workspace.latest_seq += 1;
+        let seq = workspace.latest_seq;
+        if let Some(object) = payload.as_object_mut() {
+            object.insert("seq".to_string(), Value::from(seq));
+            if let Ok(received_at) = serde_json::to_value(received_at) {
+                object.insert("receivedAt".to_string(), received_at);
+            }
+        }
+        workspace.events.push_back(BufferedEvent {
+            seq,
+            payload: payload.clone(),
+            pinned: is_approval_request(payload),
+        });
+        workspace.trim();
+        seq
+    }
+
+    pub(crate) fn ack(&mut self, workspace_id: &str, seq: u64) {
+        if let Some(workspace) = self.workspaces.get_mut(workspace_id) {
+            workspace.acked_seq = workspace.acked_seq.max(seq.min(workspace.latest_seq));
+            workspace.trim();
+        }
+    }
+
+    pub(crate) fn since(&self, workspace_id: &str, seq: u64) -> EventBackfill {
+        let Some(workspace) = self.workspaces.get(workspace_id) else {
+            return EventBackfill {
+                events: Vec::new(),
+                latest_seq: 0,
+                complete: seq == 0,
+            };
+        };
+        EventBackfill {
+            events: workspace
+                .events
+                .iter()
+                .filter(|event| event.seq > seq)
+                .map(|event| event.payload.clone())
+                .collect(),
+            latest_seq: workspace.latest_seq,
+            complete: workspace.evicted_seq <= seq && seq <= workspace.latest_seq,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(method: &str) -> Value {
+        json!({ "workspace_id": "ws", "message": { "method": method } })
+    }
+
+    const AT: Timestamp = Timestamp {
+        utc_ms: 1_000,
+        mono_ms: 10,
+        offset_minutes: 60,
+    };
+
+    #[test]
+    fn stamps_and_backfills_per_workspace() {
+        let mut buffer = EventBuffer::default();
+        let mut first = event("turn/started");
+        assert_eq!(buffer.record("ws", &mut first, AT), 1);
+        assert_eq!(first["seq"], 1);
+        assert_eq!(first["receivedAt"]["utcMs"], 1_000);
+        buffer.record("ws", &mut event("item/started"), AT);
+        buffer.record("other", &mut event("turn/started"), AT);
+
+        let backfill = buffer.since("ws", 1);
+        assert_eq!(backfill.latest_seq, 2);
+        assert!(backfill.complete);
+        assert_eq!(backfill.events.len(), 1);
+        assert_eq!(backfill.events[0]["seq"], 2);
+        assert!(!buffer.since("ws", 5).complete);
+        assert!(buffer.since("unknown", 0).complete);
+    }
+
+    #[test]
+    fn keeps_unacked_approval_requests_past_capacity() {
+        let mut buffer = EventBuffer::default();
+        buffer.record(
+            "ws",
+            &mut event("item/commandExecution/requestApproval"),
+            AT,
+        );
+        for _ in 0..BUFFER_CAPACITY + 10 {
+            buffer.record("ws", &mut event("item/agentMessage/delta"), AT);
+        }
+
+        let backfill = buffer.since("ws", 0);
+        assert!(!backfill.complete);
+        assert_eq!(backfill.events.len(), BUFFER_CAPACITY);
+        assert_eq!(backfill.events[0]["seq"], 1);
+
+        buffer.ack("ws", 1);
+        buffer.record("ws", &mut event("item/agentMessage/delta"), AT);
+        assert_ne!(buffer.since("ws", 0).events[0]["seq"], 1);
+    }
+}
@@ -1,6 +1,9 @@
 use serde::Serialize;
 use serde_json::Value;
 
+pub(crate) use crate::backend::narration::NarrationEvent;
+pub(crate) use crate::backend::session_output::SessionOutputLine;
+
 #[derive(Serialize, Clone)]
 pub(crate) struct AppServerEvent {
     pub(crate) workspace_id: String,
@@ -19,4 +22,6 @@ pub(crate) struct TerminalOutput {
 pub(crate) trait EventSink: Clone + Send + Sync + 'static {
     fn emit_app_server_event(&self, event: AppServerEvent);
     fn emit_terminal_output(&self, event: TerminalOutput);
+    fn emit_narration(&self, event: NarrationEvent);
+    fn emit_session_output(&self, event: SessionOutputLine);
 }
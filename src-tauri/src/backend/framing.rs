@@ -0,0 +1,152 @@
+use std::io::{Error, ErrorKind};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
+
+const CONTENT_LENGTH: &str = "content-length:";
+/// Largest `Content-Length` body accepted, so a corrupt header cannot make
+/// the reader allocate without bound.
+const MAX_FRAME_BYTES: usize = 64 * 1024 * 1024;
+
+/// How JSON-RPC messages are delimited on the app-server's stdio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Framing {
+    /// One JSON message per line.
+    NewlineDelimited,
+    /// LSP-style `Content-Length: N` header, blank line, then N bytes.
+    ContentLength,
+}
+
+impl Framing {
+    pub(crate) fn encode(self, message: &str) -> Vec<u8> {
+        match self {
+            Framing::NewlineDelimited => format!("{message}\n").into_bytes(),
+            Framing::ContentLength => {
+                format!("Content-Length: {}\r\n\r\n{message}", message.len()).into_bytes()
+            }
+        }
+    }
+}
+
+/// Framing used for writes; follows whatever the app-server last sent.
+#[derive(Debug, Default)]
+pub(crate) struct SessionFraming(AtomicU8);
+
+impl SessionFraming {
+    pub(crate) fn get(&self) -> Framing {
+        match self.0.load(Ordering::SeqCst) {
+            1 => Framing::ContentLength,
+            _ => Framing::NewlineDelimited,
+        }
+    }
+
+    pub(crate) fn set(&self, framing: Framing) {
+        let value = match framing {
+            Framing::NewlineDelimited => 0,
+            Framing::ContentLength => 1,
+        };
+        self.0.store(value, Ordering::SeqCst);
+    }
+}
+
+/// Parses a `Content-Length` header line; anything else, including a header
+/// with an unusable length, is treated as a newline-delimited message.
+fn content_length(line: &str) -> Option<usize> {
+    let line = line.trim();
+    let header = line.get(..CONTENT_LENGTH.len())?;
+    if !header.eq_ignore_ascii_case(CONTENT_LENGTH) {
+        return None;
+    }
+    line[CONTENT_LENGTH.len()..]
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|length| *length <= MAX_FRAME_BYTES)
+}
+
+/// Reads the next message in either framing, detected per message from
+/// whether it starts with a `Content-Length` header. Returns `None` at EOF.
+pub(crate) async fn read_frame<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> Result<Option<(String, Framing)>, Error> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(length) = content_length(&line) else {
+            let message = line.trim_end_matches(['\r', '\n']).to_string();
+            return Ok(Some((message, Framing::NewlineDelimited)));
+        };
+        // Skip any other headers, e.g. `Content-Type`, up to the blank line.
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).await? == 0 {
+                return Ok(None);
+            }
+            if line.trim().is_empty() {
+                break;
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).await?;
+        let message =
+            String::from_utf8(body).map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+        return Ok(Some((message, Framing::ContentLength)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(input: &[u8]) -> Vec<(String, Framing)> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let mut reader = input;
+            let mut frames = Vec::new();
+            while let Some(frame) = read_frame(&mut reader).await.expect("frame") {
+                frames.push(frame);
+            }
+            frames
+        })
+    }
+
+    #[test]
+    fn reads_both_framings() {
+        let body = r#"{"id":1,"result":{}}"#;
+        let mut input = b"{\"method\":\"a\"}\n\n".to_vec();
+        input.extend(Framing::ContentLength.encode(body));
+        input.extend(b"content-length: 2\r\nContent-Type: application/json\r\n\r\n{}");
+        input.extend(Framing::NewlineDelimited.encode(r#"{"method":"b"}"#));
+
+        let frames = read_all(&input);
+        assert_eq!(
+            frames,
+            vec![
+                (r#"{"method":"a"}"#.to_string(), Framing::NewlineDelimited),
+                (body.to_string(), Framing::ContentLength),
+                ("{}".to_string(), Framing::ContentLength),
+                (r#"{"method":"b"}"#.to_string(), Framing::NewlineDelimited),
+            ]
+        );
+    }
+
+    #[test]
+    fn session_framing_defaults_to_newlines() {
+        let framing = SessionFraming::default();
+        assert_eq!(framing.get(), Framing::NewlineDelimited);
+        framing.set(Framing::ContentLength);
+        assert_eq!(framing.get(), Framing::ContentLength);
+        assert_eq!(
+            Framing::ContentLength.encode("{}"),
+            b"Content-Length: 2\r\n\r\n{}".to_vec()
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+
+/// Longest window a single grant may cover.
+pub(crate) const MAX_GRANT_MINUTES: u32 = 24 * 60;
+
+/// A window during which a thread's turns run with full access.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FullAccessGrant {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) granted_at: i64,
+    pub(crate) expires_at: i64,
+}
+
+impl FullAccessGrant {
+    pub(crate) fn is_active(&self, now: i64) -> bool {
+        now < self.expires_at
+    }
+
+    fn is_for(&self, workspace_id: &str, thread_id: &str) -> bool {
+        self.workspace_id == workspace_id && self.thread_id == thread_id
+    }
+}
+
+pub(crate) fn full_access_grants_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("full_access_grants.json")
+}
+
+pub(crate) fn read_grants(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<FullAccessGrant>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_grants(
+    path: &Path,
+    cipher: &DataCipher,
+    grants: &[FullAccessGrant],
+) -> Result<(), String> {
+    if grants.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(path).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+    let data = serde_json::to_string_pretty(grants).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+/// Adds a grant, replacing the thread's current one so the window restarts.
+pub(crate) fn upsert_grant(grants: &mut Vec<FullAccessGrant>, grant: FullAccessGrant) {
+    grants.retain(|existing| !existing.is_for(&grant.workspace_id, &grant.thread_id));
+    grants.push(grant);
+}
+
+/// Removes and returns the thread's grant.
+pub(crate) fn remove_grant(
+    grants: &mut Vec<FullAccessGrant>,
+    workspace_id: &str,
+    thread_id: &str,
+) -> Option<FullAccessGrant> {
+    let index = grants
+        .iter()
+        .position(|grant| grant.is_for(workspace_id, thread_id))?;
+    Some(grants.remove(index))
+}
+
+/// Removes and returns the grants whose window has passed.
+pub(crate) fn take_expired(grants: &mut Vec<FullAccessGrant>, now: i64) -> Vec<FullAccessGrant> {
+    let (expired, active) = grants.drain(..).partition(|grant| !grant.is_active(now));
+    *grants = active;
+    expired
+}
+
+pub(crate) fn active_grant<'a>(
+    grants: &'a [FullAccessGrant],
+    workspace_id: &str,
+    thread_id: &str,
+    now: i64,
+) -> Option<&'a FullAccessGrant> {
+    grants
+        .iter()
+        .find(|grant| grant.is_for(workspace_id, thread_id) && grant.is_active(now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant(thread_id: &str, expires_at: i64) -> FullAccessGrant {
+        FullAccessGrant {
+            workspace_id: "ws".to_string(),
+            thread_id: thread_id.to_string(),
+            granted_at: 0,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn grants_expire_after_their_window() {
+        let mut grants = Vec::new();
+        upsert_grant(&mut grants, grant("a", 100));
+        upsert_grant(&mut grants, grant("b", 500));
+        upsert_grant(&mut grants, grant("a", 200));
+        assert_eq!(grants.len(), 2);
+        assert_eq!(
+            active_grant(&grants, "ws", "a", 150).map(|grant| grant.expires_at),
+            Some(200)
+        );
+        assert!(active_grant(&grants, "other", "a", 150).is_none());
+
+        let expired = take_expired(&mut grants, 200);
+        assert_eq!(expired, vec![grant("a", 200)]);
+        assert!(active_grant(&grants, "ws", "a", 200).is_none());
+        assert_eq!(remove_grant(&mut grants, "ws", "b"), Some(grant("b", 500)));
+        assert!(grants.is_empty());
+    }
+}
@@ -0,0 +1,53 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+/// Hex-encoded HMAC-SHA256 of `payload`.
+pub(crate) fn sign_hex(secret: &str, payload: &[u8]) -> Option<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).ok()?;
+    mac.update(payload);
+    Some(
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect(),
+    )
+}
+
+/// Checks a hex-encoded HMAC-SHA256 of `payload` in constant time.
+pub(crate) fn verify_hex(secret: &str, payload: &[u8], signature: &str) -> bool {
+    let Some(expected) = decode_hex(signature.trim()) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(payload);
+    mac.verify_slice(&expected).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_only_matching_hex_signatures() {
+        let signature = sign_hex("s3cret", b"payload").expect("signature");
+        assert!(verify_hex("s3cret", b"payload", &signature));
+        assert!(verify_hex("s3cret", b"payload", &format!(" {signature}\n")));
+        assert!(!verify_hex("other", b"payload", &signature));
+        assert!(!verify_hex("s3cret", b"changed", &signature));
+        assert!(!verify_hex("s3cret", b"payload", &signature[1..]));
+        assert!(!verify_hex("s3cret", b"payload", "zz"));
+    }
+}
@@ -0,0 +1,256 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+pub(crate) const DEFAULT_LOCALE: &str = "en";
+pub(crate) const SUPPORTED_LOCALES: [&str; 5] = ["en", "es", "fr", "de", "ja"];
+
+static CURRENT_LOCALE: RwLock<&'static str> = RwLock::new(DEFAULT_LOCALE);
+
+/// Stable codes for user-facing backend messages. The frontend can render a
+/// code itself, and the backend localizes messages it returns as text.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum MessageCode {
+    CodexNotFound,
+    CodexCheckTimedOut,
+    CodexFailedToStart,
+    CodexFailedToStartDetail,
+    AppServerCheckFailed,
+    AppServerInitializeTimedOut,
+    NodeNotFound,
+    NodeCheckTimedOut,
+    NodeFailedToStart,
+    WorkspaceNotFound,
+    WorkspaceNotConnected,
+}
+
+const ALL_CODES: [MessageCode; 11] = [
+    MessageCode::CodexNotFound,
+    MessageCode::CodexCheckTimedOut,
+    MessageCode::CodexFailedToStart,
+    MessageCode::CodexFailedToStartDetail,
+    MessageCode::AppServerCheckFailed,
+    MessageCode::AppServerInitializeTimedOut,
+    MessageCode::NodeNotFound,
+    MessageCode::NodeCheckTimedOut,
+    MessageCode::NodeFailedToStart,
+    MessageCode::WorkspaceNotFound,
+    MessageCode::WorkspaceNotConnected,
+];
+
+fn english(code: MessageCode) -> &'static str {
+    match code {
+        MessageCode::CodexNotFound => {
+            "Codex CLI not found. Install Codex and ensure `codex` is on your PATH."
+        }
+        MessageCode::CodexCheckTimedOut => {
+            "Timed out while checking Codex CLI. Make sure `codex --version` runs in Terminal."
+        }
+        MessageCode::CodexFailedToStart => {
+            "Codex CLI failed to start. Try running `codex --version` in Terminal."
+        }
+        MessageCode::CodexFailedToStartDetail => {
+            "Codex CLI failed to start: {detail}. Try running `codex --version` in Terminal."
+        }
+        MessageCode::AppServerCheckFailed => "Failed to run `codex app-server --help`.",
+        MessageCode::AppServerInitializeTimedOut => {
+            "Codex app-server did not respond to initialize. Check that `codex app-server` works in Terminal."
+        }
+        MessageCode::NodeNotFound => "Node not found on PATH.",
+        MessageCode::NodeCheckTimedOut => "Timed out while checking Node.",
+        MessageCode::NodeFailedToStart => "Node failed to start.",
+        MessageCode::WorkspaceNotFound => "workspace not found",
+        MessageCode::WorkspaceNotConnected => "workspace not connected",
+    }
+}
+
+fn spanish(code: MessageCode) -> &'static str {
+    match code {
+        MessageCode::CodexNotFound => {
+            "No se encontró Codex CLI. Instala Codex y asegúrate de que `codex` esté en tu PATH."
+        }
+        MessageCode::CodexCheckTimedOut => {
+            "Se agotó el tiempo al comprobar Codex CLI. Asegúrate de que `codex --version` funcione en la Terminal."
+        }
+        MessageCode::CodexFailedToStart => {
+            "Codex CLI no pudo iniciarse. Prueba a ejecutar `codex --version` en la Terminal."
+        }
+        MessageCode::CodexFailedToStartDetail => {
+            "Codex CLI no pudo iniciarse: {detail}. Prueba a ejecutar `codex --version` en la Terminal."
+        }
+        MessageCode::AppServerCheckFailed => "No se pudo ejecutar `codex app-server --help`.",
+        MessageCode::AppServerInitializeTimedOut => {
+            "Codex app-server no respondió a initialize. Comprueba que `codex app-server` funcione en la Terminal."
+        }
+        MessageCode::NodeNotFound => "No se encontró Node en el PATH.",
+        MessageCode::NodeCheckTimedOut => "Se agotó el tiempo al comprobar Node.",
+        MessageCode::NodeFailedToStart => "Node no pudo iniciarse.",
+        MessageCode::WorkspaceNotFound => "espacio de trabajo no encontrado",
+        MessageCode::WorkspaceNotConnected => "espacio de trabajo no conectado",
+    }
+}
+
+fn french(code: MessageCode) -> &'static str {
+    match code {
+        MessageCode::CodexNotFound => {
+            "Codex CLI introuvable. Installez Codex et vérifiez que `codex` est dans votre PATH."
+        }
+        MessageCode::CodexCheckTimedOut => {
+            "Délai dépassé lors de la vérification de Codex CLI. Vérifiez que `codex --version` fonctionne dans le Terminal."
+        }
+        MessageCode::CodexFailedToStart => {
+            "Codex CLI n'a pas pu démarrer. Essayez `codex --version` dans le Terminal."
+        }
+        MessageCode::CodexFailedToStartDetail => {
+            "Codex CLI n'a pas pu démarrer : {detail}. Essayez `codex --version` dans le Terminal."
+        }
+        MessageCode::AppServerCheckFailed => "Impossible d'exécuter `codex app-server --help`.",
+        MessageCode::AppServerInitializeTimedOut => {
+            "Codex app-server n'a pas répondu à initialize. Vérifiez que `codex app-server` fonctionne dans le Terminal."
+        }
+        MessageCode::NodeNotFound => "Node introuvable dans le PATH.",
+        MessageCode::NodeCheckTimedOut => "Délai dépassé lors de la vérification de Node.",
+        MessageCode::NodeFailedToStart => "Node n'a pas pu démarrer.",
+        MessageCode::WorkspaceNotFound => "espace de travail introuvable",
+        MessageCode::WorkspaceNotConnected => "espace de travail non connecté",
+    }
+}
+
+fn german(code: MessageCode) -> &'static str {
+    match code {
+        MessageCode::CodexNotFound => {
+            "Codex CLI nicht gefunden. Installiere Codex und stelle sicher, dass `codex` im PATH liegt."
+        }
+        MessageCode::CodexCheckTimedOut => {
+            "Zeitüberschreitung bei der Prüfung von Codex CLI. Stelle sicher, dass `codex --version` im Terminal läuft."
+        }
+        MessageCode::CodexFailedToStart => {
+            "Codex CLI konnte nicht gestartet werden. Führe `codex --version` im Terminal aus."
+        }
+        MessageCode::CodexFailedToStartDetail => {
+            "Codex CLI konnte nicht gestartet werden: {detail}. Führe `codex --version` im Terminal aus."
+        }
+        MessageCode::AppServerCheckFailed => "`codex app-server --help` konnte nicht ausgeführt werden.",
+        MessageCode::AppServerInitializeTimedOut => {
+            "Codex app-server hat nicht auf initialize geantwortet. Prüfe, ob `codex app-server` im Terminal funktioniert."
+        }
+        MessageCode::NodeNotFound => "Node wurde im PATH nicht gefunden.",
+        MessageCode::NodeCheckTimedOut => "Zeitüberschreitung bei der Prüfung von Node.",
+        MessageCode::NodeFailedToStart => "Node konnte nicht gestartet werden.",
+        MessageCode::WorkspaceNotFound => "Workspace nicht gefunden",
+        MessageCode::WorkspaceNotConnected => "Workspace nicht verbunden",
+    }
+}
+
+fn japanese(code: MessageCode) -> &'static str {
+    match code {
+        MessageCode::CodexNotFound => {
+            "Codex CLI が見つかりません。Codex をインストールし、`codex` が PATH にあることを確認してください。"
+        }
+        MessageCode::CodexCheckTimedOut => {
+            "Codex CLI の確認がタイムアウトしました。ターミナルで `codex --version` が実行できることを確認してください。"
+        }
+        MessageCode::CodexFailedToStart => {
+            "Codex CLI を起動できませんでした。ターミナルで `codex --version` を実行してみてください。"
+        }
+        MessageCode::CodexFailedToStartDetail => {
+            "Codex CLI を起動できませんでした: {detail}。ターミナルで `codex --version` を実行してみてください。"
+        }
+        MessageCode::AppServerCheckFailed => "`codex app-server --help` を実行できませんでした。",
+        MessageCode::AppServerInitializeTimedOut => {
+            "Codex app-server が initialize に応答しませんでした。ターミナルで `codex app-server` が動作するか確認してください。"
+        }
+        MessageCode::NodeNotFound => "PATH に Node が見つかりません。",
+        MessageCode::NodeCheckTimedOut => "Node の確認がタイムアウトしました。",
+        MessageCode::NodeFailedToStart => "Node を起動できませんでした。",
+        MessageCode::WorkspaceNotFound => "ワークスペースが見つかりません",
+        MessageCode::WorkspaceNotConnected => "ワークスペースが接続されていません",
+    }
+}
+
+/// Maps a BCP 47 tag such as `es-MX` to a supported locale, if any.
+pub(crate) fn normalize_locale(locale: &str) -> Option<&'static str> {
+    let language = locale
+        .trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    SUPPORTED_LOCALES
+        .iter()
+        .copied()
+        .find(|supported| *supported == language)
+}
+
+pub(crate) fn translate(code: MessageCode, locale: &str) -> &'static str {
+    match normalize_locale(locale).unwrap_or(DEFAULT_LOCALE) {
+        "es" => spanish(code),
+        "fr" => french(code),
+        "de" => german(code),
+        "ja" => japanese(code),
+        _ => english(code),
+    }
+}
+
+/// Sets the process-wide locale. Unsupported locales fall back to English.
+pub(crate) fn set_locale(locale: Option<&str>) -> &'static str {
+    let resolved = locale.and_then(normalize_locale).unwrap_or(DEFAULT_LOCALE);
+    if let Ok(mut current) = CURRENT_LOCALE.write() {
+        *current = resolved;
+    }
+    resolved
+}
+
+pub(crate) fn current_locale() -> &'static str {
+    CURRENT_LOCALE
+        .read()
+        .map(|current| *current)
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Returns the message for `code` in the current locale.
+pub(crate) fn localize(code: MessageCode) -> String {
+    translate(code, current_locale()).to_string()
+}
+
+/// Like `localize`, substituting `{detail}` in the message.
+pub(crate) fn localize_detail(code: MessageCode, detail: &str) -> String {
+    localize(code).replace("{detail}", detail)
+}
+
+/// The full catalog for a locale, keyed by message code.
+pub(crate) fn catalog(locale: &str) -> BTreeMap<MessageCode, &'static str> {
+    ALL_CODES
+        .iter()
+        .map(|code| (*code, translate(*code, locale)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_regional_tags_and_falls_back_to_english() {
+        assert_eq!(normalize_locale("es-MX"), Some("es"));
+        assert_eq!(normalize_locale("pt_BR"), None);
+        assert_eq!(
+            translate(MessageCode::WorkspaceNotConnected, "pt-BR"),
+            english(MessageCode::WorkspaceNotConnected)
+        );
+        assert_eq!(
+            translate(MessageCode::NodeNotFound, "fr-CA"),
+            "Node introuvable dans le PATH."
+        );
+    }
+
+    #[test]
+    fn every_locale_covers_every_code() {
+        for locale in SUPPORTED_LOCALES {
+            let catalog = catalog(locale);
+            assert_eq!(catalog.len(), ALL_CODES.len());
+            assert!(catalog[&MessageCode::CodexFailedToStartDetail].contains("{detail}"));
+        }
+    }
+}
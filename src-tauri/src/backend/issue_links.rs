@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+
+/// Association between a thread and the tracker issue it was started from.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadIssueLink {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    /// `github` or `jira`.
+    pub(crate) tracker: String,
+    pub(crate) issue_id: String,
+    pub(crate) title: String,
+    pub(crate) url: String,
+    pub(crate) linked_at: i64,
+}
+
+pub(crate) fn issue_links_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("thread_issues.json")
+}
+
+pub(crate) fn read_issue_links(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<ThreadIssueLink>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+/// Records (or replaces) the issue linked to a thread.
+pub(crate) fn record_issue_link(
+    path: &Path,
+    cipher: &DataCipher,
+    link: ThreadIssueLink,
+) -> Result<(), String> {
+    let mut links = read_issue_links(path, cipher)?;
+    links.retain(|existing| {
+        !(existing.workspace_id == link.workspace_id && existing.thread_id == link.thread_id)
+    });
+    links.push(link);
+    let data = serde_json::to_string_pretty(&links).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+pub(crate) fn find_issue_link(
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+) -> Result<Option<ThreadIssueLink>, String> {
+    Ok(read_issue_links(path, cipher)?
+        .into_iter()
+        .find(|link| link.workspace_id == workspace_id && link.thread_id == thread_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn link(thread_id: &str, issue_id: &str) -> ThreadIssueLink {
+        ThreadIssueLink {
+            workspace_id: "ws".to_string(),
+            thread_id: thread_id.to_string(),
+            tracker: "github".to_string(),
+            issue_id: issue_id.to_string(),
+            title: "Bug".to_string(),
+            url: format!("https://github.com/o/r/issues/{issue_id}"),
+            linked_at: 1,
+        }
+    }
+
+    #[test]
+    fn records_and_replaces_links_per_thread() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = issue_links_path(&dir);
+        let cipher = DataCipher::plaintext();
+        assert!(find_issue_link(&path, &cipher, "ws", "t-1")
+            .unwrap()
+            .is_none());
+
+        record_issue_link(&path, &cipher, link("t-1", "1")).expect("record");
+        record_issue_link(&path, &cipher, link("t-2", "2")).expect("record");
+        record_issue_link(&path, &cipher, link("t-1", "3")).expect("replace");
+
+        assert_eq!(read_issue_links(&path, &cipher).unwrap().len(), 2);
+        let found = find_issue_link(&path, &cipher, "ws", "t-1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.issue_id, "3");
+    }
+}
@@ -1,2 +1,79 @@
+pub(crate) mod ab_test;
+pub(crate) mod annotations;
 pub(crate) mod app_server;
+pub(crate) mod approval_audit;
+pub(crate) mod approval_inbox;
+pub(crate) mod atomic_write;
+pub(crate) mod auth_expiry;
+pub(crate) mod background_turn;
+pub(crate) mod best_of;
+pub(crate) mod blame_context;
+pub(crate) mod clock;
+pub(crate) mod code_stats;
+pub(crate) mod command_guard;
+pub(crate) mod command_output;
+#[cfg(debug_assertions)]
+pub(crate) mod connection_sim;
+pub(crate) mod context_files;
+pub(crate) mod directory_grants;
+pub(crate) mod dry_run;
+pub(crate) mod event_replay;
 pub(crate) mod events;
+pub(crate) mod framing;
+pub(crate) mod full_access;
+pub(crate) mod hmac_signature;
+pub(crate) mod i18n;
+pub(crate) mod issue_links;
+pub(crate) mod local_http;
+pub(crate) mod model_stats;
+pub(crate) mod narration;
+pub(crate) mod network;
+pub(crate) mod offline_queue;
+pub(crate) mod patch_staging;
+pub(crate) mod plan_tracker;
+pub(crate) mod prompt_budget;
+pub(crate) mod prompt_insights;
+pub(crate) mod protocol_compat;
+pub(crate) mod protocol_harness;
+pub(crate) mod quick_ask;
+pub(crate) mod quick_search;
+pub(crate) mod rate_limits;
+pub(crate) mod reasoning_visibility;
+pub(crate) mod repo_clone;
+pub(crate) mod request_id;
+pub(crate) mod resource_limits;
+pub(crate) mod review_pair;
+pub(crate) mod rpc_response;
+pub(crate) mod scratch;
+pub(crate) mod secure_storage;
+pub(crate) mod semantic_index;
+pub(crate) mod session_env;
+pub(crate) mod session_output;
+pub(crate) mod stderr_filter;
+pub(crate) mod task_queue;
+pub(crate) mod team_activity;
+pub(crate) mod telemetry;
+pub(crate) mod thread_archive;
+pub(crate) mod thread_branches;
+pub(crate) mod thread_guard;
+pub(crate) mod thread_handoff;
+pub(crate) mod thread_index;
+pub(crate) mod thread_journal;
+pub(crate) mod thread_links;
+pub(crate) mod thread_settings;
+pub(crate) mod thread_sla;
+pub(crate) mod thread_tags;
+pub(crate) mod turn_artifacts;
+pub(crate) mod turn_compare;
+pub(crate) mod turn_cost;
+pub(crate) mod turn_labels;
+pub(crate) mod turn_limiter;
+pub(crate) mod turn_manifest;
+pub(crate) mod turn_summary;
+pub(crate) mod verification;
+pub(crate) mod workspace_health;
+pub(crate) mod workspace_identity;
+pub(crate) mod workspace_lock;
+pub(crate) mod workspace_prewarm;
+pub(crate) mod workspace_validation;
+pub(crate) mod workspace_turns;
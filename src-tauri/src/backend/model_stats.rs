@@ -0,0 +1,283 @@
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::backend::code_stats::analytics_dir;
+use crate::backend::secure_storage::DataCipher;
+
+/// Daily model sample files inside the analytics data class directory.
+const FILE_PREFIX: &str = "model-stats-";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum ModelEvent {
+    #[serde(rename_all = "camelCase")]
+    TurnCompleted {
+        model: Option<String>,
+        effort: Option<String>,
+        status: Option<String>,
+        duration_ms: Option<u64>,
+        total_tokens: u64,
+        lines_changed: usize,
+    },
+    /// The workspace's verification command ran after the turn.
+    Verified { passed: bool },
+}
+
+/// One turn outcome, stored in the analytics data class.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelSample {
+    pub(crate) workspace_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) recorded_at: i64,
+    #[serde(flatten)]
+    pub(crate) event: ModelEvent,
+}
+
+/// How one model and effort combination did.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelStats {
+    /// `None` for turns that ran on the app-server's default model.
+    pub(crate) model: Option<String>,
+    pub(crate) effort: Option<String>,
+    pub(crate) turns: usize,
+    pub(crate) average_duration_ms: Option<u64>,
+    /// Share of turns that were interrupted or failed.
+    pub(crate) interrupt_rate: f64,
+    pub(crate) verified_turns: usize,
+    /// Share of verified turns whose verification passed.
+    pub(crate) verification_pass_rate: Option<f64>,
+    /// Completed turns that changed code and did not fail verification.
+    pub(crate) accepted_changes: usize,
+    pub(crate) tokens_per_accepted_change: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelComparison {
+    pub(crate) days: u32,
+    /// Most used combination first.
+    pub(crate) models: Vec<ModelStats>,
+}
+
+fn day_file(data_dir: &Path, day: NaiveDate) -> PathBuf {
+    analytics_dir(data_dir).join(format!("{FILE_PREFIX}{}.json", day.format("%Y-%m-%d")))
+}
+
+/// Model sample files, which follow the encryption setting.
+pub(crate) fn model_sample_files(data_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(analytics_dir(data_dir)) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(FILE_PREFIX))
+        })
+        .collect()
+}
+
+fn read_day(path: &Path, cipher: &DataCipher) -> Result<Vec<ModelSample>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn record_sample(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    sample: ModelSample,
+) -> Result<(), String> {
+    let day = chrono::DateTime::from_timestamp_millis(sample.recorded_at)
+        .unwrap_or_else(Utc::now)
+        .date_naive();
+    let path = day_file(data_dir, day);
+    let mut samples = read_day(&path, cipher)?;
+    samples.push(sample);
+    let data = serde_json::to_string(&samples).map_err(|e| e.to_string())?;
+    cipher.write(&path, &data)
+}
+
+/// Samples over the last `days` days, optionally of one workspace.
+pub(crate) fn read_samples(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: Option<&str>,
+    days: u32,
+    today: NaiveDate,
+) -> Result<Vec<ModelSample>, String> {
+    let mut samples = Vec::new();
+    for offset in (0..days).rev() {
+        let day = today - Duration::days(i64::from(offset));
+        samples.extend(
+            read_day(&day_file(data_dir, day), cipher)?
+                .into_iter()
+                .filter(|sample| {
+                    workspace_id.is_none_or(|workspace_id| sample.workspace_id == workspace_id)
+                }),
+        );
+    }
+    Ok(samples)
+}
+
+#[derive(Default)]
+struct Totals {
+    turns: usize,
+    interrupted: usize,
+    duration_ms: u64,
+    timed_turns: u64,
+    verified: usize,
+    passed: usize,
+    accepted: usize,
+    tokens: u64,
+}
+
+/// Groups completed turns by model and effort. A turn's last verification
+/// counts, so a failure fixed by a re-run is a pass.
+pub(crate) fn compare_models(samples: &[ModelSample]) -> Vec<ModelStats> {
+    let mut verdicts: HashMap<(&str, &str), bool> = HashMap::new();
+    for sample in samples {
+        if let ModelEvent::Verified { passed } = sample.event {
+            verdicts.insert(
+                (sample.workspace_id.as_str(), sample.turn_id.as_str()),
+                passed,
+            );
+        }
+    }
+    let mut groups: BTreeMap<(Option<String>, Option<String>), Totals> = BTreeMap::new();
+    for sample in samples {
+        let ModelEvent::TurnCompleted {
+            model,
+            effort,
+            status,
+            duration_ms,
+            total_tokens,
+            lines_changed,
+        } = &sample.event
+        else {
+            continue;
+        };
+        let totals = groups.entry((model.clone(), effort.clone())).or_default();
+        let verdict = verdicts
+            .get(&(sample.workspace_id.as_str(), sample.turn_id.as_str()))
+            .copied();
+        let interrupted = matches!(status.as_deref(), Some("interrupted" | "failed"));
+        totals.turns += 1;
+        totals.tokens += total_tokens;
+        if interrupted {
+            totals.interrupted += 1;
+        }
+        if let Some(duration_ms) = duration_ms {
+            totals.duration_ms += duration_ms;
+            totals.timed_turns += 1;
+        }
+        if let Some(passed) = verdict {
+            totals.verified += 1;
+            totals.passed += usize::from(passed);
+        }
+        if !interrupted && *lines_changed > 0 && verdict != Some(false) {
+            totals.accepted += 1;
+        }
+    }
+    let mut models: Vec<ModelStats> = groups
+        .into_iter()
+        .map(|((model, effort), totals)| ModelStats {
+            model,
+            effort,
+            turns: totals.turns,
+            average_duration_ms: (totals.timed_turns > 0)
+                .then(|| totals.duration_ms / totals.timed_turns),
+            interrupt_rate: totals.interrupted as f64 / totals.turns as f64,
+            verified_turns: totals.verified,
+            verification_pass_rate: (totals.verified > 0)
+                .then(|| totals.passed as f64 / totals.verified as f64),
+            accepted_changes: totals.accepted,
+            tokens_per_accepted_change: (totals.accepted > 0)
+                .then(|| totals.tokens / totals.accepted as u64),
+        })
+        .collect();
+    models.sort_by_key(|stats| std::cmp::Reverse(stats.turns));
+    models
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completed(turn_id: &str, model: &str, status: &str, lines_changed: usize) -> ModelSample {
+        ModelSample {
+            workspace_id: "ws".to_string(),
+            turn_id: turn_id.to_string(),
+            recorded_at: 0,
+            event: ModelEvent::TurnCompleted {
+                model: Some(model.to_string()),
+                effort: Some("high".to_string()),
+                status: Some(status.to_string()),
+                duration_ms: Some(1_000),
+                total_tokens: 3_000,
+                lines_changed,
+            },
+        }
+    }
+
+    fn verified(turn_id: &str, passed: bool) -> ModelSample {
+        ModelSample {
+            workspace_id: "ws".to_string(),
+            turn_id: turn_id.to_string(),
+            recorded_at: 0,
+            event: ModelEvent::Verified { passed },
+        }
+    }
+
+    #[test]
+    fn compares_models_by_outcome() {
+        let models = compare_models(&[
+            completed("t1", "gpt-5", "completed", 10),
+            verified("t1", false),
+            verified("t1", true),
+            completed("t2", "gpt-5", "completed", 4),
+            verified("t2", false),
+            completed("t3", "gpt-5", "interrupted", 2),
+            completed("t4", "gpt-5-mini", "completed", 0),
+        ]);
+        assert_eq!(models.len(), 2);
+        let gpt5 = &models[0];
+        assert_eq!(gpt5.model.as_deref(), Some("gpt-5"));
+        assert_eq!(gpt5.turns, 3);
+        assert_eq!(gpt5.average_duration_ms, Some(1_000));
+        assert!((gpt5.interrupt_rate - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(gpt5.verified_turns, 2);
+        assert_eq!(gpt5.verification_pass_rate, Some(0.5));
+        assert_eq!(gpt5.accepted_changes, 1);
+        assert_eq!(gpt5.tokens_per_accepted_change, Some(9_000));
+        assert_eq!(models[1].accepted_changes, 0);
+        assert_eq!(models[1].tokens_per_accepted_change, None);
+    }
+
+    #[test]
+    fn stores_samples_per_day() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        let mut sample = completed("t1", "gpt-5", "completed", 1);
+        sample.recorded_at = Utc::now().timestamp_millis();
+        record_sample(&dir, &cipher, sample.clone()).unwrap();
+        let today = Utc::now().date_naive();
+        assert_eq!(
+            read_samples(&dir, &cipher, Some("ws"), 1, today).unwrap(),
+            vec![sample]
+        );
+        assert!(read_samples(&dir, &cipher, Some("other"), 1, today)
+            .unwrap()
+            .is_empty());
+        assert_eq!(model_sample_files(&dir).len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
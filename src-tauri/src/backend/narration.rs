@@ -0,0 +1,245 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::backend::turn_summary::{excerpt, params_thread_id};
+
+/// Minimum gap between routine narrations for the same thread.
+const NARRATION_INTERVAL: Duration = Duration::from_secs(3);
+const COMMAND_MAX_CHARS: usize = 80;
+const PATH_MAX_CHARS: usize = 60;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NarrationEvent {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) text: String,
+    /// Urgent narrations (approvals, errors, turn end) bypass throttling and
+    /// should be announced assertively by screen readers.
+    pub(crate) urgent: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Narration {
+    text: String,
+    urgent: bool,
+}
+
+impl Narration {
+    fn routine(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            urgent: false,
+        }
+    }
+
+    fn urgent(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            urgent: true,
+        }
+    }
+}
+
+fn command_text(value: Option<&Value>) -> Option<String> {
+    let text = match value? {
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| part.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        Value::String(text) => text.clone(),
+        _ => return None,
+    };
+    let text = excerpt(&text, COMMAND_MAX_CHARS);
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Keeps short relative paths as-is and falls back to the file name otherwise.
+fn display_path(path: &str) -> &str {
+    if path.chars().count() <= PATH_MAX_CHARS {
+        return path;
+    }
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path)
+}
+
+fn describe_file_changes(changes: Option<&Value>) -> Option<String> {
+    let paths: Vec<&str> = changes?
+        .as_array()?
+        .iter()
+        .filter_map(|change| change.get("path").and_then(|path| path.as_str()))
+        .collect();
+    let first = display_path(paths.first()?);
+    Some(match paths.len() {
+        1 => first.to_string(),
+        2 => format!("{first} and 1 other file"),
+        count => format!("{first} and {} other files", count - 1),
+    })
+}
+
+fn describe_item_started(item: &Value) -> Option<Narration> {
+    let text = match item.get("type").and_then(|value| value.as_str())? {
+        "commandExecution" => match command_text(item.get("command")) {
+            Some(command) => format!("Agent is running {command}"),
+            None => "Agent is running a command".to_string(),
+        },
+        "fileChange" => match describe_file_changes(item.get("changes")) {
+            Some(files) => format!("Agent is editing {files}"),
+            None => "Agent is editing files".to_string(),
+        },
+        "reasoning" => "Agent is thinking".to_string(),
+        "agentMessage" => "Agent is writing a reply".to_string(),
+        "mcpToolCall" => match item.get("tool").and_then(|value| value.as_str()) {
+            Some(tool) if !tool.is_empty() => format!("Agent is calling the {tool} tool"),
+            _ => "Agent is calling a tool".to_string(),
+        },
+        "webSearch" => "Agent is searching the web".to_string(),
+        _ => return None,
+    };
+    Some(Narration::routine(text))
+}
+
+/// Converts an app-server message into a plain-language status line.
+fn describe(message: &Value) -> Option<Narration> {
+    let method = message.get("method").and_then(|value| value.as_str())?;
+    let params = message.get("params");
+    match method {
+        "turn/started" => Some(Narration::routine("Agent started working")),
+        "item/started" => describe_item_started(params?.get("item")?),
+        "item/commandExecution/requestApproval" => {
+            let command = command_text(params.and_then(|params| params.get("command")));
+            Some(Narration::urgent(match command {
+                Some(command) => format!("Awaiting approval to run {command}"),
+                None => "Awaiting approval to run a command".to_string(),
+            }))
+        }
+        "item/fileChange/requestApproval" => {
+            Some(Narration::urgent("Awaiting approval to apply file changes"))
+        }
+        "turn/completed" => {
+            let status = params
+                .and_then(|params| params.get("turn"))
+                .and_then(|turn| turn.get("status"))
+                .and_then(|status| status.as_str())
+                .unwrap_or("completed");
+            Some(Narration::urgent(match status {
+                "failed" => "Agent turn failed",
+                "interrupted" => "Agent was stopped",
+                _ => "Agent finished",
+            }))
+        }
+        "error" => {
+            let will_retry = params
+                .and_then(|params| params.get("willRetry"))
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false);
+            if will_retry {
+                Some(Narration::routine("Agent hit an error and is retrying"))
+            } else {
+                Some(Narration::urgent("Agent hit an error"))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Produces throttled narration events from the app-server message stream.
+#[derive(Default)]
+pub(crate) struct Narrator {
+    last_emitted: HashMap<Option<String>, (Instant, String)>,
+}
+
+impl Narrator {
+    pub(crate) fn observe(
+        &mut self,
+        workspace_id: &str,
+        message: &Value,
+        now: Instant,
+    ) -> Option<NarrationEvent> {
+        let narration = describe(message)?;
+        let thread_id = message.get("params").and_then(params_thread_id);
+        if let Some((at, text)) = self.last_emitted.get(&thread_id) {
+            if *text == narration.text {
+                return None;
+            }
+            if !narration.urgent && now.duration_since(*at) < NARRATION_INTERVAL {
+                return None;
+            }
+        }
+        self.last_emitted
+            .insert(thread_id.clone(), (now, narration.text.clone()));
+        Some(NarrationEvent {
+            workspace_id: workspace_id.to_string(),
+            thread_id,
+            text: narration.text,
+            urgent: narration.urgent,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn item_started(thread_id: &str, item: Value) -> Value {
+        json!({
+            "method": "item/started",
+            "params": { "threadId": thread_id, "item": item },
+        })
+    }
+
+    #[test]
+    fn describes_items_and_approvals() {
+        let edit = item_started(
+            "t-1",
+            json!({ "type": "fileChange", "changes": [{ "path": "src/main.rs" }] }),
+        );
+        assert_eq!(describe(&edit).unwrap().text, "Agent is editing src/main.rs");
+
+        let approval = json!({
+            "id": 4,
+            "method": "item/commandExecution/requestApproval",
+            "params": { "threadId": "t-1", "command": ["npm", "install"] },
+        });
+        let narration = describe(&approval).unwrap();
+        assert_eq!(narration.text, "Awaiting approval to run npm install");
+        assert!(narration.urgent);
+        assert!(describe(&json!({ "method": "item/agentMessage/delta" })).is_none());
+    }
+
+    #[test]
+    fn throttles_routine_updates_but_not_urgent_ones() {
+        let mut narrator = Narrator::default();
+        let start = Instant::now();
+        let reasoning = item_started("t-1", json!({ "type": "reasoning" }));
+        let command = item_started("t-1", json!({ "type": "commandExecution", "command": "ls" }));
+        let done = json!({
+            "method": "turn/completed",
+            "params": { "threadId": "t-1", "turn": { "status": "completed" } },
+        });
+
+        assert!(narrator.observe("ws", &reasoning, start).is_some());
+        assert!(narrator
+            .observe("ws", &command, start + Duration::from_secs(1))
+            .is_none());
+        let urgent = narrator
+            .observe("ws", &done, start + Duration::from_secs(1))
+            .expect("urgent narration");
+        assert_eq!(urgent.text, "Agent finished");
+        assert_eq!(urgent.thread_id.as_deref(), Some("t-1"));
+        assert!(narrator
+            .observe("ws", &command, start + NARRATION_INTERVAL * 2)
+            .is_some());
+    }
+}
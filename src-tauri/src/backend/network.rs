@@ -0,0 +1,81 @@
+use std::sync::RwLock;
+
+use tokio::process::Command;
+
+use crate::types::NetworkSettings;
+
+/// Variables that point Node and native TLS stacks at a custom CA bundle.
+const CA_BUNDLE_VARS: &[&str] = &["SSL_CERT_FILE", "NODE_EXTRA_CA_CERTS", "REQUESTS_CA_BUNDLE"];
+
+static NETWORK_ENV: RwLock<Vec<(&'static str, String)>> = RwLock::new(Vec::new());
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Environment variables for Codex processes. Proxies are set in upper and
+/// lower case since tools disagree on which one they read.
+pub(crate) fn network_env(settings: &NetworkSettings) -> Vec<(&'static str, String)> {
+    let mut vars = Vec::new();
+    for (value, names) in [
+        (&settings.http_proxy, ["HTTP_PROXY", "http_proxy"]),
+        (&settings.https_proxy, ["HTTPS_PROXY", "https_proxy"]),
+        (&settings.no_proxy, ["NO_PROXY", "no_proxy"]),
+    ] {
+        if let Some(value) = non_empty(value) {
+            vars.extend(names.map(|name| (name, value.clone())));
+        }
+    }
+    if let Some(path) = non_empty(&settings.ca_bundle_path) {
+        vars.extend(CA_BUNDLE_VARS.iter().map(|name| (*name, path.clone())));
+    }
+    vars
+}
+
+/// Replaces the proxy and CA environment; called when settings load or
+/// change. Sessions started earlier keep their environment until restarted.
+pub(crate) fn set_network_settings(settings: &NetworkSettings) {
+    if let Ok(mut current) = NETWORK_ENV.write() {
+        *current = network_env(settings);
+    }
+}
+
+/// Applies the configured proxy and CA environment to a Codex command.
+pub(crate) fn apply_network_env(command: &mut Command) {
+    if let Ok(vars) = NETWORK_ENV.read() {
+        for (name, value) in vars.iter() {
+            command.env(name, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets_proxy_and_ca_variables() {
+        let settings = NetworkSettings {
+            http_proxy: Some(" ".to_string()),
+            https_proxy: Some("http://proxy.corp:3128".to_string()),
+            no_proxy: None,
+            ca_bundle_path: Some("/etc/corp/ca.pem".to_string()),
+        };
+        let vars = network_env(&settings);
+        assert_eq!(
+            vars,
+            vec![
+                ("HTTPS_PROXY", "http://proxy.corp:3128".to_string()),
+                ("https_proxy", "http://proxy.corp:3128".to_string()),
+                ("SSL_CERT_FILE", "/etc/corp/ca.pem".to_string()),
+                ("NODE_EXTRA_CA_CERTS", "/etc/corp/ca.pem".to_string()),
+                ("REQUESTS_CA_BUNDLE", "/etc/corp/ca.pem".to_string()),
+            ]
+        );
+        assert!(network_env(&NetworkSettings::default()).is_empty());
+    }
+}
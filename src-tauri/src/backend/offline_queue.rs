@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+
+/// A message sent while its workspace was disconnected, held until the
+/// session reconnects.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OfflineTurn {
+    pub(crate) id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) text: String,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) effort: Option<String>,
+    #[serde(default)]
+    pub(crate) access_mode: Option<String>,
+    #[serde(default)]
+    pub(crate) images: Option<Vec<String>>,
+    #[serde(default)]
+    pub(crate) collaboration_mode: Option<Value>,
+    #[serde(default)]
+    pub(crate) dry_run: bool,
+    pub(crate) queued_at: i64,
+}
+
+pub(crate) fn offline_queue_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("offline_queue.json")
+}
+
+pub(crate) fn read_turns(path: &Path, cipher: &DataCipher) -> Result<Vec<OfflineTurn>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+fn write_turns(path: &Path, cipher: &DataCipher, turns: &[OfflineTurn]) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(turns).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+pub(crate) fn enqueue_turn(
+    path: &Path,
+    cipher: &DataCipher,
+    turn: OfflineTurn,
+) -> Result<(), String> {
+    let mut turns = read_turns(path, cipher)?;
+    turns.push(turn);
+    write_turns(path, cipher, &turns)
+}
+
+/// Removes a queued turn; returns `false` when it was already dispatched or
+/// cancelled.
+pub(crate) fn remove_turn(path: &Path, cipher: &DataCipher, turn_id: &str) -> Result<bool, String> {
+    let mut turns = read_turns(path, cipher)?;
+    let before = turns.len();
+    turns.retain(|turn| turn.id != turn_id);
+    if turns.len() == before {
+        return Ok(false);
+    }
+    write_turns(path, cipher, &turns)?;
+    Ok(true)
+}
+
+/// Removes and returns the oldest queued turn of each thread of a
+/// workspace, or only of `thread_id` when given. Later turns of a thread wait
+/// until the dispatched one completes.
+pub(crate) fn take_next_turns(
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: Option<&str>,
+) -> Result<Vec<OfflineTurn>, String> {
+    let mut taken: Vec<OfflineTurn> = Vec::new();
+    let mut kept = Vec::new();
+    for turn in read_turns(path, cipher)? {
+        let matches = turn.workspace_id == workspace_id
+            && thread_id.is_none_or(|thread_id| turn.thread_id == thread_id)
+            && !taken.iter().any(|next| next.thread_id == turn.thread_id);
+        if matches {
+            taken.push(turn);
+        } else {
+            kept.push(turn);
+        }
+    }
+    if !taken.is_empty() {
+        write_turns(path, cipher, &kept)?;
+    }
+    Ok(taken)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn turn(id: &str, workspace_id: &str, thread_id: &str) -> OfflineTurn {
+        OfflineTurn {
+            id: id.to_string(),
+            workspace_id: workspace_id.to_string(),
+            thread_id: thread_id.to_string(),
+            text: "Fix the build".to_string(),
+            model: None,
+            effort: None,
+            access_mode: None,
+            images: None,
+            collaboration_mode: None,
+            dry_run: false,
+            queued_at: 1,
+        }
+    }
+
+    #[test]
+    fn takes_the_oldest_turn_per_thread() {
+        let path = std::env::temp_dir()
+            .join(format!("codex-monitor-test-{}", Uuid::new_v4()))
+            .join("offline_queue.json");
+        let cipher = DataCipher::plaintext();
+        for (id, workspace_id, thread_id) in [
+            ("a", "ws-1", "t-1"),
+            ("b", "ws-2", "t-1"),
+            ("c", "ws-1", "t-1"),
+            ("d", "ws-1", "t-2"),
+            ("e", "ws-1", "t-2"),
+        ] {
+            enqueue_turn(&path, &cipher, turn(id, workspace_id, thread_id)).expect("enqueue");
+        }
+        assert!(remove_turn(&path, &cipher, "e").expect("remove"));
+        assert!(!remove_turn(&path, &cipher, "e").expect("remove"));
+
+        let ids = |turns: Vec<OfflineTurn>| -> Vec<String> {
+            turns.into_iter().map(|turn| turn.id).collect()
+        };
+        let taken = take_next_turns(&path, &cipher, "ws-1", None).expect("take");
+        assert_eq!(ids(taken), vec!["a", "d"]);
+        let taken = take_next_turns(&path, &cipher, "ws-1", Some("t-2")).expect("take");
+        assert!(taken.is_empty());
+        let taken = take_next_turns(&path, &cipher, "ws-1", Some("t-1")).expect("take");
+        assert_eq!(ids(taken), vec!["c"]);
+        assert_eq!(ids(read_turns(&path, &cipher).expect("read")), vec!["b"]);
+    }
+}
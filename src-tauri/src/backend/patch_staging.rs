@@ -0,0 +1,569 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Component, Path, PathBuf};
+
+use crate::backend::atomic_write::{apply_operations, FileOperation, FileWriteError};
+use crate::backend::command_output::safe_file_name;
+use crate::backend::secure_storage::DataCipher;
+
+/// Staged patches kept per workspace, oldest dropped first.
+const MAX_STAGED_PATCHES: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum StagedPatchSource {
+    DryRun,
+    DeclinedApproval,
+    /// Changes made in a scratch workspace, staged for its source.
+    Scratch,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PatchHunk {
+    /// The `@@ -a,b +c,d @@` line, or a synthetic one for whole-file changes.
+    pub(crate) header: String,
+    pub(crate) old_start: usize,
+    /// Lines prefixed with ` `, `-` or `+`.
+    pub(crate) lines: Vec<String>,
+    pub(crate) applied: bool,
+}
+
+impl PatchHunk {
+    fn side(&self, keep: char) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|line| {
+                let mut chars = line.chars();
+                match chars.next() {
+                    Some(' ') => Some(chars.as_str()),
+                    Some(prefix) if prefix == keep => Some(chars.as_str()),
+                    None => Some(""),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    fn old_lines(&self) -> Vec<&str> {
+        self.side('-')
+    }
+
+    fn new_lines(&self) -> Vec<&str> {
+        self.side('+')
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StagedFile {
+    pub(crate) path: String,
+    /// `add`, `delete` or `update`.
+    pub(crate) kind: Option<String>,
+    pub(crate) hunks: Vec<PatchHunk>,
+}
+
+/// File changes the agent proposed that were not applied.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StagedPatch {
+    pub(crate) id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: Option<String>,
+    pub(crate) source: StagedPatchSource,
+    pub(crate) created_at: i64,
+    pub(crate) files: Vec<StagedFile>,
+}
+
+impl StagedPatch {
+    fn is_fully_applied(&self) -> bool {
+        self.files
+            .iter()
+            .all(|file| file.hunks.iter().all(|hunk| hunk.applied))
+    }
+}
+
+/// Identifies one hunk of a staged patch.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HunkRef {
+    pub(crate) path: String,
+    pub(crate) hunk: usize,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HunkConflict {
+    pub(crate) path: String,
+    pub(crate) hunk: usize,
+    pub(crate) reason: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApplyReport {
+    pub(crate) applied: Vec<HunkRef>,
+    pub(crate) conflicts: Vec<HunkConflict>,
+    /// Files that could not be written. When set, no file was changed and
+    /// every selected hunk stays staged.
+    pub(crate) errors: Vec<FileWriteError>,
+    /// True once every hunk is applied and the patch left the staging area.
+    pub(crate) patch_completed: bool,
+}
+
+fn parse_header(line: &str) -> Option<usize> {
+    let old = line.strip_prefix("@@ -")?.split_whitespace().next()?;
+    old.split(',').next()?.parse().ok()
+}
+
+/// Splits a file change's diff into hunks. Added and deleted files may carry
+/// their full contents instead of a unified diff.
+pub(crate) fn parse_hunks(kind: Option<&str>, diff: &str) -> Vec<PatchHunk> {
+    let mut hunks: Vec<PatchHunk> = Vec::new();
+    for line in diff.lines() {
+        if line.starts_with("@@") {
+            hunks.push(PatchHunk {
+                header: line.to_string(),
+                old_start: parse_header(line).unwrap_or(0),
+                lines: Vec::new(),
+                applied: false,
+            });
+            continue;
+        }
+        let Some(hunk) = hunks.last_mut() else {
+            continue;
+        };
+        if line.starts_with('\\') {
+            continue;
+        }
+        if line.is_empty() || matches!(line.as_bytes()[0], b' ' | b'+' | b'-') {
+            hunk.lines.push(line.to_string());
+        }
+    }
+    if !hunks.is_empty() || diff.is_empty() {
+        return hunks;
+    }
+    let prefix = match kind {
+        Some("add") => '+',
+        Some("delete") => '-',
+        _ => return hunks,
+    };
+    let content: Vec<&str> = diff.lines().collect();
+    let prefixed = content.iter().all(|line| line.starts_with(prefix));
+    let lines: Vec<String> = content
+        .iter()
+        .map(|line| {
+            let body = if prefixed { &line[1..] } else { line };
+            format!("{prefix}{body}")
+        })
+        .collect();
+    let count = lines.len();
+    vec![PatchHunk {
+        header: if prefix == '+' {
+            format!("@@ -0,0 +1,{count} @@")
+        } else {
+            format!("@@ -1,{count} +0,0 @@")
+        },
+        old_start: if prefix == '+' { 0 } else { 1 },
+        lines,
+        applied: false,
+    }]
+}
+
+/// Applies one hunk in place. `offset` tracks how far earlier hunks moved
+/// the file; a hunk whose context is not at its recorded position is
+/// matched at the nearest position with the same lines.
+fn apply_hunk(lines: &mut Vec<String>, hunk: &PatchHunk, offset: &mut isize) -> Result<(), String> {
+    let old = hunk.old_lines();
+    let new = hunk.new_lines();
+    let expected = (hunk.old_start.saturating_sub(1) as isize + *offset).max(0) as usize;
+    let matches_at = |start: usize| {
+        start + old.len() <= lines.len()
+            && lines[start..start + old.len()]
+                .iter()
+                .zip(&old)
+                .all(|(line, old)| line == old)
+    };
+    let start = if old.is_empty() {
+        expected.min(lines.len())
+    } else if matches_at(expected) {
+        expected
+    } else {
+        (0..=lines.len().saturating_sub(old.len()))
+            .filter(|start| matches_at(*start))
+            .min_by_key(|start| start.abs_diff(expected))
+            .ok_or("The file no longer matches the hunk's context")?
+    };
+    lines.splice(
+        start..start + old.len(),
+        new.iter().map(|line| line.to_string()),
+    );
+    *offset += start as isize - expected as isize + new.len() as isize - old.len() as isize;
+    Ok(())
+}
+
+/// Resolves a change path against the workspace, refusing paths outside it.
+pub(crate) fn resolve_path(workspace_root: &Path, path: &str) -> Result<PathBuf, String> {
+    let candidate = Path::new(path);
+    let relative = if candidate.is_absolute() {
+        candidate
+            .strip_prefix(workspace_root)
+            .map_err(|_| format!("{path} is outside the workspace"))?
+    } else {
+        candidate
+    };
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(format!("{path} is outside the workspace"));
+    }
+    Ok(workspace_root.join(relative))
+}
+
+/// Result of each selected hunk, by hunk index.
+pub(crate) type HunkResults = Vec<(usize, Result<(), String>)>;
+
+/// What applying a file's hunks does to the file.
+#[derive(Debug, PartialEq)]
+pub(crate) enum FileOutcome {
+    Unchanged,
+    Write(String),
+    Remove,
+}
+
+/// Applies the selected hunks of one file to its current contents
+/// (`None` when the file does not exist). Returns the new file state and
+/// each selected hunk's result.
+pub(crate) fn apply_file(
+    file: &StagedFile,
+    selected: &[usize],
+    current: Option<&str>,
+) -> (FileOutcome, HunkResults) {
+    let kind = file.kind.as_deref();
+    let mut results = Vec::new();
+    let Some(current) = current else {
+        if kind != Some("add") {
+            let results = selected
+                .iter()
+                .map(|index| (*index, Err("The file no longer exists".to_string())))
+                .collect();
+            return (FileOutcome::Unchanged, results);
+        }
+        let mut lines = Vec::new();
+        let mut offset = 0;
+        for index in selected {
+            let hunk = &file.hunks[*index];
+            results.push((*index, apply_hunk(&mut lines, hunk, &mut offset)));
+        }
+        let outcome = if results.iter().any(|(_, result)| result.is_ok()) {
+            FileOutcome::Write(join_lines(&lines, true))
+        } else {
+            FileOutcome::Unchanged
+        };
+        return (outcome, results);
+    };
+    let trailing_newline = current.is_empty() || current.ends_with('\n');
+    let mut lines: Vec<String> = current.lines().map(str::to_string).collect();
+    let mut offset = 0;
+    for index in selected {
+        let hunk = &file.hunks[*index];
+        let result = if kind == Some("add") && !current.is_empty() {
+            Err("The file already exists".to_string())
+        } else {
+            apply_hunk(&mut lines, hunk, &mut offset)
+        };
+        results.push((*index, result));
+    }
+    if results.iter().all(|(_, result)| result.is_err()) {
+        return (FileOutcome::Unchanged, results);
+    }
+    let all_applied = file.hunks.iter().enumerate().all(|(index, hunk)| {
+        hunk.applied
+            || results
+                .iter()
+                .any(|(done, result)| *done == index && result.is_ok())
+    });
+    if kind == Some("delete") && all_applied && lines.is_empty() {
+        return (FileOutcome::Remove, results);
+    }
+    (
+        FileOutcome::Write(join_lines(&lines, trailing_newline)),
+        results,
+    )
+}
+
+/// Applies the selected hunks (all unapplied ones when `hunks` is `None`)
+/// to the files under `workspace_root`.
+pub(crate) fn apply_to_workspace(
+    workspace_root: &Path,
+    backup_dir: &Path,
+    patch: &StagedPatch,
+    hunks: Option<&[HunkRef]>,
+) -> ApplyReport {
+    let mut report = ApplyReport::default();
+    let mut operations = Vec::new();
+    for file in &patch.files {
+        let selected: Vec<usize> = file
+            .hunks
+            .iter()
+            .enumerate()
+            .filter(|(index, hunk)| {
+                !hunk.applied
+                    && hunks.is_none_or(|hunks| {
+                        hunks
+                            .iter()
+                            .any(|wanted| wanted.path == file.path && wanted.hunk == *index)
+                    })
+            })
+            .map(|(index, _)| index)
+            .collect();
+        if selected.is_empty() {
+            continue;
+        }
+        let conflict = |hunk: usize, reason: String| HunkConflict {
+            path: file.path.clone(),
+            hunk,
+            reason,
+        };
+        let path = match resolve_path(workspace_root, &file.path) {
+            Ok(path) => path,
+            Err(err) => {
+                report
+                    .conflicts
+                    .extend(selected.iter().map(|hunk| conflict(*hunk, err.clone())));
+                continue;
+            }
+        };
+        let current = std::fs::read_to_string(&path).ok();
+        let (outcome, results) = apply_file(file, &selected, current.as_deref());
+        let display = path
+            .strip_prefix(workspace_root)
+            .map(|relative| relative.to_string_lossy().to_string())
+            .unwrap_or_else(|_| file.path.clone());
+        match outcome {
+            FileOutcome::Unchanged => {}
+            FileOutcome::Write(contents) => {
+                operations.push((display, path, FileOperation::Write(contents)))
+            }
+            FileOutcome::Remove => operations.push((display, path, FileOperation::Remove)),
+        }
+        for (hunk, result) in results {
+            match result {
+                Ok(()) => report.applied.push(HunkRef {
+                    path: file.path.clone(),
+                    hunk,
+                }),
+                Err(reason) => report.conflicts.push(conflict(hunk, reason)),
+            }
+        }
+    }
+    if let Err(errors) = apply_operations(&operations, backup_dir) {
+        report.applied.clear();
+        report.errors = errors;
+    }
+    report
+}
+
+fn join_lines(lines: &[String], trailing_newline: bool) -> String {
+    let mut text = lines.join("\n");
+    if trailing_newline && !lines.is_empty() {
+        text.push('\n');
+    }
+    text
+}
+
+pub(crate) fn staged_file(path: &str, kind: Option<&str>, diff: &str) -> Option<StagedFile> {
+    let hunks = parse_hunks(kind, diff);
+    (!hunks.is_empty()).then(|| StagedFile {
+        path: path.to_string(),
+        kind: kind.map(str::to_string),
+        hunks,
+    })
+}
+
+/// Builds the staged files of a `fileChange` item's `changes` list.
+pub(crate) fn staged_files(changes: &[Value]) -> Vec<StagedFile> {
+    changes
+        .iter()
+        .filter_map(|change| {
+            let path = change.get("path").and_then(|value| value.as_str())?;
+            let kind = change.get("kind").and_then(|kind| {
+                kind.as_str()
+                    .or_else(|| kind.get("type").and_then(|value| value.as_str()))
+            });
+            let diff = change
+                .get("diff")
+                .and_then(|value| value.as_str())
+                .unwrap_or_default();
+            staged_file(path, kind, diff)
+        })
+        .collect()
+}
+
+pub(crate) fn staged_patches_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("staged_patches")
+}
+
+fn workspace_file(data_dir: &Path, workspace_id: &str) -> PathBuf {
+    staged_patches_dir(data_dir).join(format!("{}.json", safe_file_name(workspace_id)))
+}
+
+/// Staged patches of one workspace, oldest first.
+pub(crate) fn read_patches(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+) -> Result<Vec<StagedPatch>, String> {
+    let path = workspace_file(data_dir, workspace_id);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(&path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_patches(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    patches: &[StagedPatch],
+) -> Result<(), String> {
+    let path = workspace_file(data_dir, workspace_id);
+    if patches.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+    let data = serde_json::to_string(patches).map_err(|e| e.to_string())?;
+    cipher.write(&path, &data)
+}
+
+pub(crate) fn stage_patch(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    patch: StagedPatch,
+) -> Result<(), String> {
+    let mut patches = read_patches(data_dir, cipher, &patch.workspace_id)?;
+    let workspace_id = patch.workspace_id.clone();
+    patches.push(patch);
+    if patches.len() > MAX_STAGED_PATCHES {
+        let excess = patches.len() - MAX_STAGED_PATCHES;
+        patches.drain(..excess);
+    }
+    write_patches(data_dir, cipher, &workspace_id, &patches)
+}
+
+/// Marks applied hunks and drops the patch once nothing is left to apply.
+/// Returns whether the patch left the staging area.
+pub(crate) fn mark_applied(
+    patches: &mut Vec<StagedPatch>,
+    patch_id: &str,
+    applied: &[HunkRef],
+) -> bool {
+    let Some(position) = patches.iter().position(|patch| patch.id == patch_id) else {
+        return false;
+    };
+    let patch = &mut patches[position];
+    for hunk_ref in applied {
+        if let Some(hunk) = patch
+            .files
+            .iter_mut()
+            .find(|file| file.path == hunk_ref.path)
+            .and_then(|file| file.hunks.get_mut(hunk_ref.hunk))
+        {
+            hunk.applied = true;
+        }
+    }
+    if patch.is_fully_applied() {
+        patches.remove(position);
+        return true;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(diff: &str) -> StagedFile {
+        StagedFile {
+            path: "src/lib.rs".to_string(),
+            kind: Some("update".to_string()),
+            hunks: parse_hunks(Some("update"), diff),
+        }
+    }
+
+    #[test]
+    fn applies_selected_hunks_and_reports_conflicts() {
+        let file = update(
+            "--- a/src/lib.rs\n+++ b/src/lib.rs\n\
+             @@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n\
+             @@ -6,2 +6,3 @@\n f\n+F\n g\n\
+             @@ -9,2 +10,2 @@\n-x\n+X\n y\n",
+        );
+        assert_eq!(file.hunks.len(), 3);
+        assert_eq!(file.hunks[1].old_start, 6);
+
+        // A line inserted above the second hunk shifts it; it still applies.
+        let current = "a\nb\nc\nd\ne\nnew\nf\ng\nh\n";
+        let (outcome, results) = apply_file(&file, &[1, 2], Some(current));
+        assert!(results[0].1.is_ok());
+        assert!(results[1].1.is_err());
+        assert_eq!(
+            outcome,
+            FileOutcome::Write("a\nb\nc\nd\ne\nnew\nf\nF\ng\nh\n".to_string())
+        );
+
+        let (outcome, results) = apply_file(&file, &[0], None);
+        assert!(results[0].1.is_err());
+        assert_eq!(outcome, FileOutcome::Unchanged);
+    }
+
+    #[test]
+    fn handles_whole_file_changes_and_unsafe_paths() {
+        let added = StagedFile {
+            path: "NOTES.md".to_string(),
+            kind: Some("add".to_string()),
+            hunks: parse_hunks(Some("add"), "hello\nworld"),
+        };
+        let (outcome, _) = apply_file(&added, &[0], None);
+        assert_eq!(outcome, FileOutcome::Write("hello\nworld\n".to_string()));
+        let (_, results) = apply_file(&added, &[0], Some("exists\n"));
+        assert!(results[0].1.is_err());
+
+        let deleted = StagedFile {
+            path: "old.txt".to_string(),
+            kind: Some("delete".to_string()),
+            hunks: parse_hunks(Some("delete"), "-gone\n"),
+        };
+        let (outcome, _) = apply_file(&deleted, &[0], Some("gone\n"));
+        assert_eq!(outcome, FileOutcome::Remove);
+
+        let root = Path::new("/work/app");
+        assert_eq!(
+            resolve_path(root, "/work/app/src/lib.rs").expect("inside"),
+            PathBuf::from("/work/app/src/lib.rs")
+        );
+        assert!(resolve_path(root, "../secrets").is_err());
+        assert!(resolve_path(root, "/etc/passwd").is_err());
+
+        let mut patches = vec![StagedPatch {
+            id: "p-1".to_string(),
+            workspace_id: "ws".to_string(),
+            thread_id: "t".to_string(),
+            turn_id: None,
+            source: StagedPatchSource::DryRun,
+            created_at: 0,
+            files: vec![added],
+        }];
+        let hunk = HunkRef {
+            path: "NOTES.md".to_string(),
+            hunk: 0,
+        };
+        assert!(mark_applied(&mut patches, "p-1", &[hunk]));
+        assert!(patches.is_empty());
+    }
+}
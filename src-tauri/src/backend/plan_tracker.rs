@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum PlanStepStatus {
+    Pending,
+    InProgress,
+    Completed,
+}
+
+impl PlanStepStatus {
+    /// Accepts `inProgress`, `in_progress`, `in-progress`, ...; anything
+    /// unknown counts as pending.
+    fn parse(value: Option<&str>) -> Self {
+        let normalized: String = value
+            .unwrap_or_default()
+            .chars()
+            .filter(|ch| !matches!(ch, '_' | '-') && !ch.is_whitespace())
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "inprogress" => PlanStepStatus::InProgress,
+            "completed" => PlanStepStatus::Completed,
+            _ => PlanStepStatus::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlanStep {
+    pub(crate) step: String,
+    pub(crate) status: PlanStepStatus,
+}
+
+/// Latest plan reported by the agent for a thread.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadPlan {
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) explanation: Option<String>,
+    pub(crate) steps: Vec<PlanStep>,
+}
+
+impl ThreadPlan {
+    pub(crate) fn completed_steps(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.status == PlanStepStatus::Completed)
+            .count()
+    }
+}
+
+/// Payload of the synthetic `plan/stepCompleted` notification.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlanStepCompleted {
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) step_index: usize,
+    pub(crate) step: String,
+    pub(crate) completed_steps: usize,
+    pub(crate) total_steps: usize,
+}
+
+fn parse_plan(params: &Value) -> Option<ThreadPlan> {
+    let thread_id = params
+        .get("threadId")
+        .or_else(|| params.get("thread_id"))
+        .and_then(|value| value.as_str())?
+        .to_string();
+    let turn_id = params
+        .get("turnId")
+        .or_else(|| params.get("turn_id"))
+        .and_then(|value| value.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let steps = params
+        .get("plan")
+        .and_then(|plan| plan.as_array())
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let step = entry.get("step")?.as_str()?.trim();
+                    if step.is_empty() {
+                        return None;
+                    }
+                    Some(PlanStep {
+                        step: step.to_string(),
+                        status: PlanStepStatus::parse(
+                            entry.get("status").and_then(|value| value.as_str()),
+                        ),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let explanation = params
+        .get("explanation")
+        .and_then(|value| value.as_str())
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_string());
+    Some(ThreadPlan {
+        thread_id,
+        turn_id,
+        explanation,
+        steps,
+    })
+}
+
+/// Keeps the latest plan per thread, built from `turn/plan/updated`
+/// notifications.
+#[derive(Default)]
+pub(crate) struct PlanTracker {
+    plans: HashMap<(String, String), ThreadPlan>,
+}
+
+impl PlanTracker {
+    /// Applies a plan update and returns the steps that became completed with
+    /// it. A step counts as newly completed unless the previous plan of the
+    /// same turn already had a completed step with the same text.
+    pub(crate) fn observe(
+        &mut self,
+        workspace_id: &str,
+        message: &Value,
+    ) -> Vec<PlanStepCompleted> {
+        if message.get("method").and_then(|value| value.as_str()) != Some("turn/plan/updated") {
+            return Vec::new();
+        }
+        let Some(plan) = message.get("params").and_then(parse_plan) else {
+            return Vec::new();
+        };
+        let key = (workspace_id.to_string(), plan.thread_id.clone());
+        let previously_completed: Vec<&str> = self
+            .plans
+            .get(&key)
+            .filter(|previous| previous.turn_id == plan.turn_id)
+            .map(|previous| {
+                previous
+                    .steps
+                    .iter()
+                    .filter(|step| step.status == PlanStepStatus::Completed)
+                    .map(|step| step.step.as_str())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let completed_steps = plan.completed_steps();
+        let events = plan
+            .steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| {
+                step.status == PlanStepStatus::Completed
+                    && !previously_completed.contains(&step.step.as_str())
+            })
+            .map(|(index, step)| PlanStepCompleted {
+                thread_id: plan.thread_id.clone(),
+                turn_id: plan.turn_id.clone(),
+                step_index: index,
+                step: step.step.clone(),
+                completed_steps,
+                total_steps: plan.steps.len(),
+            })
+            .collect();
+        self.plans.insert(key, plan);
+        events
+    }
+
+    pub(crate) fn get(&self, workspace_id: &str, thread_id: &str) -> Option<ThreadPlan> {
+        self.plans
+            .get(&(workspace_id.to_string(), thread_id.to_string()))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn update(turn_id: &str, statuses: &[(&str, &str)]) -> Value {
+        json!({
+            "method": "turn/plan/updated",
+            "params": {
+                "threadId": "thread-1",
+                "turnId": turn_id,
+                "explanation": "Fix the bug",
+                "plan": statuses
+                    .iter()
+                    .map(|(step, status)| json!({ "step": step, "status": status }))
+                    .collect::<Vec<_>>(),
+            }
+        })
+    }
+
+    #[test]
+    fn reports_each_step_completion_once_per_turn() {
+        let mut tracker = PlanTracker::default();
+        assert!(tracker
+            .observe(
+                "ws",
+                &update("t1", &[("Read code", "in_progress"), ("Fix", "pending")])
+            )
+            .is_empty());
+
+        let events = tracker.observe(
+            "ws",
+            &update("t1", &[("Read code", "completed"), ("Fix", "inProgress")]),
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].step_index, 0);
+        assert_eq!(events[0].completed_steps, 1);
+        assert_eq!(events[0].total_steps, 2);
+
+        let events = tracker.observe(
+            "ws",
+            &update("t1", &[("Read code", "completed"), ("Fix", "completed")]),
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].step, "Fix");
+
+        let plan = tracker.get("ws", "thread-1").expect("plan");
+        assert_eq!(plan.explanation.as_deref(), Some("Fix the bug"));
+        assert_eq!(plan.completed_steps(), 2);
+        assert!(tracker.get("other", "thread-1").is_none());
+
+        let events = tracker.observe("ws", &update("t2", &[("Read code", "completed")]));
+        assert_eq!(events.len(), 1);
+    }
+}
@@ -0,0 +1,275 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::backend::turn_summary::params_thread_id;
+
+/// Rough cost of one attached image; the exact figure depends on resolution.
+const IMAGE_TOKENS: u64 = 1_500;
+/// Share of the context window above which a prompt draws a warning.
+const WARN_PERCENT: u64 = 80;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum PromptSizeLevel {
+    Ok,
+    Warning,
+    /// The prompt would not fit; `turn/start` is not sent.
+    Error,
+}
+
+/// Estimated size of a prompt against the model's context window.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptSizeCheck {
+    pub(crate) level: PromptSizeLevel,
+    pub(crate) model: Option<String>,
+    pub(crate) estimated_tokens: u64,
+    /// Tokens the thread's context already holds.
+    pub(crate) context_tokens: u64,
+    /// `None` when the window of the model is not known yet.
+    pub(crate) context_window: Option<u64>,
+    pub(crate) suggestions: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct ThreadUsage {
+    model: Option<String>,
+    context_tokens: u64,
+    /// Output tokens of the thread's most recent model response.
+    last_output_tokens: Option<u64>,
+}
+
+/// Context windows learned from `model/list` responses and from the
+/// `modelContextWindow` of token usage notifications.
+#[derive(Default)]
+pub(crate) struct ModelCatalog {
+    windows: HashMap<String, u64>,
+    default_model: Option<String>,
+    threads: HashMap<(String, String), ThreadUsage>,
+}
+
+fn u64_field(value: &Value, keys: &[&str]) -> Option<u64> {
+    keys.iter().find_map(|key| {
+        let field = value.get(*key)?;
+        field
+            .as_u64()
+            .or_else(|| field.as_str().and_then(|text| text.parse().ok()))
+    })
+}
+
+/// Approximates the token count of text at four characters per token.
+pub(crate) fn estimate_text_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(4)
+}
+
+impl ModelCatalog {
+    /// Records the models of a `model/list` response.
+    pub(crate) fn ingest_model_list(&mut self, response: &Value) {
+        let result = response.get("result").unwrap_or(response);
+        let Some(models) = result.get("data").and_then(|value| value.as_array()) else {
+            return;
+        };
+        for model in models {
+            let Some(id) = model
+                .get("model")
+                .or_else(|| model.get("id"))
+                .and_then(|value| value.as_str())
+            else {
+                continue;
+            };
+            if let Some(window) = u64_field(
+                model,
+                &["contextWindow", "context_window", "modelContextWindow"],
+            ) {
+                self.windows.insert(id.to_string(), window);
+            }
+            if model.get("isDefault").and_then(|value| value.as_bool()) == Some(true) {
+                self.default_model = Some(id.to_string());
+            }
+        }
+    }
+
+    /// Remembers the model a turn was started with, so the window reported
+    /// by the thread's token usage can be attributed to it.
+    pub(crate) fn note_turn_model(
+        &mut self,
+        workspace_id: &str,
+        thread_id: &str,
+        model: Option<&str>,
+    ) {
+        let model = model
+            .map(str::to_string)
+            .or_else(|| self.default_model.clone());
+        self.threads
+            .entry((workspace_id.to_string(), thread_id.to_string()))
+            .or_default()
+            .model = model;
+    }
+
+    pub(crate) fn observe(&mut self, workspace_id: &str, message: &Value) {
+        if message.get("method").and_then(|value| value.as_str())
+            != Some("thread/tokenUsage/updated")
+        {
+            return;
+        }
+        let Some(params) = message.get("params") else {
+            return;
+        };
+        let Some(thread_id) = params_thread_id(params) else {
+            return;
+        };
+        let Some(usage) = params
+            .get("tokenUsage")
+            .or_else(|| params.get("token_usage"))
+        else {
+            return;
+        };
+        let thread = self
+            .threads
+            .entry((workspace_id.to_string(), thread_id))
+            .or_default();
+        let last = usage
+            .get("last")
+            .and_then(|last| u64_field(last, &["totalTokens", "total_tokens"]))
+            .unwrap_or(0);
+        let total = usage
+            .get("total")
+            .and_then(|total| u64_field(total, &["totalTokens", "total_tokens"]))
+            .unwrap_or(0);
+        thread.context_tokens = if last > 0 { last } else { total };
+        if let Some(output) = usage
+            .get("last")
+            .and_then(|last| u64_field(last, &["outputTokens", "output_tokens"]))
+            .filter(|output| *output > 0)
+        {
+            thread.last_output_tokens = Some(output);
+        }
+        let window = u64_field(usage, &["modelContextWindow", "model_context_window"]);
+        if let (Some(window), Some(model)) = (window, thread.model.clone()) {
+            self.windows.insert(model, window);
+        }
+    }
+
+    /// Output tokens of the last response in a thread, when usage was seen.
+    pub(crate) fn last_output_tokens(&self, workspace_id: &str, thread_id: &str) -> Option<u64> {
+        self.threads
+            .get(&(workspace_id.to_string(), thread_id.to_string()))
+            .and_then(|thread| thread.last_output_tokens)
+    }
+
+    /// Compares the estimated size of a prompt with the window of `model`
+    /// (the catalog's default when `None`).
+    pub(crate) fn check(
+        &self,
+        workspace_id: &str,
+        thread_id: &str,
+        model: Option<&str>,
+        text: &str,
+        image_count: usize,
+    ) -> PromptSizeCheck {
+        let thread = self
+            .threads
+            .get(&(workspace_id.to_string(), thread_id.to_string()));
+        let model = model
+            .map(str::to_string)
+            .or_else(|| thread.and_then(|thread| thread.model.clone()))
+            .or_else(|| self.default_model.clone());
+        let window = model
+            .as_ref()
+            .and_then(|model| self.windows.get(model))
+            .copied();
+        let context_tokens = thread.map(|thread| thread.context_tokens).unwrap_or(0);
+        let text_tokens = estimate_text_tokens(text);
+        let image_tokens = image_count as u64 * IMAGE_TOKENS;
+        let estimated_tokens = text_tokens + image_tokens;
+        let mut check = PromptSizeCheck {
+            level: PromptSizeLevel::Ok,
+            model,
+            estimated_tokens,
+            context_tokens,
+            context_window: window,
+            suggestions: Vec::new(),
+        };
+        let Some(window) = window.filter(|window| *window > 0) else {
+            return check;
+        };
+        let needed = context_tokens + estimated_tokens;
+        let warn_at = window * WARN_PERCENT / 100;
+        if needed <= warn_at {
+            return check;
+        }
+        check.level = if needed > window {
+            PromptSizeLevel::Error
+        } else {
+            PromptSizeLevel::Warning
+        };
+        let excess = needed.saturating_sub(warn_at);
+        if image_count > 0 {
+            check.suggestions.push(format!(
+                "Remove some of the {image_count} attached images (about {image_tokens} tokens)."
+            ));
+        }
+        if text_tokens > 0 && text_tokens >= excess.min(window / 4) {
+            check.suggestions.push(format!(
+                "Shorten the prompt by about {} tokens, e.g. by trimming pasted logs or files.",
+                excess.min(text_tokens)
+            ));
+        }
+        if context_tokens >= window / 2 {
+            check.suggestions.push(format!(
+                "Start a new thread or compact this one; it already holds {context_tokens} tokens."
+            ));
+        }
+        check
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn learns_windows_from_model_list_and_token_usage() {
+        let mut catalog = ModelCatalog::default();
+        catalog.ingest_model_list(&json!({ "result": { "data": [
+            { "id": "gpt-5", "model": "gpt-5", "isDefault": true },
+            { "id": "mini", "model": "mini", "contextWindow": 1000 },
+        ] } }));
+        catalog.note_turn_model("ws", "t-1", None);
+        catalog.observe(
+            "ws",
+            &json!({ "method": "thread/tokenUsage/updated", "params": {
+                "threadId": "t-1",
+                "tokenUsage": { "last": { "totalTokens": 10_000 }, "modelContextWindow": 20_000 },
+            } }),
+        );
+
+        let check = catalog.check("ws", "t-1", None, "hello", 0);
+        assert_eq!(check.model.as_deref(), Some("gpt-5"));
+        assert_eq!(check.context_window, Some(20_000));
+        assert_eq!(check.context_tokens, 10_000);
+        assert_eq!(check.estimated_tokens, 2);
+        assert_eq!(check.level, PromptSizeLevel::Ok);
+
+        let unknown = catalog.check("ws", "t-2", Some("other"), "hello", 0);
+        assert_eq!(unknown.context_window, None);
+        assert_eq!(unknown.level, PromptSizeLevel::Ok);
+    }
+
+    #[test]
+    fn warns_and_errors_with_suggestions() {
+        let mut catalog = ModelCatalog::default();
+        catalog.ingest_model_list(&json!({ "data": [{ "model": "mini", "contextWindow": 4000 }] }));
+
+        let warning = catalog.check("ws", "t-1", Some("mini"), &"x".repeat(13_000), 0);
+        assert_eq!(warning.level, PromptSizeLevel::Warning);
+        assert_eq!(warning.estimated_tokens, 3_250);
+        assert!(warning.suggestions[0].starts_with("Shorten the prompt by about 50 tokens"));
+
+        let error = catalog.check("ws", "t-1", Some("mini"), "fix this", 3);
+        assert_eq!(error.level, PromptSizeLevel::Error);
+        assert!(error.suggestions[0].starts_with("Remove some of the 3 attached images"));
+    }
+}
@@ -0,0 +1,547 @@
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::backend::code_stats::analytics_dir;
+use crate::backend::secure_storage::DataCipher;
+use crate::backend::turn_summary::{params_thread_id, params_turn_id};
+
+/// Daily prompt outcome files inside the analytics data class directory.
+const FILE_PREFIX: &str = "prompt-outcomes-";
+/// Prompts with fewer words count as short.
+const SHORT_PROMPT_WORDS: usize = 12;
+/// Word overlap above which a prompt counts as a retry of the previous one.
+const RETRY_SIMILARITY: f64 = 0.6;
+/// Both sides of a comparison need this many turns.
+const MIN_SAMPLES: usize = 5;
+/// Differences smaller than this factor are not reported.
+const MIN_RATIO: f64 = 1.5;
+
+/// What a prompt looked like, without its text.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptFeatures {
+    pub(crate) words: usize,
+    pub(crate) file_references: bool,
+    /// A fenced code block or a pasted error.
+    pub(crate) snippet: bool,
+    pub(crate) mentions_tests: bool,
+}
+
+/// The outcome of one turn and the features of the prompt that started it,
+/// stored in the analytics data class.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptOutcome {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: Option<String>,
+    pub(crate) recorded_at: i64,
+    pub(crate) status: Option<String>,
+    pub(crate) features: PromptFeatures,
+    /// The prompt repeated the previous one in the thread.
+    pub(crate) retry: bool,
+}
+
+impl PromptOutcome {
+    fn interrupted(&self) -> bool {
+        matches!(self.status.as_deref(), Some("interrupted" | "failed"))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum PromptFeature {
+    FileReferences,
+    Snippets,
+    TestExpectations,
+    Detail,
+}
+
+impl PromptFeature {
+    const ALL: [PromptFeature; 4] = [
+        PromptFeature::FileReferences,
+        PromptFeature::Snippets,
+        PromptFeature::TestExpectations,
+        PromptFeature::Detail,
+    ];
+
+    fn present(self, features: &PromptFeatures) -> bool {
+        match self {
+            PromptFeature::FileReferences => features.file_references,
+            PromptFeature::Snippets => features.snippet,
+            PromptFeature::TestExpectations => features.mentions_tests,
+            PromptFeature::Detail => features.words >= SHORT_PROMPT_WORDS,
+        }
+    }
+
+    fn label(self, present: bool) -> &'static str {
+        match (self, present) {
+            (PromptFeature::FileReferences, true) => "Prompts with file references",
+            (PromptFeature::FileReferences, false) => "Prompts without file references",
+            (PromptFeature::Snippets, true) => "Prompts with code or error snippets",
+            (PromptFeature::Snippets, false) => "Prompts without code or error snippets",
+            (PromptFeature::TestExpectations, true) => "Prompts mentioning tests",
+            (PromptFeature::TestExpectations, false) => "Prompts not mentioning tests",
+            (PromptFeature::Detail, true) => "Detailed prompts",
+            (PromptFeature::Detail, false) => "Short prompts",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum OutcomeMetric {
+    /// The turn was interrupted or failed.
+    Interrupted,
+    /// The next prompt in the thread repeated it.
+    Retried,
+    /// The turn got a thumbs down annotation.
+    Disliked,
+}
+
+impl OutcomeMetric {
+    const ALL: [OutcomeMetric; 3] = [
+        OutcomeMetric::Interrupted,
+        OutcomeMetric::Retried,
+        OutcomeMetric::Disliked,
+    ];
+
+    fn verb(self) -> &'static str {
+        match self {
+            OutcomeMetric::Interrupted => "get interrupted or fail",
+            OutcomeMetric::Retried => "get retried",
+            OutcomeMetric::Disliked => "get a thumbs down",
+        }
+    }
+}
+
+/// A prompt feature whose presence or absence goes with worse outcomes.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptInsight {
+    pub(crate) feature: PromptFeature,
+    pub(crate) metric: OutcomeMetric,
+    /// Whether the worse outcomes go with prompts that have the feature.
+    pub(crate) worse_with_feature: bool,
+    pub(crate) with_rate: f64,
+    pub(crate) without_rate: f64,
+    pub(crate) with_turns: usize,
+    pub(crate) without_turns: usize,
+    pub(crate) ratio: f64,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptingInsights {
+    pub(crate) days: u32,
+    pub(crate) turns: usize,
+    pub(crate) interrupt_rate: f64,
+    pub(crate) retry_rate: f64,
+    /// Strongest patterns first.
+    pub(crate) insights: Vec<PromptInsight>,
+}
+
+fn trim_token(token: &str) -> &str {
+    token.trim_matches(|ch: char| "`'\"()[]{}<>,;:!?".contains(ch))
+}
+
+fn is_file_reference(token: &str) -> bool {
+    let token = trim_token(token).trim_end_matches('.');
+    if token.contains("://") || token.len() < 3 {
+        return false;
+    }
+    if token.starts_with('@') || token.contains('/') {
+        return true;
+    }
+    let Some((name, extension)) = token.rsplit_once('.') else {
+        return false;
+    };
+    !name.is_empty()
+        && (1..=5).contains(&extension.len())
+        && extension.chars().all(|ch| ch.is_ascii_alphanumeric())
+        && extension.chars().any(|ch| ch.is_ascii_alphabetic())
+}
+
+pub(crate) fn prompt_features(text: &str) -> PromptFeatures {
+    let lower = text.to_lowercase();
+    PromptFeatures {
+        words: text.split_whitespace().count(),
+        file_references: text.split_whitespace().any(is_file_reference),
+        snippet: text.contains("```")
+            || ["error:", "error[", "exception", "traceback", "panicked at"]
+                .iter()
+                .any(|marker| lower.contains(marker)),
+        mentions_tests: lower
+            .split(|ch: char| !ch.is_ascii_alphanumeric())
+            .any(|word| matches!(word, "test" | "tests" | "spec" | "specs")),
+    }
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn similarity(left: &HashSet<String>, right: &HashSet<String>) -> f64 {
+    let union = left.union(right).count();
+    if union == 0 {
+        return 0.0;
+    }
+    left.intersection(right).count() as f64 / union as f64
+}
+
+fn user_message_text(item: &Value) -> Option<String> {
+    let content = item.get("content")?.as_array()?;
+    let text: Vec<&str> = content
+        .iter()
+        .filter(|part| part.get("type").and_then(|value| value.as_str()) == Some("text"))
+        .filter_map(|part| part.get("text").and_then(|value| value.as_str()))
+        .collect();
+    (!text.is_empty()).then(|| text.join("\n"))
+}
+
+struct PendingPrompt {
+    features: PromptFeatures,
+    retry: bool,
+}
+
+/// Pairs each thread's latest user message with the status of the turn it
+/// started.
+#[derive(Default)]
+pub(crate) struct PromptOutcomeTracker {
+    pending: HashMap<(String, String), PendingPrompt>,
+    previous_words: HashMap<(String, String), HashSet<String>>,
+}
+
+impl PromptOutcomeTracker {
+    /// Returns the outcome when the message is a `turn/completed` of a turn
+    /// whose prompt was seen.
+    pub(crate) fn observe(
+        &mut self,
+        workspace_id: &str,
+        message: &Value,
+        now: i64,
+    ) -> Option<PromptOutcome> {
+        let method = message.get("method").and_then(|value| value.as_str())?;
+        let params = message.get("params")?;
+        let thread_id = params_thread_id(params)?;
+        let key = (workspace_id.to_string(), thread_id);
+        match method {
+            "item/completed" => {
+                let item = params.get("item")?;
+                if item.get("type").and_then(|value| value.as_str()) != Some("userMessage") {
+                    return None;
+                }
+                let text = user_message_text(item)?;
+                let words = words(&text);
+                let retry = self
+                    .previous_words
+                    .get(&key)
+                    .is_some_and(|previous| similarity(previous, &words) >= RETRY_SIMILARITY);
+                self.previous_words.insert(key.clone(), words);
+                self.pending.insert(
+                    key,
+                    PendingPrompt {
+                        features: prompt_features(&text),
+                        retry,
+                    },
+                );
+                None
+            }
+            "turn/completed" => {
+                let prompt = self.pending.remove(&key)?;
+                let status = params
+                    .get("turn")
+                    .and_then(|turn| turn.get("status"))
+                    .and_then(|value| value.as_str())
+                    .map(str::to_string);
+                Some(PromptOutcome {
+                    workspace_id: key.0,
+                    thread_id: key.1,
+                    turn_id: params_turn_id(params),
+                    recorded_at: now,
+                    status,
+                    features: prompt.features,
+                    retry: prompt.retry,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// One file per day so the analytics retention window applies per day.
+fn day_file(data_dir: &Path, day: NaiveDate) -> PathBuf {
+    analytics_dir(data_dir).join(format!("{FILE_PREFIX}{}.json", day.format("%Y-%m-%d")))
+}
+
+/// Prompt outcome files, which follow the encryption setting.
+pub(crate) fn prompt_outcome_files(data_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(analytics_dir(data_dir)) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(FILE_PREFIX))
+        })
+        .collect()
+}
+
+fn read_day(path: &Path, cipher: &DataCipher) -> Result<Vec<PromptOutcome>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn record_outcome(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    entry: PromptOutcome,
+) -> Result<(), String> {
+    let day = chrono::DateTime::from_timestamp_millis(entry.recorded_at)
+        .unwrap_or_else(Utc::now)
+        .date_naive();
+    let path = day_file(data_dir, day);
+    let mut entries = read_day(&path, cipher)?;
+    entries.push(entry);
+    let data = serde_json::to_string(&entries).map_err(|e| e.to_string())?;
+    cipher.write(&path, &data)
+}
+
+/// Outcomes of a workspace over the last `days` days, oldest first.
+pub(crate) fn read_outcomes(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    days: u32,
+    today: NaiveDate,
+) -> Result<Vec<PromptOutcome>, String> {
+    let mut outcomes = Vec::new();
+    for offset in (0..days).rev() {
+        let day = today - Duration::days(i64::from(offset));
+        outcomes.extend(
+            read_day(&day_file(data_dir, day), cipher)?
+                .into_iter()
+                .filter(|entry| entry.workspace_id == workspace_id),
+        );
+    }
+    Ok(outcomes)
+}
+
+fn rate(hits: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        hits as f64 / total as f64
+    }
+}
+
+/// Compares interrupt, retry and thumbs-down rates of prompts with and
+/// without each feature. `disliked` holds the turn ids with a thumbs down.
+pub(crate) fn analyze(
+    outcomes: &[PromptOutcome],
+    disliked: &HashSet<String>,
+    days: u32,
+) -> PromptingInsights {
+    let mut retried = vec![false; outcomes.len()];
+    let mut last_in_thread: HashMap<&str, usize> = HashMap::new();
+    for (index, outcome) in outcomes.iter().enumerate() {
+        if let Some(previous) = last_in_thread.insert(outcome.thread_id.as_str(), index) {
+            retried[previous] = outcome.retry;
+        }
+    }
+    let hit = |index: usize, metric: OutcomeMetric| {
+        let outcome = &outcomes[index];
+        match metric {
+            OutcomeMetric::Interrupted => outcome.interrupted(),
+            OutcomeMetric::Retried => retried[index],
+            OutcomeMetric::Disliked => outcome
+                .turn_id
+                .as_ref()
+                .is_some_and(|turn_id| disliked.contains(turn_id)),
+        }
+    };
+
+    let mut insights = Vec::new();
+    for feature in PromptFeature::ALL {
+        let (with, without): (Vec<usize>, Vec<usize>) =
+            (0..outcomes.len()).partition(|index| feature.present(&outcomes[*index].features));
+        if with.len() < MIN_SAMPLES || without.len() < MIN_SAMPLES {
+            continue;
+        }
+        for metric in OutcomeMetric::ALL {
+            let with_rate = rate(
+                with.iter().filter(|index| hit(**index, metric)).count(),
+                with.len(),
+            );
+            let without_rate = rate(
+                without.iter().filter(|index| hit(**index, metric)).count(),
+                without.len(),
+            );
+            let worse_with_feature = with_rate > without_rate;
+            let (worse, better) = if worse_with_feature {
+                (with_rate, without_rate)
+            } else {
+                (without_rate, with_rate)
+            };
+            if worse == 0.0 {
+                continue;
+            }
+            let ratio = if better == 0.0 {
+                f64::INFINITY
+            } else {
+                worse / better
+            };
+            if ratio < MIN_RATIO {
+                continue;
+            }
+            let comparison = if ratio.is_finite() {
+                format!("{ratio:.1}x more often")
+            } else {
+                "more often".to_string()
+            };
+            insights.push(PromptInsight {
+                feature,
+                metric,
+                worse_with_feature,
+                with_rate,
+                without_rate,
+                with_turns: with.len(),
+                without_turns: without.len(),
+                ratio,
+                message: format!(
+                    "{} {} {comparison} ({:.0}% vs {:.0}%).",
+                    feature.label(worse_with_feature),
+                    metric.verb(),
+                    worse * 100.0,
+                    better * 100.0
+                ),
+            });
+        }
+    }
+    insights.sort_by(|a, b| b.ratio.total_cmp(&a.ratio));
+    PromptingInsights {
+        days,
+        turns: outcomes.len(),
+        interrupt_rate: rate(
+            outcomes
+                .iter()
+                .filter(|outcome| outcome.interrupted())
+                .count(),
+            outcomes.len(),
+        ),
+        retry_rate: rate(
+            retried.iter().filter(|retried| **retried).count(),
+            outcomes.len(),
+        ),
+        insights,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn outcome(thread_id: &str, file_references: bool, status: &str) -> PromptOutcome {
+        PromptOutcome {
+            workspace_id: "ws".to_string(),
+            thread_id: thread_id.to_string(),
+            turn_id: Some(format!("{thread_id}-turn")),
+            recorded_at: 1,
+            status: Some(status.to_string()),
+            features: PromptFeatures {
+                words: 20,
+                file_references,
+                snippet: false,
+                mentions_tests: false,
+            },
+            retry: false,
+        }
+    }
+
+    #[test]
+    fn tracks_prompt_features_and_retries() {
+        let features =
+            prompt_features("Fix the crash in `src/lib.rs`, add a test\n```\nerror: boom\n```");
+        assert!(features.file_references && features.snippet && features.mentions_tests);
+        let features = prompt_features("Make it faster, see https://example.com or v1.5");
+        assert!(!features.file_references && !features.snippet && !features.mentions_tests);
+
+        let mut tracker = PromptOutcomeTracker::default();
+        let prompt = |text: &str| {
+            json!({ "method": "item/completed", "params": {
+                "threadId": "t-1",
+                "item": { "type": "userMessage", "content": [{ "type": "text", "text": text }] }
+            } })
+        };
+        let completed = |status: &str| {
+            json!({ "method": "turn/completed", "params": {
+                "threadId": "t-1", "turn": { "id": "turn-1", "status": status }
+            } })
+        };
+        assert!(tracker.observe("ws", &completed("completed"), 1).is_none());
+        tracker.observe("ws", &prompt("Refactor the login form validation"), 1);
+        let first = tracker.observe("ws", &completed("interrupted"), 2).unwrap();
+        assert!(!first.retry);
+        assert_eq!(first.status.as_deref(), Some("interrupted"));
+        tracker.observe(
+            "ws",
+            &prompt("Refactor the login form validation please"),
+            3,
+        );
+        assert!(
+            tracker
+                .observe("ws", &completed("completed"), 4)
+                .unwrap()
+                .retry
+        );
+    }
+
+    #[test]
+    fn reports_features_that_go_with_interrupts() {
+        let mut outcomes = Vec::new();
+        for index in 0..10 {
+            let status = if index < 6 {
+                "interrupted"
+            } else {
+                "completed"
+            };
+            outcomes.push(outcome(&format!("a{index}"), false, status));
+            let status = if index < 2 {
+                "interrupted"
+            } else {
+                "completed"
+            };
+            outcomes.push(outcome(&format!("b{index}"), true, status));
+        }
+        let insights = analyze(&outcomes, &HashSet::new(), 30);
+        assert_eq!(insights.turns, 20);
+        assert_eq!(insights.interrupt_rate, 0.4);
+        assert_eq!(insights.insights.len(), 1);
+        let insight = &insights.insights[0];
+        assert_eq!(insight.feature, PromptFeature::FileReferences);
+        assert_eq!(insight.metric, OutcomeMetric::Interrupted);
+        assert!(!insight.worse_with_feature);
+        assert_eq!(
+            insight.message,
+            "Prompts without file references get interrupted or fail 3.0x more often (60% vs 20%)."
+        );
+
+        assert!(analyze(&outcomes[..6], &HashSet::new(), 30)
+            .insights
+            .is_empty());
+    }
+}
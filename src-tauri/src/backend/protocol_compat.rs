@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// App-server notifications and requests CodexMonitor handles. Add methods
+/// here when support for them lands so they stop showing up as unknown.
+const KNOWN_METHODS: &[&str] = &[
+    "error",
+    "thread/started",
+    "thread/name/updated",
+    "thread/tokenUsage/updated",
+    "thread/compacted",
+    "turn/started",
+    "turn/completed",
+    "turn/diff/updated",
+    "turn/plan/updated",
+    "plan/stepCompleted",
+    "item/started",
+    "item/completed",
+    "item/agentMessage/delta",
+    "item/reasoning/summaryTextDelta",
+    "item/reasoning/summaryPartAdded",
+    "item/reasoning/textDelta",
+    "item/commandExecution/outputDelta",
+    "item/commandExecution/terminalInteraction",
+    "item/commandExecution/requestApproval",
+    "item/fileChange/outputDelta",
+    "item/fileChange/requestApproval",
+    "item/mcpToolCall/progress",
+    "item/tool/requestUserInput",
+    "account/updated",
+    "account/rateLimits/updated",
+    "account/login/completed",
+    "mcpServer/oauthLogin/completed",
+];
+
+/// Methods older app-servers send that were replaced, with the replacement.
+const DEPRECATED_METHODS: &[(&str, &str)] = &[
+    (
+        "execCommandApproval",
+        "item/commandExecution/requestApproval",
+    ),
+    ("applyPatchApproval", "item/fileChange/requestApproval"),
+    ("authStatusChange", "account/updated"),
+    ("loginChatGptComplete", "account/login/completed"),
+    ("sessionConfigured", "thread/started"),
+];
+
+/// Legacy event stream mirrored next to the v2 notifications.
+const DEPRECATED_PREFIXES: &[(&str, &str)] = &[("codex/event/", "item/* and turn/* notifications")];
+
+/// How many unknown methods a session keeps counts for.
+const MAX_TRACKED_METHODS: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum MethodStatus {
+    Known,
+    Deprecated,
+    Unknown,
+}
+
+pub(crate) fn classify_method(method: &str) -> MethodStatus {
+    if KNOWN_METHODS.contains(&method) {
+        MethodStatus::Known
+    } else if deprecated_replacement(method).is_some() {
+        MethodStatus::Deprecated
+    } else {
+        MethodStatus::Unknown
+    }
+}
+
+fn deprecated_replacement(method: &str) -> Option<&'static str> {
+    DEPRECATED_METHODS
+        .iter()
+        .find(|(deprecated, _)| *deprecated == method)
+        .or_else(|| {
+            DEPRECATED_PREFIXES
+                .iter()
+                .find(|(prefix, _)| method.starts_with(prefix))
+        })
+        .map(|(_, replacement)| *replacement)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MethodCount {
+    pub(crate) method: String,
+    pub(crate) count: u64,
+    /// When the method was first seen in the session, in epoch milliseconds.
+    pub(crate) first_seen_at: i64,
+    #[serde(default)]
+    pub(crate) replacement: Option<String>,
+}
+
+/// Per-session tally of app-server methods by registry status. Unknown
+/// methods are still forwarded to the frontend; they are only counted.
+#[derive(Debug, Default)]
+pub(crate) struct ProtocolStats {
+    known: u64,
+    deprecated: BTreeMap<String, MethodCount>,
+    unknown: BTreeMap<String, MethodCount>,
+}
+
+impl ProtocolStats {
+    pub(crate) fn record(&mut self, method: &str, now: i64) -> MethodStatus {
+        let status = classify_method(method);
+        let counts = match status {
+            MethodStatus::Known => {
+                self.known += 1;
+                return status;
+            }
+            MethodStatus::Deprecated => &mut self.deprecated,
+            MethodStatus::Unknown => &mut self.unknown,
+        };
+        if let Some(entry) = counts.get_mut(method) {
+            entry.count += 1;
+        } else if counts.len() < MAX_TRACKED_METHODS {
+            counts.insert(
+                method.to_string(),
+                MethodCount {
+                    method: method.to_string(),
+                    count: 1,
+                    first_seen_at: now,
+                    replacement: deprecated_replacement(method).map(str::to_string),
+                },
+            );
+        }
+        status
+    }
+
+    pub(crate) fn report(
+        &self,
+        workspace_id: &str,
+        codex_version: Option<&str>,
+    ) -> SessionProtocolReport {
+        SessionProtocolReport {
+            workspace_id: workspace_id.to_string(),
+            codex_version: codex_version.map(str::to_string),
+            known_count: self.known,
+            deprecated: self.deprecated.values().cloned().collect(),
+            unknown: self.unknown.values().cloned().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionProtocolReport {
+    pub(crate) workspace_id: String,
+    pub(crate) codex_version: Option<String>,
+    pub(crate) known_count: u64,
+    pub(crate) deprecated: Vec<MethodCount>,
+    pub(crate) unknown: Vec<MethodCount>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProtocolCompatReport {
+    pub(crate) sessions: Vec<SessionProtocolReport>,
+    /// Unknown methods usually mean the codex CLI is newer than this
+    /// version of CodexMonitor.
+    pub(crate) cli_newer_than_app: bool,
+    /// Deprecated methods usually mean the codex CLI is older than expected.
+    pub(crate) cli_older_than_app: bool,
+}
+
+pub(crate) fn build_report(mut sessions: Vec<SessionProtocolReport>) -> ProtocolCompatReport {
+    sessions.sort_by(|a, b| a.workspace_id.cmp(&b.workspace_id));
+    ProtocolCompatReport {
+        cli_newer_than_app: sessions.iter().any(|session| !session.unknown.is_empty()),
+        cli_older_than_app: sessions
+            .iter()
+            .any(|session| !session.deprecated.is_empty()),
+        sessions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_known_deprecated_and_unknown_methods() {
+        assert_eq!(classify_method("turn/completed"), MethodStatus::Known);
+        assert_eq!(
+            classify_method("execCommandApproval"),
+            MethodStatus::Deprecated
+        );
+        assert_eq!(
+            classify_method("codex/event/agent_message"),
+            MethodStatus::Deprecated
+        );
+        assert_eq!(classify_method("turn/paused"), MethodStatus::Unknown);
+    }
+
+    #[test]
+    fn counts_methods_per_session() {
+        let mut stats = ProtocolStats::default();
+        stats.record("turn/started", 1);
+        stats.record("turn/paused", 2);
+        stats.record("turn/paused", 3);
+        stats.record("authStatusChange", 4);
+
+        let session = stats.report("ws", Some("codex-cli 0.99.0"));
+        assert_eq!(session.known_count, 1);
+        assert_eq!(session.unknown.len(), 1);
+        assert_eq!(session.unknown[0].count, 2);
+        assert_eq!(session.unknown[0].first_seen_at, 2);
+        assert_eq!(
+            session.deprecated[0].replacement.as_deref(),
+            Some("account/updated")
+        );
+
+        let report = build_report(vec![session]);
+        assert!(report.cli_newer_than_app);
+        assert!(report.cli_older_than_app);
+        assert!(!build_report(Vec::new()).cli_newer_than_app);
+    }
+}
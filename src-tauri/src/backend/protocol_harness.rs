@@ -0,0 +1,405 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::backend::app_server::{start_session, SessionLaunch, WorkspaceSession};
+use crate::backend::events::{
+    AppServerEvent, EventSink, NarrationEvent, SessionOutputLine, TerminalOutput,
+};
+use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+
+/// Argument that turns the app binary into the scripted app-server; the
+/// script follows as JSON.
+pub(crate) const FAKE_SERVER_FLAG: &str = "--fake-app-server";
+const SCENARIO_TIMEOUT: Duration = Duration::from_secs(30);
+#[cfg(feature = "protocol-harness")]
+const GIANT_PAYLOAD_BYTES: usize = 16 * 1024 * 1024;
+
+/// One step of the fake app-server's script.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub(crate) enum FakeStep {
+    /// Reads the next newline-framed message from stdin.
+    Read,
+    /// Answers the `index`-th message read with `reply` plus its `id`.
+    Respond { index: usize, reply: Value },
+    /// Answers the `index`-th message read with its own method as result.
+    Echo { index: usize },
+    /// Writes `line` verbatim.
+    Raw { line: String },
+    /// Writes a notification whose `params.text` is `bytes` long.
+    Giant { method: String, bytes: usize },
+}
+
+/// Plays `script` against `input`/`output`, then drains `input` until the
+/// session closes it.
+pub(crate) fn run_fake_server(
+    script: &[FakeStep],
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> Result<(), String> {
+    let mut received: Vec<Value> = Vec::new();
+    let mut line = String::new();
+    for step in script {
+        let message = match step {
+            FakeStep::Read => {
+                line.clear();
+                if input.read_line(&mut line).map_err(|err| err.to_string())? == 0 {
+                    return Err("stdin closed before the script finished".to_string());
+                }
+                received.push(serde_json::from_str(&line).unwrap_or(Value::Null));
+                continue;
+            }
+            FakeStep::Respond { index, reply } => {
+                let mut message = reply.clone();
+                if let Some(object) = message.as_object_mut() {
+                    object.insert("id".to_string(), received_id(&received, *index));
+                }
+                message.to_string()
+            }
+            FakeStep::Echo { index } => {
+                let method = received
+                    .get(*index)
+                    .and_then(|message| message.get("method"))
+                    .cloned()
+                    .unwrap_or(Value::Null);
+                json!({ "id": received_id(&received, *index), "result": { "method": method } })
+                    .to_string()
+            }
+            FakeStep::Raw { line } => line.clone(),
+            FakeStep::Giant { method, bytes } => {
+                json!({ "method": method, "params": { "text": "x".repeat(*bytes) } }).to_string()
+            }
+        };
+        writeln!(output, "{message}")
+            .and_then(|_| output.flush())
+            .map_err(|err| err.to_string())?;
+    }
+    while input.read_line(&mut line).map_err(|err| err.to_string())? > 0 {
+        line.clear();
+    }
+    Ok(())
+}
+
+fn received_id(received: &[Value], index: usize) -> Value {
+    received
+        .get(index)
+        .and_then(|message| message.get("id"))
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+/// Protocol edge cases the harness drives a session through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Scenario {
+    Handshake,
+    MalformedJson,
+    OutOfOrderResponses,
+    DuplicateIds,
+    #[cfg(feature = "protocol-harness")]
+    GiantPayload,
+}
+
+/// Quick scenarios `run_selftest` runs in every build.
+pub(crate) const SELFTEST_SCENARIOS: [Scenario; 4] = [
+    Scenario::Handshake,
+    Scenario::MalformedJson,
+    Scenario::OutOfOrderResponses,
+    Scenario::DuplicateIds,
+];
+
+/// Everything, including scenarios too slow for a bug report.
+#[cfg(feature = "protocol-harness")]
+pub(crate) const ALL_SCENARIOS: [Scenario; 5] = [
+    Scenario::Handshake,
+    Scenario::MalformedJson,
+    Scenario::OutOfOrderResponses,
+    Scenario::DuplicateIds,
+    Scenario::GiantPayload,
+];
+
+/// Outcome of one scenario.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScenarioReport {
+    pub(crate) name: String,
+    pub(crate) passed: bool,
+    pub(crate) detail: Option<String>,
+    pub(crate) duration_ms: u64,
+}
+
+impl Scenario {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Scenario::Handshake => "handshake",
+            Scenario::MalformedJson => "malformedJson",
+            Scenario::OutOfOrderResponses => "outOfOrderResponses",
+            Scenario::DuplicateIds => "duplicateIds",
+            #[cfg(feature = "protocol-harness")]
+            Scenario::GiantPayload => "giantPayload",
+        }
+    }
+
+    /// Server side of the scenario. Messages 0 and 1 are `initialize` and
+    /// `initialized`; the scenario's own requests start at index 2.
+    pub(crate) fn script(self) -> Vec<FakeStep> {
+        let mut script = vec![
+            FakeStep::Read,
+            FakeStep::Respond {
+                index: 0,
+                reply: json!({ "result": { "userAgent": "fake-app-server" } }),
+            },
+            FakeStep::Read,
+        ];
+        script.extend(match self {
+            Scenario::Handshake => vec![FakeStep::Read, FakeStep::Echo { index: 2 }],
+            Scenario::MalformedJson => vec![
+                FakeStep::Raw {
+                    line: r#"{"id": 2, "result": "#.to_string(),
+                },
+                FakeStep::Raw {
+                    line: "not json at all".to_string(),
+                },
+                FakeStep::Read,
+                FakeStep::Echo { index: 2 },
+            ],
+            Scenario::OutOfOrderResponses => vec![
+                FakeStep::Read,
+                FakeStep::Read,
+                FakeStep::Echo { index: 3 },
+                FakeStep::Echo { index: 2 },
+            ],
+            Scenario::DuplicateIds => vec![
+                FakeStep::Read,
+                FakeStep::Echo { index: 2 },
+                FakeStep::Respond {
+                    index: 2,
+                    reply: json!({ "result": { "method": "duplicate" } }),
+                },
+                FakeStep::Raw {
+                    line: r#"{"id": 9999, "result": {}}"#.to_string(),
+                },
+                FakeStep::Read,
+                FakeStep::Echo { index: 3 },
+            ],
+            #[cfg(feature = "protocol-harness")]
+            Scenario::GiantPayload => vec![
+                FakeStep::Giant {
+                    method: "harness/giant".to_string(),
+                    bytes: GIANT_PAYLOAD_BYTES,
+                },
+                FakeStep::Read,
+                FakeStep::Echo { index: 2 },
+            ],
+        });
+        script
+    }
+
+    /// Client side: drives the connected session and checks what it saw.
+    async fn check(
+        self,
+        session: &Arc<WorkspaceSession>,
+        events: &CollectedEvents,
+    ) -> Result<(), String> {
+        match self {
+            Scenario::Handshake => expect_echo(session, "harness/ping").await,
+            Scenario::MalformedJson => {
+                expect_echo(session, "harness/ping").await?;
+                let parse_errors = events.count("codex/parseError");
+                if parse_errors != 2 {
+                    return Err(format!("expected 2 parse errors, saw {parse_errors}"));
+                }
+                Ok(())
+            }
+            Scenario::OutOfOrderResponses => {
+                let first_session = Arc::clone(session);
+                let first =
+                    tokio::spawn(async move { expect_echo(&first_session, "harness/first").await });
+                // Give the first request a head start so it is read first.
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                expect_echo(session, "harness/second").await?;
+                first.await.map_err(|err| err.to_string())?
+            }
+            Scenario::DuplicateIds => {
+                expect_echo(session, "harness/first").await?;
+                expect_echo(session, "harness/second").await
+            }
+            #[cfg(feature = "protocol-harness")]
+            Scenario::GiantPayload => {
+                expect_echo(session, "harness/ping").await?;
+                let length = events
+                    .find("harness/giant")
+                    .and_then(|message| message["params"]["text"].as_str().map(str::len));
+                if length != Some(GIANT_PAYLOAD_BYTES) {
+                    return Err(format!("giant notification arrived as {length:?} bytes"));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+async fn expect_echo(session: &WorkspaceSession, method: &str) -> Result<(), String> {
+    let response = session.send_request(method, json!({})).await?;
+    match response["result"]["method"].as_str() {
+        Some(echoed) if echoed == method => Ok(()),
+        _ => Err(format!("{method} was answered with {response}")),
+    }
+}
+
+/// Event sink that keeps the session's app-server events for checks.
+#[derive(Clone, Default)]
+struct CollectedEvents(Arc<Mutex<Vec<Value>>>);
+
+impl CollectedEvents {
+    fn count(&self, method: &str) -> usize {
+        self.0
+            .lock()
+            .map(|events| {
+                events
+                    .iter()
+                    .filter(|message| message["method"] == method)
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
+    #[cfg(feature = "protocol-harness")]
+    fn find(&self, method: &str) -> Option<Value> {
+        let events = self.0.lock().ok()?;
+        events
+            .iter()
+            .find(|message| message["method"] == method)
+            .cloned()
+    }
+}
+
+impl EventSink for CollectedEvents {
+    fn emit_app_server_event(&self, event: AppServerEvent) {
+        if let Ok(mut events) = self.0.lock() {
+            events.push(event.message);
+        }
+    }
+
+    fn emit_terminal_output(&self, _event: TerminalOutput) {}
+
+    fn emit_narration(&self, _event: NarrationEvent) {}
+
+    fn emit_session_output(&self, _event: SessionOutputLine) {}
+}
+
+fn harness_entry() -> WorkspaceEntry {
+    WorkspaceEntry {
+        id: "protocol-harness".to_string(),
+        name: "Protocol harness".to_string(),
+        path: std::env::temp_dir().display().to_string(),
+        codex_bin: None,
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+        icon: None,
+        color: None,
+    }
+}
+
+async fn run_scenario(server: &Path, scenario: Scenario) -> Result<(), String> {
+    let script = serde_json::to_string(&scenario.script()).map_err(|err| err.to_string())?;
+    let mut command = Command::new(server);
+    command.arg(FAKE_SERVER_FLAG).arg(script).kill_on_drop(true);
+    let launch = SessionLaunch {
+        codex_bin: Some(server.display().to_string()),
+        codex_version: None,
+        codex_home: None,
+    };
+    let events = CollectedEvents::default();
+    let session = start_session(
+        harness_entry(),
+        command,
+        launch,
+        "harness".to_string(),
+        events.clone(),
+    )
+    .await?;
+    let result = scenario.check(&session, &events).await;
+    let _ = session.child.lock().await.kill().await;
+    result
+}
+
+/// Runs each scenario against a fresh session on `server`, the binary that
+/// answers to [`FAKE_SERVER_FLAG`].
+pub(crate) async fn run_scenarios(server: &Path, scenarios: &[Scenario]) -> Vec<ScenarioReport> {
+    let mut reports = Vec::new();
+    for scenario in scenarios {
+        let started = Instant::now();
+        let result = match timeout(SCENARIO_TIMEOUT, run_scenario(server, *scenario)).await {
+            Ok(result) => result,
+            Err(_) => Err("timed out".to_string()),
+        };
+        reports.push(ScenarioReport {
+            name: scenario.name().to_string(),
+            passed: result.is_ok(),
+            detail: result.err(),
+            duration_ms: started.elapsed().as_millis() as u64,
+        });
+    }
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_server_plays_out_of_order_script() {
+        let input = concat!(
+            r#"{"id":1,"method":"initialize"}"#,
+            "\n",
+            r#"{"method":"initialized"}"#,
+            "\n",
+            r#"{"id":2,"method":"harness/first"}"#,
+            "\n",
+            r#"{"id":3,"method":"harness/second"}"#,
+            "\n",
+        );
+        let mut output = Vec::new();
+        run_fake_server(
+            &Scenario::OutOfOrderResponses.script(),
+            input.as_bytes(),
+            &mut output,
+        )
+        .expect("script");
+
+        let lines: Vec<Value> = String::from_utf8(output)
+            .expect("utf8")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json"))
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["id"], 1);
+        assert_eq!(
+            lines[1],
+            json!({ "id": 3, "result": { "method": "harness/second" } })
+        );
+        assert_eq!(
+            lines[2],
+            json!({ "id": 2, "result": { "method": "harness/first" } })
+        );
+    }
+
+    #[test]
+    fn scripts_round_trip_as_json() {
+        for scenario in SELFTEST_SCENARIOS {
+            let script = scenario.script();
+            let encoded = serde_json::to_string(&script).expect("encode");
+            let decoded: Vec<FakeStep> = serde_json::from_str(&encoded).expect("decode");
+            assert_eq!(decoded, script, "{}", scenario.name());
+        }
+    }
+}
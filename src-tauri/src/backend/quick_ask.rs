@@ -0,0 +1,141 @@
+use crate::backend::context_files::{defines_symbol, prompt_terms};
+
+/// Files up to this size are attached whole; longer ones are cut down to
+/// the parts around what the question mentions.
+pub(crate) const MAX_EXCERPT_CHARS: usize = 24_000;
+/// Lines kept above and below each line the question points at.
+const CONTEXT_LINES: usize = 15;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FileExcerpt {
+    pub(crate) text: String,
+    pub(crate) truncated: bool,
+}
+
+/// The parts of `content` most relevant to `question`, within `max_chars`.
+/// Definitions of symbols the question names come first, then lines using
+/// them, then lines with its other words, then the top of the file. Omitted
+/// stretches are marked with their line numbers.
+pub(crate) fn excerpt(content: &str, question: &str, max_chars: usize) -> FileExcerpt {
+    if content.chars().count() <= max_chars {
+        return FileExcerpt {
+            text: content.to_string(),
+            truncated: false,
+        };
+    }
+    let lines: Vec<&str> = content.lines().collect();
+    let terms = prompt_terms(question);
+    let mut anchors: Vec<(u8, usize)> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let lower = line.to_lowercase();
+        let rank = if terms
+            .symbols
+            .iter()
+            .any(|symbol| defines_symbol(line, symbol))
+        {
+            0
+        } else if terms
+            .symbols
+            .iter()
+            .any(|symbol| line.contains(symbol.as_str()))
+        {
+            1
+        } else if terms.words.iter().any(|word| lower.contains(word.as_str())) {
+            2
+        } else {
+            continue;
+        };
+        anchors.push((rank, index));
+    }
+    anchors.sort();
+    anchors.push((3, 0));
+
+    let mut selected = vec![false; lines.len()];
+    let mut used = 0;
+    for (_, index) in anchors {
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + CONTEXT_LINES + 1).min(lines.len());
+        let added: usize = (start..end)
+            .filter(|line| !selected[*line])
+            .map(|line| lines[line].chars().count() + 1)
+            .sum();
+        if used + added > max_chars {
+            continue;
+        }
+        used += added;
+        selected[start..end]
+            .iter_mut()
+            .for_each(|line| *line = true);
+    }
+    if !selected.contains(&true) {
+        return FileExcerpt {
+            text: content.chars().take(max_chars).collect(),
+            truncated: true,
+        };
+    }
+
+    let mut text = String::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let start = index;
+        let keep = selected[index];
+        while index < lines.len() && selected[index] == keep {
+            index += 1;
+        }
+        if keep {
+            for line in &lines[start..index] {
+                text.push_str(line);
+                text.push('\n');
+            }
+        } else {
+            text.push_str(&format!("… lines {}-{} omitted …\n", start + 1, index));
+        }
+    }
+    FileExcerpt {
+        text,
+        truncated: true,
+    }
+}
+
+pub(crate) fn prompt(path: &str, question: &str, excerpt: &FileExcerpt) -> String {
+    let note = if excerpt.truncated {
+        " The file is long, so only the parts relevant to the question are included; \
+omitted lines are marked."
+    } else {
+        ""
+    };
+    format!(
+        "Answer a question about the file `{path}`. Do not modify any files. Answer concisely.{note}\n\n\
+Question: {question}\n\n\
+<file path=\"{path}\">\n{}\n</file>",
+        excerpt.text.trim_end()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excerpts_long_files_around_the_asked_symbol() {
+        let short = excerpt("fn main() {}\n", "what does main do?", 100);
+        assert!(!short.truncated);
+        assert_eq!(short.text, "fn main() {}\n");
+
+        let mut content = String::new();
+        for index in 0..200 {
+            content.push_str(&format!("// filler line {index}\n"));
+        }
+        content.push_str("fn parse_config(path: &str) -> Config {\n    load(path)\n}\n");
+        for index in 200..400 {
+            content.push_str(&format!("// filler line {index}\n"));
+        }
+        let cut = excerpt(&content, "Why does parse_config panic?", 1_500);
+        assert!(cut.truncated);
+        assert!(cut.text.contains("fn parse_config(path: &str) -> Config {"));
+        assert!(cut.text.starts_with("// filler line 0\n"));
+        assert!(cut.text.contains("omitted"));
+        assert!(!cut.text.contains("filler line 300\n"));
+        assert!(cut.text.chars().count() <= 1_500 + 200);
+    }
+}
@@ -0,0 +1,301 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::types::WorkspaceEntry;
+
+const THREAD_PAGE_SIZE: u32 = 100;
+/// Upper bound on pages fetched per workspace (100 threads each).
+const THREAD_MAX_PAGES: usize = 50;
+pub(crate) const DEFAULT_RESULT_LIMIT: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum QuickSearchKind {
+    Workspace,
+    Thread,
+    Prompt,
+}
+
+impl QuickSearchKind {
+    /// Small tie-breaker so workspaces win over threads with the same score.
+    fn bonus(self) -> i64 {
+        match self {
+            QuickSearchKind::Workspace => 3,
+            QuickSearchKind::Prompt => 2,
+            QuickSearchKind::Thread => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct QuickSearchCandidate {
+    pub(crate) kind: QuickSearchKind,
+    pub(crate) id: String,
+    pub(crate) title: String,
+    pub(crate) subtitle: Option<String>,
+    pub(crate) workspace_id: Option<String>,
+    /// Secondary text matched with a lower weight than the title.
+    pub(crate) keywords: Vec<String>,
+    pub(crate) updated_at: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QuickSearchResult {
+    pub(crate) kind: QuickSearchKind,
+    pub(crate) id: String,
+    pub(crate) title: String,
+    pub(crate) subtitle: Option<String>,
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) score: i64,
+}
+
+fn is_boundary(previous: Option<char>) -> bool {
+    match previous {
+        None => true,
+        Some(ch) => matches!(ch, ' ' | '/' | '-' | '_' | '.' | ':' | '\\'),
+    }
+}
+
+/// Scores `text` against `query` as an in-order subsequence match, rewarding
+/// consecutive runs, word starts and exact substrings. `None` means no match.
+pub(crate) fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query.trim().to_lowercase().chars().collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let lowered = text.to_lowercase();
+    let haystack: Vec<char> = lowered.chars().collect();
+    let mut score: i64 = 0;
+    let mut query_index = 0;
+    let mut last_match: Option<usize> = None;
+    for (index, ch) in haystack.iter().enumerate() {
+        if query_index == query.len() {
+            break;
+        }
+        if *ch != query[query_index] {
+            continue;
+        }
+        score += 1;
+        if index == 0 {
+            score += 10;
+        }
+        if is_boundary(index.checked_sub(1).map(|prev| haystack[prev])) {
+            score += 8;
+        }
+        match last_match {
+            Some(last) if last + 1 == index => score += 5,
+            Some(last) => score -= ((index - last - 1) as i64).min(3),
+            None => {}
+        }
+        last_match = Some(index);
+        query_index += 1;
+    }
+    if query_index < query.len() {
+        return None;
+    }
+    let needle: String = query.iter().collect();
+    if lowered.contains(&needle) {
+        score += 20;
+        if lowered == needle {
+            score += 20;
+        }
+    }
+    score -= (haystack.len() / 20) as i64;
+    Some(score)
+}
+
+fn candidate_score(query: &str, candidate: &QuickSearchCandidate) -> Option<i64> {
+    let title = fuzzy_score(query, &candidate.title).map(|score| score * 2);
+    let keywords = candidate
+        .keywords
+        .iter()
+        .filter_map(|keyword| fuzzy_score(query, keyword))
+        .max();
+    let best = title.into_iter().chain(keywords).max()?;
+    Some(best + candidate.kind.bonus())
+}
+
+/// Ranks candidates for `query`. An empty query lists the most recently
+/// updated items first.
+pub(crate) fn rank(
+    query: &str,
+    candidates: Vec<QuickSearchCandidate>,
+    limit: usize,
+) -> Vec<QuickSearchResult> {
+    let mut scored: Vec<(i64, Option<i64>, QuickSearchCandidate)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let score = candidate_score(query, &candidate)?;
+            Some((score, candidate.updated_at, candidate))
+        })
+        .collect();
+    scored.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| b.1.cmp(&a.1))
+            .then_with(|| a.2.title.cmp(&b.2.title))
+    });
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(score, _, candidate)| QuickSearchResult {
+            kind: candidate.kind,
+            id: candidate.id,
+            title: candidate.title,
+            subtitle: candidate.subtitle,
+            workspace_id: candidate.workspace_id,
+            score,
+        })
+        .collect()
+}
+
+pub(crate) fn workspace_candidate(entry: &WorkspaceEntry) -> QuickSearchCandidate {
+    let mut keywords = vec![entry.path.clone()];
+    if let Some(branch) = entry.worktree.as_ref().map(|worktree| worktree.branch.clone()) {
+        keywords.push(branch);
+    }
+    QuickSearchCandidate {
+        kind: QuickSearchKind::Workspace,
+        id: entry.id.clone(),
+        title: entry.name.clone(),
+        subtitle: Some(entry.path.clone()),
+        workspace_id: Some(entry.id.clone()),
+        keywords,
+        updated_at: None,
+    }
+}
+
+fn thread_candidate(workspace: &WorkspaceEntry, thread: &Value) -> Option<QuickSearchCandidate> {
+    let id = thread.get("id")?.as_str()?.to_string();
+    let preview = thread
+        .get("preview")
+        .and_then(|value| value.as_str())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let mut keywords = vec![id.clone()];
+    if let Some(branch) = thread
+        .get("gitInfo")
+        .and_then(|info| info.get("branch"))
+        .and_then(|value| value.as_str())
+    {
+        keywords.push(branch.to_string());
+    }
+    let title =
+        preview.unwrap_or_else(|| format!("Agent {}", id.chars().take(4).collect::<String>()));
+    Some(QuickSearchCandidate {
+        kind: QuickSearchKind::Thread,
+        title,
+        subtitle: Some(workspace.name.clone()),
+        workspace_id: Some(workspace.id.clone()),
+        keywords,
+        updated_at: thread
+            .get("updatedAt")
+            .or_else(|| thread.get("createdAt"))
+            .and_then(|value| value.as_i64()),
+        id,
+    })
+}
+
+fn same_path(a: &str, b: &str) -> bool {
+    Path::new(a.trim_end_matches('/')) == Path::new(b.trim_end_matches('/'))
+}
+
+/// Pages through `thread/list` and keeps threads rooted in the workspace.
+pub(crate) async fn thread_candidates(
+    session: &WorkspaceSession,
+    workspace: &WorkspaceEntry,
+) -> Vec<QuickSearchCandidate> {
+    let mut out = Vec::new();
+    let mut cursor: Option<String> = None;
+    for _ in 0..THREAD_MAX_PAGES {
+        let Ok(response) = session
+            .send_request(
+                "thread/list",
+                json!({ "cursor": cursor, "limit": THREAD_PAGE_SIZE }),
+            )
+            .await
+        else {
+            break;
+        };
+        let Some(result) = response.get("result") else {
+            break;
+        };
+        if let Some(threads) = result.get("data").and_then(|data| data.as_array()) {
+            out.extend(
+                threads
+                    .iter()
+                    .filter(|thread| {
+                        thread
+                            .get("cwd")
+                            .and_then(|cwd| cwd.as_str())
+                            .is_some_and(|cwd| same_path(cwd, &workspace.path))
+                    })
+                    .filter_map(|thread| thread_candidate(workspace, thread)),
+            );
+        }
+        cursor = result
+            .get("nextCursor")
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string());
+        if cursor.is_none() {
+            break;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(kind: QuickSearchKind, title: &str, updated_at: Option<i64>) -> QuickSearchCandidate {
+        QuickSearchCandidate {
+            kind,
+            id: title.to_string(),
+            title: title.to_string(),
+            subtitle: None,
+            workspace_id: None,
+            keywords: Vec::new(),
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_word_starts_and_substrings() {
+        assert!(fuzzy_score("xyz", "codex monitor").is_none());
+        let initials = fuzzy_score("cm", "codex monitor").expect("initials");
+        let scattered = fuzzy_score("cm", "background compaction").expect("scattered");
+        assert!(initials > scattered);
+        let exact = fuzzy_score("monitor", "codex monitor").expect("substring");
+        let loose = fuzzy_score("monitor", "make other notes in tutor").expect("loose");
+        assert!(exact > loose);
+    }
+
+    #[test]
+    fn rank_orders_by_score_then_recency() {
+        let results = rank(
+            "fix",
+            vec![
+                candidate(QuickSearchKind::Thread, "Fix login bug", Some(1)),
+                candidate(QuickSearchKind::Thread, "Fix flaky test", Some(5)),
+                candidate(QuickSearchKind::Thread, "Add feature", Some(9)),
+                candidate(QuickSearchKind::Workspace, "fix", None),
+            ],
+            10,
+        );
+        let titles: Vec<_> = results.iter().map(|result| result.title.as_str()).collect();
+        assert_eq!(titles, vec!["fix", "Fix flaky test", "Fix login bug"]);
+
+        let recent = rank(
+            "",
+            vec![
+                candidate(QuickSearchKind::Thread, "older", Some(1)),
+                candidate(QuickSearchKind::Thread, "newer", Some(2)),
+            ],
+            1,
+        );
+        assert_eq!(recent[0].title, "newer");
+    }
+}
@@ -0,0 +1,139 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+/// One window of `account/rateLimits`, e.g. the 5-hour `primary` one.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RateLimitWindow {
+    pub(crate) name: String,
+    pub(crate) used_percent: f64,
+    /// Epoch milliseconds.
+    pub(crate) resets_at: Option<i64>,
+}
+
+/// Why a non-interactive turn is waiting, and until when.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QuotaDeferral {
+    pub(crate) window: String,
+    pub(crate) remaining_percent: f64,
+    pub(crate) reserve_percent: u32,
+    /// Epoch milliseconds.
+    pub(crate) resumes_at: i64,
+}
+
+/// Reads the windows of an `account/rateLimits/updated` notification or an
+/// `account/rateLimits/read` response.
+pub(crate) fn parse_rate_limits(value: &Value) -> Vec<RateLimitWindow> {
+    let limits = value.get("rateLimits").unwrap_or(value);
+    ["primary", "secondary"]
+        .into_iter()
+        .filter_map(|name| {
+            let window = limits.get(name)?;
+            Some(RateLimitWindow {
+                name: name.to_string(),
+                used_percent: window.get("usedPercent")?.as_f64()?,
+                resets_at: window
+                    .get("resetsAt")
+                    .and_then(|value| value.as_i64())
+                    .map(|value| {
+                        if value < 1_000_000_000_000 {
+                            value * 1000
+                        } else {
+                            value
+                        }
+                    }),
+            })
+        })
+        .collect()
+}
+
+/// Returns the deferral for the window that resets last among those with
+/// less than `reserve_percent` left. Windows without a known reset, or
+/// whose reset has passed, never defer.
+pub(crate) fn deferral(
+    windows: &[RateLimitWindow],
+    reserve_percent: u32,
+    now: i64,
+) -> Option<QuotaDeferral> {
+    if reserve_percent == 0 {
+        return None;
+    }
+    windows
+        .iter()
+        .filter(|window| 100.0 - window.used_percent < f64::from(reserve_percent))
+        .filter_map(|window| {
+            let resets_at = window.resets_at.filter(|resets_at| *resets_at > now)?;
+            Some(QuotaDeferral {
+                window: window.name.clone(),
+                remaining_percent: (100.0 - window.used_percent).max(0.0),
+                reserve_percent,
+                resumes_at: resets_at,
+            })
+        })
+        .max_by_key(|deferral| deferral.resumes_at)
+}
+
+/// Latest rate-limit windows per workspace, as reported by its app-server.
+#[derive(Default)]
+pub(crate) struct RateLimitTracker {
+    windows: Mutex<HashMap<String, Vec<RateLimitWindow>>>,
+    updated: Notify,
+}
+
+impl RateLimitTracker {
+    pub(crate) fn update(&self, workspace_id: &str, windows: Vec<RateLimitWindow>) {
+        if windows.is_empty() {
+            return;
+        }
+        if let Ok(mut tracked) = self.windows.lock() {
+            tracked.insert(workspace_id.to_string(), windows);
+        }
+        self.updated.notify_waiters();
+    }
+
+    pub(crate) fn deferral(
+        &self,
+        workspace_id: &str,
+        reserve_percent: u32,
+        now: i64,
+    ) -> Option<QuotaDeferral> {
+        let tracked = self.windows.lock().ok()?;
+        deferral(tracked.get(workspace_id)?, reserve_percent, now)
+    }
+
+    /// Resolves on the next update of any workspace's windows.
+    pub(crate) async fn changed(&self) {
+        self.updated.notified().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn defers_until_the_low_window_resets() {
+        let windows = parse_rate_limits(&json!({
+            "rateLimits": {
+                "primary": { "usedPercent": 95.0, "resetsAt": 2_000 },
+                "secondary": { "usedPercent": 40.0, "resetsAt": 9_000 }
+            }
+        }));
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].resets_at, Some(2_000_000));
+
+        let deferred = deferral(&windows, 10, 1_000_000).expect("deferred");
+        assert_eq!(deferred.window, "primary");
+        assert_eq!(deferred.resumes_at, 2_000_000);
+        assert!((deferred.remaining_percent - 5.0).abs() < f64::EPSILON);
+
+        assert!(deferral(&windows, 10, 2_000_000).is_none());
+        assert!(deferral(&windows, 0, 1_000_000).is_none());
+        assert!(deferral(&windows, 4, 1_000_000).is_none());
+    }
+}
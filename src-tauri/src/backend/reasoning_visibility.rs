@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// Streamed reasoning summary notifications; the completed reasoning item
+/// is still forwarded.
+const SUMMARY_METHODS: [&str; 2] = [
+    "item/reasoning/summaryTextDelta",
+    "item/reasoning/summaryPartAdded",
+];
+
+/// Decides whether reasoning summary notifications reach the frontend. A
+/// workspace override wins over the entry for the thread's model, which wins
+/// over the global default.
+#[derive(Debug)]
+pub(crate) struct ReasoningVisibility {
+    show: bool,
+    models: BTreeMap<String, bool>,
+    workspaces: HashMap<String, bool>,
+    thread_models: HashMap<(String, String), String>,
+}
+
+impl Default for ReasoningVisibility {
+    fn default() -> Self {
+        Self {
+            show: true,
+            models: BTreeMap::new(),
+            workspaces: HashMap::new(),
+            thread_models: HashMap::new(),
+        }
+    }
+}
+
+impl ReasoningVisibility {
+    pub(crate) fn set_defaults(&mut self, show: bool, models: &BTreeMap<String, bool>) {
+        self.show = show;
+        self.models = models.clone();
+    }
+
+    pub(crate) fn set_workspace(&mut self, workspace_id: &str, show: Option<bool>) {
+        match show {
+            Some(show) => {
+                self.workspaces.insert(workspace_id.to_string(), show);
+            }
+            None => {
+                self.workspaces.remove(workspace_id);
+            }
+        }
+    }
+
+    /// Remembers the model of the thread's latest turn.
+    pub(crate) fn set_thread_model(&mut self, workspace_id: &str, thread_id: &str, model: &str) {
+        self.thread_models.insert(
+            (workspace_id.to_string(), thread_id.to_string()),
+            model.to_string(),
+        );
+    }
+
+    fn shows(&self, workspace_id: &str, thread_id: Option<&str>) -> bool {
+        if let Some(show) = self.workspaces.get(workspace_id) {
+            return *show;
+        }
+        thread_id
+            .and_then(|thread_id| {
+                self.thread_models
+                    .get(&(workspace_id.to_string(), thread_id.to_string()))
+            })
+            .and_then(|model| self.models.get(model))
+            .copied()
+            .unwrap_or(self.show)
+    }
+
+    /// Whether the event should be dropped before it is emitted.
+    pub(crate) fn hides(
+        &self,
+        workspace_id: &str,
+        thread_id: Option<&str>,
+        method: Option<&str>,
+    ) -> bool {
+        method.is_some_and(|method| SUMMARY_METHODS.contains(&method))
+            && !self.shows(workspace_id, thread_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_overrides_model_overrides_default() {
+        let mut visibility = ReasoningVisibility::default();
+        let summary = Some("item/reasoning/summaryTextDelta");
+        assert!(!visibility.hides("ws", Some("t-1"), summary));
+
+        let models = BTreeMap::from([("gpt-5-codex".to_string(), true)]);
+        visibility.set_defaults(false, &models);
+        visibility.set_thread_model("ws", "t-1", "gpt-5-codex");
+        visibility.set_thread_model("ws", "t-2", "gpt-5");
+        assert!(!visibility.hides("ws", Some("t-1"), summary));
+        assert!(visibility.hides("ws", Some("t-2"), summary));
+        assert!(visibility.hides("ws", None, summary));
+        assert!(!visibility.hides("ws", Some("t-2"), Some("item/completed")));
+
+        visibility.set_workspace("ws", Some(false));
+        assert!(visibility.hides("ws", Some("t-1"), summary));
+        visibility.set_workspace("ws", None);
+        assert!(!visibility.hides("ws", Some("t-1"), summary));
+    }
+}
@@ -0,0 +1,125 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// One progress update parsed from `git clone --progress` output.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CloneProgress {
+    /// e.g. `Receiving objects`.
+    pub(crate) stage: String,
+    pub(crate) percent: Option<u8>,
+    pub(crate) message: String,
+}
+
+/// Accepts the URL forms `git clone` understands for remotes: `https://`,
+/// `http://`, `ssh://`, `git://`, `file://` and scp-like `user@host:path`.
+pub(crate) fn validate_clone_url(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    let has_scheme = ["https://", "http://", "ssh://", "git://", "file://"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme));
+    let scp_like = url
+        .split_once(':')
+        .is_some_and(|(host, path)| host.contains('@') && !host.contains('/') && !path.is_empty());
+    if url.starts_with('-') || url.chars().any(char::is_whitespace) || !(has_scheme || scp_like) {
+        return Err(format!("Not a git repository URL: {url}"));
+    }
+    Ok(url.to_string())
+}
+
+/// Repository name from its URL, e.g. `app` for `git@github.com:me/app.git`.
+pub(crate) fn repo_name(url: &str) -> Option<String> {
+    let name = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()?
+        .trim_end_matches(".git");
+    (!name.is_empty() && name != "." && name != "..").then(|| name.to_string())
+}
+
+/// An existing directory is treated as the folder to clone into; any other
+/// path is the clone's own directory.
+pub(crate) fn clone_destination(url: &str, dest: &str) -> Result<PathBuf, String> {
+    let dest = dest.trim();
+    if dest.is_empty() {
+        return Err("Destination is required.".to_string());
+    }
+    let dest = Path::new(dest);
+    let destination = if dest.is_dir() {
+        dest.join(repo_name(url).ok_or("Could not derive a folder name from the URL.")?)
+    } else {
+        dest.to_path_buf()
+    };
+    if destination.exists() {
+        return Err(format!(
+            "Destination already exists: {}",
+            destination.display()
+        ));
+    }
+    Ok(destination)
+}
+
+/// Parses a `\r`- or `\n`-terminated line of `git clone --progress` output.
+pub(crate) fn parse_clone_progress(line: &str) -> Option<CloneProgress> {
+    let line = line.trim();
+    let message = line.strip_prefix("remote: ").unwrap_or(line).trim();
+    if message.is_empty() {
+        return None;
+    }
+    let (stage, percent) = match message.split_once(':') {
+        Some((stage, rest)) => {
+            let percent = rest
+                .split_whitespace()
+                .next()
+                .and_then(|value| value.strip_suffix('%'))
+                .and_then(|value| value.parse::<u8>().ok());
+            (stage.trim(), percent)
+        }
+        None => (message, None),
+    };
+    Some(CloneProgress {
+        stage: stage.to_string(),
+        percent,
+        message: message.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_urls_and_derives_names() {
+        assert!(validate_clone_url("https://github.com/me/app.git").is_ok());
+        assert!(validate_clone_url("git@github.com:me/app.git").is_ok());
+        assert!(validate_clone_url("--upload-pack=touch /tmp/x").is_err());
+        assert!(validate_clone_url("/Users/me/app").is_err());
+        assert_eq!(
+            repo_name("git@github.com:me/app.git").as_deref(),
+            Some("app")
+        );
+        assert_eq!(
+            repo_name("https://example.com/group/tool/").as_deref(),
+            Some("tool")
+        );
+        assert!(repo_name("https://example.com/..").is_none());
+    }
+
+    #[test]
+    fn parses_progress_lines() {
+        let progress =
+            parse_clone_progress("Receiving objects:  45% (450/1000), 1.20 MiB | 2.00 MiB/s")
+                .expect("progress");
+        assert_eq!(progress.stage, "Receiving objects");
+        assert_eq!(progress.percent, Some(45));
+
+        let remote =
+            parse_clone_progress("remote: Counting objects: 100% (12/12), done.").expect("remote");
+        assert_eq!(remote.stage, "Counting objects");
+        assert_eq!(remote.percent, Some(100));
+
+        let cloning = parse_clone_progress("Cloning into 'app'...").expect("cloning");
+        assert_eq!(cloning.percent, None);
+        assert!(parse_clone_progress("  ").is_none());
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A JSON-RPC request id. The spec allows numbers and strings, and the
+/// app-server may use either for the requests it sends us.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(untagged)]
+pub(crate) enum RequestId {
+    Number(u64),
+    String(String),
+}
+
+impl RequestId {
+    /// The `id` of a JSON-RPC message, if it has a valid one.
+    pub(crate) fn from_message(message: &Value) -> Option<Self> {
+        Self::from_value(message.get("id")?)
+    }
+
+    pub(crate) fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(number) => number.as_u64().map(Self::Number),
+            Value::String(id) => Some(Self::String(id.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl From<u64> for RequestId {
+    fn from(id: u64) -> Self {
+        Self::Number(id)
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(id) => write!(f, "{id}"),
+            Self::String(id) => f.write_str(id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_numeric_and_string_ids() {
+        assert_eq!(
+            RequestId::from_message(&json!({ "id": 7, "method": "x" })),
+            Some(RequestId::Number(7))
+        );
+        assert_eq!(
+            RequestId::from_message(&json!({ "id": "req-7", "method": "x" })),
+            Some(RequestId::String("req-7".to_string()))
+        );
+        assert_eq!(RequestId::from_message(&json!({ "id": null })), None);
+        assert_eq!(RequestId::from_message(&json!({ "id": -1 })), None);
+        assert_eq!(RequestId::from_message(&json!({ "method": "x" })), None);
+
+        assert_eq!(json!(RequestId::Number(7)), json!(7));
+        assert_eq!(json!(RequestId::String("7".to_string())), json!("7"));
+        let parsed: RequestId = serde_json::from_value(json!("abc")).unwrap();
+        assert_eq!(parsed.to_string(), "abc");
+    }
+}
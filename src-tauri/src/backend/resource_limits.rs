@@ -0,0 +1,181 @@
+use tokio::process::Command;
+
+use crate::types::ResourceLimits;
+
+const MAX_NICE: i32 = 19;
+/// Smaller caps leave the app-server unable to start.
+const MIN_MEMORY_LIMIT_MB: u64 = 256;
+
+/// Rejects limits the OS would refuse or that would keep the app-server
+/// from starting.
+pub(crate) fn validate(limits: &ResourceLimits) -> Result<(), String> {
+    if let Some(nice) = limits.nice {
+        if !(0..=MAX_NICE).contains(&nice) {
+            return Err(format!("Nice level must be between 0 and {MAX_NICE}."));
+        }
+    }
+    if let Some(cpus) = &limits.cpu_affinity {
+        let available = std::thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(1);
+        if cpus.is_empty() {
+            return Err("CPU affinity needs at least one CPU.".to_string());
+        }
+        if let Some(cpu) = cpus.iter().find(|cpu| **cpu >= available) {
+            return Err(format!(
+                "CPU {cpu} does not exist; this machine has CPUs 0 to {}.",
+                available - 1
+            ));
+        }
+    }
+    if let Some(memory) = limits.memory_limit_mb {
+        if memory < MIN_MEMORY_LIMIT_MB {
+            return Err(format!(
+                "Memory limit must be at least {MIN_MEMORY_LIMIT_MB} MB."
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Windows has no nice levels; low levels map to below-normal priority and
+/// high ones to idle.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn windows_priority_class(nice: i32) -> Option<u32> {
+    const BELOW_NORMAL_PRIORITY_CLASS: u32 = 0x0000_4000;
+    const IDLE_PRIORITY_CLASS: u32 = 0x0000_0040;
+    match nice {
+        i32::MIN..=0 => None,
+        1..=9 => Some(BELOW_NORMAL_PRIORITY_CLASS),
+        _ => Some(IDLE_PRIORITY_CLASS),
+    }
+}
+
+/// Applies `limits` to the app-server about to be spawned by `command`; its
+/// own children, the agent's commands, inherit them. Returns the limits this
+/// platform cannot enforce, for the session log.
+pub(crate) fn apply(command: &mut Command, limits: &ResourceLimits) -> Vec<&'static str> {
+    #[cfg_attr(target_os = "linux", allow(unused_mut))]
+    let mut unsupported = Vec::new();
+    #[cfg(unix)]
+    {
+        let nice = limits.nice.filter(|nice| *nice > 0);
+        #[cfg(target_os = "linux")]
+        let cpu_set = limits.cpu_affinity.as_ref().map(|cpus| {
+            // SAFETY: an all-zero cpu_set_t is the empty set.
+            let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+            for cpu in cpus {
+                // SAFETY: `validate` keeps indices below the CPU count,
+                // far under CPU_SETSIZE.
+                unsafe { libc::CPU_SET(*cpu, &mut set) };
+            }
+            set
+        });
+        #[cfg(not(target_os = "linux"))]
+        if limits.cpu_affinity.is_some() {
+            unsupported.push("cpuAffinity");
+        }
+        // Linux counts private mappings toward RLIMIT_DATA, which caps real
+        // allocations without the huge address space reservations of V8
+        // tripping RLIMIT_AS.
+        #[cfg(target_os = "linux")]
+        let memory_resource = libc::RLIMIT_DATA;
+        #[cfg(not(target_os = "linux"))]
+        let memory_resource = libc::RLIMIT_AS;
+        let memory_bytes = limits
+            .memory_limit_mb
+            .map(|mb| mb.saturating_mul(1024 * 1024) as libc::rlim_t);
+        if nice.is_some() || memory_bytes.is_some() || limits.cpu_affinity.is_some() {
+            // SAFETY: the closure runs between fork and exec and only makes
+            // async-signal-safe system calls on data prepared beforehand.
+            unsafe {
+                command.pre_exec(move || {
+                    if let Some(nice) = nice {
+                        if libc::setpriority(libc::PRIO_PROCESS, 0, nice) != 0 {
+                            return Err(std::io::Error::last_os_error());
+                        }
+                    }
+                    #[cfg(target_os = "linux")]
+                    if let Some(set) = &cpu_set {
+                        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), set)
+                            != 0
+                        {
+                            return Err(std::io::Error::last_os_error());
+                        }
+                    }
+                    if let Some(bytes) = memory_bytes {
+                        let limit = libc::rlimit {
+                            rlim_cur: bytes,
+                            rlim_max: bytes,
+                        };
+                        if libc::setrlimit(memory_resource, &limit) != 0 {
+                            return Err(std::io::Error::last_os_error());
+                        }
+                    }
+                    Ok(())
+                });
+            }
+        }
+    }
+    #[cfg(windows)]
+    {
+        if let Some(class) = limits.nice.and_then(windows_priority_class) {
+            command.creation_flags(class);
+        }
+        // Affinity and memory caps need a job object, which sessions do not
+        // create.
+        if limits.cpu_affinity.is_some() {
+            unsupported.push("cpuAffinity");
+        }
+        if limits.memory_limit_mb.is_some() {
+            unsupported.push("memoryLimitMb");
+        }
+    }
+    unsupported
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_limits_and_maps_windows_priorities() {
+        let limits = |nice, cpu_affinity, memory_limit_mb| ResourceLimits {
+            nice,
+            cpu_affinity,
+            memory_limit_mb,
+        };
+        assert!(validate(&limits(Some(10), Some(vec![0]), Some(2048))).is_ok());
+        assert!(validate(&limits(Some(20), None, None)).is_err());
+        assert!(validate(&limits(Some(-5), None, None)).is_err());
+        assert!(validate(&limits(None, Some(Vec::new()), None)).is_err());
+        assert!(validate(&limits(None, Some(vec![usize::MAX]), None)).is_err());
+        assert!(validate(&limits(None, None, Some(64))).is_err());
+        assert_eq!(windows_priority_class(0), None);
+        assert_eq!(windows_priority_class(5), Some(0x4000));
+        assert_eq!(windows_priority_class(19), Some(0x40));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn spawned_process_runs_with_the_nice_level() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "nice"]);
+        let limits = ResourceLimits {
+            nice: Some(7),
+            cpu_affinity: None,
+            memory_limit_mb: None,
+        };
+        assert!(apply(&mut command, &limits).is_empty());
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let output = runtime.block_on(async { command.output().await }).unwrap();
+        let niceness: i32 = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .unwrap();
+        assert!(niceness >= 7);
+    }
+}
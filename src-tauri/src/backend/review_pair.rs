@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::backend::turn_summary::{params_thread_id, params_turn_id};
+
+/// Larger diffs are cut before being handed to the reviewer.
+const MAX_REVIEW_DIFF_BYTES: usize = 200 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ReviewPairStatus {
+    /// The implementation turn is running in the worktree.
+    Building,
+    /// The review of its diff was started in the original workspace.
+    Reviewing,
+    Failed,
+}
+
+/// A builder thread in a forked worktree linked to the reviewer thread that
+/// reviews its diff in the original workspace.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReviewPair {
+    pub(crate) pair_id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) worktree_id: String,
+    pub(crate) branch: String,
+    pub(crate) prompt: String,
+    pub(crate) builder_thread_id: String,
+    pub(crate) builder_turn_id: Option<String>,
+    pub(crate) reviewer_thread_id: String,
+    pub(crate) status: ReviewPairStatus,
+    pub(crate) error: Option<String>,
+    pub(crate) created_at: i64,
+}
+
+/// Review pairs of this session, keyed by id.
+#[derive(Default)]
+pub(crate) struct ReviewPairTracker {
+    pairs: HashMap<String, ReviewPair>,
+}
+
+impl ReviewPairTracker {
+    pub(crate) fn register(&mut self, pair: ReviewPair) {
+        self.pairs.insert(pair.pair_id.clone(), pair);
+    }
+
+    pub(crate) fn update(
+        &mut self,
+        pair_id: &str,
+        apply: impl FnOnce(&mut ReviewPair),
+    ) -> Option<ReviewPair> {
+        let pair = self.pairs.get_mut(pair_id)?;
+        apply(pair);
+        Some(pair.clone())
+    }
+
+    /// Returns the pair whose building turn just completed, marked as
+    /// reviewing so later turns of the builder thread do not review again.
+    pub(crate) fn observe(&mut self, workspace_id: &str, message: &Value) -> Option<ReviewPair> {
+        if message.get("method").and_then(|value| value.as_str()) != Some("turn/completed") {
+            return None;
+        }
+        let params = message.get("params")?;
+        let thread_id = params_thread_id(params)?;
+        let turn_id = params_turn_id(params);
+        let pair = self.pairs.values_mut().find(|pair| {
+            pair.status == ReviewPairStatus::Building
+                && pair.worktree_id == workspace_id
+                && pair.builder_thread_id == thread_id
+                && (pair.builder_turn_id.is_none() || pair.builder_turn_id == turn_id)
+        })?;
+        pair.builder_turn_id = turn_id.or(pair.builder_turn_id.take());
+        pair.status = ReviewPairStatus::Reviewing;
+        Some(pair.clone())
+    }
+
+    /// Pairs of a workspace or of its worktrees, newest first.
+    pub(crate) fn list(&self, workspace_id: Option<&str>) -> Vec<ReviewPair> {
+        let mut pairs: Vec<ReviewPair> = self
+            .pairs
+            .values()
+            .filter(|pair| {
+                workspace_id.is_none_or(|id| pair.workspace_id == id || pair.worktree_id == id)
+            })
+            .cloned()
+            .collect();
+        pairs.sort_by_key(|pair| std::cmp::Reverse(pair.created_at));
+        pairs
+    }
+}
+
+/// Custom review instructions carrying the builder's diff, since the
+/// original workspace cannot see the worktree's uncommitted changes.
+pub(crate) fn review_instructions(pair: &ReviewPair, diff: &str) -> String {
+    let mut end = diff.len().min(MAX_REVIEW_DIFF_BYTES);
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut instructions = format!(
+        "Review the changes another agent made on branch `{}` in a separate worktree.",
+        pair.branch
+    );
+    let prompt = pair.prompt.trim();
+    if !prompt.is_empty() {
+        instructions.push_str(&format!("\n\nIt was asked to:\n{prompt}"));
+    }
+    instructions.push_str(&format!("\n\n```diff\n{}\n```", diff[..end].trim_end()));
+    if end < diff.len() {
+        instructions.push_str(&format!(
+            "\n\nThe diff was cut after {MAX_REVIEW_DIFF_BYTES} bytes."
+        ));
+    }
+    instructions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pair() -> ReviewPair {
+        ReviewPair {
+            pair_id: "pair-1".to_string(),
+            workspace_id: "ws".to_string(),
+            worktree_id: "wt".to_string(),
+            branch: "codex/pair-1".to_string(),
+            prompt: "Add a flag".to_string(),
+            builder_thread_id: "t-build".to_string(),
+            builder_turn_id: Some("turn-1".to_string()),
+            reviewer_thread_id: "t-review".to_string(),
+            status: ReviewPairStatus::Building,
+            error: None,
+            created_at: 1,
+        }
+    }
+
+    #[test]
+    fn reviews_once_the_building_turn_completes() {
+        let mut tracker = ReviewPairTracker::default();
+        tracker.register(pair());
+        let completed = |turn_id: &str| {
+            json!({
+                "method": "turn/completed",
+                "params": { "threadId": "t-build", "turn": { "id": turn_id } }
+            })
+        };
+        assert!(tracker.observe("ws", &completed("turn-1")).is_none());
+        assert!(tracker.observe("wt", &completed("turn-0")).is_none());
+        let reviewing = tracker.observe("wt", &completed("turn-1")).expect("pair");
+        assert_eq!(reviewing.status, ReviewPairStatus::Reviewing);
+        assert!(tracker.observe("wt", &completed("turn-1")).is_none());
+        assert_eq!(tracker.list(Some("wt")).len(), 1);
+        assert!(tracker.list(Some("other")).is_empty());
+    }
+
+    #[test]
+    fn instructions_embed_a_bounded_diff() {
+        let instructions = review_instructions(&pair(), "+a\n");
+        assert!(instructions.contains("`codex/pair-1`"));
+        assert!(instructions.contains("Add a flag"));
+        assert!(instructions.contains("```diff\n+a\n```"));
+
+        let large = "+é\n".repeat(MAX_REVIEW_DIFF_BYTES);
+        let instructions = review_instructions(&pair(), &large);
+        assert!(instructions.len() < MAX_REVIEW_DIFF_BYTES + 500);
+        assert!(instructions.ends_with("bytes."));
+    }
+}
@@ -0,0 +1,42 @@
+use serde_json::Value;
+
+/// The `result` of an app-server response, or the response itself when it
+/// was already unwrapped (remote calls return the bare result).
+pub(crate) fn response_result(response: &Value) -> &Value {
+    response.get("result").unwrap_or(response)
+}
+
+/// The error message of a failed app-server response.
+pub(crate) fn response_error(response: &Value) -> Option<String> {
+    let error = response.get("error")?;
+    Some(
+        error
+            .get("message")
+            .and_then(|value| value.as_str())
+            .unwrap_or("app-server request failed")
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn unwraps_results_and_errors() {
+        let ok = json!({ "id": 1, "result": { "thread": { "id": "t-1" } } });
+        assert_eq!(response_result(&ok), &json!({ "thread": { "id": "t-1" } }));
+        assert_eq!(response_error(&ok), None);
+        let bare = json!({ "thread": { "id": "t-1" } });
+        assert_eq!(response_result(&bare), &bare);
+        assert_eq!(
+            response_error(&json!({ "error": { "message": "no such thread" } })).as_deref(),
+            Some("no such thread")
+        );
+        assert_eq!(
+            response_error(&json!({ "error": {} })).as_deref(),
+            Some("app-server request failed")
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+use crate::backend::patch_staging::{staged_file, StagedFile};
+use crate::types::GitFileDiff;
+
+/// Approval policy of scratch turns: anything not known to be safe asks.
+pub(crate) const SCRATCH_APPROVAL_POLICY: &str = "untrusted";
+
+/// Throwaway checkouts made by `spawn_scratch_workspace`.
+pub(crate) fn scratch_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("scratch")
+}
+
+/// The sandbox of every scratch turn: writes only inside the scratch
+/// directory, no network. Read-only turns stay read-only.
+pub(crate) fn scratch_sandbox_policy(access_mode: &str, path: &str) -> Value {
+    if access_mode == "read-only" {
+        return json!({ "type": "readOnly" });
+    }
+    json!({
+        "type": "workspaceWrite",
+        "writableRoots": [path],
+        "networkAccess": false
+    })
+}
+
+/// `add`, `delete` or `update`, from a file's git diff headers.
+fn change_kind(diff: &str) -> &'static str {
+    for line in diff.lines() {
+        if line.starts_with("@@") {
+            break;
+        }
+        if line.starts_with("new file mode") {
+            return "add";
+        }
+        if line.starts_with("deleted file mode") {
+            return "delete";
+        }
+    }
+    "update"
+}
+
+/// Staged files for a scratch workspace's uncommitted changes. Binary files
+/// have no hunks and are left out.
+pub(crate) fn staged_files(diffs: &[GitFileDiff]) -> Vec<StagedFile> {
+    diffs
+        .iter()
+        .filter_map(|diff| staged_file(&diff.path, Some(change_kind(&diff.diff)), &diff.diff))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stages_scratch_diffs_by_kind() {
+        let diffs = vec![
+            GitFileDiff {
+                path: "src/new.rs".to_string(),
+                diff: "diff --git a/src/new.rs b/src/new.rs\nnew file mode 100644\n\
+--- /dev/null\n+++ b/src/new.rs\n@@ -0,0 +1,2 @@\n+fn a() {}\n+fn b() {}\n"
+                    .to_string(),
+            },
+            GitFileDiff {
+                path: "README.md".to_string(),
+                diff: "diff --git a/README.md b/README.md\n--- a/README.md\n+++ b/README.md\n\
+@@ -1,2 +1,2 @@\n # Title\n-old\n+new\n"
+                    .to_string(),
+            },
+            GitFileDiff {
+                path: "logo.png".to_string(),
+                diff: "diff --git a/logo.png b/logo.png\nBinary files differ\n".to_string(),
+            },
+        ];
+        let files = staged_files(&diffs);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].kind.as_deref(), Some("add"));
+        assert_eq!(files[0].hunks[0].lines, vec!["+fn a() {}", "+fn b() {}"]);
+        assert_eq!(files[1].kind.as_deref(), Some("update"));
+        assert_eq!(
+            scratch_sandbox_policy("full-access", "/tmp/scratch")["networkAccess"],
+            json!(false)
+        );
+    }
+}
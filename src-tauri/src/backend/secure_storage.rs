@@ -0,0 +1,254 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::path::{Path, PathBuf};
+
+use crate::backend::{
+    annotations, approval_audit, clock, code_stats, command_output, directory_grants, full_access,
+    issue_links, model_stats, offline_queue, patch_staging, prompt_insights, semantic_index,
+    task_queue, telemetry, thread_archive, thread_branches, thread_journal, thread_links,
+    thread_settings, thread_sla, thread_tags, turn_labels, turn_manifest, workspace_health,
+    workspace_prewarm,
+};
+
+const ENCRYPTED_PREFIX: &str = "codexmonitor-enc-v1:";
+const KEYCHAIN_SERVICE: &str = "CodexMonitor";
+const KEYCHAIN_ACCOUNT: &str = "local-data-key";
+const NONCE_LEN: usize = 12;
+
+/// Encrypts local data files when enabled. Files written without a key stay
+/// plaintext, and plaintext files remain readable after encryption is enabled.
+#[derive(Clone, Default)]
+pub(crate) struct DataCipher {
+    key: Option<Key>,
+}
+
+impl DataCipher {
+    pub(crate) fn plaintext() -> Self {
+        Self { key: None }
+    }
+
+    fn with_key(key: Key) -> Self {
+        Self { key: Some(key) }
+    }
+
+    /// Loads the data key from the OS keychain, creating one on first use.
+    fn from_keychain() -> Result<Self, String> {
+        let entry = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+            .map_err(|err| format!("Failed to open keychain: {err}"))?;
+        match entry.get_password() {
+            Ok(encoded) => {
+                let bytes = STANDARD
+                    .decode(encoded.trim())
+                    .map_err(|err| format!("Invalid data key in keychain: {err}"))?;
+                if bytes.len() != 32 {
+                    return Err("Invalid data key in keychain.".to_string());
+                }
+                Ok(Self::with_key(*Key::from_slice(&bytes)))
+            }
+            Err(keyring::Error::NoEntry) => {
+                let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+                entry
+                    .set_password(&STANDARD.encode(key))
+                    .map_err(|err| format!("Failed to store data key in keychain: {err}"))?;
+                Ok(Self::with_key(key))
+            }
+            Err(err) => Err(format!("Failed to read data key from keychain: {err}")),
+        }
+    }
+
+    fn encrypt(&self, plaintext: &str) -> Result<String, String> {
+        let Some(key) = self.key.as_ref() else {
+            return Ok(plaintext.to_string());
+        };
+        let cipher = ChaCha20Poly1305::new(key);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| "Failed to encrypt data.".to_string())?;
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&ciphertext);
+        Ok(format!("{ENCRYPTED_PREFIX}{}", STANDARD.encode(payload)))
+    }
+
+    fn decrypt(&self, contents: &str) -> Result<String, String> {
+        let Some(encoded) = contents.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(contents.to_string());
+        };
+        let key = self
+            .key
+            .as_ref()
+            .ok_or("Data is encrypted but local data encryption is disabled.")?;
+        let payload = STANDARD
+            .decode(encoded.trim())
+            .map_err(|err| format!("Invalid encrypted data: {err}"))?;
+        if payload.len() < NONCE_LEN {
+            return Err("Invalid encrypted data.".to_string());
+        }
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let plaintext = ChaCha20Poly1305::new(key)
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| "Failed to decrypt data. The keychain key may have changed.".to_string())?;
+        String::from_utf8(plaintext).map_err(|err| err.to_string())
+    }
+
+    pub(crate) fn read_to_string(&self, path: &Path) -> Result<String, String> {
+        let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        self.decrypt(&contents)
+    }
+
+    pub(crate) fn write(&self, path: &Path, contents: &str) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let data = self.encrypt(contents)?;
+        std::fs::write(path, data).map_err(|e| e.to_string())
+    }
+}
+
+/// Local data files that follow the encryption setting.
+fn protected_data_files(data_dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![
+        thread_archive::archive_path(data_dir),
+        issue_links::issue_links_path(data_dir),
+        task_queue::task_queue_path(data_dir),
+        offline_queue::offline_queue_path(data_dir),
+        thread_settings::thread_settings_path(data_dir),
+        thread_tags::thread_tags_path(data_dir),
+        thread_tags::thread_filters_path(data_dir),
+        thread_links::thread_links_path(data_dir),
+        turn_labels::turn_labels_path(data_dir),
+        thread_branches::thread_branches_path(data_dir),
+        annotations::annotations_path(data_dir),
+        thread_sla::thread_timers_path(data_dir),
+        clock::clock_jumps_path(data_dir),
+        directory_grants::directory_grants_path(data_dir),
+        full_access::full_access_grants_path(data_dir),
+        workspace_health::health_report_path(data_dir),
+        approval_audit::chain_head_path(data_dir),
+        workspace_prewarm::workspace_usage_path(data_dir),
+    ];
+    files.extend(code_stats::code_stats_files(data_dir));
+    files.extend(telemetry::telemetry_files(data_dir));
+    files.extend(prompt_insights::prompt_outcome_files(data_dir));
+    files.extend(workspace_health::health_sample_files(data_dir));
+    files.extend(model_stats::model_sample_files(data_dir));
+    files.extend(approval_audit::audit_files(data_dir));
+    for dir in [
+        command_output::command_outputs_dir(data_dir),
+        command_output::full_outputs_dir(data_dir),
+        semantic_index::semantic_index_dir(data_dir),
+        patch_staging::staged_patches_dir(data_dir),
+        turn_manifest::turn_manifests_dir(data_dir),
+        thread_journal::thread_journals_dir(data_dir),
+    ] {
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            files.extend(
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_file()),
+            );
+        }
+    }
+    files
+}
+
+/// The keychain-backed cipher, read from the keychain once and then shared,
+/// since data files are read and written on hot event paths.
+#[derive(Default)]
+pub(crate) struct CipherCache {
+    keyed: std::sync::Mutex<Option<DataCipher>>,
+}
+
+impl CipherCache {
+    pub(crate) fn cipher(&self, encrypt_local_data: bool) -> Result<DataCipher, String> {
+        if !encrypt_local_data {
+            return Ok(DataCipher::plaintext());
+        }
+        let mut keyed = self.keyed.lock().map_err(|err| err.to_string())?;
+        if let Some(cipher) = keyed.as_ref() {
+            return Ok(cipher.clone());
+        }
+        let cipher = DataCipher::from_keychain()?;
+        *keyed = Some(cipher.clone());
+        Ok(cipher)
+    }
+
+    /// Forgets the cached key, so the next use reads the keychain again.
+    fn refresh(&self) {
+        if let Ok(mut keyed) = self.keyed.lock() {
+            *keyed = None;
+        }
+    }
+}
+
+/// Re-writes a data file with a different cipher, e.g. after toggling encryption.
+fn reencrypt_file(path: &Path, from: &DataCipher, to: &DataCipher) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let contents = from.read_to_string(path)?;
+    to.write(path, &contents)
+}
+
+/// Migrates protected data files when the encryption setting changes.
+/// Re-reads the keychain key first, in case it changed since it was cached.
+pub(crate) fn apply_encryption_setting(
+    data_dir: &Path,
+    ciphers: &CipherCache,
+    was_enabled: bool,
+    enabled: bool,
+) -> Result<(), String> {
+    if was_enabled == enabled {
+        return Ok(());
+    }
+    ciphers.refresh();
+    let from = ciphers.cipher(was_enabled)?;
+    let to = ciphers.cipher(enabled)?;
+    for path in protected_data_files(data_dir) {
+        reencrypt_file(&path, &from, &to)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn test_cipher() -> DataCipher {
+        DataCipher::with_key(ChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    #[test]
+    fn round_trips_encrypted_files() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = dir.join("data.json");
+        let cipher = test_cipher();
+        cipher.write(&path, "{\"secret\":true}").expect("write");
+
+        let raw = std::fs::read_to_string(&path).expect("read raw");
+        assert!(raw.starts_with(ENCRYPTED_PREFIX));
+        assert!(!raw.contains("secret"));
+        assert_eq!(cipher.read_to_string(&path).expect("read"), "{\"secret\":true}");
+        assert!(DataCipher::plaintext().read_to_string(&path).is_err());
+        assert!(test_cipher().read_to_string(&path).is_err());
+    }
+
+    #[test]
+    fn reencrypt_file_migrates_plaintext() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = dir.join("data.json");
+        let plain = DataCipher::plaintext();
+        plain.write(&path, "[]").expect("write");
+        let cipher = test_cipher();
+        assert_eq!(cipher.read_to_string(&path).expect("read plaintext"), "[]");
+
+        reencrypt_file(&path, &plain, &cipher).expect("encrypt");
+        assert!(plain.read_to_string(&path).is_err());
+        reencrypt_file(&path, &cipher, &plain).expect("decrypt");
+        assert_eq!(std::fs::read_to_string(&path).expect("read raw"), "[]");
+    }
+}
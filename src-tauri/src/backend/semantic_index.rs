@@ -0,0 +1,431 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use crate::backend::command_output::{command_text, safe_file_name, strip_ansi};
+use crate::backend::secure_storage::DataCipher;
+use crate::backend::turn_summary::params_thread_id;
+
+/// Model id stored with vectors from the built-in hashed embedding.
+pub(crate) const LOCAL_MODEL: &str = "local-hash-v1";
+const LOCAL_DIMENSIONS: usize = 384;
+const PASSAGE_MAX_CHARS: usize = 800;
+/// Only the end of a command's output is indexed; that is where results and
+/// errors usually are.
+const COMMAND_OUTPUT_INDEX_CHARS: usize = 2_400;
+const MAX_PASSAGES_PER_WORKSPACE: usize = 5_000;
+pub(crate) const DEFAULT_RESULT_LIMIT: usize = 20;
+/// Function words left out of local vectors; they would otherwise dominate
+/// short passages.
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "that", "this", "with", "was", "were", "are", "is", "to", "of", "in",
+    "on", "at", "it", "an", "be", "by", "as", "or", "we", "you", "time", "when", "then",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum PassageSource {
+    Message,
+    CommandOutput,
+}
+
+/// A chunk of transcript text waiting to be embedded.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PendingPassage {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) item_id: String,
+    pub(crate) source: PassageSource,
+    pub(crate) role: Option<String>,
+    pub(crate) text: String,
+    pub(crate) created_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IndexedPassage {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) item_id: String,
+    pub(crate) source: PassageSource,
+    pub(crate) role: Option<String>,
+    pub(crate) text: String,
+    pub(crate) created_at: i64,
+    /// Embedding model the vector came from; vectors of other models are
+    /// not comparable and are skipped when searching.
+    pub(crate) model: String,
+    pub(crate) vector: Vec<f32>,
+}
+
+impl IndexedPassage {
+    pub(crate) fn new(passage: PendingPassage, model: &str, vector: Vec<f32>) -> Self {
+        Self {
+            workspace_id: passage.workspace_id,
+            thread_id: passage.thread_id,
+            item_id: passage.item_id,
+            source: passage.source,
+            role: passage.role,
+            text: passage.text,
+            created_at: passage.created_at,
+            model: model.to_string(),
+            vector,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SemanticMatch {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) item_id: String,
+    pub(crate) source: PassageSource,
+    pub(crate) role: Option<String>,
+    pub(crate) text: String,
+    pub(crate) created_at: i64,
+    pub(crate) score: f32,
+}
+
+/// FNV-1a, stable across builds so stored local vectors stay comparable.
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn add_feature(vector: &mut [f32], feature: &str, weight: f32) {
+    let hash = fnv1a(feature);
+    let index = (hash % vector.len() as u64) as usize;
+    let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+    vector[index] += sign * weight;
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|value| *value /= norm);
+    }
+}
+
+/// Hashed bag of words, word bigrams and character trigrams. Trigrams let
+/// "flaky websocket test" match "websockets are flaking in tests".
+pub(crate) fn local_embedding(text: &str) -> Vec<f32> {
+    let mut vector = vec![0.0; LOCAL_DIMENSIONS];
+    let words: Vec<String> = text
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1)
+        .map(|word| word.to_lowercase())
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .collect();
+    for (index, word) in words.iter().enumerate() {
+        add_feature(&mut vector, &format!("w:{word}"), 1.0);
+        if let Some(next) = words.get(index + 1) {
+            add_feature(&mut vector, &format!("b:{word} {next}"), 0.5);
+        }
+        let padded: Vec<char> = format!("<{word}>").chars().collect();
+        for trigram in padded.windows(3) {
+            let trigram: String = trigram.iter().collect();
+            add_feature(&mut vector, &format!("t:{trigram}"), 0.3);
+        }
+    }
+    normalize(&mut vector);
+    vector
+}
+
+pub(crate) fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Splits text into passages of at most `PASSAGE_MAX_CHARS`, preferring line
+/// boundaries.
+pub(crate) fn chunk_text(text: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_chars = 0;
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_chars = line.chars().count();
+        if current_chars > 0 && current_chars + line_chars + 1 > PASSAGE_MAX_CHARS {
+            chunks.push(std::mem::take(&mut current));
+            current_chars = 0;
+        }
+        if line_chars > PASSAGE_MAX_CHARS {
+            let chars: Vec<char> = line.chars().collect();
+            for piece in chars.chunks(PASSAGE_MAX_CHARS) {
+                chunks.push(piece.iter().collect());
+            }
+            continue;
+        }
+        if current_chars > 0 {
+            current.push('\n');
+            current_chars += 1;
+        }
+        current.push_str(line);
+        current_chars += line_chars;
+    }
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+fn message_text(item: &Value) -> Option<(String, &'static str)> {
+    match item.get("type").and_then(|value| value.as_str())? {
+        "agentMessage" => item
+            .get("text")
+            .and_then(|value| value.as_str())
+            .map(|text| (text.to_string(), "assistant")),
+        "userMessage" => {
+            let content = item.get("content").and_then(|value| value.as_array())?;
+            let text = content
+                .iter()
+                .filter_map(|part| part.get("text").and_then(|value| value.as_str()))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Some((text, "user"))
+        }
+        _ => None,
+    }
+}
+
+fn tail_chars(text: &str, max_chars: usize) -> &str {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text;
+    }
+    let start = text
+        .char_indices()
+        .nth(count - max_chars)
+        .map(|(index, _)| index)
+        .unwrap_or(0);
+    &text[start..]
+}
+
+/// Passages to index for an `item/completed` notification: user and agent
+/// messages, and the command line plus output tail of executed commands.
+pub(crate) fn passages_from_event(
+    workspace_id: &str,
+    message: &Value,
+    now_ms: i64,
+) -> Vec<PendingPassage> {
+    if message.get("method").and_then(|value| value.as_str()) != Some("item/completed") {
+        return Vec::new();
+    }
+    let Some(params) = message.get("params") else {
+        return Vec::new();
+    };
+    let Some(item) = params.get("item") else {
+        return Vec::new();
+    };
+    let Some(item_id) = item.get("id").and_then(|value| value.as_str()) else {
+        return Vec::new();
+    };
+    let (text, source, role) = if let Some((text, role)) = message_text(item) {
+        (text, PassageSource::Message, Some(role.to_string()))
+    } else if item.get("type").and_then(|value| value.as_str()) == Some("commandExecution") {
+        let output = item
+            .get("aggregatedOutput")
+            .and_then(|value| value.as_str())
+            .map(strip_ansi)
+            .unwrap_or_default();
+        let text = format!(
+            "$ {}\n{}",
+            command_text(item.get("command")),
+            tail_chars(&output, COMMAND_OUTPUT_INDEX_CHARS)
+        );
+        (text, PassageSource::CommandOutput, None)
+    } else {
+        return Vec::new();
+    };
+    let thread_id = params_thread_id(params);
+    chunk_text(&text)
+        .into_iter()
+        .map(|text| PendingPassage {
+            workspace_id: workspace_id.to_string(),
+            thread_id: thread_id.clone(),
+            item_id: item_id.to_string(),
+            source,
+            role: role.clone(),
+            text,
+            created_at: now_ms,
+        })
+        .collect()
+}
+
+pub(crate) fn semantic_index_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("semantic_index")
+}
+
+fn workspace_file(data_dir: &Path, workspace_id: &str) -> PathBuf {
+    semantic_index_dir(data_dir).join(format!("{}.json", safe_file_name(workspace_id)))
+}
+
+fn read_file(path: &Path, cipher: &DataCipher) -> Result<Vec<IndexedPassage>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+/// Appends passages of one workspace, dropping the oldest beyond the cap.
+pub(crate) fn record_passages(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    passages: Vec<IndexedPassage>,
+) -> Result<(), String> {
+    let path = workspace_file(data_dir, workspace_id);
+    let mut indexed = read_file(&path, cipher)?;
+    indexed.extend(passages);
+    if indexed.len() > MAX_PASSAGES_PER_WORKSPACE {
+        let excess = indexed.len() - MAX_PASSAGES_PER_WORKSPACE;
+        indexed.drain(..excess);
+    }
+    let data = serde_json::to_string(&indexed).map_err(|e| e.to_string())?;
+    cipher.write(&path, &data)
+}
+
+/// Indexed passages of one workspace, or of every workspace.
+pub(crate) fn read_passages(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: Option<&str>,
+) -> Result<Vec<IndexedPassage>, String> {
+    if let Some(workspace_id) = workspace_id {
+        return read_file(&workspace_file(data_dir, workspace_id), cipher);
+    }
+    let Ok(entries) = std::fs::read_dir(semantic_index_dir(data_dir)) else {
+        return Ok(Vec::new());
+    };
+    let mut passages = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            passages.extend(read_file(&path, cipher)?);
+        }
+    }
+    Ok(passages)
+}
+
+/// Ranks passages embedded with `model` by cosine similarity to the query.
+pub(crate) fn search_passages(
+    passages: Vec<IndexedPassage>,
+    query_vector: &[f32],
+    model: &str,
+    limit: usize,
+) -> Vec<SemanticMatch> {
+    let mut matches: Vec<SemanticMatch> = passages
+        .into_iter()
+        .filter(|passage| passage.model == model)
+        .filter_map(|passage| {
+            let score = cosine(&passage.vector, query_vector);
+            (score > 0.0).then_some(SemanticMatch {
+                workspace_id: passage.workspace_id,
+                thread_id: passage.thread_id,
+                item_id: passage.item_id,
+                source: passage.source,
+                role: passage.role,
+                text: passage.text,
+                created_at: passage.created_at,
+                score,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.created_at.cmp(&a.created_at))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn extracts_and_chunks_message_and_command_passages() {
+        let message = json!({
+            "method": "item/completed",
+            "params": {
+                "threadId": "t1",
+                "item": { "type": "agentMessage", "id": "m1", "text": "Fixed the flaky test." }
+            }
+        });
+        let passages = passages_from_event("ws-1", &message, 1);
+        assert_eq!(passages.len(), 1);
+        assert_eq!(passages[0].role.as_deref(), Some("assistant"));
+        assert_eq!(passages[0].thread_id.as_deref(), Some("t1"));
+
+        let command = json!({
+            "method": "item/completed",
+            "params": {
+                "threadId": "t1",
+                "item": {
+                    "type": "commandExecution",
+                    "id": "c1",
+                    "command": ["cargo", "test"],
+                    "aggregatedOutput": "\u{1b}[32mok\u{1b}[0m"
+                }
+            }
+        });
+        let passages = passages_from_event("ws-1", &command, 1);
+        assert_eq!(passages[0].source, PassageSource::CommandOutput);
+        assert_eq!(passages[0].text, "$ cargo test\nok");
+
+        let long_line = "x".repeat(PASSAGE_MAX_CHARS + 10);
+        assert_eq!(chunk_text(&format!("intro\n{long_line}")).len(), 3);
+    }
+
+    #[test]
+    fn ranks_related_passages_with_local_embeddings() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        let texts = [
+            "The websocket reconnect test was flaky; added a retry with backoff.",
+            "Updated the README with installation steps.",
+            "Bumped the tauri dependency to the latest version.",
+        ];
+        let passages = texts
+            .iter()
+            .enumerate()
+            .map(|(index, text)| PendingPassage {
+                workspace_id: "ws-1".to_string(),
+                thread_id: Some(format!("t{index}")),
+                item_id: format!("m{index}"),
+                source: PassageSource::Message,
+                role: Some("assistant".to_string()),
+                text: text.to_string(),
+                created_at: index as i64,
+            })
+            .map(|passage| {
+                let vector = local_embedding(&passage.text);
+                IndexedPassage::new(passage, LOCAL_MODEL, vector)
+            })
+            .collect();
+        record_passages(&dir, &cipher, "ws-1", passages).expect("record");
+
+        let stored = read_passages(&dir, &cipher, None).expect("read");
+        let query = local_embedding("that time the agent fixed the flaky websocket tests");
+        let matches = search_passages(stored.clone(), &query, LOCAL_MODEL, 2);
+        assert_eq!(matches[0].item_id, "m0");
+        assert!(search_passages(stored, &query, "other-model", 2).is_empty());
+    }
+}
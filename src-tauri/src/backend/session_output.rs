@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Raw lines kept per session for `tail_session_output`.
+const MAX_LINES: usize = 2_000;
+/// Longer lines are cut, so one giant payload cannot hold the buffer's
+/// memory hostage.
+const MAX_LINE_BYTES: usize = 16 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// One line the app-server wrote, as read, before JSON parsing or stderr
+/// filtering. Streamed as `session-output` while the session is live.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionOutputLine {
+    pub(crate) workspace_id: String,
+    pub(crate) stream: OutputStream,
+    pub(crate) line: String,
+    pub(crate) truncated: bool,
+    pub(crate) at: i64,
+}
+
+/// Ring buffer of a session's raw stdout and stderr lines.
+#[derive(Debug, Default)]
+pub(crate) struct SessionOutputBuffer {
+    lines: VecDeque<SessionOutputLine>,
+    live: bool,
+}
+
+impl SessionOutputBuffer {
+    /// Stores a line and returns it when it should also be streamed live.
+    pub(crate) fn push(
+        &mut self,
+        workspace_id: &str,
+        stream: OutputStream,
+        line: &str,
+        at: i64,
+    ) -> Option<SessionOutputLine> {
+        let mut end = line.len().min(MAX_LINE_BYTES);
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        let entry = SessionOutputLine {
+            workspace_id: workspace_id.to_string(),
+            stream,
+            line: line[..end].to_string(),
+            truncated: end < line.len(),
+            at,
+        };
+        if self.lines.len() == MAX_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(entry.clone());
+        self.live.then_some(entry)
+    }
+
+    /// The last `count` lines, oldest first.
+    pub(crate) fn tail(&self, count: usize) -> Vec<SessionOutputLine> {
+        let skip = self.lines.len().saturating_sub(count);
+        self.lines.iter().skip(skip).cloned().collect()
+    }
+
+    pub(crate) fn set_live(&mut self, live: bool) {
+        self.live = live;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_latest_lines_and_streams_only_when_live() {
+        let mut buffer = SessionOutputBuffer::default();
+        for index in 0..MAX_LINES + 5 {
+            assert!(buffer
+                .push("ws", OutputStream::Stdout, &format!("line {index}"), 0)
+                .is_none());
+        }
+        let tail = buffer.tail(2);
+        assert_eq!(tail.len(), 2);
+        assert_eq!(tail[1].line, format!("line {}", MAX_LINES + 4));
+        assert_eq!(buffer.tail(usize::MAX).len(), MAX_LINES);
+
+        buffer.set_live(true);
+        let long = "é".repeat(MAX_LINE_BYTES);
+        let streamed = buffer
+            .push("ws", OutputStream::Stderr, &long, 1)
+            .expect("live line");
+        assert!(streamed.truncated);
+        assert!(streamed.line.len() <= MAX_LINE_BYTES);
+    }
+}
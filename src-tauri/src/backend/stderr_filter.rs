@@ -0,0 +1,140 @@
+use regex::Regex;
+use serde::Serialize;
+
+use crate::types::{StderrFilterConfig, StderrSeverity};
+
+/// Summary emitted as `codex/stderrSuppressed` so filtered lines are never
+/// dropped silently.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SuppressedStderr {
+    pub(crate) count: usize,
+    pub(crate) denied: usize,
+    pub(crate) below_severity: usize,
+    /// Most recent suppressed line.
+    pub(crate) sample: String,
+}
+
+/// Guesses the severity of a stderr line from log-level markers.
+pub(crate) fn classify_severity(line: &str) -> StderrSeverity {
+    let lower = line.to_ascii_lowercase();
+    let has = |needles: &[&str]| needles.iter().any(|needle| lower.contains(needle));
+    if has(&["error", "fatal", "panic", "exception", "traceback"]) {
+        StderrSeverity::Error
+    } else if has(&["warn", "deprecat"]) {
+        StderrSeverity::Warning
+    } else if has(&["debug", "trace"]) {
+        StderrSeverity::Debug
+    } else {
+        StderrSeverity::Info
+    }
+}
+
+fn compile(patterns: &[String]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .filter(|pattern| !pattern.trim().is_empty())
+        .filter_map(|pattern| match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(err) => {
+                eprintln!("Ignoring invalid stderr filter pattern {pattern:?}: {err}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Compiled per-workspace stderr filter used by the app-server reader task.
+#[derive(Default)]
+pub(crate) struct StderrFilter {
+    allow: Vec<Regex>,
+    deny: Vec<Regex>,
+    min_severity: StderrSeverity,
+    denied: usize,
+    below_severity: usize,
+    sample: Option<String>,
+}
+
+impl StderrFilter {
+    pub(crate) fn new(config: Option<&StderrFilterConfig>) -> Self {
+        let Some(config) = config else {
+            return Self::default();
+        };
+        Self {
+            allow: compile(&config.allow),
+            deny: compile(&config.deny),
+            min_severity: config.min_severity,
+            ..Self::default()
+        }
+    }
+
+    /// Returns `true` when the line should be forwarded; suppressed lines are
+    /// counted for the next summary.
+    pub(crate) fn accept(&mut self, line: &str) -> bool {
+        if self.allow.iter().any(|regex| regex.is_match(line)) {
+            return true;
+        }
+        if self.deny.iter().any(|regex| regex.is_match(line)) {
+            self.denied += 1;
+        } else if classify_severity(line) < self.min_severity {
+            self.below_severity += 1;
+        } else {
+            return true;
+        }
+        self.sample = Some(line.to_string());
+        false
+    }
+
+    /// Takes the counts accumulated since the previous summary.
+    pub(crate) fn take_summary(&mut self) -> Option<SuppressedStderr> {
+        let sample = self.sample.take()?;
+        let summary = SuppressedStderr {
+            count: self.denied + self.below_severity,
+            denied: self.denied,
+            below_severity: self.below_severity,
+            sample,
+        };
+        self.denied = 0;
+        self.below_severity = 0;
+        Some(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allow_beats_deny_and_severity() {
+        let config = StderrFilterConfig {
+            allow: vec!["keep me".to_string()],
+            deny: vec!["(?i)deprecationwarning".to_string(), "(".to_string()],
+            min_severity: StderrSeverity::Warning,
+        };
+        let mut filter = StderrFilter::new(Some(&config));
+        assert!(filter.accept("ERROR: connection refused"));
+        assert!(filter.accept("warning: low disk"));
+        assert!(!filter.accept("DeprecationWarning: punycode is deprecated"));
+        assert!(!filter.accept("listening on port 1234"));
+        assert!(filter.accept("info: keep me please"));
+
+        let summary = filter.take_summary().expect("summary");
+        assert_eq!(
+            (summary.count, summary.denied, summary.below_severity),
+            (2, 1, 1)
+        );
+        assert_eq!(summary.sample, "listening on port 1234");
+        assert!(filter.take_summary().is_none());
+    }
+
+    #[test]
+    fn default_filter_forwards_everything() {
+        let mut filter = StderrFilter::new(None);
+        assert!(filter.accept("debug: verbose"));
+        assert!(filter.take_summary().is_none());
+        assert_eq!(
+            classify_severity("thread panicked at"),
+            StderrSeverity::Error
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum QueuedTaskStatus {
+    Queued,
+    Started,
+}
+
+/// Agent task waiting to be started in a workspace, e.g. from a webhook.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QueuedTask {
+    pub(crate) id: String,
+    pub(crate) workspace_id: String,
+    /// `linear` or `jira`.
+    pub(crate) source: String,
+    /// Issue identifier in the source tracker, e.g. `ENG-42`.
+    pub(crate) external_id: String,
+    pub(crate) title: String,
+    pub(crate) prompt: String,
+    pub(crate) url: String,
+    pub(crate) created_at: i64,
+    pub(crate) status: QueuedTaskStatus,
+    #[serde(default)]
+    pub(crate) thread_id: Option<String>,
+}
+
+pub(crate) fn task_queue_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("task_queue.json")
+}
+
+pub(crate) fn read_tasks(path: &Path, cipher: &DataCipher) -> Result<Vec<QueuedTask>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+fn write_tasks(path: &Path, cipher: &DataCipher, tasks: &[QueuedTask]) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(tasks).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+/// Appends a task unless one for the same source issue is already queued or
+/// running. Returns `false` for duplicates so repeated deliveries are ignored.
+pub(crate) fn enqueue_task(
+    path: &Path,
+    cipher: &DataCipher,
+    task: QueuedTask,
+) -> Result<bool, String> {
+    let mut tasks = read_tasks(path, cipher)?;
+    if tasks
+        .iter()
+        .any(|existing| existing.source == task.source && existing.external_id == task.external_id)
+    {
+        return Ok(false);
+    }
+    tasks.push(task);
+    write_tasks(path, cipher, &tasks)?;
+    Ok(true)
+}
+
+/// Marks a queued task as started in `thread_id`.
+pub(crate) fn mark_task_started(
+    path: &Path,
+    cipher: &DataCipher,
+    task_id: &str,
+    thread_id: &str,
+) -> Result<QueuedTask, String> {
+    let mut tasks = read_tasks(path, cipher)?;
+    let task = tasks
+        .iter_mut()
+        .find(|task| task.id == task_id)
+        .ok_or("Queued task not found.")?;
+    task.status = QueuedTaskStatus::Started;
+    task.thread_id = Some(thread_id.to_string());
+    let updated = task.clone();
+    write_tasks(path, cipher, &tasks)?;
+    Ok(updated)
+}
+
+pub(crate) fn remove_task(path: &Path, cipher: &DataCipher, task_id: &str) -> Result<(), String> {
+    let mut tasks = read_tasks(path, cipher)?;
+    let before = tasks.len();
+    tasks.retain(|task| task.id != task_id);
+    if tasks.len() == before {
+        return Err("Queued task not found.".to_string());
+    }
+    write_tasks(path, cipher, &tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn task(id: &str, external_id: &str) -> QueuedTask {
+        QueuedTask {
+            id: id.to_string(),
+            workspace_id: "ws".to_string(),
+            source: "linear".to_string(),
+            external_id: external_id.to_string(),
+            title: "Fix login".to_string(),
+            prompt: "Fix login".to_string(),
+            url: String::new(),
+            created_at: 1,
+            status: QueuedTaskStatus::Queued,
+            thread_id: None,
+        }
+    }
+
+    #[test]
+    fn enqueue_dedupes_and_tracks_status() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = task_queue_path(&dir);
+        let cipher = DataCipher::plaintext();
+
+        assert!(enqueue_task(&path, &cipher, task("a", "ENG-1")).unwrap());
+        assert!(!enqueue_task(&path, &cipher, task("b", "ENG-1")).unwrap());
+        assert!(enqueue_task(&path, &cipher, task("c", "ENG-2")).unwrap());
+
+        let started = mark_task_started(&path, &cipher, "a", "thread-1").unwrap();
+        assert_eq!(started.status, QueuedTaskStatus::Started);
+        assert_eq!(started.thread_id.as_deref(), Some("thread-1"));
+
+        remove_task(&path, &cipher, "c").expect("remove");
+        assert!(remove_task(&path, &cipher, "c").is_err());
+        assert_eq!(read_tasks(&path, &cipher).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::backend::turn_summary::{excerpt, params_thread_id};
+
+const SUMMARY_MAX_CHARS: usize = 200;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum TeamActivityStatus {
+    Running,
+    Completed,
+    Failed,
+    Interrupted,
+}
+
+/// What one member's agent is doing, as published to the team endpoint.
+/// Never carries code, paths or real thread ids.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TeamActivity {
+    pub(crate) member: String,
+    /// The workspace name, or a stable pseudonym unless names are shared.
+    pub(crate) workspace: String,
+    /// Stable pseudonym of the thread, to pair a turn's start and end.
+    pub(crate) thread_key: String,
+    pub(crate) status: TeamActivityStatus,
+    /// Code-free excerpt of the agent's last message, when summaries are
+    /// shared.
+    #[serde(default)]
+    pub(crate) summary: Option<String>,
+    #[serde(default)]
+    pub(crate) files_changed: Option<u64>,
+    #[serde(default)]
+    pub(crate) commands_run: Option<u64>,
+    #[serde(default)]
+    pub(crate) duration_ms: Option<u64>,
+    pub(crate) updated_at: i64,
+}
+
+/// What the user chose to share, from `teamActivity` settings.
+pub(crate) struct SharingOptions<'a> {
+    pub(crate) member: &'a str,
+    pub(crate) workspace_name: Option<&'a str>,
+    pub(crate) share_summaries: bool,
+}
+
+fn pseudonym(prefix: &str, value: &str) -> String {
+    let digest = Sha256::digest(value.as_bytes());
+    let short: String = digest[..4]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("{prefix}-{short}")
+}
+
+/// Drops fenced code blocks and inline code spans.
+fn strip_code(text: &str) -> String {
+    let mut prose = Vec::new();
+    let mut in_fence = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if !in_fence {
+            let without_spans: String = line
+                .split('`')
+                .enumerate()
+                .filter(|(index, _)| index % 2 == 0)
+                .map(|(_, part)| part)
+                .collect();
+            prose.push(without_spans);
+        }
+    }
+    prose
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn status_of(params: &Value) -> TeamActivityStatus {
+    let status = params
+        .get("turn")
+        .and_then(|turn| turn.get("status"))
+        .or_else(|| {
+            params
+                .get("summary")
+                .and_then(|summary| summary.get("status"))
+        })
+        .and_then(|value| value.as_str());
+    match status {
+        Some("failed") => TeamActivityStatus::Failed,
+        Some("interrupted") => TeamActivityStatus::Interrupted,
+        _ => TeamActivityStatus::Completed,
+    }
+}
+
+/// The activity to publish for a `turn/started` or `turn/completed`
+/// notification; `None` for every other message.
+pub(crate) fn activity_from_event(
+    workspace_id: &str,
+    message: &Value,
+    sharing: &SharingOptions,
+    now: i64,
+) -> Option<TeamActivity> {
+    let method = message.get("method").and_then(|value| value.as_str())?;
+    let params = message.get("params")?;
+    let thread_id = params_thread_id(params)?;
+    let mut activity = TeamActivity {
+        member: sharing.member.to_string(),
+        workspace: sharing
+            .workspace_name
+            .map(str::to_string)
+            .unwrap_or_else(|| pseudonym("workspace", workspace_id)),
+        thread_key: pseudonym("thread", &format!("{workspace_id}:{thread_id}")),
+        status: TeamActivityStatus::Running,
+        summary: None,
+        files_changed: None,
+        commands_run: None,
+        duration_ms: None,
+        updated_at: now,
+    };
+    match method {
+        "turn/started" => {}
+        "turn/completed" => {
+            activity.status = status_of(params);
+            if let Some(summary) = params.get("summary") {
+                let count = |key: &str| summary.get(key).and_then(|value| value.as_u64());
+                activity.files_changed = count("filesChanged");
+                activity.commands_run = count("commandsRun");
+                activity.duration_ms = count("durationMs");
+                if sharing.share_summaries {
+                    activity.summary = summary
+                        .get("lastAgentMessage")
+                        .and_then(|value| value.as_str())
+                        .map(|text| excerpt(&strip_code(text), SUMMARY_MAX_CHARS))
+                        .filter(|text| !text.is_empty());
+                }
+            }
+        }
+        _ => return None,
+    }
+    Some(activity)
+}
+
+/// Parses the endpoint's feed, either a bare list or `{ "activities": [...] }`.
+pub(crate) fn parse_feed(value: Value) -> Result<Vec<TeamActivity>, String> {
+    let list = match value {
+        Value::Object(mut object) => object.remove("activities").unwrap_or(Value::Null),
+        other => other,
+    };
+    serde_json::from_value(list).map_err(|e| format!("Invalid team activity feed: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn publishes_code_free_summaries_under_pseudonyms() {
+        let sharing = SharingOptions {
+            member: "dana",
+            workspace_name: None,
+            share_summaries: true,
+        };
+        let completed = json!({
+            "method": "turn/completed",
+            "params": {
+                "threadId": "t1",
+                "turn": { "status": "completed" },
+                "summary": {
+                    "lastAgentMessage": "Fixed the `parse` bug:\n```rust\nfn secret() {}\n```\nAll tests pass.",
+                    "filesChanged": 2,
+                    "commandsRun": 3,
+                    "durationMs": 4000
+                }
+            }
+        });
+        let activity = activity_from_event("ws-1", &completed, &sharing, 10).expect("activity");
+        assert_eq!(activity.status, TeamActivityStatus::Completed);
+        assert_eq!(
+            activity.summary.as_deref(),
+            Some("Fixed the bug: All tests pass.")
+        );
+        assert_eq!(activity.files_changed, Some(2));
+        assert!(activity.workspace.starts_with("workspace-"));
+        assert!(!activity.thread_key.contains("t1"));
+
+        let started = json!({ "method": "turn/started", "params": { "threadId": "t1" } });
+        let hidden = SharingOptions {
+            workspace_name: Some("api"),
+            share_summaries: false,
+            ..sharing
+        };
+        let running = activity_from_event("ws-1", &started, &hidden, 5).expect("activity");
+        assert_eq!(running.status, TeamActivityStatus::Running);
+        assert_eq!(running.workspace, "api");
+        assert_eq!(running.thread_key, activity.thread_key);
+        assert!(
+            activity_from_event("ws-1", &json!({ "method": "item/started" }), &hidden, 5).is_none()
+        );
+    }
+
+    #[test]
+    fn parses_bare_and_wrapped_feeds() {
+        let entry = json!({
+            "member": "lee",
+            "workspace": "web",
+            "threadKey": "thread-1",
+            "status": "running",
+            "updatedAt": 1
+        });
+        assert_eq!(parse_feed(json!([entry.clone()])).unwrap().len(), 1);
+        assert_eq!(
+            parse_feed(json!({ "activities": [entry] })).unwrap()[0].member,
+            "lee"
+        );
+        assert!(parse_feed(json!({ "other": true })).is_err());
+    }
+}
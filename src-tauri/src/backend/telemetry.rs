@@ -0,0 +1,307 @@
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::backend::code_stats::analytics_dir;
+use crate::backend::secure_storage::DataCipher;
+
+/// Daily feature usage files inside the analytics data class directory, so
+/// the analytics retention window and purge apply to them.
+const FILE_PREFIX: &str = "feature-usage-";
+/// Remembers the last day whose aggregates were shared.
+const SHARED_MARKER: &str = "feature-usage-shared.json";
+/// Days before the last shared one are never uploaded, nor more than this
+/// many at once.
+const MAX_UPLOAD_DAYS: i64 = 7;
+
+pub(crate) type FeatureCounts = BTreeMap<String, u64>;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FeatureUsage {
+    pub(crate) feature: String,
+    pub(crate) count: u64,
+    pub(crate) days_used: u32,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DayUsage {
+    pub(crate) day: String,
+    pub(crate) total: u64,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TelemetryReport {
+    pub(crate) days: u32,
+    pub(crate) total: u64,
+    /// Most used first.
+    pub(crate) features: Vec<FeatureUsage>,
+    /// Oldest first, one entry per day.
+    pub(crate) daily: Vec<DayUsage>,
+}
+
+/// One day's counts as uploaded: no ids, paths or names, and counts rounded
+/// up to a coarse bucket.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SharedAggregate {
+    pub(crate) day: String,
+    pub(crate) app_version: String,
+    pub(crate) features: FeatureCounts,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct SharedMarker {
+    /// `YYYY-MM-DD`.
+    last_shared_day: Option<String>,
+}
+
+/// Feature names are short identifiers like `panel.git`, so nothing else
+/// can end up in the counters.
+pub(crate) fn is_valid_feature(feature: &str) -> bool {
+    (1..=64).contains(&feature.len())
+        && feature
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-' | '_'))
+}
+
+/// The feature an app-server notification counts as, if any.
+pub(crate) fn feature_for_event(message: &Value) -> Option<&'static str> {
+    let method = message.get("method").and_then(|value| value.as_str())?;
+    match method {
+        "turn/started" => Some("turn.started"),
+        "turn/completed" => {
+            let status = message
+                .get("params")
+                .and_then(|params| params.get("turn"))
+                .and_then(|turn| turn.get("status"))
+                .and_then(|value| value.as_str());
+            match status {
+                Some("failed") => Some("turn.failed"),
+                Some("interrupted") => Some("turn.interrupted"),
+                _ => None,
+            }
+        }
+        "review/started" => Some("review.started"),
+        method if method.ends_with("requestApproval") => Some("approval.requested"),
+        _ => None,
+    }
+}
+
+fn day_file(data_dir: &Path, day: NaiveDate) -> PathBuf {
+    analytics_dir(data_dir).join(format!("{FILE_PREFIX}{}.json", day.format("%Y-%m-%d")))
+}
+
+fn shared_marker_path(data_dir: &Path) -> PathBuf {
+    analytics_dir(data_dir).join(SHARED_MARKER)
+}
+
+/// Feature usage files, which follow the encryption setting.
+pub(crate) fn telemetry_files(data_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(analytics_dir(data_dir)) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(FILE_PREFIX))
+        })
+        .collect()
+}
+
+pub(crate) fn read_day(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    day: NaiveDate,
+) -> Result<FeatureCounts, String> {
+    let path = day_file(data_dir, day);
+    if !path.exists() {
+        return Ok(FeatureCounts::new());
+    }
+    let data = cipher.read_to_string(&path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn record(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    feature: &str,
+    day: NaiveDate,
+) -> Result<(), String> {
+    let mut counts = read_day(data_dir, cipher, day)?;
+    *counts.entry(feature.to_string()).or_default() += 1;
+    let data = serde_json::to_string(&counts).map_err(|e| e.to_string())?;
+    cipher.write(&day_file(data_dir, day), &data)
+}
+
+/// Usage over the last `days` days, today included.
+pub(crate) fn build_report(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    days: u32,
+    today: NaiveDate,
+) -> Result<TelemetryReport, String> {
+    let mut totals: BTreeMap<String, (u64, u32)> = BTreeMap::new();
+    let mut daily = Vec::new();
+    for offset in (0..days).rev() {
+        let day = today - Duration::days(i64::from(offset));
+        let counts = read_day(data_dir, cipher, day)?;
+        for (feature, count) in &counts {
+            let entry = totals.entry(feature.clone()).or_default();
+            entry.0 += count;
+            entry.1 += 1;
+        }
+        daily.push(DayUsage {
+            day: day.format("%Y-%m-%d").to_string(),
+            total: counts.values().sum(),
+        });
+    }
+    let mut features: Vec<FeatureUsage> = totals
+        .into_iter()
+        .map(|(feature, (count, days_used))| FeatureUsage {
+            feature,
+            count,
+            days_used,
+        })
+        .collect();
+    features.sort_by(|a, b| b.count.cmp(&a.count).then(a.feature.cmp(&b.feature)));
+    Ok(TelemetryReport {
+        days,
+        total: features.iter().map(|usage| usage.count).sum(),
+        features,
+        daily,
+    })
+}
+
+/// Rounds a count up to 1, 2 or 5 times a power of ten.
+fn bucket(count: u64) -> u64 {
+    let mut scale = 1;
+    loop {
+        for step in [1, 2, 5] {
+            if count <= step * scale {
+                return step * scale;
+            }
+        }
+        scale *= 10;
+    }
+}
+
+pub(crate) fn anonymize(
+    day: NaiveDate,
+    counts: &FeatureCounts,
+    app_version: &str,
+) -> SharedAggregate {
+    SharedAggregate {
+        day: day.format("%Y-%m-%d").to_string(),
+        app_version: app_version.to_string(),
+        features: counts
+            .iter()
+            .map(|(feature, count)| (feature.clone(), bucket(*count)))
+            .collect(),
+    }
+}
+
+/// Completed days not shared yet, oldest first. Today is left out since its
+/// counts still change.
+pub(crate) fn days_to_share(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    today: NaiveDate,
+) -> Vec<NaiveDate> {
+    let marker: SharedMarker = cipher
+        .read_to_string(&shared_marker_path(data_dir))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default();
+    let earliest = today - Duration::days(MAX_UPLOAD_DAYS);
+    let start = marker
+        .last_shared_day
+        .and_then(|day| NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok())
+        .map(|day| day + Duration::days(1))
+        .filter(|day| *day > earliest)
+        .unwrap_or(earliest);
+    start.iter_days().take_while(|day| *day < today).collect()
+}
+
+pub(crate) fn mark_shared(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    day: NaiveDate,
+) -> Result<(), String> {
+    let marker = SharedMarker {
+        last_shared_day: Some(day.format("%Y-%m-%d").to_string()),
+    };
+    let data = serde_json::to_string(&marker).map_err(|e| e.to_string())?;
+    cipher.write(&shared_marker_path(data_dir), &data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn maps_events_and_validates_feature_names() {
+        assert_eq!(
+            feature_for_event(&json!({ "method": "turn/started", "params": {} })),
+            Some("turn.started")
+        );
+        assert_eq!(
+            feature_for_event(&json!({
+                "method": "turn/completed",
+                "params": { "turn": { "status": "failed" } }
+            })),
+            Some("turn.failed")
+        );
+        assert_eq!(
+            feature_for_event(&json!({ "method": "item/commandExecution/requestApproval" })),
+            Some("approval.requested")
+        );
+        assert_eq!(
+            feature_for_event(&json!({ "method": "item/started" })),
+            None
+        );
+        assert!(is_valid_feature("panel.git"));
+        assert!(!is_valid_feature("path /Users/me/secret"));
+        assert!(!is_valid_feature(""));
+        assert_eq!(
+            (bucket(0), bucket(3), bucket(7), bucket(11)),
+            (1, 5, 10, 20)
+        );
+    }
+
+    #[test]
+    fn aggregates_days_and_tracks_shared_days() {
+        let data_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let yesterday = today - Duration::days(1);
+        record(&data_dir, &cipher, "panel.git", today).unwrap();
+        record(&data_dir, &cipher, "panel.git", yesterday).unwrap();
+        record(&data_dir, &cipher, "turn.started", yesterday).unwrap();
+
+        let report = build_report(&data_dir, &cipher, 3, today).unwrap();
+        assert_eq!(report.total, 3);
+        assert_eq!(report.features[0].feature, "panel.git");
+        assert_eq!(report.features[0].days_used, 2);
+        assert_eq!(
+            report.daily.iter().map(|day| day.total).collect::<Vec<_>>(),
+            [0, 2, 1]
+        );
+
+        assert_eq!(days_to_share(&data_dir, &cipher, today).len(), 7);
+        mark_shared(&data_dir, &cipher, yesterday).unwrap();
+        assert!(days_to_share(&data_dir, &cipher, today).is_empty());
+        assert_eq!(telemetry_files(&data_dir).len(), 3);
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
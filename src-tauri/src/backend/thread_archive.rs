@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::secure_storage::DataCipher;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ArchivedThread {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) archived_at: i64,
+    pub(crate) expires_at: i64,
+    #[serde(default)]
+    pub(crate) thread: Option<Value>,
+}
+
+pub(crate) fn archive_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("archived_threads.json")
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn read_archived_threads(path: &Path, cipher: &DataCipher) -> Result<Vec<ArchivedThread>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+fn write_archived_threads(
+    path: &Path,
+    cipher: &DataCipher,
+    entries: &[ArchivedThread],
+) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+fn prune_expired(entries: Vec<ArchivedThread>, now: i64) -> Vec<ArchivedThread> {
+    entries
+        .into_iter()
+        .filter(|entry| entry.expires_at > now)
+        .collect()
+}
+
+fn record_tombstone(
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+    thread: Option<Value>,
+    retention_days: u32,
+    now: i64,
+) -> Result<(), String> {
+    let mut entries = prune_expired(read_archived_threads(path, cipher)?, now);
+    entries.retain(|entry| !(entry.workspace_id == workspace_id && entry.thread_id == thread_id));
+    entries.push(ArchivedThread {
+        workspace_id: workspace_id.to_string(),
+        thread_id: thread_id.to_string(),
+        archived_at: now,
+        expires_at: now + i64::from(retention_days.max(1)) * DAY_MS,
+        thread,
+    });
+    write_archived_threads(path, cipher, &entries)
+}
+
+fn take_tombstone(
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+    now: i64,
+) -> Result<Option<ArchivedThread>, String> {
+    let mut entries = prune_expired(read_archived_threads(path, cipher)?, now);
+    let index = entries
+        .iter()
+        .position(|entry| entry.workspace_id == workspace_id && entry.thread_id == thread_id);
+    let taken = index.map(|index| entries.remove(index));
+    write_archived_threads(path, cipher, &entries)?;
+    Ok(taken)
+}
+
+/// Returns tombstones that are still inside the retention window, newest first.
+pub(crate) fn list_recently_archived(
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: Option<&str>,
+) -> Result<Vec<ArchivedThread>, String> {
+    let mut entries = prune_expired(read_archived_threads(path, cipher)?, now_ms());
+    entries.retain(|entry| workspace_id.is_none_or(|id| entry.workspace_id == id));
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.archived_at));
+    Ok(entries)
+}
+
+/// Archives the thread on the app-server and keeps a local tombstone with the
+/// last known thread snapshot so the archive can be undone.
+pub(crate) async fn archive_thread_with_tombstone(
+    session: &WorkspaceSession,
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+    retention_days: u32,
+) -> Result<Value, String> {
+    let snapshot = session
+        .send_request("thread/resume", json!({ "threadId": thread_id }))
+        .await
+        .ok()
+        .and_then(|response| response.get("result").and_then(|result| result.get("thread")).cloned());
+    let response = session
+        .send_request("thread/archive", json!({ "threadId": thread_id }))
+        .await?;
+    if response.get("error").is_none() {
+        record_tombstone(
+            path,
+            cipher,
+            workspace_id,
+            thread_id,
+            snapshot,
+            retention_days,
+            now_ms(),
+        )?;
+    }
+    Ok(response)
+}
+
+pub(crate) async fn unarchive_thread(
+    session: &WorkspaceSession,
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+) -> Result<Value, String> {
+    let response = session
+        .send_request("thread/unarchive", json!({ "threadId": thread_id }))
+        .await?;
+    if let Some(error) = response.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|value| value.as_str())
+            .unwrap_or("Failed to unarchive thread");
+        return Err(message.to_string());
+    }
+    let tombstone = take_tombstone(path, cipher, workspace_id, thread_id, now_ms())?;
+    Ok(json!({
+        "result": response.get("result").cloned().unwrap_or(Value::Null),
+        "thread": tombstone.and_then(|entry| entry.thread),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_archive_path() -> PathBuf {
+        let temp_dir =
+            std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&temp_dir).expect("create temp dir");
+        archive_path(&temp_dir)
+    }
+
+    #[test]
+    fn tombstones_expire_after_retention_window() {
+        let path = temp_archive_path();
+        let cipher = DataCipher::plaintext();
+        record_tombstone(&path, &cipher, "ws-1", "t-old", None, 1, 0).expect("record old");
+        let now = 2 * DAY_MS;
+        let snapshot = Some(json!({ "id": "t-new" }));
+        record_tombstone(&path, &cipher, "ws-1", "t-new", snapshot, 1, now).expect("record new");
+
+        let entries = read_archived_threads(&path, &cipher).expect("read");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].thread_id, "t-new");
+        assert_eq!(entries[0].expires_at, now + DAY_MS);
+    }
+
+    #[test]
+    fn take_tombstone_removes_matching_entry() {
+        let path = temp_archive_path();
+        let cipher = DataCipher::plaintext();
+        let now = now_ms();
+        record_tombstone(&path, &cipher, "ws-1", "t-1", None, 7, now).expect("record");
+        record_tombstone(&path, &cipher, "ws-2", "t-1", None, 7, now).expect("record");
+
+        let taken = take_tombstone(&path, &cipher, "ws-1", "t-1", now).expect("take");
+        assert_eq!(taken.map(|entry| entry.workspace_id).as_deref(), Some("ws-1"));
+        let remaining = list_recently_archived(&path, &cipher, None).expect("list");
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].workspace_id, "ws-2");
+        assert!(list_recently_archived(&path, &cipher, Some("ws-1"))
+            .expect("list")
+            .is_empty());
+    }
+}
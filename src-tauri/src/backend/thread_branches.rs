@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+
+/// Branches that count as "main" for automatic thread branches.
+const MAIN_BRANCHES: &[&str] = &["main", "master"];
+/// Longest slug taken from the thread title, before the prefix.
+const MAX_SLUG_LEN: usize = 48;
+
+/// Branch created for a thread by the auto-branch policy.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadBranch {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) branch: String,
+    /// Branch that was checked out when the thread branch was created.
+    pub(crate) base_branch: String,
+    pub(crate) created_at: i64,
+}
+
+pub(crate) fn thread_branches_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("thread_branches.json")
+}
+
+pub(crate) fn is_main_branch(name: &str) -> bool {
+    MAIN_BRANCHES.contains(&name)
+}
+
+/// Branch name for a thread: the prefix followed by a slug of the title,
+/// or of the thread id when the title has nothing usable.
+pub(crate) fn branch_name(prefix: &str, title: &str, thread_id: &str) -> String {
+    let slug = slugify(title);
+    let slug = if slug.is_empty() {
+        let id: String = slugify(thread_id).chars().take(12).collect();
+        id.trim_end_matches('-').to_string()
+    } else {
+        slug
+    };
+    format!("{}{slug}", prefix.trim())
+}
+
+fn slugify(value: &str) -> String {
+    let mut slug = String::new();
+    for ch in value.chars() {
+        if ch.is_ascii_alphanumeric() {
+            if slug.len() >= MAX_SLUG_LEN {
+                break;
+            }
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Appends `-2`, `-3`, ... until `exists` no longer holds.
+pub(crate) fn unique_branch_name(base: &str, exists: impl Fn(&str) -> bool) -> String {
+    if !exists(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|suffix| format!("{base}-{suffix}"))
+        .find(|candidate| !exists(candidate))
+        .unwrap_or_else(|| base.to_string())
+}
+
+pub(crate) fn read_thread_branches(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<ThreadBranch>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn find_thread_branch(
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+) -> Result<Option<ThreadBranch>, String> {
+    Ok(read_thread_branches(path, cipher)?
+        .into_iter()
+        .find(|entry| entry.workspace_id == workspace_id && entry.thread_id == thread_id))
+}
+
+pub(crate) fn record_thread_branch(
+    path: &Path,
+    cipher: &DataCipher,
+    entry: ThreadBranch,
+) -> Result<(), String> {
+    let mut entries = read_thread_branches(path, cipher)?;
+    entries.retain(|existing| {
+        !(existing.workspace_id == entry.workspace_id && existing.thread_id == entry.thread_id)
+    });
+    entries.push(entry);
+    let data = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_branches_from_thread_title() {
+        assert_eq!(
+            branch_name("codex/", "Fix the login redirect (again)!", "t1"),
+            "codex/fix-the-login-redirect-again"
+        );
+        assert_eq!(
+            branch_name("codex/", "修正", "019a-thread-id-long"),
+            "codex/019a-thread"
+        );
+        let long = branch_name("", &"word ".repeat(30), "t1");
+        assert!(long.len() <= MAX_SLUG_LEN);
+        assert!(!long.ends_with('-'));
+
+        let taken = ["codex/fix", "codex/fix-2"];
+        assert_eq!(
+            unique_branch_name("codex/fix", |name| taken.contains(&name)),
+            "codex/fix-3"
+        );
+        assert!(is_main_branch("master"));
+        assert!(!is_main_branch("feature/main"));
+    }
+}
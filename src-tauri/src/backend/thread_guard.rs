@@ -0,0 +1,216 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::backend::patch_staging::StagedPatch;
+use crate::backend::request_id::RequestId;
+use crate::backend::turn_summary::{params_thread_id, params_turn_id};
+
+/// Error code of the structured error returned instead of discarding work.
+pub(crate) const NEEDS_ATTENTION: &str = "needsAttention";
+
+/// An approval request the app-server is still waiting on.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PendingApproval {
+    pub(crate) request_id: RequestId,
+    pub(crate) method: String,
+    pub(crate) turn_id: Option<String>,
+}
+
+/// Unanswered approval requests keyed by workspace and thread.
+#[derive(Debug, Default)]
+pub(crate) struct PendingApprovalTracker {
+    by_thread: HashMap<(String, String), Vec<PendingApproval>>,
+}
+
+impl PendingApprovalTracker {
+    pub(crate) fn observe(&mut self, workspace_id: &str, message: &Value) {
+        let Some(method) = message.get("method").and_then(|value| value.as_str()) else {
+            return;
+        };
+        let Some(params) = message.get("params") else {
+            return;
+        };
+        let Some(thread_id) = params_thread_id(params) else {
+            return;
+        };
+        let key = (workspace_id.to_string(), thread_id);
+        if method.ends_with("requestApproval") {
+            let Some(request_id) = RequestId::from_message(message) else {
+                return;
+            };
+            let pending = self.by_thread.entry(key).or_default();
+            pending.retain(|approval| approval.request_id != request_id);
+            pending.push(PendingApproval {
+                request_id,
+                method: method.to_string(),
+                turn_id: params_turn_id(params),
+            });
+        } else if method == "turn/completed" {
+            // Requests of a finished turn can no longer be answered.
+            self.by_thread.remove(&key);
+        }
+    }
+
+    pub(crate) fn resolve(&mut self, workspace_id: &str, request_id: &RequestId) {
+        self.by_thread.retain(|(workspace, _), pending| {
+            if workspace == workspace_id {
+                pending.retain(|approval| &approval.request_id != request_id);
+            }
+            !pending.is_empty()
+        });
+    }
+
+    pub(crate) fn pending(&self, workspace_id: &str, thread_id: &str) -> Vec<PendingApproval> {
+        self.by_thread
+            .get(&(workspace_id.to_string(), thread_id.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// A staged patch that still has hunks to apply.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UnappliedPatch {
+    pub(crate) id: String,
+    pub(crate) turn_id: Option<String>,
+    pub(crate) files: Vec<String>,
+    pub(crate) unapplied_hunks: usize,
+}
+
+/// In-flight work that archiving or forking a thread would discard.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadAttention {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) staged_patches: Vec<UnappliedPatch>,
+    pub(crate) pending_approvals: Vec<PendingApproval>,
+}
+
+impl ThreadAttention {
+    pub(crate) fn new(
+        workspace_id: &str,
+        thread_id: &str,
+        patches: &[StagedPatch],
+        pending_approvals: Vec<PendingApproval>,
+    ) -> Self {
+        let staged_patches = patches
+            .iter()
+            .filter(|patch| patch.thread_id == thread_id)
+            .filter_map(|patch| {
+                let unapplied_hunks = patch
+                    .files
+                    .iter()
+                    .flat_map(|file| &file.hunks)
+                    .filter(|hunk| !hunk.applied)
+                    .count();
+                (unapplied_hunks > 0).then(|| UnappliedPatch {
+                    id: patch.id.clone(),
+                    turn_id: patch.turn_id.clone(),
+                    files: patch.files.iter().map(|file| file.path.clone()).collect(),
+                    unapplied_hunks,
+                })
+            })
+            .collect();
+        Self {
+            workspace_id: workspace_id.to_string(),
+            thread_id: thread_id.to_string(),
+            staged_patches,
+            pending_approvals,
+        }
+    }
+
+    pub(crate) fn is_clear(&self) -> bool {
+        self.staged_patches.is_empty() && self.pending_approvals.is_empty()
+    }
+
+    /// The error string returned to the caller: JSON with `code`, a readable
+    /// `message` and the `attention` details, so the UI can list what would
+    /// be lost and retry with `force`.
+    pub(crate) fn into_error(self, action: &str) -> String {
+        let message = format!(
+            "Cannot {action} the thread: {} staged patch(es) are unapplied and {} \
+approval(s) are pending. Pass force to {action} anyway.",
+            self.staged_patches.len(),
+            self.pending_approvals.len(),
+        );
+        json!({
+            "code": NEEDS_ATTENTION,
+            "message": message,
+            "attention": self,
+        })
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::patch_staging::{PatchHunk, StagedFile, StagedPatchSource};
+
+    #[test]
+    fn tracks_approvals_until_answered_or_turn_completes() {
+        let mut tracker = PendingApprovalTracker::default();
+        let request = |id: u64| {
+            json!({
+                "id": id,
+                "method": "item/fileChange/requestApproval",
+                "params": { "threadId": "t-1", "turnId": "turn-1" }
+            })
+        };
+        tracker.observe("ws", &request(3));
+        tracker.observe("ws", &request(4));
+        assert_eq!(tracker.pending("ws", "t-1").len(), 2);
+        assert_eq!(
+            tracker.pending("ws", "t-1")[0].turn_id.as_deref(),
+            Some("turn-1")
+        );
+
+        tracker.resolve("ws", &RequestId::Number(3));
+        assert_eq!(tracker.pending("ws", "t-1").len(), 1);
+        tracker.observe(
+            "ws",
+            &json!({ "method": "turn/completed", "params": { "turn": { "id": "turn-1", "threadId": "t-1" } } }),
+        );
+        assert!(tracker.pending("ws", "t-1").is_empty());
+    }
+
+    #[test]
+    fn lists_only_unapplied_patches_of_the_thread() {
+        let patch = |id: &str, thread_id: &str, applied: bool| StagedPatch {
+            id: id.to_string(),
+            workspace_id: "ws".to_string(),
+            thread_id: thread_id.to_string(),
+            turn_id: Some("turn-1".to_string()),
+            source: StagedPatchSource::DryRun,
+            created_at: 0,
+            files: vec![StagedFile {
+                path: "src/main.rs".to_string(),
+                kind: Some("update".to_string()),
+                hunks: vec![PatchHunk {
+                    header: "@@ -1 +1 @@".to_string(),
+                    old_start: 1,
+                    lines: vec!["-a".to_string(), "+b".to_string()],
+                    applied,
+                }],
+            }],
+        };
+        let patches = [
+            patch("p-1", "t-1", false),
+            patch("p-2", "t-1", true),
+            patch("p-3", "t-2", false),
+        ];
+        let attention = ThreadAttention::new("ws", "t-1", &patches, Vec::new());
+        assert!(!attention.is_clear());
+        assert_eq!(attention.staged_patches.len(), 1);
+        assert_eq!(attention.staged_patches[0].id, "p-1");
+
+        let error: Value = serde_json::from_str(&attention.into_error("archive")).expect("json");
+        assert_eq!(error["code"], NEEDS_ATTENTION);
+        assert_eq!(error["attention"]["stagedPatches"][0]["unappliedHunks"], 1);
+        assert!(ThreadAttention::new("ws", "t-3", &patches, Vec::new()).is_clear());
+    }
+}
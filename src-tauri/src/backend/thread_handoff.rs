@@ -0,0 +1,179 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+
+use crate::backend::thread_journal::BackfillItem;
+use crate::backend::thread_links::ThreadLink;
+
+/// Most recent part of a thread's history given to the summarizing turn.
+pub(crate) const MAX_TRANSCRIPT_CHARS: usize = 40_000;
+/// Command output kept per command in the transcript.
+const MAX_OUTPUT_CHARS: usize = 1_000;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadHandoff {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) summary: String,
+    /// Artifacts copied to the new thread, by their path from
+    /// `list_turn_artifacts`.
+    pub(crate) artifacts: Vec<String>,
+    pub(crate) link: ThreadLink,
+}
+
+fn tail_chars(text: &str, max_chars: usize) -> &str {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text;
+    }
+    let start = text
+        .char_indices()
+        .nth(count - max_chars)
+        .map(|(index, _)| index)
+        .unwrap_or(0);
+    &text[start..]
+}
+
+fn item_text(item: &Value) -> Option<String> {
+    let text = |key: &str| item.get(key).and_then(|value| value.as_str());
+    match item.get("type").and_then(|value| value.as_str())? {
+        "userMessage" => {
+            let content = item.get("content").and_then(|value| value.as_array())?;
+            let text = content
+                .iter()
+                .filter_map(|part| part.get("text").and_then(|value| value.as_str()))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Some(format!("User: {}", text.trim()))
+        }
+        "agentMessage" => Some(format!("Agent: {}", text("text")?.trim())),
+        "commandExecution" => {
+            let command = match item.get("command") {
+                Some(Value::Array(parts)) => parts
+                    .iter()
+                    .filter_map(|part| part.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                Some(Value::String(command)) => command.clone(),
+                _ => return None,
+            };
+            let output = text("aggregatedOutput").unwrap_or_default().trim_end();
+            Some(format!(
+                "Command: {command}\n{}",
+                tail_chars(output, MAX_OUTPUT_CHARS)
+            ))
+        }
+        "fileChange" => {
+            let paths: Vec<&str> = item
+                .get("changes")
+                .and_then(|value| value.as_array())?
+                .iter()
+                .filter_map(|change| change.get("path").and_then(|value| value.as_str()))
+                .collect();
+            Some(format!("Changed files: {}", paths.join(", ")))
+        }
+        _ => None,
+    }
+}
+
+/// Messages, commands and file changes of a thread's history as plain
+/// text, keeping the most recent `max_chars`.
+pub(crate) fn transcript(history: &[BackfillItem], max_chars: usize) -> String {
+    let text = history
+        .iter()
+        .filter_map(|entry| item_text(&entry.item))
+        .map(|text| text.trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    tail_chars(&text, max_chars).to_string()
+}
+
+pub(crate) fn summary_prompt(transcript: &str) -> String {
+    format!(
+        "The work in the conversation below moves to another repository and continues in a new \
+thread there. Summarize it for that thread: the goal, what was decided and why, what was done, \
+the open questions and the next steps. Name files, commands and APIs exactly. Only output the \
+summary.\n\n<conversation>\n{transcript}\n</conversation>"
+    )
+}
+
+/// First message of the new thread.
+pub(crate) fn seed_message(
+    source_name: &str,
+    summary: &str,
+    artifacts: &[String],
+    artifacts_dir: Option<&Path>,
+) -> String {
+    let mut message = format!(
+        "This thread continues work handed off from a thread in the `{source_name}` workspace. \
+Summary of that thread:\n\n{summary}"
+    );
+    if let Some(dir) = artifacts_dir.filter(|_| !artifacts.is_empty()) {
+        message.push_str(&format!(
+            "\n\nFiles from that thread were copied to {}:\n",
+            dir.display()
+        ));
+        for artifact in artifacts {
+            message.push_str(&format!("- {artifact}\n"));
+        }
+    }
+    message.push_str(
+        "\n\nReview the summary against this repository and continue from the next steps.",
+    );
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(item: Value) -> BackfillItem {
+        BackfillItem {
+            turn_id: Some("turn-1".to_string()),
+            item,
+        }
+    }
+
+    #[test]
+    fn transcript_keeps_messages_commands_and_changes() {
+        let history = vec![
+            entry(json!({
+                "type": "userMessage",
+                "content": [{ "type": "text", "text": "Prototype the parser" }]
+            })),
+            entry(json!({ "type": "reasoning", "summary": ["thinking"] })),
+            entry(json!({
+                "type": "commandExecution",
+                "command": ["cargo", "test"],
+                "aggregatedOutput": "ok\n"
+            })),
+            entry(json!({ "type": "fileChange", "changes": [{ "path": "src/parser.rs" }] })),
+            entry(json!({ "type": "agentMessage", "text": "The parser works." })),
+        ];
+        let text = transcript(&history, MAX_TRANSCRIPT_CHARS);
+        assert_eq!(
+            text,
+            "User: Prototype the parser\n\nCommand: cargo test\nok\n\n\
+Changed files: src/parser.rs\n\nAgent: The parser works."
+        );
+        assert_eq!(transcript(&history, 24), "Agent: The parser works.");
+    }
+
+    #[test]
+    fn seed_message_lists_copied_artifacts() {
+        let message = seed_message(
+            "prototype",
+            "Built a parser.",
+            &["report.md".to_string()],
+            Some(Path::new("/data/artifacts/main.t2")),
+        );
+        assert!(message.contains("`prototype` workspace"));
+        assert!(message.contains("Built a parser."));
+        assert!(message.contains("copied to /data/artifacts/main.t2:\n- report.md\n"));
+
+        let message = seed_message("prototype", "Built a parser.", &[], None);
+        assert!(!message.contains("copied"));
+    }
+}
@@ -0,0 +1,359 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::backend::quick_search::QuickSearchCandidate;
+use crate::backend::thread_tags::{self, ThreadRecord, ThreadStatus};
+use crate::backend::turn_summary::params_thread_id;
+
+/// A workspace's threads are listed again once their index is this old;
+/// app-server events keep it current in between.
+pub(crate) const REFRESH_AFTER_MS: i64 = 5 * 60 * 1000;
+pub(crate) const DEFAULT_WINDOW_LIMIT: usize = 100;
+/// Largest window one query returns.
+pub(crate) const MAX_WINDOW_LIMIT: usize = 500;
+
+/// A thread as kept in the index, before tags and status are joined in.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IndexedThread {
+    pub(crate) thread_id: String,
+    pub(crate) title: String,
+    /// Epoch milliseconds.
+    pub(crate) updated_at: Option<i64>,
+}
+
+impl IndexedThread {
+    pub(crate) fn from_candidate(candidate: QuickSearchCandidate) -> Self {
+        Self {
+            thread_id: candidate.id,
+            title: candidate.title,
+            updated_at: candidate.updated_at.map(thread_tags::timestamp_ms),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct WorkspaceThreads {
+    refreshed_at: i64,
+    threads: HashMap<String, IndexedThread>,
+}
+
+/// Threads of each workspace from `thread/list`, kept current by
+/// app-server events so windowed queries need not list them again.
+#[derive(Debug, Default)]
+pub(crate) struct ThreadIndex {
+    workspaces: HashMap<String, WorkspaceThreads>,
+}
+
+impl ThreadIndex {
+    pub(crate) fn needs_refresh(&self, workspace_id: &str, now: i64) -> bool {
+        self.workspaces
+            .get(workspace_id)
+            .is_none_or(|indexed| now - indexed.refreshed_at >= REFRESH_AFTER_MS)
+    }
+
+    pub(crate) fn replace(&mut self, workspace_id: &str, threads: Vec<IndexedThread>, now: i64) {
+        self.workspaces.insert(
+            workspace_id.to_string(),
+            WorkspaceThreads {
+                refreshed_at: now,
+                threads: threads
+                    .into_iter()
+                    .map(|thread| (thread.thread_id.clone(), thread))
+                    .collect(),
+            },
+        );
+    }
+
+    /// Adds started threads and bumps the update time of threads with turn
+    /// activity. Workspaces not indexed yet are left to their first refresh.
+    pub(crate) fn observe(&mut self, workspace_id: &str, message: &Value, now: i64) {
+        let Some(indexed) = self.workspaces.get_mut(workspace_id) else {
+            return;
+        };
+        let Some(method) = message.get("method").and_then(|value| value.as_str()) else {
+            return;
+        };
+        let Some(params) = message.get("params") else {
+            return;
+        };
+        match method {
+            "thread/started" => {
+                let Some(thread) = params.get("thread") else {
+                    return;
+                };
+                let Some(thread_id) = thread.get("id").and_then(|value| value.as_str()) else {
+                    return;
+                };
+                let title = thread
+                    .get("preview")
+                    .and_then(|value| value.as_str())
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| default_title(thread_id));
+                indexed.threads.insert(
+                    thread_id.to_string(),
+                    IndexedThread {
+                        thread_id: thread_id.to_string(),
+                        title,
+                        updated_at: Some(now),
+                    },
+                );
+            }
+            "turn/started" | "turn/completed" => {
+                let Some(thread_id) = params_thread_id(params) else {
+                    return;
+                };
+                indexed
+                    .threads
+                    .entry(thread_id.clone())
+                    .or_insert_with(|| IndexedThread {
+                        title: default_title(&thread_id),
+                        thread_id,
+                        updated_at: None,
+                    })
+                    .updated_at = Some(now);
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn threads(&self, workspace_id: &str) -> Vec<IndexedThread> {
+        self.workspaces
+            .get(workspace_id)
+            .map(|indexed| indexed.threads.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// When the least recently listed of `workspace_ids` was listed.
+    pub(crate) fn oldest_refresh(&self, workspace_ids: &[String]) -> Option<i64> {
+        workspace_ids
+            .iter()
+            .filter_map(|id| self.workspaces.get(id))
+            .map(|indexed| indexed.refreshed_at)
+            .min()
+    }
+}
+
+fn default_title(thread_id: &str) -> String {
+    format!("Agent {}", thread_id.chars().take(4).collect::<String>())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ThreadSort {
+    #[default]
+    UpdatedDesc,
+    UpdatedAsc,
+    TitleAsc,
+}
+
+/// Predicates of a windowed query; empty lists and unset fields match
+/// everything.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadQueryFilters {
+    #[serde(default)]
+    pub(crate) workspace_ids: Vec<String>,
+    #[serde(default)]
+    pub(crate) statuses: Vec<ThreadStatus>,
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+    /// Case-insensitive text the title must contain.
+    #[serde(default)]
+    pub(crate) query: Option<String>,
+    #[serde(default)]
+    pub(crate) updated_after: Option<i64>,
+    #[serde(default)]
+    pub(crate) updated_before: Option<i64>,
+}
+
+impl ThreadQueryFilters {
+    pub(crate) fn matches(&self, thread: &ThreadRecord, now: i64) -> bool {
+        let filter = thread_tags::SavedThreadFilter {
+            id: String::new(),
+            name: String::new(),
+            tags: thread_tags::normalize_tags(&self.tags),
+            workspace_ids: self.workspace_ids.clone(),
+            statuses: self.statuses.clone(),
+            updated_within_days: None,
+            updated_after: self.updated_after,
+            updated_before: self.updated_before,
+        };
+        let query = self.query.as_deref().map(str::trim).unwrap_or_default();
+        thread_tags::matches(&filter, thread, now)
+            && (query.is_empty() || thread.title.to_lowercase().contains(&query.to_lowercase()))
+    }
+}
+
+/// One window of a sorted thread query.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadWindow {
+    pub(crate) offset: usize,
+    /// Threads matching the filters, in and out of this window.
+    pub(crate) total: usize,
+    /// Threads in the index before filtering.
+    pub(crate) indexed: usize,
+    /// When the least recently listed workspace was listed.
+    pub(crate) refreshed_at: Option<i64>,
+    pub(crate) threads: Vec<ThreadRecord>,
+}
+
+pub(crate) fn sort_threads(threads: &mut [ThreadRecord], sort: ThreadSort) {
+    let by_id = |a: &ThreadRecord, b: &ThreadRecord| {
+        a.workspace_id
+            .cmp(&b.workspace_id)
+            .then_with(|| a.thread_id.cmp(&b.thread_id))
+    };
+    match sort {
+        // Threads without a time sort last either way.
+        ThreadSort::UpdatedDesc => {
+            threads.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then_with(|| by_id(a, b)))
+        }
+        ThreadSort::UpdatedAsc => threads.sort_by(|a, b| {
+            a.updated_at
+                .is_none()
+                .cmp(&b.updated_at.is_none())
+                .then_with(|| a.updated_at.cmp(&b.updated_at))
+                .then_with(|| by_id(a, b))
+        }),
+        ThreadSort::TitleAsc => threads.sort_by(|a, b| {
+            a.title
+                .to_lowercase()
+                .cmp(&b.title.to_lowercase())
+                .then_with(|| by_id(a, b))
+        }),
+    }
+}
+
+/// Filters, sorts and slices `threads`. The sort is total, so consecutive
+/// windows neither repeat nor skip threads while the index is unchanged.
+pub(crate) fn window(
+    threads: Vec<ThreadRecord>,
+    filters: &ThreadQueryFilters,
+    sort: ThreadSort,
+    offset: usize,
+    limit: usize,
+    now: i64,
+) -> (usize, Vec<ThreadRecord>) {
+    let mut matching: Vec<ThreadRecord> = threads
+        .into_iter()
+        .filter(|thread| filters.matches(thread, now))
+        .collect();
+    sort_threads(&mut matching, sort);
+    let total = matching.len();
+    let threads = matching
+        .into_iter()
+        .skip(offset)
+        .take(limit.clamp(1, MAX_WINDOW_LIMIT))
+        .collect();
+    (total, threads)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(thread_id: &str, title: &str, updated_at: Option<i64>) -> ThreadRecord {
+        ThreadRecord {
+            workspace_id: "ws".to_string(),
+            thread_id: thread_id.to_string(),
+            title: title.to_string(),
+            updated_at,
+            status: ThreadStatus::Idle,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn windows_sorted_and_filtered_threads_with_totals() {
+        let threads: Vec<ThreadRecord> = (0..10)
+            .map(|index| {
+                record(
+                    &format!("t{index}"),
+                    &format!("Fix bug {index}"),
+                    Some(index),
+                )
+            })
+            .chain([record("t-none", "Refactor", None)])
+            .collect();
+        let (total, page) = window(
+            threads.clone(),
+            &ThreadQueryFilters::default(),
+            ThreadSort::UpdatedDesc,
+            2,
+            3,
+            0,
+        );
+        assert_eq!(total, 11);
+        let ids: Vec<_> = page
+            .iter()
+            .map(|thread| thread.thread_id.as_str())
+            .collect();
+        assert_eq!(ids, ["t7", "t6", "t5"]);
+
+        let (_, page) = window(
+            threads.clone(),
+            &ThreadQueryFilters::default(),
+            ThreadSort::UpdatedAsc,
+            9,
+            5,
+            0,
+        );
+        let ids: Vec<_> = page
+            .iter()
+            .map(|thread| thread.thread_id.as_str())
+            .collect();
+        assert_eq!(ids, ["t9", "t-none"]);
+
+        let filters = ThreadQueryFilters {
+            query: Some("BUG".to_string()),
+            updated_after: Some(5),
+            ..ThreadQueryFilters::default()
+        };
+        let (total, page) = window(threads, &filters, ThreadSort::TitleAsc, 0, 100, 0);
+        assert_eq!(total, 5);
+        assert_eq!(page[0].thread_id, "t5");
+    }
+
+    #[test]
+    fn index_follows_thread_and_turn_events() {
+        let mut index = ThreadIndex::default();
+        assert!(index.needs_refresh("ws", 0));
+        index.observe(
+            "ws",
+            &json!({ "method": "thread/started", "params": { "thread": { "id": "t1" } } }),
+            1,
+        );
+        assert!(index.threads("ws").is_empty());
+
+        index.replace(
+            "ws",
+            vec![IndexedThread {
+                thread_id: "t1".to_string(),
+                title: "Old".to_string(),
+                updated_at: Some(10),
+            }],
+            100,
+        );
+        assert!(!index.needs_refresh("ws", 100 + REFRESH_AFTER_MS - 1));
+        index.observe(
+            "ws",
+            &json!({ "method": "thread/started", "params": { "thread": { "id": "t2", "preview": "New" } } }),
+            200,
+        );
+        index.observe(
+            "ws",
+            &json!({ "method": "turn/completed", "params": { "threadId": "t1", "turn": {} } }),
+            300,
+        );
+        let mut threads = index.threads("ws");
+        threads.sort_by(|a, b| a.thread_id.cmp(&b.thread_id));
+        assert_eq!(threads[0].updated_at, Some(300));
+        assert_eq!(threads[1].title, "New");
+        assert_eq!(index.oldest_refresh(&["ws".to_string()]), Some(100));
+    }
+}
@@ -0,0 +1,185 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::backend::command_output::safe_file_name;
+use crate::backend::secure_storage::DataCipher;
+
+/// Item ids kept per thread journal, oldest dropped first.
+const MAX_JOURNAL_ITEMS: usize = 20_000;
+
+/// An item of the server-side thread history the app never received.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackfillItem {
+    pub(crate) turn_id: Option<String>,
+    pub(crate) item: Value,
+}
+
+/// Params of the `thread/backfill` event.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadBackfill {
+    pub(crate) thread_id: String,
+    /// In history order.
+    pub(crate) items: Vec<BackfillItem>,
+}
+
+/// Journals share the `journals` data class directory, so they follow its
+/// retention setting.
+pub(crate) fn thread_journals_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("journals")
+}
+
+fn journal_file(data_dir: &Path, workspace_id: &str, thread_id: &str) -> PathBuf {
+    thread_journals_dir(data_dir).join(format!(
+        "{}.{}.json",
+        safe_file_name(workspace_id),
+        safe_file_name(thread_id)
+    ))
+}
+
+/// Ids of the items the app has seen in a thread, oldest first; `None` when
+/// the app has no journal for it yet.
+pub(crate) fn read_journal(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+) -> Result<Option<Vec<String>>, String> {
+    let path = journal_file(data_dir, workspace_id, thread_id);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = cipher.read_to_string(&path)?;
+    serde_json::from_str(&data)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+pub(crate) fn record_items(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+    item_ids: &[String],
+) -> Result<(), String> {
+    let path = journal_file(data_dir, workspace_id, thread_id);
+    let existing = read_journal(data_dir, cipher, workspace_id, thread_id)?;
+    let exists = existing.is_some();
+    let mut journal = existing.unwrap_or_default();
+    let mut known: HashSet<String> = journal.iter().cloned().collect();
+    let before = journal.len();
+    journal.extend(
+        item_ids
+            .iter()
+            .filter(|item_id| known.insert(item_id.to_string()))
+            .cloned(),
+    );
+    if exists && journal.len() == before {
+        return Ok(());
+    }
+    if journal.len() > MAX_JOURNAL_ITEMS {
+        let excess = journal.len() - MAX_JOURNAL_ITEMS;
+        journal.drain(..excess);
+    }
+    let data = serde_json::to_string(&journal).map_err(|e| e.to_string())?;
+    cipher.write(&path, &data)
+}
+
+/// Items of a `thread/resume` response (or its `result`), in history order.
+pub(crate) fn history_items(response: &Value) -> Vec<BackfillItem> {
+    let result = response.get("result").unwrap_or(response);
+    let turns = result
+        .get("thread")
+        .and_then(|thread| thread.get("turns"))
+        .and_then(|turns| turns.as_array());
+    turns
+        .into_iter()
+        .flatten()
+        .flat_map(|turn| {
+            let turn_id = turn
+                .get("id")
+                .and_then(|value| value.as_str())
+                .map(str::to_string);
+            let items = turn.get("items").and_then(|items| items.as_array());
+            items.into_iter().flatten().map(move |item| BackfillItem {
+                turn_id: turn_id.clone(),
+                item: item.clone(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn item_id(item: &Value) -> Option<&str> {
+    item.get("id").and_then(|value| value.as_str())
+}
+
+/// History items whose id is not in the journal. Items without an id cannot
+/// be matched and are never backfilled.
+pub(crate) fn missing_items(history: &[BackfillItem], seen: &[String]) -> Vec<BackfillItem> {
+    let seen: HashSet<&str> = seen.iter().map(String::as_str).collect();
+    history
+        .iter()
+        .filter(|entry| item_id(&entry.item).is_some_and(|id| !seen.contains(id)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn finds_items_missing_from_the_journal() {
+        let response = json!({
+            "result": { "thread": { "id": "t-1", "turns": [
+                { "id": "turn-1", "items": [
+                    { "id": "i-1", "type": "userMessage" },
+                    { "id": "i-2", "type": "agentMessage" }
+                ] },
+                { "id": "turn-2", "items": [
+                    { "id": "i-3", "type": "userMessage" },
+                    { "type": "reasoning" }
+                ] }
+            ] } }
+        });
+        let history = history_items(&response);
+        assert_eq!(history.len(), 4);
+
+        let seen = vec!["i-1".to_string(), "i-2".to_string()];
+        let missing = missing_items(&history, &seen);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].turn_id.as_deref(), Some("turn-2"));
+        assert_eq!(item_id(&missing[0].item), Some("i-3"));
+    }
+
+    #[test]
+    fn records_item_ids_once() {
+        let data_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        assert_eq!(
+            read_journal(&data_dir, &cipher, "ws", "t-1").expect("read"),
+            None
+        );
+
+        let ids = |values: &[&str]| values.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        record_items(
+            &data_dir,
+            &cipher,
+            "ws",
+            "t-1",
+            &ids(&["i-1", "i-2", "i-1"]),
+        )
+        .expect("record");
+        record_items(&data_dir, &cipher, "ws", "t-1", &ids(&["i-2", "i-3"])).expect("record");
+        assert_eq!(
+            read_journal(&data_dir, &cipher, "ws", "t-1").expect("read"),
+            Some(ids(&["i-1", "i-2", "i-3"]))
+        );
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ThreadLinkKind {
+    /// The thread waits for the target thread's work.
+    BlockedOn,
+    /// The thread continues work started in the target thread.
+    FollowUpOf,
+}
+
+/// A directed link from a thread to another, possibly in another workspace.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadLink {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) kind: ThreadLinkKind,
+    pub(crate) target_workspace_id: String,
+    pub(crate) target_thread_id: String,
+    pub(crate) created_at: i64,
+    /// When a turn of the blocking thread last completed after the link was
+    /// made; `None` while the thread is still blocked.
+    #[serde(default)]
+    pub(crate) unblocked_at: Option<i64>,
+}
+
+impl ThreadLink {
+    fn same_edge(&self, other: &ThreadLink) -> bool {
+        self.kind == other.kind
+            && self.workspace_id == other.workspace_id
+            && self.thread_id == other.thread_id
+            && self.target_workspace_id == other.target_workspace_id
+            && self.target_thread_id == other.target_thread_id
+    }
+
+    pub(crate) fn touches(&self, workspace_id: &str, thread_id: &str) -> bool {
+        (self.workspace_id == workspace_id && self.thread_id == thread_id)
+            || (self.target_workspace_id == workspace_id && self.target_thread_id == thread_id)
+    }
+}
+
+pub(crate) fn thread_links_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("thread_links.json")
+}
+
+pub(crate) fn read_thread_links(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<ThreadLink>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_thread_links(
+    path: &Path,
+    cipher: &DataCipher,
+    links: &[ThreadLink],
+) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(links).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+/// Whether `from` already waits, directly or transitively, on `to`.
+fn blocks_transitively(links: &[ThreadLink], from: (&str, &str), to: (&str, &str)) -> bool {
+    let mut pending = vec![(from.0.to_string(), from.1.to_string())];
+    let mut seen: Vec<(String, String)> = Vec::new();
+    while let Some(node) = pending.pop() {
+        if node.0 == to.0 && node.1 == to.1 {
+            return true;
+        }
+        if seen.contains(&node) {
+            continue;
+        }
+        for link in links.iter().filter(|link| {
+            link.kind == ThreadLinkKind::BlockedOn
+                && link.workspace_id == node.0
+                && link.thread_id == node.1
+        }) {
+            pending.push((
+                link.target_workspace_id.clone(),
+                link.target_thread_id.clone(),
+            ));
+        }
+        seen.push(node);
+    }
+    false
+}
+
+/// Adds a link, refusing self-links and `blockedOn` cycles. Linking again
+/// marks the thread as blocked anew.
+pub(crate) fn add_link(links: &mut Vec<ThreadLink>, link: ThreadLink) -> Result<(), String> {
+    if link.workspace_id == link.target_workspace_id && link.thread_id == link.target_thread_id {
+        return Err("A thread cannot be linked to itself.".to_string());
+    }
+    if link.kind == ThreadLinkKind::BlockedOn
+        && blocks_transitively(
+            links,
+            (&link.target_workspace_id, &link.target_thread_id),
+            (&link.workspace_id, &link.thread_id),
+        )
+    {
+        return Err(format!(
+            "Thread {} is already blocked on thread {}.",
+            link.target_thread_id, link.thread_id
+        ));
+    }
+    links.retain(|existing| !existing.same_edge(&link));
+    links.push(link);
+    Ok(())
+}
+
+/// Removes a link; returns whether it existed.
+pub(crate) fn remove_link(links: &mut Vec<ThreadLink>, link: &ThreadLink) -> bool {
+    let before = links.len();
+    links.retain(|existing| !existing.same_edge(link));
+    links.len() != before
+}
+
+/// Marks the links blocked on a thread whose turn just completed as
+/// unblocked and returns them.
+pub(crate) fn unblock(
+    links: &mut [ThreadLink],
+    workspace_id: &str,
+    thread_id: &str,
+    now: i64,
+) -> Vec<ThreadLink> {
+    links
+        .iter_mut()
+        .filter(|link| {
+            link.kind == ThreadLinkKind::BlockedOn
+                && link.unblocked_at.is_none()
+                && link.target_workspace_id == workspace_id
+                && link.target_thread_id == thread_id
+        })
+        .map(|link| {
+            link.unblocked_at = Some(now);
+            link.clone()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(thread_id: &str, kind: ThreadLinkKind, target_thread_id: &str) -> ThreadLink {
+        ThreadLink {
+            workspace_id: "ws".to_string(),
+            thread_id: thread_id.to_string(),
+            kind,
+            target_workspace_id: "ws".to_string(),
+            target_thread_id: target_thread_id.to_string(),
+            created_at: 0,
+            unblocked_at: None,
+        }
+    }
+
+    #[test]
+    fn refuses_self_links_and_blocking_cycles() {
+        let mut links = Vec::new();
+        add_link(&mut links, link("b", ThreadLinkKind::BlockedOn, "a")).unwrap();
+        add_link(&mut links, link("c", ThreadLinkKind::BlockedOn, "b")).unwrap();
+        assert!(add_link(&mut links, link("a", ThreadLinkKind::BlockedOn, "c")).is_err());
+        assert!(add_link(&mut links, link("a", ThreadLinkKind::FollowUpOf, "a")).is_err());
+        add_link(&mut links, link("a", ThreadLinkKind::FollowUpOf, "c")).unwrap();
+        add_link(&mut links, link("b", ThreadLinkKind::BlockedOn, "a")).unwrap();
+        assert_eq!(links.len(), 3);
+        assert!(remove_link(
+            &mut links,
+            &link("a", ThreadLinkKind::FollowUpOf, "c")
+        ));
+        assert!(!remove_link(
+            &mut links,
+            &link("a", ThreadLinkKind::FollowUpOf, "c")
+        ));
+    }
+
+    #[test]
+    fn unblocks_dependents_once() {
+        let mut links = vec![
+            link("b", ThreadLinkKind::BlockedOn, "a"),
+            link("c", ThreadLinkKind::FollowUpOf, "a"),
+        ];
+        let unblocked = unblock(&mut links, "ws", "a", 5);
+        assert_eq!(unblocked.len(), 1);
+        assert_eq!(unblocked[0].thread_id, "b");
+        assert_eq!(links[0].unblocked_at, Some(5));
+        assert!(unblock(&mut links, "ws", "a", 6).is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+
+/// Model, effort and access mode last used on a thread. `send_user_message`
+/// falls back to these when a message leaves them out.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadTurnSettings {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) effort: Option<String>,
+    #[serde(default)]
+    pub(crate) access_mode: Option<String>,
+    pub(crate) updated_at: i64,
+}
+
+impl ThreadTurnSettings {
+    /// Fills the fields a message left out from the remembered settings.
+    pub(crate) fn apply_defaults(
+        &self,
+        model: Option<String>,
+        effort: Option<String>,
+        access_mode: Option<String>,
+    ) -> (Option<String>, Option<String>, Option<String>) {
+        (
+            model.or_else(|| self.model.clone()),
+            effort.or_else(|| self.effort.clone()),
+            access_mode.or_else(|| self.access_mode.clone()),
+        )
+    }
+}
+
+pub(crate) fn thread_settings_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("thread_settings.json")
+}
+
+pub(crate) fn read_thread_settings(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<ThreadTurnSettings>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn find_thread_settings(
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+) -> Result<Option<ThreadTurnSettings>, String> {
+    Ok(read_thread_settings(path, cipher)?
+        .into_iter()
+        .find(|entry| entry.workspace_id == workspace_id && entry.thread_id == thread_id))
+}
+
+/// Merges the settings of a sent message into the thread's entry; fields
+/// the message left out keep their remembered value.
+pub(crate) fn remember_thread_settings(
+    path: &Path,
+    cipher: &DataCipher,
+    update: ThreadTurnSettings,
+) -> Result<ThreadTurnSettings, String> {
+    let mut entries = read_thread_settings(path, cipher)?;
+    let position = entries.iter().position(|entry| {
+        entry.workspace_id == update.workspace_id && entry.thread_id == update.thread_id
+    });
+    let merged = match position {
+        Some(index) => {
+            let existing = entries.remove(index);
+            let (model, effort, access_mode) =
+                existing.apply_defaults(update.model, update.effort, update.access_mode);
+            ThreadTurnSettings {
+                model,
+                effort,
+                access_mode,
+                ..update
+            }
+        }
+        None => update,
+    };
+    entries.push(merged.clone());
+    let data = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)?;
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn merges_and_defaults_per_thread() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = thread_settings_path(&dir);
+        let cipher = DataCipher::plaintext();
+        let update =
+            |thread_id: &str, model: Option<&str>, effort: Option<&str>| ThreadTurnSettings {
+                workspace_id: "ws".to_string(),
+                thread_id: thread_id.to_string(),
+                model: model.map(str::to_string),
+                effort: effort.map(str::to_string),
+                access_mode: None,
+                updated_at: 1,
+            };
+
+        remember_thread_settings(&path, &cipher, update("t1", Some("gpt-5"), Some("high")))
+            .unwrap();
+        let merged =
+            remember_thread_settings(&path, &cipher, update("t1", Some("gpt-5-mini"), None))
+                .unwrap();
+        assert_eq!(merged.model.as_deref(), Some("gpt-5-mini"));
+        assert_eq!(merged.effort.as_deref(), Some("high"));
+        remember_thread_settings(&path, &cipher, update("t2", None, Some("low"))).unwrap();
+
+        let stored = find_thread_settings(&path, &cipher, "ws", "t1")
+            .unwrap()
+            .expect("t1");
+        let (model, effort, access_mode) =
+            stored.apply_defaults(None, Some("medium".to_string()), None);
+        assert_eq!(model.as_deref(), Some("gpt-5-mini"));
+        assert_eq!(effort.as_deref(), Some("medium"));
+        assert!(access_mode.is_none());
+        assert_eq!(read_thread_settings(&path, &cipher).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::backend::clock::{Interval, Timestamp};
+use crate::backend::secure_storage::DataCipher;
+use crate::backend::turn_summary::{params_thread_id, params_turn_id};
+
+/// Agent time spent on a thread, summed over its completed turns, and the
+/// optional limit that raises an alert when exceeded.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadTimer {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    #[serde(default)]
+    pub(crate) active_ms: u64,
+    #[serde(default)]
+    pub(crate) turns: u32,
+    /// Turns during which the wall clock jumped; their time is measured
+    /// monotonically, so sleeps are not counted.
+    #[serde(default)]
+    pub(crate) clock_jump_turns: u32,
+    #[serde(default)]
+    pub(crate) sla_minutes: Option<u32>,
+    /// When `active_ms` first exceeded the SLA; cleared when the SLA changes.
+    #[serde(default)]
+    pub(crate) breached_at: Option<i64>,
+    pub(crate) updated_at: i64,
+}
+
+impl ThreadTimer {
+    fn new(workspace_id: &str, thread_id: &str, now: i64) -> Self {
+        Self {
+            workspace_id: workspace_id.to_string(),
+            thread_id: thread_id.to_string(),
+            active_ms: 0,
+            turns: 0,
+            clock_jump_turns: 0,
+            sla_minutes: None,
+            breached_at: None,
+            updated_at: now,
+        }
+    }
+
+    fn is_over_sla(&self) -> bool {
+        self.sla_minutes
+            .is_some_and(|minutes| self.active_ms > u64::from(minutes) * 60_000)
+    }
+}
+
+/// A completed turn and how long it ran.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TurnTiming {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) duration_ms: u64,
+    pub(crate) clock_jumped: bool,
+}
+
+/// Payload of `thread-sla-breached`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SlaBreach {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) sla_minutes: u32,
+    pub(crate) active_ms: u64,
+}
+
+/// Start times of running turns, keyed by workspace and turn id.
+#[derive(Debug, Default)]
+pub(crate) struct ThreadStopwatch {
+    started: HashMap<(String, String), Timestamp>,
+}
+
+impl ThreadStopwatch {
+    /// Returns the timing of the turn a `turn/completed` notification
+    /// finished, when its start was seen.
+    pub(crate) fn observe(
+        &mut self,
+        workspace_id: &str,
+        message: &Value,
+        now: Timestamp,
+    ) -> Option<TurnTiming> {
+        let method = message.get("method")?.as_str()?;
+        let params = message.get("params")?;
+        let turn_id = params_turn_id(params)?;
+        let key = (workspace_id.to_string(), turn_id);
+        match method {
+            "turn/started" => {
+                self.started.insert(key, now);
+                None
+            }
+            "turn/completed" => {
+                let interval = Interval::between(self.started.remove(&key)?, now);
+                Some(TurnTiming {
+                    workspace_id: workspace_id.to_string(),
+                    thread_id: params_thread_id(params)?,
+                    duration_ms: interval.elapsed_ms,
+                    clock_jumped: interval.clock_jumped(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn thread_timers_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("thread_timers.json")
+}
+
+pub(crate) fn read_thread_timers(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<ThreadTimer>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+fn write_thread_timers(
+    path: &Path,
+    cipher: &DataCipher,
+    timers: &[ThreadTimer],
+) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(timers).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+fn timer_mut<'a>(
+    timers: &'a mut Vec<ThreadTimer>,
+    workspace_id: &str,
+    thread_id: &str,
+    now: i64,
+) -> &'a mut ThreadTimer {
+    let index = match timers
+        .iter()
+        .position(|timer| timer.workspace_id == workspace_id && timer.thread_id == thread_id)
+    {
+        Some(index) => index,
+        None => {
+            timers.push(ThreadTimer::new(workspace_id, thread_id, now));
+            timers.len() - 1
+        }
+    };
+    &mut timers[index]
+}
+
+/// Adds a turn's duration to its thread. Returns the breach the first time
+/// the thread's agent time goes over its SLA.
+pub(crate) fn record_turn(
+    path: &Path,
+    cipher: &DataCipher,
+    timing: &TurnTiming,
+    now: i64,
+) -> Result<Option<SlaBreach>, String> {
+    let mut timers = read_thread_timers(path, cipher)?;
+    let timer = timer_mut(&mut timers, &timing.workspace_id, &timing.thread_id, now);
+    timer.active_ms += timing.duration_ms;
+    timer.turns += 1;
+    if timing.clock_jumped {
+        timer.clock_jump_turns += 1;
+    }
+    timer.updated_at = now;
+    let breach = match timer.sla_minutes {
+        Some(sla_minutes) if timer.breached_at.is_none() && timer.is_over_sla() => {
+            timer.breached_at = Some(now);
+            Some(SlaBreach {
+                workspace_id: timer.workspace_id.clone(),
+                thread_id: timer.thread_id.clone(),
+                sla_minutes,
+                active_ms: timer.active_ms,
+            })
+        }
+        _ => None,
+    };
+    write_thread_timers(path, cipher, &timers)?;
+    Ok(breach)
+}
+
+/// Sets or clears a thread's SLA. A thread already over the new SLA is
+/// marked breached without alerting again.
+pub(crate) fn set_sla(
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+    sla_minutes: Option<u32>,
+    now: i64,
+) -> Result<ThreadTimer, String> {
+    let mut timers = read_thread_timers(path, cipher)?;
+    let timer = timer_mut(&mut timers, workspace_id, thread_id, now);
+    timer.sla_minutes = sla_minutes.filter(|minutes| *minutes > 0);
+    timer.breached_at = timer.is_over_sla().then_some(now);
+    timer.updated_at = now;
+    let timer = timer.clone();
+    write_thread_timers(path, cipher, &timers)?;
+    Ok(timer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn turn(method: &str, turn_id: &str) -> Value {
+        json!({ "method": method, "params": { "threadId": "t-1", "turn": { "id": turn_id } } })
+    }
+
+    fn at(utc_ms: i64, mono_ms: u64) -> Timestamp {
+        Timestamp {
+            utc_ms,
+            mono_ms,
+            offset_minutes: 0,
+        }
+    }
+
+    #[test]
+    fn times_turns_from_start_to_completion() {
+        let mut stopwatch = ThreadStopwatch::default();
+        assert_eq!(
+            stopwatch.observe("ws", &turn("turn/started", "turn-1"), at(1_000, 1_000)),
+            None
+        );
+        assert_eq!(
+            stopwatch.observe("ws", &turn("turn/completed", "turn-2"), at(2_000, 2_000)),
+            None
+        );
+        assert_eq!(
+            stopwatch.observe("ws", &turn("turn/completed", "turn-1"), at(61_000, 61_000)),
+            Some(TurnTiming {
+                workspace_id: "ws".to_string(),
+                thread_id: "t-1".to_string(),
+                duration_ms: 60_000,
+                clock_jumped: false,
+            })
+        );
+
+        // The laptop slept for an hour mid-turn.
+        stopwatch.observe("ws", &turn("turn/started", "turn-3"), at(100_000, 100_000));
+        let timing = stopwatch
+            .observe(
+                "ws",
+                &turn("turn/completed", "turn-3"),
+                at(3_730_000, 130_000),
+            )
+            .unwrap();
+        assert_eq!((timing.duration_ms, timing.clock_jumped), (30_000, true));
+    }
+
+    #[test]
+    fn alerts_once_when_the_sla_is_exceeded() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = thread_timers_path(&dir);
+        let cipher = DataCipher::plaintext();
+        let timing = TurnTiming {
+            workspace_id: "ws".to_string(),
+            thread_id: "t-1".to_string(),
+            duration_ms: 40 * 60_000,
+            clock_jumped: false,
+        };
+
+        set_sla(&path, &cipher, "ws", "t-1", Some(60), 1).unwrap();
+        assert_eq!(record_turn(&path, &cipher, &timing, 2).unwrap(), None);
+        let breach = record_turn(&path, &cipher, &timing, 3).unwrap().unwrap();
+        assert_eq!(breach.sla_minutes, 60);
+        assert_eq!(breach.active_ms, 80 * 60_000);
+        assert_eq!(record_turn(&path, &cipher, &timing, 4).unwrap(), None);
+
+        let raised = set_sla(&path, &cipher, "ws", "t-1", Some(180), 5).unwrap();
+        assert_eq!((raised.turns, raised.breached_at), (3, None));
+        let lowered = set_sla(&path, &cipher, "ws", "t-1", Some(30), 6).unwrap();
+        assert_eq!(lowered.breached_at, Some(6));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadTags {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) tags: Vec<String>,
+    pub(crate) updated_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ThreadStatus {
+    /// A turn is in progress.
+    Running,
+    Idle,
+    Archived,
+}
+
+/// Saved smart filter over threads. Empty lists match everything; all
+/// predicates that are set must hold.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SavedThreadFilter {
+    #[serde(default)]
+    pub(crate) id: String,
+    pub(crate) name: String,
+    /// Threads must carry every one of these tags.
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+    #[serde(default)]
+    pub(crate) workspace_ids: Vec<String>,
+    #[serde(default)]
+    pub(crate) statuses: Vec<ThreadStatus>,
+    /// Only threads updated in the last N days.
+    #[serde(default)]
+    pub(crate) updated_within_days: Option<u32>,
+    #[serde(default)]
+    pub(crate) updated_after: Option<i64>,
+    #[serde(default)]
+    pub(crate) updated_before: Option<i64>,
+}
+
+/// A thread as seen by `query_threads`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadRecord {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) title: String,
+    /// Epoch milliseconds.
+    pub(crate) updated_at: Option<i64>,
+    pub(crate) status: ThreadStatus,
+    pub(crate) tags: Vec<String>,
+}
+
+pub(crate) fn thread_tags_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("thread_tags.json")
+}
+
+pub(crate) fn thread_filters_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("thread_filters.json")
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<T>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+fn write_json<T: Serialize>(path: &Path, cipher: &DataCipher, entries: &[T]) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+/// Tags are matched case-insensitively and stored trimmed and lowercased.
+pub(crate) fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = tags
+        .iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
+
+/// Thread timestamps from the app-server may be in seconds.
+pub(crate) fn timestamp_ms(value: i64) -> i64 {
+    if value < 1_000_000_000_000 {
+        value * 1000
+    } else {
+        value
+    }
+}
+
+pub(crate) fn read_thread_tags(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<ThreadTags>, String> {
+    read_json(path, cipher)
+}
+
+/// Replaces the tags of a thread; an empty list removes its entry.
+pub(crate) fn set_thread_tags(
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+    tags: &[String],
+    now: i64,
+) -> Result<ThreadTags, String> {
+    let mut entries = read_thread_tags(path, cipher)?;
+    entries.retain(|entry| !(entry.workspace_id == workspace_id && entry.thread_id == thread_id));
+    let updated = ThreadTags {
+        workspace_id: workspace_id.to_string(),
+        thread_id: thread_id.to_string(),
+        tags: normalize_tags(tags),
+        updated_at: now,
+    };
+    if !updated.tags.is_empty() {
+        entries.push(updated.clone());
+    }
+    write_json(path, cipher, &entries)?;
+    Ok(updated)
+}
+
+pub(crate) fn read_filters(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<SavedThreadFilter>, String> {
+    read_json(path, cipher)
+}
+
+/// Adds a filter or replaces the one with the same id.
+pub(crate) fn save_filter(
+    path: &Path,
+    cipher: &DataCipher,
+    mut filter: SavedThreadFilter,
+) -> Result<SavedThreadFilter, String> {
+    if filter.name.trim().is_empty() {
+        return Err("Filter name is required.".to_string());
+    }
+    filter.tags = normalize_tags(&filter.tags);
+    let mut filters = read_filters(path, cipher)?;
+    match filters.iter_mut().find(|existing| existing.id == filter.id) {
+        Some(existing) => *existing = filter.clone(),
+        None => filters.push(filter.clone()),
+    }
+    write_json(path, cipher, &filters)?;
+    Ok(filter)
+}
+
+pub(crate) fn delete_filter(
+    path: &Path,
+    cipher: &DataCipher,
+    filter_id: &str,
+) -> Result<(), String> {
+    let mut filters = read_filters(path, cipher)?;
+    let before = filters.len();
+    filters.retain(|filter| filter.id != filter_id);
+    if filters.len() == before {
+        return Err("Thread filter not found.".to_string());
+    }
+    write_json(path, cipher, &filters)
+}
+
+pub(crate) fn matches(filter: &SavedThreadFilter, thread: &ThreadRecord, now: i64) -> bool {
+    if !filter.tags.iter().all(|tag| thread.tags.contains(tag)) {
+        return false;
+    }
+    if !filter.workspace_ids.is_empty() && !filter.workspace_ids.contains(&thread.workspace_id) {
+        return false;
+    }
+    if !filter.statuses.is_empty() && !filter.statuses.contains(&thread.status) {
+        return false;
+    }
+    let after = filter
+        .updated_within_days
+        .map(|days| now - i64::from(days) * DAY_MS)
+        .into_iter()
+        .chain(filter.updated_after)
+        .max();
+    if after.is_none() && filter.updated_before.is_none() {
+        return true;
+    }
+    let Some(updated_at) = thread.updated_at else {
+        return false;
+    };
+    after.is_none_or(|after| updated_at >= after)
+        && filter
+            .updated_before
+            .is_none_or(|before| updated_at < before)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn filter() -> SavedThreadFilter {
+        SavedThreadFilter {
+            id: "f1".to_string(),
+            name: "Open bugs this week".to_string(),
+            tags: vec!["bug".to_string()],
+            workspace_ids: Vec::new(),
+            statuses: vec![ThreadStatus::Running],
+            updated_within_days: Some(7),
+            updated_after: None,
+            updated_before: None,
+        }
+    }
+
+    fn thread(tags: &[&str], status: ThreadStatus, updated_at: Option<i64>) -> ThreadRecord {
+        ThreadRecord {
+            workspace_id: "ws".to_string(),
+            thread_id: "t1".to_string(),
+            title: "Fix login".to_string(),
+            updated_at,
+            status,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn evaluates_tag_status_and_date_predicates() {
+        let now = 100 * DAY_MS;
+        let recent = Some(now - DAY_MS);
+        assert!(matches(
+            &filter(),
+            &thread(&["bug", "auth"], ThreadStatus::Running, recent),
+            now
+        ));
+        assert!(!matches(
+            &filter(),
+            &thread(&["auth"], ThreadStatus::Running, recent),
+            now
+        ));
+        assert!(!matches(
+            &filter(),
+            &thread(&["bug"], ThreadStatus::Idle, recent),
+            now
+        ));
+        assert!(!matches(
+            &filter(),
+            &thread(&["bug"], ThreadStatus::Running, Some(now - 8 * DAY_MS)),
+            now
+        ));
+        assert!(!matches(
+            &filter(),
+            &thread(&["bug"], ThreadStatus::Running, None),
+            now
+        ));
+
+        let mut scoped = filter();
+        scoped.workspace_ids = vec!["other".to_string()];
+        assert!(!matches(
+            &scoped,
+            &thread(&["bug"], ThreadStatus::Running, recent),
+            now
+        ));
+    }
+
+    #[test]
+    fn stores_normalized_tags_and_filters() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        let tags_path = thread_tags_path(&dir);
+        let tags = vec![" Bug ".to_string(), "bug".to_string(), "UI".to_string()];
+        let stored = set_thread_tags(&tags_path, &cipher, "ws", "t1", &tags, 1).unwrap();
+        assert_eq!(stored.tags, vec!["bug", "ui"]);
+        set_thread_tags(&tags_path, &cipher, "ws", "t1", &[], 2).unwrap();
+        assert!(read_thread_tags(&tags_path, &cipher).unwrap().is_empty());
+
+        let filters_path = thread_filters_path(&dir);
+        save_filter(&filters_path, &cipher, filter()).unwrap();
+        let mut renamed = filter();
+        renamed.name = "Bugs".to_string();
+        save_filter(&filters_path, &cipher, renamed).unwrap();
+        let filters = read_filters(&filters_path, &cipher).unwrap();
+        assert_eq!(filters.len(), 1);
+        assert_eq!(filters[0].name, "Bugs");
+        delete_filter(&filters_path, &cipher, "f1").unwrap();
+        assert!(delete_filter(&filters_path, &cipher, "f1").is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::{Component, Path, PathBuf};
+
+use crate::backend::command_output::safe_file_name;
+
+/// A file the agent wrote to a thread's artifacts directory.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnArtifact {
+    /// Relative to the artifacts directory, `/`-separated.
+    pub(crate) path: String,
+    pub(crate) absolute_path: String,
+    pub(crate) size: u64,
+    pub(crate) modified_at: Option<i64>,
+}
+
+/// Artifacts live in the `artifacts` data class directory, one
+/// subdirectory per thread, so they follow its retention setting.
+pub(crate) fn turn_artifacts_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("artifacts")
+}
+
+pub(crate) fn thread_artifacts_dir(
+    data_dir: &Path,
+    workspace_id: &str,
+    thread_id: &str,
+) -> PathBuf {
+    turn_artifacts_dir(data_dir).join(format!(
+        "{}.{}",
+        safe_file_name(workspace_id),
+        safe_file_name(thread_id)
+    ))
+}
+
+/// Prepended to the user's input, followed by the directory, so the agent
+/// knows where generated files that do not belong in the repository go.
+const ARTIFACTS_NOTE: &str = "Save generated files that do not belong in the repository, such \
+as reports, images and scratch scripts, under";
+
+pub(crate) fn artifacts_note(dir: &Path) -> Value {
+    json!({ "type": "text", "text": format!("{ARTIFACTS_NOTE} {}.", dir.display()) })
+}
+
+fn collect(root: &Path, dir: &Path, artifacts: &mut Vec<TurnArtifact>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect(root, &path, artifacts);
+            continue;
+        }
+        if !metadata.is_file() {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        let modified_at = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+            .and_then(|since| i64::try_from(since.as_millis()).ok());
+        artifacts.push(TurnArtifact {
+            path: relative
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            absolute_path: path.display().to_string(),
+            size: metadata.len(),
+            modified_at,
+        });
+    }
+}
+
+/// Files under `dir`, newest first. Symlinks are skipped so an artifact
+/// cannot point outside the directory.
+pub(crate) fn list_artifacts(dir: &Path) -> Vec<TurnArtifact> {
+    let mut artifacts = Vec::new();
+    collect(dir, dir, &mut artifacts);
+    artifacts.sort_by(|a, b| {
+        b.modified_at
+            .cmp(&a.modified_at)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    artifacts
+}
+
+/// Resolves an artifact path from `list_artifacts` inside `dir`, refusing
+/// anything that would leave it.
+pub(crate) fn resolve_artifact(dir: &Path, relative: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(relative);
+    if relative.as_os_str().is_empty()
+        || !relative
+            .components()
+            .all(|part| matches!(part, Component::Normal(_)))
+    {
+        return Err("Invalid artifact path".to_string());
+    }
+    let path = dir.join(relative);
+    match std::fs::symlink_metadata(&path) {
+        Ok(metadata) if metadata.is_file() => Ok(path),
+        _ => Err(format!("Artifact not found: {}", relative.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn lists_and_resolves_artifacts_inside_the_thread_dir() {
+        let data_dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let dir = thread_artifacts_dir(&data_dir, "ws/1", "thread:1");
+        assert!(dir.ends_with("artifacts/ws_1.thread_1"));
+        std::fs::create_dir_all(dir.join("charts")).unwrap();
+        std::fs::write(dir.join("report.md"), "# Report").unwrap();
+        std::fs::write(dir.join("charts").join("load.png"), [0u8; 4]).unwrap();
+
+        let mut paths: Vec<String> = list_artifacts(&dir)
+            .into_iter()
+            .map(|artifact| artifact.path)
+            .collect();
+        paths.sort();
+        assert_eq!(paths, vec!["charts/load.png", "report.md"]);
+
+        assert!(resolve_artifact(&dir, "charts/load.png").is_ok());
+        assert!(resolve_artifact(&dir, "../other/report.md").is_err());
+        assert!(resolve_artifact(&dir, "/etc/passwd").is_err());
+        assert!(resolve_artifact(&dir, "missing.txt").is_err());
+
+        std::fs::remove_dir_all(&data_dir).unwrap();
+    }
+}
@@ -0,0 +1,392 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+use crate::backend::app_server::WorkspaceSession;
+
+/// Message diffs fall back to a plain remove/add pair beyond this many
+/// line pairs to keep the LCS table small.
+const MAX_DIFF_CELLS: usize = 250_000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnRef {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct FileChange {
+    pub(crate) kind: Option<String>,
+    pub(crate) diff: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct TurnSnapshot {
+    pub(crate) turn_id: Option<String>,
+    pub(crate) status: Option<String>,
+    pub(crate) final_message: Option<String>,
+    pub(crate) files: BTreeMap<String, FileChange>,
+    pub(crate) commands_run: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnSideStats {
+    pub(crate) status: Option<String>,
+    pub(crate) files_changed: usize,
+    pub(crate) additions: usize,
+    pub(crate) deletions: usize,
+    pub(crate) commands_run: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum FileComparisonStatus {
+    OnlyA,
+    OnlyB,
+    Identical,
+    Different,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FileComparison {
+    pub(crate) path: String,
+    pub(crate) status: FileComparisonStatus,
+    pub(crate) kind_a: Option<String>,
+    pub(crate) kind_b: Option<String>,
+    pub(crate) diff_a: Option<String>,
+    pub(crate) diff_b: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum LineChange {
+    Same,
+    OnlyA,
+    OnlyB,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MessageDiffLine {
+    pub(crate) change: LineChange,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnComparison {
+    pub(crate) turn_a: TurnRef,
+    pub(crate) turn_b: TurnRef,
+    pub(crate) stats_a: TurnSideStats,
+    pub(crate) stats_b: TurnSideStats,
+    pub(crate) files: Vec<FileComparison>,
+    pub(crate) final_message_a: Option<String>,
+    pub(crate) final_message_b: Option<String>,
+    pub(crate) message_diff: Vec<MessageDiffLine>,
+}
+
+fn change_kind(change: &Value) -> Option<String> {
+    let kind = change.get("kind")?;
+    kind.as_str()
+        .or_else(|| kind.get("type").and_then(|value| value.as_str()))
+        .map(|value| value.to_string())
+}
+
+fn thread_turns(response: &Value) -> Option<&Vec<Value>> {
+    let result = response.get("result").unwrap_or(response);
+    result.get("thread")?.get("turns")?.as_array()
+}
+
+/// Extracts the parts of a turn that matter for comparison from a
+/// `thread/resume` response (or its `result`).
+pub(crate) fn find_turn(response: &Value, turn_id: &str) -> Option<TurnSnapshot> {
+    let turn = thread_turns(response)?
+        .iter()
+        .find(|turn| turn.get("id").and_then(|value| value.as_str()) == Some(turn_id))?;
+    Some(snapshot_turn(turn))
+}
+
+/// Like `find_turn`, for the most recent turn of the thread.
+pub(crate) fn find_last_turn(response: &Value) -> Option<TurnSnapshot> {
+    thread_turns(response)?.last().map(snapshot_turn)
+}
+
+fn snapshot_turn(turn: &Value) -> TurnSnapshot {
+    let mut snapshot = TurnSnapshot {
+        turn_id: turn
+            .get("id")
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string()),
+        status: turn
+            .get("status")
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string()),
+        ..TurnSnapshot::default()
+    };
+    let items = turn.get("items").and_then(|value| value.as_array());
+    for item in items.into_iter().flatten() {
+        match item.get("type").and_then(|value| value.as_str()) {
+            Some("agentMessage") => {
+                if let Some(text) = item.get("text").and_then(|value| value.as_str()) {
+                    if !text.trim().is_empty() {
+                        snapshot.final_message = Some(text.trim().to_string());
+                    }
+                }
+            }
+            Some("commandExecution") => snapshot.commands_run += 1,
+            Some("fileChange") => {
+                let changes = item.get("changes").and_then(|value| value.as_array());
+                for change in changes.into_iter().flatten() {
+                    let Some(path) = change.get("path").and_then(|value| value.as_str()) else {
+                        continue;
+                    };
+                    let entry = snapshot.files.entry(path.to_string()).or_default();
+                    if let Some(kind) = change_kind(change) {
+                        entry.kind = Some(kind);
+                    }
+                    if let Some(diff) = change.get("diff").and_then(|value| value.as_str()) {
+                        if !entry.diff.is_empty() && !entry.diff.ends_with('\n') {
+                            entry.diff.push('\n');
+                        }
+                        entry.diff.push_str(diff);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    snapshot
+}
+
+pub(crate) fn diff_line_counts(diff: &str) -> (usize, usize) {
+    diff.lines().fold((0, 0), |(added, removed), line| {
+        if line.starts_with('+') && !line.starts_with("+++") {
+            (added + 1, removed)
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            (added, removed + 1)
+        } else {
+            (added, removed)
+        }
+    })
+}
+
+fn side_stats(snapshot: &TurnSnapshot) -> TurnSideStats {
+    let (additions, deletions) = snapshot
+        .files
+        .values()
+        .map(|change| diff_line_counts(&change.diff))
+        .fold((0, 0), |acc, counts| (acc.0 + counts.0, acc.1 + counts.1));
+    TurnSideStats {
+        status: snapshot.status.clone(),
+        files_changed: snapshot.files.len(),
+        additions,
+        deletions,
+        commands_run: snapshot.commands_run,
+    }
+}
+
+fn line(change: LineChange, text: &str) -> MessageDiffLine {
+    MessageDiffLine {
+        change,
+        text: text.to_string(),
+    }
+}
+
+/// Line-level LCS diff between two messages.
+fn diff_lines(a: &str, b: &str) -> Vec<MessageDiffLine> {
+    let a: Vec<&str> = a.lines().collect();
+    let b: Vec<&str> = b.lines().collect();
+    if a.len().saturating_mul(b.len()) > MAX_DIFF_CELLS {
+        return a
+            .iter()
+            .map(|text| line(LineChange::OnlyA, text))
+            .chain(b.iter().map(|text| line(LineChange::OnlyB, text)))
+            .collect();
+    }
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            out.push(line(LineChange::Same, a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            out.push(line(LineChange::OnlyA, a[i]));
+            i += 1;
+        } else {
+            out.push(line(LineChange::OnlyB, b[j]));
+            j += 1;
+        }
+    }
+    out.extend(a[i..].iter().map(|text| line(LineChange::OnlyA, text)));
+    out.extend(b[j..].iter().map(|text| line(LineChange::OnlyB, text)));
+    out
+}
+
+fn compare_files(a: &TurnSnapshot, b: &TurnSnapshot) -> Vec<FileComparison> {
+    let mut paths: Vec<&String> = a.files.keys().chain(b.files.keys()).collect();
+    paths.sort();
+    paths.dedup();
+    paths
+        .into_iter()
+        .map(|path| {
+            let left = a.files.get(path);
+            let right = b.files.get(path);
+            let status = match (left, right) {
+                (Some(_), None) => FileComparisonStatus::OnlyA,
+                (None, Some(_)) => FileComparisonStatus::OnlyB,
+                (Some(left), Some(right)) if left.diff.trim() == right.diff.trim() => {
+                    FileComparisonStatus::Identical
+                }
+                _ => FileComparisonStatus::Different,
+            };
+            FileComparison {
+                path: path.clone(),
+                status,
+                kind_a: left.and_then(|change| change.kind.clone()),
+                kind_b: right.and_then(|change| change.kind.clone()),
+                diff_a: left.map(|change| change.diff.clone()),
+                diff_b: right.map(|change| change.diff.clone()),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn compare(
+    turn_a: TurnRef,
+    a: &TurnSnapshot,
+    turn_b: TurnRef,
+    b: &TurnSnapshot,
+) -> TurnComparison {
+    let message_diff = diff_lines(
+        a.final_message.as_deref().unwrap_or_default(),
+        b.final_message.as_deref().unwrap_or_default(),
+    );
+    TurnComparison {
+        turn_a,
+        turn_b,
+        stats_a: side_stats(a),
+        stats_b: side_stats(b),
+        files: compare_files(a, b),
+        final_message_a: a.final_message.clone(),
+        final_message_b: b.final_message.clone(),
+        message_diff,
+    }
+}
+
+/// Resumes the turn's thread on `session` and extracts the turn.
+pub(crate) async fn load_turn(
+    session: &WorkspaceSession,
+    turn: &TurnRef,
+) -> Result<TurnSnapshot, String> {
+    let response = session
+        .send_request("thread/resume", json!({ "threadId": turn.thread_id }))
+        .await?;
+    if let Some(error) = response.get("error") {
+        return Err(error
+            .get("message")
+            .and_then(|value| value.as_str())
+            .unwrap_or("Failed to load thread")
+            .to_string());
+    }
+    find_turn(&response, &turn.turn_id)
+        .ok_or_else(|| format!("Turn {} not found in thread {}", turn.turn_id, turn.thread_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn_ref(turn_id: &str) -> TurnRef {
+        TurnRef {
+            workspace_id: "ws".to_string(),
+            thread_id: "thread".to_string(),
+            turn_id: turn_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn find_turn_collects_files_messages_and_commands() {
+        let response = json!({
+            "id": 1,
+            "result": { "thread": { "id": "thread", "turns": [
+                { "id": "turn-1", "status": "completed", "items": [
+                    { "type": "agentMessage", "text": "Working on it" },
+                    { "type": "commandExecution", "command": "ls" },
+                    { "type": "fileChange", "changes": [
+                        { "path": "a.rs", "kind": { "type": "update" }, "diff": "+one\n-two" }
+                    ] },
+                    { "type": "agentMessage", "text": " Done. " }
+                ] },
+                { "id": "turn-2", "items": [] }
+            ] } }
+        });
+        let snapshot = find_turn(&response, "turn-1").expect("turn");
+        assert_eq!(snapshot.final_message.as_deref(), Some("Done."));
+        assert_eq!(snapshot.commands_run, 1);
+        assert_eq!(snapshot.files["a.rs"].kind.as_deref(), Some("update"));
+        assert_eq!(side_stats(&snapshot).additions, 1);
+        assert!(find_turn(&response, "missing").is_none());
+    }
+
+    #[test]
+    fn compare_classifies_files_and_diffs_messages() {
+        let mut a = TurnSnapshot {
+            final_message: Some("Fixed the bug.\nAdded a test.".to_string()),
+            ..TurnSnapshot::default()
+        };
+        let mut b = TurnSnapshot {
+            final_message: Some("Fixed the bug.\nUpdated docs.".to_string()),
+            ..TurnSnapshot::default()
+        };
+        let change = |diff: &str| FileChange {
+            kind: None,
+            diff: diff.to_string(),
+        };
+        a.files.insert("same.rs".to_string(), change("+x"));
+        b.files.insert("same.rs".to_string(), change("+x\n"));
+        a.files.insert("changed.rs".to_string(), change("+a"));
+        b.files.insert("changed.rs".to_string(), change("+b"));
+        a.files.insert("only_a.rs".to_string(), change("+a"));
+        b.files.insert("only_b.rs".to_string(), change("+b"));
+
+        let comparison = compare(turn_ref("a"), &a, turn_ref("b"), &b);
+        let statuses: Vec<_> = comparison
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("changed.rs", FileComparisonStatus::Different),
+                ("only_a.rs", FileComparisonStatus::OnlyA),
+                ("only_b.rs", FileComparisonStatus::OnlyB),
+                ("same.rs", FileComparisonStatus::Identical),
+            ]
+        );
+        let changes: Vec<_> = comparison
+            .message_diff
+            .iter()
+            .map(|line| line.change)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![LineChange::Same, LineChange::OnlyA, LineChange::OnlyB]
+        );
+    }
+}
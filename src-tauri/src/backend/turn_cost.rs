@@ -0,0 +1,214 @@
+use serde::Serialize;
+
+use crate::backend::prompt_budget::PromptSizeCheck;
+
+/// List prices in USD per million tokens, matched by model name prefix
+/// (longest first). Estimates for unknown models carry no dollar range.
+const MODEL_PRICING: &[(&str, ModelPricing)] = &[
+    ("gpt-5-nano", ModelPricing::new(0.05, 0.005, 0.40)),
+    ("gpt-5-mini", ModelPricing::new(0.25, 0.025, 2.00)),
+    ("gpt-5", ModelPricing::new(1.25, 0.125, 10.00)),
+    ("gpt-4.1-nano", ModelPricing::new(0.10, 0.025, 0.40)),
+    ("gpt-4.1-mini", ModelPricing::new(0.40, 0.10, 1.60)),
+    ("gpt-4.1", ModelPricing::new(2.00, 0.50, 8.00)),
+    ("o4-mini", ModelPricing::new(1.10, 0.275, 4.40)),
+    ("o3", ModelPricing::new(2.00, 0.50, 8.00)),
+    ("codex-mini", ModelPricing::new(1.50, 0.375, 6.00)),
+];
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ModelPricing {
+    pub(crate) input_per_million: f64,
+    pub(crate) cached_input_per_million: f64,
+    pub(crate) output_per_million: f64,
+}
+
+impl ModelPricing {
+    const fn new(input: f64, cached_input: f64, output: f64) -> Self {
+        Self {
+            input_per_million: input,
+            cached_input_per_million: cached_input,
+            output_per_million: output,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Range<T> {
+    pub(crate) low: T,
+    pub(crate) high: T,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnEstimate {
+    pub(crate) model: Option<String>,
+    pub(crate) effort: Option<String>,
+    pub(crate) prompt_tokens: u64,
+    pub(crate) context_tokens: u64,
+    pub(crate) context_window: Option<u64>,
+    /// Input over all model calls of the turn; the thread's context is
+    /// re-sent with every tool call, mostly from the prompt cache.
+    pub(crate) input_tokens: Range<u64>,
+    /// Output including reasoning.
+    pub(crate) output_tokens: Range<u64>,
+    pub(crate) cost_usd: Option<Range<f64>>,
+    pub(crate) pricing: Option<ModelPricing>,
+}
+
+pub(crate) fn model_pricing(model: &str) -> Option<ModelPricing> {
+    let model = model.to_lowercase();
+    MODEL_PRICING
+        .iter()
+        .filter(|(prefix, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, pricing)| *pricing)
+}
+
+/// Typical number of model calls and output tokens per turn for an effort.
+fn effort_profile(effort: Option<&str>) -> (Range<u64>, Range<u64>) {
+    match effort {
+        Some("minimal") | Some("low") => (
+            Range { low: 1, high: 4 },
+            Range {
+                low: 300,
+                high: 3_000,
+            },
+        ),
+        Some("high") | Some("xhigh") => (
+            Range { low: 2, high: 15 },
+            Range {
+                low: 3_000,
+                high: 30_000,
+            },
+        ),
+        _ => (
+            Range { low: 1, high: 8 },
+            Range {
+                low: 1_000,
+                high: 12_000,
+            },
+        ),
+    }
+}
+
+fn cost(pricing: &ModelPricing, fresh_input: u64, cached_input: u64, output: u64) -> f64 {
+    (fresh_input as f64 * pricing.input_per_million
+        + cached_input as f64 * pricing.cached_input_per_million
+        + output as f64 * pricing.output_per_million)
+        / 1_000_000.0
+}
+
+/// Estimates the token and dollar range of a turn from its prompt size
+/// check. `last_output_tokens` of the thread, when known, narrows the output
+/// range around what the thread has been producing.
+pub(crate) fn estimate_turn(
+    check: &PromptSizeCheck,
+    effort: Option<&str>,
+    last_output_tokens: Option<u64>,
+) -> TurnEstimate {
+    let (calls, default_output) = effort_profile(effort);
+    let output_tokens = match last_output_tokens {
+        Some(last) => Range {
+            low: (last / 2).min(default_output.high),
+            high: (last * 2).max(default_output.low),
+        },
+        None => default_output,
+    };
+    let prompt = check.estimated_tokens;
+    let context = check.context_tokens;
+    // Later calls re-send the context and everything produced so far.
+    let input_tokens = Range {
+        low: (context + prompt) * calls.low,
+        high: (context + prompt + output_tokens.high / 2) * calls.high,
+    };
+    let pricing = check.model.as_deref().and_then(model_pricing);
+    let cost_usd = pricing.map(|pricing| Range {
+        low: cost(
+            &pricing,
+            prompt,
+            input_tokens.low.saturating_sub(prompt),
+            output_tokens.low,
+        ),
+        high: cost(
+            &pricing,
+            prompt + output_tokens.high,
+            input_tokens
+                .high
+                .saturating_sub(prompt + output_tokens.high),
+            output_tokens.high,
+        ),
+    });
+    TurnEstimate {
+        model: check.model.clone(),
+        effort: effort.map(str::to_string),
+        prompt_tokens: prompt,
+        context_tokens: context,
+        context_window: check.context_window,
+        input_tokens,
+        output_tokens,
+        cost_usd,
+        pricing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::prompt_budget::PromptSizeLevel;
+
+    fn check(model: &str, estimated_tokens: u64, context_tokens: u64) -> PromptSizeCheck {
+        PromptSizeCheck {
+            level: PromptSizeLevel::Ok,
+            model: Some(model.to_string()),
+            estimated_tokens,
+            context_tokens,
+            context_window: Some(272_000),
+            suggestions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn matches_longest_model_prefix() {
+        assert_eq!(
+            model_pricing("gpt-5-mini").map(|p| p.output_per_million),
+            Some(2.0)
+        );
+        assert_eq!(
+            model_pricing("gpt-5-codex").map(|p| p.output_per_million),
+            Some(10.0)
+        );
+        assert_eq!(model_pricing("llama-3"), None);
+    }
+
+    #[test]
+    fn estimates_ranges_from_prompt_context_and_effort() {
+        let estimate = estimate_turn(&check("gpt-5", 1_000, 9_000), Some("low"), None);
+        assert_eq!(estimate.input_tokens.low, 10_000);
+        assert_eq!(
+            estimate.output_tokens,
+            Range {
+                low: 300,
+                high: 3_000
+            }
+        );
+        let cost = estimate.cost_usd.expect("cost");
+        // 1k fresh + 9k cached input and 300 output tokens.
+        assert!((cost.low - 0.005375).abs() < 1e-9);
+        assert!(cost.high > cost.low);
+
+        let narrowed = estimate_turn(&check("gpt-5", 1_000, 0), Some("high"), Some(2_000));
+        assert_eq!(
+            narrowed.output_tokens,
+            Range {
+                low: 1_000,
+                high: 4_000
+            }
+        );
+        assert!(estimate_turn(&check("other", 10, 0), None, None)
+            .cost_usd
+            .is_none());
+    }
+}
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+use crate::backend::thread_tags::normalize_tags;
+use crate::backend::turn_manifest::TurnManifest;
+
+/// Git trailer carrying the labels of the turns behind a commit.
+const TRAILER_KEY: &str = "Turn-Labels";
+
+/// Labels such as "refactor" or "experiment" on one turn.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnLabels {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) labels: Vec<String>,
+    pub(crate) updated_at: i64,
+}
+
+pub(crate) fn turn_labels_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("turn_labels.json")
+}
+
+pub(crate) fn read_turn_labels(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Vec<TurnLabels>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+/// Replaces the labels of a turn; an empty list removes its entry. Labels
+/// are normalized like thread tags.
+pub(crate) fn set_turn_labels(
+    path: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    thread_id: &str,
+    turn_id: &str,
+    labels: &[String],
+    now: i64,
+) -> Result<TurnLabels, String> {
+    let mut entries = read_turn_labels(path, cipher)?;
+    entries.retain(|entry| !(entry.workspace_id == workspace_id && entry.turn_id == turn_id));
+    let updated = TurnLabels {
+        workspace_id: workspace_id.to_string(),
+        thread_id: thread_id.to_string(),
+        turn_id: turn_id.to_string(),
+        labels: normalize_tags(labels),
+        updated_at: now,
+    };
+    if !updated.labels.is_empty() {
+        entries.push(updated.clone());
+    }
+    let data = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)?;
+    Ok(updated)
+}
+
+/// Ids of a workspace's turns that carry `label`.
+pub(crate) fn turns_with_label(
+    entries: &[TurnLabels],
+    workspace_id: Option<&str>,
+    label: &str,
+) -> HashSet<String> {
+    let label = label.trim().to_lowercase();
+    entries
+        .iter()
+        .filter(|entry| workspace_id.is_none_or(|workspace_id| entry.workspace_id == workspace_id))
+        .filter(|entry| entry.labels.contains(&label))
+        .map(|entry| entry.turn_id.clone())
+        .collect()
+}
+
+/// Sorted union of the labels on the turns whose manifest matches.
+pub(crate) fn labels_for_manifests<'a>(
+    entries: &[TurnLabels],
+    manifests: impl IntoIterator<Item = &'a TurnManifest>,
+) -> Vec<String> {
+    let turns: HashSet<(&str, &str)> = manifests
+        .into_iter()
+        .map(|manifest| (manifest.workspace_id.as_str(), manifest.turn_id.as_str()))
+        .collect();
+    entries
+        .iter()
+        .filter(|entry| turns.contains(&(entry.workspace_id.as_str(), entry.turn_id.as_str())))
+        .flat_map(|entry| entry.labels.iter().cloned())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Appends a `Turn-Labels:` trailer to a commit message, unless there are no
+/// labels or the message already has one.
+pub(crate) fn with_label_trailer(message: &str, labels: &[String]) -> String {
+    let has_trailer = message
+        .lines()
+        .any(|line| line.starts_with(&format!("{TRAILER_KEY}:")));
+    if labels.is_empty() || has_trailer {
+        return message.to_string();
+    }
+    format!(
+        "{}\n\n{TRAILER_KEY}: {}",
+        message.trim_end(),
+        labels.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::collections::BTreeMap;
+
+    fn manifest(turn_id: &str) -> TurnManifest {
+        TurnManifest {
+            workspace_id: "ws".to_string(),
+            thread_id: "t-1".to_string(),
+            turn_id: turn_id.to_string(),
+            recorded_at: 0,
+            git_sha: Some("abc".to_string()),
+            git_branch: Some("main".to_string()),
+            dirty_files: Vec::new(),
+            codex_version: None,
+            model: None,
+            effort: None,
+            access_mode: "current".to_string(),
+            approval_policy: "on-request".to_string(),
+            sandbox_policy: Value::Null,
+            collaboration_mode: None,
+            config_overrides: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn stores_normalized_labels_and_adds_a_commit_trailer() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        let path = turn_labels_path(&dir);
+        let cipher = DataCipher::plaintext();
+        let labels = |values: &[&str]| {
+            values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+        };
+        set_turn_labels(
+            &path,
+            &cipher,
+            "ws",
+            "t-1",
+            "turn-1",
+            &labels(&[" Refactor "]),
+            1,
+        )
+        .unwrap();
+        set_turn_labels(
+            &path,
+            &cipher,
+            "ws",
+            "t-1",
+            "turn-2",
+            &labels(&["bugfix", "refactor"]),
+            2,
+        )
+        .unwrap();
+        set_turn_labels(
+            &path,
+            &cipher,
+            "ws",
+            "t-1",
+            "turn-3",
+            &labels(&["experiment"]),
+            3,
+        )
+        .unwrap();
+        set_turn_labels(&path, &cipher, "ws", "t-1", "turn-3", &[], 4).unwrap();
+
+        let entries = read_turn_labels(&path, &cipher).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(turns_with_label(&entries, Some("ws"), "REFACTOR").len(), 2);
+
+        let commit_labels =
+            labels_for_manifests(&entries, &[manifest("turn-1"), manifest("turn-2")]);
+        assert_eq!(commit_labels, vec!["bugfix", "refactor"]);
+        let message = with_label_trailer("Fix parser\n", &commit_labels);
+        assert_eq!(message, "Fix parser\n\nTurn-Labels: bugfix, refactor");
+        assert_eq!(with_label_trailer(&message, &commit_labels), message);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,290 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+
+/// Priority of a turn waiting for a slot under the global concurrent-turn
+/// cap. Waiting interactive turns start before scheduled ones, which start
+/// before batch jobs.
+#[derive(
+    Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum TurnLane {
+    /// A prompt the user just sent.
+    #[default]
+    Interactive,
+    /// Queued tasks and automatic follow-ups.
+    Scheduled,
+    /// Fan-out runs such as best-of and A/B tests.
+    Batch,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnSlot {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) lane: TurnLane,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnLimiterStatus {
+    /// `None` when turns are not capped.
+    pub(crate) limit: Option<u32>,
+    pub(crate) running: Vec<TurnSlot>,
+    /// Waiting turns in the order they will start.
+    pub(crate) waiting: Vec<TurnSlot>,
+}
+
+struct Waiter {
+    ticket: u64,
+    slot: TurnSlot,
+}
+
+#[derive(Default)]
+struct LimiterState {
+    limit: Option<u32>,
+    running: HashMap<(String, String), TurnLane>,
+    waiting: Vec<Waiter>,
+    next_ticket: u64,
+}
+
+impl LimiterState {
+    fn enqueue(&mut self, slot: TurnSlot) -> u64 {
+        self.next_ticket += 1;
+        let ticket = self.next_ticket;
+        self.waiting.push(Waiter { ticket, slot });
+        ticket
+    }
+
+    fn next_waiter(&self) -> Option<u64> {
+        self.waiting
+            .iter()
+            .min_by_key(|waiter| (waiter.slot.lane, waiter.ticket))
+            .map(|waiter| waiter.ticket)
+    }
+
+    /// Starts a waiting turn when a slot is free and no waiter of a higher
+    /// lane (or an earlier one of the same lane) is ahead of it. A thread that
+    /// already holds a slot keeps using it.
+    fn try_start(&mut self, ticket: u64) -> bool {
+        let Some(index) = self
+            .waiting
+            .iter()
+            .position(|waiter| waiter.ticket == ticket)
+        else {
+            return true;
+        };
+        let slot = &self.waiting[index].slot;
+        let key = (slot.workspace_id.clone(), slot.thread_id.clone());
+        let holds_slot = self.running.contains_key(&key);
+        let free = self
+            .limit
+            .is_none_or(|limit| self.running.len() < limit as usize);
+        let next_in_line = free && self.next_waiter() == Some(ticket);
+        if !holds_slot && !next_in_line {
+            return false;
+        }
+        let waiter = self.waiting.remove(index);
+        self.running.insert(key, waiter.slot.lane);
+        true
+    }
+
+    fn cancel(&mut self, ticket: u64) {
+        self.waiting.retain(|waiter| waiter.ticket != ticket);
+    }
+
+    fn finish(&mut self, workspace_id: &str, thread_id: &str) -> bool {
+        self.running
+            .remove(&(workspace_id.to_string(), thread_id.to_string()))
+            .is_some()
+    }
+}
+
+/// Global cap on concurrently running turns across workspaces, e.g. to stay
+/// under account rate limits. Turns take a slot before `turn/start` and
+/// give it back when the turn completes.
+pub(crate) struct TurnLimiter {
+    state: Mutex<LimiterState>,
+    changed: Notify,
+}
+
+/// Removes a waiter whose `acquire` future is dropped before it started.
+struct PendingTicket<'a> {
+    limiter: &'a TurnLimiter,
+    ticket: u64,
+    started: bool,
+}
+
+impl Drop for PendingTicket<'_> {
+    fn drop(&mut self) {
+        if self.started {
+            return;
+        }
+        if let Ok(mut state) = self.limiter.state.lock() {
+            state.cancel(self.ticket);
+        }
+        self.limiter.changed.notify_waiters();
+    }
+}
+
+impl TurnLimiter {
+    pub(crate) fn new(limit: Option<u32>) -> Self {
+        Self {
+            state: Mutex::new(LimiterState {
+                limit: limit.filter(|limit| *limit > 0),
+                ..LimiterState::default()
+            }),
+            changed: Notify::new(),
+        }
+    }
+
+    /// Applies a changed `maxConcurrentTurns` setting; `None` or `0` lifts
+    /// the cap and starts every waiting turn.
+    pub(crate) fn set_limit(&self, limit: Option<u32>) {
+        if let Ok(mut state) = self.state.lock() {
+            state.limit = limit.filter(|limit| *limit > 0);
+        }
+        self.changed.notify_waiters();
+    }
+
+    /// Waits until the turn may start and takes a slot for its thread.
+    pub(crate) async fn acquire(&self, workspace_id: &str, thread_id: &str, lane: TurnLane) {
+        let ticket = match self.state.lock() {
+            Ok(mut state) => state.enqueue(TurnSlot {
+                workspace_id: workspace_id.to_string(),
+                thread_id: thread_id.to_string(),
+                lane,
+            }),
+            Err(_) => return,
+        };
+        let mut pending = PendingTicket {
+            limiter: self,
+            ticket,
+            started: false,
+        };
+        loop {
+            let mut notified = std::pin::pin!(self.changed.notified());
+            notified.as_mut().enable();
+            let started = self
+                .state
+                .lock()
+                .map(|mut state| state.try_start(ticket))
+                .unwrap_or(true);
+            if started {
+                pending.started = true;
+                // The next waiter may fit as well.
+                self.changed.notify_waiters();
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Gives back the slot of a thread whose turn completed or failed to
+    /// start.
+    pub(crate) fn finish(&self, workspace_id: &str, thread_id: &str) {
+        let finished = self
+            .state
+            .lock()
+            .map(|mut state| state.finish(workspace_id, thread_id))
+            .unwrap_or(false);
+        if finished {
+            self.changed.notify_waiters();
+        }
+    }
+
+    pub(crate) fn status(&self) -> TurnLimiterStatus {
+        let Ok(state) = self.state.lock() else {
+            return TurnLimiterStatus {
+                limit: None,
+                running: Vec::new(),
+                waiting: Vec::new(),
+            };
+        };
+        let mut running: Vec<TurnSlot> = state
+            .running
+            .iter()
+            .map(|((workspace_id, thread_id), lane)| TurnSlot {
+                workspace_id: workspace_id.clone(),
+                thread_id: thread_id.clone(),
+                lane: *lane,
+            })
+            .collect();
+        running.sort_by(|a, b| {
+            (a.lane, &a.workspace_id, &a.thread_id).cmp(&(b.lane, &b.workspace_id, &b.thread_id))
+        });
+        let mut waiting: Vec<&Waiter> = state.waiting.iter().collect();
+        waiting.sort_by_key(|waiter| (waiter.slot.lane, waiter.ticket));
+        TurnLimiterStatus {
+            limit: state.limit,
+            running,
+            waiting: waiting
+                .into_iter()
+                .map(|waiter| waiter.slot.clone())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(thread_id: &str, lane: TurnLane) -> TurnSlot {
+        TurnSlot {
+            workspace_id: "ws".to_string(),
+            thread_id: thread_id.to_string(),
+            lane,
+        }
+    }
+
+    #[test]
+    fn interactive_turns_start_before_queued_jobs() {
+        let mut state = LimiterState {
+            limit: Some(1),
+            ..LimiterState::default()
+        };
+        let nightly = state.enqueue(slot("nightly", TurnLane::Batch));
+        assert!(state.try_start(nightly));
+        let scheduled = state.enqueue(slot("scheduled", TurnLane::Scheduled));
+        let second_batch = state.enqueue(slot("batch", TurnLane::Batch));
+        let live = state.enqueue(slot("live", TurnLane::Interactive));
+        assert!(!state.try_start(live));
+
+        assert!(state.finish("ws", "nightly"));
+        assert!(!state.try_start(scheduled));
+        assert!(!state.try_start(second_batch));
+        assert!(state.try_start(live));
+
+        state.finish("ws", "live");
+        assert!(!state.try_start(second_batch));
+        assert!(state.try_start(scheduled));
+
+        // A thread that holds a slot is never queued behind others.
+        let follow_up = state.enqueue(slot("scheduled", TurnLane::Scheduled));
+        assert!(state.try_start(follow_up));
+        state.cancel(second_batch);
+        assert!(state.waiting.is_empty());
+    }
+
+    #[test]
+    fn uncapped_limiter_starts_immediately() {
+        let limiter = TurnLimiter::new(Some(0));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            limiter.acquire("ws", "a", TurnLane::Batch).await;
+            limiter.acquire("ws", "b", TurnLane::Batch).await;
+        });
+        let status = limiter.status();
+        assert_eq!(status.limit, None);
+        assert_eq!(status.running.len(), 2);
+        limiter.finish("ws", "a");
+        assert_eq!(limiter.status().running, vec![slot("b", TurnLane::Batch)]);
+    }
+}
@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::backend::command_output::safe_file_name;
+use crate::backend::secure_storage::DataCipher;
+
+/// Manifests kept per workspace, oldest dropped first.
+const MAX_MANIFESTS_PER_WORKSPACE: usize = 1_000;
+/// Larger dirty files are listed without a hash.
+const MAX_HASHED_BYTES: u64 = 8 * 1024 * 1024;
+
+/// A modified, added or deleted file at the time a turn started.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DirtyFile {
+    pub(crate) path: String,
+    /// Hex SHA-256 of the contents; `None` for deleted or oversized files.
+    pub(crate) sha256: Option<String>,
+}
+
+/// Workspace state and settings a turn started from, so its results can be
+/// audited and reproduced later.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnManifest {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+    pub(crate) recorded_at: i64,
+    pub(crate) git_sha: Option<String>,
+    pub(crate) git_branch: Option<String>,
+    pub(crate) dirty_files: Vec<DirtyFile>,
+    pub(crate) codex_version: Option<String>,
+    pub(crate) model: Option<String>,
+    pub(crate) effort: Option<String>,
+    pub(crate) access_mode: String,
+    pub(crate) approval_policy: String,
+    pub(crate) sandbox_policy: Value,
+    pub(crate) collaboration_mode: Option<Value>,
+    /// Settings the app applied on top of the user's codex config.
+    pub(crate) config_overrides: BTreeMap<String, Value>,
+}
+
+/// Hashes a dirty file, or returns `None` when it is gone or too large.
+pub(crate) fn hash_file(path: &Path) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_HASHED_BYTES {
+        return None;
+    }
+    let bytes = std::fs::read(path).ok()?;
+    Some(
+        Sha256::digest(&bytes)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect(),
+    )
+}
+
+pub(crate) fn turn_manifests_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join("turn_manifests")
+}
+
+fn workspace_file(data_dir: &Path, workspace_id: &str) -> PathBuf {
+    turn_manifests_dir(data_dir).join(format!("{}.json", safe_file_name(workspace_id)))
+}
+
+/// Manifests of one workspace, oldest first.
+pub(crate) fn read_manifests(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+) -> Result<Vec<TurnManifest>, String> {
+    let path = workspace_file(data_dir, workspace_id);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(&path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn record_manifest(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    manifest: TurnManifest,
+) -> Result<(), String> {
+    let path = workspace_file(data_dir, &manifest.workspace_id);
+    let mut manifests = read_manifests(data_dir, cipher, &manifest.workspace_id)?;
+    manifests.retain(|existing| existing.turn_id != manifest.turn_id);
+    manifests.push(manifest);
+    if manifests.len() > MAX_MANIFESTS_PER_WORKSPACE {
+        let excess = manifests.len() - MAX_MANIFESTS_PER_WORKSPACE;
+        manifests.drain(..excess);
+    }
+    let data = serde_json::to_string(&manifests).map_err(|e| e.to_string())?;
+    cipher.write(&path, &data)
+}
+
+pub(crate) fn find_manifest(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    workspace_id: &str,
+    turn_id: &str,
+) -> Result<Option<TurnManifest>, String> {
+    Ok(read_manifests(data_dir, cipher, workspace_id)?
+        .into_iter()
+        .find(|manifest| manifest.turn_id == turn_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn manifest(turn_id: &str) -> TurnManifest {
+        TurnManifest {
+            workspace_id: "ws-1".to_string(),
+            thread_id: "thread-1".to_string(),
+            turn_id: turn_id.to_string(),
+            recorded_at: 1,
+            git_sha: Some("abc123".to_string()),
+            git_branch: Some("main".to_string()),
+            dirty_files: vec![DirtyFile {
+                path: "src/lib.rs".to_string(),
+                sha256: None,
+            }],
+            codex_version: Some("codex-cli 0.80.0".to_string()),
+            model: Some("gpt-5".to_string()),
+            effort: None,
+            access_mode: "current".to_string(),
+            approval_policy: "on-request".to_string(),
+            sandbox_policy: json!({ "type": "workspaceWrite" }),
+            collaboration_mode: None,
+            config_overrides: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn records_and_finds_manifests_and_hashes_files() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        record_manifest(&dir, &cipher, manifest("turn-1")).expect("record");
+        record_manifest(&dir, &cipher, manifest("turn-2")).expect("record");
+        let found = find_manifest(&dir, &cipher, "ws-1", "turn-2")
+            .expect("read")
+            .expect("manifest");
+        assert_eq!(found.git_sha.as_deref(), Some("abc123"));
+        assert!(find_manifest(&dir, &cipher, "ws-2", "turn-2")
+            .expect("read")
+            .is_none());
+
+        let file = dir.join("hello.txt");
+        std::fs::write(&file, "hello").expect("write");
+        assert_eq!(
+            hash_file(&file).as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+        assert_eq!(hash_file(&dir.join("missing.txt")), None);
+    }
+}
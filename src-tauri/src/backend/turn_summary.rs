@@ -0,0 +1,205 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use crate::backend::code_stats::CodeStats;
+
+const EXCERPT_MAX_CHARS: usize = 280;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnSummary {
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: Option<String>,
+    pub(crate) status: Option<String>,
+    pub(crate) last_agent_message: Option<String>,
+    pub(crate) files_changed: usize,
+    pub(crate) commands_run: usize,
+    pub(crate) duration_ms: Option<u64>,
+    pub(crate) total_tokens: u64,
+    pub(crate) code_stats: CodeStats,
+}
+
+#[derive(Default)]
+struct TurnAccumulator {
+    started_at: Option<Instant>,
+    last_agent_message: Option<String>,
+    files: HashSet<String>,
+    /// `(path, diff)` of every file change, for language/LOC stats.
+    changes: Vec<(String, String)>,
+    commands_run: usize,
+    total_tokens: u64,
+}
+
+/// Accumulates per-thread turn activity from app-server notifications so a
+/// compact summary can be attached to `turn/completed`.
+#[derive(Default)]
+pub(crate) struct TurnSummaryTracker {
+    turns: HashMap<String, TurnAccumulator>,
+}
+
+pub(crate) fn params_thread_id(params: &Value) -> Option<String> {
+    params
+        .get("threadId")
+        .or_else(|| params.get("thread_id"))
+        .or_else(|| params.get("turn").and_then(|turn| turn.get("threadId")))
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string())
+}
+
+pub(crate) fn params_turn_id(params: &Value) -> Option<String> {
+    params
+        .get("turn")
+        .and_then(|turn| turn.get("id"))
+        .or_else(|| params.get("turnId"))
+        .or_else(|| params.get("turn_id"))
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string())
+}
+
+pub(crate) fn excerpt(text: &str, max_chars: usize) -> String {
+    let trimmed = text.trim();
+    if trimmed.chars().count() <= max_chars {
+        return trimmed.to_string();
+    }
+    let mut out: String = trimmed.chars().take(max_chars.saturating_sub(3)).collect();
+    out.push_str("...");
+    out
+}
+
+impl TurnSummaryTracker {
+    /// Feeds a notification into the tracker. Returns the finished summary when
+    /// the message is a `turn/completed` notification.
+    pub(crate) fn observe(&mut self, message: &Value) -> Option<TurnSummary> {
+        let method = message.get("method").and_then(|value| value.as_str())?;
+        let params = message.get("params")?;
+        let thread_id = params_thread_id(params)?;
+        match method {
+            "turn/started" => {
+                self.turns.insert(
+                    thread_id,
+                    TurnAccumulator {
+                        started_at: Some(Instant::now()),
+                        ..Default::default()
+                    },
+                );
+                None
+            }
+            "item/completed" => {
+                let item = params.get("item")?;
+                let turn = self.turns.entry(thread_id).or_default();
+                match item.get("type").and_then(|value| value.as_str()) {
+                    Some("agentMessage") => {
+                        let text = item.get("text").and_then(|value| value.as_str())?;
+                        if !text.trim().is_empty() {
+                            turn.last_agent_message = Some(excerpt(text, EXCERPT_MAX_CHARS));
+                        }
+                    }
+                    Some("commandExecution") => {
+                        turn.commands_run += 1;
+                    }
+                    Some("fileChange") => {
+                        let changes = item.get("changes").and_then(|value| value.as_array());
+                        for change in changes.into_iter().flatten() {
+                            if let Some(path) = change.get("path").and_then(|value| value.as_str()) {
+                                turn.files.insert(path.to_string());
+                                let diff = change
+                                    .get("diff")
+                                    .and_then(|value| value.as_str())
+                                    .unwrap_or_default();
+                                turn.changes.push((path.to_string(), diff.to_string()));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                None
+            }
+            "thread/tokenUsage/updated" => {
+                let usage = params
+                    .get("tokenUsage")
+                    .or_else(|| params.get("token_usage"))?;
+                let last = usage.get("last")?;
+                let tokens = last
+                    .get("totalTokens")
+                    .or_else(|| last.get("total_tokens"))
+                    .and_then(|value| value.as_u64())
+                    .unwrap_or(0);
+                self.turns.entry(thread_id).or_default().total_tokens += tokens;
+                None
+            }
+            "turn/completed" => {
+                let turn = self.turns.remove(&thread_id).unwrap_or_default();
+                let status = params
+                    .get("turn")
+                    .and_then(|turn| turn.get("status"))
+                    .and_then(|value| value.as_str())
+                    .map(|value| value.to_string());
+                Some(TurnSummary {
+                    turn_id: params_turn_id(params),
+                    thread_id,
+                    status,
+                    last_agent_message: turn.last_agent_message,
+                    files_changed: turn.files.len(),
+                    commands_run: turn.commands_run,
+                    duration_ms: turn
+                        .started_at
+                        .map(|started| started.elapsed().as_millis() as u64),
+                    total_tokens: turn.total_tokens,
+                    code_stats: CodeStats::from_changes(
+                        turn.changes
+                            .iter()
+                            .map(|(path, diff)| (path.as_str(), diff.as_str())),
+                    ),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn summarizes_completed_turn() {
+        let mut tracker = TurnSummaryTracker::default();
+        let events = [
+            json!({ "method": "turn/started", "params": { "threadId": "t1", "turn": { "id": "turn-1" } } }),
+            json!({ "method": "item/completed", "params": { "threadId": "t1", "item": { "type": "commandExecution", "id": "c1" } } }),
+            json!({ "method": "item/completed", "params": { "threadId": "t1", "item": { "type": "fileChange", "id": "f1", "changes": [{ "path": "a.rs" }, { "path": "b.rs" }] } } }),
+            json!({ "method": "item/completed", "params": { "threadId": "t1", "item": { "type": "fileChange", "id": "f2", "changes": [{ "path": "a.rs", "diff": "@@\n+x\n-y\n" }] } } }),
+            json!({ "method": "item/completed", "params": { "threadId": "t1", "item": { "type": "agentMessage", "id": "m1", "text": "  Done.  " } } }),
+            json!({ "method": "thread/tokenUsage/updated", "params": { "threadId": "t1", "tokenUsage": { "last": { "totalTokens": 120 } } } }),
+            json!({ "method": "thread/tokenUsage/updated", "params": { "threadId": "t1", "tokenUsage": { "last": { "totalTokens": 30 } } } }),
+        ];
+        for event in &events {
+            assert!(tracker.observe(event).is_none());
+        }
+        let summary = tracker
+            .observe(&json!({
+                "method": "turn/completed",
+                "params": { "threadId": "t1", "turn": { "id": "turn-1", "status": "completed" } }
+            }))
+            .expect("summary");
+        assert_eq!(summary.turn_id.as_deref(), Some("turn-1"));
+        assert_eq!(summary.status.as_deref(), Some("completed"));
+        assert_eq!(summary.last_agent_message.as_deref(), Some("Done."));
+        assert_eq!(summary.files_changed, 2);
+        assert_eq!(summary.commands_run, 1);
+        assert_eq!(summary.total_tokens, 150);
+        assert!(summary.duration_ms.is_some());
+        assert_eq!(summary.code_stats.by_language["Rust"].files, 2);
+        assert_eq!((summary.code_stats.added, summary.code_stats.removed), (1, 1));
+    }
+
+    #[test]
+    fn excerpt_truncates_long_text() {
+        let text = "a".repeat(400);
+        let value = excerpt(&text, 10);
+        assert_eq!(value, "aaaaaaa...");
+    }
+}
@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Duration;
+
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::backend::turn_summary::{params_thread_id, params_turn_id};
+
+/// Output kept per verification, from the end.
+const OUTPUT_MAX_CHARS: usize = 4_000;
+
+/// Result of running a verification command.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VerificationOutcome {
+    pub(crate) passed: bool,
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) timed_out: bool,
+    /// Last lines of the combined output, or why the command could not run.
+    pub(crate) output: String,
+}
+
+impl VerificationOutcome {
+    pub(crate) fn failed(output: impl Into<String>) -> Self {
+        Self {
+            passed: false,
+            exit_code: None,
+            timed_out: false,
+            output: output.into(),
+        }
+    }
+}
+
+fn output_tail(stdout: &[u8], stderr: &[u8]) -> String {
+    let mut output = String::from_utf8_lossy(stdout).to_string();
+    output.push_str(&String::from_utf8_lossy(stderr));
+    let chars = output.chars().count();
+    if chars <= OUTPUT_MAX_CHARS {
+        return output;
+    }
+    output.chars().skip(chars - OUTPUT_MAX_CHARS).collect()
+}
+
+/// Runs `command` through the platform shell in `cwd`; the process is killed
+/// when `limit` elapses.
+pub(crate) async fn run_command(cwd: &Path, command: &str, limit: Duration) -> VerificationOutcome {
+    let mut process = if cfg!(windows) {
+        let mut process = Command::new("cmd");
+        process.args(["/C", command]);
+        process
+    } else {
+        let mut process = Command::new("sh");
+        process.args(["-c", command]);
+        process
+    };
+    process.current_dir(cwd).kill_on_drop(true);
+    match timeout(limit, process.output()).await {
+        Ok(Ok(output)) => VerificationOutcome {
+            passed: output.status.success(),
+            exit_code: output.status.code(),
+            timed_out: false,
+            output: output_tail(&output.stdout, &output.stderr),
+        },
+        Ok(Err(err)) => {
+            VerificationOutcome::failed(format!("Failed to run verification command: {err}"))
+        }
+        Err(_) => VerificationOutcome {
+            passed: false,
+            exit_code: None,
+            timed_out: true,
+            output: String::new(),
+        },
+    }
+}
+
+/// Input of the follow-up turn started when verification fails.
+pub(crate) fn fix_prompt(command: &str, outcome: &VerificationOutcome) -> String {
+    let result = if outcome.timed_out {
+        "timed out".to_string()
+    } else {
+        match outcome.exit_code {
+            Some(code) => format!("failed with exit code {code}"),
+            None => "failed".to_string(),
+        }
+    };
+    format!(
+        "The verification command `{command}` {result} after your changes:\n\n```\n{}\n```\n\nFix the failures.",
+        outcome.output.trim_end()
+    )
+}
+
+/// A write-enabled turn that finished and should be verified.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FinishedTurn {
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: Option<String>,
+    /// Automatic fix turns already started for this chain of failures.
+    pub(crate) fix_attempt: u32,
+}
+
+/// Tracks which turns were started with write access, and how many automatic
+/// fix turns each thread has used since its last passing verification.
+#[derive(Default)]
+pub(crate) struct VerificationTracker {
+    armed: HashSet<(String, String)>,
+    fix_attempts: HashMap<(String, String), u32>,
+}
+
+impl VerificationTracker {
+    /// Marks the next turn of a thread as write-enabled.
+    pub(crate) fn arm(&mut self, workspace_id: &str, thread_id: &str) {
+        self.armed
+            .insert((workspace_id.to_string(), thread_id.to_string()));
+    }
+
+    /// Returns the armed turn a `turn/completed` notification finished.
+    /// Interrupted or failed turns are not verified and end the fix chain.
+    pub(crate) fn observe(&mut self, workspace_id: &str, message: &Value) -> Option<FinishedTurn> {
+        if message.get("method").and_then(|value| value.as_str()) != Some("turn/completed") {
+            return None;
+        }
+        let params = message.get("params")?;
+        let thread_id = params_thread_id(params)?;
+        let key = (workspace_id.to_string(), thread_id.clone());
+        if !self.armed.remove(&key) {
+            return None;
+        }
+        let status = params
+            .get("turn")
+            .and_then(|turn| turn.get("status"))
+            .or_else(|| {
+                params
+                    .get("summary")
+                    .and_then(|summary| summary.get("status"))
+            })
+            .and_then(|value| value.as_str());
+        if matches!(status, Some("interrupted" | "failed")) {
+            self.fix_attempts.remove(&key);
+            return None;
+        }
+        Some(FinishedTurn {
+            turn_id: params_turn_id(params),
+            fix_attempt: self.fix_attempts.get(&key).copied().unwrap_or(0),
+            thread_id,
+        })
+    }
+
+    /// Records that an automatic fix turn is being started.
+    pub(crate) fn record_fix(&mut self, workspace_id: &str, thread_id: &str, fix_attempt: u32) {
+        self.fix_attempts.insert(
+            (workspace_id.to_string(), thread_id.to_string()),
+            fix_attempt,
+        );
+    }
+
+    /// Ends a thread's fix chain after verification passed or gave up.
+    pub(crate) fn finish(&mut self, workspace_id: &str, thread_id: &str) {
+        self.fix_attempts
+            .remove(&(workspace_id.to_string(), thread_id.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn completed(thread_id: &str, status: &str) -> Value {
+        json!({
+            "method": "turn/completed",
+            "params": { "threadId": thread_id, "turn": { "id": "turn-1", "status": status } },
+        })
+    }
+
+    #[test]
+    fn only_armed_completed_turns_are_verified() {
+        let mut tracker = VerificationTracker::default();
+        assert!(tracker
+            .observe("ws", &completed("t-1", "completed"))
+            .is_none());
+
+        tracker.arm("ws", "t-1");
+        tracker.record_fix("ws", "t-1", 2);
+        let finished = tracker
+            .observe("ws", &completed("t-1", "completed"))
+            .expect("verified");
+        assert_eq!(finished.turn_id.as_deref(), Some("turn-1"));
+        assert_eq!(finished.fix_attempt, 2);
+        assert!(tracker
+            .observe("ws", &completed("t-1", "completed"))
+            .is_none());
+
+        tracker.arm("ws", "t-1");
+        assert!(tracker
+            .observe("ws", &completed("t-1", "interrupted"))
+            .is_none());
+        tracker.arm("ws", "t-1");
+        let finished = tracker
+            .observe("ws", &completed("t-1", "completed"))
+            .expect("verified");
+        assert_eq!(finished.fix_attempt, 0);
+    }
+
+    #[test]
+    fn fix_prompt_quotes_command_and_output() {
+        let outcome = VerificationOutcome {
+            passed: false,
+            exit_code: Some(101),
+            timed_out: false,
+            output: "test foo ... FAILED\n".to_string(),
+        };
+        let prompt = fix_prompt("cargo test -q", &outcome);
+        assert!(prompt
+            .starts_with("The verification command `cargo test -q` failed with exit code 101"));
+        assert!(prompt.contains("```\ntest foo ... FAILED\n```"));
+        assert_eq!(
+            output_tail(&[b'a'; OUTPUT_MAX_CHARS], b"b").len(),
+            OUTPUT_MAX_CHARS
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::backend::code_stats::analytics_dir;
+use crate::backend::prompt_insights::PromptOutcome;
+use crate::backend::secure_storage::DataCipher;
+
+/// Daily health sample files inside the analytics data class directory.
+const FILE_PREFIX: &str = "health-";
+/// Weight of each signal in the 0-100 score.
+const STABILITY_WEIGHT: f64 = 30.0;
+const FAILED_TURNS_WEIGHT: f64 = 30.0;
+const APPROVAL_LATENCY_WEIGHT: f64 = 15.0;
+const MAX_DOCTOR_PENALTY: f64 = 25.0;
+/// Average approval waits up to this long cost nothing.
+const APPROVAL_LATENCY_GRACE_MS: i64 = 60_000;
+/// Average approval waits this long cost the full weight.
+const APPROVAL_LATENCY_LIMIT_MS: i64 = 15 * 60_000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum HealthEvent {
+    SessionStarted,
+    /// A session that died or stopped answering and had to be restarted.
+    SessionLost,
+    #[serde(rename_all = "camelCase")]
+    ApprovalAnswered {
+        waited_ms: i64,
+    },
+}
+
+/// One health signal of a workspace, stored in the analytics data class.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HealthSample {
+    pub(crate) workspace_id: String,
+    pub(crate) recorded_at: i64,
+    #[serde(flatten)]
+    pub(crate) event: HealthEvent,
+}
+
+/// The raw counts a workspace's score is computed from.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HealthSignals {
+    pub(crate) sessions_started: usize,
+    pub(crate) sessions_lost: usize,
+    pub(crate) turns: usize,
+    pub(crate) failed_turns: usize,
+    pub(crate) approvals: usize,
+    pub(crate) average_approval_ms: Option<i64>,
+    pub(crate) doctor_errors: usize,
+    pub(crate) doctor_warnings: usize,
+}
+
+/// Points each signal took off the score.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HealthPenalties {
+    pub(crate) session_stability: f64,
+    pub(crate) failed_turns: f64,
+    pub(crate) approval_latency: f64,
+    pub(crate) doctor_findings: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceHealth {
+    pub(crate) workspace_id: String,
+    pub(crate) name: String,
+    /// 0-100, higher is healthier.
+    pub(crate) score: u8,
+    pub(crate) signals: HealthSignals,
+    pub(crate) penalties: HealthPenalties,
+}
+
+/// Health of every workspace over `days` days, least healthy first.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceHealthReport {
+    pub(crate) generated_at: i64,
+    pub(crate) days: u32,
+    pub(crate) workspaces: Vec<WorkspaceHealth>,
+}
+
+fn day_file(data_dir: &Path, day: NaiveDate) -> PathBuf {
+    analytics_dir(data_dir).join(format!("{FILE_PREFIX}{}.json", day.format("%Y-%m-%d")))
+}
+
+/// Health sample files, which follow the encryption setting.
+pub(crate) fn health_sample_files(data_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(analytics_dir(data_dir)) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(FILE_PREFIX))
+        })
+        .collect()
+}
+
+/// The last weekly report, kept so the next one is due a week later.
+pub(crate) fn health_report_path(data_dir: &Path) -> PathBuf {
+    analytics_dir(data_dir).join("workspace-health-report.json")
+}
+
+fn read_day(path: &Path, cipher: &DataCipher) -> Result<Vec<HealthSample>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn record_sample(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    sample: HealthSample,
+) -> Result<(), String> {
+    let day = chrono::DateTime::from_timestamp_millis(sample.recorded_at)
+        .unwrap_or_else(Utc::now)
+        .date_naive();
+    let path = day_file(data_dir, day);
+    let mut samples = read_day(&path, cipher)?;
+    samples.push(sample);
+    let data = serde_json::to_string(&samples).map_err(|e| e.to_string())?;
+    cipher.write(&path, &data)
+}
+
+/// Samples of every workspace over the last `days` days, oldest first.
+pub(crate) fn read_samples(
+    data_dir: &Path,
+    cipher: &DataCipher,
+    days: u32,
+    today: NaiveDate,
+) -> Result<Vec<HealthSample>, String> {
+    let mut samples = Vec::new();
+    for offset in (0..days).rev() {
+        let day = today - Duration::days(i64::from(offset));
+        samples.extend(read_day(&day_file(data_dir, day), cipher)?);
+    }
+    Ok(samples)
+}
+
+pub(crate) fn read_last_report(
+    path: &Path,
+    cipher: &DataCipher,
+) -> Result<Option<WorkspaceHealthReport>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_report(
+    path: &Path,
+    cipher: &DataCipher,
+    report: &WorkspaceHealthReport,
+) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(report).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+/// Counts a workspace's samples and turn outcomes. Doctor findings are
+/// filled in by the caller, since they are checked live.
+pub(crate) fn collect_signals(
+    workspace_id: &str,
+    samples: &[HealthSample],
+    outcomes: &[PromptOutcome],
+) -> HealthSignals {
+    let mut signals = HealthSignals::default();
+    let mut waited_total = 0;
+    for sample in samples
+        .iter()
+        .filter(|sample| sample.workspace_id == workspace_id)
+    {
+        match sample.event {
+            HealthEvent::SessionStarted => signals.sessions_started += 1,
+            HealthEvent::SessionLost => signals.sessions_lost += 1,
+            HealthEvent::ApprovalAnswered { waited_ms } => {
+                signals.approvals += 1;
+                waited_total += waited_ms.max(0);
+            }
+        }
+    }
+    if signals.approvals > 0 {
+        signals.average_approval_ms = Some(waited_total / signals.approvals as i64);
+    }
+    for outcome in outcomes
+        .iter()
+        .filter(|outcome| outcome.workspace_id == workspace_id)
+    {
+        signals.turns += 1;
+        if outcome.status.as_deref() == Some("failed") {
+            signals.failed_turns += 1;
+        }
+    }
+    signals
+}
+
+fn ratio(hits: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (hits as f64 / total as f64).min(1.0)
+    }
+}
+
+/// Scores signals out of 100: lost sessions and failed turns are weighed by
+/// how often they happen, slow approvals by how far the average wait is
+/// past a minute, and doctor findings by severity.
+pub(crate) fn score(signals: &HealthSignals) -> (u8, HealthPenalties) {
+    let approval_latency = signals
+        .average_approval_ms
+        .map(|average| {
+            let over = (average - APPROVAL_LATENCY_GRACE_MS).max(0) as f64;
+            let span = (APPROVAL_LATENCY_LIMIT_MS - APPROVAL_LATENCY_GRACE_MS) as f64;
+            APPROVAL_LATENCY_WEIGHT * (over / span).min(1.0)
+        })
+        .unwrap_or(0.0);
+    let penalties = HealthPenalties {
+        session_stability: STABILITY_WEIGHT
+            * ratio(signals.sessions_lost, signals.sessions_started.max(1)),
+        failed_turns: FAILED_TURNS_WEIGHT * ratio(signals.failed_turns, signals.turns),
+        approval_latency,
+        doctor_findings: (signals.doctor_errors as f64 * 10.0
+            + signals.doctor_warnings as f64 * 3.0)
+            .min(MAX_DOCTOR_PENALTY),
+    };
+    let total = penalties.session_stability
+        + penalties.failed_turns
+        + penalties.approval_latency
+        + penalties.doctor_findings;
+    let score = (100.0 - total).round().clamp(0.0, 100.0) as u8;
+    (score, penalties)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::prompt_insights::PromptFeatures;
+
+    fn sample(workspace_id: &str, event: HealthEvent) -> HealthSample {
+        HealthSample {
+            workspace_id: workspace_id.to_string(),
+            recorded_at: 0,
+            event,
+        }
+    }
+
+    fn outcome(status: &str) -> PromptOutcome {
+        PromptOutcome {
+            workspace_id: "ws".to_string(),
+            thread_id: "t-1".to_string(),
+            turn_id: None,
+            recorded_at: 0,
+            status: Some(status.to_string()),
+            features: PromptFeatures::default(),
+            retry: false,
+        }
+    }
+
+    #[test]
+    fn scores_unstable_workspaces_lower() {
+        let samples = vec![
+            sample("ws", HealthEvent::SessionStarted),
+            sample("ws", HealthEvent::SessionStarted),
+            sample("ws", HealthEvent::SessionLost),
+            sample("ws", HealthEvent::ApprovalAnswered { waited_ms: 1_000 }),
+            sample("ws", HealthEvent::ApprovalAnswered { waited_ms: 3_000 }),
+            sample("other", HealthEvent::SessionLost),
+        ];
+        let outcomes = vec![outcome("completed"), outcome("failed")];
+        let signals = collect_signals("ws", &samples, &outcomes);
+        assert_eq!(signals.sessions_started, 2);
+        assert_eq!(signals.sessions_lost, 1);
+        assert_eq!(signals.failed_turns, 1);
+        assert_eq!(signals.average_approval_ms, Some(2_000));
+
+        let (unstable, penalties) = score(&signals);
+        assert_eq!(penalties.session_stability, 15.0);
+        assert_eq!(penalties.failed_turns, 15.0);
+        assert_eq!(penalties.approval_latency, 0.0);
+        assert_eq!(unstable, 70);
+        assert_eq!(score(&HealthSignals::default()).0, 100);
+    }
+
+    #[test]
+    fn samples_round_trip_through_day_files() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        let cipher = DataCipher::plaintext();
+        let now = Utc::now();
+        let mut entry = sample("ws", HealthEvent::ApprovalAnswered { waited_ms: 42 });
+        entry.recorded_at = now.timestamp_millis();
+        record_sample(&dir, &cipher, entry.clone()).unwrap();
+
+        let samples = read_samples(&dir, &cipher, 7, now.date_naive()).unwrap();
+        assert_eq!(samples, vec![entry]);
+        assert_eq!(health_sample_files(&dir).len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,98 @@
+use crate::types::{WorkspaceEntry, WorkspaceIdentity};
+
+/// Colors offered for workspaces; defaults are picked from these.
+const PALETTE: &[&str] = &[
+    "#e5484d", "#f76b15", "#ffc53d", "#46a758", "#12a594", "#0090ff", "#6e56cf", "#d6409f",
+];
+
+/// Icon names the frontend knows how to render.
+pub(crate) const WORKSPACE_ICONS: &[&str] = &[
+    "folder", "box", "rocket", "leaf", "flame", "star", "bolt", "globe", "terminal", "book",
+    "beaker", "cpu",
+];
+
+/// FNV-1a, so the default identity of a path stays the same across builds.
+fn path_hash(path: &str) -> u64 {
+    path.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+pub(crate) fn default_identity(path: &str) -> WorkspaceIdentity {
+    let hash = path_hash(path);
+    WorkspaceIdentity {
+        color: PALETTE[(hash % PALETTE.len() as u64) as usize].to_string(),
+        icon: WORKSPACE_ICONS[((hash >> 32) % WORKSPACE_ICONS.len() as u64) as usize].to_string(),
+    }
+}
+
+/// Accepts `#rgb` or `#rrggbb` and returns the lowercase `#rrggbb` form.
+pub(crate) fn normalize_color(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    let digits = value
+        .strip_prefix('#')
+        .filter(|digits| digits.chars().all(|ch| ch.is_ascii_hexdigit()))
+        .ok_or_else(|| format!("Invalid workspace color: {value}"))?;
+    match digits.len() {
+        6 => Ok(format!("#{}", digits.to_ascii_lowercase())),
+        3 => Ok(digits.chars().fold(String::from("#"), |mut color, ch| {
+            let ch = ch.to_ascii_lowercase();
+            color.push(ch);
+            color.push(ch);
+            color
+        })),
+        _ => Err(format!("Invalid workspace color: {value}")),
+    }
+}
+
+pub(crate) fn normalize_icon(value: &str) -> Result<String, String> {
+    let icon = value.trim().to_lowercase();
+    if WORKSPACE_ICONS.contains(&icon.as_str()) {
+        Ok(icon)
+    } else {
+        Err(format!("Unknown workspace icon: {value}"))
+    }
+}
+
+impl WorkspaceEntry {
+    /// The chosen icon and color, falling back to the path-derived defaults
+    /// for anything unset or no longer valid.
+    pub(crate) fn identity(&self) -> WorkspaceIdentity {
+        let defaults = default_identity(&self.path);
+        WorkspaceIdentity {
+            icon: self
+                .icon
+                .as_deref()
+                .and_then(|icon| normalize_icon(icon).ok())
+                .unwrap_or(defaults.icon),
+            color: self
+                .color
+                .as_deref()
+                .and_then(|color| normalize_color(color).ok())
+                .unwrap_or(defaults.color),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_stable_per_path() {
+        let first = default_identity("/Users/me/code/api");
+        assert_eq!(first, default_identity("/Users/me/code/api"));
+        assert!(PALETTE.contains(&first.color.as_str()));
+        assert!(WORKSPACE_ICONS.contains(&first.icon.as_str()));
+    }
+
+    #[test]
+    fn validates_colors_and_icons() {
+        assert_eq!(normalize_color("#0A0B0C").unwrap(), "#0a0b0c");
+        assert_eq!(normalize_color(" #AbC ").unwrap(), "#aabbcc");
+        assert!(normalize_color("red").is_err());
+        assert!(normalize_color("#12345").is_err());
+        assert_eq!(normalize_icon("Rocket").unwrap(), "rocket");
+        assert!(normalize_icon("unicorn").is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+pub(crate) const LOCK_FILE_NAME: &str = ".codexmonitor.lock";
+/// A lock not refreshed for this long is treated as abandoned, since the
+/// process of a lock written on another machine cannot be checked.
+pub(crate) const STALE_AFTER_MS: i64 = 10 * 60 * 1000;
+
+/// Advisory lock an instance writes into a checkout before letting an agent
+/// write to it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceLock {
+    pub(crate) pid: u32,
+    pub(crate) host: String,
+    pub(crate) acquired_at: i64,
+    pub(crate) refreshed_at: i64,
+}
+
+impl WorkspaceLock {
+    pub(crate) fn is_own(&self, pid: u32, host: &str) -> bool {
+        self.pid == pid && self.host == host
+    }
+
+    /// Whether another live process holds the lock. `pid_alive` is only
+    /// consulted for locks written on this host.
+    pub(crate) fn held_by_other(
+        &self,
+        pid: u32,
+        host: &str,
+        now: i64,
+        pid_alive: impl Fn(u32) -> bool,
+    ) -> bool {
+        if self.is_own(pid, host) || now - self.refreshed_at > STALE_AFTER_MS {
+            return false;
+        }
+        self.host != host || pid_alive(self.pid)
+    }
+}
+
+pub(crate) fn lock_path(workspace_path: &Path) -> PathBuf {
+    workspace_path.join(LOCK_FILE_NAME)
+}
+
+/// The lock in a checkout; `None` when there is none or it is unreadable.
+pub(crate) fn read_lock(path: &Path) -> Option<WorkspaceLock> {
+    let data = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+pub(crate) fn write_lock(path: &Path, lock: &WorkspaceLock) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(lock).map_err(|e| e.to_string())?;
+    std::fs::write(path, data).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+pub(crate) fn host_name() -> String {
+    #[cfg(unix)]
+    {
+        let mut buffer = [0u8; 256];
+        // SAFETY: the buffer is valid for its full length and gethostname
+        // writes at most that many bytes.
+        let result = unsafe { libc::gethostname(buffer.as_mut_ptr().cast(), buffer.len()) };
+        if result == 0 {
+            let end = buffer.iter().position(|byte| *byte == 0).unwrap_or(0);
+            if end > 0 {
+                return String::from_utf8_lossy(&buffer[..end]).into_owned();
+            }
+        }
+    }
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn pid_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        let Ok(pid) = libc::pid_t::try_from(pid) else {
+            return false;
+        };
+        // SAFETY: signal 0 only checks whether the process exists.
+        if unsafe { libc::kill(pid, 0) } == 0 {
+            return true;
+        }
+        std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = pid;
+        true
+    }
+}
+
+pub(crate) enum LockOutcome {
+    /// This process now holds the lock at the path.
+    Acquired(PathBuf),
+    HeldByOther(WorkspaceLock),
+}
+
+/// Takes or refreshes this process's lock on a checkout, unless another
+/// live instance holds it and `force` is not set.
+pub(crate) fn acquire(workspace_path: &Path, now: i64, force: bool) -> Result<LockOutcome, String> {
+    let pid = std::process::id();
+    let host = host_name();
+    let path = lock_path(workspace_path);
+    let acquired_at = match read_lock(&path) {
+        Some(lock) if !force && lock.held_by_other(pid, &host, now, pid_alive) => {
+            return Ok(LockOutcome::HeldByOther(lock));
+        }
+        Some(lock) if lock.is_own(pid, &host) => lock.acquired_at,
+        _ => now,
+    };
+    write_lock(
+        &path,
+        &WorkspaceLock {
+            pid,
+            host,
+            acquired_at,
+            refreshed_at: now,
+        },
+    )?;
+    Ok(LockOutcome::Acquired(path))
+}
+
+pub(crate) fn conflict_message(lock: &WorkspaceLock) -> String {
+    format!(
+        "Another Codex Monitor (pid {} on {}) is working in this checkout. Take over its lock to send anyway.",
+        lock.pid, lock.host
+    )
+}
+
+/// Removes the lock only if it is still this process's.
+pub(crate) fn release_lock(path: &Path) {
+    let (pid, host) = (std::process::id(), host_name());
+    if read_lock(path).is_some_and(|lock| lock.is_own(pid, &host)) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock(pid: u32, host: &str, refreshed_at: i64) -> WorkspaceLock {
+        WorkspaceLock {
+            pid,
+            host: host.to_string(),
+            acquired_at: 0,
+            refreshed_at,
+        }
+    }
+
+    #[test]
+    fn detects_live_locks_of_other_instances() {
+        let now = STALE_AFTER_MS;
+        assert!(!lock(1, "mac", now).held_by_other(1, "mac", now, |_| true));
+        assert!(lock(2, "mac", now).held_by_other(1, "mac", now, |_| true));
+        assert!(!lock(2, "mac", now).held_by_other(1, "mac", now, |_| false));
+        assert!(lock(2, "linux", now).held_by_other(1, "mac", now, |_| false));
+        assert!(!lock(2, "linux", -1).held_by_other(1, "mac", now, |_| true));
+    }
+
+    #[test]
+    fn releases_only_its_own_lock() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = lock_path(&dir);
+        let other = lock(std::process::id() + 1, "elsewhere", 0);
+        write_lock(&path, &other).unwrap();
+        release_lock(&path);
+        assert_eq!(read_lock(&path), Some(other));
+        assert!(matches!(
+            acquire(&dir, 1, true),
+            Ok(LockOutcome::Acquired(_))
+        ));
+        release_lock(&path);
+        assert!(read_lock(&path).is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::backend::secure_storage::DataCipher;
+
+/// Opens within an hour of the current one a workspace needs before it is
+/// prewarmed.
+pub(crate) const MIN_OPENS_NEAR_HOUR: u32 = 3;
+/// Most sessions prewarmed at once.
+pub(crate) const MAX_PREWARMED: usize = 3;
+
+/// How often the user opened a workspace, by local hour of day.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceUsage {
+    pub(crate) workspace_id: String,
+    pub(crate) opens_by_hour: [u32; 24],
+    pub(crate) last_opened_at: i64,
+}
+
+impl WorkspaceUsage {
+    /// Opens in the hour before, at and after `hour`.
+    fn opens_near(&self, hour: usize) -> u32 {
+        [hour + 23, hour, hour + 1]
+            .iter()
+            .map(|hour| self.opens_by_hour[hour % 24])
+            .sum()
+    }
+}
+
+pub(crate) fn workspace_usage_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("workspace_usage.json")
+}
+
+pub(crate) fn read_usage(path: &Path, cipher: &DataCipher) -> Result<Vec<WorkspaceUsage>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = cipher.read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+pub(crate) fn write_usage(
+    path: &Path,
+    cipher: &DataCipher,
+    usage: &[WorkspaceUsage],
+) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(usage).map_err(|e| e.to_string())?;
+    cipher.write(path, &data)
+}
+
+pub(crate) fn record_open(
+    usage: &mut Vec<WorkspaceUsage>,
+    workspace_id: &str,
+    hour: usize,
+    now: i64,
+) {
+    let index = match usage
+        .iter()
+        .position(|entry| entry.workspace_id == workspace_id)
+    {
+        Some(index) => index,
+        None => {
+            usage.push(WorkspaceUsage {
+                workspace_id: workspace_id.to_string(),
+                opens_by_hour: [0; 24],
+                last_opened_at: now,
+            });
+            usage.len() - 1
+        }
+    };
+    let entry = &mut usage[index];
+    entry.opens_by_hour[hour % 24] = entry.opens_by_hour[hour % 24].saturating_add(1);
+    entry.last_opened_at = now;
+}
+
+/// Workspaces the user usually opens around `hour`, most opened first and
+/// the most recently opened breaking ties.
+pub(crate) fn prewarm_candidates(
+    usage: &[WorkspaceUsage],
+    hour: usize,
+    limit: usize,
+) -> Vec<String> {
+    let mut scored: Vec<_> = usage
+        .iter()
+        .map(|entry| (entry.opens_near(hour % 24), entry))
+        .filter(|(opens, _)| *opens >= MIN_OPENS_NEAR_HOUR)
+        .collect();
+    scored.sort_by(|(a_opens, a), (b_opens, b)| {
+        b_opens
+            .cmp(a_opens)
+            .then(b.last_opened_at.cmp(&a.last_opened_at))
+    });
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, entry)| entry.workspace_id.clone())
+        .collect()
+}
+
+/// Forgets workspaces that no longer exist.
+pub(crate) fn retain_workspaces(usage: &mut Vec<WorkspaceUsage>, workspace_ids: &[String]) -> bool {
+    let before = usage.len();
+    usage.retain(|entry| workspace_ids.contains(&entry.workspace_id));
+    usage.len() != before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prewarms_workspaces_opened_around_the_hour() {
+        let mut usage = Vec::new();
+        for (hour, now) in [(9, 1), (9, 2), (10, 3)] {
+            record_open(&mut usage, "morning", hour, now);
+        }
+        for (hour, now) in [(8, 4), (9, 5), (9, 6), (10, 7)] {
+            record_open(&mut usage, "busy", hour, now);
+        }
+        record_open(&mut usage, "rare", 9, 8);
+        for now in 9..13 {
+            record_open(&mut usage, "evening", 20, now);
+        }
+
+        assert_eq!(
+            prewarm_candidates(&usage, 9, MAX_PREWARMED),
+            vec!["busy".to_string(), "morning".to_string()]
+        );
+        assert_eq!(prewarm_candidates(&usage, 9, 1), vec!["busy".to_string()]);
+        assert_eq!(
+            prewarm_candidates(&usage, 21, MAX_PREWARMED),
+            vec!["evening".to_string()]
+        );
+        assert!(prewarm_candidates(&usage, 3, MAX_PREWARMED).is_empty());
+    }
+
+    #[test]
+    fn hour_window_wraps_around_midnight() {
+        let mut usage = Vec::new();
+        for now in 0..3 {
+            record_open(&mut usage, "late", 23, now);
+        }
+        assert_eq!(
+            prewarm_candidates(&usage, 0, MAX_PREWARMED),
+            vec!["late".to_string()]
+        );
+        assert!(retain_workspaces(&mut usage, &[]));
+        assert!(usage.is_empty());
+    }
+}
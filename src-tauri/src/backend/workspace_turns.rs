@@ -0,0 +1,251 @@
+use serde::Serialize;
+use serde_json::json;
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+
+use crate::types::TurnOverlapMode;
+
+/// Error code of the structured error returned when a turn would overlap.
+pub(crate) const TURN_ACTIVE: &str = "turnActive";
+
+/// A turn started in a workspace that has not completed yet. `turn_id` is
+/// `None` until `turn/start` returns it.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ActiveTurn {
+    pub(crate) workspace_id: String,
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: Option<String>,
+    pub(crate) started_at: i64,
+}
+
+impl ActiveTurn {
+    pub(crate) fn into_error(self) -> String {
+        let message = format!(
+            "A turn is already running in thread {}. Interrupt it, or send with \
+replaceActiveTurn to interrupt and replace it.",
+            self.thread_id
+        );
+        json!({
+            "code": TURN_ACTIVE,
+            "message": message,
+            "activeTurn": self,
+        })
+        .to_string()
+    }
+}
+
+#[derive(Default)]
+struct GateState {
+    limit: Option<u32>,
+    mode: TurnOverlapMode,
+    active: Vec<ActiveTurn>,
+}
+
+impl GateState {
+    /// The turn keeping a new one on `thread_id` from starting: a turn
+    /// running on the same thread, or the oldest turn of a workspace at its
+    /// cap. Without a cap only the same thread blocks.
+    fn blocking(&self, workspace_id: &str, thread_id: &str) -> Option<&ActiveTurn> {
+        let mut in_workspace = self
+            .active
+            .iter()
+            .filter(|turn| turn.workspace_id == workspace_id);
+        if let Some(same_thread) = in_workspace
+            .clone()
+            .find(|turn| turn.thread_id == thread_id)
+        {
+            return Some(same_thread);
+        }
+        let limit = self.limit? as usize;
+        if in_workspace.clone().count() >= limit {
+            return in_workspace.next();
+        }
+        None
+    }
+
+    fn position(&self, workspace_id: &str, thread_id: &str) -> Option<usize> {
+        self.active
+            .iter()
+            .position(|turn| turn.workspace_id == workspace_id && turn.thread_id == thread_id)
+    }
+}
+
+/// Tracks the running turn of every thread and caps how many run at once
+/// per workspace (`maxWorkspaceTurns`). A turn that would overlap waits or
+/// is rejected, depending on `turnOverlapMode`.
+pub(crate) struct WorkspaceTurnGate {
+    state: Mutex<GateState>,
+    changed: Notify,
+}
+
+impl WorkspaceTurnGate {
+    pub(crate) fn new(limit: Option<u32>, mode: TurnOverlapMode) -> Self {
+        Self {
+            state: Mutex::new(GateState {
+                limit: limit.filter(|limit| *limit > 0),
+                mode,
+                active: Vec::new(),
+            }),
+            changed: Notify::new(),
+        }
+    }
+
+    /// Applies changed `maxWorkspaceTurns`/`turnOverlapMode` settings.
+    pub(crate) fn configure(&self, limit: Option<u32>, mode: TurnOverlapMode) {
+        if let Ok(mut state) = self.state.lock() {
+            state.limit = limit.filter(|limit| *limit > 0);
+            state.mode = mode;
+        }
+        self.changed.notify_waiters();
+    }
+
+    /// Registers a turn about to start on the thread. While it would overlap,
+    /// waits in `queue` mode or when `wait` is set, and otherwise returns the
+    /// turn in the way.
+    pub(crate) async fn enter(
+        &self,
+        workspace_id: &str,
+        thread_id: &str,
+        wait: bool,
+        now: i64,
+    ) -> Result<(), ActiveTurn> {
+        loop {
+            let mut notified = std::pin::pin!(self.changed.notified());
+            notified.as_mut().enable();
+            {
+                let Ok(mut state) = self.state.lock() else {
+                    return Ok(());
+                };
+                match state.blocking(workspace_id, thread_id) {
+                    None => {
+                        state.active.push(ActiveTurn {
+                            workspace_id: workspace_id.to_string(),
+                            thread_id: thread_id.to_string(),
+                            turn_id: None,
+                            started_at: now,
+                        });
+                        return Ok(());
+                    }
+                    Some(turn) if !wait && state.mode == TurnOverlapMode::Reject => {
+                        return Err(turn.clone());
+                    }
+                    Some(_) => {}
+                }
+            }
+            notified.await;
+        }
+    }
+
+    /// Records the id of a thread's running turn, also for turns started
+    /// outside `enter`, e.g. by another client of the app-server.
+    pub(crate) fn started(&self, workspace_id: &str, thread_id: &str, turn_id: &str, now: i64) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        match state.position(workspace_id, thread_id) {
+            Some(index) => state.active[index].turn_id = Some(turn_id.to_string()),
+            None => state.active.push(ActiveTurn {
+                workspace_id: workspace_id.to_string(),
+                thread_id: thread_id.to_string(),
+                turn_id: Some(turn_id.to_string()),
+                started_at: now,
+            }),
+        }
+    }
+
+    /// Forgets the thread's turn once it completed or failed to start.
+    pub(crate) fn finish(&self, workspace_id: &str, thread_id: &str) {
+        let finished = self
+            .state
+            .lock()
+            .map(|mut state| {
+                let index = state.position(workspace_id, thread_id);
+                index.map(|index| state.active.remove(index)).is_some()
+            })
+            .unwrap_or(false);
+        if finished {
+            self.changed.notify_waiters();
+        }
+    }
+
+    /// Forgets every turn of a workspace whose app-server was restarted.
+    pub(crate) fn finish_workspace(&self, workspace_id: &str) {
+        if let Ok(mut state) = self.state.lock() {
+            state
+                .active
+                .retain(|turn| turn.workspace_id != workspace_id);
+        }
+        self.changed.notify_waiters();
+    }
+
+    pub(crate) fn active_turn(&self, workspace_id: &str, thread_id: &str) -> Option<ActiveTurn> {
+        let state = self.state.lock().ok()?;
+        let index = state.position(workspace_id, thread_id)?;
+        Some(state.active[index].clone())
+    }
+
+    /// Running turns, optionally of one workspace, oldest first.
+    pub(crate) fn active(&self, workspace_id: Option<&str>) -> Vec<ActiveTurn> {
+        let Ok(state) = self.state.lock() else {
+            return Vec::new();
+        };
+        state
+            .active
+            .iter()
+            .filter(|turn| {
+                workspace_id.is_none_or(|workspace_id| turn.workspace_id == workspace_id)
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime")
+            .block_on(future)
+    }
+
+    #[test]
+    fn rejects_overlapping_turns_with_the_active_turn_id() {
+        let gate = WorkspaceTurnGate::new(Some(2), TurnOverlapMode::Reject);
+        block_on(gate.enter("ws", "a", false, 1)).unwrap();
+        gate.started("ws", "a", "turn-a", 1);
+
+        let same_thread = block_on(gate.enter("ws", "a", false, 2)).unwrap_err();
+        assert_eq!(same_thread.turn_id.as_deref(), Some("turn-a"));
+
+        block_on(gate.enter("ws", "b", false, 3)).unwrap();
+        let over_cap = block_on(gate.enter("ws", "c", false, 4)).unwrap_err();
+        assert_eq!(over_cap.thread_id, "a");
+        block_on(gate.enter("other", "c", false, 4)).unwrap();
+
+        gate.finish("ws", "a");
+        block_on(gate.enter("ws", "c", false, 5)).unwrap();
+        assert_eq!(gate.active(Some("ws")).len(), 2);
+
+        gate.finish_workspace("ws");
+        assert_eq!(gate.active(None).len(), 1);
+    }
+
+    #[test]
+    fn uncapped_gate_still_rejects_overlap_on_the_same_thread() {
+        let gate = WorkspaceTurnGate::new(None, TurnOverlapMode::Reject);
+        block_on(gate.enter("ws", "a", false, 1)).unwrap();
+        let same_thread = block_on(gate.enter("ws", "a", false, 2)).unwrap_err();
+        assert_eq!(same_thread.started_at, 1);
+        block_on(gate.enter("ws", "c", false, 2)).unwrap();
+        gate.started("ws", "b", "turn-b", 3);
+        assert_eq!(
+            gate.active_turn("ws", "b").and_then(|turn| turn.turn_id),
+            Some("turn-b".to_string())
+        );
+    }
+}
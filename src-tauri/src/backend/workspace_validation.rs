@@ -0,0 +1,276 @@
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::types::WorkspaceEntry;
+
+/// `code` of the JSON error returned when a new workspace is rejected.
+pub(crate) const INVALID_WORKSPACE: &str = "invalidWorkspace";
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WorkspaceField {
+    Path,
+    CodexBin,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum FieldErrorReason {
+    Empty,
+    NotFound,
+    NotADirectory,
+    Duplicate,
+    NotExecutable,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FieldError {
+    pub(crate) field: WorkspaceField,
+    pub(crate) reason: FieldErrorReason,
+    pub(crate) message: String,
+    /// The workspace already added for the same directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) existing_workspace_id: Option<String>,
+}
+
+impl FieldError {
+    fn new(field: WorkspaceField, reason: FieldErrorReason, message: String) -> Self {
+        Self {
+            field,
+            reason,
+            message,
+            existing_workspace_id: None,
+        }
+    }
+}
+
+/// A workspace path and `codex_bin` that passed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NormalizedWorkspace {
+    /// The canonical directory, with symlinks and `..` resolved.
+    pub(crate) path: String,
+    pub(crate) name: String,
+    pub(crate) codex_bin: Option<String>,
+}
+
+/// Whether `program` is a file path that exists or a bare name found on
+/// `path_var`.
+pub(crate) fn program_on_path(program: &str, path_var: Option<&OsStr>) -> bool {
+    let program = Path::new(program);
+    if program.components().count() > 1 {
+        return program.is_file();
+    }
+    let Some(path_var) = path_var else {
+        return false;
+    };
+    let extensions: &[&str] = if cfg!(windows) {
+        &["", "exe", "cmd", "bat"]
+    } else {
+        &[""]
+    };
+    std::env::split_paths(path_var).any(|dir| {
+        extensions.iter().any(|extension| {
+            let candidate = dir.join(program);
+            if extension.is_empty() {
+                candidate.is_file()
+            } else {
+                candidate.with_extension(extension).is_file()
+            }
+        })
+    })
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn validate_path(path: &str, existing: &[WorkspaceEntry]) -> Result<PathBuf, FieldError> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err(FieldError::new(
+            WorkspaceField::Path,
+            FieldErrorReason::Empty,
+            "Choose a folder for the workspace.".to_string(),
+        ));
+    }
+    let resolved = std::fs::canonicalize(trimmed).map_err(|_| {
+        FieldError::new(
+            WorkspaceField::Path,
+            FieldErrorReason::NotFound,
+            format!("{trimmed} does not exist."),
+        )
+    })?;
+    if !resolved.is_dir() {
+        return Err(FieldError::new(
+            WorkspaceField::Path,
+            FieldErrorReason::NotADirectory,
+            format!("{trimmed} is not a folder."),
+        ));
+    }
+    if let Some(duplicate) = existing
+        .iter()
+        .find(|entry| canonical(Path::new(&entry.path)) == resolved)
+    {
+        return Err(FieldError {
+            existing_workspace_id: Some(duplicate.id.clone()),
+            ..FieldError::new(
+                WorkspaceField::Path,
+                FieldErrorReason::Duplicate,
+                format!(
+                    "{} is already added as \"{}\".",
+                    resolved.display(),
+                    duplicate.name
+                ),
+            )
+        });
+    }
+    Ok(resolved)
+}
+
+fn validate_codex_bin(
+    codex_bin: Option<&str>,
+    path_var: Option<&OsStr>,
+) -> Result<Option<String>, FieldError> {
+    let Some(codex_bin) = codex_bin.map(str::trim).filter(|bin| !bin.is_empty()) else {
+        return Ok(None);
+    };
+    if !program_on_path(codex_bin, path_var) {
+        return Err(FieldError::new(
+            WorkspaceField::CodexBin,
+            FieldErrorReason::NotFound,
+            format!("Codex binary {codex_bin} was not found."),
+        ));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let is_executable = std::fs::metadata(codex_bin)
+            .map(|metadata| metadata.permissions().mode() & 0o111 != 0)
+            .unwrap_or(true);
+        if !is_executable {
+            return Err(FieldError::new(
+                WorkspaceField::CodexBin,
+                FieldErrorReason::NotExecutable,
+                format!("Codex binary {codex_bin} is not executable."),
+            ));
+        }
+    }
+    Ok(Some(codex_bin.to_string()))
+}
+
+/// Checks a workspace about to be added against the ones already added,
+/// reporting every invalid field at once.
+pub(crate) fn validate_new_workspace(
+    path: &str,
+    codex_bin: Option<&str>,
+    existing: &[WorkspaceEntry],
+    path_var: Option<&OsStr>,
+) -> Result<NormalizedWorkspace, Vec<FieldError>> {
+    let path = validate_path(path, existing);
+    let codex_bin = validate_codex_bin(codex_bin, path_var);
+    match (path, codex_bin) {
+        (Ok(path), Ok(codex_bin)) => Ok(NormalizedWorkspace {
+            name: path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("Workspace")
+                .to_string(),
+            path: path.to_string_lossy().to_string(),
+            codex_bin,
+        }),
+        (path, codex_bin) => Err([path.err(), codex_bin.err()]
+            .into_iter()
+            .flatten()
+            .collect()),
+    }
+}
+
+/// The error string returned to the caller: JSON with `code`, a readable
+/// `message` and the per-field `errors`, so the UI can point at each field.
+pub(crate) fn into_error(errors: Vec<FieldError>) -> String {
+    let message = errors
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    json!({
+        "code": INVALID_WORKSPACE,
+        "message": message,
+        "errors": errors,
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{WorkspaceKind, WorkspaceSettings};
+    use uuid::Uuid;
+
+    fn entry(id: &str, path: &Path) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string_lossy().to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+            icon: None,
+            color: None,
+        }
+    }
+
+    #[test]
+    fn normalizes_paths_and_detects_canonical_duplicates() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let repo = dir.join("repo");
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::write(dir.join("file.txt"), "").unwrap();
+        let dotted = format!("{}/../repo/", repo.display());
+
+        let normalized = validate_new_workspace(&dotted, None, &[], None).unwrap();
+        assert_eq!(normalized.name, "repo");
+        assert_eq!(Path::new(&normalized.path), canonical(&repo));
+
+        let existing = [entry("first", &repo)];
+        let errors = validate_new_workspace(&dotted, Some("  "), &existing, None).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].reason, FieldErrorReason::Duplicate);
+        assert_eq!(errors[0].existing_workspace_id.as_deref(), Some("first"));
+
+        let file = dir.join("file.txt").to_string_lossy().to_string();
+        let errors = validate_new_workspace(&file, Some("/missing/codex"), &[], None).unwrap_err();
+        let reasons: Vec<_> = errors
+            .iter()
+            .map(|error| (error.field, error.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                (WorkspaceField::Path, FieldErrorReason::NotADirectory),
+                (WorkspaceField::CodexBin, FieldErrorReason::NotFound),
+            ]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn structured_error_lists_fields() {
+        let error = into_error(vec![FieldError::new(
+            WorkspaceField::CodexBin,
+            FieldErrorReason::NotFound,
+            "missing".to_string(),
+        )]);
+        let value: serde_json::Value = serde_json::from_str(&error).unwrap();
+        assert_eq!(value["code"], INVALID_WORKSPACE);
+        assert_eq!(value["errors"][0]["field"], "codexBin");
+        assert_eq!(value["errors"][0]["reason"], "notFound");
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+use crate::access_control;
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::background_turn::AnswerCollector;
+use crate::backend::rpc_response::{response_error, response_result};
+use crate::state::AppState;
+
+/// Starts a thread for background turns, which never ask for approval.
+pub(crate) async fn start_thread(session: &WorkspaceSession) -> Result<String, String> {
+    let response = session
+        .send_request(
+            "thread/start",
+            json!({ "cwd": session.entry.path, "approvalPolicy": "never" }),
+        )
+        .await?;
+    if let Some(error) = response_error(&response) {
+        return Err(error);
+    }
+    response_result(&response)
+        .get("thread")
+        .and_then(|thread| thread.get("id"))
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| "thread/start did not return a thread id".to_string())
+}
+
+/// Starts a read-only turn whose events go to `tx` instead of the UI until
+/// `wait_for_answer` returns.
+pub(crate) async fn start_turn(
+    session: &WorkspaceSession,
+    thread_id: &str,
+    prompt: &str,
+    tx: &mpsc::UnboundedSender<Value>,
+) -> Result<(), String> {
+    session
+        .background_thread_callbacks
+        .lock()
+        .await
+        .insert(thread_id.to_string(), tx.clone());
+    let params = json!({
+        "threadId": thread_id,
+        "input": [{ "type": "text", "text": prompt }],
+        "cwd": session.entry.path,
+        "approvalPolicy": "never",
+        "sandboxPolicy": { "type": "readOnly" },
+    });
+    let result = session
+        .send_request("turn/start", params)
+        .await
+        .and_then(|response| match response_error(&response) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        });
+    if result.is_err() {
+        session
+            .background_thread_callbacks
+            .lock()
+            .await
+            .remove(thread_id);
+    }
+    result
+}
+
+/// The final agent message of the turn started with `start_turn`.
+pub(crate) async fn wait_for_answer(
+    session: &WorkspaceSession,
+    thread_id: &str,
+    mut rx: mpsc::UnboundedReceiver<Value>,
+    limit: Duration,
+) -> Result<String, String> {
+    let mut collector = AnswerCollector::default();
+    let answer = timeout(limit, async {
+        while let Some(event) = rx.recv().await {
+            if let Some(answer) = collector.observe(&event) {
+                return answer;
+            }
+        }
+        Err("The app-server closed before answering.".to_string())
+    })
+    .await;
+    session
+        .background_thread_callbacks
+        .lock()
+        .await
+        .remove(thread_id);
+    answer.map_err(|_| "Timed out waiting for an answer.".to_string())?
+}
+
+/// Runs `prompt` in a throwaway thread and archives it afterwards. Locked
+/// workspaces are refused like any other turn.
+pub(crate) async fn run_once(
+    state: &AppState,
+    session: &WorkspaceSession,
+    prompt: &str,
+    limit: Duration,
+) -> Result<String, String> {
+    access_control::ensure_workspace_unlocked(state, &session.entry.id).await?;
+    let thread_id = start_thread(session).await?;
+    let (tx, rx) = mpsc::unbounded_channel::<Value>();
+    let result = match start_turn(session, &thread_id, prompt, &tx).await {
+        Ok(()) => {
+            drop(tx);
+            wait_for_answer(session, &thread_id, rx, limit).await
+        }
+        Err(err) => Err(err),
+    };
+    let _ = session
+        .send_request("thread/archive", json!({ "threadId": thread_id }))
+        .await;
+    result
+}
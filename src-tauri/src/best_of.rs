@@ -0,0 +1,260 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::backend::ab_test::AbVariant;
+use crate::backend::best_of::{self, BestOfAttempt, BestOfCompleted, FinishedBestOf, MAX_ATTEMPTS};
+use crate::backend::dry_run::ProposedPatch;
+use crate::backend::patch_staging::{self, staged_file, StagedPatch, StagedPatchSource};
+use crate::backend::rpc_response::{response_error, response_result};
+use crate::backend::turn_limiter::TurnLane;
+use crate::backend::verification::{self, VerificationOutcome};
+use crate::codex::{self, SendOptions};
+use crate::git_utils::resolve_git_root;
+use crate::remote_backend;
+use crate::state::AppState;
+
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BestOfStarted {
+    pub(crate) run_id: String,
+    pub(crate) attempts: Vec<BestOfAttempt>,
+}
+
+async fn run_git(repo_root: &Path, args: &[&str]) -> Result<(), String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_root)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Applies an attempt's patch to a detached worktree of `HEAD` and runs the
+/// verification command there; the worktree is removed afterwards.
+async fn verify_attempt(
+    repo_root: &Path,
+    workspace_root: &Path,
+    patch: &ProposedPatch,
+    command: &str,
+) -> VerificationOutcome {
+    let scratch = std::env::temp_dir().join(format!("codex-monitor-best-of-{}", Uuid::new_v4()));
+    let worktree = scratch.join("worktree");
+    let worktree_arg = worktree.to_string_lossy().to_string();
+    if let Err(err) = run_git(
+        repo_root,
+        &["worktree", "add", "--detach", &worktree_arg, "HEAD"],
+    )
+    .await
+    {
+        return VerificationOutcome::failed(format!("Failed to create worktree: {err}"));
+    }
+    let cwd = match workspace_root.strip_prefix(repo_root) {
+        Ok(relative) => worktree.join(relative),
+        Err(_) => worktree.clone(),
+    };
+    let staged = StagedPatch {
+        id: patch.turn_id.clone(),
+        workspace_id: patch.workspace_id.clone(),
+        thread_id: patch.thread_id.clone(),
+        turn_id: Some(patch.turn_id.clone()),
+        source: StagedPatchSource::DryRun,
+        created_at: 0,
+        files: patch
+            .files
+            .iter()
+            .filter_map(|file| staged_file(&file.path, file.kind.as_deref(), &file.diff))
+            .collect(),
+    };
+    let apply_root = cwd.clone();
+    let backup_dir = scratch.join("backups");
+    let report = tokio::task::spawn_blocking(move || {
+        patch_staging::apply_to_workspace(&apply_root, &backup_dir, &staged, None)
+    })
+    .await;
+    let outcome = match report {
+        Ok(report) if report.conflicts.is_empty() && report.errors.is_empty() => {
+            verification::run_command(&cwd, command, VERIFY_TIMEOUT).await
+        }
+        Ok(report) => VerificationOutcome::failed(format!(
+            "Patch did not apply cleanly to HEAD ({} conflicts, {} write errors).",
+            report.conflicts.len(),
+            report.errors.len()
+        )),
+        Err(err) => VerificationOutcome::failed(err.to_string()),
+    };
+    if let Err(err) = run_git(repo_root, &["worktree", "remove", "--force", &worktree_arg]).await {
+        eprintln!("Failed to remove best-of worktree: {err}");
+    }
+    let _ = std::fs::remove_dir_all(&scratch);
+    outcome
+}
+
+async fn evaluate(app: AppHandle, finished: FinishedBestOf) {
+    let state = app.state::<AppState>();
+    let roots: Option<(PathBuf, PathBuf)> = match &finished.verify_command {
+        Some(_) if !remote_backend::is_remote_mode(&*state).await => {
+            let workspaces = state.workspaces.lock().await;
+            workspaces.get(&finished.workspace_id).and_then(|entry| {
+                resolve_git_root(entry)
+                    .ok()
+                    .map(|root| (root, PathBuf::from(&entry.path)))
+            })
+        }
+        _ => None,
+    };
+    let mut results = Vec::with_capacity(finished.attempts.len());
+    for (attempt, patch) in &finished.attempts {
+        let verification = match (&finished.verify_command, &roots) {
+            (Some(command), Some((repo_root, workspace_root))) => {
+                Some(verify_attempt(repo_root, workspace_root, patch, command).await)
+            }
+            (Some(_), None) => Some(VerificationOutcome::failed(
+                "Verification needs a local git workspace.",
+            )),
+            (None, _) => None,
+        };
+        results.push(best_of::build_result(attempt.clone(), patch, verification));
+    }
+    best_of::rank_results(&mut results);
+    let _ = app.emit(
+        "best-of-completed",
+        BestOfCompleted {
+            run_id: finished.run_id,
+            workspace_id: finished.workspace_id,
+            prompt: finished.prompt,
+            verify_command: finished.verify_command,
+            results,
+        },
+    );
+}
+
+/// Collects the dry-run patch of a best-of attempt; called by the dry-run
+/// observer when a dry-run turn completes. Once every attempt finished, the
+/// attempts are verified one at a time and ranked in the background.
+pub(crate) fn observe_dry_run(app: &AppHandle, patch: &ProposedPatch) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let finished = match state.best_of_runs.lock() {
+        Ok(mut runs) => runs.observe_patch(patch),
+        Err(_) => None,
+    };
+    if let Some(finished) = finished {
+        tauri::async_runtime::spawn(evaluate(app.clone(), finished));
+    }
+}
+
+async fn start_attempt(
+    workspace_id: &str,
+    index: usize,
+    state: &State<'_, AppState>,
+    app: &AppHandle,
+) -> Result<BestOfAttempt, String> {
+    let response =
+        codex::start_thread(workspace_id.to_string(), state.clone(), app.clone()).await?;
+    if let Some(error) = response_error(&response) {
+        return Err(error);
+    }
+    let thread_id = response_result(&response)
+        .get("thread")
+        .and_then(|thread| thread.get("id"))
+        .and_then(|value| value.as_str())
+        .ok_or("thread/start did not return a thread id")?
+        .to_string();
+    Ok(BestOfAttempt {
+        index,
+        thread_id,
+        turn_id: None,
+    })
+}
+
+/// Runs `prompt` as `n` parallel dry-run turns in fresh threads with the same
+/// model/effort profile. When all have finished, each proposed patch is
+/// applied to a scratch worktree of `HEAD` and checked with `verify_command`
+/// (if given), and a `best-of-completed` event reports the attempts ranked
+/// best first. The patches are staged, so the chosen one can be applied.
+#[tauri::command]
+pub(crate) async fn run_best_of(
+    workspace_id: String,
+    prompt: String,
+    n: usize,
+    profile: Option<AbVariant>,
+    verify_command: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<BestOfStarted, String> {
+    if prompt.trim().is_empty() {
+        return Err("empty user message".to_string());
+    }
+    if !(2..=MAX_ATTEMPTS).contains(&n) {
+        return Err(format!("Best-of runs need 2 to {MAX_ATTEMPTS} attempts."));
+    }
+    let profile = profile.unwrap_or_default();
+    let verify_command = verify_command.filter(|command| !command.trim().is_empty());
+    let run_id = format!("best-{}", Uuid::new_v4().simple());
+    let mut attempts = Vec::with_capacity(n);
+    for index in 0..n {
+        attempts.push(start_attempt(&workspace_id, index, &state, &app).await?);
+    }
+    state
+        .best_of_runs
+        .lock()
+        .map_err(|err| err.to_string())?
+        .register(
+            &run_id,
+            &workspace_id,
+            &prompt,
+            verify_command,
+            attempts.clone(),
+        );
+
+    for attempt in attempts.iter_mut() {
+        let response = codex::send_message(
+            workspace_id.clone(),
+            attempt.thread_id.clone(),
+            prompt.clone(),
+            SendOptions {
+                model: profile.model.clone(),
+                effort: profile.effort.clone(),
+                dry_run: true,
+                lane: TurnLane::Batch,
+                ..SendOptions::default()
+            },
+            state.clone(),
+            app.clone(),
+        )
+        .await
+        .and_then(|response| match response_error(&response) {
+            Some(error) => Err(error),
+            None => Ok(response),
+        });
+        match response {
+            Ok(response) => {
+                attempt.turn_id = response_result(&response)
+                    .get("turn")
+                    .and_then(|turn| turn.get("id"))
+                    .and_then(|value| value.as_str())
+                    .map(|value| value.to_string());
+            }
+            Err(error) => {
+                if let Ok(mut runs) = state.best_of_runs.lock() {
+                    runs.cancel(&run_id);
+                }
+                return Err(error);
+            }
+        }
+    }
+    Ok(BestOfStarted { run_id, attempts })
+}
@@ -21,20 +21,34 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use ignore::WalkBuilder;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::process::Command;
 use tokio::sync::{broadcast, mpsc, Mutex};
 use uuid::Uuid;
 
 use backend::app_server::{spawn_workspace_session, WorkspaceSession};
-use backend::events::{AppServerEvent, EventSink, TerminalOutput};
+use backend::approval_audit::{self, ApprovalAuditAction, ApprovalAuditEntry};
+use backend::approval_inbox::{self, ApprovalInbox, InboxRoute};
+use backend::events::{
+    AppServerEvent, EventSink, NarrationEvent, SessionOutputLine, TerminalOutput,
+};
+use backend::local_http;
+use backend::secure_storage::{self, CipherCache, DataCipher};
+use backend::protocol_compat;
+use backend::quick_search;
+use backend::request_id::RequestId;
+use backend::thread_archive;
+use backend::turn_compare::{self, TurnRef};
+use backend::workspace_lock::{self, LockOutcome};
+use backend::workspace_validation;
 use storage::{read_settings, read_workspaces, write_settings, write_workspaces};
 use types::{
     AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
 };
 
 const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:4732";
+const APPROVALS_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
 #[derive(Clone)]
 struct DaemonEventSink {
@@ -46,6 +60,8 @@ enum DaemonEvent {
     AppServer(AppServerEvent),
     #[allow(dead_code)]
     TerminalOutput(TerminalOutput),
+    Narration(NarrationEvent),
+    SessionOutput(SessionOutputLine),
 }
 
 impl EventSink for DaemonEventSink {
@@ -56,12 +72,26 @@ impl EventSink for DaemonEventSink {
     fn emit_terminal_output(&self, event: TerminalOutput) {
         let _ = self.tx.send(DaemonEvent::TerminalOutput(event));
     }
+
+    fn emit_narration(&self, event: NarrationEvent) {
+        let _ = self.tx.send(DaemonEvent::Narration(event));
+    }
+
+    fn emit_session_output(&self, event: SessionOutputLine) {
+        let _ = self.tx.send(DaemonEvent::SessionOutput(event));
+    }
 }
 
 struct DaemonConfig {
     listen: SocketAddr,
     token: Option<String>,
     data_dir: PathBuf,
+    /// HTTP listener limited to the approval inbox, for ChatOps bots.
+    approvals_listen: Option<SocketAddr>,
+    approvals_token: Option<String>,
+    /// Unix socket path, or named pipe name on Windows, serving the same
+    /// JSON-RPC protocol as `listen` to local editor integrations.
+    socket: Option<String>,
 }
 
 struct DaemonState {
@@ -72,6 +102,10 @@ struct DaemonState {
     settings_path: PathBuf,
     app_settings: Mutex<AppSettings>,
     event_sink: DaemonEventSink,
+    approval_inbox: std::sync::Mutex<ApprovalInbox>,
+    ciphers: CipherCache,
+    /// Serializes approval audit log appends, which chain on the last entry.
+    audit_writes: Mutex<()>,
 }
 
 impl DaemonState {
@@ -80,6 +114,9 @@ impl DaemonState {
         let settings_path = config.data_dir.join("settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
         let app_settings = read_settings(&settings_path).unwrap_or_default();
+        backend::i18n::set_locale(app_settings.locale.as_deref());
+        backend::command_guard::set_blocklist(&app_settings.command_blocklist);
+        backend::network::set_network_settings(&app_settings.network);
         Self {
             data_dir: config.data_dir.clone(),
             workspaces: Mutex::new(workspaces),
@@ -88,6 +125,9 @@ impl DaemonState {
             settings_path,
             app_settings: Mutex::new(app_settings),
             event_sink,
+            approval_inbox: std::sync::Mutex::new(ApprovalInbox::default()),
+            ciphers: CipherCache::default(),
+            audit_writes: Mutex::new(()),
         }
     }
 
@@ -111,6 +151,7 @@ impl DaemonState {
         let mut result = Vec::new();
         for entry in workspaces.values() {
             result.push(WorkspaceInfo {
+                identity: entry.identity(),
                 id: entry.id.clone(),
                 name: entry.name.clone(),
                 path: entry.path.clone(),
@@ -132,21 +173,27 @@ impl DaemonState {
         codex_bin: Option<String>,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
-        let name = PathBuf::from(&path)
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or("Workspace")
-            .to_string();
+        let existing: Vec<WorkspaceEntry> =
+            self.workspaces.lock().await.values().cloned().collect();
+        let normalized = workspace_validation::validate_new_workspace(
+            &path,
+            codex_bin.as_deref(),
+            &existing,
+            env::var_os("PATH").as_deref(),
+        )
+        .map_err(workspace_validation::into_error)?;
 
         let entry = WorkspaceEntry {
             id: Uuid::new_v4().to_string(),
-            name: name.clone(),
-            path: path.clone(),
-            codex_bin,
+            name: normalized.name,
+            path: normalized.path,
+            codex_bin: normalized.codex_bin,
             kind: WorkspaceKind::Main,
             parent_id: None,
             worktree: None,
             settings: WorkspaceSettings::default(),
+            icon: None,
+            color: None,
         };
 
         let default_bin = {
@@ -174,6 +221,7 @@ impl DaemonState {
         self.sessions.lock().await.insert(entry.id.clone(), session);
 
         Ok(WorkspaceInfo {
+            identity: entry.identity(),
             id: entry.id,
             name: entry.name,
             path: entry.path,
@@ -250,6 +298,8 @@ impl DaemonState {
                 branch: branch.to_string(),
             }),
             settings: WorkspaceSettings::default(),
+            icon: None,
+            color: None,
         };
 
         let default_bin = {
@@ -277,6 +327,7 @@ impl DaemonState {
         self.sessions.lock().await.insert(entry.id.clone(), session);
 
         Ok(WorkspaceInfo {
+            identity: entry.identity(),
             id: entry.id,
             name: entry.name,
             path: entry.path,
@@ -548,6 +599,7 @@ impl DaemonState {
 
         let connected = self.sessions.lock().await.contains_key(&entry_snapshot.id);
         Ok(WorkspaceInfo {
+            identity: entry_snapshot.identity(),
             id: entry_snapshot.id,
             name: entry_snapshot.name,
             path: entry_snapshot.path,
@@ -648,6 +700,9 @@ impl DaemonState {
         id: String,
         settings: WorkspaceSettings,
     ) -> Result<WorkspaceInfo, String> {
+        if let Some(limits) = &settings.resource_limits {
+            backend::resource_limits::validate(limits)?;
+        }
         let (entry_snapshot, list) = {
             let mut workspaces = self.workspaces.lock().await;
             let entry_snapshot = match workspaces.get_mut(&id) {
@@ -664,6 +719,7 @@ impl DaemonState {
 
         let connected = self.sessions.lock().await.contains_key(&id);
         Ok(WorkspaceInfo {
+            identity: entry_snapshot.identity(),
             id: entry_snapshot.id,
             name: entry_snapshot.name,
             path: entry_snapshot.path,
@@ -697,6 +753,7 @@ impl DaemonState {
 
         let connected = self.sessions.lock().await.contains_key(&id);
         Ok(WorkspaceInfo {
+            identity: entry_snapshot.identity(),
             id: entry_snapshot.id,
             name: entry_snapshot.name,
             path: entry_snapshot.path,
@@ -758,12 +815,25 @@ impl DaemonState {
         let _ = codex_config::write_collab_enabled(settings.experimental_collab_enabled);
         let _ = codex_config::write_steer_enabled(settings.experimental_steer_enabled);
         let _ = codex_config::write_unified_exec_enabled(settings.experimental_unified_exec_enabled);
-        write_settings(&self.settings_path, &settings)?;
         let mut current = self.app_settings.lock().await;
+        secure_storage::apply_encryption_setting(
+            &self.data_dir,
+            &self.ciphers,
+            current.encrypt_local_data,
+            settings.encrypt_local_data,
+        )?;
+        write_settings(&self.settings_path, &settings)?;
+        backend::command_guard::set_blocklist(&settings.command_blocklist);
+        backend::network::set_network_settings(&settings.network);
         *current = settings.clone();
         Ok(settings)
     }
 
+    async fn data_cipher(&self) -> Result<DataCipher, String> {
+        let encrypt_local_data = self.app_settings.lock().await.encrypt_local_data;
+        self.ciphers.cipher(encrypt_local_data)
+    }
+
     async fn get_session(&self, workspace_id: &str) -> Result<Arc<WorkspaceSession>, String> {
         let sessions = self.sessions.lock().await;
         sessions
@@ -772,6 +842,24 @@ impl DaemonState {
             .ok_or("workspace not connected".to_string())
     }
 
+    async fn workspace_lock(&self, workspace_id: String, force: bool) -> Result<Value, String> {
+        let entry = {
+            let workspaces = self.workspaces.lock().await;
+            workspaces
+                .get(&workspace_id)
+                .cloned()
+                .ok_or("workspace not found")?
+        };
+        let path = PathBuf::from(entry.path);
+        if force {
+            let now = chrono::Utc::now().timestamp_millis();
+            workspace_lock::acquire(&path, now, true)?;
+            return Ok(Value::Null);
+        }
+        serde_json::to_value(workspace_lock::read_lock(&workspace_lock::lock_path(&path)))
+            .map_err(|err| err.to_string())
+    }
+
     async fn list_workspace_files(&self, workspace_id: String) -> Result<Vec<String>, String> {
         let entry = {
             let workspaces = self.workspaces.lock().await;
@@ -802,6 +890,15 @@ impl DaemonState {
         session.send_request("thread/resume", params).await
     }
 
+    async fn compare_turns(&self, turn_a: TurnRef, turn_b: TurnRef) -> Result<Value, String> {
+        let session_a = self.get_session(&turn_a.workspace_id).await?;
+        let session_b = self.get_session(&turn_b.workspace_id).await?;
+        let a = turn_compare::load_turn(&session_a, &turn_a).await?;
+        let b = turn_compare::load_turn(&session_b, &turn_b).await?;
+        serde_json::to_value(turn_compare::compare(turn_a, &a, turn_b, &b))
+            .map_err(|err| err.to_string())
+    }
+
     async fn list_threads(
         &self,
         workspace_id: String,
@@ -816,10 +913,73 @@ impl DaemonState {
         session.send_request("thread/list", params).await
     }
 
+    async fn quick_search(&self, query: String, limit: Option<u32>) -> Result<Value, String> {
+        let workspaces: Vec<WorkspaceEntry> =
+            self.workspaces.lock().await.values().cloned().collect();
+        let sessions = self.sessions.lock().await.clone();
+        let mut candidates: Vec<_> = workspaces
+            .iter()
+            .map(quick_search::workspace_candidate)
+            .collect();
+        for entry in &workspaces {
+            if let Some(session) = sessions.get(&entry.id) {
+                candidates.extend(quick_search::thread_candidates(session, entry).await);
+            }
+        }
+        let limit = limit
+            .map(|value| value as usize)
+            .unwrap_or(quick_search::DEFAULT_RESULT_LIMIT);
+        serde_json::to_value(quick_search::rank(&query, candidates, limit))
+            .map_err(|err| err.to_string())
+    }
+
+    async fn protocol_compat_report(&self) -> Result<Value, String> {
+        let sessions = self.sessions.lock().await;
+        let report = protocol_compat::build_report(
+            sessions
+                .values()
+                .map(|session| session.protocol_report())
+                .collect(),
+        );
+        serde_json::to_value(report).map_err(|err| err.to_string())
+    }
+
     async fn archive_thread(&self, workspace_id: String, thread_id: String) -> Result<Value, String> {
         let session = self.get_session(&workspace_id).await?;
-        let params = json!({ "threadId": thread_id });
-        session.send_request("thread/archive", params).await
+        let retention_days = self.app_settings.lock().await.archive_retention_days;
+        let cipher = self.data_cipher().await?;
+        thread_archive::archive_thread_with_tombstone(
+            &session,
+            &thread_archive::archive_path(&self.data_dir),
+            &cipher,
+            &workspace_id,
+            &thread_id,
+            retention_days,
+        )
+        .await
+    }
+
+    async fn unarchive_thread(&self, workspace_id: String, thread_id: String) -> Result<Value, String> {
+        let session = self.get_session(&workspace_id).await?;
+        let cipher = self.data_cipher().await?;
+        thread_archive::unarchive_thread(
+            &session,
+            &thread_archive::archive_path(&self.data_dir),
+            &cipher,
+            &workspace_id,
+            &thread_id,
+        )
+        .await
+    }
+
+    async fn list_recently_archived(&self, workspace_id: Option<String>) -> Result<Value, String> {
+        let cipher = self.data_cipher().await?;
+        let entries = thread_archive::list_recently_archived(
+            &thread_archive::archive_path(&self.data_dir),
+            &cipher,
+            workspace_id.as_deref(),
+        )?;
+        serde_json::to_value(entries).map_err(|err| err.to_string())
     }
 
     async fn send_user_message(
@@ -832,9 +992,21 @@ impl DaemonState {
         access_mode: Option<String>,
         images: Option<Vec<String>>,
         collaboration_mode: Option<Value>,
+        dry_run: bool,
     ) -> Result<Value, String> {
         let session = self.get_session(&workspace_id).await?;
-        let access_mode = access_mode.unwrap_or_else(|| "current".to_string());
+        let access_mode = if dry_run {
+            backend::dry_run::DRY_RUN_ACCESS_MODE.to_string()
+        } else {
+            access_mode.unwrap_or_else(|| "current".to_string())
+        };
+        if !dry_run && access_mode != "read-only" {
+            let now = chrono::Utc::now().timestamp_millis();
+            let path = PathBuf::from(&session.entry.path);
+            if let LockOutcome::HeldByOther(lock) = workspace_lock::acquire(&path, now, false)? {
+                return Err(workspace_lock::conflict_message(&lock));
+            }
+        }
         let sandbox_policy = match access_mode.as_str() {
             "full-access" => json!({
                 "type": "dangerFullAccess"
@@ -849,7 +1021,9 @@ impl DaemonState {
             }),
         };
 
-        let approval_policy = if access_mode == "full-access" {
+        let approval_policy = if dry_run {
+            backend::dry_run::DRY_RUN_APPROVAL_POLICY
+        } else if access_mode == "full-access" {
             "never"
         } else {
             "on-request"
@@ -857,6 +1031,9 @@ impl DaemonState {
 
         let trimmed_text = text.trim();
         let mut input: Vec<Value> = Vec::new();
+        if dry_run {
+            input.push(backend::dry_run::dry_run_note());
+        }
         if !trimmed_text.is_empty() {
             input.push(json!({ "type": "text", "text": trimmed_text }));
         }
@@ -956,14 +1133,71 @@ impl DaemonState {
     async fn respond_to_server_request(
         &self,
         workspace_id: String,
-        request_id: u64,
+        request_id: RequestId,
         result: Value,
     ) -> Result<Value, String> {
-        let session = self.get_session(&workspace_id).await?;
-        session.send_response(request_id, result).await?;
+        self.answer_server_request(
+            workspace_id,
+            request_id,
+            result,
+            ApprovalAuditAction::AnsweredLocally,
+            "local",
+        )
+        .await?;
         Ok(json!({ "ok": true }))
     }
 
+    /// Sends the answer and, when it settles a pending approval, records it
+    /// in the approval audit log as the desktop app does.
+    async fn answer_server_request(
+        &self,
+        workspace_id: String,
+        request_id: RequestId,
+        result: Value,
+        action: ApprovalAuditAction,
+        actor: &str,
+    ) -> Result<(), String> {
+        let session = self.get_session(&workspace_id).await?;
+        session.send_response(&request_id, result.clone()).await?;
+        let approval = self.approval_inbox.lock().ok().and_then(|mut inbox| {
+            let approval = inbox
+                .get(&approval_inbox::approval_id(&workspace_id, &request_id))
+                .cloned();
+            inbox.resolve(&workspace_id, &request_id);
+            approval
+        });
+        let Some(approval) = approval else {
+            return Ok(());
+        };
+        self.record_approval(ApprovalAuditEntry {
+            recorded_at: chrono::Utc::now().timestamp_millis(),
+            workspace_id,
+            request_id: Some(request_id),
+            delegation_id: None,
+            action,
+            actor: actor.to_string(),
+            method: Some(approval.method),
+            decision: Some(result),
+            detail: approval.command,
+        })
+        .await;
+        Ok(())
+    }
+
+    /// Appends to the approval audit log, logging rather than failing on errors.
+    async fn record_approval(&self, entry: ApprovalAuditEntry) {
+        let result = match self.data_cipher().await {
+            Ok(cipher) => {
+                let _guard = self.audit_writes.lock().await;
+                approval_audit::append_entry(&self.data_dir, &cipher, entry)
+            }
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to write approval audit log: {err}");
+        }
+    }
+
     async fn remember_approval_rule(
         &self,
         workspace_id: String,
@@ -1322,8 +1556,8 @@ fn default_data_dir() -> PathBuf {
 fn usage() -> String {
     format!(
         "\
-USAGE:\n  codex-monitor-daemon [--listen <addr>] [--data-dir <path>] [--token <token> | --insecure-no-auth]\n\n\
-OPTIONS:\n  --listen <addr>        Bind address (default: {DEFAULT_LISTEN_ADDR})\n  --data-dir <path>      Data dir holding workspaces.json/settings.json\n  --token <token>        Shared token required by clients\n  --insecure-no-auth      Disable auth (dev only)\n  -h, --help             Show this help\n"
+USAGE:\n  codex-monitor-daemon [--listen <addr>] [--data-dir <path>] [--token <token> | --insecure-no-auth]\n                       [--approvals-listen <addr> --approvals-token <token>] [--socket <path>]\n\n\
+OPTIONS:\n  --listen <addr>        Bind address (default: {DEFAULT_LISTEN_ADDR})\n  --data-dir <path>      Data dir holding workspaces.json/settings.json\n  --token <token>        Shared token required by clients\n  --insecure-no-auth      Disable auth (dev only)\n  --approvals-listen <addr>\n                         Serve GET /approvals and POST /approvals/{{id}}/respond over HTTP\n  --approvals-token <token>\n                         Bearer token for the approvals endpoint; must differ from --token\n  --socket <path>        Also serve the JSON-RPC protocol on a Unix socket, or on a named\n                         pipe such as \\\\.\\pipe\\codex-monitor on Windows\n  -h, --help             Show this help\n"
     )
 }
 
@@ -1337,6 +1571,12 @@ fn parse_args() -> Result<DaemonConfig, String> {
         .filter(|value| !value.is_empty());
     let mut insecure_no_auth = false;
     let mut data_dir: Option<PathBuf> = None;
+    let mut approvals_listen: Option<SocketAddr> = None;
+    let mut approvals_token = env::var("CODEX_MONITOR_APPROVALS_TOKEN")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let mut socket: Option<String> = None;
 
     let mut args = env::args().skip(1);
     while let Some(arg) = args.next() {
@@ -1369,6 +1609,27 @@ fn parse_args() -> Result<DaemonConfig, String> {
                 insecure_no_auth = true;
                 token = None;
             }
+            "--approvals-listen" => {
+                let value = args.next().ok_or("--approvals-listen requires a value")?;
+                approvals_listen =
+                    Some(value.parse::<SocketAddr>().map_err(|err| err.to_string())?);
+            }
+            "--approvals-token" => {
+                let value = args.next().ok_or("--approvals-token requires a value")?;
+                let trimmed = value.trim();
+                if trimmed.is_empty() {
+                    return Err("--approvals-token requires a non-empty value".to_string());
+                }
+                approvals_token = Some(trimmed.to_string());
+            }
+            "--socket" => {
+                let value = args.next().ok_or("--socket requires a value")?;
+                let trimmed = value.trim();
+                if trimmed.is_empty() {
+                    return Err("--socket requires a non-empty value".to_string());
+                }
+                socket = Some(trimmed.to_string());
+            }
             _ => return Err(format!("Unknown argument: {arg}")),
         }
     }
@@ -1380,10 +1641,25 @@ fn parse_args() -> Result<DaemonConfig, String> {
         );
     }
 
+    if approvals_listen.is_some() {
+        let Some(approvals) = approvals_token.as_deref() else {
+            return Err(
+                "--approvals-listen requires --approvals-token (or set CODEX_MONITOR_APPROVALS_TOKEN)."
+                    .to_string(),
+            );
+        };
+        if token.as_deref() == Some(approvals) {
+            return Err("--approvals-token must differ from --token.".to_string());
+        }
+    }
+
     Ok(DaemonConfig {
         listen,
         token,
         data_dir: data_dir.unwrap_or_else(default_data_dir),
+        approvals_listen,
+        approvals_token,
+        socket,
     })
 }
 
@@ -1415,6 +1691,14 @@ fn build_event_notification(event: DaemonEvent) -> Option<String> {
             "method": "terminal-output",
             "params": payload,
         }),
+        DaemonEvent::Narration(payload) => json!({
+            "method": "narration-event",
+            "params": payload,
+        }),
+        DaemonEvent::SessionOutput(payload) => json!({
+            "method": "session-output",
+            "params": payload,
+        }),
     };
     serde_json::to_string(&payload).ok()
 }
@@ -1451,6 +1735,14 @@ fn parse_optional_string(value: &Value, key: &str) -> Option<String> {
     }
 }
 
+fn parse_turn_ref(value: &Value, key: &str) -> Result<TurnRef, String> {
+    let raw = value
+        .get(key)
+        .cloned()
+        .ok_or_else(|| format!("missing `{key}`"))?;
+    serde_json::from_value(raw).map_err(|_| format!("missing or invalid `{key}`"))
+}
+
 fn parse_optional_u32(value: &Value, key: &str) -> Option<u32> {
     match value {
         Value::Object(map) => map.get(key).and_then(|value| value.as_u64()).and_then(|v| {
@@ -1464,6 +1756,13 @@ fn parse_optional_u32(value: &Value, key: &str) -> Option<u32> {
     }
 }
 
+fn parse_optional_bool(value: &Value, key: &str) -> Option<bool> {
+    match value {
+        Value::Object(map) => map.get(key).and_then(|value| value.as_bool()),
+        _ => None,
+    }
+}
+
 fn parse_optional_string_array(value: &Value, key: &str) -> Option<Vec<String>> {
     match value {
         Value::Object(map) => map.get(key).and_then(|value| value.as_array()).map(|items| {
@@ -1518,6 +1817,12 @@ async fn handle_rpc_request(
             state.connect_workspace(id, client_version).await?;
             Ok(json!({ "ok": true }))
         }
+        "reload_session" => {
+            let id = parse_string(&params, "id")?;
+            state.kill_session(&id).await;
+            state.connect_workspace(id, client_version).await?;
+            Ok(json!({ "ok": true }))
+        }
         "remove_workspace" => {
             let id = parse_string(&params, "id")?;
             state.remove_workspace(id).await?;
@@ -1603,11 +1908,50 @@ async fn handle_rpc_request(
             let limit = parse_optional_u32(&params, "limit");
             state.list_threads(workspace_id, cursor, limit).await
         }
+        "compare_turns" => {
+            let turn_a = parse_turn_ref(&params, "turnA")?;
+            let turn_b = parse_turn_ref(&params, "turnB")?;
+            state.compare_turns(turn_a, turn_b).await
+        }
+        "quick_search" => {
+            let query = parse_optional_string(&params, "query").unwrap_or_default();
+            let limit = parse_optional_u32(&params, "limit");
+            state.quick_search(query, limit).await
+        }
+        "get_protocol_compat_report" => state.protocol_compat_report().await,
+        "get_session_environment" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let session = state.get_session(&workspace_id).await?;
+            serde_json::to_value(&session.environment).map_err(|err| err.to_string())
+        }
+        "tail_session_output" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let lines = parse_optional_u32(&params, "lines");
+            let session = state.get_session(&workspace_id).await?;
+            let tail = session.tail_output(lines);
+            serde_json::to_value(tail).map_err(|err| err.to_string())
+        }
+        "set_session_output_live" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let enabled = parse_optional_bool(&params, "enabled").unwrap_or(false);
+            let session = state.get_session(&workspace_id).await?;
+            session.set_output_live(enabled);
+            Ok(Value::Null)
+        }
         "archive_thread" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let thread_id = parse_string(&params, "threadId")?;
             state.archive_thread(workspace_id, thread_id).await
         }
+        "unarchive_thread" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            state.unarchive_thread(workspace_id, thread_id).await
+        }
+        "list_recently_archived" => {
+            let workspace_id = parse_optional_string(&params, "workspaceId");
+            state.list_recently_archived(workspace_id).await
+        }
         "send_user_message" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let thread_id = parse_string(&params, "threadId")?;
@@ -1617,6 +1961,7 @@ async fn handle_rpc_request(
             let access_mode = parse_optional_string(&params, "accessMode");
             let images = parse_optional_string_array(&params, "images");
             let collaboration_mode = parse_optional_value(&params, "collaborationMode");
+            let dry_run = parse_optional_bool(&params, "dryRun").unwrap_or(false);
             state
                 .send_user_message(
                     workspace_id,
@@ -1627,9 +1972,18 @@ async fn handle_rpc_request(
                     access_mode,
                     images,
                     collaboration_mode,
+                    dry_run,
                 )
                 .await
         }
+        "get_workspace_lock" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            state.workspace_lock(workspace_id, false).await
+        }
+        "force_workspace_lock" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            state.workspace_lock(workspace_id, true).await
+        }
         "turn_interrupt" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let thread_id = parse_string(&params, "threadId")?;
@@ -1668,7 +2022,7 @@ async fn handle_rpc_request(
             let map = params.as_object().ok_or("missing requestId")?;
             let request_id = map
                 .get("requestId")
-                .and_then(|value| value.as_u64())
+                .and_then(RequestId::from_value)
                 .ok_or("missing requestId")?;
             let result = map.get("result").cloned().ok_or("missing `result`")?;
             state
@@ -1705,13 +2059,15 @@ async fn forward_events(
     }
 }
 
-async fn handle_client(
-    socket: TcpStream,
+async fn handle_client<S>(
+    socket: S,
     config: Arc<DaemonConfig>,
     state: Arc<DaemonState>,
     events: broadcast::Sender<DaemonEvent>,
-) {
-    let (reader, mut writer) = socket.into_split();
+) where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (reader, mut writer) = tokio::io::split(socket);
     let mut lines = BufReader::new(reader).lines();
 
     let (out_tx, mut out_rx) = mpsc::unbounded_channel::<String>();
@@ -1801,6 +2157,183 @@ async fn handle_client(
     write_task.abort();
 }
 
+/// Serves local clients on a Unix socket readable only by the current user.
+#[cfg(unix)]
+async fn serve_socket(
+    path: String,
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+    events: broadcast::Sender<DaemonEvent>,
+) {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    // A socket left behind by a previous run would make the bind fail; never
+    // remove anything else.
+    if std::fs::symlink_metadata(&path).is_ok_and(|meta| meta.file_type().is_socket()) {
+        let _ = std::fs::remove_file(&path);
+    }
+    // Bind under a restrictive umask so the socket is never reachable by
+    // other users, not even before its permissions are set below.
+    // SAFETY: umask only swaps the process file mode mask.
+    let previous_umask = unsafe { libc::umask(0o077) };
+    let bound = tokio::net::UnixListener::bind(&path);
+    // SAFETY: as above; restores the mask read before binding.
+    unsafe { libc::umask(previous_umask) };
+    let listener = match bound {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("failed to bind socket {path}: {err}");
+            return;
+        }
+    };
+    if let Err(err) = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)) {
+        eprintln!("failed to restrict socket {path}: {err}");
+        return;
+    }
+    eprintln!("codex-monitor-daemon listening on socket {path}");
+    loop {
+        let Ok((socket, _addr)) = listener.accept().await else {
+            continue;
+        };
+        tokio::spawn(handle_client(
+            socket,
+            Arc::clone(&config),
+            Arc::clone(&state),
+            events.clone(),
+        ));
+    }
+}
+
+/// Serves local clients on a named pipe, creating a new pipe instance for
+/// each connection.
+#[cfg(windows)]
+async fn serve_socket(
+    path: String,
+    config: Arc<DaemonConfig>,
+    state: Arc<DaemonState>,
+    events: broadcast::Sender<DaemonEvent>,
+) {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let mut server = match ServerOptions::new().first_pipe_instance(true).create(&path) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("failed to create pipe {path}: {err}");
+            return;
+        }
+    };
+    eprintln!("codex-monitor-daemon listening on pipe {path}");
+    loop {
+        if server.connect().await.is_err() {
+            continue;
+        }
+        let client = server;
+        server = match ServerOptions::new().create(&path) {
+            Ok(server) => server,
+            Err(err) => {
+                eprintln!("failed to create pipe {path}: {err}");
+                return;
+            }
+        };
+        tokio::spawn(handle_client(
+            client,
+            Arc::clone(&config),
+            Arc::clone(&state),
+            events.clone(),
+        ));
+    }
+}
+
+/// Keeps the approval inbox in sync with the app-server events of every
+/// workspace.
+async fn track_approvals(state: Arc<DaemonState>, mut rx: broadcast::Receiver<DaemonEvent>) {
+    loop {
+        let event = match rx.recv().await {
+            Ok(DaemonEvent::AppServer(event)) => event,
+            Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        if let Ok(mut inbox) = state.approval_inbox.lock() {
+            inbox.observe(
+                &event.workspace_id,
+                &event.message,
+                chrono::Utc::now().timestamp_millis(),
+            );
+        }
+    }
+}
+
+async fn respond_to_inbox_approval(
+    state: &DaemonState,
+    id: &str,
+    body: &[u8],
+) -> Result<Value, (u16, String)> {
+    let result = approval_inbox::decision_result(body).map_err(|err| (400, err))?;
+    let approval = state
+        .approval_inbox
+        .lock()
+        .ok()
+        .and_then(|inbox| inbox.get(id).cloned())
+        .ok_or((404, "Approval not found or already answered.".to_string()))?;
+    state
+        .answer_server_request(
+            approval.workspace_id,
+            approval.request_id,
+            result.clone(),
+            ApprovalAuditAction::AnsweredFromInbox,
+            "inbox",
+        )
+        .await
+        .map_err(|err| (400, err))?;
+    Ok(json!({ "status": "answered", "id": id, "result": result }))
+}
+
+/// Serves the approval inbox: `GET /approvals` and
+/// `POST /approvals/{id}/respond`. Nothing else of the daemon is reachable
+/// with the approvals token.
+async fn handle_approvals_client(
+    mut socket: TcpStream,
+    token: Arc<String>,
+    state: Arc<DaemonState>,
+) {
+    let request = match tokio::time::timeout(
+        APPROVALS_READ_TIMEOUT,
+        local_http::read_request(&mut socket),
+    )
+    .await
+    {
+        Ok(Ok(request)) => request,
+        Ok(Err((status, message))) => {
+            local_http::respond(&mut socket, status, json!({ "error": message })).await;
+            return;
+        }
+        Err(_) => return,
+    };
+    if !approval_inbox::authorized(&request, &token) {
+        local_http::respond(&mut socket, 401, json!({ "error": "Invalid token" })).await;
+        return;
+    }
+    let (status, body) = match approval_inbox::route(&request.method, &request.path) {
+        InboxRoute::List => {
+            let approvals = state
+                .approval_inbox
+                .lock()
+                .map(|inbox| inbox.list())
+                .unwrap_or_default();
+            (200, json!({ "approvals": approvals }))
+        }
+        InboxRoute::Respond(id) => {
+            match respond_to_inbox_approval(&state, &id, &request.body).await {
+                Ok(body) => (200, body),
+                Err((status, err)) => (status, json!({ "error": err })),
+            }
+        }
+        InboxRoute::MethodNotAllowed => (405, json!({ "error": "Method not allowed" })),
+        InboxRoute::NotFound => (404, json!({ "error": "Not found" })),
+    };
+    local_http::respond(&mut socket, status, body).await;
+}
+
 fn main() {
     let config = match parse_args() {
         Ok(config) => config,
@@ -1822,6 +2355,38 @@ fn main() {
         };
         let state = Arc::new(DaemonState::load(&config, event_sink));
         let config = Arc::new(config);
+        tokio::spawn(track_approvals(Arc::clone(&state), events_tx.subscribe()));
+
+        if let (Some(addr), Some(token)) = (config.approvals_listen, config.approvals_token.clone())
+        {
+            let approvals = TcpListener::bind(addr)
+                .await
+                .unwrap_or_else(|err| panic!("failed to bind {addr}: {err}"));
+            eprintln!("codex-monitor-daemon serving the approval inbox on http://{addr}");
+            let token = Arc::new(token);
+            let state = Arc::clone(&state);
+            tokio::spawn(async move {
+                loop {
+                    let Ok((socket, _addr)) = approvals.accept().await else {
+                        continue;
+                    };
+                    tokio::spawn(handle_approvals_client(
+                        socket,
+                        Arc::clone(&token),
+                        Arc::clone(&state),
+                    ));
+                }
+            });
+        }
+
+        if let Some(path) = config.socket.clone() {
+            tokio::spawn(serve_socket(
+                path,
+                Arc::clone(&config),
+                Arc::clone(&state),
+                events_tx.clone(),
+            ));
+        }
 
         let listener = TcpListener::bind(config.listen)
             .await
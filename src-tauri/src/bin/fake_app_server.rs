@@ -0,0 +1,6 @@
+fn main() {
+    if !codex_monitor_lib::serve_fake_app_server() {
+        eprintln!("usage: fake-app-server --fake-app-server <script-json>");
+        std::process::exit(2);
+    }
+}
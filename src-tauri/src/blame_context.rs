@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use git2::Repository;
+use tauri::State;
+
+use crate::backend::blame_context::{
+    self, BlameContext, BlameFingerprint, CommitInfo, FileBlame, LineRange,
+};
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::patch_staging::resolve_path;
+use crate::git_utils::resolve_git_root;
+use crate::state::AppState;
+use crate::utils::normalize_git_path;
+
+fn head_sha(repo_root: &Path) -> Option<String> {
+    let repo = Repository::open(repo_root).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
+
+/// Blames the working copy `content` of `relative`, so lines changed since
+/// `HEAD` show up as uncommitted.
+fn blame_file(repo_root: &Path, relative: &str, content: &[u8]) -> Result<FileBlame, String> {
+    let repo = Repository::open(repo_root).map_err(|err| err.to_string())?;
+    let blame_error = |err: git2::Error| format!("Failed to blame {relative}: {}", err.message());
+    let committed = repo
+        .blame_file(Path::new(relative), None)
+        .map_err(blame_error)?;
+    let blame = committed.blame_buffer(content).map_err(blame_error)?;
+    let line_count = String::from_utf8_lossy(content).lines().count();
+    let mut lines = Vec::with_capacity(line_count);
+    let mut commits = HashMap::new();
+    for line in 1..=line_count {
+        let oid = blame
+            .get_line(line)
+            .map(|hunk| hunk.final_commit_id())
+            .filter(|oid| !oid.is_zero());
+        let Some(oid) = oid else {
+            lines.push(None);
+            continue;
+        };
+        let sha = oid.to_string();
+        if !commits.contains_key(&sha) {
+            let commit = repo.find_commit(oid).map_err(|err| err.to_string())?;
+            commits.insert(
+                sha.clone(),
+                CommitInfo {
+                    sha: sha.clone(),
+                    author: commit.author().name().unwrap_or("").to_string(),
+                    timestamp: commit.time().seconds(),
+                    message: commit.message().unwrap_or("").to_string(),
+                },
+            );
+        }
+        lines.push(Some(sha));
+    }
+    Ok(FileBlame { lines, commits })
+}
+
+/// Authors and commit messages behind lines `line_range` of a workspace
+/// file, plus the same history as text for prompts. Blames are cached per
+/// workspace until `HEAD` or the file changes.
+#[tauri::command]
+pub(crate) async fn get_blame_context(
+    workspace_id: String,
+    path: String,
+    line_range: LineRange,
+    state: State<'_, AppState>,
+) -> Result<BlameContext, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?
+    };
+    let full_path = resolve_path(&PathBuf::from(&entry.path), &path)?;
+    let repo_root = resolve_git_root(&entry)?;
+    let relative = full_path
+        .strip_prefix(&repo_root)
+        .map(|relative| normalize_git_path(&relative.to_string_lossy()))
+        .map_err(|_| format!("{path} is outside the workspace's git repository"))?;
+    let content = tokio::fs::read(&full_path)
+        .await
+        .map_err(|err| format!("Failed to read {path}: {err}"))?;
+
+    let head = {
+        let repo_root = repo_root.clone();
+        tokio::task::spawn_blocking(move || head_sha(&repo_root))
+            .await
+            .map_err(|err| err.to_string())?
+    };
+    let fingerprint = BlameFingerprint::new(head.clone(), &content);
+    {
+        let mut cache = state.blame_cache.lock().map_err(|err| err.to_string())?;
+        if let Some(blame) = cache.get(&workspace_id, &relative, &fingerprint) {
+            return blame_context::context(&path, blame, line_range, head, true);
+        }
+    }
+
+    let blame = {
+        let relative = relative.clone();
+        tokio::task::spawn_blocking(move || blame_file(&repo_root, &relative, &content))
+            .await
+            .map_err(|err| err.to_string())??
+    };
+    let context = blame_context::context(&path, &blame, line_range, head, false);
+    state
+        .blame_cache
+        .lock()
+        .map_err(|err| err.to_string())?
+        .insert(&workspace_id, &relative, fingerprint, blame);
+    context
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::backend::clock::{self, ClockJump, Timestamp};
+use crate::state::AppState;
+use crate::system_resume;
+
+/// Often enough that a jump is noticed soon after waking from sleep.
+const CLOCK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Stamps the current moment. A wall-clock jump since the previous stamp is
+/// stored in the clock jump journal and announced as `clock-jumped`; one
+/// caused by a sleep also starts session recovery.
+pub(crate) fn now(app: &AppHandle, state: &AppState) -> Timestamp {
+    let (now, jump) = match state.clock.lock() {
+        Ok(mut clock) => clock.now(),
+        Err(poisoned) => poisoned.into_inner().now(),
+    };
+    if let Some(jump) = jump {
+        let _ = app.emit("clock-jumped", &jump);
+        if system_resume::is_wake(&jump) {
+            system_resume::spawn_recovery(app, &jump);
+        }
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<AppState>();
+            let _guard = state.clock_jump_writes.lock().await;
+            let result = match state.data_cipher().await {
+                Ok(cipher) => clock::record_clock_jump(
+                    &clock::clock_jumps_path(&state.data_dir),
+                    &cipher,
+                    jump,
+                ),
+                Err(err) => Err(err),
+            };
+            if let Err(err) = result {
+                eprintln!("Failed to record clock jump: {err}");
+            }
+        });
+    }
+    now
+}
+
+/// Stamps periodically so sleeps and clock corrections are caught even
+/// while nothing else is being timed.
+pub(crate) fn spawn_clock_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = app.state::<AppState>();
+            now(&app, &state);
+            tokio::time::sleep(CLOCK_POLL_INTERVAL).await;
+        }
+    });
+}
+
+/// Recorded wall-clock jumps, oldest first, optionally only those ending at
+/// or after `since` (UTC ms). Analytics use them to discount durations
+/// measured across a jump.
+#[tauri::command]
+pub(crate) async fn list_clock_jumps(
+    since: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ClockJump>, String> {
+    let cipher = state.data_cipher().await?;
+    let mut jumps = clock::read_clock_jumps(&clock::clock_jumps_path(&state.data_dir), &cipher)?;
+    if let Some(since) = since {
+        jumps.retain(|jump| jump.after.utc_ms >= since);
+    }
+    Ok(jumps)
+}
@@ -0,0 +1,177 @@
+use std::process::Stdio;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+
+use crate::backend::network::apply_network_env;
+use crate::backend::repo_clone::{self, CloneProgress};
+use crate::codex::{self, SendOptions};
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::types::WorkspaceInfo;
+use crate::workspace_doctor::{self, WorkspaceDoctorReport};
+use crate::workspaces;
+
+/// First message of the optional onboarding thread.
+const ONBOARDING_PROMPT: &str = "Get familiar with this repository. Summarize what it does, \
+how it is laid out, and how to build, test and run it. Do not change any files.";
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct CloneProgressEvent<'a> {
+    url: &'a str,
+    destination: &'a str,
+    #[serde(flatten)]
+    progress: CloneProgress,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ClonedWorkspace {
+    pub(crate) workspace: WorkspaceInfo,
+    /// `None` when the checks could not run.
+    pub(crate) doctor: Option<WorkspaceDoctorReport>,
+    pub(crate) onboarding_thread_id: Option<String>,
+}
+
+/// Runs `git clone --progress`, emitting each progress line as
+/// `workspace-clone-progress`. Fails with the last lines git printed.
+async fn clone_repository(app: &AppHandle, url: &str, destination: &str) -> Result<(), String> {
+    let mut command = Command::new("git");
+    command
+        .args(["clone", "--progress", "--", url, destination])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    apply_network_env(&mut command);
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    let mut stderr = child.stderr.take().ok_or("missing stderr")?;
+
+    let mut pending = String::new();
+    let mut last_lines: Vec<String> = Vec::new();
+    let mut buffer = [0u8; 4096];
+    loop {
+        let read = stderr
+            .read(&mut buffer)
+            .await
+            .map_err(|e| format!("Failed to read git output: {e}"))?;
+        if read == 0 {
+            break;
+        }
+        pending.push_str(&String::from_utf8_lossy(&buffer[..read]));
+        // Progress lines are rewritten in place with `\r`.
+        while let Some(end) = pending.find(['\r', '\n']) {
+            let line: String = pending.drain(..=end).collect();
+            let Some(progress) = repo_clone::parse_clone_progress(&line) else {
+                continue;
+            };
+            if progress.percent.is_none() {
+                last_lines.push(progress.message.clone());
+                if last_lines.len() > 5 {
+                    last_lines.remove(0);
+                }
+            }
+            let _ = app.emit(
+                "workspace-clone-progress",
+                CloneProgressEvent {
+                    url,
+                    destination,
+                    progress,
+                },
+            );
+        }
+    }
+    last_lines.extend(repo_clone::parse_clone_progress(&pending).map(|progress| progress.message));
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if status.success() {
+        Ok(())
+    } else if last_lines.is_empty() {
+        Err("Git clone failed.".to_string())
+    } else {
+        Err(last_lines.join("\n"))
+    }
+}
+
+/// Clones a repository from a URL, adds it as a workspace and checks it
+/// with the workspace doctor. With `start_onboarding`, also starts a thread
+/// asking the agent to get familiar with the repository.
+#[tauri::command]
+pub(crate) async fn clone_and_add_workspace(
+    url: String,
+    dest: String,
+    start_onboarding: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ClonedWorkspace, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Cloning from a URL is only available for local workspaces.".to_string());
+    }
+    let url = repo_clone::validate_clone_url(&url)?;
+    let destination = repo_clone::clone_destination(&url, &dest)?;
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create destination folder: {e}"))?;
+    }
+    let destination = destination.to_string_lossy().to_string();
+    if let Err(error) = clone_repository(&app, &url, &destination).await {
+        let _ = tokio::fs::remove_dir_all(&destination).await;
+        return Err(error);
+    }
+
+    let workspace =
+        workspaces::add_workspace(destination, None, state.clone(), app.clone()).await?;
+    let doctor = workspace_doctor::workspace_doctor(workspace.id.clone(), state.clone())
+        .await
+        .ok();
+    let onboarding_thread_id = if start_onboarding.unwrap_or(false) {
+        start_onboarding_thread(&workspace.id, &state, &app).await
+    } else {
+        None
+    };
+    Ok(ClonedWorkspace {
+        workspace,
+        doctor,
+        onboarding_thread_id,
+    })
+}
+
+/// The clone already succeeded, so a failure here only skips the thread.
+async fn start_onboarding_thread(
+    workspace_id: &str,
+    state: &State<'_, AppState>,
+    app: &AppHandle,
+) -> Option<String> {
+    let response = codex::start_thread(workspace_id.to_string(), state.clone(), app.clone())
+        .await
+        .ok()?;
+    let result = response.get("result").unwrap_or(&response);
+    let thread_id = result
+        .get("thread")
+        .and_then(|thread| thread.get("id"))
+        .and_then(|value| value.as_str())?
+        .to_string();
+    if let Err(err) = codex::send_message(
+        workspace_id.to_string(),
+        thread_id.clone(),
+        ONBOARDING_PROMPT.to_string(),
+        SendOptions {
+            access_mode: Some("read-only".to_string()),
+            ..SendOptions::default()
+        },
+        state.clone(),
+        app.clone(),
+    )
+    .await
+    {
+        eprintln!("Failed to start onboarding thread: {err}");
+    }
+    Some(thread_id)
+}
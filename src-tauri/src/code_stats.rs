@@ -0,0 +1,74 @@
+use chrono::Utc;
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::code_stats::{self, CodeStats, CodeStatsReport, TurnCodeStats};
+use crate::backend::turn_summary::{params_thread_id, params_turn_id};
+use crate::state::AppState;
+use crate::turn_labels;
+
+const DEFAULT_REPORT_DAYS: u32 = 30;
+
+/// Records the code stats attached to `turn/completed` summaries; called for
+/// every `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    if message.get("method").and_then(|value| value.as_str()) != Some("turn/completed") {
+        return;
+    }
+    let Some(params) = message.get("params") else {
+        return;
+    };
+    let Some(stats) = params
+        .get("summary")
+        .and_then(|summary| summary.get("codeStats"))
+        .and_then(|stats| serde_json::from_value::<CodeStats>(stats.clone()).ok())
+        .filter(|stats| !stats.by_language.is_empty())
+    else {
+        return;
+    };
+    let entry = TurnCodeStats {
+        workspace_id: workspace_id.to_string(),
+        thread_id: params_thread_id(params).unwrap_or_default(),
+        turn_id: params_turn_id(params),
+        recorded_at: Utc::now().timestamp_millis(),
+        stats,
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let _guard = state.code_stats_writes.lock().await;
+        let result = match state.data_cipher().await {
+            Ok(cipher) => code_stats::record_turn_stats(&state.data_dir, &cipher, entry),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to record code stats: {err}");
+        }
+    });
+}
+
+/// Aggregates language/LOC stats of agent turns over the last `days` days,
+/// optionally only of turns labeled `label`.
+#[tauri::command]
+pub(crate) async fn get_code_stats(
+    workspace_id: Option<String>,
+    days: Option<u32>,
+    label: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<CodeStatsReport, String> {
+    let turn_ids = match label.as_deref().filter(|label| !label.trim().is_empty()) {
+        Some(label) => {
+            Some(turn_labels::turns_with_label(&state, workspace_id.as_deref(), label).await?)
+        }
+        None => None,
+    };
+    let cipher = state.data_cipher().await?;
+    code_stats::build_report(
+        &state.data_dir,
+        &cipher,
+        workspace_id.as_deref(),
+        turn_ids.as_ref(),
+        days.unwrap_or(DEFAULT_REPORT_DAYS).max(1),
+        Utc::now().date_naive(),
+    )
+}
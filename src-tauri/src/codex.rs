@@ -1,32 +1,71 @@
 use serde_json::{json, Map, Value};
 use std::io::ErrorKind;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 use tokio::process::Command;
 use tokio::sync::mpsc;
 use tokio::time::timeout;
 
 pub(crate) use crate::backend::app_server::WorkspaceSession;
+use crate::access_control;
+use crate::approval_delegation::{self, DelegatedDecision};
+use crate::auth_expiry;
 use crate::backend::app_server::{
     build_codex_command_with_bin, build_codex_path_env, check_codex_installation,
     spawn_workspace_session as spawn_workspace_session_inner,
 };
+use crate::backend::dry_run;
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::offline_queue::OfflineTurn;
+use crate::backend::prompt_budget::PromptSizeCheck;
+use crate::backend::rate_limits::parse_rate_limits;
+use crate::backend::request_id::RequestId;
+use crate::backend::scratch;
+use crate::backend::thread_archive::{self, ArchivedThread};
+use crate::backend::turn_artifacts::artifacts_note;
+use crate::backend::turn_compare::{self, TurnComparison, TurnRef};
+use crate::backend::turn_limiter::TurnLane;
+use crate::backend::turn_summary::params_turn_id;
+use crate::backend::workspace_health::HealthEvent;
 use crate::codex_home::{resolve_default_codex_home, resolve_workspace_codex_home};
+use crate::directory_access;
 use crate::event_sink::TauriEventSink;
+use crate::full_access;
+use crate::offline_queue;
+use crate::prompt_budget;
+use crate::rate_limits;
 use crate::remote_backend;
 use crate::rules;
 use crate::state::AppState;
+use crate::thread_branches;
+use crate::thread_guard;
+use crate::thread_journal;
+use crate::thread_settings;
+use crate::turn_artifacts;
+use crate::turn_manifest;
+use crate::verification;
+use crate::workspace_health;
+use crate::workspace_lock;
+use crate::workspace_turns;
 use crate::types::WorkspaceEntry;
 
 pub(crate) async fn spawn_workspace_session(
-    entry: WorkspaceEntry,
+    mut entry: WorkspaceEntry,
     default_codex_bin: Option<String>,
     app_handle: AppHandle,
     codex_home: Option<PathBuf>,
 ) -> Result<Arc<WorkspaceSession>, String> {
+    let policy = app_handle.state::<AppState>().policy.policy.clone();
+    let default_codex_bin = if policy.codex_bin.is_some() {
+        // A pinned binary also wins over per-workspace overrides.
+        entry.codex_bin = None;
+        policy.codex_bin(default_codex_bin)
+    } else {
+        default_codex_bin
+    };
     let client_version = app_handle.package_info().version.to_string();
     let event_sink = TauriEventSink::new(app_handle);
     spawn_workspace_session_inner(
@@ -39,6 +78,60 @@ pub(crate) async fn spawn_workspace_session(
     .await
 }
 
+/// Returns `(ok, version, details)` for the `node` binary on the given PATH.
+pub(crate) async fn check_node_installation(
+    path_env: Option<&str>,
+) -> (bool, Option<String>, Option<String>) {
+    let mut node_command = Command::new("node");
+    if let Some(path_env) = path_env {
+        node_command.env("PATH", path_env);
+    }
+    node_command.arg("--version");
+    node_command.stdout(std::process::Stdio::piped());
+    node_command.stderr(std::process::Stdio::piped());
+    match timeout(Duration::from_secs(5), node_command.output()).await {
+        Ok(result) => match result {
+            Ok(output) => {
+                if output.status.success() {
+                    let version = String::from_utf8_lossy(&output.stdout)
+                        .trim()
+                        .to_string();
+                    (
+                        !version.is_empty(),
+                        if version.is_empty() { None } else { Some(version) },
+                        None,
+                    )
+                } else {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    let detail = if stderr.trim().is_empty() {
+                        stdout.trim()
+                    } else {
+                        stderr.trim()
+                    };
+                    (
+                        false,
+                        None,
+                        Some(if detail.is_empty() {
+                            localize(MessageCode::NodeFailedToStart)
+                        } else {
+                            detail.to_string()
+                        }),
+                    )
+                }
+            }
+            Err(err) => {
+                if err.kind() == ErrorKind::NotFound {
+                    (false, None, Some(localize(MessageCode::NodeNotFound)))
+                } else {
+                    (false, None, Some(err.to_string()))
+                }
+            }
+        },
+        Err(_) => (false, None, Some(localize(MessageCode::NodeCheckTimedOut))),
+    }
+}
+
 #[tauri::command]
 pub(crate) async fn codex_doctor(
     codex_bin: Option<String>,
@@ -48,10 +141,12 @@ pub(crate) async fn codex_doctor(
         let settings = state.app_settings.lock().await;
         settings.codex_bin.clone()
     };
-    let resolved = codex_bin
-        .clone()
-        .filter(|value| !value.trim().is_empty())
-        .or(default_bin);
+    let resolved = state.policy.policy.codex_bin(
+        codex_bin
+            .clone()
+            .filter(|value| !value.trim().is_empty())
+            .or(default_bin),
+    );
     let path_env = build_codex_path_env(resolved.as_deref());
     let version = check_codex_installation(resolved.clone()).await?;
     let mut command = build_codex_command_with_bin(resolved.clone());
@@ -63,60 +158,12 @@ pub(crate) async fn codex_doctor(
         Ok(result) => result.map(|output| output.status.success()).unwrap_or(false),
         Err(_) => false,
     };
-    let (node_ok, node_version, node_details) = {
-        let mut node_command = Command::new("node");
-        if let Some(ref path_env) = path_env {
-            node_command.env("PATH", path_env);
-        }
-        node_command.arg("--version");
-        node_command.stdout(std::process::Stdio::piped());
-        node_command.stderr(std::process::Stdio::piped());
-        match timeout(Duration::from_secs(5), node_command.output()).await {
-            Ok(result) => match result {
-                Ok(output) => {
-                    if output.status.success() {
-                        let version = String::from_utf8_lossy(&output.stdout)
-                            .trim()
-                            .to_string();
-                        (
-                            !version.is_empty(),
-                            if version.is_empty() { None } else { Some(version) },
-                            None,
-                        )
-                    } else {
-                        let stderr = String::from_utf8_lossy(&output.stderr);
-                        let stdout = String::from_utf8_lossy(&output.stdout);
-                        let detail = if stderr.trim().is_empty() {
-                            stdout.trim()
-                        } else {
-                            stderr.trim()
-                        };
-                        (
-                            false,
-                            None,
-                            Some(if detail.is_empty() {
-                                "Node failed to start.".to_string()
-                            } else {
-                                detail.to_string()
-                            }),
-                        )
-                    }
-                }
-                Err(err) => {
-                    if err.kind() == ErrorKind::NotFound {
-                        (false, None, Some("Node not found on PATH.".to_string()))
-                    } else {
-                        (false, None, Some(err.to_string()))
-                    }
-                }
-            },
-            Err(_) => (false, None, Some("Timed out while checking Node.".to_string())),
-        }
-    };
+    let (node_ok, node_version, node_details) =
+        check_node_installation(path_env.as_deref()).await;
     let details = if app_server_ok {
         None
     } else {
-        Some("Failed to run `codex app-server --help`.".to_string())
+        Some(localize(MessageCode::AppServerCheckFailed))
     };
     Ok(json!({
         "ok": version.is_some() && app_server_ok,
@@ -150,10 +197,10 @@ pub(crate) async fn start_thread(
     let sessions = state.sessions.lock().await;
     let session = sessions
         .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
     let params = json!({
         "cwd": session.entry.path,
-        "approvalPolicy": "on-request"
+        "approvalPolicy": state.policy.policy.approval_policy("on-request")
     });
     session.send_request("thread/start", params).await
 }
@@ -165,24 +212,28 @@ pub(crate) async fn resume_thread(
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
-    if remote_backend::is_remote_mode(&*state).await {
-        return remote_backend::call_remote(
+    let response = if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
             &*state,
-            app,
+            app.clone(),
             "resume_thread",
             json!({ "workspaceId": workspace_id, "threadId": thread_id }),
         )
+        .await?
+    } else {
+        access_control::ensure_workspace_visible(&*state, &workspace_id).await?;
+        let sessions = state.sessions.lock().await;
+        let session = sessions
+            .get(&workspace_id)
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
+        let params = json!({
+            "threadId": thread_id
+        });
+        session.send_request("thread/resume", params).await?
+    };
+    thread_journal::backfill_resumed_thread(&state, &app, &workspace_id, &thread_id, &response)
         .await;
-    }
-
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
-    let params = json!({
-        "threadId": thread_id
-    });
-    session.send_request("thread/resume", params).await
+    Ok(response)
 }
 
 #[tauri::command]
@@ -203,10 +254,11 @@ pub(crate) async fn list_threads(
         .await;
     }
 
+    access_control::ensure_workspace_visible(&*state, &workspace_id).await?;
     let sessions = state.sessions.lock().await;
     let session = sessions
         .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
     let params = json!({
         "cursor": cursor,
         "limit": limit,
@@ -214,13 +266,62 @@ pub(crate) async fn list_threads(
     session.send_request("thread/list", params).await
 }
 
+/// Diffs the file changes and final messages of two turns, e.g. the same
+/// prompt run with two different models.
+#[tauri::command]
+pub(crate) async fn compare_turns(
+    turn_a: TurnRef,
+    turn_b: TurnRef,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<TurnComparison, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "compare_turns",
+            json!({ "turnA": turn_a, "turnB": turn_b }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let mut snapshots = Vec::with_capacity(2);
+    for turn in [&turn_a, &turn_b] {
+        access_control::ensure_workspace_visible(&*state, &turn.workspace_id).await?;
+        let session = state
+            .sessions
+            .lock()
+            .await
+            .get(&turn.workspace_id)
+            .cloned()
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
+        snapshots.push(turn_compare::load_turn(&session, turn).await?);
+    }
+    Ok(turn_compare::compare(
+        turn_a,
+        &snapshots[0],
+        turn_b,
+        &snapshots[1],
+    ))
+}
+
 #[tauri::command]
 pub(crate) async fn archive_thread(
     workspace_id: String,
     thread_id: String,
+    force: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
+    thread_guard::ensure_no_pending_work(
+        &state,
+        &workspace_id,
+        &thread_id,
+        "archive",
+        force.unwrap_or(false),
+    )
+    .await?;
     if remote_backend::is_remote_mode(&*state).await {
         return remote_backend::call_remote(
             &*state,
@@ -231,14 +332,98 @@ pub(crate) async fn archive_thread(
         .await;
     }
 
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
-    let params = json!({
-        "threadId": thread_id
-    });
-    session.send_request("thread/archive", params).await
+    let retention_days = state.app_settings.lock().await.archive_retention_days;
+    let session = {
+        let sessions = state.sessions.lock().await;
+        sessions
+            .get(&workspace_id)
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?
+            .clone()
+    };
+    let cipher = state.data_cipher().await?;
+    thread_archive::archive_thread_with_tombstone(
+        &session,
+        &thread_archive::archive_path(&state.data_dir),
+        &cipher,
+        &workspace_id,
+        &thread_id,
+        retention_days,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn unarchive_thread(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "unarchive_thread",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id }),
+        )
+        .await;
+    }
+
+    let session = {
+        let sessions = state.sessions.lock().await;
+        sessions
+            .get(&workspace_id)
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?
+            .clone()
+    };
+    let cipher = state.data_cipher().await?;
+    thread_archive::unarchive_thread(
+        &session,
+        &thread_archive::archive_path(&state.data_dir),
+        &cipher,
+        &workspace_id,
+        &thread_id,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn list_recently_archived(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<ArchivedThread>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_recently_archived",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let cipher = state.data_cipher().await?;
+    thread_archive::list_recently_archived(
+        &thread_archive::archive_path(&state.data_dir),
+        &cipher,
+        workspace_id.as_deref(),
+    )
+}
+
+/// Optional settings for a turn started with `send_message`. The defaults
+/// send a plain interactive message.
+#[derive(Default)]
+pub(crate) struct SendOptions {
+    pub(crate) model: Option<String>,
+    pub(crate) effort: Option<String>,
+    pub(crate) access_mode: Option<String>,
+    pub(crate) images: Option<Vec<String>>,
+    pub(crate) collaboration_mode: Option<Value>,
+    pub(crate) dry_run: bool,
+    pub(crate) lane: TurnLane,
+    pub(crate) replace_active_turn: bool,
 }
 
 #[tauri::command]
@@ -251,11 +436,93 @@ pub(crate) async fn send_user_message(
     access_mode: Option<String>,
     images: Option<Vec<String>>,
     collaboration_mode: Option<Value>,
+    dry_run: Option<bool>,
+    lane: Option<TurnLane>,
+    replace_active_turn: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
+    let options = SendOptions {
+        model,
+        effort,
+        access_mode,
+        images,
+        collaboration_mode,
+        dry_run: dry_run.unwrap_or(false),
+        lane: lane.unwrap_or_default(),
+        replace_active_turn: replace_active_turn.unwrap_or(false),
+    };
+    send_message(workspace_id, thread_id, text, options, state, app).await
+}
+
+/// Starts a turn. A model, effort or access mode left out defaults to what
+/// the thread used last.
+pub(crate) async fn send_message(
+    workspace_id: String,
+    thread_id: String,
+    text: String,
+    options: SendOptions,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    let SendOptions {
+        model,
+        effort,
+        access_mode,
+        images,
+        collaboration_mode,
+        dry_run,
+        lane,
+        replace_active_turn,
+    } = options;
+    access_control::ensure_workspace_unlocked(&*state, &workspace_id).await?;
+    let policy = &state.policy.policy;
+    let (model, effort, access_mode) = thread_settings::resolve_turn_settings(
+        &state,
+        &workspace_id,
+        &thread_id,
+        model,
+        effort,
+        access_mode,
+        dry_run,
+    )
+    .await;
+    let access_mode = if dry_run {
+        Some(dry_run::DRY_RUN_ACCESS_MODE.to_string())
+    } else {
+        full_access::elevate_access_mode(&state, &workspace_id, &thread_id, access_mode).await
+    };
+    if let (Some(model), Ok(mut visibility)) = (model.as_deref(), state.reasoning_visibility.lock())
+    {
+        visibility.set_thread_model(&workspace_id, &thread_id, model);
+    }
+    let size_warning = prompt_budget::check_before_send(
+        &state,
+        &workspace_id,
+        &thread_id,
+        model.as_deref(),
+        &text,
+        images.as_deref(),
+    )?;
+    let admission = workspace_turns::admit_turn(
+        &state,
+        &app,
+        &workspace_id,
+        &thread_id,
+        replace_active_turn,
+    )
+    .await?;
     if remote_backend::is_remote_mode(&*state).await {
-        return remote_backend::call_remote(
+        policy.check_full_access(access_mode.as_deref().unwrap_or("current"))?;
+        auth_expiry::wait_for_auth(&state, &workspace_id, lane).await?;
+        rate_limits::wait_for_quota(&state, &app, &workspace_id, &thread_id, lane).await;
+        state
+            .turn_limiter
+            .acquire(&workspace_id, &thread_id, lane)
+            .await;
+        admission.sent();
+        begin_dry_run(&state, dry_run, &workspace_id, &thread_id);
+        let response = remote_backend::call_remote(
             &*state,
             app,
             "send_user_message",
@@ -268,17 +535,59 @@ pub(crate) async fn send_user_message(
                 "accessMode": access_mode,
                 "images": images,
                 "collaborationMode": collaboration_mode,
+                "dryRun": dry_run,
             }),
         )
         .await;
+        release_failed_turn(&state, &workspace_id, &thread_id, &response);
+        let response = finish_dry_run(&state, dry_run, &workspace_id, &thread_id, response);
+        return attach_size_warning(response, size_warning);
     }
 
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let session = state.sessions.lock().await.get(&workspace_id).cloned();
+    let Some(session) = session else {
+        if state.app_settings.lock().await.queue_offline_turns {
+            let turn = OfflineTurn {
+                id: String::new(),
+                workspace_id,
+                thread_id,
+                text,
+                model,
+                effort,
+                access_mode,
+                images,
+                collaboration_mode,
+                dry_run,
+                queued_at: 0,
+            };
+            return offline_queue::queue_turn(&state, &app, turn).await;
+        }
+        return Err(localize(MessageCode::WorkspaceNotConnected));
+    };
     let access_mode = access_mode.unwrap_or_else(|| "current".to_string());
+    policy.check_access(&access_mode, &session.entry.path)?;
+    let scratch = session.entry.settings.scratch.is_some();
+    let artifacts_dir = if access_mode == "read-only" || scratch {
+        None
+    } else {
+        turn_artifacts::prepare_thread_dir(&state, &workspace_id, &thread_id)
+            .filter(|dir| policy.allows_writes_in(dir))
+    };
+    let mut writable_roots = vec![session.entry.path.clone()];
+    if let Some(dir) = &artifacts_dir {
+        writable_roots.push(dir.display().to_string());
+    }
+    if access_mode != "read-only" && !scratch {
+        writable_roots.extend(
+            directory_access::granted_roots(&state, &workspace_id, &thread_id)
+                .await
+                .into_iter()
+                .filter(|root| policy.allows_writes_in(Path::new(root))),
+        );
+    }
     let sandbox_policy = match access_mode.as_str() {
+        // Scratch workspaces ignore full access and grants, and have no network.
+        _ if scratch => scratch::scratch_sandbox_policy(&access_mode, &session.entry.path),
         "full-access" => json!({
             "type": "dangerFullAccess"
         }),
@@ -287,19 +596,31 @@ pub(crate) async fn send_user_message(
         }),
         _ => json!({
             "type": "workspaceWrite",
-            "writableRoots": [session.entry.path],
+            "writableRoots": writable_roots,
             "networkAccess": true
         }),
     };
 
-    let approval_policy = if access_mode == "full-access" {
-        "never"
+    let approval_policy = if dry_run {
+        dry_run::DRY_RUN_APPROVAL_POLICY
+    } else if scratch {
+        scratch::SCRATCH_APPROVAL_POLICY
     } else {
-        "on-request"
+        policy.approval_policy(if access_mode == "full-access" {
+            "never"
+        } else {
+            "on-request"
+        })
     };
 
     let trimmed_text = text.trim();
     let mut input: Vec<Value> = Vec::new();
+    if dry_run {
+        input.push(dry_run::dry_run_note());
+    }
+    if let Some(dir) = &artifacts_dir {
+        input.push(artifacts_note(dir));
+    }
     if !trimmed_text.is_empty() {
         input.push(json!({ "type": "text", "text": trimmed_text }));
     }
@@ -333,7 +654,134 @@ pub(crate) async fn send_user_message(
         "effort": effort,
         "collaborationMode": collaboration_mode,
     });
-    session.send_request("turn/start", params).await
+    if !dry_run && access_mode != "read-only" && !scratch {
+        workspace_lock::ensure_workspace_lock(
+            &state,
+            &app,
+            &workspace_id,
+            Path::new(&session.entry.path),
+        )?;
+    }
+    let thread_branch = if !dry_run && access_mode != "read-only" {
+        thread_branches::ensure_thread_branch(&state, &workspace_id, &thread_id, trimmed_text)
+            .await?
+    } else {
+        None
+    };
+    let snapshot = turn_manifest::capture_snapshot(&session.entry).await;
+    auth_expiry::wait_for_auth(&state, &workspace_id, lane).await?;
+    rate_limits::wait_for_quota(&state, &app, &workspace_id, &thread_id, lane).await;
+    state
+        .turn_limiter
+        .acquire(&workspace_id, &thread_id, lane)
+        .await;
+    admission.sent();
+    begin_dry_run(&state, dry_run, &workspace_id, &thread_id);
+    let response = session.send_request("turn/start", params).await;
+    release_failed_turn(&state, &workspace_id, &thread_id, &response);
+    if let Ok(value) = &response {
+        if !dry_run && access_mode != "read-only" && value.get("error").is_none() {
+            verification::arm_turn(&state, &workspace_id, &thread_id);
+        }
+        turn_manifest::record_turn(
+            &state,
+            &session,
+            &thread_id,
+            value,
+            snapshot,
+            turn_manifest::TurnSettings {
+                model,
+                effort,
+                access_mode,
+                approval_policy: approval_policy.to_string(),
+                sandbox_policy,
+                collaboration_mode,
+            },
+        )
+        .await;
+    }
+    let mut response = finish_dry_run(&state, dry_run, &workspace_id, &thread_id, response);
+    if let (Some(branch), Ok(Value::Object(object))) = (thread_branch, response.as_mut()) {
+        if let Ok(value) = serde_json::to_value(branch) {
+            object.insert("threadBranch".to_string(), value);
+        }
+    }
+    attach_size_warning(response, size_warning)
+}
+
+/// Gives back the concurrent-turn slot when `turn/start` failed, since no
+/// `turn/completed` will follow, and otherwise records the started turn's id.
+fn release_failed_turn(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+    response: &Result<Value, String>,
+) {
+    if response
+        .as_ref()
+        .map_or(true, |value| value.get("error").is_some())
+    {
+        state.turn_limiter.finish(workspace_id, thread_id);
+        state.workspace_turns.finish(workspace_id, thread_id);
+        return;
+    }
+    let turn_id = response
+        .as_ref()
+        .ok()
+        .and_then(|value| params_turn_id(value.get("result").unwrap_or(value)));
+    if let Some(turn_id) = turn_id {
+        state.workspace_turns.started(
+            workspace_id,
+            thread_id,
+            &turn_id,
+            chrono::Utc::now().timestamp_millis(),
+        );
+    }
+}
+
+/// Adds a prompt size warning to the `turn/start` response as
+/// `promptSizeWarning`.
+fn attach_size_warning(
+    response: Result<Value, String>,
+    warning: Option<PromptSizeCheck>,
+) -> Result<Value, String> {
+    let mut response = response?;
+    if let (Some(warning), Some(object)) = (warning, response.as_object_mut()) {
+        if let Ok(value) = serde_json::to_value(warning) {
+            object.insert("promptSizeWarning".to_string(), value);
+        }
+    }
+    Ok(response)
+}
+
+/// Annotates the thread before `turn/start`, so the tracker claims the
+/// `turn/started` notification even if it arrives before the response.
+fn begin_dry_run(state: &AppState, dry_run: bool, workspace_id: &str, thread_id: &str) {
+    if !dry_run {
+        return;
+    }
+    if let Ok(mut dry_runs) = state.dry_runs.lock() {
+        dry_runs.begin(workspace_id, thread_id);
+    }
+}
+
+fn finish_dry_run(
+    state: &AppState,
+    dry_run: bool,
+    workspace_id: &str,
+    thread_id: &str,
+    response: Result<Value, String>,
+) -> Result<Value, String> {
+    let failed = match &response {
+        Ok(value) => value.get("error").is_some(),
+        Err(_) => true,
+    };
+    if dry_run && failed {
+        if let Ok(mut dry_runs) = state.dry_runs.lock() {
+            dry_runs.cancel(workspace_id, thread_id);
+        }
+    }
+    response
 }
 
 #[tauri::command]
@@ -355,7 +803,7 @@ pub(crate) async fn collaboration_mode_list(
     let sessions = state.sessions.lock().await;
     let session = sessions
         .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
     session
         .send_request("collaborationMode/list", json!({}))
         .await
@@ -382,7 +830,7 @@ pub(crate) async fn turn_interrupt(
     let sessions = state.sessions.lock().await;
     let session = sessions
         .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
     let params = json!({
         "threadId": thread_id,
         "turnId": turn_id,
@@ -414,10 +862,11 @@ pub(crate) async fn start_review(
         .await;
     }
 
+    access_control::ensure_workspace_unlocked(&*state, &workspace_id).await?;
     let sessions = state.sessions.lock().await;
     let session = sessions
         .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
     let mut params = Map::new();
     params.insert("threadId".to_string(), json!(thread_id));
     params.insert("target".to_string(), target);
@@ -442,15 +891,18 @@ pub(crate) async fn model_list(
             "model_list",
             json!({ "workspaceId": workspace_id }),
         )
-        .await;
+        .await
+        .inspect(|response| prompt_budget::ingest_model_list(&state, response));
     }
 
     let sessions = state.sessions.lock().await;
     let session = sessions
         .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
     let params = json!({});
-    session.send_request("model/list", params).await
+    let response = session.send_request("model/list", params).await?;
+    prompt_budget::ingest_model_list(&state, &response);
+    Ok(response)
 }
 
 #[tauri::command]
@@ -459,23 +911,26 @@ pub(crate) async fn account_rate_limits(
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
-    if remote_backend::is_remote_mode(&*state).await {
-        return remote_backend::call_remote(
+    let response = if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
             &*state,
             app,
             "account_rate_limits",
             json!({ "workspaceId": workspace_id }),
         )
-        .await;
-    }
-
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
-    session
-        .send_request("account/rateLimits/read", Value::Null)
-        .await
+        .await?
+    } else {
+        let sessions = state.sessions.lock().await;
+        let session = sessions
+            .get(&workspace_id)
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
+        session
+            .send_request("account/rateLimits/read", Value::Null)
+            .await?
+    };
+    let result = response.get("result").unwrap_or(&response);
+    state.rate_limits.update(&workspace_id, parse_rate_limits(result));
+    Ok(response)
 }
 
 #[tauri::command]
@@ -497,7 +952,7 @@ pub(crate) async fn skills_list(
     let sessions = state.sessions.lock().await;
     let session = sessions
         .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
     let params = json!({
         "cwd": session.entry.path
     });
@@ -507,27 +962,54 @@ pub(crate) async fn skills_list(
 #[tauri::command]
 pub(crate) async fn respond_to_server_request(
     workspace_id: String,
-    request_id: u64,
+    request_id: RequestId,
     result: Value,
+    delegation: Option<DelegatedDecision>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
+    approval_delegation::authorize_response(
+        &state,
+        &workspace_id,
+        &request_id,
+        &result,
+        delegation.as_ref(),
+    )
+    .await?;
     if remote_backend::is_remote_mode(&*state).await {
         remote_backend::call_remote(
             &*state,
-            app,
+            app.clone(),
             "respond_to_server_request",
             json!({ "workspaceId": workspace_id, "requestId": request_id, "result": result }),
         )
         .await?;
+        record_approval_wait(&state, &app, &workspace_id, &request_id);
         return Ok(());
     }
 
     let sessions = state.sessions.lock().await;
     let session = sessions
         .get(&workspace_id)
-        .ok_or("workspace not connected")?;
-    session.send_response(request_id, result).await
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
+    session.send_response(&request_id, result).await?;
+    record_approval_wait(&state, &app, &workspace_id, &request_id);
+    Ok(())
+}
+
+fn record_approval_wait(
+    state: &AppState,
+    app: &AppHandle,
+    workspace_id: &str,
+    request_id: &RequestId,
+) {
+    if let Some(waited_ms) = thread_guard::resolve_approval(state, workspace_id, request_id) {
+        workspace_health::record(
+            app,
+            workspace_id,
+            HealthEvent::ApprovalAnswered { waited_ms },
+        );
+    }
 }
 
 /// Gets the diff content for commit message generation
@@ -573,7 +1055,7 @@ pub(crate) async fn remember_approval_rule(
         let workspaces = state.workspaces.lock().await;
         let entry = workspaces
             .get(&workspace_id)
-            .ok_or("workspace not found")?
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?
             .clone();
         let parent_path = entry
             .parent_id
@@ -621,7 +1103,7 @@ Changes:\n{diff}"
         let sessions = state.sessions.lock().await;
         sessions
             .get(&workspace_id)
-            .ok_or("workspace not connected")?
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?
             .clone()
     };
 
@@ -0,0 +1,189 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::doctor_fix::detected_codex_bins;
+use crate::state::AppState;
+use crate::storage::write_workspaces;
+use crate::types::WorkspaceEntry;
+
+/// Delay after launch before the startup check reports, so the window is
+/// listening by then.
+const STARTUP_CHECK_DELAY: Duration = Duration::from_secs(5);
+
+/// A workspace whose `codex_bin` override points at a missing file.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BrokenCodexBin {
+    pub(crate) workspace_id: String,
+    pub(crate) workspace_name: String,
+    pub(crate) codex_bin: String,
+    /// A Codex installation found on this machine to use instead.
+    pub(crate) suggestion: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CodexBinRemap {
+    pub(crate) workspace_id: String,
+    pub(crate) from: String,
+    pub(crate) to: String,
+}
+
+/// Overrides given as a bare name are looked up on `PATH` and never
+/// reported.
+fn is_broken(codex_bin: &str) -> bool {
+    let path = Path::new(codex_bin.trim());
+    path.is_absolute() && !path.is_file()
+}
+
+/// The installation with the same file name as the broken override, e.g.
+/// `codex.cmd` on Windows, or else the first one found.
+fn suggest_replacement(codex_bin: &str, installed: &[PathBuf]) -> Option<String> {
+    let name = Path::new(codex_bin.trim()).file_name();
+    installed
+        .iter()
+        .find(|path| path.file_name() == name)
+        .or_else(|| installed.first())
+        .map(|path| path.to_string_lossy().to_string())
+}
+
+fn remap_prefix(codex_bin: &str, old_prefix: &str, new_prefix: &str) -> Option<String> {
+    codex_bin
+        .strip_prefix(old_prefix)
+        .map(|rest| format!("{new_prefix}{rest}"))
+}
+
+fn broken_overrides(entries: &[WorkspaceEntry], installed: &[PathBuf]) -> Vec<BrokenCodexBin> {
+    let mut broken: Vec<_> = entries
+        .iter()
+        .filter_map(|entry| {
+            let codex_bin = entry.codex_bin.as_deref()?;
+            is_broken(codex_bin).then(|| BrokenCodexBin {
+                workspace_id: entry.id.clone(),
+                workspace_name: entry.name.clone(),
+                codex_bin: codex_bin.to_string(),
+                suggestion: suggest_replacement(codex_bin, installed),
+            })
+        })
+        .collect();
+    broken.sort_by(|a, b| a.workspace_name.cmp(&b.workspace_name));
+    broken
+}
+
+async fn find_broken(state: &AppState) -> Vec<BrokenCodexBin> {
+    let entries: Vec<_> = state.workspaces.lock().await.values().cloned().collect();
+    if !entries
+        .iter()
+        .any(|entry| entry.codex_bin.as_deref().is_some_and(is_broken))
+    {
+        return Vec::new();
+    }
+    broken_overrides(&entries, &detected_codex_bins().await)
+}
+
+/// Emits `codex-bin-overrides-broken` shortly after launch when workspace
+/// `codex_bin` overrides point at missing files, e.g. after a Node upgrade
+/// removed the nvm version they were installed under.
+pub(crate) fn spawn_startup_check(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_CHECK_DELAY).await;
+        let broken = find_broken(&app.state::<AppState>()).await;
+        if !broken.is_empty() {
+            let _ = app.emit("codex-bin-overrides-broken", broken);
+        }
+    });
+}
+
+/// Workspace `codex_bin` overrides that point at missing files, each with a
+/// suggested replacement from the installations found on this machine.
+#[tauri::command]
+pub(crate) async fn list_broken_codex_bins(
+    state: State<'_, AppState>,
+) -> Result<Vec<BrokenCodexBin>, String> {
+    Ok(find_broken(&state).await)
+}
+
+/// Rewrites every workspace `codex_bin` override starting with `old_prefix`
+/// to start with `new_prefix` instead, e.g. to move all of them from one
+/// nvm Node version to another. Running sessions keep their binary until
+/// they reconnect.
+#[tauri::command]
+pub(crate) async fn remap_codex_bin(
+    old_prefix: String,
+    new_prefix: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<CodexBinRemap>, String> {
+    if old_prefix.trim().is_empty() {
+        return Err("The prefix to replace cannot be empty.".to_string());
+    }
+    let mut workspaces = state.workspaces.lock().await;
+    let mut remaps = Vec::new();
+    for entry in workspaces.values_mut() {
+        let Some(from) = entry.codex_bin.clone() else {
+            continue;
+        };
+        if let Some(to) = remap_prefix(&from, &old_prefix, &new_prefix) {
+            entry.codex_bin = Some(to.clone());
+            remaps.push(CodexBinRemap {
+                workspace_id: entry.id.clone(),
+                from,
+                to,
+            });
+        }
+    }
+    if !remaps.is_empty() {
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        write_workspaces(&state.storage_path, &list)?;
+    }
+    Ok(remaps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{WorkspaceKind, WorkspaceSettings};
+
+    #[test]
+    fn reports_missing_overrides_with_suggestions() {
+        let dir = std::env::temp_dir().join(format!("codex-bin-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let installed = dir.join("codex");
+        std::fs::write(&installed, "").expect("write binary");
+        let dead = dir.join("v18.0.0/bin/codex").to_string_lossy().to_string();
+        let entry = |id: &str, codex_bin: Option<String>| WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: "/tmp".to_string(),
+            codex_bin,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+            icon: None,
+            color: None,
+        };
+        let entries = vec![
+            entry("dead", Some(dead.clone())),
+            entry("live", Some(installed.to_string_lossy().to_string())),
+            entry("bare", Some("codex".to_string())),
+            entry("default", None),
+        ];
+
+        let broken = broken_overrides(&entries, std::slice::from_ref(&installed));
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].workspace_id, "dead");
+        assert_eq!(
+            broken[0].suggestion.as_deref(),
+            Some(installed.to_string_lossy().as_ref())
+        );
+        assert_eq!(
+            remap_prefix(&dead, &dir.join("v18.0.0").to_string_lossy(), "/node/v22"),
+            Some("/node/v22/bin/codex".to_string())
+        );
+        assert_eq!(remap_prefix("codex", "/old", "/new"), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
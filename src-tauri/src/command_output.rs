@@ -0,0 +1,77 @@
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::command_output::{self, CommandOutputMatch, ItemOutputPage};
+use crate::state::AppState;
+
+const DEFAULT_SEARCH_LIMIT: usize = 100;
+const DEFAULT_PAGE_CHARS: usize = 64 * 1024;
+const MAX_PAGE_CHARS: usize = 1024 * 1024;
+
+/// Captures the output of finished agent commands; called for every
+/// `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let captured = match state.command_outputs.lock() {
+        Ok(mut collector) => {
+            collector.observe(workspace_id, message, chrono::Utc::now().timestamp_millis())
+        }
+        Err(_) => None,
+    };
+    let Some(captured) = captured else {
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let _guard = state.command_output_writes.lock().await;
+        let result = match state.data_cipher().await {
+            Ok(cipher) => command_output::record_command(&state.data_dir, &cipher, captured),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to store command output: {err}");
+        }
+    });
+}
+
+/// Searches the captured output of commands the agent ran.
+#[tauri::command]
+pub(crate) async fn search_command_outputs(
+    query: String,
+    workspace_id: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<CommandOutputMatch>, String> {
+    let cipher = state.data_cipher().await?;
+    let commands =
+        command_output::read_commands(&state.data_dir, &cipher, workspace_id.as_deref())?;
+    Ok(command_output::search_commands(
+        commands,
+        &query,
+        limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+    ))
+}
+
+/// Returns one page of a command's complete captured output, so multi-megabyte
+/// results never have to cross the bridge in one piece.
+#[tauri::command]
+pub(crate) async fn get_item_output(
+    workspace_id: String,
+    item_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<ItemOutputPage, String> {
+    let cipher = state.data_cipher().await?;
+    command_output::read_output_page(
+        &state.data_dir,
+        &cipher,
+        &workspace_id,
+        &item_id,
+        offset.unwrap_or(0),
+        limit.unwrap_or(DEFAULT_PAGE_CHARS).clamp(1, MAX_PAGE_CHARS),
+    )
+}
@@ -0,0 +1,19 @@
+use crate::backend::connection_sim::{self, ConnectionSimulation};
+
+#[tauri::command]
+pub(crate) async fn get_connection_simulation() -> Result<ConnectionSimulation, String> {
+    Ok(connection_sim::simulation())
+}
+
+/// Developer toggle that degrades the connection to local app-server
+/// sessions; takes effect immediately for running sessions.
+#[tauri::command]
+pub(crate) async fn set_connection_simulation(
+    simulation: ConnectionSimulation,
+) -> Result<ConnectionSimulation, String> {
+    if !(0.0..=100.0).contains(&simulation.drop_percent) {
+        return Err("dropPercent must be between 0 and 100".to_string());
+    }
+    connection_sim::set_simulation(simulation);
+    Ok(connection_sim::simulation())
+}
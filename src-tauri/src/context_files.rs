@@ -0,0 +1,169 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use git2::{DiffOptions, Repository, Sort, StatusOptions};
+use tauri::State;
+use tokio::task;
+
+use crate::backend::code_stats::language_for_path;
+use crate::backend::context_files::{
+    defines_symbol, prompt_terms, rank_files, ContextFileSuggestion, ContextSignals,
+    DEFAULT_SUGGESTION_LIMIT,
+};
+use crate::backend::i18n::{localize, MessageCode};
+use crate::git_utils::resolve_git_root;
+use crate::state::AppState;
+use crate::utils::normalize_git_path;
+use crate::workspaces::list_workspace_files_inner;
+
+const MAX_FILES: usize = 20_000;
+const RECENT_COMMITS: usize = 20;
+/// Bounds the symbol scan so large repositories stay responsive.
+const MAX_SCANNED_FILES: usize = 4_000;
+const MAX_SCANNED_BYTES: u64 = 256 * 1024;
+
+/// Maps a repository-relative path to a workspace-relative one, for
+/// workspaces whose git root is not the workspace folder itself.
+fn workspace_relative(repo_root: &Path, workspace_root: &Path, path: &str) -> Option<String> {
+    let absolute = repo_root.join(path);
+    let relative = absolute.strip_prefix(workspace_root).ok()?;
+    Some(normalize_git_path(&relative.to_string_lossy()))
+}
+
+fn diff_paths(repo: &Repository, repo_root: &Path, workspace_root: &Path) -> HashSet<String> {
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+    let Ok(statuses) = repo.statuses(Some(&mut options)) else {
+        return HashSet::new();
+    };
+    statuses
+        .iter()
+        .filter_map(|entry| workspace_relative(repo_root, workspace_root, entry.path()?))
+        .collect()
+}
+
+fn recent_paths(
+    repo: &Repository,
+    repo_root: &Path,
+    workspace_root: &Path,
+) -> HashMap<String, usize> {
+    let mut paths = HashMap::new();
+    let Ok(mut revwalk) = repo.revwalk() else {
+        return paths;
+    };
+    if revwalk.push_head().is_err() || revwalk.set_sorting(Sort::TIME).is_err() {
+        return paths;
+    }
+    for (rank, oid) in revwalk.flatten().take(RECENT_COMMITS).enumerate() {
+        let Ok(commit) = repo.find_commit(oid) else {
+            continue;
+        };
+        let tree = commit.tree().ok();
+        let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+        let Ok(diff) = repo.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            tree.as_ref(),
+            Some(&mut DiffOptions::new()),
+        ) else {
+            continue;
+        };
+        for delta in diff.deltas() {
+            let Some(path) = delta.new_file().path().and_then(|path| path.to_str()) else {
+                continue;
+            };
+            if let Some(path) = workspace_relative(repo_root, workspace_root, path) {
+                paths.entry(path).or_insert(rank);
+            }
+        }
+    }
+    paths
+}
+
+fn symbol_paths(
+    workspace_root: &Path,
+    files: &[String],
+    symbols: &[String],
+) -> HashMap<String, Vec<String>> {
+    let mut found = HashMap::new();
+    if symbols.is_empty() {
+        return found;
+    }
+    let candidates = files
+        .iter()
+        .filter(|path| {
+            !matches!(
+                language_for_path(path),
+                "Other" | "Markdown" | "JSON" | "YAML" | "TOML" | "XML" | "Lockfile"
+            )
+        })
+        .take(MAX_SCANNED_FILES);
+    for path in candidates {
+        let full_path = workspace_root.join(path);
+        let small_enough =
+            std::fs::metadata(&full_path).is_ok_and(|metadata| metadata.len() <= MAX_SCANNED_BYTES);
+        if !small_enough {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(&full_path) else {
+            continue;
+        };
+        let defined: Vec<String> = symbols
+            .iter()
+            .filter(|symbol| defines_symbol(&content, symbol))
+            .cloned()
+            .collect();
+        if !defined.is_empty() {
+            found.insert(path.clone(), defined);
+        }
+    }
+    found
+}
+
+fn suggest(
+    workspace_root: PathBuf,
+    repo_root: Option<PathBuf>,
+    prompt_text: &str,
+    limit: usize,
+) -> Vec<ContextFileSuggestion> {
+    let terms = prompt_terms(prompt_text);
+    let files = list_workspace_files_inner(&workspace_root, MAX_FILES);
+    let mut signals = ContextSignals {
+        symbol_paths: symbol_paths(&workspace_root, &files, &terms.symbols),
+        ..ContextSignals::default()
+    };
+    if let Some(repo_root) = repo_root {
+        if let Ok(repo) = Repository::open(&repo_root) {
+            signals.diff_paths = diff_paths(&repo, &repo_root, &workspace_root);
+            signals.recent_paths = recent_paths(&repo, &repo_root, &workspace_root);
+        }
+    }
+    rank_files(&files, &terms, &signals, limit)
+}
+
+/// Ranks workspace files likely to matter for a prompt, from path and symbol
+/// matches plus the current diff and recent commits, so the UI can propose
+/// attachments before the turn starts.
+#[tauri::command]
+pub(crate) async fn suggest_context_files(
+    workspace_id: String,
+    prompt_text: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ContextFileSuggestion>, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?
+    };
+    if prompt_text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let workspace_root = PathBuf::from(&entry.path);
+    let repo_root = resolve_git_root(&entry).ok();
+    let limit = limit.unwrap_or(DEFAULT_SUGGESTION_LIMIT).max(1);
+    task::spawn_blocking(move || suggest(workspace_root, repo_root, &prompt_text, limit))
+        .await
+        .map_err(|err| err.to_string())
+}
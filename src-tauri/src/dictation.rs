@@ -21,6 +21,9 @@ use objc2_av_foundation::{AVAuthorizationStatus, AVCaptureDevice, AVMediaTypeAud
 
 const DEFAULT_MODEL_ID: &str = "base";
 const MAX_CAPTURE_SECONDS: u32 = 120;
+const PARTIAL_TRANSCRIPT_INTERVAL: Duration = Duration::from_millis(1500);
+/// Longest stretch of audio transcribed again for each partial transcript.
+const PARTIAL_WINDOW_SECONDS: u32 = 10;
 
 /// Checks microphone authorization status on macOS.
 #[cfg(target_os = "macos")]
@@ -170,6 +173,7 @@ pub(crate) enum DictationSessionState {
 pub(crate) enum DictationEvent {
     State { state: DictationSessionState },
     Level { value: f32 },
+    Partial { text: String },
     Transcript { text: String },
     Error { message: String },
     Canceled { message: String },
@@ -773,6 +777,7 @@ pub(crate) async fn dictation_start(
     let app_handle = app.clone();
     let preferred_clone = preferred_language.clone();
     let audio_capture = audio.clone();
+    let audio_partial = audio.clone();
 
     std::thread::spawn(move || {
         start_capture_thread(
@@ -811,6 +816,14 @@ pub(crate) async fn dictation_start(
         });
     }
 
+    spawn_partial_transcripts(
+        app.clone(),
+        audio_partial,
+        sample_rate,
+        model_id,
+        preferred_language,
+    );
+
     emit_event(
         &app,
         DictationEvent::State {
@@ -872,83 +885,21 @@ pub(crate) async fn dictation_stop(
             return;
         }
 
-        let state_handle = app_handle.state::<AppState>();
-        let cached_context = {
-            let dictation = state_handle.dictation.lock().await;
-            dictation
-                .cached_context
-                .as_ref()
-                .filter(|cached| cached.model_id == model_id)
-                .map(|cached| Arc::clone(&cached.context))
-        };
-
-        let context = if let Some(context) = cached_context {
-            context
-        } else {
-            let model_path = match model_path(&app_handle, &model_id) {
-                Ok(path) => path,
-                Err(error) => {
-                    emit_event(&app_handle, DictationEvent::Error { message: error });
-                    let mut dictation = state_handle.dictation.lock().await;
-                    dictation.session_state = DictationSessionState::Idle;
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::State {
-                            state: DictationSessionState::Idle,
-                        },
-                    );
-                    return;
-                }
-            };
-            let path = model_path.to_string_lossy().into_owned();
-            let created = tokio::task::spawn_blocking(move || {
-                WhisperContext::new_with_params(&path, WhisperContextParameters::default())
-            })
-            .await;
-            let context = match created {
-                Ok(Ok(context)) => context,
-                Ok(Err(error)) => {
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::Error {
-                            message: format!("Failed to load Whisper model: {error}"),
-                        },
-                    );
-                    let mut dictation = state_handle.dictation.lock().await;
-                    dictation.session_state = DictationSessionState::Idle;
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::State {
-                            state: DictationSessionState::Idle,
-                        },
-                    );
-                    return;
-                }
-                Err(error) => {
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::Error {
-                            message: format!("Failed to load Whisper model: {error}"),
-                        },
-                    );
-                    let mut dictation = state_handle.dictation.lock().await;
-                    dictation.session_state = DictationSessionState::Idle;
-                    emit_event(
-                        &app_handle,
-                        DictationEvent::State {
-                            state: DictationSessionState::Idle,
-                        },
-                    );
-                    return;
-                }
-            };
-            let context = Arc::new(context);
-            let mut dictation = state_handle.dictation.lock().await;
-            dictation.cached_context = Some(CachedWhisperContext {
-                model_id: model_id.clone(),
-                context: Arc::clone(&context),
-            });
-            context
+        let context = match load_whisper_context(&app_handle, &model_id).await {
+            Ok(context) => context,
+            Err(message) => {
+                emit_event(&app_handle, DictationEvent::Error { message });
+                let state_handle = app_handle.state::<AppState>();
+                let mut dictation = state_handle.dictation.lock().await;
+                dictation.session_state = DictationSessionState::Idle;
+                emit_event(
+                    &app_handle,
+                    DictationEvent::State {
+                        state: DictationSessionState::Idle,
+                    },
+                );
+                return;
+            }
         };
 
         let preferred = preferred_language.clone();
@@ -1065,6 +1016,106 @@ pub(crate) async fn dictation_cancel(
     Ok(DictationSessionState::Idle)
 }
 
+async fn load_whisper_context(
+    app: &AppHandle,
+    model_id: &str,
+) -> Result<Arc<WhisperContext>, String> {
+    let state_handle = app.state::<AppState>();
+    let cached_context = {
+        let dictation = state_handle.dictation.lock().await;
+        dictation
+            .cached_context
+            .as_ref()
+            .filter(|cached| cached.model_id == model_id)
+            .map(|cached| Arc::clone(&cached.context))
+    };
+    if let Some(context) = cached_context {
+        return Ok(context);
+    }
+    let path = model_path(app, model_id)?.to_string_lossy().into_owned();
+    let context = tokio::task::spawn_blocking(move || {
+        WhisperContext::new_with_params(&path, WhisperContextParameters::default())
+    })
+    .await
+    .map_err(|error| format!("Failed to load Whisper model: {error}"))?
+    .map_err(|error| format!("Failed to load Whisper model: {error}"))?;
+    let context = Arc::new(context);
+    let mut dictation = state_handle.dictation.lock().await;
+    dictation.cached_context = Some(CachedWhisperContext {
+        model_id: model_id.to_string(),
+        context: Arc::clone(&context),
+    });
+    Ok(context)
+}
+
+async fn is_listening_to(app: &AppHandle, audio: &Arc<Mutex<Vec<f32>>>) -> bool {
+    let state_handle = app.state::<AppState>();
+    let dictation = state_handle.dictation.lock().await;
+    dictation.session_state == DictationSessionState::Listening
+        && dictation
+            .session
+            .as_ref()
+            .is_some_and(|session| Arc::ptr_eq(&session.audio, audio))
+}
+
+/// Periodically transcribes the audio captured since the last settled
+/// window and streams it, after the text of earlier windows, as `partial`
+/// events until the session stops listening. A window settles once it is
+/// `PARTIAL_WINDOW_SECONDS` long, so each pass transcribes a bounded tail.
+fn spawn_partial_transcripts(
+    app: AppHandle,
+    audio: Arc<Mutex<Vec<f32>>>,
+    sample_rate: u32,
+    model_id: String,
+    preferred_language: Option<String>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let min_new_samples = (sample_rate as usize) / 2;
+        let window_samples = (sample_rate * PARTIAL_WINDOW_SECONDS) as usize;
+        let mut window_start = 0usize;
+        let mut transcribed_len = 0usize;
+        let mut settled_text = String::new();
+        let mut last_text = String::new();
+        loop {
+            tokio::time::sleep(PARTIAL_TRANSCRIPT_INTERVAL).await;
+            if !is_listening_to(&app, &audio).await {
+                break;
+            }
+            let samples = match audio.lock() {
+                Ok(guard) if guard.len() >= transcribed_len + min_new_samples => {
+                    guard[window_start..].to_vec()
+                }
+                Ok(_) => continue,
+                Err(_) => break,
+            };
+            transcribed_len = window_start + samples.len();
+            let settles = samples.len() >= window_samples;
+            let context = match load_whisper_context(&app, &model_id).await {
+                Ok(context) => context,
+                Err(_) => break,
+            };
+            let preferred = preferred_language.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                transcribe_audio(samples, sample_rate, &context, preferred)
+            })
+            .await;
+            let Ok(Ok(window_text)) = result else {
+                continue;
+            };
+            let text = format!("{settled_text} {window_text}").trim().to_string();
+            if settles {
+                window_start = transcribed_len;
+                settled_text = text.clone();
+            }
+            if text.is_empty() || text == last_text || !is_listening_to(&app, &audio).await {
+                continue;
+            }
+            last_text = text.clone();
+            emit_event(&app, DictationEvent::Partial { text });
+        }
+    });
+}
+
 fn start_capture_thread(
     app: AppHandle,
     audio: Arc<Mutex<Vec<f32>>>,
@@ -46,6 +46,7 @@ pub(crate) enum DictationSessionState {
 pub(crate) enum DictationEvent {
     State { state: DictationSessionState },
     Level { value: f32 },
+    Partial { text: String },
     Transcript { text: String },
     Error { message: String },
     Canceled { message: String },
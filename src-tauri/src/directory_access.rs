@@ -0,0 +1,149 @@
+use std::path::Path;
+
+use chrono::Utc;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::approval_audit::{self, ApprovalAuditAction, ApprovalAuditEntry};
+use crate::backend::directory_grants::{self, DirectoryGrant};
+use crate::backend::request_id::RequestId;
+use crate::backend::turn_artifacts;
+use crate::codex;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::workspace_windows::emit_for_workspace;
+
+/// Directories a thread was granted, which its later turns get as extra
+/// writable roots.
+pub(crate) async fn granted_roots(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+) -> Vec<String> {
+    let grants = match state.data_cipher().await {
+        Ok(cipher) => directory_grants::read_directory_grants(
+            &directory_grants::directory_grants_path(&state.data_dir),
+            &cipher,
+        ),
+        Err(err) => Err(err),
+    };
+    match grants {
+        Ok(grants) => directory_grants::thread_grants(&grants, workspace_id, thread_id),
+        Err(err) => {
+            eprintln!("Failed to read directory grants: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Turns approval requests that ask to write under a directory outside the
+/// thread's writable roots into a `directory-access-requested` prompt.
+/// Remote workspaces are skipped, since their paths live on another
+/// machine.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(request) = directory_grants::requested_root(workspace_id, message) else {
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        if remote_backend::is_remote_mode(&state).await {
+            return;
+        }
+        let Some(workspace_path) = state
+            .workspaces
+            .lock()
+            .await
+            .get(&request.workspace_id)
+            .map(|entry| entry.path.clone())
+        else {
+            return;
+        };
+        if !state
+            .policy
+            .policy
+            .allows_writes_in(Path::new(&request.path))
+        {
+            return;
+        }
+        let mut roots = granted_roots(&state, &request.workspace_id, &request.thread_id).await;
+        roots.push(workspace_path);
+        roots.push(
+            turn_artifacts::thread_artifacts_dir(
+                &state.data_dir,
+                &request.workspace_id,
+                &request.thread_id,
+            )
+            .display()
+            .to_string(),
+        );
+        if directory_grants::covered_by(&roots, &request.path) {
+            return;
+        }
+        let workspace_id = request.workspace_id.clone();
+        emit_for_workspace(&app, "directory-access-requested", &workspace_id, request);
+    });
+}
+
+/// Answers a `directory-access-requested` prompt. Allowing it accepts the
+/// pending approval and adds the directory to the writable roots of the
+/// thread's later turns; either way the decision goes to the approval
+/// audit log.
+#[tauri::command]
+pub(crate) async fn answer_directory_access(
+    workspace_id: String,
+    thread_id: String,
+    request_id: RequestId,
+    path: String,
+    allow: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if allow && !state.policy.policy.allows_writes_in(Path::new(&path)) {
+        return Err(format!("Policy does not allow writes to {path}"));
+    }
+    let cipher = state.data_cipher().await?;
+    if allow {
+        let _guard = state.directory_grant_writes.lock().await;
+        directory_grants::add_directory_grant(
+            &directory_grants::directory_grants_path(&state.data_dir),
+            &cipher,
+            DirectoryGrant {
+                workspace_id: workspace_id.clone(),
+                thread_id: thread_id.clone(),
+                path: path.clone(),
+                granted_at: Utc::now().timestamp_millis(),
+            },
+        )?;
+    }
+    let decision = json!({ "decision": if allow { "accept" } else { "decline" } });
+    codex::respond_to_server_request(
+        workspace_id.clone(),
+        request_id.clone(),
+        decision.clone(),
+        None,
+        state.clone(),
+        app,
+    )
+    .await?;
+    let entry = ApprovalAuditEntry {
+        recorded_at: Utc::now().timestamp_millis(),
+        workspace_id,
+        request_id: Some(request_id),
+        delegation_id: None,
+        action: if allow {
+            ApprovalAuditAction::DirectoryGranted
+        } else {
+            ApprovalAuditAction::DirectoryDenied
+        },
+        actor: "local".to_string(),
+        method: None,
+        decision: Some(decision),
+        detail: Some(format!("thread {thread_id}: {path}")),
+    };
+    let _guard = state.audit_writes.lock().await;
+    if let Err(err) = approval_audit::append_entry(&state.data_dir, &cipher, entry) {
+        eprintln!("Failed to write approval audit log: {err}");
+    }
+    Ok(())
+}
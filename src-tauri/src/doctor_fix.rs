@@ -0,0 +1,451 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::time::timeout;
+use uuid::Uuid;
+
+use crate::backend::app_server::{build_codex_path_env, check_codex_installation};
+use crate::codex::check_node_installation;
+use crate::codex_home::resolve_default_codex_home;
+use crate::state::AppState;
+use crate::storage::write_settings;
+
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+const NVM_INSTALL_TIMEOUT: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DoctorIssueId {
+    /// Codex does not start with the configured binary or `PATH`, but an
+    /// installation was found elsewhere.
+    CodexNotOnPath,
+    /// Node is missing while nvm is installed.
+    NodeMissingNvm,
+    /// CODEX_HOME exists but is not writable.
+    CodexHomePermissions,
+}
+
+impl DoctorIssueId {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "codexNotOnPath" => Some(Self::CodexNotOnPath),
+            "nodeMissingNvm" => Some(Self::NodeMissingNvm),
+            "codexHomePermissions" => Some(Self::CodexHomePermissions),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DoctorIssue {
+    pub(crate) id: DoctorIssueId,
+    pub(crate) label: String,
+    pub(crate) detail: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DoctorFixOutput<'a> {
+    issue_id: DoctorIssueId,
+    stream: &'a str,
+    line: &'a str,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DoctorFixResult {
+    pub(crate) issue_id: DoctorIssueId,
+    pub(crate) ok: bool,
+    pub(crate) message: String,
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .filter(|home| !home.as_os_str().is_empty())
+}
+
+fn codex_file_names() -> &'static [&'static str] {
+    if cfg!(windows) {
+        &["codex.cmd", "codex.exe"]
+    } else {
+        &["codex"]
+    }
+}
+
+/// Codex binaries in the usual global install locations of npm, pnpm,
+/// yarn, volta, nvm and Homebrew.
+fn codex_candidates(home: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = [
+        ".npm-global/bin",
+        ".volta/bin",
+        ".yarn/bin",
+        ".bun/bin",
+        ".local/bin",
+        ".local/share/pnpm",
+        "Library/pnpm",
+        "AppData/Roaming/npm",
+    ]
+    .iter()
+    .map(|dir| home.join(dir))
+    .collect();
+    if let Ok(entries) = std::fs::read_dir(home.join(".nvm/versions/node")) {
+        let mut versions: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path().join("bin"))
+            .collect();
+        versions.sort();
+        dirs.extend(versions.into_iter().rev());
+    }
+    dirs.extend(["/opt/homebrew/bin", "/usr/local/bin"].map(PathBuf::from));
+    dirs.iter()
+        .flat_map(|dir| codex_file_names().iter().map(move |name| dir.join(name)))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Looks `codex` up in the user's login shell, whose `PATH` apps launched
+/// from the desktop do not inherit.
+async fn login_shell_codex() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return None;
+    }
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+    let mut command = Command::new(shell);
+    command
+        .args(["-lc", "command -v codex"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null());
+    let output = timeout(LOOKUP_TIMEOUT, command.output()).await.ok()?.ok()?;
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Some(PathBuf::from(path)).filter(|path| path.is_absolute() && path.is_file())
+}
+
+async fn detect_codex() -> Option<PathBuf> {
+    if let Some(path) = login_shell_codex().await {
+        return Some(path);
+    }
+    home_dir().and_then(|home| codex_candidates(&home).into_iter().next())
+}
+
+/// Every Codex installation found on this machine, the one on the login
+/// shell's `PATH` first.
+pub(crate) async fn detected_codex_bins() -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = login_shell_codex().await.into_iter().collect();
+    if let Some(home) = home_dir() {
+        for path in codex_candidates(&home) {
+            if !found.contains(&path) {
+                found.push(path);
+            }
+        }
+    }
+    found
+}
+
+fn nvm_dir() -> Option<PathBuf> {
+    std::env::var_os("NVM_DIR")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".nvm")))
+        .filter(|dir| dir.join("nvm.sh").is_file())
+}
+
+fn is_writable_dir(dir: &Path) -> bool {
+    let probe = dir.join(format!(".codex-monitor-write-check-{}", Uuid::new_v4()));
+    match std::fs::File::create(&probe) {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+fn unwritable_codex_home() -> Option<PathBuf> {
+    resolve_default_codex_home().filter(|home| home.is_dir() && !is_writable_dir(home))
+}
+
+async fn codex_bin(state: &AppState, codex_bin: Option<String>) -> Option<String> {
+    let default_bin = state.app_settings.lock().await.codex_bin.clone();
+    state.policy.policy.codex_bin(
+        codex_bin
+            .filter(|value| !value.trim().is_empty())
+            .or(default_bin),
+    )
+}
+
+fn emit_output(app: &AppHandle, issue_id: DoctorIssueId, stream: &str, line: &str) {
+    let _ = app.emit(
+        "doctor-fix-output",
+        DoctorFixOutput {
+            issue_id,
+            stream,
+            line,
+        },
+    );
+}
+
+async fn forward_lines(
+    app: AppHandle,
+    issue_id: DoctorIssueId,
+    stream: &'static str,
+    reader: impl AsyncRead + Unpin,
+) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        emit_output(&app, issue_id, stream, &line);
+    }
+}
+
+/// Runs `command`, emitting its output line by line; returns whether it
+/// exited successfully before `limit`.
+async fn run_streamed(
+    app: &AppHandle,
+    issue_id: DoctorIssueId,
+    mut command: Command,
+    limit: Duration,
+) -> Result<bool, String> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = command.spawn().map_err(|err| err.to_string())?;
+    let stdout = child.stdout.take().map(|stdout| {
+        tauri::async_runtime::spawn(forward_lines(app.clone(), issue_id, "stdout", stdout))
+    });
+    let stderr = child.stderr.take().map(|stderr| {
+        tauri::async_runtime::spawn(forward_lines(app.clone(), issue_id, "stderr", stderr))
+    });
+    let status = match timeout(limit, child.wait()).await {
+        Ok(status) => status.map_err(|err| err.to_string())?,
+        Err(_) => {
+            let _ = child.kill().await;
+            emit_output(app, issue_id, "info", "Timed out.");
+            return Ok(false);
+        }
+    };
+    for task in [stdout, stderr].into_iter().flatten() {
+        let _ = task.await;
+    }
+    Ok(status.success())
+}
+
+async fn fix_codex_not_on_path(
+    state: &AppState,
+    app: &AppHandle,
+) -> Result<DoctorFixResult, String> {
+    let issue_id = DoctorIssueId::CodexNotOnPath;
+    let path = detect_codex()
+        .await
+        .ok_or("No Codex installation was found.")?;
+    let path = path.to_string_lossy().to_string();
+    emit_output(app, issue_id, "info", &format!("Found Codex at {path}."));
+    let version = check_codex_installation(Some(path.clone())).await?;
+    if let Some(version) = &version {
+        emit_output(app, issue_id, "stdout", version);
+    }
+    let mut current = state.app_settings.lock().await;
+    let mut settings = current.clone();
+    settings.codex_bin = Some(path.clone());
+    write_settings(&state.settings_path, &settings)?;
+    *current = settings;
+    Ok(DoctorFixResult {
+        issue_id,
+        ok: true,
+        message: format!("Codex binary set to {path}."),
+    })
+}
+
+async fn fix_node_missing_nvm(
+    state: &AppState,
+    app: &AppHandle,
+) -> Result<DoctorFixResult, String> {
+    let issue_id = DoctorIssueId::NodeMissingNvm;
+    let nvm_dir = nvm_dir().ok_or("nvm is not installed.")?;
+    emit_output(
+        app,
+        issue_id,
+        "info",
+        "Installing the latest LTS release of Node with nvm.",
+    );
+    let mut command = Command::new("bash");
+    command
+        .args([
+            "-c",
+            ". \"$NVM_DIR/nvm.sh\" && nvm install --lts && nvm alias default 'lts/*'",
+        ])
+        .env("NVM_DIR", &nvm_dir);
+    let installed = run_streamed(app, issue_id, command, NVM_INSTALL_TIMEOUT).await?;
+    let codex_bin = codex_bin(state, None).await;
+    let path_env = build_codex_path_env(codex_bin.as_deref());
+    let (node_ok, node_version, node_details) = check_node_installation(path_env.as_deref()).await;
+    let message = match (installed && node_ok, node_version) {
+        (true, Some(version)) => format!("Node {version} is installed."),
+        (true, None) => "Node is installed.".to_string(),
+        (false, _) => node_details.unwrap_or_else(|| "nvm could not install Node.".to_string()),
+    };
+    Ok(DoctorFixResult {
+        issue_id,
+        ok: installed && node_ok,
+        message,
+    })
+}
+
+#[cfg(unix)]
+fn grant_user_access(path: &Path, failures: &mut Vec<String>) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return;
+    };
+    if metadata.file_type().is_symlink() {
+        return;
+    }
+    let bits = if metadata.is_dir() { 0o700 } else { 0o600 };
+    let mode = metadata.permissions().mode();
+    if mode & bits != bits {
+        let permissions = std::fs::Permissions::from_mode(mode | bits);
+        if let Err(err) = std::fs::set_permissions(path, permissions) {
+            failures.push(format!("{}: {err}", path.display()));
+        }
+    }
+    if metadata.is_dir() {
+        if let Ok(entries) = std::fs::read_dir(path) {
+            for entry in entries.flatten() {
+                grant_user_access(&entry.path(), failures);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn grant_user_access(path: &Path, failures: &mut Vec<String>) {
+    failures.push(format!(
+        "{}: changing permissions is not supported on this platform",
+        path.display()
+    ));
+}
+
+fn fix_codex_home_permissions(app: &AppHandle) -> Result<DoctorFixResult, String> {
+    let issue_id = DoctorIssueId::CodexHomePermissions;
+    let home = resolve_default_codex_home().ok_or("Unable to resolve CODEX_HOME")?;
+    emit_output(
+        app,
+        issue_id,
+        "info",
+        &format!(
+            "Granting your user read and write access to {}.",
+            home.display()
+        ),
+    );
+    let mut failures = Vec::new();
+    grant_user_access(&home, &mut failures);
+    for failure in &failures {
+        emit_output(app, issue_id, "stderr", failure);
+    }
+    let ok = is_writable_dir(&home);
+    let message = if ok {
+        format!("{} is writable.", home.display())
+    } else {
+        format!(
+            "{} is owned by another user; run `sudo chown -R \"$(whoami)\" \"{}\"` to take ownership.",
+            home.display(),
+            home.display()
+        )
+    };
+    Ok(DoctorFixResult {
+        issue_id,
+        ok,
+        message,
+    })
+}
+
+/// Lists the doctor failures `doctor_fix` can repair on this machine.
+#[tauri::command]
+pub(crate) async fn list_doctor_issues(
+    codex_bin: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<DoctorIssue>, String> {
+    let resolved = self::codex_bin(&state, codex_bin).await;
+    let mut issues = Vec::new();
+    if check_codex_installation(resolved.clone()).await.is_err() {
+        if let Some(path) = detect_codex().await {
+            issues.push(DoctorIssue {
+                id: DoctorIssueId::CodexNotOnPath,
+                label: "Use the Codex installation found on this machine".to_string(),
+                detail: Some(path.to_string_lossy().to_string()),
+            });
+        }
+    }
+    let path_env = build_codex_path_env(resolved.as_deref());
+    let (node_ok, _, _) = check_node_installation(path_env.as_deref()).await;
+    if !node_ok {
+        if let Some(dir) = nvm_dir() {
+            issues.push(DoctorIssue {
+                id: DoctorIssueId::NodeMissingNvm,
+                label: "Install Node with nvm".to_string(),
+                detail: Some(dir.to_string_lossy().to_string()),
+            });
+        }
+    }
+    if let Some(home) = unwritable_codex_home() {
+        issues.push(DoctorIssue {
+            id: DoctorIssueId::CodexHomePermissions,
+            label: "Fix permissions of CODEX_HOME".to_string(),
+            detail: Some(home.to_string_lossy().to_string()),
+        });
+    }
+    Ok(issues)
+}
+
+/// Applies the remediation of a doctor issue. Progress is streamed as
+/// `doctor-fix-output` events while the fix runs.
+#[tauri::command]
+pub(crate) async fn doctor_fix(
+    issue_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<DoctorFixResult, String> {
+    let issue_id = DoctorIssueId::parse(&issue_id)
+        .ok_or_else(|| format!("Unknown doctor issue: {issue_id}"))?;
+    match issue_id {
+        DoctorIssueId::CodexNotOnPath => fix_codex_not_on_path(&state, &app).await,
+        DoctorIssueId::NodeMissingNvm => fix_node_missing_nvm(&state, &app).await,
+        DoctorIssueId::CodexHomePermissions => fix_codex_home_permissions(&app),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_codex_in_global_install_locations() {
+        let home = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let volta = home.join(".volta/bin");
+        let nvm = home.join(".nvm/versions/node/v22.1.0/bin");
+        for dir in [&volta, &nvm] {
+            std::fs::create_dir_all(dir).expect("create dir");
+            for name in codex_file_names() {
+                std::fs::write(dir.join(name), "").expect("write binary");
+            }
+        }
+
+        let candidates = codex_candidates(&home);
+        assert_eq!(candidates.first(), Some(&volta.join(codex_file_names()[0])));
+        assert!(candidates.contains(&nvm.join(codex_file_names()[0])));
+        assert_eq!(
+            DoctorIssueId::parse("codexHomePermissions"),
+            Some(DoctorIssueId::CodexHomePermissions)
+        );
+        assert_eq!(DoctorIssueId::parse("other"), None);
+        let _ = std::fs::remove_dir_all(&home);
+    }
+}
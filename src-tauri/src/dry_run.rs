@@ -0,0 +1,40 @@
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::dry_run::ProposedPatch;
+use crate::best_of;
+use crate::patch_staging;
+use crate::state::AppState;
+
+/// Collects file changes proposed by dry-run turns and stages them once the
+/// turn completes; called for every `app-server-event`, local or forwarded
+/// from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let completed = match state.dry_runs.lock() {
+        Ok(mut dry_runs) => dry_runs.observe(workspace_id, message),
+        Err(_) => None,
+    };
+    if let Some(patch) = completed {
+        best_of::observe_dry_run(app, &patch);
+        patch_staging::stage_dry_run(app, patch);
+    }
+}
+
+/// Returns the un-applied changes a dry-run turn proposed in this session.
+#[tauri::command]
+pub(crate) async fn get_turn_proposed_patch(
+    workspace_id: String,
+    turn_id: String,
+    state: State<'_, AppState>,
+) -> Result<ProposedPatch, String> {
+    let dry_runs = state
+        .dry_runs
+        .lock()
+        .map_err(|_| "dry-run tracker unavailable")?;
+    dry_runs
+        .get(&workspace_id, &turn_id)
+        .ok_or_else(|| format!("No dry-run turn {turn_id} in this session"))
+}
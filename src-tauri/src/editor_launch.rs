@@ -0,0 +1,196 @@
+use std::ffi::OsStr;
+
+use serde::Serialize;
+use tauri::State;
+use tokio::process::Command;
+
+use crate::backend::workspace_validation::program_on_path;
+use crate::state::AppState;
+use crate::types::EditorTemplate;
+
+/// Editors known out of the box, in auto-detection order.
+const BUILTIN_EDITORS: [(&str, &str, &str); 6] = [
+    ("vscode", "Visual Studio Code", "code -g {path}:{line}"),
+    ("cursor", "Cursor", "cursor -g {path}:{line}"),
+    ("windsurf", "Windsurf", "windsurf -g {path}:{line}"),
+    ("zed", "Zed", "zed {path}:{line}"),
+    ("sublime", "Sublime Text", "subl {path}:{line}"),
+    ("idea", "IntelliJ IDEA", "idea --line {line} {path}"),
+];
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EditorInfo {
+    pub(crate) id: String,
+    pub(crate) label: String,
+    pub(crate) command: String,
+    /// Whether the command's program was found on `PATH`.
+    pub(crate) available: bool,
+}
+
+/// Built-in templates with the user's templates applied on top; a user
+/// template replaces the built-in one with the same id.
+fn editor_templates(custom: &[EditorTemplate]) -> Vec<EditorTemplate> {
+    let mut templates: Vec<EditorTemplate> = BUILTIN_EDITORS
+        .iter()
+        .map(|(id, label, command)| EditorTemplate {
+            id: id.to_string(),
+            label: label.to_string(),
+            command: command.to_string(),
+        })
+        .collect();
+    for template in custom {
+        match templates.iter_mut().find(|entry| entry.id == template.id) {
+            Some(entry) => *entry = template.clone(),
+            None => templates.push(template.clone()),
+        }
+    }
+    templates
+}
+
+fn template_available(template: &EditorTemplate, path_var: Option<&OsStr>) -> bool {
+    template
+        .command
+        .split_whitespace()
+        .next()
+        .is_some_and(|program| program_on_path(program, path_var))
+}
+
+/// Splits `template` into program and arguments, then fills in `{path}` and
+/// `{line}`. Substituting after splitting keeps paths with spaces intact.
+fn editor_command(template: &str, path: &str, line: u32) -> Result<Vec<String>, String> {
+    let args: Vec<String> = template
+        .split_whitespace()
+        .map(|arg| {
+            arg.replace("{path}", path)
+                .replace("{line}", &line.to_string())
+        })
+        .collect();
+    if args.is_empty() {
+        return Err("Editor command is empty.".to_string());
+    }
+    Ok(args)
+}
+
+/// The editor with id `preferred`, or the first one found on `PATH`.
+fn resolve_editor(
+    preferred: Option<&str>,
+    templates: Vec<EditorTemplate>,
+    path_var: Option<&OsStr>,
+) -> Result<EditorTemplate, String> {
+    match preferred {
+        Some(id) => templates
+            .into_iter()
+            .find(|template| template.id == id)
+            .ok_or_else(|| format!("Unknown editor `{id}`.")),
+        None => templates
+            .into_iter()
+            .find(|template| template_available(template, path_var))
+            .ok_or_else(|| {
+                "No supported editor found on PATH; choose one in settings.".to_string()
+            }),
+    }
+}
+
+/// Opens `path` at `line` in `editor`, the configured preferred editor, or
+/// the first editor detected on `PATH`.
+#[tauri::command]
+pub(crate) async fn open_in_editor(
+    path: String,
+    line: Option<u32>,
+    editor: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let (preferred, custom) = {
+        let settings = state.app_settings.lock().await;
+        (
+            settings.preferred_editor.clone(),
+            settings.editor_templates.clone(),
+        )
+    };
+    let preferred = editor.or(preferred).filter(|id| !id.trim().is_empty());
+    let path_var = std::env::var_os("PATH");
+    let template = resolve_editor(
+        preferred.as_deref(),
+        editor_templates(&custom),
+        path_var.as_deref(),
+    )?;
+    let args = editor_command(&template.command, &path, line.unwrap_or(1).max(1))?;
+    let status = Command::new(&args[0])
+        .args(&args[1..])
+        .status()
+        .await
+        .map_err(|err| format!("Failed to launch {}: {err}", template.label))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {status}", template.label))
+    }
+}
+
+/// Every known editor with whether it was detected on `PATH`.
+#[tauri::command]
+pub(crate) async fn list_editors(state: State<'_, AppState>) -> Result<Vec<EditorInfo>, String> {
+    let custom = state.app_settings.lock().await.editor_templates.clone();
+    let path_var = std::env::var_os("PATH");
+    Ok(editor_templates(&custom)
+        .into_iter()
+        .map(|template| EditorInfo {
+            available: template_available(&template, path_var.as_deref()),
+            id: template.id,
+            label: template.label,
+            command: template.command,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(id: &str, command: &str) -> EditorTemplate {
+        EditorTemplate {
+            id: id.to_string(),
+            label: id.to_string(),
+            command: command.to_string(),
+        }
+    }
+
+    #[test]
+    fn fills_templates_and_applies_overrides() {
+        assert_eq!(
+            editor_command("code -g {path}:{line}", "/work/my app/main.rs", 42).unwrap(),
+            vec!["code", "-g", "/work/my app/main.rs:42"]
+        );
+        assert!(editor_command("  ", "/a", 1).is_err());
+
+        let templates = editor_templates(&[
+            template("vscode", "code-insiders -g {path}:{line}"),
+            template("helix", "hx {path}:{line}"),
+        ]);
+        assert_eq!(templates.len(), BUILTIN_EDITORS.len() + 1);
+        assert_eq!(templates[0].command, "code-insiders -g {path}:{line}");
+        assert_eq!(
+            templates.last().map(|entry| entry.id.as_str()),
+            Some("helix")
+        );
+    }
+
+    #[test]
+    fn detects_the_first_editor_on_path() {
+        let dir = std::env::temp_dir().join(format!("editor-launch-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("zed"), "").unwrap();
+        let path_var = std::env::join_paths([&dir]).unwrap();
+
+        let detected = resolve_editor(None, editor_templates(&[]), Some(&path_var)).unwrap();
+        assert_eq!(detected.id, "zed");
+        let preferred =
+            resolve_editor(Some("sublime"), editor_templates(&[]), Some(&path_var)).unwrap();
+        assert_eq!(preferred.id, "sublime");
+        assert!(resolve_editor(Some("emacs"), editor_templates(&[]), Some(&path_var)).is_err());
+        assert!(resolve_editor(None, editor_templates(&[]), None).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
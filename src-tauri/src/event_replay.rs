@@ -0,0 +1,35 @@
+use tauri::State;
+
+use crate::backend::event_replay::EventBackfill;
+use crate::state::AppState;
+
+/// Records the last `app-server-event` sequence id a webview handled, which
+/// lets buffered approval requests up to it be dropped.
+#[tauri::command]
+pub(crate) async fn ack_events(
+    workspace_id: String,
+    seq: u64,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut buffer = state
+        .event_buffer
+        .lock()
+        .map_err(|_| "Event buffer unavailable".to_string())?;
+    buffer.ack(&workspace_id, seq);
+    Ok(())
+}
+
+/// Buffered `app-server-event` payloads of a workspace after `seq`, so a
+/// reloaded webview can replay what it missed.
+#[tauri::command]
+pub(crate) async fn get_events_since(
+    workspace_id: String,
+    seq: u64,
+    state: State<'_, AppState>,
+) -> Result<EventBackfill, String> {
+    let buffer = state
+        .event_buffer
+        .lock()
+        .map_err(|_| "Event buffer unavailable".to_string())?;
+    Ok(buffer.since(&workspace_id, seq))
+}
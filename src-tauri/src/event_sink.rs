@@ -1,6 +1,38 @@
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 
-use crate::backend::events::{AppServerEvent, EventSink, TerminalOutput};
+use crate::ab_test;
+use crate::approval_delegation;
+use crate::auth_expiry;
+use crate::backend::command_output::trim_event_output;
+use crate::backend::events::{
+    AppServerEvent, EventSink, NarrationEvent, SessionOutputLine, TerminalOutput,
+};
+use crate::backend::turn_summary::params_thread_id;
+use crate::code_stats;
+use crate::command_output;
+use crate::directory_access;
+use crate::dry_run;
+use crate::model_stats;
+use crate::offline_queue;
+use crate::patch_staging;
+use crate::plan_tracker;
+use crate::prompt_budget;
+use crate::prompt_insights;
+use crate::rate_limits;
+use crate::review_pair;
+use crate::semantic_search;
+use crate::team_activity;
+use crate::telemetry;
+use crate::thread_guard;
+use crate::thread_index;
+use crate::thread_journal;
+use crate::thread_links;
+use crate::thread_sla;
+use crate::turn_limiter;
+use crate::verification;
+use crate::workspace_health;
+use crate::workspace_turns;
+use crate::workspace_windows::{emit_app_server_event, emit_for_workspace};
 
 #[derive(Clone)]
 pub(crate) struct TauriEventSink {
@@ -14,11 +46,63 @@ impl TauriEventSink {
 }
 
 impl EventSink for TauriEventSink {
-    fn emit_app_server_event(&self, event: AppServerEvent) {
-        let _ = self.app.emit("app-server-event", event);
+    fn emit_app_server_event(&self, mut event: AppServerEvent) {
+        let workspace_id = event.workspace_id.clone();
+        let method = event
+            .message
+            .get("method")
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string());
+        let thread_id = event.message.get("params").and_then(params_thread_id);
+        ab_test::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        plan_tracker::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        prompt_budget::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        dry_run::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        patch_staging::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        command_output::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        code_stats::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        semantic_search::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        approval_delegation::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        verification::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        offline_queue::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        turn_limiter::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        rate_limits::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        thread_guard::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        thread_journal::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        review_pair::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        prompt_insights::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        thread_sla::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        directory_access::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        workspace_health::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        workspace_turns::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        model_stats::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        thread_links::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        auth_expiry::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        team_activity::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        telemetry::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        thread_index::observe_app_server_event(&self.app, &workspace_id, &event.message);
+        trim_event_output(&mut event.message);
+        emit_app_server_event(
+            &self.app,
+            &workspace_id,
+            method.as_deref(),
+            thread_id.as_deref(),
+            event,
+        );
     }
 
     fn emit_terminal_output(&self, event: TerminalOutput) {
-        let _ = self.app.emit("terminal-output", event);
+        let workspace_id = event.workspace_id.clone();
+        emit_for_workspace(&self.app, "terminal-output", &workspace_id, event);
+    }
+
+    fn emit_narration(&self, event: NarrationEvent) {
+        let workspace_id = event.workspace_id.clone();
+        emit_for_workspace(&self.app, "narration-event", &workspace_id, event);
+    }
+
+    fn emit_session_output(&self, event: SessionOutputLine) {
+        let workspace_id = event.workspace_id.clone();
+        emit_for_workspace(&self.app, "session-output", &workspace_id, event);
     }
 }
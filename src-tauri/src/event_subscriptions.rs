@@ -0,0 +1,249 @@
+use std::collections::{HashMap, HashSet};
+
+use tauri::{State, Window};
+
+use crate::state::AppState;
+
+#[derive(Debug, Clone, Default)]
+struct EventSubscription {
+    /// `None` matches every workspace.
+    workspace_ids: Option<HashSet<String>>,
+    /// Exact method names, or prefixes ending in `*` such as `item/*`.
+    /// `None` matches every method.
+    methods: Option<Vec<String>>,
+}
+
+fn method_matches(pattern: &str, method: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix),
+        None => pattern == method,
+    }
+}
+
+impl EventSubscription {
+    fn accepts(&self, workspace_id: &str, method: Option<&str>) -> bool {
+        let workspace_ok = self
+            .workspace_ids
+            .as_ref()
+            .is_none_or(|ids| ids.contains(workspace_id));
+        let method_ok = match (&self.methods, method) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(patterns), Some(method)) => {
+                patterns.iter().any(|pattern| method_matches(pattern, method))
+            }
+        };
+        workspace_ok && method_ok
+    }
+}
+
+/// App-server event filters registered by each window. A window without
+/// subscriptions receives every event, so existing listeners keep working.
+#[derive(Debug, Default)]
+pub(crate) struct EventSubscriptions {
+    next_id: u64,
+    by_window: HashMap<String, HashMap<u64, EventSubscription>>,
+}
+
+impl EventSubscriptions {
+    fn subscribe(&mut self, window: &str, subscription: EventSubscription) -> u64 {
+        self.next_id += 1;
+        self.by_window
+            .entry(window.to_string())
+            .or_default()
+            .insert(self.next_id, subscription);
+        self.next_id
+    }
+
+    fn unsubscribe(&mut self, window: &str, id: Option<u64>) -> bool {
+        let Some(subscriptions) = self.by_window.get_mut(window) else {
+            return false;
+        };
+        let removed = match id {
+            Some(id) => subscriptions.remove(&id).is_some(),
+            None => {
+                let had_any = !subscriptions.is_empty();
+                subscriptions.clear();
+                had_any
+            }
+        };
+        if subscriptions.is_empty() {
+            self.by_window.remove(window);
+        }
+        removed
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.by_window.is_empty()
+    }
+
+    pub(crate) fn forget_window(&mut self, window: &str) {
+        self.by_window.remove(window);
+    }
+
+    /// Whether `window` wants an app-server event for this workspace/method.
+    pub(crate) fn accepts(&self, window: &str, workspace_id: &str, method: Option<&str>) -> bool {
+        match self.by_window.get(window) {
+            None => true,
+            Some(subscriptions) => subscriptions
+                .values()
+                .any(|subscription| subscription.accepts(workspace_id, method)),
+        }
+    }
+}
+
+/// Notification methods muted per thread, e.g. token deltas of background
+/// threads. Muted events still reach the backend observers and the replay
+/// buffer; only the emit to the UI is skipped.
+#[derive(Debug, Default)]
+pub(crate) struct ThreadEventFilters {
+    by_thread: HashMap<(String, String), Vec<String>>,
+}
+
+impl ThreadEventFilters {
+    /// Replaces the muted methods of a thread; an empty list unmutes it.
+    fn set(&mut self, workspace_id: &str, thread_id: &str, methods: Vec<String>) {
+        let key = (workspace_id.to_string(), thread_id.to_string());
+        let methods: Vec<String> = methods
+            .into_iter()
+            .map(|method| method.trim().to_string())
+            .filter(|method| !method.is_empty())
+            .collect();
+        if methods.is_empty() {
+            self.by_thread.remove(&key);
+        } else {
+            self.by_thread.insert(key, methods);
+        }
+    }
+
+    /// Whether the event should be dropped before it is emitted. Server
+    /// requests are never muted: a hidden approval would stall the turn.
+    pub(crate) fn mutes(
+        &self,
+        workspace_id: &str,
+        thread_id: Option<&str>,
+        method: Option<&str>,
+        request: bool,
+    ) -> bool {
+        let (Some(thread_id), Some(method), false) = (thread_id, method, request) else {
+            return false;
+        };
+        self.by_thread
+            .get(&(workspace_id.to_string(), thread_id.to_string()))
+            .is_some_and(|patterns| {
+                patterns.iter().any(|pattern| method_matches(pattern, method))
+            })
+    }
+}
+
+/// Registers an app-server event filter for the calling window and returns
+/// its id. Events that match none of a window's filters are not sent to it.
+#[tauri::command]
+pub(crate) async fn subscribe_events(
+    workspace_ids: Option<Vec<String>>,
+    methods: Option<Vec<String>>,
+    state: State<'_, AppState>,
+    window: Window,
+) -> Result<u64, String> {
+    let subscription = EventSubscription {
+        workspace_ids: workspace_ids.map(|ids| ids.into_iter().collect()),
+        methods,
+    };
+    let mut subscriptions = state
+        .event_subscriptions
+        .lock()
+        .map_err(|err| err.to_string())?;
+    Ok(subscriptions.subscribe(window.label(), subscription))
+}
+
+/// Removes one filter, or every filter of the calling window when `id` is
+/// omitted.
+#[tauri::command]
+pub(crate) async fn unsubscribe_events(
+    id: Option<u64>,
+    state: State<'_, AppState>,
+    window: Window,
+) -> Result<bool, String> {
+    let mut subscriptions = state
+        .event_subscriptions
+        .lock()
+        .map_err(|err| err.to_string())?;
+    Ok(subscriptions.unsubscribe(window.label(), id))
+}
+
+/// Mutes notification methods (exact names or `prefix*` patterns) for one
+/// thread in every window. Pass an empty list to unmute the thread.
+#[tauri::command]
+pub(crate) async fn set_thread_event_filter(
+    workspace_id: String,
+    thread_id: String,
+    muted_methods: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut filters = state
+        .thread_event_filters
+        .lock()
+        .map_err(|err| err.to_string())?;
+    filters.set(&workspace_id, &thread_id, muted_methods);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_by_workspace_and_method_patterns() {
+        let mut subscriptions = EventSubscriptions::default();
+        assert!(subscriptions.accepts("main", "ws-1", Some("turn/started")));
+
+        let id = subscriptions.subscribe(
+            "main",
+            EventSubscription {
+                workspace_ids: Some(HashSet::from(["ws-1".to_string()])),
+                methods: Some(vec!["turn/completed".to_string(), "item/*".to_string()]),
+            },
+        );
+        assert!(subscriptions.accepts("main", "ws-1", Some("item/agentMessage/delta")));
+        assert!(subscriptions.accepts("main", "ws-1", Some("turn/completed")));
+        assert!(!subscriptions.accepts("main", "ws-1", Some("turn/started")));
+        assert!(!subscriptions.accepts("main", "ws-2", Some("turn/completed")));
+        assert!(subscriptions.accepts("other", "ws-2", Some("turn/started")));
+
+        subscriptions.subscribe("main", EventSubscription::default());
+        assert!(subscriptions.accepts("main", "ws-2", Some("turn/started")));
+
+        assert!(subscriptions.unsubscribe("main", Some(id)));
+        assert!(subscriptions.unsubscribe("main", None));
+        assert!(subscriptions.is_empty());
+    }
+
+    #[test]
+    fn mutes_methods_per_thread() {
+        let mut filters = ThreadEventFilters::default();
+        filters.set(
+            "ws-1",
+            "thread-1",
+            vec!["item/agentMessage/delta".to_string(), "item/reasoning/*".to_string()],
+        );
+        let muted = |method| filters.mutes("ws-1", Some("thread-1"), Some(method), false);
+        assert!(muted("item/agentMessage/delta"));
+        assert!(muted("item/reasoning/summaryTextDelta"));
+        assert!(!muted("turn/completed"));
+        let delta = Some("item/agentMessage/delta");
+        assert!(!filters.mutes("ws-1", Some("thread-2"), delta, false));
+        assert!(!filters.mutes("ws-1", None, delta, false));
+
+        filters.set("ws-1", "thread-1", Vec::new());
+        assert!(!filters.mutes("ws-1", Some("thread-1"), delta, false));
+    }
+
+    #[test]
+    fn never_mutes_server_requests() {
+        let mut filters = ThreadEventFilters::default();
+        filters.set("ws-1", "thread-1", vec!["item/*".to_string()]);
+        let approval = Some("item/commandExecution/requestApproval");
+        assert!(filters.mutes("ws-1", Some("thread-1"), approval, false));
+        assert!(!filters.mutes("ws-1", Some("thread-1"), approval, true));
+    }
+}
@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+use tauri::State;
+
+use crate::state::AppState;
+
+/// How long a detected Focus state is reused before the OS is asked again.
+const FOCUS_CACHE_TTL: Duration = Duration::from_secs(5);
+const DETECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum NotificationKind {
+    Approval,
+    TurnCompleted,
+    TurnError,
+    SlaBreach,
+    HealthReport,
+    /// A thread another one was blocked on completed a turn.
+    ThreadUnblocked,
+    /// The account's sign-in expired and turns are paused.
+    AuthExpired,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum NotificationDelivery {
+    /// Sound or OS notification as usual.
+    System,
+    /// Only listed in the in-app activity feed.
+    ActivityFeed,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FocusState {
+    pub(crate) do_not_disturb: bool,
+    /// Which OS setting reported do-not-disturb; `None` when it could not be
+    /// read, which is treated as off.
+    pub(crate) source: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NotificationRoute {
+    pub(crate) delivery: NotificationDelivery,
+    pub(crate) do_not_disturb: bool,
+    /// Delivered despite do-not-disturb because of a workspace override.
+    pub(crate) broke_through: bool,
+}
+
+fn route(
+    kind: NotificationKind,
+    do_not_disturb: bool,
+    approvals_break_through: bool,
+) -> NotificationRoute {
+    let broke_through =
+        do_not_disturb && approvals_break_through && kind == NotificationKind::Approval;
+    let delivery = if do_not_disturb && !broke_through {
+        NotificationDelivery::ActivityFeed
+    } else {
+        NotificationDelivery::System
+    };
+    NotificationRoute {
+        delivery,
+        do_not_disturb,
+        broke_through,
+    }
+}
+
+/// A Focus is on when macOS holds an assertion for it in
+/// `~/Library/DoNotDisturb/DB/Assertions.json`.
+fn macos_assertions_active(assertions: &Value) -> bool {
+    assertions
+        .get("data")
+        .and_then(|data| data.as_array())
+        .is_some_and(|data| {
+            data.iter().any(|entry| {
+                entry
+                    .get("storeAssertionRecords")
+                    .and_then(|records| records.as_array())
+                    .is_some_and(|records| !records.is_empty())
+            })
+        })
+}
+
+async fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let mut command = tokio::process::Command::new(program);
+    command
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    let output = tokio::time::timeout(DETECT_TIMEOUT, command.output())
+        .await
+        .ok()?
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+async fn detect_focus_state() -> FocusState {
+    let detected = if cfg!(target_os = "macos") {
+        std::env::var_os("HOME")
+            .map(|home| {
+                std::path::PathBuf::from(home).join("Library/DoNotDisturb/DB/Assertions.json")
+            })
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|data| serde_json::from_str::<Value>(&data).ok())
+            .map(|assertions| (macos_assertions_active(&assertions), "macosFocus"))
+    } else if cfg!(target_os = "windows") {
+        // Windows 11 do-not-disturb and Focus Assist turn toasts off globally.
+        command_output(
+            "reg",
+            &[
+                "query",
+                r"HKCU\Software\Microsoft\Windows\CurrentVersion\Notifications\Settings",
+                "/v",
+                "NOC_GLOBAL_SETTING_TOASTS_ENABLED",
+            ],
+        )
+        .await
+        .map(|output| (output.trim_end().ends_with("0x0"), "windowsDoNotDisturb"))
+    } else {
+        command_output(
+            "gsettings",
+            &["get", "org.gnome.desktop.notifications", "show-banners"],
+        )
+        .await
+        .map(|output| (output.trim() == "false", "gnomeDoNotDisturb"))
+    };
+    match detected {
+        Some((true, source)) => FocusState {
+            do_not_disturb: true,
+            source: Some(source.to_string()),
+        },
+        _ => FocusState::default(),
+    }
+}
+
+async fn focus_state(state: &AppState) -> FocusState {
+    if let Ok(cached) = state.focus_state.lock() {
+        if let Some((checked_at, focus)) = cached.as_ref() {
+            if checked_at.elapsed() < FOCUS_CACHE_TTL {
+                return focus.clone();
+            }
+        }
+    }
+    let focus = detect_focus_state().await;
+    if let Ok(mut cached) = state.focus_state.lock() {
+        *cached = Some((Instant::now(), focus.clone()));
+    }
+    focus
+}
+
+/// Reports whether the OS is in do-not-disturb (macOS Focus, Windows
+/// do-not-disturb / Focus Assist, GNOME).
+#[tauri::command]
+pub(crate) async fn get_focus_state(state: State<'_, AppState>) -> Result<FocusState, String> {
+    Ok(focus_state(&state).await)
+}
+
+/// Decides how a notification for a workspace is delivered. During
+/// do-not-disturb it is downgraded to the activity feed, except approvals of
+/// workspaces that let them break through.
+#[tauri::command]
+pub(crate) async fn get_notification_route(
+    workspace_id: String,
+    kind: NotificationKind,
+    state: State<'_, AppState>,
+) -> Result<NotificationRoute, String> {
+    let approvals_break_through = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .is_some_and(|entry| entry.settings.approvals_break_through_dnd);
+    let focus = focus_state(&state).await;
+    Ok(route(kind, focus.do_not_disturb, approvals_break_through))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn downgrades_during_dnd_unless_approvals_break_through() {
+        let normal = route(NotificationKind::TurnCompleted, false, false);
+        assert_eq!(normal.delivery, NotificationDelivery::System);
+
+        let quiet = route(NotificationKind::TurnCompleted, true, true);
+        assert_eq!(quiet.delivery, NotificationDelivery::ActivityFeed);
+        assert!(!quiet.broke_through);
+
+        let approval = route(NotificationKind::Approval, true, true);
+        assert_eq!(approval.delivery, NotificationDelivery::System);
+        assert!(approval.broke_through);
+        assert_eq!(
+            route(NotificationKind::Approval, true, false).delivery,
+            NotificationDelivery::ActivityFeed
+        );
+    }
+
+    #[test]
+    fn reads_macos_focus_assertions() {
+        let active = json!({ "data": [{ "storeAssertionRecords": [
+            { "assertionDetails": { "assertionDetailsModeIdentifier": "com.apple.donotdisturb.mode.default" } }
+        ] }] });
+        assert!(macos_assertions_active(&active));
+        assert!(!macos_assertions_active(&json!({ "data": [{}] })));
+    }
+}
@@ -0,0 +1,244 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+
+use crate::access_control;
+use crate::approval_delegation;
+use crate::backend::approval_audit::{ApprovalAuditAction, ApprovalAuditEntry};
+use crate::backend::full_access::{self, FullAccessGrant, MAX_GRANT_MINUTES};
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::rpc_response::{response_error, response_result};
+use crate::codex;
+use crate::state::AppState;
+use crate::workspace_windows::emit_for_workspace;
+
+/// How often expired grants are reverted and logged.
+const REVERT_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Payload of `full-access-changed`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct FullAccessChanged {
+    workspace_id: String,
+    thread_id: String,
+    /// `None` once the thread is back to its own access mode.
+    grant: Option<FullAccessGrant>,
+}
+
+async fn read_grants(state: &AppState) -> Result<Vec<FullAccessGrant>, String> {
+    let cipher = state.data_cipher().await?;
+    full_access::read_grants(
+        &full_access::full_access_grants_path(&state.data_dir),
+        &cipher,
+    )
+}
+
+async fn record(state: &AppState, action: ApprovalAuditAction, grant: &FullAccessGrant) {
+    approval_delegation::record(
+        state,
+        ApprovalAuditEntry {
+            recorded_at: Utc::now().timestamp_millis(),
+            workspace_id: grant.workspace_id.clone(),
+            request_id: None,
+            delegation_id: None,
+            action,
+            actor: "local".to_string(),
+            method: None,
+            decision: Some(json!({ "expiresAt": grant.expires_at })),
+            detail: Some(format!("thread {}", grant.thread_id)),
+        },
+    )
+    .await;
+}
+
+/// Full access follows the same rules as starting a turn: the workspace must
+/// exist and be unlocked, and the thread must belong to it.
+async fn ensure_grantable(
+    workspace_id: &str,
+    thread_id: &str,
+    state: &State<'_, AppState>,
+    app: &AppHandle,
+) -> Result<(), String> {
+    if !state.workspaces.lock().await.contains_key(workspace_id) {
+        return Err(localize(MessageCode::WorkspaceNotFound));
+    }
+    access_control::ensure_workspace_unlocked(state, workspace_id).await?;
+    let response = codex::resume_thread(
+        workspace_id.to_string(),
+        thread_id.to_string(),
+        state.clone(),
+        app.clone(),
+    )
+    .await?;
+    if let Some(error) = response_error(&response) {
+        return Err(error);
+    }
+    let found = response_result(&response)
+        .get("thread")
+        .and_then(|thread| thread.get("id"))
+        .and_then(|value| value.as_str())
+        == Some(thread_id);
+    if !found {
+        return Err(format!(
+            "Thread {thread_id} was not found in this workspace."
+        ));
+    }
+    Ok(())
+}
+
+fn emit_changed(app: &AppHandle, grant: &FullAccessGrant, active: bool) {
+    emit_for_workspace(
+        app,
+        "full-access-changed",
+        &grant.workspace_id,
+        FullAccessChanged {
+            workspace_id: grant.workspace_id.clone(),
+            thread_id: grant.thread_id.clone(),
+            grant: active.then(|| grant.clone()),
+        },
+    );
+}
+
+/// The access mode a turn runs with: full access while the thread has an
+/// unexpired grant, unless the turn asked to be read-only.
+pub(crate) async fn elevate_access_mode(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+    access_mode: Option<String>,
+) -> Option<String> {
+    if access_mode.as_deref() == Some("read-only") {
+        return access_mode;
+    }
+    let grants = match read_grants(state).await {
+        Ok(grants) => grants,
+        Err(err) => {
+            eprintln!("Failed to read full access grants: {err}");
+            return access_mode;
+        }
+    };
+    let now = Utc::now().timestamp_millis();
+    if full_access::active_grant(&grants, workspace_id, thread_id, now).is_some() {
+        Some("full-access".to_string())
+    } else {
+        access_mode
+    }
+}
+
+async fn revert_expired(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let expired = {
+        let _guard = state.full_access_grant_writes.lock().await;
+        let path = full_access::full_access_grants_path(&state.data_dir);
+        let cipher = state.data_cipher().await?;
+        let mut grants = full_access::read_grants(&path, &cipher)?;
+        let expired = full_access::take_expired(&mut grants, Utc::now().timestamp_millis());
+        if !expired.is_empty() {
+            full_access::write_grants(&path, &cipher, &grants)?;
+        }
+        expired
+    };
+    for grant in expired {
+        record(&state, ApprovalAuditAction::FullAccessReverted, &grant).await;
+        emit_changed(app, &grant, false);
+    }
+    Ok(())
+}
+
+/// Reverts and logs grants once their window passes, including ones that
+/// expired while the app was closed.
+pub(crate) fn spawn_full_access_reverter(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(err) = revert_expired(&app).await {
+                eprintln!("Failed to revert full access grants: {err}");
+            }
+            tokio::time::sleep(REVERT_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Runs the thread's turns with full access for the next `minutes`, then
+/// reverts to the thread's own access mode. Restricted workspaces must be
+/// unlocked with the app passphrase first. Granting again restarts the
+/// window. Grants and reverts go to the approval audit log.
+#[tauri::command]
+pub(crate) async fn grant_full_access(
+    workspace_id: String,
+    thread_id: String,
+    minutes: u32,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<FullAccessGrant, String> {
+    if minutes == 0 || minutes > MAX_GRANT_MINUTES {
+        return Err(format!(
+            "Full access can be granted for 1 to {MAX_GRANT_MINUTES} minutes."
+        ));
+    }
+    state.policy.policy.check_full_access("full-access")?;
+    ensure_grantable(&workspace_id, &thread_id, &state, &app).await?;
+    let now = Utc::now().timestamp_millis();
+    let grant = FullAccessGrant {
+        workspace_id,
+        thread_id,
+        granted_at: now,
+        expires_at: now + i64::from(minutes) * 60_000,
+    };
+    {
+        let _guard = state.full_access_grant_writes.lock().await;
+        let path = full_access::full_access_grants_path(&state.data_dir);
+        let cipher = state.data_cipher().await?;
+        let mut grants = full_access::read_grants(&path, &cipher)?;
+        full_access::upsert_grant(&mut grants, grant.clone());
+        full_access::write_grants(&path, &cipher, &grants)?;
+    }
+    record(&state, ApprovalAuditAction::FullAccessGranted, &grant).await;
+    emit_changed(&app, &grant, true);
+    Ok(grant)
+}
+
+/// Ends a thread's full access grant before its window passes.
+#[tauri::command]
+pub(crate) async fn revoke_full_access(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let removed = {
+        let _guard = state.full_access_grant_writes.lock().await;
+        let path = full_access::full_access_grants_path(&state.data_dir);
+        let cipher = state.data_cipher().await?;
+        let mut grants = full_access::read_grants(&path, &cipher)?;
+        let removed = full_access::remove_grant(&mut grants, &workspace_id, &thread_id);
+        full_access::write_grants(&path, &cipher, &grants)?;
+        removed
+    };
+    if let Some(grant) = removed {
+        record(&state, ApprovalAuditAction::FullAccessReverted, &grant).await;
+        emit_changed(&app, &grant, false);
+    }
+    Ok(())
+}
+
+/// Unexpired full access grants, optionally of one workspace.
+#[tauri::command]
+pub(crate) async fn list_full_access_grants(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<FullAccessGrant>, String> {
+    let now = Utc::now().timestamp_millis();
+    Ok(read_grants(&state)
+        .await?
+        .into_iter()
+        .filter(|grant| grant.is_active(now))
+        .filter(|grant| {
+            workspace_id
+                .as_deref()
+                .is_none_or(|workspace_id| grant.workspace_id == workspace_id)
+        })
+        .collect())
+}
@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use git2::{BranchType, DiffOptions, Repository, Sort, Status, StatusOptions};
+use git2::{BranchType, Delta, DiffOptions, Repository, Sort, Status, StatusOptions};
 use serde_json::json;
 use tauri::State;
 use tokio::process::Command;
@@ -10,6 +10,7 @@ use crate::git_utils::{
     list_git_roots as scan_git_roots, parse_github_repo, resolve_git_root,
 };
 use crate::state::AppState;
+use crate::turn_labels;
 use crate::types::{
     BranchInfo, GitCommitDiff, GitFileDiff, GitFileStatus, GitHubIssue, GitHubIssuesResponse,
     GitHubPullRequest, GitHubPullRequestComment, GitHubPullRequestDiff,
@@ -268,7 +269,156 @@ fn collect_workspace_diff(repo_root: &Path) -> Result<String, String> {
     Ok(build_combined_diff(&diff))
 }
 
-fn github_repo_from_path(path: &Path) -> Result<String, String> {
+fn diff_changes(diff: &git2::Diff) -> Vec<(String, Delta)> {
+    diff.deltas()
+        .filter_map(|delta| {
+            let path = delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())?;
+            Some((
+                normalize_git_path(&path.to_string_lossy()),
+                delta.status(),
+            ))
+        })
+        .collect()
+}
+
+fn collect_staged_changes(repo: &Repository) -> Result<Vec<(String, Delta)>, String> {
+    let head_tree = repo
+        .head()
+        .ok()
+        .and_then(|head| head.peel_to_tree().ok());
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let mut options = DiffOptions::new();
+    let diff = repo
+        .diff_tree_to_index(head_tree.as_ref(), Some(&index), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    Ok(diff_changes(&diff))
+}
+
+fn collect_workspace_changes(repo_root: &Path) -> Result<Vec<(String, Delta)>, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let staged = collect_staged_changes(&repo)?;
+    if !staged.is_empty() {
+        return Ok(staged);
+    }
+    let head_tree = repo
+        .head()
+        .ok()
+        .and_then(|head| head.peel_to_tree().ok());
+    let mut options = DiffOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    Ok(diff_changes(&diff))
+}
+
+fn is_docs_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    lower.ends_with(".md") || lower.ends_with(".txt") || lower.starts_with("docs/")
+}
+
+fn is_test_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    lower.contains("/tests/")
+        || lower.starts_with("tests/")
+        || lower.contains(".test.")
+        || lower.contains(".spec.")
+        || lower.contains("_test.")
+}
+
+fn is_chore_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    let name = lower.rsplit('/').next().unwrap_or(&lower);
+    lower.starts_with(".github/")
+        || name.ends_with(".lock")
+        || name == "package-lock.json"
+        || name == "cargo.toml"
+        || name == "package.json"
+        || name.starts_with(".")
+}
+
+fn commit_scope(paths: &[&str]) -> Option<String> {
+    let parents = paths
+        .iter()
+        .map(|path| match path.rsplit_once('/') {
+            Some((parent, _)) => parent,
+            None => "",
+        })
+        .collect::<Vec<_>>();
+    let first = parents.first()?.split('/').collect::<Vec<_>>();
+    let mut common = first.len();
+    for parent in parents.iter().skip(1) {
+        let parts = parent.split('/').collect::<Vec<_>>();
+        common = common.min(
+            first
+                .iter()
+                .zip(parts.iter())
+                .take_while(|(a, b)| a == b)
+                .count(),
+        );
+    }
+    first[..common]
+        .iter()
+        .rev()
+        .find(|segment| !segment.is_empty() && **segment != "src")
+        .map(|segment| segment.to_lowercase())
+}
+
+/// Builds a conventional-commit message from the changed paths using
+/// file kinds and change types, without calling the agent.
+fn suggest_commit_message_for_changes(changes: &[(String, Delta)]) -> Option<String> {
+    if changes.is_empty() {
+        return None;
+    }
+    let paths = changes.iter().map(|(path, _)| path.as_str()).collect::<Vec<_>>();
+    let all_added = changes.iter().all(|(_, status)| *status == Delta::Added);
+    let all_deleted = changes.iter().all(|(_, status)| *status == Delta::Deleted);
+    let all_renamed = changes.iter().all(|(_, status)| *status == Delta::Renamed);
+    let kind = if paths.iter().all(|path| is_docs_path(path)) {
+        "docs"
+    } else if paths.iter().all(|path| is_test_path(path)) {
+        "test"
+    } else if paths.iter().all(|path| is_chore_path(path)) {
+        "chore"
+    } else if changes
+        .iter()
+        .any(|(path, status)| *status == Delta::Added && !is_test_path(path))
+    {
+        "feat"
+    } else if all_deleted || all_renamed {
+        "refactor"
+    } else {
+        "fix"
+    };
+    let verb = if all_added {
+        "add"
+    } else if all_deleted {
+        "remove"
+    } else if all_renamed {
+        "rename"
+    } else {
+        "update"
+    };
+    let target = if paths.len() == 1 {
+        paths[0].rsplit('/').next().unwrap_or(paths[0]).to_string()
+    } else {
+        format!("{} files", paths.len())
+    };
+    let prefix = match commit_scope(&paths).filter(|scope| scope != kind) {
+        Some(scope) => format!("{kind}({scope})"),
+        None => kind.to_string(),
+    };
+    let mut message = format!("{prefix}: {verb} {target}");
+    if message.chars().count() > 72 {
+        message = message.chars().take(69).collect::<String>() + "...";
+    }
+    Some(message)
+}
+
+pub(crate) fn github_repo_from_path(path: &Path) -> Result<String, String> {
     let repo = Repository::open(path).map_err(|e| e.to_string())?;
     let remotes = repo.remotes().map_err(|e| e.to_string())?;
     let name = if remotes.iter().any(|remote| remote == Some("origin")) {
@@ -629,10 +779,51 @@ pub(crate) async fn commit_git(
         .ok_or("workspace not found")?
         .clone();
 
+    drop(workspaces);
+
     let repo_root = resolve_git_root(&entry)?;
+    let (message, head) = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let message = if message.trim().is_empty() {
+            suggest_commit_message_for_changes(&collect_staged_changes(&repo)?)
+                .ok_or("No staged changes to commit.")?
+        } else {
+            message
+        };
+        (message, head_sha(&repo))
+    };
+    let message =
+        turn_labels::with_commit_labels(&state, &workspace_id, head.as_deref(), message).await;
     run_git_command(&repo_root, &["commit", "-m", &message]).await
 }
 
+#[tauri::command]
+pub(crate) async fn suggest_commit_message(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let changes = collect_workspace_changes(&repo_root)?;
+    let message = suggest_commit_message_for_changes(&changes)
+        .ok_or_else(|| "No changes to generate commit message for".to_string())?;
+    let head = Repository::open(&repo_root)
+        .ok()
+        .and_then(|repo| head_sha(&repo));
+    Ok(turn_labels::with_commit_labels(&state, &workspace_id, head.as_deref(), message).await)
+}
+
+fn head_sha(repo: &Repository) -> Option<String> {
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
+
 #[tauri::command]
 pub(crate) async fn push_git(
     workspace_id: String,
@@ -1301,6 +1492,44 @@ mod tests {
         (root, repo)
     }
 
+    #[test]
+    fn suggest_commit_message_uses_change_kinds() {
+        let docs = vec![("docs/guide.md".to_string(), Delta::Modified)];
+        assert_eq!(
+            suggest_commit_message_for_changes(&docs).as_deref(),
+            Some("docs: update guide.md")
+        );
+
+        let feature = vec![
+            ("src/features/git/useGit.ts".to_string(), Delta::Added),
+            ("src/features/git/GitPanel.tsx".to_string(), Delta::Modified),
+        ];
+        assert_eq!(
+            suggest_commit_message_for_changes(&feature).as_deref(),
+            Some("feat(git): update 2 files")
+        );
+
+        let fix = vec![("main.rs".to_string(), Delta::Modified)];
+        assert_eq!(
+            suggest_commit_message_for_changes(&fix).as_deref(),
+            Some("fix: update main.rs")
+        );
+        assert!(suggest_commit_message_for_changes(&[]).is_none());
+    }
+
+    #[test]
+    fn collect_workspace_changes_prefers_staged_changes() {
+        let (root, repo) = create_temp_repo();
+        fs::write(root.join("staged.txt"), "staged\n").expect("write staged file");
+        fs::write(root.join("untracked.txt"), "untracked\n").expect("write untracked file");
+        let mut index = repo.index().expect("index");
+        index.add_path(Path::new("staged.txt")).expect("add path");
+        index.write().expect("write index");
+
+        let changes = collect_workspace_changes(&root).expect("collect changes");
+        assert_eq!(changes, vec![("staged.txt".to_string(), Delta::Added)]);
+    }
+
     #[test]
     fn collect_workspace_diff_prefers_staged_changes() {
         let (root, repo) = create_temp_repo();
@@ -0,0 +1,705 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, State};
+use tokio::process::Command;
+
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::issue_links::{self, ThreadIssueLink};
+use crate::backend::turn_compare::{diff_line_counts, find_last_turn, TurnSnapshot};
+use crate::codex::{self, SendOptions};
+use crate::git::github_repo_from_path;
+use crate::git_utils::resolve_git_root;
+use crate::state::AppState;
+use crate::types::{IssueTrackerConfig, WorkspaceEntry};
+
+const ISSUE_LIST_LIMIT: usize = 50;
+const JIRA_TOKEN_ENV: &str = "JIRA_API_TOKEN";
+const MAX_PROMPT_LINKS: usize = 20;
+/// Diffs are dropped from comments beyond this size; Jira caps comments at
+/// 32k characters.
+const COMMENT_DIFF_MAX_CHARS: usize = 24_000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TrackedIssue {
+    pub(crate) id: String,
+    pub(crate) tracker: String,
+    pub(crate) title: String,
+    pub(crate) url: String,
+    pub(crate) updated_at: Option<String>,
+    pub(crate) labels: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct IssueDetail {
+    issue: TrackedIssue,
+    body: String,
+    links: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IssueThreadStarted {
+    pub(crate) thread_id: String,
+    pub(crate) link: ThreadIssueLink,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PostedComment {
+    pub(crate) tracker: String,
+    pub(crate) issue_id: String,
+    pub(crate) url: Option<String>,
+}
+
+/// Resolved tracker for a workspace.
+enum Tracker {
+    Github {
+        repo: String,
+        root: PathBuf,
+    },
+    Jira {
+        base_url: String,
+        project_key: String,
+        email: String,
+    },
+}
+
+fn resolve_tracker(entry: &WorkspaceEntry) -> Result<Tracker, String> {
+    let root = resolve_git_root(entry).unwrap_or_else(|_| PathBuf::from(&entry.path));
+    match entry.settings.issue_tracker.clone() {
+        Some(IssueTrackerConfig::Jira {
+            base_url,
+            project_key,
+            email,
+        }) => Ok(Tracker::Jira {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            project_key,
+            email,
+        }),
+        Some(IssueTrackerConfig::Github { repo: Some(repo) }) if !repo.trim().is_empty() => {
+            Ok(Tracker::Github {
+                repo: repo.trim().to_string(),
+                root,
+            })
+        }
+        _ => {
+            let repo = github_repo_from_path(&root)?;
+            Ok(Tracker::Github { repo, root })
+        }
+    }
+}
+
+fn labels_from(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|labels| labels.as_array())
+        .map(|labels| {
+            labels
+                .iter()
+                .filter_map(|label| {
+                    label
+                        .get("name")
+                        .and_then(|name| name.as_str())
+                        .or_else(|| label.as_str())
+                })
+                .map(|label| label.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn github_issue(value: &Value) -> Option<TrackedIssue> {
+    Some(TrackedIssue {
+        id: value.get("number")?.as_u64()?.to_string(),
+        tracker: "github".to_string(),
+        title: value.get("title")?.as_str()?.to_string(),
+        url: value.get("url")?.as_str()?.to_string(),
+        updated_at: value
+            .get("updatedAt")
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string()),
+        labels: labels_from(value.get("labels")),
+    })
+}
+
+fn jira_issue(base_url: &str, value: &Value) -> Option<TrackedIssue> {
+    let key = value.get("key")?.as_str()?.to_string();
+    let fields = value.get("fields")?;
+    Some(TrackedIssue {
+        url: format!("{base_url}/browse/{key}"),
+        id: key,
+        tracker: "jira".to_string(),
+        title: fields.get("summary")?.as_str()?.to_string(),
+        updated_at: fields
+            .get("updated")
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string()),
+        labels: labels_from(fields.get("labels")),
+    })
+}
+
+/// Collects http(s) URLs mentioned in an issue body.
+fn extract_links(body: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    for word in body.split(|ch: char| {
+        ch.is_whitespace() || matches!(ch, '(' | ')' | '<' | '>' | '[' | ']' | '|')
+    }) {
+        let word = word.trim_end_matches(['.', ',', ';', ':', '"', '\'']);
+        if (word.starts_with("https://") || word.starts_with("http://"))
+            && !links.iter().any(|link| link == word)
+        {
+            links.push(word.to_string());
+        }
+        if links.len() >= MAX_PROMPT_LINKS {
+            break;
+        }
+    }
+    links
+}
+
+fn build_issue_prompt(detail: &IssueDetail) -> String {
+    let issue = &detail.issue;
+    let label = match issue.tracker.as_str() {
+        "github" => format!("GitHub issue #{}", issue.id),
+        "linear" => format!("Linear issue {}", issue.id),
+        _ => format!("Jira issue {}", issue.id),
+    };
+    let mut prompt = format!("Work on {label}: {}\n{}\n", issue.title, issue.url);
+    if !issue.labels.is_empty() {
+        prompt.push_str(&format!("Labels: {}\n", issue.labels.join(", ")));
+    }
+    let body = detail.body.trim();
+    if !body.is_empty() {
+        prompt.push('\n');
+        prompt.push_str(body);
+        prompt.push('\n');
+    }
+    let links: Vec<&String> = detail
+        .links
+        .iter()
+        .filter(|link| **link != issue.url)
+        .collect();
+    if !links.is_empty() {
+        prompt.push_str("\nRelated links:\n");
+        for link in links {
+            prompt.push_str(&format!("- {link}\n"));
+        }
+    }
+    prompt.trim_end().to_string()
+}
+
+/// Builds the agent prompt for an issue whose body is already known, e.g. one
+/// delivered by a webhook.
+pub(crate) fn issue_prompt(issue: TrackedIssue, body: &str) -> String {
+    build_issue_prompt(&IssueDetail {
+        links: extract_links(body),
+        body: body.to_string(),
+        issue,
+    })
+}
+
+/// Formats the latest turn as an issue comment. GitHub gets markdown, Jira
+/// gets its wiki markup.
+fn format_result_comment(snapshot: &TurnSnapshot, include_diff: bool, markdown: bool) -> String {
+    let code = |text: &str| {
+        if markdown {
+            format!("`{text}`")
+        } else {
+            format!("{{{{{text}}}}}")
+        }
+    };
+    let bold = |text: &str| {
+        if markdown {
+            format!("**{text}**")
+        } else {
+            format!("*{text}*")
+        }
+    };
+    let status = snapshot.status.as_deref().unwrap_or("unknown");
+    let mut comment = format!("{} (status: {status})\n", bold("Agent result"));
+    if let Some(message) = snapshot.final_message.as_deref() {
+        comment.push('\n');
+        comment.push_str(message);
+        comment.push('\n');
+    }
+    if !snapshot.files.is_empty() {
+        comment.push_str(&format!(
+            "\n{}\n",
+            bold(&format!("Files changed ({})", snapshot.files.len()))
+        ));
+        for (path, change) in &snapshot.files {
+            let (added, removed) = diff_line_counts(&change.diff);
+            comment.push_str(&format!("- {} (+{added} -{removed})\n", code(path)));
+        }
+    }
+    if snapshot.commands_run > 0 {
+        comment.push_str(&format!(
+            "\n{} {}\n",
+            bold("Commands run:"),
+            snapshot.commands_run
+        ));
+    }
+    if include_diff && !snapshot.files.is_empty() {
+        let diff = snapshot
+            .files
+            .iter()
+            .map(|(path, change)| format!("--- {path}\n{}", change.diff.trim_end()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if diff.chars().count() > COMMENT_DIFF_MAX_CHARS {
+            comment.push_str("\nDiff omitted: too large for a comment.\n");
+        } else if markdown {
+            comment.push_str(&format!(
+                "\n<details><summary>Diff</summary>\n\n```diff\n{diff}\n```\n</details>\n"
+            ));
+        } else {
+            comment.push_str(&format!("\n{{code:diff}}\n{diff}\n{{code}}\n"));
+        }
+    }
+    comment.trim_end().to_string()
+}
+
+async fn run_gh(root: &Path, args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = Command::new("gh")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr.trim();
+        if detail.is_empty() {
+            return Err("GitHub CLI command failed.".to_string());
+        }
+        return Err(detail.to_string());
+    }
+    Ok(output.stdout)
+}
+
+async fn jira_request(
+    base_url: &str,
+    email: &str,
+    method: reqwest::Method,
+    path: &str,
+    query: &[(&str, String)],
+    body: Option<Value>,
+) -> Result<Value, String> {
+    let token = std::env::var(JIRA_TOKEN_ENV)
+        .ok()
+        .filter(|token| !token.trim().is_empty())
+        .ok_or_else(|| format!("Set {JIRA_TOKEN_ENV} to use the Jira integration."))?;
+    let mut request = reqwest::Client::new()
+        .request(method, format!("{base_url}{path}"))
+        .query(query)
+        .basic_auth(email, Some(token.trim()))
+        .header("Accept", "application/json");
+    if let Some(body) = body {
+        request = request
+            .header("Content-Type", "application/json")
+            .body(body.to_string());
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|err| format!("Jira request failed: {err}"))?;
+    let status = response.status();
+    let body = response.text().await.map_err(|err| err.to_string())?;
+    if !status.is_success() {
+        return Err(format!("Jira request failed ({status})."));
+    }
+    serde_json::from_str(&body).map_err(|err| err.to_string())
+}
+
+async fn jira_get(
+    base_url: &str,
+    email: &str,
+    path: &str,
+    query: &[(&str, String)],
+) -> Result<Value, String> {
+    jira_request(base_url, email, reqwest::Method::GET, path, query, None).await
+}
+
+async fn fetch_issues(tracker: &Tracker) -> Result<Vec<TrackedIssue>, String> {
+    match tracker {
+        Tracker::Github { repo, root } => {
+            let limit = ISSUE_LIST_LIMIT.to_string();
+            let stdout = run_gh(
+                root,
+                &[
+                    "issue",
+                    "list",
+                    "--repo",
+                    repo,
+                    "--limit",
+                    &limit,
+                    "--json",
+                    "number,title,url,updatedAt,labels",
+                ],
+            )
+            .await?;
+            let values: Vec<Value> = serde_json::from_slice(&stdout).map_err(|e| e.to_string())?;
+            Ok(values.iter().filter_map(github_issue).collect())
+        }
+        Tracker::Jira {
+            base_url,
+            project_key,
+            email,
+        } => {
+            let jql = format!(
+                "project = \"{project_key}\" AND statusCategory != Done ORDER BY updated DESC"
+            );
+            let response = jira_get(
+                base_url,
+                email,
+                "/rest/api/2/search",
+                &[
+                    ("jql", jql),
+                    ("maxResults", ISSUE_LIST_LIMIT.to_string()),
+                    ("fields", "summary,updated,labels".to_string()),
+                ],
+            )
+            .await?;
+            let issues = response.get("issues").and_then(|value| value.as_array());
+            Ok(issues
+                .into_iter()
+                .flatten()
+                .filter_map(|issue| jira_issue(base_url, issue))
+                .collect())
+        }
+    }
+}
+
+async fn fetch_issue_detail(tracker: &Tracker, issue_id: &str) -> Result<IssueDetail, String> {
+    match tracker {
+        Tracker::Github { repo, root } => {
+            let stdout = run_gh(
+                root,
+                &[
+                    "issue",
+                    "view",
+                    issue_id.trim_start_matches('#'),
+                    "--repo",
+                    repo,
+                    "--json",
+                    "number,title,url,updatedAt,labels,body",
+                ],
+            )
+            .await?;
+            let value: Value = serde_json::from_slice(&stdout).map_err(|e| e.to_string())?;
+            let issue = github_issue(&value).ok_or("Unexpected issue format from gh.")?;
+            let body = value
+                .get("body")
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string();
+            Ok(IssueDetail {
+                links: extract_links(&body),
+                issue,
+                body,
+            })
+        }
+        Tracker::Jira {
+            base_url, email, ..
+        } => {
+            let value = jira_get(
+                base_url,
+                email,
+                &format!("/rest/api/2/issue/{issue_id}"),
+                &[(
+                    "fields",
+                    "summary,updated,labels,description,issuelinks".to_string(),
+                )],
+            )
+            .await?;
+            let issue = jira_issue(base_url, &value).ok_or("Unexpected issue format from Jira.")?;
+            let body = value
+                .get("fields")
+                .and_then(|fields| fields.get("description"))
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let mut links = extract_links(&body);
+            let issue_links = value
+                .get("fields")
+                .and_then(|fields| fields.get("issuelinks"))
+                .and_then(|value| value.as_array());
+            for linked in issue_links.into_iter().flatten() {
+                let key = linked
+                    .get("outwardIssue")
+                    .or_else(|| linked.get("inwardIssue"))
+                    .and_then(|issue| issue.get("key"))
+                    .and_then(|key| key.as_str());
+                if let Some(key) = key {
+                    links.push(format!("{base_url}/browse/{key}"));
+                }
+            }
+            Ok(IssueDetail { issue, body, links })
+        }
+    }
+}
+
+async fn workspace_entry(state: &AppState, workspace_id: &str) -> Result<WorkspaceEntry, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(workspace_id)
+        .cloned()
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?;
+    state.access_control.lock().await.ensure_visible(&entry)?;
+    Ok(entry)
+}
+
+/// Lists open issues from the workspace's configured GitHub or Jira project.
+#[tauri::command]
+pub(crate) async fn list_tracked_issues(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<TrackedIssue>, String> {
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    let tracker = resolve_tracker(&entry)?;
+    fetch_issues(&tracker).await
+}
+
+/// Starts a thread whose first prompt is seeded from the issue and records
+/// the thread/issue association for later back-linking.
+#[tauri::command]
+pub(crate) async fn start_thread_from_issue(
+    workspace_id: String,
+    issue_id: String,
+    model: Option<String>,
+    effort: Option<String>,
+    access_mode: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<IssueThreadStarted, String> {
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    let tracker = resolve_tracker(&entry)?;
+    let detail = fetch_issue_detail(&tracker, &issue_id).await?;
+
+    let response = codex::start_thread(workspace_id.clone(), state.clone(), app.clone()).await?;
+    let result = response.get("result").unwrap_or(&response);
+    let thread_id = result
+        .get("thread")
+        .and_then(|thread| thread.get("id"))
+        .and_then(|value| value.as_str())
+        .ok_or("thread/start did not return a thread id")?
+        .to_string();
+
+    let link = ThreadIssueLink {
+        workspace_id: workspace_id.clone(),
+        thread_id: thread_id.clone(),
+        tracker: detail.issue.tracker.clone(),
+        issue_id: detail.issue.id.clone(),
+        title: detail.issue.title.clone(),
+        url: detail.issue.url.clone(),
+        linked_at: chrono::Utc::now().timestamp_millis(),
+    };
+    let cipher = state.data_cipher().await?;
+    issue_links::record_issue_link(
+        &issue_links::issue_links_path(&state.data_dir),
+        &cipher,
+        link.clone(),
+    )?;
+
+    codex::send_message(
+        workspace_id,
+        thread_id.clone(),
+        build_issue_prompt(&detail),
+        SendOptions {
+            model,
+            effort,
+            access_mode,
+            ..SendOptions::default()
+        },
+        state,
+        app,
+    )
+    .await?;
+    Ok(IssueThreadStarted { thread_id, link })
+}
+
+/// Posts a summary of the thread's latest turn (optionally with its diff)
+/// as a comment on the issue or PR the thread is linked to.
+#[tauri::command]
+pub(crate) async fn post_result_comment(
+    workspace_id: String,
+    thread_id: String,
+    include_diff: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<PostedComment, String> {
+    let cipher = state.data_cipher().await?;
+    let link = issue_links::find_issue_link(
+        &issue_links::issue_links_path(&state.data_dir),
+        &cipher,
+        &workspace_id,
+        &thread_id,
+    )?
+    .ok_or("Thread is not linked to an issue.")?;
+    let entry = workspace_entry(&state, &workspace_id).await?;
+    let tracker = resolve_tracker(&entry)?;
+
+    let response = codex::resume_thread(workspace_id, thread_id, state, app).await?;
+    if let Some(error) = response.get("error") {
+        return Err(error
+            .get("message")
+            .and_then(|value| value.as_str())
+            .unwrap_or("Failed to load thread")
+            .to_string());
+    }
+    let snapshot = find_last_turn(&response).ok_or("Thread has no turns to report.")?;
+    let include_diff = include_diff.unwrap_or(false);
+
+    let url = match (&tracker, link.tracker.as_str()) {
+        (Tracker::Github { repo, root }, "github") => {
+            let body = format_result_comment(&snapshot, include_diff, true);
+            let stdout = run_gh(
+                root,
+                &[
+                    "api",
+                    "-X",
+                    "POST",
+                    &format!("/repos/{repo}/issues/{}/comments", link.issue_id),
+                    "-f",
+                    &format!("body={body}"),
+                ],
+            )
+            .await?;
+            serde_json::from_slice::<Value>(&stdout)
+                .ok()
+                .and_then(|value| {
+                    value
+                        .get("html_url")
+                        .and_then(|url| url.as_str())
+                        .map(|url| url.to_string())
+                })
+        }
+        (
+            Tracker::Jira {
+                base_url, email, ..
+            },
+            "jira",
+        ) => {
+            let body = format_result_comment(&snapshot, include_diff, false);
+            let response = jira_request(
+                base_url,
+                email,
+                reqwest::Method::POST,
+                &format!("/rest/api/2/issue/{}/comment", link.issue_id),
+                &[],
+                Some(serde_json::json!({ "body": body })),
+            )
+            .await?;
+            response
+                .get("id")
+                .and_then(|id| id.as_str())
+                .map(|id| format!("{}?focusedCommentId={id}", link.url))
+        }
+        _ => {
+            return Err(format!(
+                "The workspace issue tracker no longer matches the linked {} issue.",
+                link.tracker
+            ))
+        }
+    };
+    Ok(PostedComment {
+        tracker: link.tracker,
+        issue_id: link.issue_id,
+        url,
+    })
+}
+
+/// Returns the issue a thread was started from, if any.
+#[tauri::command]
+pub(crate) async fn get_thread_issue(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<ThreadIssueLink>, String> {
+    let cipher = state.data_cipher().await?;
+    issue_links::find_issue_link(
+        &issue_links::issue_links_path(&state.data_dir),
+        &cipher,
+        &workspace_id,
+        &thread_id,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_github_and_jira_issues() {
+        let github = github_issue(&json!({
+            "number": 12,
+            "title": "Crash on start",
+            "url": "https://github.com/o/r/issues/12",
+            "updatedAt": "2024-01-01T00:00:00Z",
+            "labels": [{ "name": "bug" }]
+        }))
+        .expect("github issue");
+        assert_eq!(github.id, "12");
+        assert_eq!(github.labels, vec!["bug"]);
+
+        let jira = jira_issue(
+            "https://acme.atlassian.net",
+            &json!({ "key": "APP-7", "fields": { "summary": "Slow search", "labels": ["perf"] } }),
+        )
+        .expect("jira issue");
+        assert_eq!(jira.url, "https://acme.atlassian.net/browse/APP-7");
+        assert_eq!(jira.labels, vec!["perf"]);
+        assert!(jira_issue("https://acme.atlassian.net", &json!({ "key": "APP-8" })).is_none());
+    }
+
+    #[test]
+    fn result_comment_lists_files_and_optional_diff() {
+        let mut snapshot = TurnSnapshot {
+            status: Some("completed".to_string()),
+            final_message: Some("Fixed the crash.".to_string()),
+            commands_run: 2,
+            ..TurnSnapshot::default()
+        };
+        snapshot.files.insert(
+            "src/main.rs".to_string(),
+            crate::backend::turn_compare::FileChange {
+                kind: None,
+                diff: "@@\n+fixed\n-broken\n+extra".to_string(),
+            },
+        );
+        let markdown = format_result_comment(&snapshot, false, true);
+        assert_eq!(
+            markdown,
+            "**Agent result** (status: completed)\n\nFixed the crash.\n\n**Files changed (1)**\n- `src/main.rs` (+2 -1)\n\n**Commands run:** 2"
+        );
+        let jira = format_result_comment(&snapshot, true, false);
+        assert!(jira.contains("- {{src/main.rs}} (+2 -1)"));
+        assert!(jira.ends_with("{code:diff}\n--- src/main.rs\n@@\n+fixed\n-broken\n+extra\n{code}"));
+    }
+
+    #[test]
+    fn issue_prompt_includes_body_and_related_links() {
+        let body = "Steps in (https://example.com/logs). See https://example.com/logs and https://github.com/o/r/issues/12.";
+        let detail = IssueDetail {
+            issue: TrackedIssue {
+                id: "12".to_string(),
+                tracker: "github".to_string(),
+                title: "Crash on start".to_string(),
+                url: "https://github.com/o/r/issues/12".to_string(),
+                updated_at: None,
+                labels: vec!["bug".to_string()],
+            },
+            body: body.to_string(),
+            links: extract_links(body),
+        };
+        assert_eq!(
+            build_issue_prompt(&detail),
+            format!(
+                "Work on GitHub issue #12: Crash on start\nhttps://github.com/o/r/issues/12\nLabels: bug\n\n{body}\n\nRelated links:\n- https://example.com/logs"
+            )
+        );
+    }
+}
@@ -1,34 +1,120 @@
 use tauri::Manager;
 
+mod ab_test;
+mod access_control;
+mod actions;
+mod annotations;
+mod app_lock;
+mod app_update;
+mod approval_delegation;
+mod approval_shortcuts;
+mod auth_expiry;
 mod backend;
+mod background_turn;
+mod best_of;
+mod blame_context;
+mod clone_workspace;
+mod clock;
+mod code_stats;
 mod codex;
+mod codex_bin_overrides;
 mod codex_home;
 mod codex_config;
+mod command_output;
+#[cfg(debug_assertions)]
+mod connection_sim;
+mod context_files;
+mod directory_access;
 #[cfg(not(target_os = "windows"))]
 #[path = "dictation.rs"]
 mod dictation;
 #[cfg(target_os = "windows")]
 #[path = "dictation_stub.rs"]
 mod dictation;
+mod doctor_fix;
+mod dry_run;
+mod editor_launch;
+mod event_replay;
 mod event_sink;
+mod event_subscriptions;
+mod focus_mode;
+mod full_access;
 mod git;
 mod git_utils;
+mod issue_tracker;
 mod local_usage;
 mod menu;
+mod model_stats;
+mod network;
+mod offline_queue;
+mod onboarding;
+mod patch_staging;
+mod plan_tracker;
+mod policy;
+mod privacy;
+mod prompt_budget;
+mod prompt_insights;
 mod prompts;
+mod protocol_compat;
+mod quick_ask;
+mod quick_search;
+mod rate_limits;
 mod remote_backend;
+mod report;
+mod review_pair;
 mod rules;
+mod scratch_workspace;
+mod selftest;
+mod semantic_search;
+mod session_env;
+mod session_output;
+mod session_reload;
 mod settings;
+mod share_bundle;
 mod state;
+mod task_queue;
+mod team_activity;
+mod team_pack;
+mod telemetry;
 mod terminal;
+mod terminal_handoff;
+mod thread_branches;
+mod thread_guard;
+mod thread_handoff;
+mod thread_index;
+mod thread_journal;
+mod thread_links;
+mod thread_settings;
+mod thread_sla;
+mod thread_tags;
+mod turn_artifacts;
+mod turn_labels;
+mod turn_limiter;
+mod turn_manifest;
 mod window;
 mod storage;
+mod system_resume;
 mod types;
 mod utils;
+mod verification;
+mod webhook_intake;
+mod workspace_doctor;
+mod workspace_health;
+mod workspace_lock;
+mod workspace_prewarm;
+mod workspace_turns;
+mod workspace_windows;
 mod workspaces;
 
+#[cfg(feature = "protocol-harness")]
+pub use selftest::{run_protocol_harness, serve_fake_app_server};
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // `run_selftest` launches this binary as its scripted app-server.
+    if selftest::serve_fake_app_server() {
+        return;
+    }
     #[cfg(target_os = "linux")]
     {
         // Avoid WebKit compositing issues on some Linux setups (GBM buffer errors).
@@ -42,13 +128,29 @@ pub fn run() {
         .manage(menu::MenuItemRegistry::<tauri::Wry>::default())
         .menu(menu::build_menu)
         .on_menu_event(menu::handle_menu_event)
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::Destroyed = event {
+                workspace_windows::forget_window(window.app_handle(), window.label());
+            }
+        })
         .setup(|app| {
             let state = state::AppState::load(&app.handle());
             app.manage(state);
+            privacy::spawn_retention_purger(app.handle().clone());
+            app_lock::spawn_lock_watcher(app.handle().clone());
+            webhook_intake::restart_listener(app.handle());
+            session_reload::spawn_config_watcher(app.handle().clone());
+            clock::spawn_clock_monitor(app.handle().clone());
+            workspace_health::spawn_health_reports(app.handle().clone());
+            full_access::spawn_full_access_reverter(app.handle().clone());
+            workspace_prewarm::spawn_launch_prewarm(app.handle().clone());
+            codex_bin_overrides::spawn_startup_check(app.handle().clone());
+            telemetry::spawn_aggregate_sharing(app.handle().clone());
             #[cfg(desktop)]
             {
                 app.handle()
                     .plugin(tauri_plugin_updater::Builder::new().build())?;
+                app.manage(app_update::PendingAppUpdate::default());
             }
             Ok(())
         });
@@ -60,7 +162,7 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(app_lock::guard(tauri::generate_handler![
             settings::get_app_settings,
             settings::update_app_settings,
             menu::menu_set_accelerators,
@@ -87,6 +189,8 @@ pub fn run() {
             codex::resume_thread,
             codex::list_threads,
             codex::archive_thread,
+            codex::unarchive_thread,
+            codex::list_recently_archived,
             codex::collaboration_mode_list,
             workspaces::connect_workspace,
             git::get_git_status,
@@ -101,6 +205,7 @@ pub fn run() {
             git::revert_git_file,
             git::revert_git_all,
             git::commit_git,
+            git::suggest_commit_message,
             git::push_git,
             git::pull_git,
             git::sync_git,
@@ -134,8 +239,152 @@ pub fn run() {
             dictation::dictation_start,
             dictation::dictation_stop,
             dictation::dictation_cancel,
-            local_usage::local_usage_snapshot
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+            local_usage::local_usage_snapshot,
+            report::export_workspace_report,
+            team_pack::export_team_pack,
+            team_pack::import_team_pack,
+            privacy::prepare_purge_local_data,
+            privacy::purge_all_local_data,
+            access_control::get_access_control_status,
+            access_control::set_app_passphrase,
+            access_control::unlock_workspace,
+            access_control::lock_workspace,
+            access_control::set_observer_mode,
+            onboarding::get_onboarding_progress,
+            onboarding::run_onboarding_fix,
+            app_update::check_app_update,
+            app_update::install_app_update,
+            settings::set_locale,
+            settings::get_message_catalog,
+            quick_search::quick_search,
+            workspace_windows::open_workspace_window,
+            workspace_windows::focus_workspace_window,
+            workspace_windows::set_window_workspaces,
+            event_subscriptions::subscribe_events,
+            event_subscriptions::unsubscribe_events,
+            event_subscriptions::set_thread_event_filter,
+            codex::compare_turns,
+            ab_test::run_ab_test,
+            share_bundle::create_share_bundle,
+            share_bundle::open_share_bundle,
+            issue_tracker::list_tracked_issues,
+            issue_tracker::start_thread_from_issue,
+            issue_tracker::get_thread_issue,
+            issue_tracker::post_result_comment,
+            task_queue::list_queued_tasks,
+            task_queue::start_queued_task,
+            task_queue::dismiss_queued_task,
+            plan_tracker::get_thread_plan,
+            command_output::search_command_outputs,
+            command_output::get_item_output,
+            code_stats::get_code_stats,
+            context_files::suggest_context_files,
+            semantic_search::semantic_search,
+            policy::get_effective_policy,
+            approval_delegation::get_approval_audit_log,
+            dry_run::get_turn_proposed_patch,
+            patch_staging::list_staged_patches,
+            patch_staging::apply_staged_patch,
+            patch_staging::discard_staged_patch,
+            turn_manifest::get_turn_manifest,
+            best_of::run_best_of,
+            prompt_budget::check_prompt_size,
+            offline_queue::list_offline_queue,
+            offline_queue::cancel_offline_turn,
+            workspace_doctor::workspace_doctor,
+            doctor_fix::list_doctor_issues,
+            doctor_fix::doctor_fix,
+            network::probe_connectivity,
+            #[cfg(debug_assertions)]
+            connection_sim::get_connection_simulation,
+            #[cfg(debug_assertions)]
+            connection_sim::set_connection_simulation,
+            focus_mode::get_focus_state,
+            focus_mode::get_notification_route,
+            app_lock::get_app_lock_status,
+            app_lock::report_user_activity,
+            app_lock::lock_app,
+            app_lock::unlock_app,
+            prompt_budget::estimate_turn,
+            turn_limiter::get_turn_limiter_status,
+            protocol_compat::get_protocol_compat_report,
+            thread_settings::get_thread_turn_settings,
+            thread_tags::set_thread_tags,
+            thread_tags::list_thread_tags,
+            thread_tags::list_thread_filters,
+            thread_tags::save_thread_filter,
+            thread_tags::delete_thread_filter,
+            thread_tags::query_threads,
+            actions::list_actions,
+            actions::invoke_action,
+            workspaces::update_workspace_identity,
+            thread_branches::list_thread_branches,
+            clone_workspace::clone_and_add_workspace,
+            session_env::get_session_environment,
+            review_pair::start_review_pair,
+            review_pair::list_review_pairs,
+            annotations::annotate_item,
+            annotations::list_annotations,
+            annotations::get_annotation_feedback,
+            prompt_insights::get_prompting_insights,
+            editor_launch::open_in_editor,
+            editor_launch::list_editors,
+            thread_sla::set_thread_sla,
+            thread_sla::list_thread_timers,
+            event_replay::ack_events,
+            event_replay::get_events_since,
+            session_reload::reload_session_config,
+            selftest::run_selftest,
+            clock::list_clock_jumps,
+            turn_artifacts::list_turn_artifacts,
+            turn_artifacts::open_artifact,
+            directory_access::answer_directory_access,
+            workspace_health::get_workspace_health,
+            workspace_health::get_last_health_report,
+            session_output::tail_session_output,
+            session_output::set_session_output_live,
+            turn_labels::set_turn_labels,
+            turn_labels::list_turn_labels,
+            turn_labels::apply_turn_labels_to_pull_request,
+            workspace_turns::list_active_turns,
+            terminal_handoff::open_in_terminal,
+            model_stats::get_model_comparison,
+            approval_delegation::verify_audit_chain,
+            approval_delegation::export_approval_audit_log,
+            approval_delegation::retry_approval_delegation,
+            scratch_workspace::spawn_scratch_workspace,
+            scratch_workspace::stage_scratch_changes,
+            scratch_workspace::discard_scratch_workspace,
+            full_access::grant_full_access,
+            full_access::revoke_full_access,
+            full_access::list_full_access_grants,
+            thread_links::link_threads,
+            thread_links::unlink_threads,
+            thread_links::get_thread_links,
+            workspace_prewarm::record_workspace_open,
+            codex_bin_overrides::list_broken_codex_bins,
+            codex_bin_overrides::remap_codex_bin,
+            auth_expiry::get_auth_expired_workspaces,
+            auth_expiry::start_account_login,
+            team_activity::get_team_activity,
+            workspace_lock::get_workspace_lock,
+            workspace_lock::force_workspace_lock,
+            telemetry::record_feature_usage,
+            telemetry::get_local_telemetry,
+            approval_shortcuts::set_focused_workspace,
+            approval_shortcuts::respond_to_next_approval,
+            thread_tags::query_threads_window,
+            quick_ask::quick_ask,
+            thread_handoff::handoff_thread,
+            blame_context::get_blame_context
+        ]))
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                if let Some(state) = app.try_state::<state::AppState>() {
+                    workspace_lock::release_all(&state);
+                }
+            }
+        });
 }
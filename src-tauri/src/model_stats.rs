@@ -0,0 +1,136 @@
+use chrono::Utc;
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::model_stats::{self, ModelComparison, ModelEvent, ModelSample};
+use crate::backend::secure_storage::DataCipher;
+use crate::backend::turn_manifest;
+use crate::backend::turn_summary::params_turn_id;
+use crate::state::AppState;
+
+const DEFAULT_COMPARISON_DAYS: u32 = 30;
+
+fn record(app: &AppHandle, sample: ModelSample) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let result = match state.data_cipher().await {
+            Ok(cipher) => {
+                let sample = with_manifest_settings(&state, &cipher, sample);
+                let _guard = state.model_sample_writes.lock().await;
+                model_stats::record_sample(&state.data_dir, &cipher, sample)
+            }
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to record model stats: {err}");
+        }
+    });
+}
+
+/// Fills in the model and effort the turn's manifest recorded at
+/// `turn/start`; turns without one count under the default model.
+fn with_manifest_settings(
+    state: &AppState,
+    cipher: &DataCipher,
+    mut sample: ModelSample,
+) -> ModelSample {
+    if let ModelEvent::TurnCompleted { model, effort, .. } = &mut sample.event {
+        if let Ok(Some(manifest)) = turn_manifest::find_manifest(
+            &state.data_dir,
+            cipher,
+            &sample.workspace_id,
+            &sample.turn_id,
+        ) {
+            *model = manifest.model;
+            *effort = manifest.effort;
+        }
+    }
+    sample
+}
+
+/// Records the outcome in `turn/completed` summaries; called for every
+/// `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    if message.get("method").and_then(|value| value.as_str()) != Some("turn/completed") {
+        return;
+    }
+    let Some(params) = message.get("params") else {
+        return;
+    };
+    let (Some(turn_id), Some(summary)) = (params_turn_id(params), params.get("summary")) else {
+        return;
+    };
+    let lines_changed = ["added", "removed"]
+        .iter()
+        .filter_map(|key| summary.get("codeStats")?.get(key)?.as_u64())
+        .sum::<u64>() as usize;
+    let event = ModelEvent::TurnCompleted {
+        model: None,
+        effort: None,
+        status: summary
+            .get("status")
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string()),
+        duration_ms: summary.get("durationMs").and_then(|value| value.as_u64()),
+        total_tokens: summary
+            .get("totalTokens")
+            .and_then(|value| value.as_u64())
+            .unwrap_or(0),
+        lines_changed,
+    };
+    record(
+        app,
+        ModelSample {
+            workspace_id: workspace_id.to_string(),
+            turn_id,
+            recorded_at: Utc::now().timestamp_millis(),
+            event,
+        },
+    );
+}
+
+/// Records whether the verification command passed after a turn.
+pub(crate) fn record_verification(
+    app: &AppHandle,
+    workspace_id: &str,
+    turn_id: Option<&str>,
+    passed: bool,
+) {
+    let Some(turn_id) = turn_id else {
+        return;
+    };
+    record(
+        app,
+        ModelSample {
+            workspace_id: workspace_id.to_string(),
+            turn_id: turn_id.to_string(),
+            recorded_at: Utc::now().timestamp_millis(),
+            event: ModelEvent::Verified { passed },
+        },
+    );
+}
+
+/// Compares model and effort combinations over the last `days` days:
+/// average turn duration, interrupt rate, verification pass rate and tokens
+/// per accepted change.
+#[tauri::command]
+pub(crate) async fn get_model_comparison(
+    workspace_id: Option<String>,
+    days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<ModelComparison, String> {
+    let days = days.unwrap_or(DEFAULT_COMPARISON_DAYS).max(1);
+    let cipher = state.data_cipher().await?;
+    let samples = model_stats::read_samples(
+        &state.data_dir,
+        &cipher,
+        workspace_id.as_deref(),
+        days,
+        Utc::now().date_naive(),
+    )?;
+    Ok(ModelComparison {
+        days,
+        models: model_stats::compare_models(&samples),
+    })
+}
@@ -0,0 +1,186 @@
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+use reqwest::{Certificate, Client, NoProxy, Proxy};
+use tauri::State;
+
+use crate::state::AppState;
+use crate::types::NetworkSettings;
+
+/// Endpoints Codex needs for signing in and for model requests.
+const DEFAULT_PROBE_URLS: &[&str] = &[
+    "https://auth.openai.com",
+    "https://api.openai.com/v1/models",
+];
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConnectivityProbe {
+    pub(crate) url: String,
+    /// The endpoint answered; any HTTP status counts, including 401.
+    pub(crate) ok: bool,
+    pub(crate) status: Option<u16>,
+    pub(crate) elapsed_ms: u64,
+    pub(crate) proxy: Option<String>,
+    pub(crate) error: Option<String>,
+    pub(crate) hint: Option<String>,
+}
+
+fn setting(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn build_client(settings: &NetworkSettings) -> Result<Client, String> {
+    let mut builder = Client::builder().timeout(PROBE_TIMEOUT);
+    let no_proxy = setting(&settings.no_proxy).and_then(NoProxy::from_string);
+    if let Some(url) = setting(&settings.http_proxy) {
+        let proxy = Proxy::http(url).map_err(|err| format!("Invalid HTTP proxy: {err}"))?;
+        builder = builder.proxy(proxy.no_proxy(no_proxy.clone()));
+    }
+    if let Some(url) = setting(&settings.https_proxy) {
+        let proxy = Proxy::https(url).map_err(|err| format!("Invalid HTTPS proxy: {err}"))?;
+        builder = builder.proxy(proxy.no_proxy(no_proxy));
+    }
+    if let Some(path) = setting(&settings.ca_bundle_path) {
+        let pem = std::fs::read(path).map_err(|err| format!("Failed to read CA bundle: {err}"))?;
+        let certificates = Certificate::from_pem_bundle(&pem)
+            .map_err(|err| format!("Invalid CA bundle: {err}"))?;
+        if certificates.is_empty() {
+            return Err("The CA bundle contains no certificates.".to_string());
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    builder.build().map_err(|err| err.to_string())
+}
+
+/// Suggests a setting to change for a failed request.
+fn failure_hint(error: &str, settings: &NetworkSettings) -> Option<String> {
+    let error = error.to_lowercase();
+    if error.contains("certificate") || error.contains("unknownissuer") || error.contains("tls") {
+        return Some(if setting(&settings.ca_bundle_path).is_some() {
+            "TLS verification failed; check that the CA bundle contains the root certificate of your network's TLS inspection.".to_string()
+        } else {
+            "TLS verification failed; your network may intercept TLS. Set a CA bundle with its root certificate.".to_string()
+        });
+    }
+    if error.contains("proxy") || error.contains("tunnel") {
+        return Some(
+            "The proxy refused or failed the connection; check the proxy URL and credentials."
+                .to_string(),
+        );
+    }
+    if error.contains("dns") || error.contains("resolve") || error.contains("timed out") {
+        return Some(if setting(&settings.https_proxy).is_some() {
+            "The endpoint could not be reached through the proxy.".to_string()
+        } else {
+            "The endpoint could not be reached; your network may require an HTTPS proxy."
+                .to_string()
+        });
+    }
+    None
+}
+
+fn error_chain(error: &reqwest::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Checks that the endpoints Codex uses are reachable with the proxy and CA
+/// bundle of `settings`, or of the saved settings when omitted, so they can
+/// be tested before saving. Requests are made from this machine.
+#[tauri::command]
+pub(crate) async fn probe_connectivity(
+    settings: Option<NetworkSettings>,
+    urls: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ConnectivityProbe>, String> {
+    let settings = match settings {
+        Some(settings) => settings,
+        None => state.app_settings.lock().await.network.clone(),
+    };
+    let client = build_client(&settings)?;
+    let urls = urls.filter(|urls| !urls.is_empty()).unwrap_or_else(|| {
+        DEFAULT_PROBE_URLS
+            .iter()
+            .map(|url| url.to_string())
+            .collect()
+    });
+    let mut probes = Vec::new();
+    for url in urls {
+        let proxy = if url.starts_with("https:") {
+            setting(&settings.https_proxy)
+        } else {
+            setting(&settings.http_proxy)
+        }
+        .map(str::to_string);
+        let started = Instant::now();
+        let response = client.get(&url).send().await;
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        probes.push(match response {
+            Ok(response) => ConnectivityProbe {
+                url,
+                ok: true,
+                status: Some(response.status().as_u16()),
+                elapsed_ms,
+                proxy,
+                error: None,
+                hint: None,
+            },
+            Err(err) => {
+                let error = error_chain(&err);
+                ConnectivityProbe {
+                    url,
+                    ok: false,
+                    status: None,
+                    elapsed_ms,
+                    proxy,
+                    hint: failure_hint(&error, &settings),
+                    error: Some(error),
+                }
+            }
+        });
+    }
+    Ok(probes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_at_ca_bundle_for_tls_failures() {
+        let settings = NetworkSettings::default();
+        let hint = failure_hint(
+            "error sending request: invalid peer certificate: UnknownIssuer",
+            &settings,
+        )
+        .expect("hint");
+        assert!(hint.contains("Set a CA bundle"));
+        assert!(
+            failure_hint("dns error: failed to lookup address", &settings)
+                .expect("hint")
+                .contains("HTTPS proxy")
+        );
+        assert_eq!(failure_hint("connection reset", &settings), None);
+
+        let invalid = NetworkSettings {
+            https_proxy: Some("not a url".to_string()),
+            ..NetworkSettings::default()
+        };
+        assert!(build_client(&invalid)
+            .expect_err("invalid proxy")
+            .starts_with("Invalid HTTPS proxy"));
+    }
+}
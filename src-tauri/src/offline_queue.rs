@@ -0,0 +1,204 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
+
+use crate::backend::events::AppServerEvent;
+use crate::backend::offline_queue::{self, OfflineTurn};
+use crate::backend::turn_summary::params_thread_id;
+use crate::codex::{self, SendOptions};
+use crate::state::AppState;
+use crate::workspace_windows::emit_app_server_event;
+
+/// How long to wait for a reconnected session to be registered.
+const SESSION_WAIT: Duration = Duration::from_secs(30);
+const SESSION_POLL: Duration = Duration::from_millis(100);
+
+fn emit(app: &AppHandle, workspace_id: &str, thread_id: &str, method: &str, params: Value) {
+    emit_app_server_event(
+        app,
+        workspace_id,
+        Some(method),
+        Some(thread_id),
+        AppServerEvent {
+            workspace_id: workspace_id.to_string(),
+            message: json!({ "method": method, "params": params }),
+        },
+    );
+}
+
+/// Queues a message whose workspace is disconnected and emits
+/// `turn/queuedOffline`. The returned value stands in for the `turn/start`
+/// response.
+pub(crate) async fn queue_turn(
+    state: &AppState,
+    app: &AppHandle,
+    mut turn: OfflineTurn,
+) -> Result<Value, String> {
+    turn.id = Uuid::new_v4().to_string();
+    turn.queued_at = chrono::Utc::now().timestamp_millis();
+    {
+        let _guard = state.offline_queue_writes.lock().await;
+        let cipher = state.data_cipher().await?;
+        offline_queue::enqueue_turn(
+            &offline_queue::offline_queue_path(&state.data_dir),
+            &cipher,
+            turn.clone(),
+        )?;
+    }
+    let queued = json!({ "threadId": turn.thread_id, "turn": turn });
+    emit(
+        app,
+        &turn.workspace_id,
+        &turn.thread_id,
+        "turn/queuedOffline",
+        queued.clone(),
+    );
+    Ok(json!({ "result": { "queuedOffline": queued } }))
+}
+
+async fn wait_for_session(state: &AppState, workspace_id: &str) -> bool {
+    let mut waited = Duration::ZERO;
+    while waited < SESSION_WAIT {
+        if state.sessions.lock().await.contains_key(workspace_id) {
+            return true;
+        }
+        tokio::time::sleep(SESSION_POLL).await;
+        waited += SESSION_POLL;
+    }
+    false
+}
+
+async fn dispatch(app: AppHandle, workspace_id: String, thread_id: Option<String>) {
+    let state = app.state::<AppState>();
+    if !wait_for_session(&state, &workspace_id).await {
+        return;
+    }
+    // Sent again by `resume_dispatch` after a new login.
+    if state.auth_expiry.is_expired(&workspace_id) {
+        return;
+    }
+    let turns = {
+        let _guard = state.offline_queue_writes.lock().await;
+        match state.data_cipher().await {
+            Ok(cipher) => offline_queue::take_next_turns(
+                &offline_queue::offline_queue_path(&state.data_dir),
+                &cipher,
+                &workspace_id,
+                thread_id.as_deref(),
+            ),
+            Err(err) => Err(err),
+        }
+    };
+    let turns = match turns {
+        Ok(turns) => turns,
+        Err(err) => {
+            eprintln!("Failed to read offline queue: {err}");
+            return;
+        }
+    };
+    for turn in turns {
+        let result = codex::send_message(
+            turn.workspace_id.clone(),
+            turn.thread_id.clone(),
+            turn.text.clone(),
+            SendOptions {
+                model: turn.model.clone(),
+                effort: turn.effort.clone(),
+                access_mode: turn.access_mode.clone(),
+                images: turn.images.clone(),
+                collaboration_mode: turn.collaboration_mode.clone(),
+                dry_run: turn.dry_run,
+                ..SendOptions::default()
+            },
+            state.clone(),
+            app.clone(),
+        )
+        .await;
+        if let Err(error) = result {
+            emit(
+                &app,
+                &turn.workspace_id,
+                &turn.thread_id,
+                "turn/offlineDispatchFailed",
+                json!({ "threadId": turn.thread_id, "turn": turn, "error": error }),
+            );
+        }
+    }
+}
+
+/// Dispatches queued messages once their workspace reconnects, one turn per
+/// thread at a time; called for every `app-server-event`. The next message
+/// of a thread is sent when its previous turn completes.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let thread_id = match message.get("method").and_then(|value| value.as_str()) {
+        Some("codex/connected") => None,
+        Some("turn/completed") => {
+            let Some(thread_id) = message.get("params").and_then(params_thread_id) else {
+                return;
+            };
+            Some(thread_id)
+        }
+        _ => return,
+    };
+    let path = match app.try_state::<AppState>() {
+        Some(state) => offline_queue::offline_queue_path(&state.data_dir),
+        None => return,
+    };
+    if !path.exists() {
+        return;
+    }
+    tauri::async_runtime::spawn(dispatch(app.clone(), workspace_id.to_string(), thread_id));
+}
+
+/// Dispatches the workspace's queued messages, e.g. once its sign-in is
+/// restored.
+pub(crate) fn resume_dispatch(app: &AppHandle, workspace_id: &str) {
+    let path = match app.try_state::<AppState>() {
+        Some(state) => offline_queue::offline_queue_path(&state.data_dir),
+        None => return,
+    };
+    if path.exists() {
+        tauri::async_runtime::spawn(dispatch(app.clone(), workspace_id.to_string(), None));
+    }
+}
+
+/// Lists messages waiting for their workspace to reconnect, oldest first.
+#[tauri::command]
+pub(crate) async fn list_offline_queue(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<OfflineTurn>, String> {
+    let cipher = state.data_cipher().await?;
+    let turns =
+        offline_queue::read_turns(&offline_queue::offline_queue_path(&state.data_dir), &cipher)?;
+    Ok(turns
+        .into_iter()
+        .filter(|turn| {
+            workspace_id
+                .as_deref()
+                .is_none_or(|workspace_id| turn.workspace_id == workspace_id)
+        })
+        .collect())
+}
+
+/// Drops a queued message before it is dispatched.
+#[tauri::command]
+pub(crate) async fn cancel_offline_turn(
+    turn_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let _guard = state.offline_queue_writes.lock().await;
+    let cipher = state.data_cipher().await?;
+    let removed = offline_queue::remove_turn(
+        &offline_queue::offline_queue_path(&state.data_dir),
+        &cipher,
+        &turn_id,
+    )?;
+    if removed {
+        Ok(())
+    } else {
+        Err("Offline turn not found; it may have been sent already.".to_string())
+    }
+}
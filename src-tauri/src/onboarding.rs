@@ -0,0 +1,355 @@
+use serde::Serialize;
+use serde_json::json;
+use std::time::Duration;
+
+use tauri::State;
+use tokio::time::timeout;
+
+use crate::backend::app_server::{
+    build_codex_command_with_bin, build_codex_path_env, check_codex_installation,
+};
+use crate::codex::check_node_installation;
+use crate::state::AppState;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum OnboardingStepId {
+    CodexInstalled,
+    Authenticated,
+    NodePresent,
+    WorkspaceAdded,
+    ThreadStarted,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum OnboardingStepStatus {
+    Done,
+    Pending,
+    /// Waiting on an earlier step before it can be checked or fixed.
+    Blocked,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum OnboardingFix {
+    /// A shell command the user can run; not executed automatically.
+    Command { label: String, command: String },
+    /// Launches `codex login` from the app.
+    Login { label: String },
+    /// Handled by the frontend, e.g. opening the add-workspace dialog.
+    Action { label: String, action: String },
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OnboardingStep {
+    pub(crate) id: OnboardingStepId,
+    pub(crate) label: String,
+    pub(crate) status: OnboardingStepStatus,
+    pub(crate) detail: Option<String>,
+    pub(crate) fix: Option<OnboardingFix>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OnboardingProgress {
+    pub(crate) steps: Vec<OnboardingStep>,
+    pub(crate) completed: usize,
+    pub(crate) total: usize,
+    pub(crate) next_step: Option<OnboardingStepId>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct OnboardingChecks {
+    codex_version: Option<String>,
+    codex_error: Option<String>,
+    authenticated: bool,
+    auth_detail: Option<String>,
+    node_version: Option<String>,
+    node_error: Option<String>,
+    workspace_count: usize,
+    thread_started: bool,
+}
+
+fn codex_install_fix() -> OnboardingFix {
+    let command = if cfg!(target_os = "macos") {
+        "brew install codex"
+    } else {
+        "npm install -g @openai/codex"
+    };
+    OnboardingFix::Command {
+        label: "Install the Codex CLI".to_string(),
+        command: command.to_string(),
+    }
+}
+
+fn node_install_fix() -> OnboardingFix {
+    let command = if cfg!(target_os = "macos") {
+        "brew install node"
+    } else if cfg!(target_os = "windows") {
+        "winget install OpenJS.NodeJS.LTS"
+    } else {
+        "curl -fsSL https://fnm.vercel.app/install | bash && fnm install --lts"
+    };
+    OnboardingFix::Command {
+        label: "Install Node.js".to_string(),
+        command: command.to_string(),
+    }
+}
+
+fn step(
+    id: OnboardingStepId,
+    label: &str,
+    status: OnboardingStepStatus,
+    detail: Option<String>,
+    fix: Option<OnboardingFix>,
+) -> OnboardingStep {
+    let fix = if status == OnboardingStepStatus::Pending {
+        fix
+    } else {
+        None
+    };
+    OnboardingStep {
+        id,
+        label: label.to_string(),
+        status,
+        detail,
+        fix,
+    }
+}
+
+fn status_for(done: bool, blocked: bool) -> OnboardingStepStatus {
+    if done {
+        OnboardingStepStatus::Done
+    } else if blocked {
+        OnboardingStepStatus::Blocked
+    } else {
+        OnboardingStepStatus::Pending
+    }
+}
+
+fn build_progress(checks: &OnboardingChecks) -> OnboardingProgress {
+    let codex_ok = checks.codex_version.is_some();
+    let steps = vec![
+        step(
+            OnboardingStepId::CodexInstalled,
+            "Install the Codex CLI",
+            status_for(codex_ok, false),
+            checks
+                .codex_version
+                .clone()
+                .or_else(|| checks.codex_error.clone()),
+            Some(codex_install_fix()),
+        ),
+        step(
+            OnboardingStepId::Authenticated,
+            "Sign in to Codex",
+            status_for(checks.authenticated, !codex_ok),
+            checks.auth_detail.clone(),
+            Some(OnboardingFix::Login {
+                label: "Run codex login".to_string(),
+            }),
+        ),
+        step(
+            OnboardingStepId::NodePresent,
+            "Install Node.js",
+            status_for(checks.node_version.is_some(), false),
+            checks
+                .node_version
+                .clone()
+                .or_else(|| checks.node_error.clone()),
+            Some(node_install_fix()),
+        ),
+        step(
+            OnboardingStepId::WorkspaceAdded,
+            "Add a workspace",
+            status_for(checks.workspace_count > 0, false),
+            (checks.workspace_count > 0)
+                .then(|| format!("{} workspace(s) added", checks.workspace_count)),
+            Some(OnboardingFix::Action {
+                label: "Add workspace".to_string(),
+                action: "addWorkspace".to_string(),
+            }),
+        ),
+        step(
+            OnboardingStepId::ThreadStarted,
+            "Start a test thread",
+            status_for(
+                checks.thread_started,
+                !checks.authenticated || checks.workspace_count == 0,
+            ),
+            None,
+            Some(OnboardingFix::Action {
+                label: "Start a thread".to_string(),
+                action: "startThread".to_string(),
+            }),
+        ),
+    ];
+    let completed = steps
+        .iter()
+        .filter(|step| step.status == OnboardingStepStatus::Done)
+        .count();
+    let next_step = steps
+        .iter()
+        .find(|step| step.status == OnboardingStepStatus::Pending)
+        .map(|step| step.id);
+    OnboardingProgress {
+        total: steps.len(),
+        steps,
+        completed,
+        next_step,
+    }
+}
+
+/// Runs `codex login status`, which exits successfully once credentials exist.
+async fn check_codex_login(codex_bin: Option<String>) -> (bool, Option<String>) {
+    let mut command = build_codex_command_with_bin(codex_bin);
+    command.args(["login", "status"]);
+    command.stdout(std::process::Stdio::piped());
+    command.stderr(std::process::Stdio::piped());
+    match timeout(Duration::from_secs(5), command.output()).await {
+        Ok(Ok(output)) => {
+            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            let detail = if stdout.is_empty() { stderr } else { stdout };
+            (
+                output.status.success(),
+                if detail.is_empty() { None } else { Some(detail) },
+            )
+        }
+        Ok(Err(err)) => (false, Some(err.to_string())),
+        Err(_) => (false, Some("Timed out while checking Codex login.".to_string())),
+    }
+}
+
+async fn any_thread_started(state: &AppState) -> bool {
+    let sessions: Vec<_> = state.sessions.lock().await.values().cloned().collect();
+    for session in sessions {
+        let Ok(response) = session
+            .send_request("thread/list", json!({ "limit": 1 }))
+            .await
+        else {
+            continue;
+        };
+        let has_threads = response
+            .get("result")
+            .and_then(|result| result.get("data"))
+            .and_then(|data| data.as_array())
+            .is_some_and(|data| !data.is_empty());
+        if has_threads {
+            return true;
+        }
+    }
+    false
+}
+
+async fn codex_bin(state: &AppState) -> Option<String> {
+    state.app_settings.lock().await.codex_bin.clone()
+}
+
+#[tauri::command]
+pub(crate) async fn get_onboarding_progress(
+    state: State<'_, AppState>,
+) -> Result<OnboardingProgress, String> {
+    let codex_bin = codex_bin(&state).await;
+    let mut checks = OnboardingChecks::default();
+    match check_codex_installation(codex_bin.clone()).await {
+        Ok(Some(version)) => checks.codex_version = Some(version),
+        Ok(None) => checks.codex_error = Some("Codex CLI did not report a version.".to_string()),
+        Err(err) => checks.codex_error = Some(err),
+    }
+    if checks.codex_version.is_some() {
+        let (authenticated, detail) = check_codex_login(codex_bin.clone()).await;
+        checks.authenticated = authenticated;
+        checks.auth_detail = detail;
+    }
+    let path_env = build_codex_path_env(codex_bin.as_deref());
+    let (_, node_version, node_error) = check_node_installation(path_env.as_deref()).await;
+    checks.node_version = node_version;
+    checks.node_error = node_error;
+    checks.workspace_count = state.workspaces.lock().await.len();
+    checks.thread_started = any_thread_started(&state).await;
+    Ok(build_progress(&checks))
+}
+
+/// Executes the fix for an onboarding step where the app can do so itself
+/// (launching login) and otherwise returns the suggested fix.
+#[tauri::command]
+pub(crate) async fn run_onboarding_fix(
+    step_id: String,
+    state: State<'_, AppState>,
+) -> Result<OnboardingFix, String> {
+    match step_id.as_str() {
+        "codexInstalled" => Ok(codex_install_fix()),
+        "nodePresent" => Ok(node_install_fix()),
+        "authenticated" => {
+            let mut command = build_codex_command_with_bin(codex_bin(&state).await);
+            command.arg("login");
+            command.stdin(std::process::Stdio::null());
+            command.stdout(std::process::Stdio::null());
+            command.stderr(std::process::Stdio::null());
+            command
+                .spawn()
+                .map_err(|err| format!("Failed to launch codex login: {err}"))?;
+            Ok(OnboardingFix::Login {
+                label: "Finish signing in from your browser.".to_string(),
+            })
+        }
+        "workspaceAdded" => Ok(OnboardingFix::Action {
+            label: "Add workspace".to_string(),
+            action: "addWorkspace".to_string(),
+        }),
+        "threadStarted" => Ok(OnboardingFix::Action {
+            label: "Start a thread".to_string(),
+            action: "startThread".to_string(),
+        }),
+        _ => Err(format!("Unknown onboarding step: {step_id}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_install_blocks_dependent_steps() {
+        let checks = OnboardingChecks {
+            codex_error: Some("not found".to_string()),
+            node_version: Some("v20.0.0".to_string()),
+            ..OnboardingChecks::default()
+        };
+        let progress = build_progress(&checks);
+        let statuses: Vec<_> = progress.steps.iter().map(|step| step.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                OnboardingStepStatus::Pending,
+                OnboardingStepStatus::Blocked,
+                OnboardingStepStatus::Done,
+                OnboardingStepStatus::Pending,
+                OnboardingStepStatus::Blocked,
+            ]
+        );
+        assert_eq!(progress.completed, 1);
+        assert_eq!(progress.next_step, Some(OnboardingStepId::CodexInstalled));
+        assert!(progress.steps[0].fix.is_some());
+        assert!(progress.steps[1].fix.is_none());
+    }
+
+    #[test]
+    fn completed_setup_has_no_next_step() {
+        let checks = OnboardingChecks {
+            codex_version: Some("codex 1.0.0".to_string()),
+            authenticated: true,
+            node_version: Some("v20.0.0".to_string()),
+            workspace_count: 2,
+            thread_started: true,
+            ..OnboardingChecks::default()
+        };
+        let progress = build_progress(&checks);
+        assert_eq!(progress.completed, progress.total);
+        assert_eq!(progress.next_step, None);
+        assert!(progress.steps.iter().all(|step| step.fix.is_none()));
+    }
+}
@@ -0,0 +1,173 @@
+use std::path::PathBuf;
+
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+use tokio::task;
+use uuid::Uuid;
+
+use crate::access_control;
+use crate::backend::atomic_write::backups_dir;
+use crate::backend::command_output::safe_file_name;
+use crate::backend::dry_run::ProposedPatch;
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::patch_staging::{
+    self, staged_file, staged_files, ApplyReport, HunkRef, StagedPatch, StagedPatchSource,
+};
+use crate::state::AppState;
+
+fn stage(app: &AppHandle, patch: StagedPatch) {
+    if patch.files.is_empty() {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let _guard = state.staged_patch_writes.lock().await;
+        let result = match state.data_cipher().await {
+            Ok(cipher) => patch_staging::stage_patch(&state.data_dir, &cipher, patch),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to stage patch: {err}");
+        }
+    });
+}
+
+/// Stages the changes of a finished dry-run turn.
+pub(crate) fn stage_dry_run(app: &AppHandle, proposed: ProposedPatch) {
+    let files = proposed
+        .files
+        .iter()
+        .filter_map(|file| staged_file(&file.path, file.kind.as_deref(), &file.diff))
+        .collect();
+    stage(
+        app,
+        StagedPatch {
+            id: Uuid::new_v4().to_string(),
+            workspace_id: proposed.workspace_id,
+            thread_id: proposed.thread_id,
+            turn_id: Some(proposed.turn_id),
+            source: StagedPatchSource::DryRun,
+            created_at: chrono::Utc::now().timestamp_millis(),
+            files,
+        },
+    );
+}
+
+/// Stages file changes whose approval was declined; called for every
+/// `app-server-event`, local or forwarded from a remote daemon. Dry-run turns
+/// are staged as a whole when they complete instead.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    if message.get("method").and_then(|value| value.as_str()) != Some("item/completed") {
+        return;
+    }
+    let Some(params) = message.get("params") else {
+        return;
+    };
+    let Some(item) = params.get("item") else {
+        return;
+    };
+    if item.get("type").and_then(|value| value.as_str()) != Some("fileChange")
+        || item.get("status").and_then(|value| value.as_str()) != Some("declined")
+    {
+        return;
+    }
+    let Some(thread_id) = params.get("threadId").and_then(|value| value.as_str()) else {
+        return;
+    };
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let in_dry_run = state
+        .dry_runs
+        .lock()
+        .map(|dry_runs| dry_runs.is_running(workspace_id, thread_id))
+        .unwrap_or(false);
+    if in_dry_run {
+        return;
+    }
+    let changes = item
+        .get("changes")
+        .and_then(|value| value.as_array())
+        .cloned()
+        .unwrap_or_default();
+    stage(
+        app,
+        StagedPatch {
+            id: Uuid::new_v4().to_string(),
+            workspace_id: workspace_id.to_string(),
+            thread_id: thread_id.to_string(),
+            turn_id: params
+                .get("turnId")
+                .and_then(|value| value.as_str())
+                .map(str::to_string),
+            source: StagedPatchSource::DeclinedApproval,
+            created_at: chrono::Utc::now().timestamp_millis(),
+            files: staged_files(&changes),
+        },
+    );
+}
+
+/// Lists patches the agent proposed that were not applied, oldest first.
+#[tauri::command]
+pub(crate) async fn list_staged_patches(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<StagedPatch>, String> {
+    let cipher = state.data_cipher().await?;
+    patch_staging::read_patches(&state.data_dir, &cipher, &workspace_id)
+}
+
+/// Applies hunks of a staged patch to the workspace, all remaining ones when
+/// `hunks` is omitted. Hunks whose context no longer matches are reported as
+/// conflicts and stay staged. Files are written atomically with session
+/// backups; if any write fails, none of the patch's files change.
+#[tauri::command]
+pub(crate) async fn apply_staged_patch(
+    workspace_id: String,
+    patch_id: String,
+    hunks: Option<Vec<HunkRef>>,
+    state: State<'_, AppState>,
+) -> Result<ApplyReport, String> {
+    access_control::ensure_workspace_unlocked(&*state, &workspace_id).await?;
+    let workspace_root = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?;
+        PathBuf::from(&entry.path)
+    };
+    let _guard = state.staged_patch_writes.lock().await;
+    let cipher = state.data_cipher().await?;
+    let mut patches = patch_staging::read_patches(&state.data_dir, &cipher, &workspace_id)?;
+    let patch = patches
+        .iter()
+        .find(|patch| patch.id == patch_id)
+        .cloned()
+        .ok_or_else(|| format!("No staged patch {patch_id}"))?;
+
+    let backup_dir = backups_dir(&state.data_dir).join(safe_file_name(&workspace_id));
+    let mut report = task::spawn_blocking(move || {
+        patch_staging::apply_to_workspace(&workspace_root, &backup_dir, &patch, hunks.as_deref())
+    })
+    .await
+    .map_err(|err| err.to_string())?;
+
+    report.patch_completed = patch_staging::mark_applied(&mut patches, &patch_id, &report.applied);
+    patch_staging::write_patches(&state.data_dir, &cipher, &workspace_id, &patches)?;
+    Ok(report)
+}
+
+/// Removes a staged patch without applying it.
+#[tauri::command]
+pub(crate) async fn discard_staged_patch(
+    workspace_id: String,
+    patch_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let _guard = state.staged_patch_writes.lock().await;
+    let cipher = state.data_cipher().await?;
+    let mut patches = patch_staging::read_patches(&state.data_dir, &cipher, &workspace_id)?;
+    patches.retain(|patch| patch.id != patch_id);
+    patch_staging::write_patches(&state.data_dir, &cipher, &workspace_id, &patches)
+}
@@ -0,0 +1,46 @@
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::events::AppServerEvent;
+use crate::backend::plan_tracker::ThreadPlan;
+use crate::state::AppState;
+use crate::workspace_windows::emit_app_server_event;
+
+const STEP_COMPLETED_METHOD: &str = "plan/stepCompleted";
+
+/// Tracks agent plans from app-server notifications and emits a
+/// `plan/stepCompleted` app-server event per newly completed step; called for
+/// every `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let completed = match state.plans.lock() {
+        Ok(mut plans) => plans.observe(workspace_id, message),
+        Err(_) => return,
+    };
+    for step in completed {
+        let thread_id = step.thread_id.clone();
+        emit_app_server_event(
+            app,
+            workspace_id,
+            Some(STEP_COMPLETED_METHOD),
+            Some(&thread_id),
+            AppServerEvent {
+                workspace_id: workspace_id.to_string(),
+                message: json!({ "method": STEP_COMPLETED_METHOD, "params": step }),
+            },
+        );
+    }
+}
+
+/// Returns the latest plan the agent reported for a thread in this session.
+#[tauri::command]
+pub(crate) async fn get_thread_plan(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<ThreadPlan>, String> {
+    let plans = state.plans.lock().map_err(|_| "plan tracker unavailable")?;
+    Ok(plans.get(&workspace_id, &thread_id))
+}
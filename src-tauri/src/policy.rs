@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use tauri::State;
+
+use crate::state::AppState;
+use crate::types::ApprovalDelegationSettings;
+
+const APPROVAL_POLICIES: [&str; 4] = ["untrusted", "on-failure", "on-request", "never"];
+
+/// Admin-managed policy. It is read from a system location once at startup
+/// and is never written by the app, so users cannot relax it from the UI.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OrgPolicy {
+    /// Rejects turns in `full-access` mode.
+    #[serde(default, alias = "disallow_full_access")]
+    pub(crate) disallow_full_access: bool,
+    /// Approval policy used for every interactive thread and turn.
+    #[serde(default, alias = "approval_policy")]
+    pub(crate) approval_policy: Option<String>,
+    /// Workspaces must live under one of these roots to run writable turns.
+    /// Full access is rejected as well, since it ignores writable roots.
+    #[serde(default, alias = "writable_roots")]
+    pub(crate) writable_roots: Option<Vec<PathBuf>>,
+    /// Codex binary used for every workspace, overriding settings.
+    #[serde(default, alias = "codex_bin")]
+    pub(crate) codex_bin: Option<String>,
+    /// Keeps approval delegation on with sign-off required, whatever the
+    /// settings say.
+    #[serde(default, alias = "require_approval_signoff")]
+    pub(crate) require_approval_signoff: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EffectivePolicy {
+    /// Policy file that was loaded, if any.
+    pub(crate) source: Option<String>,
+    /// Problem reading the policy file; the app then denies full access.
+    pub(crate) error: Option<String>,
+    #[serde(flatten)]
+    pub(crate) policy: OrgPolicy,
+}
+
+pub(crate) fn policy_path() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    {
+        let program_data =
+            std::env::var("ProgramData").unwrap_or_else(|_| "C:\\ProgramData".to_string());
+        PathBuf::from(program_data)
+            .join("CodexMonitor")
+            .join("policy.toml")
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        PathBuf::from("/etc/codexmonitor/policy.toml")
+    }
+}
+
+fn parse_policy(contents: &str) -> Result<OrgPolicy, String> {
+    let policy: OrgPolicy = toml::from_str(contents).map_err(|err| err.to_string())?;
+    if let Some(approval) = policy.approval_policy.as_deref() {
+        if !APPROVAL_POLICIES.contains(&approval) {
+            return Err(format!("Unknown approval_policy \"{approval}\""));
+        }
+    }
+    Ok(policy)
+}
+
+/// Loads the policy file. A file that exists but cannot be parsed fails
+/// closed: full access is denied until an admin fixes it.
+pub(crate) fn load_policy(path: &Path) -> EffectivePolicy {
+    let source = Some(path.display().to_string());
+    match std::fs::read_to_string(path) {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => EffectivePolicy {
+            source: None,
+            error: None,
+            policy: OrgPolicy::default(),
+        },
+        Err(err) => EffectivePolicy {
+            source,
+            error: Some(err.to_string()),
+            policy: OrgPolicy {
+                disallow_full_access: true,
+                ..OrgPolicy::default()
+            },
+        },
+        Ok(contents) => match parse_policy(&contents) {
+            Ok(policy) => EffectivePolicy {
+                source,
+                error: None,
+                policy,
+            },
+            Err(err) => EffectivePolicy {
+                source,
+                error: Some(err),
+                policy: OrgPolicy {
+                    disallow_full_access: true,
+                    ..OrgPolicy::default()
+                },
+            },
+        },
+    }
+}
+
+impl OrgPolicy {
+    /// Checks a turn's access mode against the policy for a workspace path.
+    pub(crate) fn check_access(
+        &self,
+        access_mode: &str,
+        workspace_path: &str,
+    ) -> Result<(), String> {
+        self.check_full_access(access_mode)?;
+        if access_mode == "read-only" {
+            return Ok(());
+        }
+        if !self.allows_writes_in(Path::new(workspace_path)) {
+            return Err(format!(
+                "Your organization's policy does not allow writes in {workspace_path}; \
+                 use read-only mode."
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether the policy's writable roots, if any, cover `path`.
+    pub(crate) fn allows_writes_in(&self, path: &Path) -> bool {
+        self.writable_roots
+            .as_ref()
+            .is_none_or(|roots| roots.iter().any(|root| path.starts_with(root)))
+    }
+
+    /// Rejects `full-access` when the policy forbids it. Remote workspaces
+    /// only get this check, since their paths live on another machine.
+    pub(crate) fn check_full_access(&self, access_mode: &str) -> Result<(), String> {
+        if access_mode != "full-access" {
+            return Ok(());
+        }
+        if self.disallow_full_access {
+            return Err("Full access is disabled by your organization's policy.".to_string());
+        }
+        if self.writable_roots.is_some() {
+            return Err(
+                "Full access is disabled because your organization restricts writable roots."
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// The approval policy to send, given the one the app would use.
+    pub(crate) fn approval_policy<'a>(&'a self, default: &'a str) -> &'a str {
+        self.approval_policy.as_deref().unwrap_or(default)
+    }
+
+    /// Approval delegation settings with the policy's sign-off requirement
+    /// applied.
+    pub(crate) fn approval_delegation(
+        &self,
+        settings: &ApprovalDelegationSettings,
+    ) -> ApprovalDelegationSettings {
+        let mut settings = settings.clone();
+        if self.require_approval_signoff {
+            settings.enabled = true;
+            settings.require_signoff = true;
+        }
+        settings
+    }
+
+    /// Rejects delegation settings that turn off sign-off the policy requires.
+    pub(crate) fn check_approval_delegation(
+        &self,
+        settings: &ApprovalDelegationSettings,
+    ) -> Result<(), String> {
+        if self.require_approval_signoff && !(settings.enabled && settings.require_signoff) {
+            return Err(
+                "Your organization's policy requires sign-off for delegated approvals.".to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// The codex binary to use, given the one from settings.
+    pub(crate) fn codex_bin(&self, configured: Option<String>) -> Option<String> {
+        self.codex_bin
+            .clone()
+            .filter(|value| !value.trim().is_empty())
+            .or(configured)
+    }
+}
+
+/// Returns the organization policy in effect and where it came from.
+#[tauri::command]
+pub(crate) async fn get_effective_policy(
+    state: State<'_, AppState>,
+) -> Result<EffectivePolicy, String> {
+    Ok(state.policy.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn parses_policy_and_enforces_access() {
+        let policy = parse_policy(
+            r#"
+disallow_full_access = true
+approval_policy = "untrusted"
+writable_roots = ["/work"]
+codex_bin = "/opt/codex/bin/codex"
+require_approval_signoff = true
+"#,
+        )
+        .expect("policy");
+        assert!(policy.check_access("full-access", "/work/app").is_err());
+        assert!(policy.check_access("current", "/work/app").is_ok());
+        assert!(policy.check_access("current", "/home/me/app").is_err());
+        assert!(policy.check_access("read-only", "/home/me/app").is_ok());
+        assert_eq!(policy.approval_policy("on-request"), "untrusted");
+        assert_eq!(
+            policy.codex_bin(Some("codex".to_string())).as_deref(),
+            Some("/opt/codex/bin/codex")
+        );
+
+        let relaxed = ApprovalDelegationSettings::default();
+        assert!(policy.check_approval_delegation(&relaxed).is_err());
+        let pinned = policy.approval_delegation(&relaxed);
+        assert!(pinned.enabled && pinned.require_signoff);
+        assert!(policy.check_approval_delegation(&pinned).is_ok());
+
+        assert!(parse_policy("approval_policy = \"sometimes\"").is_err());
+        let open = OrgPolicy::default();
+        assert!(open.check_access("full-access", "/anywhere").is_ok());
+        assert_eq!(open.approval_policy("on-request"), "on-request");
+        assert!(open.check_approval_delegation(&relaxed).is_ok());
+    }
+
+    #[test]
+    fn missing_file_is_open_and_broken_file_fails_closed() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let missing = load_policy(&dir.join("policy.toml"));
+        assert_eq!(missing.source, None);
+        assert!(!missing.policy.disallow_full_access);
+
+        std::fs::create_dir_all(&dir).expect("dir");
+        let path = dir.join("policy.toml");
+        std::fs::write(&path, "disallow_full_access = maybe").expect("write");
+        let broken = load_policy(&path);
+        assert!(broken.error.is_some());
+        assert!(broken.policy.disallow_full_access);
+    }
+}
@@ -0,0 +1,325 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
+
+use crate::backend::{
+    annotations, atomic_write, clock, command_output, directory_grants, full_access, issue_links,
+    offline_queue, patch_staging, semantic_index, task_queue, thread_archive, thread_branches,
+    thread_links, thread_settings, thread_sla, thread_tags, turn_labels, turn_manifest,
+    workspace_prewarm,
+};
+use crate::state::AppState;
+use crate::types::DataRetentionSettings;
+
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const PURGE_TOKEN_TTL: Duration = Duration::from_secs(60);
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Local data that can be retained for a limited time and purged on demand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DataClass {
+    Journals,
+    Analytics,
+    Drafts,
+    ApprovalLogs,
+    Reports,
+    Artifacts,
+}
+
+const DATA_CLASSES: [DataClass; 6] = [
+    DataClass::Journals,
+    DataClass::Analytics,
+    DataClass::Drafts,
+    DataClass::ApprovalLogs,
+    DataClass::Reports,
+    DataClass::Artifacts,
+];
+
+impl DataClass {
+    fn dir_name(self) -> &'static str {
+        match self {
+            DataClass::Journals => "journals",
+            DataClass::Analytics => "analytics",
+            DataClass::Drafts => "drafts",
+            DataClass::ApprovalLogs => "approvals",
+            DataClass::Reports => "reports",
+            DataClass::Artifacts => "artifacts",
+        }
+    }
+
+    fn dir(self, data_dir: &Path) -> PathBuf {
+        data_dir.join(self.dir_name())
+    }
+
+    fn retention_days(self, retention: &DataRetentionSettings) -> Option<u32> {
+        match self {
+            DataClass::Journals => retention.journals_days,
+            DataClass::Analytics => retention.analytics_days,
+            DataClass::Drafts => retention.drafts_days,
+            DataClass::ApprovalLogs => retention.approval_logs_days,
+            DataClass::Reports => retention.reports_days,
+            DataClass::Artifacts => retention.artifacts_days,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PurgeSummary {
+    pub(crate) removed_entries: usize,
+    pub(crate) removed_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PurgeConfirmation {
+    pub(crate) token: String,
+    pub(crate) expires_in_ms: u64,
+    pub(crate) pending: PurgeSummary,
+}
+
+/// Pending confirmation token for `purge_all_local_data`.
+#[derive(Default)]
+pub(crate) struct PurgeTokenState {
+    token: Option<(String, Instant)>,
+}
+
+fn entry_size(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn remove_entry(path: &Path, summary: &mut PurgeSummary) -> Result<(), String> {
+    let size = entry_size(path);
+    let is_dir = std::fs::symlink_metadata(path)
+        .map(|metadata| metadata.is_dir())
+        .unwrap_or(false);
+    let result = if is_dir {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    };
+    match result {
+        Ok(()) => {
+            summary.removed_entries += 1;
+            summary.removed_bytes += size;
+            Ok(())
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(format!("Failed to remove {}: {err}", path.display())),
+    }
+}
+
+/// Removes top-level entries in `dir` last modified before `cutoff`.
+fn purge_dir_older_than(
+    dir: &Path,
+    cutoff: SystemTime,
+    summary: &mut PurgeSummary,
+) -> Result<(), String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries.flatten() {
+        let modified = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .unwrap_or_else(|_| SystemTime::now());
+        if modified < cutoff {
+            remove_entry(&entry.path(), summary)?;
+        }
+    }
+    Ok(())
+}
+
+/// Applies the retention window of every data class.
+pub(crate) fn purge_expired(
+    data_dir: &Path,
+    retention: &DataRetentionSettings,
+    now: SystemTime,
+) -> Result<PurgeSummary, String> {
+    let mut summary = PurgeSummary::default();
+    for class in DATA_CLASSES {
+        let Some(days) = class.retention_days(retention) else {
+            continue;
+        };
+        let cutoff = now
+            .checked_sub(DAY * days)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        purge_dir_older_than(&class.dir(data_dir), cutoff, &mut summary)?;
+    }
+    Ok(summary)
+}
+
+fn purgeable_paths(data_dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = DATA_CLASSES
+        .iter()
+        .map(|class| class.dir(data_dir))
+        .collect();
+    paths.push(thread_archive::archive_path(data_dir));
+    paths.push(issue_links::issue_links_path(data_dir));
+    paths.push(task_queue::task_queue_path(data_dir));
+    paths.push(offline_queue::offline_queue_path(data_dir));
+    paths.push(thread_settings::thread_settings_path(data_dir));
+    paths.push(thread_tags::thread_tags_path(data_dir));
+    paths.push(thread_tags::thread_filters_path(data_dir));
+    paths.push(thread_links::thread_links_path(data_dir));
+    paths.push(turn_labels::turn_labels_path(data_dir));
+    paths.push(thread_branches::thread_branches_path(data_dir));
+    paths.push(annotations::annotations_path(data_dir));
+    paths.push(thread_sla::thread_timers_path(data_dir));
+    paths.push(clock::clock_jumps_path(data_dir));
+    paths.push(directory_grants::directory_grants_path(data_dir));
+    paths.push(full_access::full_access_grants_path(data_dir));
+    paths.push(workspace_prewarm::workspace_usage_path(data_dir));
+    paths.push(command_output::command_outputs_dir(data_dir));
+    paths.push(semantic_index::semantic_index_dir(data_dir));
+    paths.push(patch_staging::staged_patches_dir(data_dir));
+    paths.push(atomic_write::backups_dir(data_dir));
+    paths.push(turn_manifest::turn_manifests_dir(data_dir));
+    paths
+}
+
+fn pending_purge(data_dir: &Path) -> PurgeSummary {
+    let existing: Vec<PathBuf> = purgeable_paths(data_dir)
+        .into_iter()
+        .filter(|path| path.exists())
+        .collect();
+    PurgeSummary {
+        removed_entries: existing.len(),
+        removed_bytes: existing.iter().map(|path| entry_size(path)).sum(),
+    }
+}
+
+/// Deletes every local data class, archived thread snapshots, thread issue
+/// links, queued agent tasks and offline messages, per-thread model and
+/// effort memory, thread tags and saved filters, automatic thread branch
+/// mappings, captured command outputs, the semantic search index, staged
+/// patches and their backups, and turn manifests. Settings and the workspace
+/// list are kept.
+fn purge_all(data_dir: &Path) -> Result<PurgeSummary, String> {
+    let mut summary = PurgeSummary::default();
+    for path in purgeable_paths(data_dir) {
+        remove_entry(&path, &mut summary)?;
+    }
+    Ok(summary)
+}
+
+/// Runs the retention purger periodically for the lifetime of the app.
+pub(crate) fn spawn_retention_purger(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = app.state::<AppState>();
+            let retention = state.app_settings.lock().await.data_retention.clone();
+            if let Err(err) = purge_expired(&state.data_dir, &retention, SystemTime::now()) {
+                eprintln!("Failed to purge expired local data: {err}");
+            }
+            tokio::time::sleep(PURGE_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) async fn prepare_purge_local_data(
+    state: State<'_, AppState>,
+) -> Result<PurgeConfirmation, String> {
+    let token = Uuid::new_v4().to_string();
+    state.purge_token.lock().await.token = Some((token.clone(), Instant::now()));
+    Ok(PurgeConfirmation {
+        token,
+        expires_in_ms: PURGE_TOKEN_TTL.as_millis() as u64,
+        pending: pending_purge(&state.data_dir),
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn purge_all_local_data(
+    confirmation_token: String,
+    state: State<'_, AppState>,
+) -> Result<PurgeSummary, String> {
+    let pending = state.purge_token.lock().await.token.take();
+    match pending {
+        Some((token, issued_at))
+            if token == confirmation_token && issued_at.elapsed() <= PURGE_TOKEN_TTL => {}
+        _ => {
+            return Err(
+                "Invalid or expired confirmation token. Request a new one first.".to_string(),
+            )
+        }
+    }
+    purge_all(&state.data_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_data_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn purge_expired_respects_retention_per_class() {
+        let data_dir = temp_data_dir();
+        for class in DATA_CLASSES {
+            let dir = class.dir(&data_dir);
+            std::fs::create_dir_all(&dir).expect("create class dir");
+            std::fs::write(dir.join("entry.json"), "{}").expect("write entry");
+        }
+        let retention = DataRetentionSettings {
+            journals_days: Some(1),
+            analytics_days: Some(30),
+            drafts_days: None,
+            approval_logs_days: Some(1),
+            reports_days: None,
+            artifacts_days: Some(1),
+        };
+        let now = SystemTime::now() + DAY * 2;
+        let summary = purge_expired(&data_dir, &retention, now).expect("purge");
+
+        assert_eq!(summary.removed_entries, 3);
+        assert!(!DataClass::Journals.dir(&data_dir).join("entry.json").exists());
+        assert!(!DataClass::ApprovalLogs.dir(&data_dir).join("entry.json").exists());
+        assert!(!DataClass::Artifacts.dir(&data_dir).join("entry.json").exists());
+        assert!(DataClass::Analytics.dir(&data_dir).join("entry.json").exists());
+        assert!(DataClass::Drafts.dir(&data_dir).join("entry.json").exists());
+        assert!(DataClass::Reports.dir(&data_dir).join("entry.json").exists());
+    }
+
+    #[test]
+    fn purge_all_keeps_settings_and_workspaces() {
+        let data_dir = temp_data_dir();
+        std::fs::write(data_dir.join("settings.json"), "{}").expect("write settings");
+        std::fs::write(data_dir.join("workspaces.json"), "[]").expect("write workspaces");
+        std::fs::write(thread_archive::archive_path(&data_dir), "[]").expect("write archive");
+        let drafts = DataClass::Drafts.dir(&data_dir);
+        std::fs::create_dir_all(&drafts).expect("create drafts");
+        std::fs::write(drafts.join("draft.txt"), "hello").expect("write draft");
+
+        assert_eq!(pending_purge(&data_dir).removed_entries, 2);
+        let summary = purge_all(&data_dir).expect("purge");
+        assert_eq!(summary.removed_entries, 2);
+        assert_eq!(summary.removed_bytes, 7);
+        assert!(!drafts.exists());
+        assert!(!thread_archive::archive_path(&data_dir).exists());
+        assert!(data_dir.join("settings.json").exists());
+        assert!(data_dir.join("workspaces.json").exists());
+    }
+}
@@ -0,0 +1,130 @@
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::prompt_budget::{PromptSizeCheck, PromptSizeLevel};
+use crate::backend::turn_cost::{self, TurnEstimate};
+use crate::state::AppState;
+
+fn image_count(images: Option<&[String]>) -> usize {
+    images
+        .unwrap_or_default()
+        .iter()
+        .filter(|path| !path.trim().is_empty())
+        .count()
+}
+
+/// Tracks context usage and windows from token usage notifications; called
+/// for every `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    if let Ok(mut catalog) = state.model_catalog.lock() {
+        catalog.observe(workspace_id, message);
+    };
+}
+
+/// Records the models of a `model/list` response in the catalog.
+pub(crate) fn ingest_model_list(state: &AppState, response: &Value) {
+    if let Ok(mut catalog) = state.model_catalog.lock() {
+        catalog.ingest_model_list(response);
+    }
+}
+
+/// Checks a message before `turn/start`. Prompts that cannot fit are
+/// rejected with the suggested trimming; warnings are returned so they can
+/// be attached to the response.
+pub(crate) fn check_before_send(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+    model: Option<&str>,
+    text: &str,
+    images: Option<&[String]>,
+) -> Result<Option<PromptSizeCheck>, String> {
+    let Ok(mut catalog) = state.model_catalog.lock() else {
+        return Ok(None);
+    };
+    let check = catalog.check(
+        workspace_id,
+        thread_id,
+        model,
+        text.trim(),
+        image_count(images),
+    );
+    catalog.note_turn_model(workspace_id, thread_id, check.model.as_deref());
+    match check.level {
+        PromptSizeLevel::Ok => Ok(None),
+        PromptSizeLevel::Warning => Ok(Some(check)),
+        PromptSizeLevel::Error => {
+            let mut message = format!(
+                "Prompt is too large for {}: about {} tokens plus {} already in context exceed the {}-token context window.",
+                check.model.as_deref().unwrap_or("the selected model"),
+                check.estimated_tokens,
+                check.context_tokens,
+                check.context_window.unwrap_or_default(),
+            );
+            for suggestion in &check.suggestions {
+                message.push(' ');
+                message.push_str(suggestion);
+            }
+            Err(message)
+        }
+    }
+}
+
+/// Estimates the size of a prompt and its attachments against the selected
+/// model's context window without sending it. Windows are learned from
+/// `model/list` and token usage, so the window may be unknown at first.
+#[tauri::command]
+pub(crate) async fn check_prompt_size(
+    workspace_id: String,
+    thread_id: String,
+    text: String,
+    images: Option<Vec<String>>,
+    model: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<PromptSizeCheck, String> {
+    let catalog = state
+        .model_catalog
+        .lock()
+        .map_err(|_| "model catalog unavailable")?;
+    Ok(catalog.check(
+        &workspace_id,
+        &thread_id,
+        model.as_deref(),
+        text.trim(),
+        image_count(images.as_deref()),
+    ))
+}
+
+/// Estimates the token and dollar range of a turn before it is sent, from
+/// the prompt size, the thread's context, the reasoning effort and the
+/// thread's last output. Models without built-in pricing get no dollar range.
+#[tauri::command]
+pub(crate) async fn estimate_turn(
+    workspace_id: String,
+    thread_id: String,
+    text: String,
+    images: Option<Vec<String>>,
+    model: Option<String>,
+    effort: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<TurnEstimate, String> {
+    let catalog = state
+        .model_catalog
+        .lock()
+        .map_err(|_| "model catalog unavailable")?;
+    let check = catalog.check(
+        &workspace_id,
+        &thread_id,
+        model.as_deref(),
+        text.trim(),
+        image_count(images.as_deref()),
+    );
+    Ok(turn_cost::estimate_turn(
+        &check,
+        effort.as_deref(),
+        catalog.last_output_tokens(&workspace_id, &thread_id),
+    ))
+}
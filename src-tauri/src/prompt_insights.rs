@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::annotations;
+use crate::backend::annotations::Reaction;
+use crate::backend::prompt_insights::{self, PromptingInsights};
+use crate::state::AppState;
+
+const DEFAULT_INSIGHT_DAYS: u32 = 30;
+
+/// Records each prompt's features with the status of the turn it started;
+/// called for every `app-server-event`, local or forwarded from a remote
+/// daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let outcome = match state.prompt_outcomes.lock() {
+        Ok(mut tracker) => tracker.observe(workspace_id, message, Utc::now().timestamp_millis()),
+        Err(_) => None,
+    };
+    let Some(outcome) = outcome else {
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let _guard = state.prompt_outcome_writes.lock().await;
+        let result = match state.data_cipher().await {
+            Ok(cipher) => prompt_insights::record_outcome(&state.data_dir, &cipher, outcome),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to record prompt outcome: {err}");
+        }
+    });
+}
+
+/// Patterns in how a workspace's prompts relate to interrupted, retried and
+/// thumbs-down turns over the last `days` days, e.g. prompts without file
+/// references getting interrupted more often.
+#[tauri::command]
+pub(crate) async fn get_prompting_insights(
+    workspace_id: String,
+    days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<PromptingInsights, String> {
+    let days = days.unwrap_or(DEFAULT_INSIGHT_DAYS).max(1);
+    let cipher = state.data_cipher().await?;
+    let outcomes = prompt_insights::read_outcomes(
+        &state.data_dir,
+        &cipher,
+        &workspace_id,
+        days,
+        Utc::now().date_naive(),
+    )?;
+    let disliked: HashSet<String> = annotations::thread_annotations(&state, &workspace_id, None)
+        .await
+        .into_iter()
+        .filter(|annotation| annotation.reaction == Some(Reaction::ThumbsDown))
+        .map(|annotation| annotation.turn_id)
+        .collect();
+    Ok(prompt_insights::analyze(&outcomes, &disliked, days))
+}
@@ -48,7 +48,7 @@ fn resolve_codex_home() -> Option<PathBuf> {
     resolve_home_dir().map(|home| home.join(".codex"))
 }
 
-fn default_prompts_dir() -> Option<PathBuf> {
+pub(crate) fn default_prompts_dir() -> Option<PathBuf> {
     resolve_codex_home().map(|home| home.join("prompts"))
 }
 
@@ -70,7 +70,7 @@ fn app_data_dir(state: &State<'_, AppState>) -> Result<PathBuf, String> {
         .ok_or_else(|| "Unable to resolve app data dir.".to_string())
 }
 
-fn workspace_prompts_dir(
+pub(crate) fn workspace_prompts_dir(
     state: &State<'_, AppState>,
     entry: &WorkspaceEntry,
 ) -> Result<PathBuf, String> {
@@ -190,7 +190,7 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
     (description, argument_hint, body)
 }
 
-fn build_prompt_contents(
+pub(crate) fn build_prompt_contents(
     description: Option<String>,
     argument_hint: Option<String>,
     content: String,
@@ -223,7 +223,7 @@ fn build_prompt_contents(
     output
 }
 
-fn sanitize_prompt_name(name: &str) -> Result<String, String> {
+pub(crate) fn sanitize_prompt_name(name: &str) -> Result<String, String> {
     let trimmed = name.trim();
     if trimmed.is_empty() {
         return Err("Prompt name is required.".to_string());
@@ -237,7 +237,7 @@ fn sanitize_prompt_name(name: &str) -> Result<String, String> {
     Ok(trimmed.to_string())
 }
 
-fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry> {
+pub(crate) fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry> {
     let mut out: Vec<CustomPromptEntry> = Vec::new();
     let entries = match fs::read_dir(dir) {
         Ok(entries) => entries,
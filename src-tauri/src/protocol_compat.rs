@@ -0,0 +1,29 @@
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::backend::protocol_compat::{build_report, ProtocolCompatReport};
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// Reports which app-server methods each connected session has sent that
+/// CodexMonitor does not know or considers deprecated, so users can tell
+/// when their codex CLI is newer or older than this version expects.
+#[tauri::command]
+pub(crate) async fn get_protocol_compat_report(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ProtocolCompatReport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "get_protocol_compat_report", json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+    let sessions = state.sessions.lock().await;
+    Ok(build_report(
+        sessions
+            .values()
+            .map(|session| session.protocol_report())
+            .collect(),
+    ))
+}
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::State;
+use tokio::sync::mpsc;
+
+use crate::access_control;
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::patch_staging::resolve_path;
+use crate::backend::quick_ask::{self, MAX_EXCERPT_CHARS};
+use crate::background_turn::{start_thread, start_turn, wait_for_answer};
+use crate::remote_backend;
+use crate::state::AppState;
+
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QuickAskAnswer {
+    pub(crate) thread_id: String,
+    pub(crate) answer: String,
+    /// Whether only excerpts of the file were attached.
+    pub(crate) truncated: bool,
+}
+
+/// Answers `question` about one workspace file in a read-only turn of the
+/// workspace's quick-ask thread, which is started on first use and reused
+/// after. The file is attached whole, or cut down around what the question
+/// mentions when it is long.
+#[tauri::command]
+pub(crate) async fn quick_ask(
+    workspace_id: String,
+    path: String,
+    question: String,
+    state: State<'_, AppState>,
+) -> Result<QuickAskAnswer, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Quick questions are only available for local workspaces.".to_string());
+    }
+    let question = question.trim();
+    if question.is_empty() {
+        return Err("empty user message".to_string());
+    }
+    access_control::ensure_workspace_visible(&*state, &workspace_id).await?;
+    access_control::ensure_workspace_unlocked(&*state, &workspace_id).await?;
+    let session: Arc<WorkspaceSession> = state
+        .sessions
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
+    let full_path = resolve_path(&PathBuf::from(&session.entry.path), &path)?;
+    let bytes = tokio::fs::read(&full_path)
+        .await
+        .map_err(|err| format!("Failed to read {path}: {err}"))?;
+    let content = String::from_utf8(bytes).map_err(|_| format!("{path} is not a text file"))?;
+    let excerpt = quick_ask::excerpt(&content, question, MAX_EXCERPT_CHARS);
+    let prompt = quick_ask::prompt(&path, question, &excerpt);
+
+    // Taking the thread out of the map claims it, so a concurrent ask starts
+    // its own thread instead of sharing a running turn.
+    let reused = state.quick_ask_threads.lock().await.remove(&workspace_id);
+    let (tx, rx) = mpsc::unbounded_channel::<Value>();
+    let thread_id = match reused {
+        Some(thread_id) if start_turn(&session, &thread_id, &prompt, &tx).await.is_ok() => {
+            thread_id
+        }
+        // The quick-ask thread is gone once its app-server restarts.
+        _ => {
+            let thread_id = start_thread(&session).await?;
+            start_turn(&session, &thread_id, &prompt, &tx).await?;
+            thread_id
+        }
+    };
+    drop(tx);
+    let answer = wait_for_answer(&session, &thread_id, rx, ANSWER_TIMEOUT).await;
+    state
+        .quick_ask_threads
+        .lock()
+        .await
+        .insert(workspace_id, thread_id.clone());
+    let answer = answer?;
+    Ok(QuickAskAnswer {
+        thread_id,
+        answer,
+        truncated: excerpt.truncated,
+    })
+}
@@ -0,0 +1,99 @@
+use serde_json::json;
+
+use tauri::{AppHandle, State};
+use tokio::task;
+
+use crate::backend::quick_search::{
+    rank, thread_candidates, workspace_candidate, QuickSearchCandidate, QuickSearchKind,
+    QuickSearchResult, DEFAULT_RESULT_LIMIT,
+};
+use crate::prompts::{default_prompts_dir, discover_prompts_in, workspace_prompts_dir};
+use crate::remote_backend;
+use crate::state::AppState;
+
+fn prompt_candidates(
+    dir: std::path::PathBuf,
+    workspace_id: Option<String>,
+) -> Vec<QuickSearchCandidate> {
+    let scope = if workspace_id.is_some() {
+        "workspace"
+    } else {
+        "global"
+    };
+    discover_prompts_in(&dir, Some(scope))
+        .into_iter()
+        .map(|prompt| QuickSearchCandidate {
+            kind: QuickSearchKind::Prompt,
+            id: prompt.path.clone(),
+            title: prompt.name.clone(),
+            subtitle: prompt.description.clone(),
+            workspace_id: workspace_id.clone(),
+            keywords: prompt.description.into_iter().collect(),
+            updated_at: None,
+        })
+        .collect()
+}
+
+/// Fuzzy-matches workspaces, threads and prompt templates into one ranked list
+/// for the command palette.
+#[tauri::command]
+pub(crate) async fn quick_search(
+    query: String,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<QuickSearchResult>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "quick_search",
+            json!({ "query": query, "limit": limit }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let workspaces: Vec<_> = {
+        let workspaces = state.workspaces.lock().await;
+        let access = state.access_control.lock().await;
+        workspaces
+            .values()
+            .filter(|entry| access.is_visible(entry))
+            .cloned()
+            .collect()
+    };
+    let sessions = state.sessions.lock().await.clone();
+
+    let mut candidates: Vec<QuickSearchCandidate> =
+        workspaces.iter().map(workspace_candidate).collect();
+    for entry in &workspaces {
+        if let Some(session) = sessions.get(&entry.id) {
+            candidates.extend(thread_candidates(session, entry).await);
+        }
+    }
+
+    let mut prompt_dirs: Vec<_> = workspaces
+        .iter()
+        .filter_map(|entry| {
+            workspace_prompts_dir(&state, entry)
+                .ok()
+                .map(|dir| (dir, Some(entry.id.clone())))
+        })
+        .collect();
+    if let Some(dir) = default_prompts_dir() {
+        prompt_dirs.push((dir, None));
+    }
+    let prompts = task::spawn_blocking(move || {
+        prompt_dirs
+            .into_iter()
+            .flat_map(|(dir, workspace_id)| prompt_candidates(dir, workspace_id))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|_| "prompt discovery failed".to_string())?;
+    candidates.extend(prompts);
+
+    let limit = limit.map(|value| value as usize).unwrap_or(DEFAULT_RESULT_LIMIT);
+    Ok(rank(&query, candidates, limit))
+}
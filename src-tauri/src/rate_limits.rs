@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::backend::rate_limits::{parse_rate_limits, QuotaDeferral};
+use crate::backend::turn_limiter::TurnLane;
+use crate::state::AppState;
+use crate::workspace_windows::emit_for_workspace;
+
+/// Rechecks at least this often while waiting, in case the reset time was
+/// reported in the wrong unit or no update follows it.
+const MAX_WAIT: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct TurnDeferredEvent<'a> {
+    workspace_id: &'a str,
+    thread_id: &'a str,
+    lane: TurnLane,
+    /// `None` once the turn stops waiting.
+    deferral: Option<QuotaDeferral>,
+}
+
+/// Tracks the rate-limit windows each workspace's app-server reports;
+/// called for every `app-server-event`, local or forwarded from a remote
+/// daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    if message.get("method").and_then(|value| value.as_str()) != Some("account/rateLimits/updated")
+    {
+        return;
+    }
+    let Some(params) = message.get("params") else {
+        return;
+    };
+    if let Some(state) = app.try_state::<AppState>() {
+        state
+            .rate_limits
+            .update(workspace_id, parse_rate_limits(params));
+    }
+}
+
+/// Holds back scheduled and batch turns while the workspace's remaining
+/// quota is below `rateLimitReservePercent`, until the window resets.
+/// Emits `turn-deferred` with the reason when the turn starts waiting and
+/// again with no deferral when it resumes.
+pub(crate) async fn wait_for_quota(
+    state: &AppState,
+    app: &AppHandle,
+    workspace_id: &str,
+    thread_id: &str,
+    lane: TurnLane,
+) {
+    if lane == TurnLane::Interactive {
+        return;
+    }
+    let mut waited = false;
+    loop {
+        let reserve_percent = state.app_settings.lock().await.rate_limit_reserve_percent;
+        let now = chrono::Utc::now().timestamp_millis();
+        let Some(deferral) = state
+            .rate_limits
+            .deferral(workspace_id, reserve_percent, now)
+        else {
+            break;
+        };
+        let wait = Duration::from_millis(u64::try_from(deferral.resumes_at - now).unwrap_or(0))
+            .min(MAX_WAIT);
+        emit_for_workspace(
+            app,
+            "turn-deferred",
+            workspace_id,
+            TurnDeferredEvent {
+                workspace_id,
+                thread_id,
+                lane,
+                deferral: Some(deferral),
+            },
+        );
+        waited = true;
+        let _ = tokio::time::timeout(wait, state.rate_limits.changed()).await;
+    }
+    if waited {
+        emit_for_workspace(
+            app,
+            "turn-deferred",
+            workspace_id,
+            TurnDeferredEvent {
+                workspace_id,
+                thread_id,
+                lane,
+                deferral: None,
+            },
+        );
+    }
+}
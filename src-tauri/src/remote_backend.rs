@@ -3,12 +3,42 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, oneshot, Mutex};
 
+use crate::ab_test::observe_app_server_event;
+use crate::approval_delegation;
+use crate::auth_expiry;
+use crate::backend::command_output::trim_event_output;
+use crate::backend::turn_summary::params_thread_id;
+use crate::code_stats;
+use crate::command_output;
+use crate::directory_access;
+use crate::dry_run;
+use crate::model_stats;
+use crate::offline_queue;
+use crate::patch_staging;
+use crate::plan_tracker;
+use crate::prompt_budget;
+use crate::prompt_insights;
+use crate::rate_limits;
+use crate::review_pair;
+use crate::semantic_search;
+use crate::team_activity;
+use crate::telemetry;
+use crate::thread_guard;
+use crate::thread_index;
+use crate::thread_journal;
+use crate::thread_links;
+use crate::thread_sla;
+use crate::turn_limiter;
+use crate::verification;
+use crate::workspace_health;
+use crate::workspace_turns;
 use crate::state::AppState;
+use crate::workspace_windows::{emit_app_server_event, emit_for_workspace};
 use crate::types::BackendMode;
 
 const DEFAULT_REMOTE_HOST: &str = "127.0.0.1:4732";
@@ -209,13 +239,67 @@ async fn read_loop(
         if method.is_empty() {
             continue;
         }
-        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let mut params = message.get("params").cloned().unwrap_or(Value::Null);
         match method {
-            "app-server-event" => {
-                let _ = app.emit("app-server-event", params);
-            }
-            "terminal-output" => {
-                let _ = app.emit("terminal-output", params);
+            "app-server-event" | "narration-event" | "terminal-output" | "session-output" => {
+                let workspace_id = params
+                    .get("workspace_id")
+                    .or_else(|| params.get("workspaceId"))
+                    .and_then(|value| value.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                if method == "app-server-event" {
+                    let event_method = params
+                        .get("message")
+                        .and_then(|message| message.get("method"))
+                        .and_then(|value| value.as_str())
+                        .map(|value| value.to_string());
+                    let thread_id = params
+                        .get("message")
+                        .and_then(|message| message.get("params"))
+                        .and_then(params_thread_id);
+                    if let Some(message) = params.get("message") {
+                        observe_app_server_event(&app, &workspace_id, message);
+                        plan_tracker::observe_app_server_event(&app, &workspace_id, message);
+                        prompt_budget::observe_app_server_event(&app, &workspace_id, message);
+                        dry_run::observe_app_server_event(&app, &workspace_id, message);
+                        patch_staging::observe_app_server_event(&app, &workspace_id, message);
+                        command_output::observe_app_server_event(&app, &workspace_id, message);
+                        code_stats::observe_app_server_event(&app, &workspace_id, message);
+                        semantic_search::observe_app_server_event(&app, &workspace_id, message);
+                        approval_delegation::observe_app_server_event(&app, &workspace_id, message);
+                        verification::observe_app_server_event(&app, &workspace_id, message);
+                        offline_queue::observe_app_server_event(&app, &workspace_id, message);
+                        turn_limiter::observe_app_server_event(&app, &workspace_id, message);
+                        rate_limits::observe_app_server_event(&app, &workspace_id, message);
+                        thread_guard::observe_app_server_event(&app, &workspace_id, message);
+                        thread_journal::observe_app_server_event(&app, &workspace_id, message);
+                        review_pair::observe_app_server_event(&app, &workspace_id, message);
+                        prompt_insights::observe_app_server_event(&app, &workspace_id, message);
+                        thread_sla::observe_app_server_event(&app, &workspace_id, message);
+                        directory_access::observe_app_server_event(&app, &workspace_id, message);
+                        workspace_health::observe_app_server_event(&app, &workspace_id, message);
+                        workspace_turns::observe_app_server_event(&app, &workspace_id, message);
+                        model_stats::observe_app_server_event(&app, &workspace_id, message);
+                        thread_links::observe_app_server_event(&app, &workspace_id, message);
+                        auth_expiry::observe_app_server_event(&app, &workspace_id, message);
+                        team_activity::observe_app_server_event(&app, &workspace_id, message);
+                        telemetry::observe_app_server_event(&app, &workspace_id, message);
+                        thread_index::observe_app_server_event(&app, &workspace_id, message);
+                    }
+                    if let Some(message) = params.get_mut("message") {
+                        trim_event_output(message);
+                    }
+                    emit_app_server_event(
+                        &app,
+                        &workspace_id,
+                        event_method.as_deref(),
+                        thread_id.as_deref(),
+                        params,
+                    );
+                } else {
+                    emit_for_workspace(&app, method, &workspace_id, params);
+                }
             }
             _ => {}
         }
@@ -0,0 +1,525 @@
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tauri::State;
+
+use crate::annotations;
+use crate::backend::annotations::{Annotation, Reaction};
+use crate::backend::turn_manifest::TurnManifest;
+use crate::state::AppState;
+use crate::turn_manifest;
+use crate::types::WorkspaceEntry;
+
+const THREAD_PAGE_SIZE: u32 = 50;
+const MAX_REPORT_THREADS: usize = 200;
+
+const REPORT_STYLE: &str = "body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;\
+max-width:960px;margin:2rem auto;padding:0 1rem;color:#1d1d1f;background:#fafafa}\
+h1,h2,h3{font-weight:600}a{color:#0a66c2}\
+.stats{display:flex;gap:1.5rem;flex-wrap:wrap;margin:1rem 0}\
+.stat{background:#fff;border:1px solid #ddd;border-radius:8px;padding:.6rem 1rem}\
+.stat b{display:block;font-size:1.3rem}\
+.item{background:#fff;border:1px solid #e2e2e2;border-radius:8px;padding:.75rem 1rem;margin:.75rem 0}\
+.item .label{font-size:.75rem;text-transform:uppercase;color:#777;margin-bottom:.4rem}\
+.manifest{font-size:.8rem;color:#777;margin:-.4rem 0 .75rem}\
+.annotation{font-size:.85rem;background:#fff8e1;border-radius:6px;padding:.4rem .6rem;margin:.5rem 0}\
+.user{border-left:4px solid #0a66c2}.agent{border-left:4px solid #2e7d32}\
+pre{white-space:pre-wrap;word-break:break-word;background:#f4f4f4;padding:.6rem;border-radius:6px;\
+font-size:.8rem;max-height:480px;overflow:auto}\
+table{border-collapse:collapse;width:100%}td,th{text-align:left;padding:.4rem;border-bottom:1px solid #e5e5e5}";
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct ReportStats {
+    threads: usize,
+    turns: usize,
+    messages: usize,
+    commands: usize,
+    file_changes: usize,
+}
+
+impl ReportStats {
+    fn add(&mut self, other: &ReportStats) {
+        self.threads += other.threads;
+        self.turns += other.turns;
+        self.messages += other.messages;
+        self.commands += other.commands;
+        self.file_changes += other.file_changes;
+    }
+}
+
+struct ThreadPage {
+    id: String,
+    title: String,
+    stats: ReportStats,
+    html: String,
+}
+
+fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+<title>{}</title>\n<style>{REPORT_STYLE}</style>\n</head>\n<body>\n{body}\n</body>\n</html>\n",
+        escape_html(title)
+    )
+}
+
+fn text_field(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(|value| value.as_str())
+        .unwrap_or("")
+        .to_string()
+}
+
+fn user_message_text(item: &Value) -> String {
+    item.get("content")
+        .and_then(|value| value.as_array())
+        .map(|inputs| {
+            inputs
+                .iter()
+                .filter_map(|input| match input.get("type").and_then(|t| t.as_str()) {
+                    Some("text") => input.get("text").and_then(|t| t.as_str()).map(str::to_string),
+                    Some("image") | Some("localImage") => Some("[image]".to_string()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default()
+}
+
+fn command_text(item: &Value) -> String {
+    match item.get("command") {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| part.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        Some(Value::String(command)) => command.clone(),
+        _ => String::new(),
+    }
+}
+
+fn render_item(item: &Value, stats: &mut ReportStats) -> Option<String> {
+    let kind = item.get("type").and_then(|value| value.as_str())?;
+    let html = match kind {
+        "userMessage" => {
+            stats.messages += 1;
+            format!(
+                "<div class=\"item user\"><div class=\"label\">User</div><pre>{}</pre></div>",
+                escape_html(&user_message_text(item))
+            )
+        }
+        "agentMessage" => {
+            stats.messages += 1;
+            format!(
+                "<div class=\"item agent\"><div class=\"label\">Agent</div><pre>{}</pre></div>",
+                escape_html(&text_field(item, "text"))
+            )
+        }
+        "commandExecution" => {
+            stats.commands += 1;
+            let exit = item
+                .get("exitCode")
+                .and_then(|value| value.as_i64())
+                .map(|code| format!(" (exit {code})"))
+                .unwrap_or_default();
+            format!(
+                "<div class=\"item\"><div class=\"label\">Command{}</div><pre>$ {}</pre><pre>{}</pre></div>",
+                escape_html(&exit),
+                escape_html(&command_text(item)),
+                escape_html(&text_field(item, "aggregatedOutput"))
+            )
+        }
+        "fileChange" => {
+            let changes = item
+                .get("changes")
+                .and_then(|value| value.as_array())
+                .cloned()
+                .unwrap_or_default();
+            stats.file_changes += changes.len();
+            let mut html = String::from("<div class=\"item\"><div class=\"label\">File changes</div>");
+            for change in &changes {
+                html.push_str(&format!(
+                    "<h3>{}</h3><pre>{}</pre>",
+                    escape_html(&text_field(change, "path")),
+                    escape_html(&text_field(change, "diff"))
+                ));
+            }
+            html.push_str("</div>");
+            html
+        }
+        _ => return None,
+    };
+    Some(html)
+}
+
+fn render_manifest(manifest: &TurnManifest) -> String {
+    let mut parts = vec![format!(
+        "commit {}",
+        manifest.git_sha.as_deref().unwrap_or("unknown")
+    )];
+    if !manifest.dirty_files.is_empty() {
+        parts.push(format!("{} dirty files", manifest.dirty_files.len()));
+    }
+    if let Some(model) = &manifest.model {
+        parts.push(format!("model {model}"));
+    }
+    if let Some(version) = &manifest.codex_version {
+        parts.push(version.clone());
+    }
+    parts.push(format!("{} access", manifest.access_mode));
+    format!(
+        "<p class=\"manifest\">{}</p>\n",
+        escape_html(&parts.join(" · "))
+    )
+}
+
+fn render_annotation(annotation: &Annotation) -> String {
+    let mut parts = Vec::new();
+    match annotation.reaction {
+        Some(Reaction::ThumbsUp) => parts.push("Thumbs up".to_string()),
+        Some(Reaction::ThumbsDown) => parts.push("Thumbs down".to_string()),
+        None => {}
+    }
+    if annotation.needs_follow_up {
+        parts.push("Needs follow-up".to_string());
+    }
+    if let Some(comment) = &annotation.comment {
+        parts.push(comment.clone());
+    }
+    let label = if annotation.item_id.is_some() {
+        "Item note"
+    } else {
+        "Turn note"
+    };
+    format!(
+        "<p class=\"annotation\"><b>{label}:</b> {}</p>\n",
+        escape_html(&parts.join(" · "))
+    )
+}
+
+fn render_thread_page(
+    thread: &Value,
+    workspace_name: &str,
+    manifests: &HashMap<String, TurnManifest>,
+    annotations: &HashMap<String, Vec<Annotation>>,
+) -> ThreadPage {
+    let id = text_field(thread, "id");
+    let preview = text_field(thread, "preview");
+    let title = if preview.trim().is_empty() {
+        id.clone()
+    } else {
+        preview.lines().next().unwrap_or("").to_string()
+    };
+    let mut stats = ReportStats {
+        threads: 1,
+        ..Default::default()
+    };
+    let mut body = format!(
+        "<p><a href=\"../index.html\">&larr; {}</a></p>\n<h1>{}</h1>\n",
+        escape_html(workspace_name),
+        escape_html(&title)
+    );
+    let turns = thread
+        .get("turns")
+        .and_then(|value| value.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for (index, turn) in turns.iter().enumerate() {
+        stats.turns += 1;
+        body.push_str(&format!("<h2>Turn {}</h2>\n", index + 1));
+        let turn_id = text_field(turn, "id");
+        if let Some(manifest) = manifests.get(&turn_id) {
+            body.push_str(&render_manifest(manifest));
+        }
+        for annotation in annotations.get(&turn_id).into_iter().flatten() {
+            body.push_str(&render_annotation(annotation));
+        }
+        let items = turn
+            .get("items")
+            .and_then(|value| value.as_array())
+            .cloned()
+            .unwrap_or_default();
+        for item in &items {
+            if let Some(html) = render_item(item, &mut stats) {
+                body.push_str(&html);
+                body.push('\n');
+            }
+        }
+    }
+    let html = page(&title, &body);
+    ThreadPage {
+        id,
+        title,
+        stats,
+        html,
+    }
+}
+
+fn render_stats(stats: &ReportStats) -> String {
+    let entries = [
+        ("Threads", stats.threads),
+        ("Turns", stats.turns),
+        ("Messages", stats.messages),
+        ("Commands", stats.commands),
+        ("File changes", stats.file_changes),
+    ];
+    let mut html = String::from("<div class=\"stats\">");
+    for (label, value) in entries {
+        html.push_str(&format!("<div class=\"stat\"><b>{value}</b>{label}</div>"));
+    }
+    html.push_str("</div>");
+    html
+}
+
+fn render_index(entry: &WorkspaceEntry, pages: &[ThreadPage], diff: &str, generated_at: &str) -> String {
+    let mut totals = ReportStats::default();
+    for thread in pages {
+        totals.add(&thread.stats);
+    }
+    let mut body = format!(
+        "<h1>{}</h1>\n<p>{}<br>Generated {}</p>\n{}\n<h2>Threads</h2>\n",
+        escape_html(&entry.name),
+        escape_html(&entry.path),
+        escape_html(generated_at),
+        render_stats(&totals)
+    );
+    if pages.is_empty() {
+        body.push_str("<p>No threads recorded for this workspace.</p>\n");
+    } else {
+        body.push_str("<table><tr><th>Thread</th><th>Turns</th><th>Commands</th><th>File changes</th></tr>\n");
+        for thread in pages {
+            body.push_str(&format!(
+                "<tr><td><a href=\"threads/{}.html\">{}</a></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&thread.id),
+                escape_html(&thread.title),
+                thread.stats.turns,
+                thread.stats.commands,
+                thread.stats.file_changes
+            ));
+        }
+        body.push_str("</table>\n");
+    }
+    if !diff.trim().is_empty() {
+        body.push_str(&format!(
+            "<h2>Uncommitted changes</h2>\n<pre>{}</pre>\n",
+            escape_html(diff)
+        ));
+    }
+    page(&format!("{} report", entry.name), &body)
+}
+
+fn same_path(left: &str, right: &str) -> bool {
+    left.trim_end_matches(['/', '\\']) == right.trim_end_matches(['/', '\\'])
+}
+
+fn sanitize_file_name(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' { ch } else { '-' })
+        .collect();
+    let trimmed = cleaned.trim_matches('-');
+    if trimmed.is_empty() {
+        "workspace".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn write_report(root: &Path, index: &str, pages: &[ThreadPage]) -> Result<(), String> {
+    let threads_dir = root.join("threads");
+    fs::create_dir_all(&threads_dir).map_err(|e| e.to_string())?;
+    fs::write(root.join("index.html"), index).map_err(|e| e.to_string())?;
+    for thread in pages {
+        let path = threads_dir.join(format!("{}.html", sanitize_file_name(&thread.id)));
+        fs::write(path, &thread.html).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn export_workspace_report(
+    workspace_id: String,
+    output_dir: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let session = {
+        let sessions = state.sessions.lock().await;
+        sessions
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not connected")?
+    };
+
+    let mut summaries = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let response = session
+            .send_request(
+                "thread/list",
+                json!({ "cursor": cursor, "limit": THREAD_PAGE_SIZE }),
+            )
+            .await?;
+        let result = response.get("result").cloned().unwrap_or(Value::Null);
+        let data = result
+            .get("data")
+            .and_then(|value| value.as_array())
+            .cloned()
+            .unwrap_or_default();
+        summaries.extend(
+            data.into_iter()
+                .filter(|thread| same_path(&text_field(thread, "cwd"), &entry.path)),
+        );
+        cursor = result
+            .get("nextCursor")
+            .and_then(|value| value.as_str())
+            .map(str::to_string);
+        if cursor.is_none() || summaries.len() >= MAX_REPORT_THREADS {
+            break;
+        }
+    }
+    summaries.truncate(MAX_REPORT_THREADS);
+
+    let manifests: HashMap<String, TurnManifest> =
+        turn_manifest::workspace_manifests(&state, &workspace_id)
+            .await
+            .into_iter()
+            .map(|manifest| (manifest.turn_id.clone(), manifest))
+            .collect();
+    let mut turn_annotations: HashMap<String, Vec<Annotation>> = HashMap::new();
+    for annotation in annotations::thread_annotations(&state, &workspace_id, None).await {
+        turn_annotations
+            .entry(annotation.turn_id.clone())
+            .or_default()
+            .push(annotation);
+    }
+    let mut pages = Vec::with_capacity(summaries.len());
+    for summary in &summaries {
+        let thread_id = text_field(summary, "id");
+        if thread_id.is_empty() {
+            continue;
+        }
+        let response = session
+            .send_request("thread/resume", json!({ "threadId": thread_id }))
+            .await?;
+        let thread = response
+            .get("result")
+            .and_then(|result| result.get("thread"))
+            .cloned()
+            .unwrap_or_else(|| summary.clone());
+        pages.push(render_thread_page(
+            &thread,
+            &entry.name,
+            &manifests,
+            &turn_annotations,
+        ));
+    }
+
+    let diff = crate::git::get_workspace_diff(&workspace_id, &state)
+        .await
+        .unwrap_or_default();
+    let now = chrono::Local::now();
+    let index = render_index(&entry, &pages, &diff, &now.format("%Y-%m-%d %H:%M").to_string());
+
+    let root = match output_dir.filter(|value| !value.trim().is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => state.data_dir.join("reports").join(format!(
+            "{}-{}",
+            sanitize_file_name(&entry.name),
+            now.format("%Y%m%d-%H%M%S")
+        )),
+    };
+    write_report(&root, &index, &pages)?;
+    Ok(root.join("index.html").to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_html_escapes_markup() {
+        assert_eq!(
+            escape_html("<a href=\"x\">Tom & 'Jerry'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn render_thread_page_collects_stats_and_escapes_items() {
+        let thread = json!({
+            "id": "thread-1",
+            "preview": "Fix <script> bug\nsecond line",
+            "turns": [{
+                "id": "turn-1",
+                "items": [
+                    { "type": "userMessage", "content": [{ "type": "text", "text": "please fix" }] },
+                    { "type": "commandExecution", "command": ["cargo", "test"], "aggregatedOutput": "ok", "exitCode": 0 },
+                    { "type": "fileChange", "changes": [{ "path": "src/lib.rs", "diff": "+fn a() {}" }] },
+                    { "type": "agentMessage", "text": "Done" },
+                    { "type": "reasoning", "summary": "hidden" }
+                ]
+            }]
+        });
+        let annotation = Annotation {
+            workspace_id: "ws".to_string(),
+            thread_id: "thread-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            item_id: None,
+            reaction: Some(Reaction::ThumbsDown),
+            comment: Some("Missed <edge> case".to_string()),
+            needs_follow_up: true,
+            updated_at: 1,
+        };
+        let annotations = HashMap::from([("turn-1".to_string(), vec![annotation])]);
+        let page = render_thread_page(&thread, "demo", &HashMap::new(), &annotations);
+        assert_eq!(page.title, "Fix <script> bug");
+        assert_eq!(
+            page.stats,
+            ReportStats {
+                threads: 1,
+                turns: 1,
+                messages: 2,
+                commands: 1,
+                file_changes: 1,
+            }
+        );
+        assert!(page.html.contains("Fix &lt;script&gt; bug"));
+        assert!(page.html.contains("$ cargo test"));
+        assert!(!page.html.contains("hidden"));
+        assert!(page
+            .html
+            .contains("Thumbs down · Needs follow-up · Missed &lt;edge&gt; case"));
+    }
+
+    #[test]
+    fn sanitize_file_name_replaces_unsafe_characters() {
+        assert_eq!(sanitize_file_name("My Project/../x"), "My-Project----x");
+        assert_eq!(sanitize_file_name("///"), "workspace");
+    }
+}
@@ -0,0 +1,200 @@
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
+
+use crate::backend::review_pair::{self, ReviewPair, ReviewPairStatus};
+use crate::backend::rpc_response::response_result;
+use crate::backend::turn_limiter::TurnLane;
+use crate::codex::{self, SendOptions};
+use crate::git;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::workspace_windows::emit_for_workspace;
+use crate::workspaces;
+
+async fn start_thread_id(
+    workspace_id: &str,
+    state: &State<'_, AppState>,
+    app: &AppHandle,
+) -> Result<String, String> {
+    let response =
+        codex::start_thread(workspace_id.to_string(), state.clone(), app.clone()).await?;
+    response_result(&response)
+        .get("thread")
+        .and_then(|thread| thread.get("id"))
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| "thread/start did not return a thread id".to_string())
+}
+
+/// Emits `review-pair-updated` to the original workspace and the worktree.
+fn emit_update(app: &AppHandle, pair: &ReviewPair) {
+    emit_for_workspace(app, "review-pair-updated", &pair.workspace_id, pair.clone());
+    emit_for_workspace(app, "review-pair-updated", &pair.worktree_id, pair.clone());
+}
+
+fn update(app: &AppHandle, pair_id: &str, apply: impl FnOnce(&mut ReviewPair)) {
+    let state = app.state::<AppState>();
+    let updated = match state.review_pairs.lock() {
+        Ok(mut pairs) => pairs.update(pair_id, apply),
+        Err(_) => None,
+    };
+    if let Some(pair) = updated {
+        emit_update(app, &pair);
+    }
+}
+
+async fn start_review(app: AppHandle, pair: ReviewPair) {
+    emit_update(&app, &pair);
+    let state = app.state::<AppState>();
+    let result = async {
+        let diff = git::get_workspace_diff(&pair.worktree_id, &state).await?;
+        if diff.trim().is_empty() {
+            return Err("The implementation turn did not change any files.".to_string());
+        }
+        let target = json!({
+            "type": "custom",
+            "instructions": review_pair::review_instructions(&pair, &diff),
+        });
+        codex::start_review(
+            pair.workspace_id.clone(),
+            pair.reviewer_thread_id.clone(),
+            target,
+            None,
+            state.clone(),
+            app.clone(),
+        )
+        .await
+    }
+    .await;
+    if let Err(err) = result {
+        update(&app, &pair.pair_id, |pair| {
+            pair.status = ReviewPairStatus::Failed;
+            pair.error = Some(err);
+        });
+    }
+}
+
+/// Starts the review once a builder thread's implementation turn completes;
+/// called for every `app-server-event`, local or forwarded from a remote
+/// daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let completed = match state.review_pairs.lock() {
+        Ok(mut pairs) => pairs.observe(workspace_id, message),
+        Err(_) => None,
+    };
+    if let Some(pair) = completed {
+        tauri::async_runtime::spawn(start_review(app.clone(), pair));
+    }
+}
+
+/// Builder + reviewer pairing: forks a worktree of the workspace on
+/// `branch`, runs `prompt` there in a new builder thread and, when that turn
+/// completes, reviews its diff in a reviewer thread of the original
+/// workspace. Progress is emitted as `review-pair-updated`.
+#[tauri::command]
+pub(crate) async fn start_review_pair(
+    workspace_id: String,
+    prompt: String,
+    branch: Option<String>,
+    model: Option<String>,
+    effort: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ReviewPair, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Review pairs are only available for local workspaces.".to_string());
+    }
+    if prompt.trim().is_empty() {
+        return Err("empty user message".to_string());
+    }
+    let pair_id = format!("pair-{}", Uuid::new_v4().simple());
+    let branch = branch
+        .map(|branch| branch.trim().to_string())
+        .filter(|branch| !branch.is_empty())
+        .unwrap_or_else(|| format!("codex/{}", &pair_id[..13]));
+    let worktree = workspaces::add_worktree(
+        workspace_id.clone(),
+        branch.clone(),
+        state.clone(),
+        app.clone(),
+    )
+    .await?;
+    let builder_thread_id = start_thread_id(&worktree.id, &state, &app).await?;
+    let reviewer_thread_id = start_thread_id(&workspace_id, &state, &app).await?;
+    let pair = ReviewPair {
+        pair_id: pair_id.clone(),
+        workspace_id,
+        worktree_id: worktree.id.clone(),
+        branch,
+        prompt: prompt.clone(),
+        builder_thread_id: builder_thread_id.clone(),
+        builder_turn_id: None,
+        reviewer_thread_id,
+        status: ReviewPairStatus::Building,
+        error: None,
+        created_at: chrono::Utc::now().timestamp_millis(),
+    };
+    state
+        .review_pairs
+        .lock()
+        .map_err(|err| err.to_string())?
+        .register(pair.clone());
+
+    let response = codex::send_message(
+        worktree.id,
+        builder_thread_id,
+        prompt,
+        SendOptions {
+            model,
+            effort,
+            lane: TurnLane::Interactive,
+            ..SendOptions::default()
+        },
+        state.clone(),
+        app.clone(),
+    )
+    .await;
+    let turn_id = match &response {
+        Ok(response) => response_result(response)
+            .get("turn")
+            .and_then(|turn| turn.get("id"))
+            .and_then(|value| value.as_str())
+            .map(str::to_string),
+        Err(_) => None,
+    };
+    let pair = state
+        .review_pairs
+        .lock()
+        .map_err(|err| err.to_string())?
+        .update(&pair_id, |pair| match &response {
+            Ok(_) => {
+                if pair.builder_turn_id.is_none() {
+                    pair.builder_turn_id = turn_id;
+                }
+            }
+            Err(err) => {
+                pair.status = ReviewPairStatus::Failed;
+                pair.error = Some(err.clone());
+            }
+        })
+        .unwrap_or(pair);
+    Ok(pair)
+}
+
+/// Review pairs started in this session for a workspace or one of its
+/// worktrees, newest first.
+#[tauri::command]
+pub(crate) async fn list_review_pairs(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ReviewPair>, String> {
+    let pairs = state
+        .review_pairs
+        .lock()
+        .map_err(|_| "review pair tracker unavailable")?;
+    Ok(pairs.list(workspace_id.as_deref()))
+}
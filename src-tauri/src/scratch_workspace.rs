@@ -0,0 +1,186 @@
+use std::path::Path;
+
+use tauri::{AppHandle, State};
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::patch_staging::{self, StagedPatch, StagedPatchSource};
+use crate::backend::scratch::{self, scratch_dir};
+use crate::git;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::storage::write_workspaces;
+use crate::types::{ScratchOrigin, WorkspaceInfo};
+use crate::workspaces;
+
+async fn run_git(dir: &Path, args: &[&str]) -> Result<(), String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Fills the scratch directory: a shallow clone of the source's committed
+/// state without its remotes, or an empty repository.
+async fn prepare_checkout(destination: &Path, source_path: Option<&str>) -> Result<(), String> {
+    std::fs::create_dir_all(destination)
+        .map_err(|e| format!("Failed to create scratch folder: {e}"))?;
+    let Some(source_path) = source_path else {
+        return run_git(destination, &["init", "-q"]).await;
+    };
+    // `--depth` is ignored for plain local paths.
+    let source_url = format!("file://{source_path}");
+    run_git(
+        destination,
+        &["clone", "-q", "--depth", "1", "--", &source_url, "."],
+    )
+    .await?;
+    // Nothing run in the scratch checkout can push to the real remote.
+    run_git(destination, &["remote", "remove", "origin"]).await
+}
+
+/// Creates a throwaway workspace for trying prompts that should not touch the
+/// real checkout: a shallow clone of `source_workspace_id`, or an empty
+/// directory. Its turns run with writes limited to the scratch directory, no
+/// network and approvals for anything untrusted, whatever access mode is
+/// asked for. Accepted changes go back through `stage_scratch_changes`.
+#[tauri::command]
+pub(crate) async fn spawn_scratch_workspace(
+    source_workspace_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Scratch workspaces need a local backend.".to_string());
+    }
+    let source = match &source_workspace_id {
+        Some(id) => Some(
+            state
+                .workspaces
+                .lock()
+                .await
+                .get(id)
+                .cloned()
+                .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?,
+        ),
+        None => None,
+    };
+    let destination = scratch_dir(&state.data_dir).join(Uuid::new_v4().to_string());
+    if let Err(error) = prepare_checkout(
+        &destination,
+        source.as_ref().map(|entry| entry.path.as_str()),
+    )
+    .await
+    {
+        let _ = tokio::fs::remove_dir_all(&destination).await;
+        return Err(error);
+    }
+    let codex_bin = source.as_ref().and_then(|entry| entry.codex_bin.clone());
+    let path = destination.to_string_lossy().to_string();
+    let mut workspace = match workspaces::add_workspace(path, codex_bin, state.clone(), app).await {
+        Ok(workspace) => workspace,
+        Err(error) => {
+            let _ = tokio::fs::remove_dir_all(&destination).await;
+            return Err(error);
+        }
+    };
+    let origin = ScratchOrigin {
+        source_workspace_id,
+        created_at: chrono::Utc::now().timestamp_millis(),
+    };
+    let name = match &source {
+        Some(entry) => format!("Scratch: {}", entry.name),
+        None => "Scratch".to_string(),
+    };
+    {
+        let mut workspaces = state.workspaces.lock().await;
+        if let Some(entry) = workspaces.get_mut(&workspace.id) {
+            entry.name = name.clone();
+            entry.settings.scratch = Some(origin.clone());
+        }
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        write_workspaces(&state.storage_path, &list)?;
+    }
+    workspace.name = name;
+    workspace.settings.scratch = Some(origin);
+    Ok(workspace)
+}
+
+/// Stages the scratch workspace's uncommitted changes as a patch on its
+/// source workspace, to be reviewed and applied hunk by hunk with
+/// `apply_staged_patch`. Binary files are not staged.
+#[tauri::command]
+pub(crate) async fn stage_scratch_changes(
+    workspace_id: String,
+    thread_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<StagedPatch, String> {
+    let origin = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?
+        .settings
+        .scratch
+        .clone()
+        .ok_or("Not a scratch workspace.")?;
+    let source_id = origin
+        .source_workspace_id
+        .ok_or("This scratch workspace has no source workspace to copy changes to.")?;
+    if !state.workspaces.lock().await.contains_key(&source_id) {
+        return Err("The source workspace was removed.".to_string());
+    }
+    let diffs = git::get_git_diffs(workspace_id, state.clone()).await?;
+    let files = scratch::staged_files(&diffs);
+    if files.is_empty() {
+        return Err("The scratch workspace has no changes to copy.".to_string());
+    }
+    let patch = StagedPatch {
+        id: Uuid::new_v4().to_string(),
+        workspace_id: source_id,
+        thread_id: thread_id.unwrap_or_default(),
+        turn_id: None,
+        source: StagedPatchSource::Scratch,
+        created_at: chrono::Utc::now().timestamp_millis(),
+        files,
+    };
+    let cipher = state.data_cipher().await?;
+    let _guard = state.staged_patch_writes.lock().await;
+    patch_staging::stage_patch(&state.data_dir, &cipher, patch.clone())?;
+    Ok(patch)
+}
+
+/// Removes a scratch workspace and deletes its directory.
+#[tauri::command]
+pub(crate) async fn discard_scratch_workspace(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let path = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?;
+        if entry.settings.scratch.is_none() {
+            return Err("Not a scratch workspace.".to_string());
+        }
+        std::path::PathBuf::from(&entry.path)
+    };
+    workspaces::remove_workspace(workspace_id, state.clone()).await?;
+    // Only directories `spawn_scratch_workspace` created are deleted.
+    if path.starts_with(scratch_dir(&state.data_dir)) {
+        tokio::fs::remove_dir_all(&path)
+            .await
+            .map_err(|e| format!("Failed to remove scratch folder: {e}"))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::backend::protocol_harness::{
+    run_fake_server, run_scenarios, FakeStep, ScenarioReport, FAKE_SERVER_FLAG, SELFTEST_SCENARIOS,
+};
+
+/// Result of `run_selftest`, meant to be pasted into bug reports.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SelftestReport {
+    pub(crate) app_version: String,
+    pub(crate) os: String,
+    pub(crate) passed: bool,
+    pub(crate) scenarios: Vec<ScenarioReport>,
+}
+
+/// Serves the protocol harness's scripted app-server when the process was
+/// launched with [`FAKE_SERVER_FLAG`]; returns `false` for a normal launch.
+pub fn serve_fake_app_server() -> bool {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() != Some(FAKE_SERVER_FLAG) {
+        return false;
+    }
+    let script: Vec<FakeStep> = match serde_json::from_str(&args.next().unwrap_or_default()) {
+        Ok(script) => script,
+        Err(err) => {
+            eprintln!("Invalid fake app-server script: {err}");
+            std::process::exit(2);
+        }
+    };
+    let stdin = std::io::stdin();
+    if let Err(err) = run_fake_server(&script, stdin.lock(), std::io::stdout()) {
+        eprintln!("Fake app-server stopped: {err}");
+        std::process::exit(1);
+    }
+    true
+}
+
+/// Runs every protocol harness scenario against `server`, a binary that
+/// calls [`serve_fake_app_server`] first thing, and lists the failures.
+#[cfg(feature = "protocol-harness")]
+pub fn run_protocol_harness(server: &std::path::Path) -> Result<(), String> {
+    use crate::backend::protocol_harness::ALL_SCENARIOS;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| err.to_string())?;
+    let failures: Vec<String> = runtime
+        .block_on(run_scenarios(server, &ALL_SCENARIOS))
+        .into_iter()
+        .filter(|report| !report.passed)
+        .map(|report| format!("{}: {}", report.name, report.detail.unwrap_or_default()))
+        .collect();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+/// Drives a session against this binary acting as a scripted app-server,
+/// covering the protocol edge cases that are quick to check.
+#[tauri::command]
+pub(crate) async fn run_selftest(app: AppHandle) -> Result<SelftestReport, String> {
+    let server = std::env::current_exe().map_err(|err| err.to_string())?;
+    let scenarios = run_scenarios(&server, &SELFTEST_SCENARIOS).await;
+    Ok(SelftestReport {
+        app_version: app.package_info().version.to_string(),
+        os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        passed: scenarios.iter().all(|report| report.passed),
+        scenarios,
+    })
+}
@@ -0,0 +1,165 @@
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::semantic_index::{
+    self, local_embedding, IndexedPassage, SemanticMatch, DEFAULT_RESULT_LIMIT, LOCAL_MODEL,
+};
+use crate::state::AppState;
+use crate::types::{EmbeddingProvider, SemanticSearchSettings};
+
+/// Model id stored alongside vectors, so switching providers never mixes
+/// incomparable vectors.
+fn model_id(settings: &SemanticSearchSettings) -> String {
+    match settings.provider {
+        EmbeddingProvider::Local => LOCAL_MODEL.to_string(),
+        EmbeddingProvider::Api => format!("api:{}", settings.api_model),
+    }
+}
+
+async fn api_embeddings(
+    settings: &SemanticSearchSettings,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, String> {
+    let key = std::env::var(&settings.api_key_env)
+        .ok()
+        .filter(|key| !key.trim().is_empty())
+        .ok_or_else(|| format!("Set {} to use the embeddings API.", settings.api_key_env))?;
+    let response = reqwest::Client::new()
+        .post(&settings.api_url)
+        .bearer_auth(key.trim())
+        .header("Content-Type", "application/json")
+        .body(json!({ "model": settings.api_model, "input": texts }).to_string())
+        .send()
+        .await
+        .map_err(|err| err.to_string())?;
+    let status = response.status();
+    let body = response.text().await.map_err(|err| err.to_string())?;
+    let body: Value = serde_json::from_str(&body).unwrap_or(Value::Null);
+    if !status.is_success() {
+        let message = body
+            .get("error")
+            .and_then(|error| error.get("message"))
+            .and_then(|value| value.as_str())
+            .unwrap_or("request failed");
+        return Err(format!("Embeddings API error ({status}): {message}"));
+    }
+    let data = body
+        .get("data")
+        .and_then(|value| value.as_array())
+        .ok_or("Embeddings API response has no data")?;
+    let mut vectors = vec![Vec::new(); texts.len()];
+    for (position, entry) in data.iter().enumerate() {
+        let index = entry
+            .get("index")
+            .and_then(|value| value.as_u64())
+            .map(|value| value as usize)
+            .unwrap_or(position);
+        let vector: Vec<f32> = entry
+            .get("embedding")
+            .and_then(|value| value.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|value| value.as_f64())
+                    .map(|value| value as f32)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(slot) = vectors.get_mut(index) {
+            *slot = vector;
+        }
+    }
+    if vectors.iter().any(|vector| vector.is_empty()) {
+        return Err("Embeddings API returned fewer vectors than inputs".to_string());
+    }
+    Ok(vectors)
+}
+
+async fn embed(
+    settings: &SemanticSearchSettings,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, String> {
+    match settings.provider {
+        EmbeddingProvider::Local => Ok(texts.iter().map(|text| local_embedding(text)).collect()),
+        EmbeddingProvider::Api => api_embeddings(settings, texts).await,
+    }
+}
+
+/// Indexes completed messages and command outputs when semantic search is
+/// enabled; called for every `app-server-event`, local or forwarded from a
+/// remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let passages = semantic_index::passages_from_event(
+        workspace_id,
+        message,
+        chrono::Utc::now().timestamp_millis(),
+    );
+    if passages.is_empty() {
+        return;
+    }
+    let app = app.clone();
+    let workspace_id = workspace_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let settings = state.app_settings.lock().await.semantic_search.clone();
+        if !settings.enabled {
+            return;
+        }
+        let texts: Vec<String> = passages
+            .iter()
+            .map(|passage| passage.text.clone())
+            .collect();
+        let vectors = match embed(&settings, &texts).await {
+            Ok(vectors) => vectors,
+            Err(err) => {
+                eprintln!("Failed to embed passages: {err}");
+                return;
+            }
+        };
+        let model = model_id(&settings);
+        let indexed: Vec<IndexedPassage> = passages
+            .into_iter()
+            .zip(vectors)
+            .map(|(passage, vector)| IndexedPassage::new(passage, &model, vector))
+            .collect();
+        let _guard = state.semantic_index_writes.lock().await;
+        let result = match state.data_cipher().await {
+            Ok(cipher) => {
+                semantic_index::record_passages(&state.data_dir, &cipher, &workspace_id, indexed)
+            }
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to update semantic index: {err}");
+        }
+    });
+}
+
+/// Ranks indexed message and command output passages by meaning rather than
+/// keywords. Only history recorded while semantic search is enabled is
+/// indexed.
+#[tauri::command]
+pub(crate) async fn semantic_search(
+    query: String,
+    workspace_id: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SemanticMatch>, String> {
+    let settings = state.app_settings.lock().await.semantic_search.clone();
+    if !settings.enabled {
+        return Err("Semantic search is disabled in settings.".to_string());
+    }
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let query_vector = embed(&settings, &[query]).await?.pop().unwrap_or_default();
+    let cipher = state.data_cipher().await?;
+    let passages =
+        semantic_index::read_passages(&state.data_dir, &cipher, workspace_id.as_deref())?;
+    Ok(semantic_index::search_passages(
+        passages,
+        &query_vector,
+        &model_id(&settings),
+        limit.unwrap_or(DEFAULT_RESULT_LIMIT),
+    ))
+}
@@ -0,0 +1,33 @@
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::session_env::SessionEnvironment;
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// Reports the command line, cwd, `PATH` and environment the workspace's
+/// app-server was launched with, secrets masked, so "works in my terminal"
+/// differences can be compared directly.
+#[tauri::command]
+pub(crate) async fn get_session_environment(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SessionEnvironment, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_session_environment",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+    let sessions = state.sessions.lock().await;
+    sessions
+        .get(&workspace_id)
+        .map(|session| session.environment.clone())
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))
+}
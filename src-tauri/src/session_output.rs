@@ -0,0 +1,61 @@
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::session_output::SessionOutputLine;
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// The last `lines` raw stdout and stderr lines of the workspace's
+/// app-server, as read before JSON parsing or stderr filtering. Shows
+/// diagnostics that otherwise only surface as `codex/parseError`.
+#[tauri::command]
+pub(crate) async fn tail_session_output(
+    workspace_id: String,
+    lines: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<SessionOutputLine>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "tail_session_output",
+            json!({ "workspaceId": workspace_id, "lines": lines }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+    let sessions = state.sessions.lock().await;
+    sessions
+        .get(&workspace_id)
+        .map(|session| session.tail_output(lines))
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))
+}
+
+/// Streams each new raw output line of the workspace's app-server as a
+/// `session-output` event while `enabled`.
+#[tauri::command]
+pub(crate) async fn set_session_output_live(
+    workspace_id: String,
+    enabled: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "set_session_output_live",
+            json!({ "workspaceId": workspace_id, "enabled": enabled }),
+        )
+        .await?;
+        return Ok(());
+    }
+    let sessions = state.sessions.lock().await;
+    let session = sessions
+        .get(&workspace_id)
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
+    session.set_output_live(enabled);
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::codex;
+use crate::codex_home::{resolve_default_codex_home, resolve_workspace_codex_home};
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::workspace_doctor::project_configs;
+use crate::workspaces;
+
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Modification time of every config file a workspace's app-server reads.
+type ConfigSnapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Payload of `codex-config-changed`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigChange {
+    pub(crate) workspace_id: String,
+    pub(crate) paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SessionReload {
+    pub(crate) workspace_id: String,
+    pub(crate) resumed_thread_id: Option<String>,
+}
+
+fn config_snapshot(
+    workspace_path: &Path,
+    workspace_home: Option<&Path>,
+    default_home: Option<&Path>,
+) -> ConfigSnapshot {
+    let mut files = project_configs(workspace_path, default_home);
+    if let Some(home) = workspace_home.or(default_home) {
+        files.push(home.join("config.toml"));
+    }
+    files
+        .into_iter()
+        .map(|path| {
+            let modified = std::fs::metadata(&path)
+                .and_then(|meta| meta.modified())
+                .ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Files added, removed or modified between two snapshots.
+fn changed_paths(previous: &ConfigSnapshot, current: &ConfigSnapshot) -> Vec<String> {
+    previous
+        .keys()
+        .chain(current.keys())
+        .filter(|path| previous.get(*path) != current.get(*path))
+        .map(|path| path.display().to_string())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+async fn connected_snapshots(state: &AppState) -> Vec<(String, ConfigSnapshot)> {
+    let connected: Vec<String> = state.sessions.lock().await.keys().cloned().collect();
+    let workspaces = state.workspaces.lock().await;
+    let default_home = resolve_default_codex_home();
+    connected
+        .into_iter()
+        .filter_map(|workspace_id| {
+            let entry = workspaces.get(&workspace_id)?;
+            let parent_path = entry
+                .parent_id
+                .as_ref()
+                .and_then(|parent_id| workspaces.get(parent_id))
+                .map(|parent| parent.path.clone());
+            let workspace_home = resolve_workspace_codex_home(entry, parent_path.as_deref());
+            let snapshot = config_snapshot(
+                Path::new(&entry.path),
+                workspace_home.as_deref(),
+                default_home.as_deref(),
+            );
+            Some((workspace_id, snapshot))
+        })
+        .collect()
+}
+
+/// Emits `codex-config-changed` when a config file read by a connected
+/// workspace's app-server changes, whether from the in-app editor or
+/// outside the app.
+pub(crate) fn spawn_config_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut snapshots: HashMap<String, ConfigSnapshot> = HashMap::new();
+        loop {
+            tokio::time::sleep(CONFIG_POLL_INTERVAL).await;
+            let state = app.state::<AppState>();
+            let current = connected_snapshots(&state).await;
+            let mut next = HashMap::new();
+            for (workspace_id, snapshot) in current {
+                if let Some(previous) = snapshots.get(&workspace_id) {
+                    let paths = changed_paths(previous, &snapshot);
+                    if !paths.is_empty() {
+                        let change = ConfigChange {
+                            workspace_id: workspace_id.clone(),
+                            paths,
+                        };
+                        let _ = app.emit("codex-config-changed", change);
+                    }
+                }
+                next.insert(workspace_id, snapshot);
+            }
+            snapshots = next;
+        }
+    });
+}
+
+/// Restarts a workspace's app-server so it picks up config changes, then
+/// resumes `thread_id`. Refuses while a turn is running unless `force`.
+#[tauri::command]
+pub(crate) async fn reload_session_config(
+    workspace_id: String,
+    thread_id: Option<String>,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<SessionReload, String> {
+    let running: Vec<String> = state
+        .turn_limiter
+        .status()
+        .running
+        .into_iter()
+        .filter(|slot| slot.workspace_id == workspace_id)
+        .map(|slot| slot.thread_id)
+        .collect();
+    if !running.is_empty() && !force.unwrap_or(false) {
+        return Err(format!(
+            "A turn is still running in {}; wait for it or reload with force.",
+            running.join(", ")
+        ));
+    }
+
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app.clone(),
+            "reload_session",
+            json!({ "id": workspace_id }),
+        )
+        .await?;
+    } else {
+        if let Some(session) = state.sessions.lock().await.remove(&workspace_id) {
+            let mut child = session.child.lock().await;
+            let _ = child.kill().await;
+        }
+        workspaces::connect_workspace(workspace_id.clone(), state.clone(), app.clone()).await?;
+    }
+
+    if let Some(thread_id) = &thread_id {
+        codex::resume_thread(workspace_id.clone(), thread_id.clone(), state, app).await?;
+    }
+    Ok(SessionReload {
+        workspace_id,
+        resumed_thread_id: thread_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_added_removed_and_modified_configs() {
+        let at = |secs: u64| Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        let previous: ConfigSnapshot = [
+            (PathBuf::from("/home/.codex/config.toml"), at(1)),
+            (PathBuf::from("/work/.codex/config.toml"), at(1)),
+        ]
+        .into_iter()
+        .collect();
+        assert!(changed_paths(&previous, &previous.clone()).is_empty());
+
+        let current: ConfigSnapshot = [
+            (PathBuf::from("/home/.codex/config.toml"), at(2)),
+            (PathBuf::from("/work/app/.codex/config.toml"), at(1)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            changed_paths(&previous, &current),
+            vec![
+                "/home/.codex/config.toml",
+                "/work/.codex/config.toml",
+                "/work/app/.codex/config.toml",
+            ]
+        );
+    }
+}
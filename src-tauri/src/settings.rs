@@ -1,9 +1,17 @@
-use tauri::{State, Window};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tauri::{Manager, State, Window};
 
+use crate::approval_delegation;
+use crate::backend::command_guard;
+use crate::backend::i18n::{self, MessageCode};
+use crate::backend::network;
+use crate::backend::secure_storage;
 use crate::codex_config;
 use crate::state::AppState;
 use crate::storage::write_settings;
-use crate::types::AppSettings;
+use crate::types::{AppSettings, ApprovalDelegationSettings};
+use crate::webhook_intake;
 use crate::window;
 
 #[tauri::command]
@@ -25,18 +33,129 @@ pub(crate) async fn get_app_settings(
     Ok(settings)
 }
 
+/// Keeps approval sign-off from being switched off by whoever needs it:
+/// the admin policy can pin it, and otherwise changing delegation settings
+/// while sign-off is required needs the app passphrase.
+async fn check_delegation_change(
+    state: &AppState,
+    current: &ApprovalDelegationSettings,
+    next: &ApprovalDelegationSettings,
+    passphrase: Option<&str>,
+) -> Result<(), String> {
+    state.policy.policy.check_approval_delegation(next)?;
+    let access = state.access_control.lock().await;
+    if approval_delegation::loosens_signoff(current, next) {
+        return access.check_passphrase(passphrase).map_err(|_| {
+            "Changing approval delegation while sign-off is required needs the app passphrase."
+                .to_string()
+        });
+    }
+    let pinned = state.policy.policy.require_approval_signoff;
+    let turns_on_signoff = next.enabled && next.require_signoff && !current.require_signoff;
+    if turns_on_signoff && !pinned && !access.passphrase_set() {
+        return Err(
+            "Set an app passphrase before requiring sign-off for delegated approvals.".to_string(),
+        );
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub(crate) async fn update_app_settings(
     settings: AppSettings,
+    passphrase: Option<String>,
     state: State<'_, AppState>,
     window: Window,
 ) -> Result<AppSettings, String> {
+    let mut current = state.app_settings.lock().await;
+    check_delegation_change(
+        &state,
+        &current.approval_delegation,
+        &settings.approval_delegation,
+        passphrase.as_deref(),
+    )
+    .await?;
     let _ = codex_config::write_collab_enabled(settings.experimental_collab_enabled);
     let _ = codex_config::write_steer_enabled(settings.experimental_steer_enabled);
     let _ = codex_config::write_unified_exec_enabled(settings.experimental_unified_exec_enabled);
+    secure_storage::apply_encryption_setting(
+        &state.data_dir,
+        &state.ciphers,
+        current.encrypt_local_data,
+        settings.encrypt_local_data,
+    )?;
     write_settings(&state.settings_path, &settings)?;
-    let mut current = state.app_settings.lock().await;
+    i18n::set_locale(settings.locale.as_deref());
+    command_guard::set_blocklist(&settings.command_blocklist);
+    network::set_network_settings(&settings.network);
+    let webhook_changed = current.webhook_intake != settings.webhook_intake
+        || current.approval_delegation.inbox_token != settings.approval_delegation.inbox_token;
+    if current.auto_lock_minutes != settings.auto_lock_minutes {
+        if let Ok(mut lock) = state.app_lock.lock() {
+            lock.set_timeout(settings.auto_lock_minutes);
+        }
+    }
+    if current.max_concurrent_turns != settings.max_concurrent_turns {
+        state.turn_limiter.set_limit(settings.max_concurrent_turns);
+    }
+    if current.max_workspace_turns != settings.max_workspace_turns
+        || current.turn_overlap_mode != settings.turn_overlap_mode
+    {
+        state
+            .workspace_turns
+            .configure(settings.max_workspace_turns, settings.turn_overlap_mode);
+    }
+    if let Ok(mut visibility) = state.reasoning_visibility.lock() {
+        visibility.set_defaults(
+            settings.show_reasoning_summaries,
+            &settings.reasoning_summary_models,
+        );
+    }
     *current = settings.clone();
+    drop(current);
+    if webhook_changed {
+        webhook_intake::restart_listener(window.app_handle());
+    }
     let _ = window::apply_window_appearance(&window, settings.theme.as_str());
     Ok(settings)
 }
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MessageCatalog {
+    pub(crate) locale: &'static str,
+    pub(crate) messages: BTreeMap<MessageCode, &'static str>,
+}
+
+fn message_catalog(locale: &'static str) -> MessageCatalog {
+    MessageCatalog {
+        locale,
+        messages: i18n::catalog(locale),
+    }
+}
+
+/// Persists the locale used for backend messages and returns its catalog.
+#[tauri::command]
+pub(crate) async fn set_locale(
+    locale: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<MessageCatalog, String> {
+    let mut current = state.app_settings.lock().await;
+    let mut settings = current.clone();
+    settings.locale = locale.filter(|value| !value.trim().is_empty());
+    write_settings(&state.settings_path, &settings)?;
+    let resolved = i18n::set_locale(settings.locale.as_deref());
+    *current = settings;
+    Ok(message_catalog(resolved))
+}
+
+#[tauri::command]
+pub(crate) async fn get_message_catalog(
+    locale: Option<String>,
+) -> Result<MessageCatalog, String> {
+    let resolved = locale
+        .as_deref()
+        .and_then(i18n::normalize_locale)
+        .unwrap_or_else(i18n::current_locale);
+    Ok(message_catalog(resolved))
+}
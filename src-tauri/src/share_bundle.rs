@@ -0,0 +1,466 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, State};
+use uuid::Uuid;
+
+use crate::annotations;
+use crate::backend::annotations::Annotation;
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::turn_manifest::TurnManifest;
+use crate::codex;
+use crate::state::AppState;
+use crate::turn_manifest;
+
+const BUNDLE_FORMAT: &str = "codexmonitor-share-v1";
+const BUNDLE_EXTENSION: &str = "cmshare";
+const KDF_NAME: &str = "pbkdf2-sha256";
+const KDF_ITERATIONS: u32 = 210_000;
+/// Refuse bundles asking for absurd work factors.
+const KDF_MAX_ITERATIONS: u32 = 5_000_000;
+const MIN_PASSPHRASE_CHARS: usize = 8;
+const REDACTED: &str = "[REDACTED]";
+const SECRET_PREFIXES: &[&str] = &[
+    "sk-",
+    "ghp_",
+    "gho_",
+    "ghs_",
+    "github_pat_",
+    "glpat-",
+    "xoxb-",
+    "xoxp-",
+    "AKIA",
+];
+const SECRET_KEYS: &[&str] = &["token", "secret", "password", "passwd", "api_key", "apikey"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ShareEntry {
+    pub(crate) turn_id: Option<String>,
+    /// `user`, `assistant`, `reasoning`, `command` or `fileChange`.
+    pub(crate) kind: String,
+    pub(crate) text: String,
+}
+
+/// Decrypted contents of a share bundle. Bundles are read-only; nothing in
+/// them can be resumed or replayed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ShareBundle {
+    pub(crate) format: String,
+    pub(crate) created_at: i64,
+    pub(crate) workspace_name: String,
+    pub(crate) thread_id: String,
+    pub(crate) title: Option<String>,
+    pub(crate) entries: Vec<ShareEntry>,
+    /// Reproducibility manifests of the thread's turns started on the
+    /// sender's machine.
+    #[serde(default)]
+    pub(crate) manifests: Vec<TurnManifest>,
+    /// The sender's reactions, comments and follow-up flags on its turns.
+    #[serde(default)]
+    pub(crate) annotations: Vec<Annotation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EncryptedBundle {
+    format: String,
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ShareBundleInfo {
+    pub(crate) path: String,
+    pub(crate) entries: usize,
+}
+
+fn is_secret_token(token: &str) -> bool {
+    let token = token.trim_matches(|ch: char| matches!(ch, '"' | '\'' | '`' | ',' | ';'));
+    token.len() >= 16
+        && SECRET_PREFIXES
+            .iter()
+            .any(|prefix| token.starts_with(prefix))
+}
+
+fn redact_word(word: &str, home: Option<&str>, workspace_path: &str) -> String {
+    if is_secret_token(word) {
+        return REDACTED.to_string();
+    }
+    if let Some((key, value)) = word.split_once(['=', ':']) {
+        let lowered = key.to_lowercase();
+        if !value.is_empty() && SECRET_KEYS.iter().any(|secret| lowered.contains(secret)) {
+            let separator = &word[key.len()..key.len() + 1];
+            return format!("{key}{separator}{REDACTED}");
+        }
+    }
+    let mut out = word.to_string();
+    if !workspace_path.is_empty() {
+        out = out.replace(workspace_path, "<workspace>");
+    }
+    if let Some(home) = home.filter(|home| home.len() > 1) {
+        out = out.replace(home, "~");
+    }
+    out
+}
+
+/// Masks API keys, `secret=value` pairs and local paths that would leak the
+/// user's machine layout.
+pub(crate) fn redact(text: &str, home: Option<&str>, workspace_path: &str) -> String {
+    text.split_inclusive(char::is_whitespace)
+        .map(|segment| {
+            let word = segment.trim_end_matches(char::is_whitespace);
+            let whitespace = &segment[word.len()..];
+            format!("{}{whitespace}", redact_word(word, home, workspace_path))
+        })
+        .collect()
+}
+
+fn user_message_text(item: &Value) -> Option<String> {
+    let parts: Vec<&str> = item
+        .get("content")?
+        .as_array()?
+        .iter()
+        .filter(|part| part.get("type").and_then(|value| value.as_str()) == Some("text"))
+        .filter_map(|part| part.get("text").and_then(|value| value.as_str()))
+        .collect();
+    Some(parts.join("\n"))
+}
+
+fn command_string(item: &Value) -> String {
+    match item.get("command") {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| part.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        Some(Value::String(command)) => command.clone(),
+        _ => String::new(),
+    }
+}
+
+fn item_entry(item: &Value) -> Option<(&'static str, String)> {
+    let text_field = |key: &str| {
+        item.get(key)
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string())
+    };
+    match item.get("type").and_then(|value| value.as_str())? {
+        "userMessage" => Some(("user", user_message_text(item)?)),
+        "agentMessage" => Some(("assistant", text_field("text")?)),
+        "reasoning" => {
+            let summary = item.get("summary").and_then(|value| value.as_array())?;
+            let text = summary
+                .iter()
+                .filter_map(|part| part.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            Some(("reasoning", text))
+        }
+        "commandExecution" => {
+            let mut text = format!("$ {}", command_string(item));
+            if let Some(output) = text_field("aggregatedOutput") {
+                text.push('\n');
+                text.push_str(output.trim_end());
+            }
+            Some(("command", text))
+        }
+        "fileChange" => {
+            let changes = item.get("changes").and_then(|value| value.as_array())?;
+            let text = changes
+                .iter()
+                .map(|change| {
+                    let path = change
+                        .get("path")
+                        .and_then(|value| value.as_str())
+                        .unwrap_or_default();
+                    let diff = change
+                        .get("diff")
+                        .and_then(|value| value.as_str())
+                        .unwrap_or_default();
+                    format!("--- {path}\n{diff}")
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            Some(("fileChange", text))
+        }
+        _ => None,
+    }
+}
+
+/// Builds a redacted transcript from a `thread/resume` response.
+pub(crate) fn build_bundle(
+    response: &Value,
+    workspace_name: &str,
+    workspace_path: &str,
+    home: Option<&str>,
+    created_at: i64,
+) -> Result<ShareBundle, String> {
+    let result = response.get("result").unwrap_or(response);
+    let thread = result.get("thread").ok_or("Thread not found.")?;
+    let thread_id = thread
+        .get("id")
+        .and_then(|value| value.as_str())
+        .ok_or("Thread not found.")?
+        .to_string();
+    let mut entries = Vec::new();
+    let turns = thread.get("turns").and_then(|value| value.as_array());
+    for turn in turns.into_iter().flatten() {
+        let turn_id = turn
+            .get("id")
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string());
+        let items = turn.get("items").and_then(|value| value.as_array());
+        for (kind, text) in items.into_iter().flatten().filter_map(item_entry) {
+            if text.trim().is_empty() {
+                continue;
+            }
+            entries.push(ShareEntry {
+                turn_id: turn_id.clone(),
+                kind: kind.to_string(),
+                text: redact(&text, home, workspace_path),
+            });
+        }
+    }
+    Ok(ShareBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        created_at,
+        workspace_name: workspace_name.to_string(),
+        thread_id,
+        title: thread
+            .get("preview")
+            .and_then(|value| value.as_str())
+            .map(|value| redact(value.trim(), home, workspace_path))
+            .filter(|value| !value.is_empty()),
+        entries,
+        manifests: Vec::new(),
+        annotations: Vec::new(),
+    })
+}
+
+fn redact_value(value: &mut Value, home: Option<&str>, workspace_path: &str) {
+    match value {
+        Value::String(text) => *text = redact(text, home, workspace_path),
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| redact_value(value, home, workspace_path)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|value| redact_value(value, home, workspace_path)),
+        _ => {}
+    }
+}
+
+/// Masks local paths in a manifest's settings before it leaves the machine.
+fn redact_manifest(
+    mut manifest: TurnManifest,
+    home: Option<&str>,
+    workspace_path: &str,
+) -> TurnManifest {
+    for value in manifest.config_overrides.values_mut() {
+        redact_value(value, home, workspace_path);
+    }
+    redact_value(&mut manifest.sandbox_policy, home, workspace_path);
+    manifest
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Key {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    *Key::from_slice(&key)
+}
+
+fn seal(bundle: &ShareBundle, passphrase: &str, iterations: u32) -> Result<String, String> {
+    let salt = Uuid::new_v4().into_bytes();
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt, iterations));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let plaintext = serde_json::to_vec(bundle).map_err(|err| err.to_string())?;
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|_| "Failed to encrypt share bundle.".to_string())?;
+    let encrypted = EncryptedBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        kdf: KDF_NAME.to_string(),
+        iterations,
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+    };
+    serde_json::to_string_pretty(&encrypted).map_err(|err| err.to_string())
+}
+
+fn unseal(contents: &str, passphrase: &str) -> Result<ShareBundle, String> {
+    let encrypted: EncryptedBundle = serde_json::from_str(contents)
+        .map_err(|_| "Not a CodexMonitor share bundle.".to_string())?;
+    if encrypted.format != BUNDLE_FORMAT || encrypted.kdf != KDF_NAME {
+        return Err("Unsupported share bundle format.".to_string());
+    }
+    if encrypted.iterations == 0 || encrypted.iterations > KDF_MAX_ITERATIONS {
+        return Err("Unsupported share bundle format.".to_string());
+    }
+    let decode = |value: &str| {
+        STANDARD
+            .decode(value)
+            .map_err(|_| "Share bundle is corrupted.".to_string())
+    };
+    let salt = decode(&encrypted.salt)?;
+    let nonce = decode(&encrypted.nonce)?;
+    if nonce.len() != 12 {
+        return Err("Share bundle is corrupted.".to_string());
+    }
+    let ciphertext = decode(&encrypted.ciphertext)?;
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt, encrypted.iterations));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "Incorrect passphrase or corrupted share bundle.".to_string())?;
+    serde_json::from_slice(&plaintext).map_err(|err| err.to_string())
+}
+
+fn default_bundle_path(data_dir: &Path, thread_id: &str, created_at: i64) -> PathBuf {
+    let safe: String = thread_id
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '-')
+        .collect();
+    data_dir
+        .join("shares")
+        .join(format!("{safe}-{created_at}.{BUNDLE_EXTENSION}"))
+}
+
+/// Packages a redacted transcript of a thread into a passphrase-encrypted
+/// bundle file that can be opened with `open_share_bundle`.
+#[tauri::command]
+pub(crate) async fn create_share_bundle(
+    workspace_id: String,
+    thread_id: String,
+    passphrase: String,
+    output_path: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ShareBundleInfo, String> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+        return Err(format!(
+            "Share passphrase must be at least {MIN_PASSPHRASE_CHARS} characters."
+        ));
+    }
+    let (workspace_name, workspace_path) = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?;
+        (entry.name.clone(), entry.path.clone())
+    };
+    let response =
+        codex::resume_thread(workspace_id.clone(), thread_id.clone(), state.clone(), app).await?;
+    if let Some(error) = response.get("error") {
+        return Err(error
+            .get("message")
+            .and_then(|value| value.as_str())
+            .unwrap_or("Failed to load thread")
+            .to_string());
+    }
+    let home = std::env::var("HOME").ok();
+    let created_at = chrono::Utc::now().timestamp_millis();
+    let mut bundle = build_bundle(
+        &response,
+        &workspace_name,
+        &workspace_path,
+        home.as_deref(),
+        created_at,
+    )?;
+    bundle.manifests = turn_manifest::workspace_manifests(&state, &workspace_id)
+        .await
+        .into_iter()
+        .filter(|manifest| manifest.thread_id == bundle.thread_id)
+        .map(|manifest| redact_manifest(manifest, home.as_deref(), &workspace_path))
+        .collect();
+    bundle.annotations =
+        annotations::thread_annotations(&state, &workspace_id, Some(&bundle.thread_id))
+            .await
+            .into_iter()
+            .map(|mut annotation| {
+                annotation.comment = annotation
+                    .comment
+                    .map(|comment| redact(&comment, home.as_deref(), &workspace_path));
+                annotation
+            })
+            .collect();
+    let path = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_bundle_path(&state.data_dir, &thread_id, created_at));
+    let contents = tokio::task::spawn_blocking({
+        let bundle = bundle.clone();
+        move || seal(&bundle, &passphrase, KDF_ITERATIONS)
+    })
+    .await
+    .map_err(|err| err.to_string())??;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    std::fs::write(&path, contents).map_err(|err| err.to_string())?;
+    Ok(ShareBundleInfo {
+        path: path.to_string_lossy().to_string(),
+        entries: bundle.entries.len(),
+    })
+}
+
+/// Decrypts a share bundle for read-only viewing.
+#[tauri::command]
+pub(crate) async fn open_share_bundle(
+    path: String,
+    passphrase: String,
+) -> Result<ShareBundle, String> {
+    let contents = std::fs::read_to_string(&path).map_err(|err| err.to_string())?;
+    tokio::task::spawn_blocking(move || unseal(&contents, &passphrase))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn redact_masks_secrets_and_local_paths() {
+        let text = "export OPENAI_API_KEY=abc123\nuse sk-proj-aaaaaaaaaaaaaaaa in /home/me/repo/src and /home/me/.ssh";
+        let redacted = redact(text, Some("/home/me"), "/home/me/repo");
+        assert_eq!(
+            redacted,
+            "export OPENAI_API_KEY=[REDACTED]\nuse [REDACTED] in <workspace>/src and ~/.ssh"
+        );
+    }
+
+    #[test]
+    fn bundles_round_trip_with_passphrase() {
+        let response = json!({ "result": { "thread": {
+            "id": "thread-1",
+            "preview": "Fix login",
+            "turns": [{ "id": "turn-1", "items": [
+                { "type": "userMessage", "content": [{ "type": "text", "text": "Fix login in /work/app" }] },
+                { "type": "commandExecution", "command": ["cargo", "test"], "aggregatedOutput": "ok\n" },
+                { "type": "agentMessage", "text": "Done." }
+            ] }]
+        } } });
+        let bundle = build_bundle(&response, "app", "/work/app", None, 42).expect("bundle");
+        let kinds: Vec<_> = bundle.entries.iter().map(|entry| entry.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["user", "command", "assistant"]);
+        assert_eq!(bundle.entries[0].text, "Fix login in <workspace>");
+        assert_eq!(bundle.entries[1].text, "$ cargo test\nok");
+
+        let sealed = seal(&bundle, "correct horse", 1_000).expect("seal");
+        assert!(!sealed.contains("Fix login"));
+        assert_eq!(unseal(&sealed, "correct horse").expect("unseal"), bundle);
+        assert!(unseal(&sealed, "wrong horse").is_err());
+    }
+}
@@ -1,15 +1,52 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 
+use tauri::async_runtime::JoinHandle;
 use tauri::{AppHandle, Manager};
 use tokio::sync::Mutex;
 
+use crate::access_control::{access_control_path, AccessControl};
+use crate::app_lock::AppLock;
+use crate::approval_delegation::PendingDelegations;
+use crate::backend::ab_test::AbTestTracker;
+use crate::backend::approval_inbox::ApprovalInbox;
+use crate::backend::atomic_write;
+use crate::backend::auth_expiry::AuthExpiryTracker;
+use crate::backend::best_of::BestOfTracker;
+use crate::backend::blame_context::BlameCache;
+use crate::backend::clock::Clock;
+use crate::backend::command_guard;
+use crate::backend::command_output::CommandOutputCollector;
+use crate::backend::dry_run::DryRunTracker;
+use crate::backend::event_replay::EventBuffer;
+use crate::backend::i18n;
+use crate::backend::network;
+use crate::backend::plan_tracker::PlanTracker;
+use crate::backend::prompt_budget::ModelCatalog;
+use crate::backend::prompt_insights::PromptOutcomeTracker;
+use crate::backend::secure_storage::{CipherCache, DataCipher};
+use crate::backend::rate_limits::RateLimitTracker;
+use crate::backend::reasoning_visibility::ReasoningVisibility;
+use crate::backend::review_pair::ReviewPairTracker;
+use crate::backend::thread_guard::PendingApprovalTracker;
+use crate::backend::thread_index::ThreadIndex;
+use crate::backend::thread_sla::ThreadStopwatch;
+use crate::backend::turn_limiter::TurnLimiter;
+use crate::backend::verification::VerificationTracker;
+use crate::backend::workspace_turns::WorkspaceTurnGate;
 use crate::dictation::DictationState;
+use crate::event_subscriptions::{EventSubscriptions, ThreadEventFilters};
+use crate::focus_mode::FocusState;
+use crate::policy::{load_policy, policy_path, EffectivePolicy};
+use crate::privacy::PurgeTokenState;
 use crate::storage::{read_settings, read_workspaces};
-use crate::types::{AppSettings, WorkspaceEntry};
+use crate::types::{AppSettings, WorkspaceEntry, WorkspaceIdentity};
+use crate::workspace_windows::WindowScopes;
 
 pub(crate) struct AppState {
+    pub(crate) data_dir: PathBuf,
     pub(crate) workspaces: Mutex<HashMap<String, WorkspaceEntry>>,
     pub(crate) sessions: Mutex<HashMap<String, Arc<crate::codex::WorkspaceSession>>>,
     pub(crate) terminal_sessions:
@@ -18,7 +55,91 @@ pub(crate) struct AppState {
     pub(crate) storage_path: PathBuf,
     pub(crate) settings_path: PathBuf,
     pub(crate) app_settings: Mutex<AppSettings>,
+    /// Data key from the OS keychain; use `data_cipher` rather than this.
+    pub(crate) ciphers: CipherCache,
     pub(crate) dictation: Mutex<DictationState>,
+    pub(crate) purge_token: Mutex<PurgeTokenState>,
+    pub(crate) access_control: Mutex<AccessControl>,
+    /// Organization policy, loaded once at startup.
+    pub(crate) policy: EffectivePolicy,
+    pub(crate) window_scopes: std::sync::Mutex<WindowScopes>,
+    /// Resolved icon and color per workspace, attached to workspace events.
+    pub(crate) workspace_identities: std::sync::Mutex<HashMap<String, WorkspaceIdentity>>,
+    pub(crate) event_subscriptions: std::sync::Mutex<EventSubscriptions>,
+    pub(crate) thread_event_filters: std::sync::Mutex<ThreadEventFilters>,
+    pub(crate) reasoning_visibility: std::sync::Mutex<ReasoningVisibility>,
+    pub(crate) ab_tests: std::sync::Mutex<AbTestTracker>,
+    pub(crate) plans: std::sync::Mutex<PlanTracker>,
+    pub(crate) model_catalog: std::sync::Mutex<ModelCatalog>,
+    /// Last detected OS do-not-disturb state and when it was read.
+    pub(crate) focus_state: std::sync::Mutex<Option<(Instant, FocusState)>>,
+    pub(crate) app_lock: std::sync::Mutex<AppLock>,
+    pub(crate) turn_limiter: TurnLimiter,
+    pub(crate) workspace_turns: WorkspaceTurnGate,
+    pub(crate) rate_limits: RateLimitTracker,
+    pub(crate) auth_expiry: AuthExpiryTracker,
+    pub(crate) dry_runs: std::sync::Mutex<DryRunTracker>,
+    pub(crate) best_of_runs: std::sync::Mutex<BestOfTracker>,
+    pub(crate) verifications: std::sync::Mutex<VerificationTracker>,
+    /// Serializes verification commands, which share the workspace checkout.
+    pub(crate) verification_runs: Mutex<()>,
+    /// Serializes writes to the offline message queue.
+    pub(crate) offline_queue_writes: Mutex<()>,
+    /// Serializes writes to the per-thread model and effort memory.
+    pub(crate) thread_settings_writes: Mutex<()>,
+    /// Serializes writes to thread tags and saved thread filters.
+    pub(crate) thread_tag_writes: Mutex<()>,
+    pub(crate) thread_index: std::sync::Mutex<ThreadIndex>,
+    pub(crate) blame_cache: std::sync::Mutex<BlameCache>,
+    pub(crate) thread_link_writes: Mutex<()>,
+    /// Serializes writes to the workspace open counts used for prewarming.
+    pub(crate) workspace_usage_writes: Mutex<()>,
+    /// Lockfiles this instance wrote into checkouts, removed on exit.
+    pub(crate) workspace_locks: std::sync::Mutex<HashSet<PathBuf>>,
+    pub(crate) turn_label_writes: Mutex<()>,
+    pub(crate) annotation_writes: Mutex<()>,
+    /// Serializes automatic thread branch creation and its mapping file.
+    pub(crate) thread_branch_writes: Mutex<()>,
+    /// Serializes writes to the thread item journals.
+    pub(crate) thread_journal_writes: Mutex<()>,
+    pub(crate) command_outputs: std::sync::Mutex<CommandOutputCollector>,
+    /// Serializes writes to the captured command output files.
+    pub(crate) command_output_writes: Mutex<()>,
+    /// Serializes writes to the daily code stats files.
+    pub(crate) code_stats_writes: Mutex<()>,
+    /// Serializes writes to the daily feature usage counters.
+    pub(crate) telemetry_writes: Mutex<()>,
+    /// Serializes writes to the daily prompt outcome files.
+    pub(crate) prompt_outcome_writes: Mutex<()>,
+    /// Serializes writes to the daily workspace health sample files.
+    pub(crate) health_sample_writes: Mutex<()>,
+    pub(crate) model_sample_writes: Mutex<()>,
+    pub(crate) thread_timer_writes: Mutex<()>,
+    pub(crate) clock_jump_writes: Mutex<()>,
+    /// Serializes writes to the per-thread directory grants.
+    pub(crate) directory_grant_writes: Mutex<()>,
+    pub(crate) full_access_grant_writes: Mutex<()>,
+    /// Held while sessions are recovered after a sleep.
+    pub(crate) resume_recovery: Mutex<()>,
+    /// Serializes writes to the semantic search index.
+    pub(crate) semantic_index_writes: Mutex<()>,
+    pub(crate) staged_patch_writes: Mutex<()>,
+    /// Serializes appends to the hash-chained approval audit log.
+    pub(crate) audit_writes: Mutex<()>,
+    pub(crate) webhook_listener: std::sync::Mutex<Option<JoinHandle<()>>>,
+    pub(crate) delegations: std::sync::Mutex<PendingDelegations>,
+    pub(crate) pending_approvals: std::sync::Mutex<PendingApprovalTracker>,
+    pub(crate) approval_inbox: std::sync::Mutex<ApprovalInbox>,
+    /// Workspace shown in the main window, whose approvals the keyboard
+    /// shortcuts answer.
+    pub(crate) focused_workspace: std::sync::Mutex<Option<String>>,
+    /// Quick-ask thread per workspace; held while a question is answered.
+    pub(crate) quick_ask_threads: Mutex<HashMap<String, String>>,
+    pub(crate) review_pairs: std::sync::Mutex<ReviewPairTracker>,
+    pub(crate) prompt_outcomes: std::sync::Mutex<PromptOutcomeTracker>,
+    pub(crate) thread_stopwatch: std::sync::Mutex<ThreadStopwatch>,
+    pub(crate) event_buffer: std::sync::Mutex<EventBuffer>,
+    pub(crate) clock: std::sync::Mutex<Clock>,
 }
 
 impl AppState {
@@ -31,7 +152,32 @@ impl AppState {
         let settings_path = data_dir.join("settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
         let app_settings = read_settings(&settings_path).unwrap_or_default();
+        i18n::set_locale(app_settings.locale.as_deref());
+        command_guard::set_blocklist(&app_settings.command_blocklist);
+        network::set_network_settings(&app_settings.network);
+        let app_lock = AppLock::new(app_settings.auto_lock_minutes);
+        let turn_limiter = TurnLimiter::new(app_settings.max_concurrent_turns);
+        let workspace_turns = WorkspaceTurnGate::new(
+            app_settings.max_workspace_turns,
+            app_settings.turn_overlap_mode,
+        );
+        // Backups of files changed by applied patches only last one session.
+        atomic_write::clear_backups(&data_dir);
+        let access_control = AccessControl::load(access_control_path(&data_dir));
+        let workspace_identities = workspaces
+            .values()
+            .map(|entry| (entry.id.clone(), entry.identity()))
+            .collect();
+        let mut reasoning_visibility = ReasoningVisibility::default();
+        reasoning_visibility.set_defaults(
+            app_settings.show_reasoning_summaries,
+            &app_settings.reasoning_summary_models,
+        );
+        for entry in workspaces.values() {
+            reasoning_visibility.set_workspace(&entry.id, entry.settings.show_reasoning_summaries);
+        }
         Self {
+            data_dir,
             workspaces: Mutex::new(workspaces),
             sessions: Mutex::new(HashMap::new()),
             terminal_sessions: Mutex::new(HashMap::new()),
@@ -39,7 +185,79 @@ impl AppState {
             storage_path,
             settings_path,
             app_settings: Mutex::new(app_settings),
+            ciphers: CipherCache::default(),
             dictation: Mutex::new(DictationState::default()),
+            purge_token: Mutex::new(PurgeTokenState::default()),
+            access_control: Mutex::new(access_control),
+            policy: load_policy(&policy_path()),
+            window_scopes: std::sync::Mutex::new(WindowScopes::new()),
+            workspace_identities: std::sync::Mutex::new(workspace_identities),
+            event_subscriptions: std::sync::Mutex::new(EventSubscriptions::default()),
+            thread_event_filters: std::sync::Mutex::new(ThreadEventFilters::default()),
+            reasoning_visibility: std::sync::Mutex::new(reasoning_visibility),
+            ab_tests: std::sync::Mutex::new(AbTestTracker::default()),
+            plans: std::sync::Mutex::new(PlanTracker::default()),
+            model_catalog: std::sync::Mutex::new(ModelCatalog::default()),
+            focus_state: std::sync::Mutex::new(None),
+            app_lock: std::sync::Mutex::new(app_lock),
+            turn_limiter,
+            workspace_turns,
+            rate_limits: RateLimitTracker::default(),
+            auth_expiry: AuthExpiryTracker::default(),
+            dry_runs: std::sync::Mutex::new(DryRunTracker::default()),
+            best_of_runs: std::sync::Mutex::new(BestOfTracker::default()),
+            verifications: std::sync::Mutex::new(VerificationTracker::default()),
+            verification_runs: Mutex::new(()),
+            offline_queue_writes: Mutex::new(()),
+            thread_settings_writes: Mutex::new(()),
+            thread_tag_writes: Mutex::new(()),
+            thread_index: std::sync::Mutex::new(ThreadIndex::default()),
+            blame_cache: std::sync::Mutex::new(BlameCache::default()),
+            thread_link_writes: Mutex::new(()),
+            workspace_usage_writes: Mutex::new(()),
+            workspace_locks: std::sync::Mutex::new(HashSet::new()),
+            turn_label_writes: Mutex::new(()),
+            annotation_writes: Mutex::new(()),
+            thread_branch_writes: Mutex::new(()),
+            thread_journal_writes: Mutex::new(()),
+            command_outputs: std::sync::Mutex::new(CommandOutputCollector::default()),
+            command_output_writes: Mutex::new(()),
+            code_stats_writes: Mutex::new(()),
+            telemetry_writes: Mutex::new(()),
+            prompt_outcome_writes: Mutex::new(()),
+            health_sample_writes: Mutex::new(()),
+            model_sample_writes: Mutex::new(()),
+            thread_timer_writes: Mutex::new(()),
+            clock_jump_writes: Mutex::new(()),
+            directory_grant_writes: Mutex::new(()),
+            full_access_grant_writes: Mutex::new(()),
+            resume_recovery: Mutex::new(()),
+            semantic_index_writes: Mutex::new(()),
+            staged_patch_writes: Mutex::new(()),
+            audit_writes: Mutex::new(()),
+            webhook_listener: std::sync::Mutex::new(None),
+            delegations: std::sync::Mutex::new(PendingDelegations::default()),
+            pending_approvals: std::sync::Mutex::new(PendingApprovalTracker::default()),
+            approval_inbox: std::sync::Mutex::new(ApprovalInbox::default()),
+            focused_workspace: std::sync::Mutex::new(None),
+            quick_ask_threads: Mutex::new(HashMap::new()),
+            review_pairs: std::sync::Mutex::new(ReviewPairTracker::default()),
+            prompt_outcomes: std::sync::Mutex::new(PromptOutcomeTracker::default()),
+            thread_stopwatch: std::sync::Mutex::new(ThreadStopwatch::default()),
+            event_buffer: std::sync::Mutex::new(EventBuffer::default()),
+            clock: std::sync::Mutex::new(Clock::default()),
+        }
+    }
+
+    pub(crate) async fn data_cipher(&self) -> Result<DataCipher, String> {
+        let encrypt_local_data = self.app_settings.lock().await.encrypt_local_data;
+        self.ciphers.cipher(encrypt_local_data)
+    }
+
+    /// Call whenever a workspace is added or its path, icon or color change.
+    pub(crate) fn cache_workspace_identity(&self, entry: &WorkspaceEntry) {
+        if let Ok(mut identities) = self.workspace_identities.lock() {
+            identities.insert(entry.id.clone(), entry.identity());
         }
     }
 }
@@ -67,6 +67,8 @@ mod tests {
             parent_id: None,
             worktree: None,
             settings: settings.clone(),
+            icon: None,
+            color: None,
         };
 
         write_workspaces(&path, &[entry]).expect("write workspaces");
@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::time::timeout;
+
+use crate::backend::clock::{ClockJump, JumpKind};
+use crate::backend::workspace_health::HealthEvent;
+use crate::codex;
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::workspace_health;
+use crate::workspace_prewarm;
+use crate::workspaces;
+
+/// Forward clock jumps at least this long are treated as a sleep.
+const MIN_SLEEP_MS: i64 = 30_000;
+/// Time a session has to answer the health check before it is restarted.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum SessionRecovery {
+    Healthy,
+    Reconnected,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceRecovery {
+    pub(crate) workspace_id: String,
+    pub(crate) status: SessionRecovery,
+    pub(crate) error: Option<String>,
+}
+
+/// Payload of `system/resumed`.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ResumeReport {
+    pub(crate) slept_ms: i64,
+    pub(crate) resumed_at: i64,
+    pub(crate) workspaces: Vec<WorkspaceRecovery>,
+}
+
+/// Whether a clock jump means the machine was asleep.
+pub(crate) fn is_wake(jump: &ClockJump) -> bool {
+    jump.kind == JumpKind::Forward && jump.skew_ms >= MIN_SLEEP_MS
+}
+
+/// After a sleep, health-checks every connected session, reconnects the
+/// dead ones, refreshes rate limits and emits one `system/resumed` event
+/// with the outcome, then prewarms the workspaces usually opened at this
+/// hour. A recovery already in progress absorbs the next wake.
+pub(crate) fn spawn_recovery(app: &AppHandle, jump: &ClockJump) {
+    let app = app.clone();
+    let slept_ms = jump.skew_ms;
+    let resumed_at = jump.after.utc_ms;
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let Ok(_guard) = state.resume_recovery.try_lock() else {
+            return;
+        };
+        let mut workspace_ids = connected_workspaces(&app).await;
+        if workspace_ids.is_err() {
+            // The first remote call after a sleep drops the stale connection.
+            workspace_ids = connected_workspaces(&app).await;
+        }
+        let workspace_ids = workspace_ids.unwrap_or_else(|err| {
+            eprintln!("Failed to list workspaces after resume: {err}");
+            Vec::new()
+        });
+        let tasks: Vec<_> = workspace_ids
+            .into_iter()
+            .map(|workspace_id| tokio::spawn(recover_workspace(app.clone(), workspace_id)))
+            .collect();
+        let mut workspaces = Vec::new();
+        for task in tasks {
+            if let Ok(recovery) = task.await {
+                workspaces.push(recovery);
+            }
+        }
+        let report = ResumeReport {
+            slept_ms,
+            resumed_at,
+            workspaces,
+        };
+        let _ = app.emit("system/resumed", report);
+        workspace_prewarm::prewarm_usual_workspaces(&app).await;
+    });
+}
+
+async fn connected_workspaces(app: &AppHandle) -> Result<Vec<String>, String> {
+    let workspaces = workspaces::list_workspaces(app.state(), app.clone()).await?;
+    Ok(workspaces
+        .into_iter()
+        .filter(|workspace| workspace.connected)
+        .map(|workspace| workspace.id)
+        .collect())
+}
+
+/// Reads rate limits, which doubles as the session health check.
+async fn refresh_rate_limits(app: &AppHandle, workspace_id: &str) -> Result<(), String> {
+    let request = codex::account_rate_limits(workspace_id.to_string(), app.state(), app.clone());
+    match timeout(HEALTH_CHECK_TIMEOUT, request).await {
+        Ok(result) => result.map(|_| ()),
+        Err(_) => Err("app-server did not respond".to_string()),
+    }
+}
+
+/// `false` when the local app-server process has exited.
+async fn local_process_alive(state: &AppState, workspace_id: &str) -> bool {
+    let session = state.sessions.lock().await.get(workspace_id).cloned();
+    let Some(session) = session else {
+        return false;
+    };
+    let mut child = session.child.lock().await;
+    matches!(child.try_wait(), Ok(None))
+}
+
+async fn recover_workspace(app: AppHandle, workspace_id: String) -> WorkspaceRecovery {
+    let state = app.state::<AppState>();
+    let remote = remote_backend::is_remote_mode(&state).await;
+    let alive = remote || local_process_alive(&state, &workspace_id).await;
+    if alive && refresh_rate_limits(&app, &workspace_id).await.is_ok() {
+        return WorkspaceRecovery {
+            workspace_id,
+            status: SessionRecovery::Healthy,
+            error: None,
+        };
+    }
+
+    if !remote {
+        if let Some(session) = state.sessions.lock().await.remove(&workspace_id) {
+            let mut child = session.child.lock().await;
+            let _ = child.kill().await;
+        }
+    }
+    workspace_health::record(&app, &workspace_id, HealthEvent::SessionLost);
+    match workspaces::connect_workspace(workspace_id.clone(), state.clone(), app.clone()).await {
+        Ok(()) => {
+            let _ = refresh_rate_limits(&app, &workspace_id).await;
+            WorkspaceRecovery {
+                workspace_id,
+                status: SessionRecovery::Reconnected,
+                error: None,
+            }
+        }
+        Err(err) => WorkspaceRecovery {
+            workspace_id,
+            status: SessionRecovery::Failed,
+            error: Some(err),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::clock::Timestamp;
+
+    fn jump(kind: JumpKind, skew_ms: i64) -> ClockJump {
+        let at = Timestamp {
+            utc_ms: 0,
+            mono_ms: 0,
+            offset_minutes: 0,
+        };
+        ClockJump {
+            before: at,
+            after: at,
+            kind,
+            skew_ms,
+        }
+    }
+
+    #[test]
+    fn only_long_forward_jumps_are_wakes() {
+        assert!(is_wake(&jump(JumpKind::Forward, 8 * 3_600_000)));
+        assert!(!is_wake(&jump(JumpKind::Forward, 5_000)));
+        assert!(!is_wake(&jump(JumpKind::Backward, -3_600_000)));
+    }
+}
@@ -0,0 +1,143 @@
+use tauri::{AppHandle, Emitter, State};
+
+use crate::backend::issue_links::{self, ThreadIssueLink};
+use crate::backend::task_queue::{self, QueuedTask, QueuedTaskStatus};
+use crate::backend::turn_limiter::TurnLane;
+use crate::codex::{self, SendOptions};
+use crate::state::AppState;
+
+/// Re-reads the queue and broadcasts it as `task-queue-updated`.
+pub(crate) async fn emit_queue_updated(app: &AppHandle, state: &AppState) {
+    let Ok(cipher) = state.data_cipher().await else {
+        return;
+    };
+    if let Ok(tasks) =
+        task_queue::read_tasks(&task_queue::task_queue_path(&state.data_dir), &cipher)
+    {
+        let _ = app.emit("task-queue-updated", tasks);
+    }
+}
+
+/// Adds a task to the queue; returns `false` when the source issue is
+/// already queued.
+pub(crate) async fn enqueue(
+    task: QueuedTask,
+    state: &AppState,
+    app: &AppHandle,
+) -> Result<bool, String> {
+    let cipher = state.data_cipher().await?;
+    let added =
+        task_queue::enqueue_task(&task_queue::task_queue_path(&state.data_dir), &cipher, task)?;
+    if added {
+        emit_queue_updated(app, state).await;
+    }
+    Ok(added)
+}
+
+/// Starts a queued task in a fresh thread of its workspace and links the
+/// thread to the source issue. The turn waits in the scheduled lane of the
+/// concurrent-turn cap unless `lane` says otherwise.
+pub(crate) async fn start_task(
+    task_id: String,
+    model: Option<String>,
+    effort: Option<String>,
+    access_mode: Option<String>,
+    lane: Option<TurnLane>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<QueuedTask, String> {
+    let path = task_queue::task_queue_path(&state.data_dir);
+    let cipher = state.data_cipher().await?;
+    let task = task_queue::read_tasks(&path, &cipher)?
+        .into_iter()
+        .find(|task| task.id == task_id)
+        .ok_or("Queued task not found.")?;
+    if task.status != QueuedTaskStatus::Queued {
+        return Err("Task has already been started.".to_string());
+    }
+
+    let response =
+        codex::start_thread(task.workspace_id.clone(), state.clone(), app.clone()).await?;
+    let result = response.get("result").unwrap_or(&response);
+    let thread_id = result
+        .get("thread")
+        .and_then(|thread| thread.get("id"))
+        .and_then(|value| value.as_str())
+        .ok_or("thread/start did not return a thread id")?
+        .to_string();
+
+    issue_links::record_issue_link(
+        &issue_links::issue_links_path(&state.data_dir),
+        &cipher,
+        ThreadIssueLink {
+            workspace_id: task.workspace_id.clone(),
+            thread_id: thread_id.clone(),
+            tracker: task.source.clone(),
+            issue_id: task.external_id.clone(),
+            title: task.title.clone(),
+            url: task.url.clone(),
+            linked_at: chrono::Utc::now().timestamp_millis(),
+        },
+    )?;
+    let updated = task_queue::mark_task_started(&path, &cipher, &task.id, &thread_id)?;
+
+    codex::send_message(
+        task.workspace_id,
+        thread_id,
+        task.prompt,
+        SendOptions {
+            model,
+            effort,
+            access_mode,
+            lane: lane.unwrap_or(TurnLane::Scheduled),
+            ..SendOptions::default()
+        },
+        state.clone(),
+        app.clone(),
+    )
+    .await?;
+    emit_queue_updated(&app, &state).await;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub(crate) async fn list_queued_tasks(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<QueuedTask>, String> {
+    let cipher = state.data_cipher().await?;
+    let mut tasks = task_queue::read_tasks(&task_queue::task_queue_path(&state.data_dir), &cipher)?;
+    if let Some(workspace_id) = workspace_id {
+        tasks.retain(|task| task.workspace_id == workspace_id);
+    }
+    Ok(tasks)
+}
+
+#[tauri::command]
+pub(crate) async fn start_queued_task(
+    task_id: String,
+    model: Option<String>,
+    effort: Option<String>,
+    access_mode: Option<String>,
+    lane: Option<TurnLane>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<QueuedTask, String> {
+    start_task(task_id, model, effort, access_mode, lane, state, app).await
+}
+
+#[tauri::command]
+pub(crate) async fn dismiss_queued_task(
+    task_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let cipher = state.data_cipher().await?;
+    task_queue::remove_task(
+        &task_queue::task_queue_path(&state.data_dir),
+        &cipher,
+        &task_id,
+    )?;
+    emit_queue_updated(&app, &state).await;
+    Ok(())
+}
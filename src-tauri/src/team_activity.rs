@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::team_activity::{self, SharingOptions, TeamActivity};
+use crate::state::AppState;
+use crate::types::TeamActivitySettings;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn activity_url(settings: &TeamActivitySettings) -> Option<String> {
+    let base = settings.server_url.as_deref()?.trim().trim_end_matches('/');
+    (settings.enabled && !base.is_empty()).then(|| format!("{base}/activity"))
+}
+
+fn request(
+    method: reqwest::Method,
+    url: &str,
+    settings: &TeamActivitySettings,
+) -> Result<reqwest::RequestBuilder, String> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client.request(method, url);
+    if let Some(token) = settings
+        .token
+        .as_deref()
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+    {
+        request = request.bearer_auth(token);
+    }
+    Ok(request)
+}
+
+async fn publish(app: AppHandle, workspace_id: String, message: Value) {
+    let state = app.state::<AppState>();
+    let settings = state.app_settings.lock().await.team_activity.clone();
+    let Some(url) = activity_url(&settings) else {
+        return;
+    };
+    let workspace_name = if settings.share_workspace_names {
+        state
+            .workspaces
+            .lock()
+            .await
+            .get(&workspace_id)
+            .map(|entry| entry.name.clone())
+    } else {
+        None
+    };
+    let member = settings.member_name.trim();
+    let sharing = SharingOptions {
+        member: if member.is_empty() {
+            "anonymous"
+        } else {
+            member
+        },
+        workspace_name: workspace_name.as_deref(),
+        share_summaries: settings.share_summaries,
+    };
+    let Some(activity) = team_activity::activity_from_event(
+        &workspace_id,
+        &message,
+        &sharing,
+        Utc::now().timestamp_millis(),
+    ) else {
+        return;
+    };
+    let body = match serde_json::to_string(&activity) {
+        Ok(body) => body,
+        Err(err) => {
+            eprintln!("Failed to serialize team activity: {err}");
+            return;
+        }
+    };
+    let result = match request(reqwest::Method::POST, &url, &settings) {
+        Ok(request) => request
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string()),
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(response) if response.status().is_success() => {}
+        Ok(response) => eprintln!("Team activity endpoint returned {}", response.status()),
+        Err(err) => eprintln!("Failed to publish team activity: {err}"),
+    }
+}
+
+/// Publishes turn starts and completions to the team endpoint when
+/// `teamActivity` is enabled; called for every `app-server-event`, local or
+/// forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    if !matches!(
+        message.get("method").and_then(|value| value.as_str()),
+        Some("turn/started" | "turn/completed")
+    ) {
+        return;
+    }
+    tauri::async_runtime::spawn(publish(
+        app.clone(),
+        workspace_id.to_string(),
+        message.clone(),
+    ));
+}
+
+/// Teammates' latest agent activity from the team endpoint, for the team
+/// panel.
+#[tauri::command]
+pub(crate) async fn get_team_activity(
+    state: State<'_, AppState>,
+) -> Result<Vec<TeamActivity>, String> {
+    let settings = state.app_settings.lock().await.team_activity.clone();
+    let url = activity_url(&settings).ok_or("Team activity is not enabled.")?;
+    let response = request(reqwest::Method::GET, &url, &settings)?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach the team endpoint: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Team endpoint returned {}", response.status()));
+    }
+    let body = response.text().await.map_err(|e| e.to_string())?;
+    let value: Value =
+        serde_json::from_str(&body).map_err(|e| format!("Invalid team activity feed: {e}"))?;
+    team_activity::parse_feed(value)
+}
@@ -0,0 +1,388 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tauri::State;
+
+use crate::codex_home::resolve_default_codex_home;
+use crate::prompts::{
+    build_prompt_contents, default_prompts_dir, discover_prompts_in, sanitize_prompt_name,
+};
+use crate::rules;
+use crate::state::AppState;
+use crate::storage::write_settings;
+
+const TEAM_PACK_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TeamPackPrompt {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) argument_hint: Option<String>,
+    pub(crate) content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TeamPackProfile {
+    #[serde(default)]
+    pub(crate) default_access_mode: Option<String>,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) reasoning_effort: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TeamPack {
+    pub(crate) version: u32,
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+    #[serde(default)]
+    pub(crate) exported_at: i64,
+    #[serde(default)]
+    pub(crate) approval_rules: Vec<Vec<String>>,
+    #[serde(default)]
+    pub(crate) prompts: Vec<TeamPackPrompt>,
+    #[serde(default)]
+    pub(crate) profile: Option<TeamPackProfile>,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TeamPackImportReport {
+    pub(crate) rules_added: usize,
+    pub(crate) prompts_added: Vec<String>,
+    pub(crate) prompts_updated: Vec<String>,
+    pub(crate) prompts_unchanged: Vec<String>,
+    pub(crate) conflicts: Vec<String>,
+    pub(crate) profile_applied: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ConflictStrategy {
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+impl ConflictStrategy {
+    fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value.unwrap_or("skip") {
+            "skip" => Ok(Self::Skip),
+            "overwrite" => Ok(Self::Overwrite),
+            "rename" => Ok(Self::Rename),
+            other => Err(format!("Unknown conflict strategy: {other}")),
+        }
+    }
+}
+
+/// Extracts `allow` prefix rule patterns from a Codex rules file.
+fn parse_allow_prefix_rules(contents: &str) -> Vec<Vec<String>> {
+    let mut rules = Vec::new();
+    let mut pattern: Option<Vec<String>> = None;
+    let mut allows = false;
+    let mut in_rule = false;
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("prefix_rule(") {
+            in_rule = true;
+            pattern = None;
+            allows = false;
+            continue;
+        }
+        if !in_rule {
+            continue;
+        }
+        if let Some((key, value)) = trimmed.split_once('=') {
+            let value = value.trim().trim_end_matches(',');
+            match key.trim() {
+                "pattern" => pattern = serde_json::from_str::<Vec<String>>(value).ok(),
+                "decision" => allows = value.contains("allow"),
+                _ => {}
+            }
+        } else if trimmed.starts_with(')') {
+            if let (Some(found), true) = (pattern.take(), allows) {
+                if !found.is_empty() {
+                    rules.push(found);
+                }
+            }
+            in_rule = false;
+        }
+    }
+    rules
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn rules_path() -> Result<PathBuf, String> {
+    let codex_home =
+        resolve_default_codex_home().ok_or("Unable to resolve CODEX_HOME".to_string())?;
+    Ok(rules::default_rules_path(&codex_home))
+}
+
+fn unique_prompt_name(dir: &Path, base: &str) -> String {
+    let mut candidate = format!("{base}-team");
+    let mut index = 2;
+    while dir.join(format!("{candidate}.md")).exists() {
+        candidate = format!("{base}-team-{index}");
+        index += 1;
+    }
+    candidate
+}
+
+fn import_prompts(
+    dir: &Path,
+    prompts: &[TeamPackPrompt],
+    strategy: ConflictStrategy,
+    report: &mut TeamPackImportReport,
+) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    let existing = discover_prompts_in(dir, None);
+    for prompt in prompts {
+        let name = sanitize_prompt_name(&prompt.name)?;
+        let contents = build_prompt_contents(
+            prompt.description.clone(),
+            prompt.argument_hint.clone(),
+            prompt.content.clone(),
+        );
+        let current = existing.iter().find(|entry| entry.name == name);
+        let target_name = match current {
+            None => {
+                report.prompts_added.push(name.clone());
+                name
+            }
+            Some(entry)
+                if entry.content == prompt.content
+                    && entry.description == prompt.description
+                    && entry.argument_hint == prompt.argument_hint =>
+            {
+                report.prompts_unchanged.push(name);
+                continue;
+            }
+            Some(_) => match strategy {
+                ConflictStrategy::Skip => {
+                    report.conflicts.push(format!("prompt:{name}"));
+                    continue;
+                }
+                ConflictStrategy::Overwrite => {
+                    report.prompts_updated.push(name.clone());
+                    name
+                }
+                ConflictStrategy::Rename => {
+                    let renamed = unique_prompt_name(dir, &name);
+                    report.prompts_added.push(renamed.clone());
+                    renamed
+                }
+            },
+        };
+        fs::write(dir.join(format!("{target_name}.md")), contents)
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+fn profile_conflicts(current: &TeamPackProfile, incoming: &TeamPackProfile) -> Vec<String> {
+    let fields = [
+        ("defaultAccessMode", &current.default_access_mode, &incoming.default_access_mode),
+        ("model", &current.model, &incoming.model),
+        ("reasoningEffort", &current.reasoning_effort, &incoming.reasoning_effort),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, current, incoming)| {
+            incoming.is_some() && current.is_some() && current != incoming
+        })
+        .map(|(name, _, _)| format!("profile:{name}"))
+        .collect()
+}
+
+#[tauri::command]
+pub(crate) async fn export_team_pack(
+    path: String,
+    name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<TeamPack, String> {
+    let rules_contents = fs::read_to_string(rules_path()?).unwrap_or_default();
+    let prompts = default_prompts_dir()
+        .map(|dir| discover_prompts_in(&dir, None))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry| TeamPackPrompt {
+            name: entry.name,
+            description: entry.description,
+            argument_hint: entry.argument_hint,
+            content: entry.content,
+        })
+        .collect();
+    let profile = {
+        let settings = state.app_settings.lock().await;
+        TeamPackProfile {
+            default_access_mode: Some(settings.default_access_mode.clone()),
+            model: settings.last_composer_model_id.clone(),
+            reasoning_effort: settings.last_composer_reasoning_effort.clone(),
+        }
+    };
+    let pack = TeamPack {
+        version: TEAM_PACK_VERSION,
+        name: name.filter(|value| !value.trim().is_empty()),
+        exported_at: now_ms(),
+        approval_rules: parse_allow_prefix_rules(&rules_contents),
+        prompts,
+        profile: Some(profile),
+    };
+    let data = serde_json::to_string_pretty(&pack).map_err(|err| err.to_string())?;
+    let path = PathBuf::from(path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    fs::write(&path, data).map_err(|err| err.to_string())?;
+    Ok(pack)
+}
+
+#[tauri::command]
+pub(crate) async fn import_team_pack(
+    path: String,
+    conflict_strategy: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<TeamPackImportReport, String> {
+    let strategy = ConflictStrategy::parse(conflict_strategy.as_deref())?;
+    let data = fs::read_to_string(&path).map_err(|err| err.to_string())?;
+    let pack: TeamPack = serde_json::from_str(&data).map_err(|err| err.to_string())?;
+    if pack.version > TEAM_PACK_VERSION {
+        return Err(format!(
+            "Team pack version {} is newer than supported version {TEAM_PACK_VERSION}.",
+            pack.version
+        ));
+    }
+
+    let mut report = TeamPackImportReport::default();
+    if !pack.approval_rules.is_empty() {
+        let rules_path = rules_path()?;
+        let before = parse_allow_prefix_rules(&fs::read_to_string(&rules_path).unwrap_or_default());
+        for pattern in &pack.approval_rules {
+            if before.contains(pattern) {
+                continue;
+            }
+            rules::append_prefix_rule(&rules_path, pattern)?;
+            report.rules_added += 1;
+        }
+    }
+
+    if !pack.prompts.is_empty() {
+        let dir = default_prompts_dir().ok_or("Unable to resolve CODEX_HOME".to_string())?;
+        import_prompts(&dir, &pack.prompts, strategy, &mut report)?;
+    }
+
+    if let Some(incoming) = pack.profile {
+        let mut settings = state.app_settings.lock().await;
+        let current = TeamPackProfile {
+            default_access_mode: Some(settings.default_access_mode.clone()),
+            model: settings.last_composer_model_id.clone(),
+            reasoning_effort: settings.last_composer_reasoning_effort.clone(),
+        };
+        let conflicts = profile_conflicts(&current, &incoming);
+        if conflicts.is_empty() || strategy == ConflictStrategy::Overwrite {
+            let mut updated = settings.clone();
+            if let Some(mode) = incoming.default_access_mode {
+                updated.default_access_mode = mode;
+            }
+            if incoming.model.is_some() {
+                updated.last_composer_model_id = incoming.model;
+            }
+            if incoming.reasoning_effort.is_some() {
+                updated.last_composer_reasoning_effort = incoming.reasoning_effort;
+            }
+            write_settings(&state.settings_path, &updated)?;
+            *settings = updated;
+            report.profile_applied = true;
+        } else {
+            report.conflicts.extend(conflicts);
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn parse_allow_prefix_rules_reads_allow_patterns() {
+        let contents = "prefix_rule(\n    pattern = [\"git\", \"status\"],\n    decision = \"allow\",\n)\n\n\
+prefix_rule(\n    pattern = [\"rm\", \"-rf\"],\n    decision = \"forbidden\",\n)\n";
+        assert_eq!(
+            parse_allow_prefix_rules(contents),
+            vec![vec!["git".to_string(), "status".to_string()]]
+        );
+    }
+
+    #[test]
+    fn import_prompts_respects_conflict_strategy() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("create temp dir");
+        fs::write(dir.join("review.md"), "local body").expect("write prompt");
+        let prompts = vec![
+            TeamPackPrompt {
+                name: "review".to_string(),
+                description: None,
+                argument_hint: None,
+                content: "team body".to_string(),
+            },
+            TeamPackPrompt {
+                name: "triage".to_string(),
+                description: Some("Triage".to_string()),
+                argument_hint: None,
+                content: "triage body".to_string(),
+            },
+        ];
+
+        let mut report = TeamPackImportReport::default();
+        import_prompts(&dir, &prompts, ConflictStrategy::Skip, &mut report).expect("import");
+        assert_eq!(report.conflicts, vec!["prompt:review".to_string()]);
+        assert_eq!(report.prompts_added, vec!["triage".to_string()]);
+        assert_eq!(
+            fs::read_to_string(dir.join("review.md")).expect("read"),
+            "local body"
+        );
+
+        let mut report = TeamPackImportReport::default();
+        import_prompts(&dir, &prompts, ConflictStrategy::Rename, &mut report).expect("import");
+        assert_eq!(report.prompts_added, vec!["review-team".to_string()]);
+        assert_eq!(report.prompts_unchanged, vec!["triage".to_string()]);
+        assert_eq!(
+            fs::read_to_string(dir.join("review-team.md")).expect("read"),
+            "team body"
+        );
+    }
+
+    #[test]
+    fn profile_conflicts_ignore_unset_fields() {
+        let current = TeamPackProfile {
+            default_access_mode: Some("current".to_string()),
+            model: None,
+            reasoning_effort: Some("high".to_string()),
+        };
+        let incoming = TeamPackProfile {
+            default_access_mode: Some("read-only".to_string()),
+            model: Some("gpt-5".to_string()),
+            reasoning_effort: None,
+        };
+        assert_eq!(
+            profile_conflicts(&current, &incoming),
+            vec!["profile:defaultAccessMode".to_string()]
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::telemetry::{self, TelemetryReport};
+use crate::state::AppState;
+
+const DEFAULT_REPORT_DAYS: u32 = 30;
+const SHARE_CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Counts one use of `feature` for today, if the user opted in.
+async fn record(state: &AppState, feature: &str) -> Result<(), String> {
+    if !state.app_settings.lock().await.telemetry.enabled {
+        return Ok(());
+    }
+    let _guard = state.telemetry_writes.lock().await;
+    let cipher = state.data_cipher().await?;
+    telemetry::record(&state.data_dir, &cipher, feature, Utc::now().date_naive())
+}
+
+/// Counts turns, reviews and approval requests; called for every
+/// `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, _workspace_id: &str, message: &Value) {
+    let Some(feature) = telemetry::feature_for_event(message) else {
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        if let Err(err) = record(&state, feature).await {
+            eprintln!("Failed to record feature usage: {err}");
+        }
+    });
+}
+
+/// Uploads anonymized aggregates of completed days when sharing is on.
+pub(crate) fn spawn_aggregate_sharing(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(err) = share_pending_days(&app).await {
+                eprintln!("Failed to share usage aggregates: {err}");
+            }
+            tokio::time::sleep(SHARE_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+async fn share_pending_days(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let settings = state.app_settings.lock().await.telemetry.clone();
+    let endpoint = match settings.endpoint.as_deref().map(str::trim) {
+        Some(endpoint) if settings.enabled && settings.share_aggregates && !endpoint.is_empty() => {
+            endpoint.to_string()
+        }
+        _ => return Ok(()),
+    };
+    let cipher = state.data_cipher().await?;
+    let days = telemetry::days_to_share(&state.data_dir, &cipher, Utc::now().date_naive());
+    let Some(last_day) = days.last().copied() else {
+        return Ok(());
+    };
+    let app_version = app.package_info().version.to_string();
+    let mut aggregates = Vec::new();
+    for day in days {
+        let counts = telemetry::read_day(&state.data_dir, &cipher, day)?;
+        if !counts.is_empty() {
+            aggregates.push(telemetry::anonymize(day, &counts, &app_version));
+        }
+    }
+    if !aggregates.is_empty() {
+        let body = serde_json::to_string(&aggregates).map_err(|e| e.to_string())?;
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| e.to_string())?;
+        let response = client
+            .post(&endpoint)
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("Usage endpoint returned {}", response.status()));
+        }
+    }
+    telemetry::mark_shared(&state.data_dir, &cipher, last_day)
+}
+
+/// Counts a use of a UI feature, e.g. `panel.git`. Does nothing unless the
+/// user opted in.
+#[tauri::command]
+pub(crate) async fn record_feature_usage(
+    feature: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if !telemetry::is_valid_feature(&feature) {
+        return Err(format!("Invalid feature name: {feature}"));
+    }
+    record(&state, &feature).await
+}
+
+/// The user's own feature usage over the last `days` days, from the local
+/// counters; available whether or not aggregates are shared.
+#[tauri::command]
+pub(crate) async fn get_local_telemetry(
+    days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<TelemetryReport, String> {
+    let cipher = state.data_cipher().await?;
+    telemetry::build_report(
+        &state.data_dir,
+        &cipher,
+        days.unwrap_or(DEFAULT_REPORT_DAYS).clamp(1, 365),
+        Utc::now().date_naive(),
+    )
+}
@@ -0,0 +1,140 @@
+use serde::Serialize;
+use tauri::State;
+use tokio::process::Command;
+
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::session_env::{shell_quote, SessionEnvironment};
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// Terminal emulators tried on Linux, in order, with the arguments that
+/// precede `sh -c <command>`.
+const LINUX_TERMINALS: [(&str, &[&str]); 4] = [
+    ("x-terminal-emulator", &["-e"]),
+    ("gnome-terminal", &["--"]),
+    ("konsole", &["-e"]),
+    ("xterm", &["-e"]),
+];
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TerminalHandoff {
+    /// Shell command that resumes the thread with the session's binary, cwd
+    /// and `CODEX_HOME`.
+    pub(crate) command_line: String,
+    /// Whether a terminal was opened; otherwise the command is meant to be
+    /// pasted into one.
+    pub(crate) launched: bool,
+}
+
+/// `codex resume <thread_id>` as the app-server of `environment` would run
+/// it, so the CLI picks up the same conversation store.
+fn resume_command_line(environment: &SessionEnvironment, thread_id: &str) -> String {
+    let mut parts = Vec::new();
+    if let Some(cwd) = &environment.cwd {
+        parts.push(format!("cd {} &&", shell_quote(cwd)));
+    }
+    if let Some(codex_home) = environment.env.get("CODEX_HOME") {
+        parts.push(format!("CODEX_HOME={}", shell_quote(codex_home)));
+    }
+    parts.push(shell_quote(&environment.program));
+    parts.push("resume".to_string());
+    parts.push(shell_quote(thread_id));
+    parts.join(" ")
+}
+
+/// AppleScript string literal for `value`.
+fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+async fn launch_terminal(command_line: &str) -> bool {
+    if cfg!(target_os = "macos") {
+        let script = format!(
+            "tell application \"Terminal\"\nactivate\ndo script {}\nend tell",
+            applescript_string(command_line)
+        );
+        return Command::new("osascript")
+            .arg("-e")
+            .arg(script)
+            .status()
+            .await
+            .is_ok_and(|status| status.success());
+    }
+    if cfg!(target_os = "linux") {
+        // Keeps the terminal open once the CLI exits.
+        let script = format!("{command_line}; exec \"${{SHELL:-sh}}\"");
+        for (program, args) in LINUX_TERMINALS {
+            let spawned = Command::new(program)
+                .args(args)
+                .args(["sh", "-c", &script])
+                .spawn();
+            if spawned.is_ok() {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Hands a thread over to the Codex CLI: opens a terminal running
+/// `codex resume <thread_id>` in the workspace's cwd with its `CODEX_HOME`.
+/// Resuming the thread in the app afterwards picks up what happened there.
+#[tauri::command]
+pub(crate) async fn open_in_terminal(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+) -> Result<TerminalHandoff, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Terminal handoff needs a local backend.".to_string());
+    }
+    let environment = state
+        .sessions
+        .lock()
+        .await
+        .get(&workspace_id)
+        .map(|session| session.environment.clone())
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?;
+    let command_line = resume_command_line(&environment, &thread_id);
+    let launched = launch_terminal(&command_line).await;
+    Ok(TerminalHandoff {
+        command_line,
+        launched,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn builds_a_resume_command_for_the_session() {
+        let mut environment = SessionEnvironment {
+            program: "/opt/codex/bin/codex".to_string(),
+            args: vec!["app-server".to_string()],
+            command_line: "/opt/codex/bin/codex app-server".to_string(),
+            cwd: Some("/work/my app".to_string()),
+            path: Vec::new(),
+            env: BTreeMap::from([(
+                "CODEX_HOME".to_string(),
+                "/work/my app/.codexmonitor".to_string(),
+            )]),
+            overridden: Vec::new(),
+            launched_at: 0,
+        };
+        assert_eq!(
+            resume_command_line(&environment, "thr_1"),
+            "cd '/work/my app' && CODEX_HOME='/work/my app/.codexmonitor' \
+/opt/codex/bin/codex resume thr_1"
+        );
+        environment.cwd = None;
+        environment.env.clear();
+        assert_eq!(
+            resume_command_line(&environment, "thr_1"),
+            "/opt/codex/bin/codex resume thr_1"
+        );
+        assert_eq!(applescript_string(r#"say "hi" \"#), r#""say \"hi\" \\""#);
+    }
+}
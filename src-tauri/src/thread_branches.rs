@@ -0,0 +1,106 @@
+use git2::{BranchType, Repository, StatusOptions};
+use tauri::State;
+
+use crate::backend::thread_branches::{self, ThreadBranch};
+use crate::git_utils::{checkout_branch, resolve_git_root};
+use crate::state::AppState;
+use crate::types::{AutoBranchConfig, WorkspaceEntry};
+
+/// Returns the checked out branch when it is `main` or `master` and no
+/// tracked file has changes.
+fn clean_main_branch(repo: &Repository) -> Result<Option<String>, String> {
+    let head = repo.head().map_err(|e| e.to_string())?;
+    let Some(branch) = head.shorthand().filter(|_| head.is_branch()) else {
+        return Ok(None);
+    };
+    if !thread_branches::is_main_branch(branch) {
+        return Ok(None);
+    }
+    let mut options = StatusOptions::new();
+    options.include_untracked(false).include_ignored(false);
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    Ok(statuses.is_empty().then(|| branch.to_string()))
+}
+
+fn create_thread_branch(
+    entry: &WorkspaceEntry,
+    config: &AutoBranchConfig,
+    thread_id: &str,
+    title: &str,
+) -> Result<Option<ThreadBranch>, String> {
+    let repo_root = resolve_git_root(entry)?;
+    let Ok(repo) = Repository::open(&repo_root) else {
+        return Ok(None);
+    };
+    let Some(base_branch) = clean_main_branch(&repo)? else {
+        return Ok(None);
+    };
+    let branch = thread_branches::unique_branch_name(
+        &thread_branches::branch_name(&config.prefix, title, thread_id),
+        |name| repo.find_branch(name, BranchType::Local).is_ok(),
+    );
+    let target = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(|e| e.to_string())?;
+    repo.branch(&branch, &target, false)
+        .map_err(|e| e.to_string())?;
+    checkout_branch(&repo, &branch).map_err(|e| e.to_string())?;
+    Ok(Some(ThreadBranch {
+        workspace_id: entry.id.clone(),
+        thread_id: thread_id.to_string(),
+        branch,
+        base_branch,
+        created_at: chrono::Utc::now().timestamp_millis(),
+    }))
+}
+
+/// Applies the workspace's auto-branch policy before a write-enabled turn:
+/// a thread without a branch yet that starts on a clean `main` gets a new
+/// branch named from the message, which becomes the thread's title. Errors
+/// stop the turn so the agent does not write to `main` instead.
+pub(crate) async fn ensure_thread_branch(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+    text: &str,
+) -> Result<Option<ThreadBranch>, String> {
+    let Some(entry) = state.workspaces.lock().await.get(workspace_id).cloned() else {
+        return Ok(None);
+    };
+    let Some(config) = entry.settings.auto_branch.as_ref() else {
+        return Ok(None);
+    };
+    let cipher = state.data_cipher().await?;
+    let path = thread_branches::thread_branches_path(&state.data_dir);
+    let _guard = state.thread_branch_writes.lock().await;
+    if thread_branches::find_thread_branch(&path, &cipher, &entry.id, thread_id)?.is_some() {
+        return Ok(None);
+    }
+    let created = create_thread_branch(&entry, config, thread_id, text)
+        .map_err(|err| format!("Failed to create a branch for the thread: {err}"))?;
+    if let Some(created) = &created {
+        thread_branches::record_thread_branch(&path, &cipher, created.clone())?;
+    }
+    Ok(created)
+}
+
+/// Lists the branches the auto-branch policy created, optionally for one
+/// workspace.
+#[tauri::command]
+pub(crate) async fn list_thread_branches(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ThreadBranch>, String> {
+    let cipher = state.data_cipher().await?;
+    let mut entries = thread_branches::read_thread_branches(
+        &thread_branches::thread_branches_path(&state.data_dir),
+        &cipher,
+    )?;
+    if let Some(workspace_id) = workspace_id {
+        entries.retain(|entry| entry.workspace_id == workspace_id);
+    }
+    Ok(entries)
+}
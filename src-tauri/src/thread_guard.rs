@@ -0,0 +1,69 @@
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::backend::patch_staging;
+use crate::backend::request_id::RequestId;
+use crate::backend::thread_guard::ThreadAttention;
+use crate::state::AppState;
+
+/// Tracks approval requests the app-server is waiting on; called for every
+/// `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    if let Ok(mut pending) = state.pending_approvals.lock() {
+        pending.observe(workspace_id, message);
+    };
+    if let Ok(mut inbox) = state.approval_inbox.lock() {
+        inbox.observe(workspace_id, message, chrono::Utc::now().timestamp_millis());
+    };
+}
+
+/// Forgets an approval request once it has been answered, returning how
+/// long it waited.
+pub(crate) fn resolve_approval(
+    state: &AppState,
+    workspace_id: &str,
+    request_id: &RequestId,
+) -> Option<i64> {
+    if let Ok(mut pending) = state.pending_approvals.lock() {
+        pending.resolve(workspace_id, request_id);
+    }
+    let received_at = match state.approval_inbox.lock() {
+        Ok(mut inbox) => inbox.resolve(workspace_id, request_id),
+        Err(_) => None,
+    }?;
+    Some(chrono::Utc::now().timestamp_millis() - received_at)
+}
+
+/// Refuses to `action` a thread that still has unapplied staged patches or
+/// unanswered approvals, returning a `needsAttention` error that lists them.
+/// `force` skips the check.
+pub(crate) async fn ensure_no_pending_work(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+    action: &str,
+    force: bool,
+) -> Result<(), String> {
+    if force {
+        return Ok(());
+    }
+    let patches = {
+        let _guard = state.staged_patch_writes.lock().await;
+        let cipher = state.data_cipher().await?;
+        patch_staging::read_patches(&state.data_dir, &cipher, workspace_id)?
+    };
+    let approvals = state
+        .pending_approvals
+        .lock()
+        .map(|pending| pending.pending(workspace_id, thread_id))
+        .unwrap_or_default();
+    let attention = ThreadAttention::new(workspace_id, thread_id, &patches, approvals);
+    if attention.is_clear() {
+        Ok(())
+    } else {
+        Err(attention.into_error(action))
+    }
+}
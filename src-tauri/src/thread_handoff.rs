@@ -0,0 +1,163 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde_json::json;
+use tauri::{AppHandle, State};
+
+use crate::access_control;
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::rpc_response::{response_error, response_result};
+use crate::backend::thread_handoff::{self, ThreadHandoff, MAX_TRANSCRIPT_CHARS};
+use crate::backend::thread_journal::history_items;
+use crate::backend::thread_links::ThreadLinkKind;
+use crate::backend::turn_artifacts;
+use crate::background_turn;
+use crate::codex::{self, SendOptions};
+use crate::remote_backend;
+use crate::state::AppState;
+use crate::thread_links;
+use crate::turn_artifacts::prepare_thread_dir;
+
+const SUMMARY_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Copies resolved artifacts to the same relative paths under `target_dir`.
+fn copy_artifacts(
+    artifacts: &[(String, PathBuf)],
+    target_dir: &Path,
+) -> Result<Vec<String>, String> {
+    let mut copied = Vec::with_capacity(artifacts.len());
+    for (relative, source) in artifacts {
+        let target = target_dir.join(relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| format!("Failed to copy artifact {relative}: {err}"))?;
+        }
+        std::fs::copy(source, &target)
+            .map_err(|err| format!("Failed to copy artifact {relative}: {err}"))?;
+        copied.push(relative.clone());
+    }
+    Ok(copied)
+}
+
+/// Moves a thread's work to another workspace: summarizes the thread in a
+/// background turn, starts a thread in `target_workspace_id` seeded with the
+/// summary and copies of the selected artifacts, and links the new thread
+/// to the source as a `followUpOf`.
+#[tauri::command]
+pub(crate) async fn handoff_thread(
+    workspace_id: String,
+    thread_id: String,
+    target_workspace_id: String,
+    artifacts: Option<Vec<String>>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ThreadHandoff, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Thread handoff is only available for local workspaces.".to_string());
+    }
+    if workspace_id == target_workspace_id {
+        return Err("Pick another workspace to hand the thread off to.".to_string());
+    }
+    access_control::ensure_workspace_visible(&*state, &workspace_id).await?;
+    access_control::ensure_workspace_visible(&*state, &target_workspace_id).await?;
+    let session = {
+        let sessions = state.sessions.lock().await;
+        if !sessions.contains_key(&target_workspace_id) {
+            return Err(localize(MessageCode::WorkspaceNotConnected));
+        }
+        sessions
+            .get(&workspace_id)
+            .cloned()
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotConnected))?
+    };
+    let source_dir =
+        turn_artifacts::thread_artifacts_dir(&state.data_dir, &workspace_id, &thread_id);
+    let artifacts = artifacts
+        .unwrap_or_default()
+        .into_iter()
+        .map(|relative| {
+            let source = turn_artifacts::resolve_artifact(&source_dir, &relative)?;
+            Ok((relative, source))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let history = session
+        .send_request("thread/resume", json!({ "threadId": thread_id }))
+        .await?;
+    if let Some(error) = response_error(&history) {
+        return Err(error);
+    }
+    let transcript = thread_handoff::transcript(&history_items(&history), MAX_TRANSCRIPT_CHARS);
+    if transcript.trim().is_empty() {
+        return Err("The thread has nothing to hand off yet.".to_string());
+    }
+    let summary = background_turn::run_once(
+        &state,
+        &session,
+        &thread_handoff::summary_prompt(&transcript),
+        SUMMARY_TIMEOUT,
+    )
+    .await?;
+
+    let response =
+        codex::start_thread(target_workspace_id.clone(), state.clone(), app.clone()).await?;
+    if let Some(error) = response_error(&response) {
+        return Err(error);
+    }
+    let new_thread_id = response_result(&response)
+        .get("thread")
+        .and_then(|thread| thread.get("id"))
+        .and_then(|value| value.as_str())
+        .ok_or("thread/start did not return a thread id")?
+        .to_string();
+
+    let (copied, artifacts_dir) = if artifacts.is_empty() {
+        (Vec::new(), None)
+    } else {
+        let target_dir = prepare_thread_dir(&state, &target_workspace_id, &new_thread_id)
+            .ok_or("Failed to create the new thread's artifacts folder.")?;
+        let dir = target_dir.clone();
+        let copied = tokio::task::spawn_blocking(move || copy_artifacts(&artifacts, &dir))
+            .await
+            .map_err(|err| err.to_string())??;
+        (copied, Some(target_dir))
+    };
+
+    let source_name = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .map(|entry| entry.name.clone())
+        .unwrap_or_else(|| workspace_id.clone());
+    let link = thread_links::new_link(
+        target_workspace_id.clone(),
+        new_thread_id.clone(),
+        ThreadLinkKind::FollowUpOf,
+        Some(workspace_id),
+        thread_id,
+    );
+    thread_links::save_link(&state, link.clone()).await?;
+
+    let seed =
+        thread_handoff::seed_message(&source_name, &summary, &copied, artifacts_dir.as_deref());
+    let response = codex::send_message(
+        target_workspace_id.clone(),
+        new_thread_id.clone(),
+        seed,
+        SendOptions::default(),
+        state.clone(),
+        app,
+    )
+    .await?;
+    if let Some(error) = response_error(&response) {
+        return Err(error);
+    }
+    Ok(ThreadHandoff {
+        workspace_id: target_workspace_id,
+        thread_id: new_thread_id,
+        summary,
+        artifacts: copied,
+        link,
+    })
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::quick_search::thread_candidates;
+use crate::backend::thread_index::IndexedThread;
+use crate::state::AppState;
+use crate::types::WorkspaceEntry;
+
+/// Keeps the thread index current between listings; called for every
+/// `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    if let Ok(mut index) = state.thread_index.lock() {
+        index.observe(workspace_id, message, chrono::Utc::now().timestamp_millis());
+    };
+}
+
+/// Lists the threads of `workspaces` that have a session and were not
+/// listed recently, and returns every indexed thread of those workspaces.
+pub(crate) async fn indexed_threads(
+    state: &AppState,
+    workspaces: &[WorkspaceEntry],
+    sessions: &HashMap<String, Arc<WorkspaceSession>>,
+) -> Vec<(String, IndexedThread)> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut threads = Vec::new();
+    for entry in workspaces {
+        let Some(session) = sessions.get(&entry.id) else {
+            continue;
+        };
+        let stale = state
+            .thread_index
+            .lock()
+            .map(|index| index.needs_refresh(&entry.id, now))
+            .unwrap_or(true);
+        if stale {
+            let listed = thread_candidates(session, entry)
+                .await
+                .into_iter()
+                .map(IndexedThread::from_candidate)
+                .collect();
+            if let Ok(mut index) = state.thread_index.lock() {
+                index.replace(&entry.id, listed, now);
+            }
+        }
+        if let Ok(index) = state.thread_index.lock() {
+            threads.extend(
+                index
+                    .threads(&entry.id)
+                    .into_iter()
+                    .map(|thread| (entry.id.clone(), thread)),
+            );
+        }
+    }
+    threads
+}
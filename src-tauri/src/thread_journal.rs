@@ -0,0 +1,99 @@
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::backend::events::AppServerEvent;
+use crate::backend::thread_journal::{self, ThreadBackfill};
+use crate::backend::turn_summary::params_thread_id;
+use crate::state::AppState;
+use crate::workspace_windows::emit_app_server_event;
+
+const BACKFILL_METHOD: &str = "thread/backfill";
+
+async fn record_items(state: &AppState, workspace_id: &str, thread_id: &str, item_ids: &[String]) {
+    let _guard = state.thread_journal_writes.lock().await;
+    let result = match state.data_cipher().await {
+        Ok(cipher) => thread_journal::record_items(
+            &state.data_dir,
+            &cipher,
+            workspace_id,
+            thread_id,
+            item_ids,
+        ),
+        Err(err) => Err(err),
+    };
+    if let Err(err) = result {
+        eprintln!("Failed to write thread journal: {err}");
+    }
+}
+
+/// Journals the id of every item the app receives; called for every
+/// `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    if message.get("method").and_then(|value| value.as_str()) != Some("item/completed") {
+        return;
+    }
+    let Some(params) = message.get("params") else {
+        return;
+    };
+    let (Some(thread_id), Some(item_id)) = (
+        params_thread_id(params),
+        params.get("item").and_then(thread_journal::item_id),
+    ) else {
+        return;
+    };
+    let app = app.clone();
+    let workspace_id = workspace_id.to_string();
+    let item_id = item_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        record_items(&state, &workspace_id, &thread_id, &[item_id]).await;
+    });
+}
+
+/// Compares a `thread/resume` response with the thread's journal and emits
+/// the items the app never saw, e.g. turns run from the terminal CLI, as a
+/// `thread/backfill` app-server event. A thread without a journal only gets
+/// one started; its whole history is already in the response.
+pub(crate) async fn backfill_resumed_thread(
+    state: &AppState,
+    app: &AppHandle,
+    workspace_id: &str,
+    thread_id: &str,
+    response: &Value,
+) {
+    let history = thread_journal::history_items(response);
+    let seen = match state.data_cipher().await.and_then(|cipher| {
+        thread_journal::read_journal(&state.data_dir, &cipher, workspace_id, thread_id)
+    }) {
+        Ok(seen) => seen,
+        Err(err) => {
+            eprintln!("Failed to read thread journal: {err}");
+            return;
+        }
+    };
+    if let Some(seen) = seen {
+        let items = thread_journal::missing_items(&history, &seen);
+        if !items.is_empty() {
+            let backfill = ThreadBackfill {
+                thread_id: thread_id.to_string(),
+                items,
+            };
+            emit_app_server_event(
+                app,
+                workspace_id,
+                Some(BACKFILL_METHOD),
+                Some(thread_id),
+                AppServerEvent {
+                    workspace_id: workspace_id.to_string(),
+                    message: json!({ "method": BACKFILL_METHOD, "params": backfill }),
+                },
+            );
+        }
+    }
+    let item_ids: Vec<String> = history
+        .iter()
+        .filter_map(|entry| thread_journal::item_id(&entry.item))
+        .map(str::to_string)
+        .collect();
+    record_items(state, workspace_id, thread_id, &item_ids).await;
+}
@@ -0,0 +1,170 @@
+use chrono::Utc;
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::thread_links::{self, ThreadLink, ThreadLinkKind};
+use crate::backend::turn_summary::params_thread_id;
+use crate::state::AppState;
+use crate::workspace_windows::emit_for_workspace;
+
+fn completed_thread(message: &Value) -> Option<String> {
+    if message.get("method").and_then(|value| value.as_str()) != Some("turn/completed") {
+        return None;
+    }
+    let params = message.get("params")?;
+    let status = params
+        .get("turn")
+        .and_then(|turn| turn.get("status"))
+        .or_else(|| {
+            params
+                .get("summary")
+                .and_then(|summary| summary.get("status"))
+        })
+        .and_then(|value| value.as_str());
+    if matches!(status, Some("interrupted" | "failed")) {
+        return None;
+    }
+    params_thread_id(params)
+}
+
+/// Emits `thread-unblocked` for every thread blocked on one whose turn just
+/// completed, so the dependent thread can be resumed; called for every
+/// `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(thread_id) = completed_thread(message) else {
+        return;
+    };
+    let app = app.clone();
+    let workspace_id = workspace_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let result = {
+            let _guard = state.thread_link_writes.lock().await;
+            let path = thread_links::thread_links_path(&state.data_dir);
+            match state.data_cipher().await {
+                Ok(cipher) => {
+                    thread_links::read_thread_links(&path, &cipher).and_then(|mut links| {
+                        let unblocked = thread_links::unblock(
+                            &mut links,
+                            &workspace_id,
+                            &thread_id,
+                            Utc::now().timestamp_millis(),
+                        );
+                        if !unblocked.is_empty() {
+                            thread_links::write_thread_links(&path, &cipher, &links)?;
+                        }
+                        Ok(unblocked)
+                    })
+                }
+                Err(err) => Err(err),
+            }
+        };
+        match result {
+            Ok(unblocked) => {
+                for link in unblocked {
+                    let dependent_workspace_id = link.workspace_id.clone();
+                    emit_for_workspace(&app, "thread-unblocked", &dependent_workspace_id, link);
+                }
+            }
+            Err(err) => eprintln!("Failed to update thread links: {err}"),
+        }
+    });
+}
+
+pub(crate) fn new_link(
+    workspace_id: String,
+    thread_id: String,
+    kind: ThreadLinkKind,
+    target_workspace_id: Option<String>,
+    target_thread_id: String,
+) -> ThreadLink {
+    ThreadLink {
+        target_workspace_id: target_workspace_id.unwrap_or_else(|| workspace_id.clone()),
+        workspace_id,
+        thread_id,
+        kind,
+        target_thread_id,
+        created_at: Utc::now().timestamp_millis(),
+        unblocked_at: None,
+    }
+}
+
+pub(crate) async fn save_link(state: &AppState, link: ThreadLink) -> Result<(), String> {
+    let cipher = state.data_cipher().await?;
+    let _guard = state.thread_link_writes.lock().await;
+    let path = thread_links::thread_links_path(&state.data_dir);
+    let mut links = thread_links::read_thread_links(&path, &cipher)?;
+    thread_links::add_link(&mut links, link)?;
+    thread_links::write_thread_links(&path, &cipher, &links)
+}
+
+/// Links a thread to another one, in the same workspace unless
+/// `target_workspace_id` is given. A thread `blockedOn` another is flagged
+/// with `thread-unblocked` when a turn of the other one completes.
+#[tauri::command]
+pub(crate) async fn link_threads(
+    workspace_id: String,
+    thread_id: String,
+    kind: ThreadLinkKind,
+    target_workspace_id: Option<String>,
+    target_thread_id: String,
+    state: State<'_, AppState>,
+) -> Result<ThreadLink, String> {
+    let link = new_link(
+        workspace_id,
+        thread_id,
+        kind,
+        target_workspace_id,
+        target_thread_id,
+    );
+    save_link(&state, link.clone()).await?;
+    Ok(link)
+}
+
+#[tauri::command]
+pub(crate) async fn unlink_threads(
+    workspace_id: String,
+    thread_id: String,
+    kind: ThreadLinkKind,
+    target_workspace_id: Option<String>,
+    target_thread_id: String,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    let link = new_link(
+        workspace_id,
+        thread_id,
+        kind,
+        target_workspace_id,
+        target_thread_id,
+    );
+    let cipher = state.data_cipher().await?;
+    let _guard = state.thread_link_writes.lock().await;
+    let path = thread_links::thread_links_path(&state.data_dir);
+    let mut links = thread_links::read_thread_links(&path, &cipher)?;
+    let removed = thread_links::remove_link(&mut links, &link);
+    if removed {
+        thread_links::write_thread_links(&path, &cipher, &links)?;
+    }
+    Ok(removed)
+}
+
+/// The thread link graph as a list of edges: every link touching the
+/// thread when `thread_id` is given, otherwise every link from or to the
+/// workspace's threads.
+#[tauri::command]
+pub(crate) async fn get_thread_links(
+    workspace_id: String,
+    thread_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ThreadLink>, String> {
+    let cipher = state.data_cipher().await?;
+    let mut links = thread_links::read_thread_links(
+        &thread_links::thread_links_path(&state.data_dir),
+        &cipher,
+    )?;
+    links.retain(|link| match &thread_id {
+        Some(thread_id) => link.touches(&workspace_id, thread_id),
+        None => link.workspace_id == workspace_id || link.target_workspace_id == workspace_id,
+    });
+    Ok(links)
+}
@@ -0,0 +1,54 @@
+use tauri::State;
+
+use crate::backend::thread_settings::{self, ThreadTurnSettings};
+use crate::state::AppState;
+
+/// Fills the model, effort and access mode a message left out from what
+/// the thread used last, then remembers the result for the next message.
+/// Dry runs force their own access mode, so theirs is not remembered.
+pub(crate) async fn resolve_turn_settings(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+    model: Option<String>,
+    effort: Option<String>,
+    access_mode: Option<String>,
+    dry_run: bool,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let Ok(cipher) = state.data_cipher().await else {
+        return (model, effort, access_mode);
+    };
+    let path = thread_settings::thread_settings_path(&state.data_dir);
+    let _guard = state.thread_settings_writes.lock().await;
+    let update = ThreadTurnSettings {
+        workspace_id: workspace_id.to_string(),
+        thread_id: thread_id.to_string(),
+        model,
+        effort,
+        access_mode: if dry_run { None } else { access_mode.clone() },
+        updated_at: chrono::Utc::now().timestamp_millis(),
+    };
+    let requested_access_mode = access_mode;
+    match thread_settings::remember_thread_settings(&path, &cipher, update.clone()) {
+        Ok(merged) if dry_run => (merged.model, merged.effort, requested_access_mode),
+        Ok(merged) => (merged.model, merged.effort, merged.access_mode),
+        Err(_) => (update.model, update.effort, requested_access_mode),
+    }
+}
+
+/// Returns the model, effort and access mode a thread last used, so the
+/// composer can restore them when the thread is opened.
+#[tauri::command]
+pub(crate) async fn get_thread_turn_settings(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<ThreadTurnSettings>, String> {
+    let cipher = state.data_cipher().await?;
+    thread_settings::find_thread_settings(
+        &thread_settings::thread_settings_path(&state.data_dir),
+        &cipher,
+        &workspace_id,
+        &thread_id,
+    )
+}
@@ -0,0 +1,79 @@
+use chrono::Utc;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::backend::thread_sla::{self, ThreadTimer};
+use crate::clock;
+use crate::state::AppState;
+
+/// Adds each completed turn's duration to its thread's stopwatch and emits
+/// `thread-sla-breached` when a thread goes over its SLA; called for every
+/// `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let now = clock::now(app, &state);
+    let timing = match state.thread_stopwatch.lock() {
+        Ok(mut stopwatch) => stopwatch.observe(workspace_id, message, now),
+        Err(_) => None,
+    };
+    let Some(timing) = timing else {
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let _guard = state.thread_timer_writes.lock().await;
+        let result = match state.data_cipher().await {
+            Ok(cipher) => thread_sla::record_turn(
+                &thread_sla::thread_timers_path(&state.data_dir),
+                &cipher,
+                &timing,
+                Utc::now().timestamp_millis(),
+            ),
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(Some(breach)) => {
+                let _ = app.emit("thread-sla-breached", breach);
+            }
+            Ok(None) => {}
+            Err(err) => eprintln!("Failed to record thread time: {err}"),
+        }
+    });
+}
+
+/// Sets the agent time, in minutes, a thread may use before an alert is
+/// raised. `None` or `0` removes the SLA.
+#[tauri::command]
+pub(crate) async fn set_thread_sla(
+    workspace_id: String,
+    thread_id: String,
+    sla_minutes: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<ThreadTimer, String> {
+    let cipher = state.data_cipher().await?;
+    let _guard = state.thread_timer_writes.lock().await;
+    thread_sla::set_sla(
+        &thread_sla::thread_timers_path(&state.data_dir),
+        &cipher,
+        &workspace_id,
+        &thread_id,
+        sla_minutes,
+        Utc::now().timestamp_millis(),
+    )
+}
+
+/// Agent time and SLA of every timed thread in a workspace.
+#[tauri::command]
+pub(crate) async fn list_thread_timers(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ThreadTimer>, String> {
+    let cipher = state.data_cipher().await?;
+    let mut timers =
+        thread_sla::read_thread_timers(&thread_sla::thread_timers_path(&state.data_dir), &cipher)?;
+    timers.retain(|timer| timer.workspace_id == workspace_id);
+    Ok(timers)
+}
@@ -0,0 +1,232 @@
+use std::collections::{HashMap, HashSet};
+
+use tauri::State;
+use uuid::Uuid;
+
+use crate::backend::secure_storage::DataCipher;
+use crate::backend::thread_archive;
+use crate::backend::thread_index::{self, ThreadQueryFilters, ThreadSort, ThreadWindow};
+use crate::backend::thread_tags::{
+    self, SavedThreadFilter, ThreadRecord, ThreadStatus, ThreadTags,
+};
+use crate::remote_backend;
+use crate::state::AppState;
+
+#[tauri::command]
+pub(crate) async fn set_thread_tags(
+    workspace_id: String,
+    thread_id: String,
+    tags: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<ThreadTags, String> {
+    let cipher = state.data_cipher().await?;
+    let _guard = state.thread_tag_writes.lock().await;
+    thread_tags::set_thread_tags(
+        &thread_tags::thread_tags_path(&state.data_dir),
+        &cipher,
+        &workspace_id,
+        &thread_id,
+        &tags,
+        chrono::Utc::now().timestamp_millis(),
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn list_thread_tags(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ThreadTags>, String> {
+    let cipher = state.data_cipher().await?;
+    let mut entries =
+        thread_tags::read_thread_tags(&thread_tags::thread_tags_path(&state.data_dir), &cipher)?;
+    if let Some(workspace_id) = workspace_id {
+        entries.retain(|entry| entry.workspace_id == workspace_id);
+    }
+    Ok(entries)
+}
+
+#[tauri::command]
+pub(crate) async fn list_thread_filters(
+    state: State<'_, AppState>,
+) -> Result<Vec<SavedThreadFilter>, String> {
+    let cipher = state.data_cipher().await?;
+    thread_tags::read_filters(&thread_tags::thread_filters_path(&state.data_dir), &cipher)
+}
+
+/// Saves a smart filter; filters without an id are created.
+#[tauri::command]
+pub(crate) async fn save_thread_filter(
+    mut filter: SavedThreadFilter,
+    state: State<'_, AppState>,
+) -> Result<SavedThreadFilter, String> {
+    if filter.id.trim().is_empty() {
+        filter.id = Uuid::new_v4().to_string();
+    }
+    let cipher = state.data_cipher().await?;
+    let _guard = state.thread_tag_writes.lock().await;
+    thread_tags::save_filter(
+        &thread_tags::thread_filters_path(&state.data_dir),
+        &cipher,
+        filter,
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn delete_thread_filter(
+    filter_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let cipher = state.data_cipher().await?;
+    let _guard = state.thread_tag_writes.lock().await;
+    thread_tags::delete_filter(
+        &thread_tags::thread_filters_path(&state.data_dir),
+        &cipher,
+        &filter_id,
+    )
+}
+
+/// Threads of connected, visible workspaces (all of them when
+/// `workspace_ids` is empty) from the thread index, with recently archived
+/// threads, their tags and status. Also returns the workspaces covered.
+async fn collect_threads(
+    state: &AppState,
+    cipher: &DataCipher,
+    workspace_ids: &[String],
+) -> Result<(Vec<ThreadRecord>, Vec<String>), String> {
+    let tags: HashMap<(String, String), Vec<String>> =
+        thread_tags::read_thread_tags(&thread_tags::thread_tags_path(&state.data_dir), cipher)?
+            .into_iter()
+            .map(|entry| ((entry.workspace_id, entry.thread_id), entry.tags))
+            .collect();
+    let running: HashSet<(String, String)> = state
+        .turn_limiter
+        .status()
+        .running
+        .into_iter()
+        .map(|slot| (slot.workspace_id, slot.thread_id))
+        .collect();
+
+    let workspaces: Vec<_> = {
+        let workspaces = state.workspaces.lock().await;
+        let access = state.access_control.lock().await;
+        workspaces
+            .values()
+            .filter(|entry| access.is_visible(entry))
+            .filter(|entry| workspace_ids.is_empty() || workspace_ids.contains(&entry.id))
+            .cloned()
+            .collect()
+    };
+    let sessions = state.sessions.lock().await.clone();
+    let mut threads = Vec::new();
+    for (workspace_id, thread) in
+        crate::thread_index::indexed_threads(state, &workspaces, &sessions).await
+    {
+        let key = (workspace_id.clone(), thread.thread_id.clone());
+        threads.push(ThreadRecord {
+            status: if running.contains(&key) {
+                ThreadStatus::Running
+            } else {
+                ThreadStatus::Idle
+            },
+            tags: tags.get(&key).cloned().unwrap_or_default(),
+            workspace_id,
+            thread_id: thread.thread_id,
+            title: thread.title,
+            updated_at: thread.updated_at,
+        });
+    }
+    let visible: HashSet<&str> = workspaces.iter().map(|entry| entry.id.as_str()).collect();
+    let archived = thread_archive::list_recently_archived(
+        &thread_archive::archive_path(&state.data_dir),
+        cipher,
+        None,
+    )?;
+    for entry in archived
+        .into_iter()
+        .filter(|entry| visible.contains(entry.workspace_id.as_str()))
+    {
+        let key = (entry.workspace_id.clone(), entry.thread_id.clone());
+        let title = entry
+            .thread
+            .as_ref()
+            .and_then(|thread| thread.get("preview"))
+            .and_then(|value| value.as_str())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| entry.thread_id.clone());
+        threads.push(ThreadRecord {
+            status: ThreadStatus::Archived,
+            tags: tags.get(&key).cloned().unwrap_or_default(),
+            workspace_id: entry.workspace_id,
+            thread_id: entry.thread_id,
+            title,
+            updated_at: Some(entry.archived_at),
+        });
+    }
+    let covered = workspaces.into_iter().map(|entry| entry.id).collect();
+    Ok((threads, covered))
+}
+
+/// Evaluates a saved filter over the threads of connected workspaces and
+/// recently archived threads, newest first.
+#[tauri::command]
+pub(crate) async fn query_threads(
+    filter_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ThreadRecord>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Thread filters are not available in remote mode yet.".to_string());
+    }
+    let cipher = state.data_cipher().await?;
+    let filter =
+        thread_tags::read_filters(&thread_tags::thread_filters_path(&state.data_dir), &cipher)?
+            .into_iter()
+            .find(|filter| filter.id == filter_id)
+            .ok_or("Thread filter not found.")?;
+    let (mut threads, _) = collect_threads(&state, &cipher, &filter.workspace_ids).await?;
+    let now = chrono::Utc::now().timestamp_millis();
+    threads.retain(|thread| thread_tags::matches(&filter, thread, now));
+    threads.sort_by_key(|thread| std::cmp::Reverse(thread.updated_at));
+    Ok(threads)
+}
+
+/// One window of the threads matching `filters`, with the total count, for
+/// virtual scrolling. Served from the thread index, which lists a
+/// workspace's threads again only once it is stale.
+#[tauri::command]
+pub(crate) async fn query_threads_window(
+    offset: Option<usize>,
+    limit: Option<usize>,
+    sort: Option<ThreadSort>,
+    filters: Option<ThreadQueryFilters>,
+    state: State<'_, AppState>,
+) -> Result<ThreadWindow, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Thread queries are not available in remote mode yet.".to_string());
+    }
+    let filters = filters.unwrap_or_default();
+    let cipher = state.data_cipher().await?;
+    let (threads, covered) = collect_threads(&state, &cipher, &filters.workspace_ids).await?;
+    let indexed = threads.len();
+    let offset = offset.unwrap_or(0);
+    let (total, threads) = thread_index::window(
+        threads,
+        &filters,
+        sort.unwrap_or_default(),
+        offset,
+        limit.unwrap_or(thread_index::DEFAULT_WINDOW_LIMIT),
+        chrono::Utc::now().timestamp_millis(),
+    );
+    let refreshed_at = state
+        .thread_index
+        .lock()
+        .ok()
+        .and_then(|index| index.oldest_refresh(&covered));
+    Ok(ThreadWindow {
+        offset,
+        total,
+        indexed,
+        refreshed_at,
+        threads,
+    })
+}
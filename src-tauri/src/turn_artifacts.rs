@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use tauri::State;
+
+use crate::backend::turn_artifacts::{self, TurnArtifact};
+use crate::state::AppState;
+
+/// Creates the thread's artifacts directory, which turns get as an extra
+/// writable root. Only turns started from this machine have one.
+pub(crate) fn prepare_thread_dir(
+    state: &AppState,
+    workspace_id: &str,
+    thread_id: &str,
+) -> Option<PathBuf> {
+    let dir = turn_artifacts::thread_artifacts_dir(&state.data_dir, workspace_id, thread_id);
+    match std::fs::create_dir_all(&dir) {
+        Ok(()) => Some(dir),
+        Err(err) => {
+            eprintln!("Failed to create artifacts directory: {err}");
+            None
+        }
+    }
+}
+
+/// Files the agent generated outside the repository for a thread, newest
+/// first.
+#[tauri::command]
+pub(crate) async fn list_turn_artifacts(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<TurnArtifact>, String> {
+    let dir = turn_artifacts::thread_artifacts_dir(&state.data_dir, &workspace_id, &thread_id);
+    tokio::task::spawn_blocking(move || turn_artifacts::list_artifacts(&dir))
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Opens an artifact, by its path from `list_turn_artifacts`, in the
+/// system's default app.
+#[tauri::command]
+pub(crate) async fn open_artifact(
+    workspace_id: String,
+    thread_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let dir = turn_artifacts::thread_artifacts_dir(&state.data_dir, &workspace_id, &thread_id);
+    let artifact = turn_artifacts::resolve_artifact(&dir, &path)?;
+    tauri_plugin_opener::open_path(artifact, None::<&str>)
+        .map_err(|err| format!("Failed to open artifact: {err}"))
+}
@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use tauri::State;
+use tokio::process::Command;
+
+use crate::backend::turn_labels::{self, TurnLabels};
+use crate::backend::turn_manifest;
+use crate::git::github_repo_from_path;
+use crate::git_utils::resolve_git_root;
+use crate::state::AppState;
+
+#[tauri::command]
+pub(crate) async fn set_turn_labels(
+    workspace_id: String,
+    thread_id: String,
+    turn_id: String,
+    labels: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<TurnLabels, String> {
+    let cipher = state.data_cipher().await?;
+    let _guard = state.turn_label_writes.lock().await;
+    turn_labels::set_turn_labels(
+        &turn_labels::turn_labels_path(&state.data_dir),
+        &cipher,
+        &workspace_id,
+        &thread_id,
+        &turn_id,
+        &labels,
+        chrono::Utc::now().timestamp_millis(),
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn list_turn_labels(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<TurnLabels>, String> {
+    let cipher = state.data_cipher().await?;
+    let mut entries =
+        turn_labels::read_turn_labels(&turn_labels::turn_labels_path(&state.data_dir), &cipher)?;
+    if let Some(workspace_id) = workspace_id {
+        entries.retain(|entry| entry.workspace_id == workspace_id);
+    }
+    Ok(entries)
+}
+
+/// Ids of the turns labeled `label`, for filtering analytics.
+pub(crate) async fn turns_with_label(
+    state: &AppState,
+    workspace_id: Option<&str>,
+    label: &str,
+) -> Result<HashSet<String>, String> {
+    let cipher = state.data_cipher().await?;
+    let entries =
+        turn_labels::read_turn_labels(&turn_labels::turn_labels_path(&state.data_dir), &cipher)?;
+    Ok(turn_labels::turns_with_label(&entries, workspace_id, label))
+}
+
+/// Labels of the workspace's turns whose manifest matches `started_from`,
+/// e.g. turns started at a commit or on a branch.
+async fn labels_of_turns(
+    state: &AppState,
+    workspace_id: &str,
+    started_from: impl Fn(&turn_manifest::TurnManifest) -> bool,
+) -> Vec<String> {
+    let Ok(cipher) = state.data_cipher().await else {
+        return Vec::new();
+    };
+    let (Ok(entries), Ok(manifests)) = (
+        turn_labels::read_turn_labels(&turn_labels::turn_labels_path(&state.data_dir), &cipher),
+        turn_manifest::read_manifests(&state.data_dir, &cipher, workspace_id),
+    ) else {
+        return Vec::new();
+    };
+    turn_labels::labels_for_manifests(
+        &entries,
+        manifests.iter().filter(|manifest| started_from(manifest)),
+    )
+}
+
+/// Adds a `Turn-Labels:` trailer for the turns started on top of `head`,
+/// whose changes the next commit contains.
+pub(crate) async fn with_commit_labels(
+    state: &AppState,
+    workspace_id: &str,
+    head: Option<&str>,
+    message: String,
+) -> String {
+    let Some(head) = head else {
+        return message;
+    };
+    let labels = labels_of_turns(state, workspace_id, |manifest| {
+        manifest.git_sha.as_deref() == Some(head)
+    })
+    .await;
+    turn_labels::with_label_trailer(&message, &labels)
+}
+
+async fn run_gh(repo_root: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("gh")
+        .args(args)
+        .current_dir(repo_root)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr.trim();
+        if detail.is_empty() {
+            return Err("GitHub CLI command failed.".to_string());
+        }
+        return Err(detail.to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Adds the labels of the turns started on a pull request's head branch to
+/// the pull request, creating labels the repository does not have yet.
+/// Returns the labels applied.
+#[tauri::command]
+pub(crate) async fn apply_turn_labels_to_pull_request(
+    workspace_id: String,
+    pr_number: u64,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let entry = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or("workspace not found")?;
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let pr_number = pr_number.to_string();
+    let branch = run_gh(
+        &repo_root,
+        &[
+            "pr",
+            "view",
+            &pr_number,
+            "--repo",
+            &repo_name,
+            "--json",
+            "headRefName",
+            "--jq",
+            ".headRefName",
+        ],
+    )
+    .await?;
+    let labels = labels_of_turns(&state, &workspace_id, |manifest| {
+        manifest.git_branch.as_deref() == Some(branch.as_str())
+    })
+    .await;
+    if labels.is_empty() {
+        return Ok(labels);
+    }
+    let existing = run_gh(
+        &repo_root,
+        &[
+            "label", "list", "--repo", &repo_name, "--limit", "1000", "--json", "name", "--jq",
+            ".[].name",
+        ],
+    )
+    .await?
+    .lines()
+    .map(|name| name.trim().to_lowercase())
+    .collect::<HashSet<_>>();
+    for label in labels.iter().filter(|label| !existing.contains(*label)) {
+        run_gh(
+            &repo_root,
+            &["label", "create", label, "--repo", &repo_name],
+        )
+        .await?;
+    }
+    run_gh(
+        &repo_root,
+        &[
+            "pr",
+            "edit",
+            &pr_number,
+            "--repo",
+            &repo_name,
+            "--add-label",
+            &labels.join(","),
+        ],
+    )
+    .await?;
+    Ok(labels)
+}
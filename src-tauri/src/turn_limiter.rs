@@ -0,0 +1,29 @@
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::turn_limiter::TurnLimiterStatus;
+use crate::backend::turn_summary::params_thread_id;
+use crate::state::AppState;
+
+/// Gives back the concurrent-turn slot of a thread when its turn completes;
+/// called for every `app-server-event`, local or forwarded from a remote
+/// daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    if message.get("method").and_then(|value| value.as_str()) != Some("turn/completed") {
+        return;
+    }
+    let Some(thread_id) = message.get("params").and_then(params_thread_id) else {
+        return;
+    };
+    if let Some(state) = app.try_state::<AppState>() {
+        state.turn_limiter.finish(workspace_id, &thread_id);
+    }
+}
+
+/// Lists running and waiting turns under the `maxConcurrentTurns` cap.
+#[tauri::command]
+pub(crate) async fn get_turn_limiter_status(
+    state: State<'_, AppState>,
+) -> Result<TurnLimiterStatus, String> {
+    Ok(state.turn_limiter.status())
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use git2::{Repository, StatusOptions};
+use serde_json::{json, Value};
+use tauri::State;
+use tokio::task;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::turn_manifest::{self, hash_file, DirtyFile, TurnManifest};
+use crate::git_utils::resolve_git_root;
+use crate::state::AppState;
+use crate::types::WorkspaceEntry;
+
+/// Bounds the dirty file list of very messy working trees.
+const MAX_DIRTY_FILES: usize = 2_000;
+
+/// Git state of a workspace right before a turn starts.
+#[derive(Debug, Default)]
+pub(crate) struct WorkspaceSnapshot {
+    git_sha: Option<String>,
+    git_branch: Option<String>,
+    dirty_files: Vec<DirtyFile>,
+}
+
+/// The turn settings sent with `turn/start`.
+pub(crate) struct TurnSettings {
+    pub(crate) model: Option<String>,
+    pub(crate) effort: Option<String>,
+    pub(crate) access_mode: String,
+    pub(crate) approval_policy: String,
+    pub(crate) sandbox_policy: Value,
+    pub(crate) collaboration_mode: Option<Value>,
+}
+
+fn snapshot_repo(repo_root: &Path) -> WorkspaceSnapshot {
+    let Ok(repo) = Repository::open(repo_root) else {
+        return WorkspaceSnapshot::default();
+    };
+    let head = repo.head().ok();
+    let mut snapshot = WorkspaceSnapshot {
+        git_sha: head
+            .as_ref()
+            .and_then(|head| head.peel_to_commit().ok())
+            .map(|commit| commit.id().to_string()),
+        git_branch: head
+            .as_ref()
+            .filter(|head| head.is_branch())
+            .and_then(|head| head.shorthand())
+            .map(str::to_string),
+        dirty_files: Vec::new(),
+    };
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+    let Ok(statuses) = repo.statuses(Some(&mut options)) else {
+        return snapshot;
+    };
+    for entry in statuses.iter().take(MAX_DIRTY_FILES) {
+        let Some(path) = entry.path() else {
+            continue;
+        };
+        snapshot.dirty_files.push(DirtyFile {
+            path: path.to_string(),
+            sha256: hash_file(&repo_root.join(path)),
+        });
+    }
+    snapshot.dirty_files.sort_by(|a, b| a.path.cmp(&b.path));
+    snapshot
+}
+
+/// Reads the commit and hashes the dirty files of a workspace.
+pub(crate) async fn capture_snapshot(entry: &WorkspaceEntry) -> WorkspaceSnapshot {
+    let Ok(repo_root) = resolve_git_root(entry) else {
+        return WorkspaceSnapshot::default();
+    };
+    task::spawn_blocking(move || snapshot_repo(&repo_root))
+        .await
+        .unwrap_or_default()
+}
+
+/// Stores the manifest of a turn once `turn/start` returned its id.
+pub(crate) async fn record_turn(
+    state: &AppState,
+    session: &WorkspaceSession,
+    thread_id: &str,
+    response: &Value,
+    snapshot: WorkspaceSnapshot,
+    settings: TurnSettings,
+) {
+    let Some(turn_id) = response
+        .get("result")
+        .unwrap_or(response)
+        .get("turn")
+        .and_then(|turn| turn.get("id"))
+        .and_then(|value| value.as_str())
+    else {
+        return;
+    };
+    let mut config_overrides = BTreeMap::new();
+    {
+        let app_settings = state.app_settings.lock().await;
+        config_overrides.insert(
+            "experimentalCollabEnabled".to_string(),
+            json!(app_settings.experimental_collab_enabled),
+        );
+        config_overrides.insert(
+            "experimentalSteerEnabled".to_string(),
+            json!(app_settings.experimental_steer_enabled),
+        );
+        config_overrides.insert(
+            "experimentalUnifiedExecEnabled".to_string(),
+            json!(app_settings.experimental_unified_exec_enabled),
+        );
+    }
+    if let Some(codex_home) = &session.codex_home {
+        config_overrides.insert("codexHome".to_string(), json!(codex_home.to_string_lossy()));
+    }
+    if let Some(codex_bin) = session.codex_bin.as_deref() {
+        config_overrides.insert("codexBin".to_string(), json!(codex_bin));
+    }
+    let manifest = TurnManifest {
+        workspace_id: session.entry.id.clone(),
+        thread_id: thread_id.to_string(),
+        turn_id: turn_id.to_string(),
+        recorded_at: chrono::Utc::now().timestamp_millis(),
+        git_sha: snapshot.git_sha,
+        git_branch: snapshot.git_branch,
+        dirty_files: snapshot.dirty_files,
+        codex_version: session.codex_version.clone(),
+        model: settings.model,
+        effort: settings.effort,
+        access_mode: settings.access_mode,
+        approval_policy: settings.approval_policy,
+        sandbox_policy: settings.sandbox_policy,
+        collaboration_mode: settings.collaboration_mode,
+        config_overrides,
+    };
+    let result = match state.data_cipher().await {
+        Ok(cipher) => turn_manifest::record_manifest(&state.data_dir, &cipher, manifest),
+        Err(err) => Err(err),
+    };
+    if let Err(err) = result {
+        eprintln!("Failed to record turn manifest: {err}");
+    }
+}
+
+/// Manifests recorded for a workspace, oldest first; used by exports.
+pub(crate) async fn workspace_manifests(state: &AppState, workspace_id: &str) -> Vec<TurnManifest> {
+    match state.data_cipher().await {
+        Ok(cipher) => turn_manifest::read_manifests(&state.data_dir, &cipher, workspace_id)
+            .unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Returns the reproducibility manifest recorded when a turn started: the
+/// git commit, hashes of dirty files, codex version, model and settings.
+/// Only turns started from this machine have one.
+#[tauri::command]
+pub(crate) async fn get_turn_manifest(
+    workspace_id: String,
+    turn_id: String,
+    state: State<'_, AppState>,
+) -> Result<TurnManifest, String> {
+    let cipher = state.data_cipher().await?;
+    turn_manifest::find_manifest(&state.data_dir, &cipher, &workspace_id, &turn_id)?
+        .ok_or_else(|| format!("No manifest recorded for turn {turn_id}"))
+}
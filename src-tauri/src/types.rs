@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -175,6 +177,22 @@ pub(crate) struct WorkspaceEntry {
     pub(crate) worktree: Option<WorktreeInfo>,
     #[serde(default)]
     pub(crate) settings: WorkspaceSettings,
+    /// Icon name; unset uses the default derived from the path.
+    #[serde(default)]
+    pub(crate) icon: Option<String>,
+    /// `#rrggbb` color; unset uses the default derived from the path.
+    #[serde(default)]
+    pub(crate) color: Option<String>,
+}
+
+/// Icon and color that tell workspaces apart across windows and
+/// notifications.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub(crate) struct WorkspaceIdentity {
+    #[serde(default)]
+    pub(crate) icon: String,
+    #[serde(default)]
+    pub(crate) color: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -192,6 +210,8 @@ pub(crate) struct WorkspaceInfo {
     pub(crate) worktree: Option<WorktreeInfo>,
     #[serde(default)]
     pub(crate) settings: WorkspaceSettings,
+    #[serde(flatten)]
+    pub(crate) identity: WorkspaceIdentity,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -238,6 +258,132 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) group_id: Option<String>,
     #[serde(default, rename = "gitRoot")]
     pub(crate) git_root: Option<String>,
+    #[serde(default)]
+    pub(crate) restricted: bool,
+    #[serde(default, rename = "issueTracker")]
+    pub(crate) issue_tracker: Option<IssueTrackerConfig>,
+    #[serde(default, rename = "stderrFilter")]
+    pub(crate) stderr_filter: Option<StderrFilterConfig>,
+    #[serde(default)]
+    pub(crate) verification: Option<VerificationConfig>,
+    /// Deliver approval notifications even while the OS is in do-not-disturb.
+    #[serde(default, rename = "approvalsBreakThroughDnd")]
+    pub(crate) approvals_break_through_dnd: bool,
+    #[serde(default, rename = "autoBranch")]
+    pub(crate) auto_branch: Option<AutoBranchConfig>,
+    /// Overrides the app's reasoning summary visibility for this workspace.
+    #[serde(default, rename = "showReasoningSummaries")]
+    pub(crate) show_reasoning_summaries: Option<bool>,
+    /// Set on throwaway workspaces made by `spawn_scratch_workspace`.
+    #[serde(default)]
+    pub(crate) scratch: Option<ScratchOrigin>,
+    /// Priority and limits of the app-server, applied when it starts.
+    #[serde(default, rename = "resourceLimits")]
+    pub(crate) resource_limits: Option<ResourceLimits>,
+}
+
+/// Keeps heavy agent sessions from starving the rest of the machine. The
+/// app-server's own children, the agent's commands, inherit the limits.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ResourceLimits {
+    /// Niceness from 0 to 19; on Windows, below-normal or idle priority.
+    #[serde(default)]
+    pub(crate) nice: Option<i32>,
+    /// CPUs the app-server may run on; Linux only.
+    #[serde(default)]
+    pub(crate) cpu_affinity: Option<Vec<usize>>,
+    /// Memory cap in MB; Unix only.
+    #[serde(default)]
+    pub(crate) memory_limit_mb: Option<u64>,
+}
+
+/// Where a scratch workspace came from. Its turns always run under the
+/// strictest sandbox, and its changes reach the source workspace only
+/// through the staging area.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScratchOrigin {
+    /// `None` for an empty scratch directory.
+    pub(crate) source_workspace_id: Option<String>,
+    pub(crate) created_at: i64,
+}
+
+/// Moves a thread onto its own branch before its first write-enabled turn
+/// when the workspace is on a clean `main` or `master`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AutoBranchConfig {
+    #[serde(default = "default_auto_branch_prefix")]
+    pub(crate) prefix: String,
+}
+
+fn default_auto_branch_prefix() -> String {
+    "codex/".to_string()
+}
+
+/// Command run in the workspace after each write-enabled turn completes,
+/// e.g. `cargo test -q`. With `auto_fix`, a failing run starts a follow-up
+/// turn asking the agent to fix it, up to `max_fix_attempts` times in a row.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VerificationConfig {
+    pub(crate) command: String,
+    #[serde(default)]
+    pub(crate) auto_fix: bool,
+    #[serde(default = "default_max_fix_attempts")]
+    pub(crate) max_fix_attempts: u32,
+    #[serde(default = "default_verification_timeout_secs")]
+    pub(crate) timeout_secs: u64,
+}
+
+fn default_max_fix_attempts() -> u32 {
+    2
+}
+
+fn default_verification_timeout_secs() -> u64 {
+    600
+}
+
+/// Filters applied to app-server stderr lines before they become events.
+/// Lines matching `allow` are always forwarded; otherwise lines matching
+/// `deny` or below `min_severity` are suppressed and only counted.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StderrFilterConfig {
+    #[serde(default)]
+    pub(crate) allow: Vec<String>,
+    #[serde(default)]
+    pub(crate) deny: Vec<String>,
+    #[serde(default)]
+    pub(crate) min_severity: StderrSeverity,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum StderrSeverity {
+    #[default]
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+/// Where `list_tracked_issues` pulls issues from. Workspaces without one use
+/// the GitHub repository of their git remote.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum IssueTrackerConfig {
+    Github {
+        #[serde(default)]
+        repo: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Jira {
+        base_url: String,
+        project_key: String,
+        email: String,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -319,6 +465,16 @@ pub(crate) struct AppSettings {
         rename = "cycleWorkspacePrevShortcut"
     )]
     pub(crate) cycle_workspace_prev_shortcut: Option<String>,
+    #[serde(
+        default = "default_accept_approval_shortcut",
+        rename = "acceptApprovalShortcut"
+    )]
+    pub(crate) accept_approval_shortcut: Option<String>,
+    #[serde(
+        default = "default_decline_approval_shortcut",
+        rename = "declineApprovalShortcut"
+    )]
+    pub(crate) decline_approval_shortcut: Option<String>,
     #[serde(default, rename = "lastComposerModelId")]
     pub(crate) last_composer_model_id: Option<String>,
     #[serde(default, rename = "lastComposerReasoningEffort")]
@@ -363,6 +519,311 @@ pub(crate) struct AppSettings {
     pub(crate) dictation_hold_key: String,
     #[serde(default = "default_workspace_groups", rename = "workspaceGroups")]
     pub(crate) workspace_groups: Vec<WorkspaceGroup>,
+    #[serde(default = "default_archive_retention_days", rename = "archiveRetentionDays")]
+    pub(crate) archive_retention_days: u32,
+    #[serde(default = "default_encrypt_local_data", rename = "encryptLocalData")]
+    pub(crate) encrypt_local_data: bool,
+    #[serde(default = "default_data_retention", rename = "dataRetention")]
+    pub(crate) data_retention: DataRetentionSettings,
+    #[serde(default = "default_update_channel", rename = "updateChannel")]
+    pub(crate) update_channel: UpdateChannel,
+    #[serde(default, rename = "locale")]
+    pub(crate) locale: Option<String>,
+    #[serde(default = "default_webhook_intake", rename = "webhookIntake")]
+    pub(crate) webhook_intake: WebhookIntakeSettings,
+    #[serde(default = "default_semantic_search", rename = "semanticSearch")]
+    pub(crate) semantic_search: SemanticSearchSettings,
+    #[serde(default = "default_approval_delegation", rename = "approvalDelegation")]
+    pub(crate) approval_delegation: ApprovalDelegationSettings,
+    #[serde(default = "default_command_blocklist", rename = "commandBlocklist")]
+    pub(crate) command_blocklist: Vec<String>,
+    #[serde(default = "default_queue_offline_turns", rename = "queueOfflineTurns")]
+    pub(crate) queue_offline_turns: bool,
+    #[serde(default = "default_network", rename = "network")]
+    pub(crate) network: NetworkSettings,
+    #[serde(default, rename = "autoLockMinutes")]
+    pub(crate) auto_lock_minutes: Option<u32>,
+    /// Global cap on concurrently running turns; `None` does not limit them.
+    #[serde(default, rename = "maxConcurrentTurns")]
+    pub(crate) max_concurrent_turns: Option<u32>,
+    /// Cap on running turns per workspace, counting at most one per thread;
+    /// `None` only keeps turns on the same thread from overlapping.
+    #[serde(default, rename = "maxWorkspaceTurns")]
+    pub(crate) max_workspace_turns: Option<u32>,
+    /// Whether a turn that would overlap waits or is rejected.
+    #[serde(default, rename = "turnOverlapMode")]
+    pub(crate) turn_overlap_mode: TurnOverlapMode,
+    /// Share of the rate-limit window kept for interactive turns; scheduled
+    /// and batch turns wait for the reset below it. `0` turns this off.
+    #[serde(default = "default_rate_limit_reserve_percent", rename = "rateLimitReservePercent")]
+    pub(crate) rate_limit_reserve_percent: u32,
+    /// Whether reasoning summary deltas are forwarded to the frontend.
+    /// Overridden per model by `reasoning_summary_models` and per workspace
+    /// by its `show_reasoning_summaries` setting.
+    #[serde(
+        default = "default_show_reasoning_summaries",
+        rename = "showReasoningSummaries"
+    )]
+    pub(crate) show_reasoning_summaries: bool,
+    #[serde(
+        default = "default_reasoning_summary_models",
+        rename = "reasoningSummaryModels"
+    )]
+    pub(crate) reasoning_summary_models: BTreeMap<String, bool>,
+    /// Editor id used by `open_in_editor`; detected from `PATH` when unset.
+    #[serde(default, rename = "preferredEditor")]
+    pub(crate) preferred_editor: Option<String>,
+    #[serde(default, rename = "editorTemplates")]
+    pub(crate) editor_templates: Vec<EditorTemplate>,
+    #[serde(default, rename = "teamActivity")]
+    pub(crate) team_activity: TeamActivitySettings,
+    #[serde(default)]
+    pub(crate) telemetry: TelemetrySettings,
+}
+
+/// Command line that opens a file in an editor, e.g.
+/// `code -g {path}:{line}`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EditorTemplate {
+    pub(crate) id: String,
+    pub(crate) label: String,
+    pub(crate) command: String,
+}
+
+/// Retention window in days per local data class; `None` keeps data forever.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DataRetentionSettings {
+    #[serde(default = "default_journals_retention_days")]
+    pub(crate) journals_days: Option<u32>,
+    #[serde(default = "default_analytics_retention_days")]
+    pub(crate) analytics_days: Option<u32>,
+    #[serde(default = "default_drafts_retention_days")]
+    pub(crate) drafts_days: Option<u32>,
+    #[serde(default = "default_approval_logs_retention_days")]
+    pub(crate) approval_logs_days: Option<u32>,
+    #[serde(default)]
+    pub(crate) reports_days: Option<u32>,
+    /// Per-thread artifacts directories untouched for this long are removed.
+    #[serde(default = "default_artifacts_retention_days")]
+    pub(crate) artifacts_days: Option<u32>,
+}
+
+fn default_journals_retention_days() -> Option<u32> {
+    Some(30)
+}
+
+fn default_analytics_retention_days() -> Option<u32> {
+    Some(90)
+}
+
+fn default_drafts_retention_days() -> Option<u32> {
+    Some(14)
+}
+
+fn default_approval_logs_retention_days() -> Option<u32> {
+    Some(90)
+}
+
+fn default_artifacts_retention_days() -> Option<u32> {
+    Some(30)
+}
+
+impl Default for DataRetentionSettings {
+    fn default() -> Self {
+        Self {
+            journals_days: default_journals_retention_days(),
+            analytics_days: default_analytics_retention_days(),
+            drafts_days: default_drafts_retention_days(),
+            approval_logs_days: default_approval_logs_retention_days(),
+            reports_days: None,
+            artifacts_days: default_artifacts_retention_days(),
+        }
+    }
+}
+
+/// Local webhook listener that turns tracker issues into queued agent tasks.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookIntakeSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default = "default_webhook_intake_port")]
+    pub(crate) port: u16,
+    /// Shared secret used to verify the HMAC-SHA256 request signature.
+    #[serde(default)]
+    pub(crate) secret: Option<String>,
+    /// Only issues carrying this label are enqueued; empty accepts every issue.
+    #[serde(default = "default_webhook_trigger_label")]
+    pub(crate) trigger_label: String,
+    /// Start a thread right away when the mapped workspace is connected.
+    #[serde(default)]
+    pub(crate) auto_start: bool,
+    #[serde(default)]
+    pub(crate) mappings: Vec<WebhookWorkspaceMapping>,
+}
+
+/// Routes issues of a Linear team or Jira project to a workspace.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookWorkspaceMapping {
+    pub(crate) project_key: String,
+    pub(crate) workspace_id: String,
+}
+
+fn default_webhook_intake_port() -> u16 {
+    4790
+}
+
+fn default_webhook_trigger_label() -> String {
+    "agent".to_string()
+}
+
+impl Default for WebhookIntakeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_webhook_intake_port(),
+            secret: None,
+            trigger_label: default_webhook_trigger_label(),
+            auto_start: false,
+            mappings: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum EmbeddingProvider {
+    /// Hashed n-gram vectors computed on this machine; nothing leaves it.
+    #[default]
+    Local,
+    /// An OpenAI-compatible `/embeddings` endpoint.
+    Api,
+}
+
+/// Embedding index over thread messages and command outputs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SemanticSearchSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) provider: EmbeddingProvider,
+    #[serde(default = "default_embedding_api_url")]
+    pub(crate) api_url: String,
+    #[serde(default = "default_embedding_api_model")]
+    pub(crate) api_model: String,
+    /// Environment variable holding the API key, so it never lands in settings.
+    #[serde(default = "default_embedding_api_key_env")]
+    pub(crate) api_key_env: String,
+}
+
+fn default_embedding_api_url() -> String {
+    "https://api.openai.com/v1/embeddings".to_string()
+}
+
+fn default_embedding_api_model() -> String {
+    "text-embedding-3-small".to_string()
+}
+
+fn default_embedding_api_key_env() -> String {
+    "OPENAI_API_KEY".to_string()
+}
+
+impl Default for SemanticSearchSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: EmbeddingProvider::Local,
+            api_url: default_embedding_api_url(),
+            api_model: default_embedding_api_model(),
+            api_key_env: default_embedding_api_key_env(),
+        }
+    }
+}
+
+/// Forwards pending approvals to a designated approver whose HMAC-signed
+/// decision is then applied on their behalf.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalDelegationSettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// Name recorded in the audit log for signed decisions.
+    #[serde(default)]
+    pub(crate) approver: String,
+    /// Endpoint that receives pending approvals as signed JSON.
+    #[serde(default)]
+    pub(crate) forward_url: Option<String>,
+    /// Shared secret for signing forwarded requests and verifying decisions.
+    #[serde(default)]
+    pub(crate) secret: Option<String>,
+    /// Reject local answers to delegated approvals.
+    #[serde(default)]
+    pub(crate) require_signoff: bool,
+    /// Bearer token for the `/approvals` inbox served on the webhook intake
+    /// port; the inbox is off while unset.
+    #[serde(default)]
+    pub(crate) inbox_token: Option<String>,
+}
+
+/// Opt-in sync of turn statuses, and optionally code-free summaries, with a
+/// self-hosted team endpoint that also serves teammates' activity.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TeamActivitySettings {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// Base URL of the team endpoint; activity is posted to and read from
+    /// `{serverUrl}/activity`.
+    #[serde(default)]
+    pub(crate) server_url: Option<String>,
+    /// Bearer token sent to the endpoint.
+    #[serde(default)]
+    pub(crate) token: Option<String>,
+    /// Name shown to teammates; empty publishes as "anonymous".
+    #[serde(default)]
+    pub(crate) member_name: String,
+    /// Publish an excerpt of the agent's last message, with code removed.
+    #[serde(default)]
+    pub(crate) share_summaries: bool,
+    /// Publish workspace names instead of pseudonyms.
+    #[serde(default)]
+    pub(crate) share_workspace_names: bool,
+}
+
+/// Feature usage counters, off unless the user opts in. Counts stay on this
+/// machine unless sharing is also enabled.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TelemetrySettings {
+    /// Count feature usage locally.
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// Upload anonymized daily aggregates to `endpoint`.
+    #[serde(default)]
+    pub(crate) share_aggregates: bool,
+    #[serde(default)]
+    pub(crate) endpoint: Option<String>,
+}
+
+/// Proxy and CA bundle passed to Codex processes, for networks that require
+/// a proxy or intercept TLS.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NetworkSettings {
+    #[serde(default)]
+    pub(crate) http_proxy: Option<String>,
+    #[serde(default)]
+    pub(crate) https_proxy: Option<String>,
+    /// Comma-separated hosts that bypass the proxy.
+    #[serde(default)]
+    pub(crate) no_proxy: Option<String>,
+    /// PEM bundle of additional trusted certificates.
+    #[serde(default)]
+    pub(crate) ca_bundle_path: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -378,6 +839,26 @@ impl Default for BackendMode {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+/// What happens to a turn sent while its thread, or its workspace at
+/// `max_workspace_turns`, already has one running.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum TurnOverlapMode {
+    /// Waits until the running turn completes.
+    #[default]
+    Queue,
+    /// Fails with a `turnActive` error naming the running turn.
+    Reject,
+}
+
 fn default_access_mode() -> String {
     "current".to_string()
 }
@@ -450,6 +931,14 @@ fn default_cycle_workspace_prev_shortcut() -> Option<String> {
     Some("cmd+shift+up".to_string())
 }
 
+fn default_accept_approval_shortcut() -> Option<String> {
+    Some("cmd+shift+y".to_string())
+}
+
+fn default_decline_approval_shortcut() -> Option<String> {
+    Some("cmd+shift+x".to_string())
+}
+
 fn default_notification_sounds_enabled() -> bool {
     true
 }
@@ -482,6 +971,58 @@ fn default_workspace_groups() -> Vec<WorkspaceGroup> {
     Vec::new()
 }
 
+fn default_archive_retention_days() -> u32 {
+    7
+}
+
+fn default_encrypt_local_data() -> bool {
+    false
+}
+
+fn default_data_retention() -> DataRetentionSettings {
+    DataRetentionSettings::default()
+}
+
+fn default_update_channel() -> UpdateChannel {
+    UpdateChannel::Stable
+}
+
+fn default_webhook_intake() -> WebhookIntakeSettings {
+    WebhookIntakeSettings::default()
+}
+
+fn default_semantic_search() -> SemanticSearchSettings {
+    SemanticSearchSettings::default()
+}
+
+fn default_approval_delegation() -> ApprovalDelegationSettings {
+    ApprovalDelegationSettings::default()
+}
+
+fn default_command_blocklist() -> Vec<String> {
+    Vec::new()
+}
+
+fn default_queue_offline_turns() -> bool {
+    false
+}
+
+fn default_network() -> NetworkSettings {
+    NetworkSettings::default()
+}
+
+fn default_rate_limit_reserve_percent() -> u32 {
+    10
+}
+
+fn default_show_reasoning_summaries() -> bool {
+    true
+}
+
+fn default_reasoning_summary_models() -> BTreeMap<String, bool> {
+    BTreeMap::new()
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -504,6 +1045,8 @@ impl Default for AppSettings {
             cycle_agent_prev_shortcut: default_cycle_agent_prev_shortcut(),
             cycle_workspace_next_shortcut: default_cycle_workspace_next_shortcut(),
             cycle_workspace_prev_shortcut: default_cycle_workspace_prev_shortcut(),
+            accept_approval_shortcut: default_accept_approval_shortcut(),
+            decline_approval_shortcut: default_decline_approval_shortcut(),
             last_composer_model_id: None,
             last_composer_reasoning_effort: None,
             ui_scale: 1.0,
@@ -517,6 +1060,28 @@ impl Default for AppSettings {
             dictation_preferred_language: None,
             dictation_hold_key: default_dictation_hold_key(),
             workspace_groups: default_workspace_groups(),
+            archive_retention_days: default_archive_retention_days(),
+            encrypt_local_data: default_encrypt_local_data(),
+            data_retention: default_data_retention(),
+            update_channel: default_update_channel(),
+            locale: None,
+            webhook_intake: default_webhook_intake(),
+            semantic_search: default_semantic_search(),
+            approval_delegation: default_approval_delegation(),
+            command_blocklist: default_command_blocklist(),
+            queue_offline_turns: default_queue_offline_turns(),
+            network: default_network(),
+            auto_lock_minutes: None,
+            max_concurrent_turns: None,
+            max_workspace_turns: None,
+            turn_overlap_mode: TurnOverlapMode::Queue,
+            rate_limit_reserve_percent: default_rate_limit_reserve_percent(),
+            show_reasoning_summaries: default_show_reasoning_summaries(),
+            reasoning_summary_models: default_reasoning_summary_models(),
+            preferred_editor: None,
+            editor_templates: Vec::new(),
+            team_activity: TeamActivitySettings::default(),
+            telemetry: TelemetrySettings::default(),
         }
     }
 }
@@ -524,7 +1089,8 @@ impl Default for AppSettings {
 #[cfg(test)]
 mod tests {
     use super::{
-        AppSettings, BackendMode, WorkspaceEntry, WorkspaceGroup, WorkspaceKind, WorkspaceSettings,
+        AppSettings, BackendMode, DataRetentionSettings, TurnOverlapMode, UpdateChannel,
+        WebhookIntakeSettings, WorkspaceEntry, WorkspaceGroup, WorkspaceKind, WorkspaceSettings,
     };
 
     #[test]
@@ -571,6 +1137,14 @@ mod tests {
             settings.cycle_workspace_prev_shortcut.as_deref(),
             Some("cmd+shift+up")
         );
+        assert_eq!(
+            settings.accept_approval_shortcut.as_deref(),
+            Some("cmd+shift+y")
+        );
+        assert_eq!(
+            settings.decline_approval_shortcut.as_deref(),
+            Some("cmd+shift+x")
+        );
         assert!(settings.last_composer_model_id.is_none());
         assert!(settings.last_composer_reasoning_effort.is_none());
         assert!((settings.ui_scale - 1.0).abs() < f64::EPSILON);
@@ -582,6 +1156,26 @@ mod tests {
         assert!(settings.dictation_preferred_language.is_none());
         assert_eq!(settings.dictation_hold_key, "alt");
         assert!(settings.workspace_groups.is_empty());
+        assert_eq!(settings.archive_retention_days, 7);
+        assert!(!settings.encrypt_local_data);
+        assert_eq!(settings.data_retention, DataRetentionSettings::default());
+        assert!(matches!(settings.update_channel, UpdateChannel::Stable));
+        assert!(settings.locale.is_none());
+        assert_eq!(settings.webhook_intake, WebhookIntakeSettings::default());
+        assert!(!settings.semantic_search.enabled);
+        assert!(!settings.approval_delegation.enabled);
+        assert!(settings.command_blocklist.is_empty());
+        assert!(!settings.queue_offline_turns);
+        assert!(settings.network.https_proxy.is_none());
+        assert!(settings.auto_lock_minutes.is_none());
+        assert!(settings.max_concurrent_turns.is_none());
+        assert!(settings.max_workspace_turns.is_none());
+        assert_eq!(settings.turn_overlap_mode, TurnOverlapMode::Queue);
+        assert_eq!(settings.rate_limit_reserve_percent, 10);
+        assert!(settings.show_reasoning_summaries);
+        assert!(settings.reasoning_summary_models.is_empty());
+        assert!(settings.preferred_editor.is_none());
+        assert!(settings.editor_templates.is_empty());
     }
 
     #[test]
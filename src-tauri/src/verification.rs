@@ -0,0 +1,123 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::backend::events::AppServerEvent;
+use crate::backend::turn_limiter::TurnLane;
+use crate::backend::verification::{self, FinishedTurn};
+use crate::codex::{self, SendOptions};
+use crate::model_stats;
+use crate::state::AppState;
+use crate::workspace_windows::emit_app_server_event;
+
+/// Marks the turn just started in a thread as write-enabled, so the
+/// workspace's verification command runs once it completes.
+pub(crate) fn arm_turn(state: &AppState, workspace_id: &str, thread_id: &str) {
+    if let Ok(mut verifications) = state.verifications.lock() {
+        verifications.arm(workspace_id, thread_id);
+    }
+}
+
+fn finish(state: &AppState, workspace_id: &str, thread_id: &str) {
+    if let Ok(mut verifications) = state.verifications.lock() {
+        verifications.finish(workspace_id, thread_id);
+    }
+}
+
+async fn verify_turn(app: AppHandle, workspace_id: String, turn: FinishedTurn) {
+    let state = app.state::<AppState>();
+    let workspace = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces.get(&workspace_id).and_then(|entry| {
+            entry
+                .settings
+                .verification
+                .clone()
+                .filter(|config| !config.command.trim().is_empty())
+                .map(|config| (config, PathBuf::from(&entry.path)))
+        })
+    };
+    let Some((config, cwd)) = workspace else {
+        finish(&state, &workspace_id, &turn.thread_id);
+        return;
+    };
+    let outcome = {
+        let _guard = state.verification_runs.lock().await;
+        verification::run_command(
+            &cwd,
+            &config.command,
+            Duration::from_secs(config.timeout_secs),
+        )
+        .await
+    };
+    model_stats::record_verification(
+        &app,
+        &workspace_id,
+        turn.turn_id.as_deref(),
+        outcome.passed,
+    );
+    let will_fix = !outcome.passed && config.auto_fix && turn.fix_attempt < config.max_fix_attempts;
+    emit_app_server_event(
+        &app,
+        &workspace_id,
+        Some("codex/turnVerification"),
+        Some(&turn.thread_id),
+        AppServerEvent {
+            workspace_id: workspace_id.clone(),
+            message: json!({
+                "method": "codex/turnVerification",
+                "params": {
+                    "threadId": turn.thread_id,
+                    "turnId": turn.turn_id,
+                    "command": config.command,
+                    "verification": outcome,
+                    "fixAttempt": turn.fix_attempt,
+                    "willFix": will_fix,
+                },
+            }),
+        },
+    );
+    if !will_fix {
+        finish(&state, &workspace_id, &turn.thread_id);
+        return;
+    }
+    if let Ok(mut verifications) = state.verifications.lock() {
+        verifications.record_fix(&workspace_id, &turn.thread_id, turn.fix_attempt + 1);
+    }
+    let result = codex::send_message(
+        workspace_id.clone(),
+        turn.thread_id.clone(),
+        verification::fix_prompt(&config.command, &outcome),
+        SendOptions {
+            lane: TurnLane::Scheduled,
+            ..SendOptions::default()
+        },
+        state.clone(),
+        app.clone(),
+    )
+    .await;
+    if let Err(err) = result {
+        eprintln!("Failed to start verification fix turn: {err}");
+        finish(&state, &workspace_id, &turn.thread_id);
+    }
+}
+
+/// Runs the workspace's verification command when a write-enabled turn
+/// completes and reports the result as a `codex/turnVerification` event that
+/// completes the turn's summary; called for every `app-server-event`. Only
+/// turns started from this app are armed, so forwarded remote events pass
+/// through untouched.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let finished = match state.verifications.lock() {
+        Ok(mut verifications) => verifications.observe(workspace_id, message),
+        Err(_) => None,
+    };
+    if let Some(turn) = finished {
+        tauri::async_runtime::spawn(verify_turn(app.clone(), workspace_id.to_string(), turn));
+    }
+}
@@ -0,0 +1,556 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tokio::net::{TcpListener, TcpStream};
+use uuid::Uuid;
+
+use crate::app_lock;
+use crate::approval_delegation::{self, DelegatedDecision};
+use crate::backend::approval_audit::{ApprovalAuditAction, ApprovalAuditEntry};
+use crate::backend::approval_inbox::{self, InboxRoute};
+use crate::backend::hmac_signature;
+use crate::backend::local_http::{read_request, respond, HttpRequest};
+use crate::backend::request_id::RequestId;
+use crate::backend::task_queue::{QueuedTask, QueuedTaskStatus};
+use crate::codex;
+use crate::issue_tracker::{issue_prompt, TrackedIssue};
+use crate::state::AppState;
+use crate::task_queue;
+use crate::types::WebhookIntakeSettings;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// The previous listener releases its port asynchronously after being aborted.
+const BIND_ATTEMPTS: u32 = 10;
+const BIND_RETRY_DELAY: Duration = Duration::from_millis(200);
+/// Route receiving signed decisions for delegated approvals.
+const DELEGATED_DECISION_PATH: &str = "/approvals/decision";
+
+/// Issue extracted from a Linear or Jira webhook delivery.
+#[derive(Debug, Clone, PartialEq)]
+struct IntakeIssue {
+    source: &'static str,
+    project_key: String,
+    external_id: String,
+    title: String,
+    description: String,
+    url: String,
+    labels: Vec<String>,
+}
+
+/// Body posted by an approver to `DELEGATED_DECISION_PATH`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DelegatedDecisionRequest {
+    workspace_id: String,
+    request_id: RequestId,
+    result: Value,
+    decision: DelegatedDecision,
+}
+
+/// Checks the HMAC-SHA256 body signature sent by Linear (`Linear-Signature`,
+/// hex) or by Jira (`X-Hub-Signature`, `sha256=<hex>`).
+fn verify_signature(secret: &str, request: &HttpRequest) -> bool {
+    let Some(signature) = request
+        .header("linear-signature")
+        .or_else(|| request.header("x-hub-signature-256"))
+        .or_else(|| request.header("x-hub-signature"))
+    else {
+        return false;
+    };
+    let signature = signature.trim();
+    hmac_signature::verify_hex(
+        secret,
+        &request.body,
+        signature.strip_prefix("sha256=").unwrap_or(signature),
+    )
+}
+
+/// Label names given either as plain strings (Jira) or `{ name }` objects (Linear).
+fn label_names(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|labels| labels.as_array())
+        .map(|labels| {
+            labels
+                .iter()
+                .filter_map(|label| {
+                    label
+                        .as_str()
+                        .or_else(|| label.get("name").and_then(|name| name.as_str()))
+                        .map(|name| name.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn str_field(value: &Value, pointer: &str) -> String {
+    value
+        .pointer(pointer)
+        .and_then(|field| field.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn parse_linear(payload: &Value) -> Option<IntakeIssue> {
+    if payload.get("type")?.as_str()? != "Issue" {
+        return None;
+    }
+    if !matches!(payload.get("action")?.as_str()?, "create" | "update") {
+        return None;
+    }
+    let data = payload.get("data")?;
+    Some(IntakeIssue {
+        source: "linear",
+        project_key: data.pointer("/team/key")?.as_str()?.to_string(),
+        external_id: data.get("identifier")?.as_str()?.to_string(),
+        title: str_field(data, "/title"),
+        description: str_field(data, "/description"),
+        url: str_field(data, "/url"),
+        labels: label_names(data.get("labels")),
+    })
+}
+
+fn parse_jira(payload: &Value) -> Option<IntakeIssue> {
+    if !matches!(
+        payload.get("webhookEvent")?.as_str()?,
+        "jira:issue_created" | "jira:issue_updated"
+    ) {
+        return None;
+    }
+    let issue = payload.get("issue")?;
+    let key = issue.get("key")?.as_str()?.to_string();
+    let url = issue
+        .get("self")
+        .and_then(|value| value.as_str())
+        .and_then(|api_url| api_url.split_once("/rest/"))
+        .map(|(base_url, _)| format!("{base_url}/browse/{key}"))
+        .unwrap_or_default();
+    Some(IntakeIssue {
+        source: "jira",
+        project_key: issue.pointer("/fields/project/key")?.as_str()?.to_string(),
+        external_id: key,
+        title: str_field(issue, "/fields/summary"),
+        description: str_field(issue, "/fields/description"),
+        url,
+        labels: label_names(issue.pointer("/fields/labels")),
+    })
+}
+
+fn parse_issue_event(payload: &Value) -> Option<IntakeIssue> {
+    parse_linear(payload).or_else(|| parse_jira(payload))
+}
+
+fn has_trigger_label(issue: &IntakeIssue, trigger_label: &str) -> bool {
+    let trigger_label = trigger_label.trim();
+    trigger_label.is_empty()
+        || issue
+            .labels
+            .iter()
+            .any(|label| label.eq_ignore_ascii_case(trigger_label))
+}
+
+fn mapped_workspace(settings: &WebhookIntakeSettings, project_key: &str) -> Option<String> {
+    settings
+        .mappings
+        .iter()
+        .find(|mapping| mapping.project_key.eq_ignore_ascii_case(project_key))
+        .map(|mapping| mapping.workspace_id.clone())
+}
+
+fn queued_task(issue: IntakeIssue, workspace_id: String) -> QueuedTask {
+    let prompt = issue_prompt(
+        TrackedIssue {
+            id: issue.external_id.clone(),
+            tracker: issue.source.to_string(),
+            title: issue.title.clone(),
+            url: issue.url.clone(),
+            updated_at: None,
+            labels: issue.labels.clone(),
+        },
+        &issue.description,
+    );
+    QueuedTask {
+        id: Uuid::new_v4().to_string(),
+        workspace_id,
+        source: issue.source.to_string(),
+        external_id: issue.external_id,
+        title: issue.title,
+        prompt,
+        url: issue.url,
+        created_at: chrono::Utc::now().timestamp_millis(),
+        status: QueuedTaskStatus::Queued,
+        thread_id: None,
+    }
+}
+
+/// Enqueues the delivered issue and, when enabled, starts it right away.
+async fn accept_delivery(
+    app: &AppHandle,
+    settings: &WebhookIntakeSettings,
+    body: &[u8],
+) -> Result<(u16, Value), String> {
+    let payload: Value =
+        serde_json::from_slice(body).map_err(|err| format!("Invalid JSON payload: {err}"))?;
+    let Some(issue) = parse_issue_event(&payload) else {
+        return Ok((
+            200,
+            json!({ "status": "ignored", "reason": "not an issue event" }),
+        ));
+    };
+    if !has_trigger_label(&issue, &settings.trigger_label) {
+        return Ok((
+            200,
+            json!({ "status": "ignored", "reason": "missing trigger label" }),
+        ));
+    }
+    let Some(workspace_id) = mapped_workspace(settings, &issue.project_key) else {
+        return Ok((
+            200,
+            json!({ "status": "ignored", "reason": "no workspace mapped" }),
+        ));
+    };
+
+    let state = app.state::<AppState>();
+    let task = queued_task(issue, workspace_id.clone());
+    let task_id = task.id.clone();
+    if !task_queue::enqueue(task, &state, app).await? {
+        return Ok((200, json!({ "status": "duplicate" })));
+    }
+    let connected = state.sessions.lock().await.contains_key(&workspace_id);
+    if settings.auto_start && connected {
+        let app = app.clone();
+        let task_id = task_id.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<AppState>();
+            if let Err(err) =
+                task_queue::start_task(task_id, None, None, None, None, state, app.clone()).await
+            {
+                eprintln!("Failed to auto-start queued task: {err}");
+            }
+        });
+    }
+    Ok((202, json!({ "status": "queued", "taskId": task_id })))
+}
+
+/// Applies an approver's signed decision. The decision carries its own
+/// signature, so the intake secret is not required here.
+async fn accept_delegated_decision(app: &AppHandle, body: &[u8]) -> Result<(), String> {
+    let payload: DelegatedDecisionRequest =
+        serde_json::from_slice(body).map_err(|err| format!("Invalid decision payload: {err}"))?;
+    codex::respond_to_server_request(
+        payload.workspace_id,
+        payload.request_id,
+        payload.result,
+        Some(payload.decision),
+        app.state::<AppState>(),
+        app.clone(),
+    )
+    .await
+}
+
+/// Serves the `/approvals` inbox used by ChatOps bots. Answers go through
+/// `respond_to_server_request`, so delegation sign-off still applies.
+async fn handle_inbox(app: &AppHandle, route: InboxRoute, request: &HttpRequest) -> (u16, Value) {
+    let state = app.state::<AppState>();
+    let token = state
+        .app_settings
+        .lock()
+        .await
+        .approval_delegation
+        .inbox_token
+        .clone()
+        .filter(|token| !token.trim().is_empty());
+    let Some(token) = token else {
+        return (404, json!({ "error": "Approval inbox is disabled" }));
+    };
+    if !approval_inbox::authorized(request, &token) {
+        return (401, json!({ "error": "Invalid token" }));
+    }
+    match route {
+        InboxRoute::List => {
+            let approvals = state
+                .approval_inbox
+                .lock()
+                .map(|inbox| inbox.list())
+                .unwrap_or_default();
+            (200, json!({ "approvals": approvals }))
+        }
+        InboxRoute::Respond(_) if app_lock::is_locked(&state) => {
+            (423, json!({ "error": app_lock::LOCKED_MESSAGE }))
+        }
+        InboxRoute::Respond(id) => {
+            let approval = state
+                .approval_inbox
+                .lock()
+                .ok()
+                .and_then(|inbox| inbox.get(&id).cloned());
+            let Some(approval) = approval else {
+                return (404, json!({ "error": "Unknown approval" }));
+            };
+            let result = match approval_inbox::decision_result(&request.body) {
+                Ok(result) => result,
+                Err(err) => return (400, json!({ "error": err })),
+            };
+            if let Err(err) = codex::respond_to_server_request(
+                approval.workspace_id.clone(),
+                approval.request_id.clone(),
+                result.clone(),
+                None,
+                state.clone(),
+                app.clone(),
+            )
+            .await
+            {
+                return (400, json!({ "error": err }));
+            }
+            approval_delegation::record(
+                &state,
+                ApprovalAuditEntry {
+                    recorded_at: chrono::Utc::now().timestamp_millis(),
+                    workspace_id: approval.workspace_id,
+                    request_id: Some(approval.request_id),
+                    delegation_id: None,
+                    action: ApprovalAuditAction::AnsweredFromInbox,
+                    actor: "inbox".to_string(),
+                    method: Some(approval.method),
+                    decision: Some(result),
+                    detail: approval.command,
+                },
+            )
+            .await;
+            (200, json!({ "status": "applied" }))
+        }
+        InboxRoute::MethodNotAllowed => (405, json!({ "error": "Method not allowed" })),
+        InboxRoute::NotFound => (404, json!({ "error": "Not found" })),
+    }
+}
+
+async fn handle_connection(app: AppHandle, settings: WebhookIntakeSettings, mut stream: TcpStream) {
+    let request = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(request)) => request,
+        Ok(Err((status, message))) => {
+            respond(&mut stream, status, json!({ "error": message })).await;
+            return;
+        }
+        Err(_) => return,
+    };
+    let route = approval_inbox::route(&request.method, &request.path);
+    if route != InboxRoute::NotFound {
+        let (status, body) = handle_inbox(&app, route, &request).await;
+        respond(&mut stream, status, body).await;
+        return;
+    }
+    // The listener may be up for the inbox alone.
+    if !intake_ready(&settings) {
+        respond(
+            &mut stream,
+            404,
+            json!({ "error": "Webhook intake is disabled" }),
+        )
+        .await;
+        return;
+    }
+    if request.method != "POST" {
+        respond(
+            &mut stream,
+            405,
+            json!({ "error": "Only POST is supported" }),
+        )
+        .await;
+        return;
+    }
+    // Decisions and deliveries answer approvals and start turns, which the
+    // lock holds back like mutating commands.
+    if app_lock::is_locked(&app.state::<AppState>()) {
+        respond(
+            &mut stream,
+            423,
+            json!({ "error": app_lock::LOCKED_MESSAGE }),
+        )
+        .await;
+        return;
+    }
+    if request.path == DELEGATED_DECISION_PATH {
+        match accept_delegated_decision(&app, &request.body).await {
+            Ok(()) => respond(&mut stream, 200, json!({ "status": "applied" })).await,
+            Err(err) => respond(&mut stream, 400, json!({ "error": err })).await,
+        }
+        return;
+    }
+    let secret = settings.secret.as_deref().unwrap_or_default();
+    if !verify_signature(secret, &request) {
+        respond(&mut stream, 401, json!({ "error": "Invalid signature" })).await;
+        return;
+    }
+    match accept_delivery(&app, &settings, &request.body).await {
+        Ok((status, body)) => respond(&mut stream, status, body).await,
+        Err(err) => respond(&mut stream, 400, json!({ "error": err })).await,
+    }
+}
+
+async fn bind_listener(port: u16) -> Result<TcpListener, String> {
+    let mut attempt = 0;
+    loop {
+        match TcpListener::bind(("127.0.0.1", port)).await {
+            Ok(listener) => return Ok(listener),
+            Err(err) if attempt + 1 >= BIND_ATTEMPTS => {
+                return Err(format!(
+                    "Failed to bind webhook intake on port {port}: {err}"
+                ))
+            }
+            Err(_) => {
+                attempt += 1;
+                tokio::time::sleep(BIND_RETRY_DELAY).await;
+            }
+        }
+    }
+}
+
+/// Whether webhook deliveries are accepted; they need a secret to verify.
+fn intake_ready(settings: &WebhookIntakeSettings) -> bool {
+    settings.enabled
+        && settings
+            .secret
+            .as_deref()
+            .is_some_and(|secret| !secret.trim().is_empty())
+}
+
+/// Listens while webhook intake is ready or the approval inbox has a token.
+/// The inbox checks its own token, so it doesn't need the intake secret.
+async fn run_listener(app: AppHandle) {
+    let (settings, inbox_enabled) = {
+        let app_settings = app.state::<AppState>().app_settings.lock().await.clone();
+        let inbox_enabled = app_settings
+            .approval_delegation
+            .inbox_token
+            .as_deref()
+            .is_some_and(|token| !token.trim().is_empty());
+        (app_settings.webhook_intake, inbox_enabled)
+    };
+    if settings.enabled && !intake_ready(&settings) {
+        eprintln!("Webhook intake is enabled but has no secret; not accepting deliveries.");
+    }
+    if !intake_ready(&settings) && !inbox_enabled {
+        return;
+    }
+    let listener = match bind_listener(settings.port).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("{err}");
+            return;
+        }
+    };
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+        tauri::async_runtime::spawn(handle_connection(app.clone(), settings.clone(), stream));
+    }
+}
+
+/// (Re)starts the webhook listener from the current settings. Called at
+/// startup and whenever the webhook intake settings or the inbox token
+/// change.
+pub(crate) fn restart_listener(app: &AppHandle) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let Ok(mut listener) = state.webhook_listener.lock() else {
+        return;
+    };
+    if let Some(previous) = listener.take() {
+        previous.abort();
+    }
+    *listener = Some(tauri::async_runtime::spawn(run_listener(app.clone())));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::WebhookWorkspaceMapping;
+
+    fn signed_request(secret: &str, header: &str, prefix: &str, body: &[u8]) -> HttpRequest {
+        let signature = hmac_signature::sign_hex(secret, body).unwrap();
+        HttpRequest {
+            method: "POST".to_string(),
+            path: "/".to_string(),
+            headers: vec![(header.to_string(), format!("{prefix}{signature}"))],
+            body: body.to_vec(),
+        }
+    }
+
+    #[test]
+    fn verifies_linear_and_jira_signatures() {
+        let body = br#"{"type":"Issue"}"#;
+        assert!(verify_signature(
+            "s3cret",
+            &signed_request("s3cret", "Linear-Signature", "", body)
+        ));
+        assert!(verify_signature(
+            "s3cret",
+            &signed_request("s3cret", "X-Hub-Signature", "sha256=", body)
+        ));
+        assert!(!verify_signature(
+            "other",
+            &signed_request("s3cret", "Linear-Signature", "", body)
+        ));
+        let mut unsigned = signed_request("s3cret", "Linear-Signature", "", body);
+        unsigned.headers.clear();
+        assert!(!verify_signature("s3cret", &unsigned));
+    }
+
+    #[test]
+    fn parses_issue_events_and_maps_workspaces() {
+        let linear = json!({
+            "type": "Issue",
+            "action": "create",
+            "data": {
+                "identifier": "ENG-42",
+                "title": "Fix login",
+                "description": "See https://example.com/spec",
+                "url": "https://linear.app/acme/issue/ENG-42",
+                "team": { "key": "ENG" },
+                "labels": [{ "name": "Agent" }]
+            }
+        });
+        let issue = parse_issue_event(&linear).expect("linear issue");
+        assert_eq!(issue.source, "linear");
+        assert_eq!(issue.external_id, "ENG-42");
+        assert!(has_trigger_label(&issue, "agent"));
+
+        let jira = json!({
+            "webhookEvent": "jira:issue_created",
+            "issue": {
+                "key": "OPS-7",
+                "self": "https://acme.atlassian.net/rest/api/2/issue/10001",
+                "fields": {
+                    "summary": "Rotate keys",
+                    "project": { "key": "OPS" },
+                    "labels": ["infra"]
+                }
+            }
+        });
+        let issue = parse_issue_event(&jira).expect("jira issue");
+        assert_eq!(issue.url, "https://acme.atlassian.net/browse/OPS-7");
+        assert!(!has_trigger_label(&issue, "agent"));
+        assert!(has_trigger_label(&issue, ""));
+
+        let comment = json!({ "type": "Comment", "action": "create", "data": {} });
+        assert!(parse_issue_event(&comment).is_none());
+
+        let settings = WebhookIntakeSettings {
+            mappings: vec![WebhookWorkspaceMapping {
+                project_key: "ops".to_string(),
+                workspace_id: "ws-ops".to_string(),
+            }],
+            ..WebhookIntakeSettings::default()
+        };
+        assert_eq!(
+            mapped_workspace(&settings, "OPS").as_deref(),
+            Some("ws-ops")
+        );
+        assert!(mapped_workspace(&settings, "ENG").is_none());
+    }
+}
@@ -0,0 +1,343 @@
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+
+use tauri::State;
+use uuid::Uuid;
+
+use crate::codex_home::{resolve_default_codex_home, resolve_workspace_codex_home};
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// Path fragments of folders kept in sync by iCloud Drive, Dropbox and other
+/// file providers. File watching and git locks are unreliable inside them.
+const SYNCED_FOLDER_MARKERS: &[(&str, &str)] = &[
+    ("/Library/Mobile Documents/", "iCloud Drive"),
+    ("/iCloud Drive/", "iCloud Drive"),
+    ("/iCloudDrive/", "iCloud Drive"),
+    ("/Library/CloudStorage/", "a cloud storage provider"),
+    ("/Dropbox/", "Dropbox"),
+    ("/OneDrive/", "OneDrive"),
+];
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WorkspaceCheckId {
+    PathExists,
+    GitRepository,
+    Writable,
+    SyncedFolder,
+    CodexConfig,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum FindingSeverity {
+    Ok,
+    Info,
+    Warning,
+    /// Codex cannot work in the workspace until this is fixed.
+    Error,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceFinding {
+    pub(crate) id: WorkspaceCheckId,
+    pub(crate) severity: FindingSeverity,
+    pub(crate) message: String,
+    pub(crate) detail: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceDoctorReport {
+    pub(crate) workspace_id: String,
+    pub(crate) path: String,
+    /// `false` when any finding is an error.
+    pub(crate) ok: bool,
+    pub(crate) findings: Vec<WorkspaceFinding>,
+}
+
+fn finding(
+    id: WorkspaceCheckId,
+    severity: FindingSeverity,
+    message: impl Into<String>,
+    detail: Option<String>,
+) -> WorkspaceFinding {
+    WorkspaceFinding {
+        id,
+        severity,
+        message: message.into(),
+        detail,
+    }
+}
+
+/// Name of the sync provider whose folder contains `path`, if any.
+fn synced_folder_provider(path: &Path) -> Option<&'static str> {
+    let normalized = format!("{}/", path.to_string_lossy().replace('\\', "/"));
+    SYNCED_FOLDER_MARKERS
+        .iter()
+        .find(|(marker, _)| normalized.contains(marker))
+        .map(|(_, provider)| *provider)
+}
+
+fn check_git_repository(path: &Path) -> WorkspaceFinding {
+    match git2::Repository::discover(path) {
+        Ok(repo) => {
+            let root = repo
+                .workdir()
+                .map(|root| root.to_string_lossy().trim_end_matches('/').to_string());
+            let nested = root.as_deref().is_some_and(|root| Path::new(root) != path);
+            finding(
+                WorkspaceCheckId::GitRepository,
+                FindingSeverity::Ok,
+                if nested {
+                    "Inside a git repository."
+                } else {
+                    "Git repository."
+                },
+                root,
+            )
+        }
+        Err(err) => finding(
+            WorkspaceCheckId::GitRepository,
+            FindingSeverity::Warning,
+            "Not a git repository; diffs, commits and worktrees are unavailable.",
+            Some(err.message().to_string()),
+        ),
+    }
+}
+
+fn check_writable(path: &Path) -> WorkspaceFinding {
+    let probe = path.join(format!(".codex-monitor-write-check-{}", Uuid::new_v4()));
+    let result = OpenOptions::new().write(true).create_new(true).open(&probe);
+    match result {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&probe);
+            finding(
+                WorkspaceCheckId::Writable,
+                FindingSeverity::Ok,
+                "Writable.",
+                None,
+            )
+        }
+        Err(err) => finding(
+            WorkspaceCheckId::Writable,
+            FindingSeverity::Error,
+            "You do not have write permission in this folder; Codex cannot apply changes.",
+            Some(err.to_string()),
+        ),
+    }
+}
+
+fn check_synced_folder(path: &Path) -> WorkspaceFinding {
+    match synced_folder_provider(path) {
+        Some(provider) => finding(
+            WorkspaceCheckId::SyncedFolder,
+            FindingSeverity::Warning,
+            format!("The workspace is inside a folder synced by {provider}."),
+            Some(
+                "Synced folders break file watching and can corrupt git locks; move the workspace to a local folder."
+                    .to_string(),
+            ),
+        ),
+        None => finding(
+            WorkspaceCheckId::SyncedFolder,
+            FindingSeverity::Ok,
+            "Not inside a synced folder.",
+            None,
+        ),
+    }
+}
+
+/// Project `.codex/config.toml` files that apply to `path`, nearest first.
+pub(crate) fn project_configs(path: &Path, default_home: Option<&Path>) -> Vec<PathBuf> {
+    path.ancestors()
+        .map(|dir| dir.join(".codex"))
+        .filter(|dir| Some(dir.as_path()) != default_home)
+        .map(|dir| dir.join("config.toml"))
+        .filter(|config| config.is_file())
+        .collect()
+}
+
+fn check_codex_config(
+    project_configs: &[PathBuf],
+    workspace_home: Option<&Path>,
+    default_home: Option<&Path>,
+) -> WorkspaceFinding {
+    let mut conflicts = Vec::new();
+    let home_config = |home: &Path| Some(home.join("config.toml")).filter(|path| path.is_file());
+    if let (Some(workspace), Some(default)) = (
+        workspace_home.and_then(home_config),
+        default_home.and_then(home_config),
+    ) {
+        conflicts.push(format!(
+            "{} replaces {}; settings in the latter are ignored for this workspace.",
+            workspace.display(),
+            default.display()
+        ));
+    }
+    if project_configs.len() > 1 {
+        let paths: Vec<_> = project_configs
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        conflicts.push(format!(
+            "Several project configs apply and are layered: {}.",
+            paths.join(", ")
+        ));
+    }
+    if !conflicts.is_empty() {
+        return finding(
+            WorkspaceCheckId::CodexConfig,
+            FindingSeverity::Warning,
+            "Conflicting Codex configs apply to this workspace.",
+            Some(conflicts.join(" ")),
+        );
+    }
+    match project_configs.first() {
+        Some(config) => finding(
+            WorkspaceCheckId::CodexConfig,
+            FindingSeverity::Info,
+            "A project Codex config applies to this workspace.",
+            Some(config.display().to_string()),
+        ),
+        None => finding(
+            WorkspaceCheckId::CodexConfig,
+            FindingSeverity::Ok,
+            "No conflicting Codex configs.",
+            None,
+        ),
+    }
+}
+
+fn diagnose(
+    path: &Path,
+    workspace_home: Option<&Path>,
+    default_home: Option<&Path>,
+) -> Vec<WorkspaceFinding> {
+    if !path.is_dir() {
+        return vec![finding(
+            WorkspaceCheckId::PathExists,
+            FindingSeverity::Error,
+            "The workspace folder does not exist.",
+            Some(path.display().to_string()),
+        )];
+    }
+    let configs = project_configs(path, default_home);
+    vec![
+        finding(
+            WorkspaceCheckId::PathExists,
+            FindingSeverity::Ok,
+            "Folder exists.",
+            None,
+        ),
+        check_git_repository(path),
+        check_writable(path),
+        check_synced_folder(path),
+        check_codex_config(&configs, workspace_home, default_home),
+    ]
+}
+
+/// Checks a workspace folder for problems `codex_doctor` cannot see: missing
+/// git repository, write permission, synced folders and conflicting configs.
+#[tauri::command]
+pub(crate) async fn workspace_doctor(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceDoctorReport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Workspace checks are only available for local workspaces.".to_string());
+    }
+    let (entry, parent_path) = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?;
+        let parent_path = entry
+            .parent_id
+            .as_ref()
+            .and_then(|parent_id| workspaces.get(parent_id))
+            .map(|parent| parent.path.clone());
+        (entry, parent_path)
+    };
+    let workspace_home = resolve_workspace_codex_home(&entry, parent_path.as_deref());
+    let default_home = resolve_default_codex_home();
+    let path = PathBuf::from(&entry.path);
+    let findings = tokio::task::spawn_blocking(move || {
+        diagnose(&path, workspace_home.as_deref(), default_home.as_deref())
+    })
+    .await
+    .map_err(|err| err.to_string())?;
+    Ok(WorkspaceDoctorReport {
+        workspace_id,
+        ok: findings
+            .iter()
+            .all(|finding| finding.severity != FindingSeverity::Error),
+        path: entry.path,
+        findings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_synced_folders() {
+        assert_eq!(
+            synced_folder_provider(Path::new(
+                "/Users/me/Library/Mobile Documents/com~apple~CloudDocs/app"
+            )),
+            Some("iCloud Drive")
+        );
+        assert_eq!(
+            synced_folder_provider(Path::new("/Users/me/Dropbox")),
+            Some("Dropbox")
+        );
+        assert_eq!(
+            synced_folder_provider(Path::new("C:\\Users\\me\\OneDrive\\app")),
+            Some("OneDrive")
+        );
+        assert_eq!(
+            synced_folder_provider(Path::new("/Users/me/code/app")),
+            None
+        );
+    }
+
+    #[test]
+    fn reports_layered_project_configs() {
+        let root = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let nested = root.join("packages").join("app");
+        for dir in [&root, &nested] {
+            std::fs::create_dir_all(dir.join(".codex")).expect("create dir");
+            std::fs::write(dir.join(".codex").join("config.toml"), "").expect("write config");
+        }
+
+        let findings = diagnose(&nested, None, None);
+        let ids: Vec<_> = findings.iter().map(|finding| finding.id).collect();
+        assert_eq!(
+            ids,
+            vec![
+                WorkspaceCheckId::PathExists,
+                WorkspaceCheckId::GitRepository,
+                WorkspaceCheckId::Writable,
+                WorkspaceCheckId::SyncedFolder,
+                WorkspaceCheckId::CodexConfig,
+            ]
+        );
+        assert_eq!(findings[2].severity, FindingSeverity::Ok);
+        assert_eq!(findings[4].severity, FindingSeverity::Warning);
+        assert!(findings[4]
+            .detail
+            .as_deref()
+            .is_some_and(|detail| detail.starts_with("Several project configs apply")));
+
+        let missing = diagnose(&root.join("missing"), None, None);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].severity, FindingSeverity::Error);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::backend::prompt_insights;
+use crate::backend::workspace_health::{
+    self, HealthEvent, HealthSample, WorkspaceHealth, WorkspaceHealthReport,
+};
+use crate::state::AppState;
+use crate::workspace_doctor::{self, FindingSeverity};
+use crate::workspaces;
+
+const DEFAULT_HEALTH_DAYS: u32 = 7;
+const REPORT_INTERVAL_MS: i64 = 7 * 24 * 3_600_000;
+/// How often the scheduler checks whether a weekly report is due.
+const REPORT_CHECK_INTERVAL: Duration = Duration::from_secs(3_600);
+
+/// Stores a health sample for a workspace in the background.
+pub(crate) fn record(app: &AppHandle, workspace_id: &str, event: HealthEvent) {
+    let app = app.clone();
+    let sample = HealthSample {
+        workspace_id: workspace_id.to_string(),
+        recorded_at: Utc::now().timestamp_millis(),
+        event,
+    };
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let _guard = state.health_sample_writes.lock().await;
+        let result = match state.data_cipher().await {
+            Ok(cipher) => workspace_health::record_sample(&state.data_dir, &cipher, sample),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            eprintln!("Failed to record workspace health sample: {err}");
+        }
+    });
+}
+
+/// Counts session starts for the stability signal; called for every
+/// `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    if message.get("method").and_then(|value| value.as_str()) == Some("codex/connected") {
+        record(app, workspace_id, HealthEvent::SessionStarted);
+    }
+}
+
+/// Errors and warnings `workspace_doctor` finds right now; zero for remote
+/// workspaces, which it cannot check.
+async fn doctor_findings(app: &AppHandle, workspace_id: &str) -> (usize, usize) {
+    let Ok(report) =
+        workspace_doctor::workspace_doctor(workspace_id.to_string(), app.state()).await
+    else {
+        return (0, 0);
+    };
+    let count = |severity| {
+        report
+            .findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .count()
+    };
+    (
+        count(FindingSeverity::Error),
+        count(FindingSeverity::Warning),
+    )
+}
+
+async fn build_report(app: &AppHandle, days: u32) -> Result<WorkspaceHealthReport, String> {
+    let state = app.state::<AppState>();
+    let listed = workspaces::list_workspaces(app.state(), app.clone()).await?;
+    let cipher = state.data_cipher().await?;
+    let today = Utc::now().date_naive();
+    let samples = workspace_health::read_samples(&state.data_dir, &cipher, days, today)?;
+    let mut report = Vec::new();
+    for workspace in listed {
+        let outcomes =
+            prompt_insights::read_outcomes(&state.data_dir, &cipher, &workspace.id, days, today)?;
+        let mut signals = workspace_health::collect_signals(&workspace.id, &samples, &outcomes);
+        (signals.doctor_errors, signals.doctor_warnings) =
+            doctor_findings(app, &workspace.id).await;
+        let (score, penalties) = workspace_health::score(&signals);
+        report.push(WorkspaceHealth {
+            workspace_id: workspace.id,
+            name: workspace.name,
+            score,
+            signals,
+            penalties,
+        });
+    }
+    report.sort_by(|a, b| a.score.cmp(&b.score).then_with(|| a.name.cmp(&b.name)));
+    Ok(WorkspaceHealthReport {
+        generated_at: Utc::now().timestamp_millis(),
+        days,
+        workspaces: report,
+    })
+}
+
+/// Sends `workspace-health-report` once a week. The first report after
+/// install is only stored, so the first one shown covers a full week.
+pub(crate) fn spawn_health_reports(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(err) = send_report_if_due(&app).await {
+                eprintln!("Failed to build workspace health report: {err}");
+            }
+            tokio::time::sleep(REPORT_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+async fn send_report_if_due(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let path = workspace_health::health_report_path(&state.data_dir);
+    let last = workspace_health::read_last_report(&path, &state.data_cipher().await?)?;
+    if last
+        .as_ref()
+        .is_some_and(|last| Utc::now().timestamp_millis() - last.generated_at < REPORT_INTERVAL_MS)
+    {
+        return Ok(());
+    }
+    let report = build_report(app, DEFAULT_HEALTH_DAYS).await?;
+    workspace_health::write_report(&path, &state.data_cipher().await?, &report)?;
+    if last.is_some() {
+        let _ = app.emit("workspace-health-report", &report);
+    }
+    Ok(())
+}
+
+/// Health score of every workspace over the last `days` days, least
+/// healthy first: session stability, failed turns, approval latency and
+/// current doctor findings.
+#[tauri::command]
+pub(crate) async fn get_workspace_health(
+    days: Option<u32>,
+    app: AppHandle,
+) -> Result<WorkspaceHealthReport, String> {
+    build_report(&app, days.unwrap_or(DEFAULT_HEALTH_DAYS).max(1)).await
+}
+
+/// The last weekly health report, if one was generated.
+#[tauri::command]
+pub(crate) async fn get_last_health_report(
+    state: State<'_, AppState>,
+) -> Result<Option<WorkspaceHealthReport>, String> {
+    let cipher = state.data_cipher().await?;
+    workspace_health::read_last_report(
+        &workspace_health::health_report_path(&state.data_dir),
+        &cipher,
+    )
+}
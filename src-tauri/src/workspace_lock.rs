@@ -0,0 +1,156 @@
+use std::path::Path;
+
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::workspace_lock::{self, LockOutcome, WorkspaceLock, LOCK_FILE_NAME};
+use crate::remote_backend;
+use crate::state::AppState;
+
+/// Payload of `workspace/lockConflict`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct LockConflict {
+    workspace_id: String,
+    lock: WorkspaceLock,
+}
+
+/// Keeps the lockfile out of `git status` through the repository's
+/// `info/exclude`, which is never committed.
+fn exclude_from_git(workspace_path: &Path) {
+    let Ok(repo) = git2::Repository::open(workspace_path) else {
+        return;
+    };
+    let exclude = repo.commondir().join("info").join("exclude");
+    let existing = std::fs::read_to_string(&exclude).unwrap_or_default();
+    if existing.lines().any(|line| line.trim() == LOCK_FILE_NAME) {
+        return;
+    }
+    let mut updated = existing;
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(LOCK_FILE_NAME);
+    updated.push('\n');
+    if let Some(parent) = exclude.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(&exclude, updated);
+}
+
+fn acquire(
+    state: &AppState,
+    app: &AppHandle,
+    workspace_id: &str,
+    workspace_path: &Path,
+    force: bool,
+) -> Result<(), String> {
+    let now = Utc::now().timestamp_millis();
+    match workspace_lock::acquire(workspace_path, now, force)? {
+        LockOutcome::Acquired(path) => {
+            let newly_held = state
+                .workspace_locks
+                .lock()
+                .map(|mut held| held.insert(path))
+                .unwrap_or(false);
+            if newly_held {
+                exclude_from_git(workspace_path);
+            }
+            Ok(())
+        }
+        LockOutcome::HeldByOther(lock) => {
+            let message = workspace_lock::conflict_message(&lock);
+            let _ = app.emit(
+                "workspace/lockConflict",
+                LockConflict {
+                    workspace_id: workspace_id.to_string(),
+                    lock,
+                },
+            );
+            Err(message)
+        }
+    }
+}
+
+/// Checked before every write-enabled turn. Refreshes this instance's lock
+/// on the checkout, or emits `workspace/lockConflict` and refuses the turn
+/// while another live instance, on this machine or another one sharing the
+/// checkout, holds it.
+pub(crate) fn ensure_workspace_lock(
+    state: &AppState,
+    app: &AppHandle,
+    workspace_id: &str,
+    workspace_path: &Path,
+) -> Result<(), String> {
+    acquire(state, app, workspace_id, workspace_path, false)
+}
+
+/// Removes the lockfiles this instance still holds; called on exit.
+pub(crate) fn release_all(state: &AppState) {
+    let held = state
+        .workspace_locks
+        .lock()
+        .map(|mut held| std::mem::take(&mut *held))
+        .unwrap_or_default();
+    for path in held {
+        workspace_lock::release_lock(&path);
+    }
+}
+
+async fn workspace_path(state: &AppState, workspace_id: &str) -> Result<String, String> {
+    state
+        .workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .map(|entry| entry.path.clone())
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))
+}
+
+/// The lock on a workspace's checkout, if any instance holds one.
+#[tauri::command]
+pub(crate) async fn get_workspace_lock(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Option<WorkspaceLock>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "get_workspace_lock",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+    let path = workspace_path(&state, &workspace_id).await?;
+    Ok(workspace_lock::read_lock(&workspace_lock::lock_path(
+        Path::new(&path),
+    )))
+}
+
+/// Overrides another instance's lock on a workspace's checkout so turns can
+/// be sent from here.
+#[tauri::command]
+pub(crate) async fn force_workspace_lock(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "force_workspace_lock",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return Ok(());
+    }
+    let path = workspace_path(&state, &workspace_id).await?;
+    acquire(&state, &app, &workspace_id, Path::new(&path), true)
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use chrono::{Local, Timelike, Utc};
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::workspace_prewarm::{self, MAX_PREWARMED};
+use crate::codex;
+use crate::state::AppState;
+use crate::workspaces;
+
+/// Delay after launch before prewarming, leaving the first connects to the
+/// window restoring its workspaces.
+const LAUNCH_PREWARM_DELAY: Duration = Duration::from_secs(20);
+/// Threads listed to warm a session.
+const PREWARM_THREAD_LIMIT: u32 = 20;
+
+fn local_hour() -> usize {
+    Local::now().hour() as usize
+}
+
+/// Connects the session if it has none yet, then lists its threads and
+/// models so the first interaction does not wait on a cold app-server.
+async fn prewarm_workspace(app: &AppHandle, workspace_id: &str, connected: bool) {
+    let state = app.state::<AppState>();
+    if !connected && !state.sessions.lock().await.contains_key(workspace_id) {
+        if let Err(err) =
+            workspaces::connect_workspace(workspace_id.to_string(), state.clone(), app.clone())
+                .await
+        {
+            eprintln!("Failed to prewarm workspace {workspace_id}: {err}");
+            return;
+        }
+    }
+    let threads = codex::list_threads(
+        workspace_id.to_string(),
+        None,
+        Some(PREWARM_THREAD_LIMIT),
+        state.clone(),
+        app.clone(),
+    )
+    .await;
+    let result = match threads {
+        Ok(_) => codex::model_list(workspace_id.to_string(), state.clone(), app.clone()).await,
+        Err(err) => Err(err),
+    };
+    if let Err(err) = result {
+        eprintln!("Failed to prewarm workspace {workspace_id}: {err}");
+    }
+}
+
+/// Prewarms the sessions of the workspaces the user usually opens around
+/// this hour of the day.
+pub(crate) async fn prewarm_usual_workspaces(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let known = match workspaces::list_workspaces(state.clone(), app.clone()).await {
+        Ok(known) => known,
+        Err(err) => {
+            eprintln!("Failed to list workspaces to prewarm: {err}");
+            return;
+        }
+    };
+    let candidates = {
+        let _guard = state.workspace_usage_writes.lock().await;
+        let path = workspace_prewarm::workspace_usage_path(&state.data_dir);
+        let result = match state.data_cipher().await {
+            Ok(cipher) => workspace_prewarm::read_usage(&path, &cipher).and_then(|mut usage| {
+                let ids: Vec<String> = known.iter().map(|entry| entry.id.clone()).collect();
+                if workspace_prewarm::retain_workspaces(&mut usage, &ids) {
+                    workspace_prewarm::write_usage(&path, &cipher, &usage)?;
+                }
+                Ok(workspace_prewarm::prewarm_candidates(
+                    &usage,
+                    local_hour(),
+                    MAX_PREWARMED,
+                ))
+            }),
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(candidates) => candidates,
+            Err(err) => {
+                eprintln!("Failed to read workspace usage: {err}");
+                return;
+            }
+        }
+    };
+    let tasks: Vec<_> = candidates
+        .into_iter()
+        .filter_map(|workspace_id| {
+            let connected = known
+                .iter()
+                .find(|entry| entry.id == workspace_id)?
+                .connected;
+            let app = app.clone();
+            Some(tokio::spawn(async move {
+                prewarm_workspace(&app, &workspace_id, connected).await;
+            }))
+        })
+        .collect();
+    for task in tasks {
+        let _ = task.await;
+    }
+}
+
+/// Prewarms the usual workspaces shortly after launch.
+pub(crate) fn spawn_launch_prewarm(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(LAUNCH_PREWARM_DELAY).await;
+        prewarm_usual_workspaces(&app).await;
+    });
+}
+
+/// Counts an open of the workspace at the current local hour, feeding the
+/// sessions prewarmed after launch and wake.
+#[tauri::command]
+pub(crate) async fn record_workspace_open(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let cipher = state.data_cipher().await?;
+    let _guard = state.workspace_usage_writes.lock().await;
+    let path = workspace_prewarm::workspace_usage_path(&state.data_dir);
+    let mut usage = workspace_prewarm::read_usage(&path, &cipher)?;
+    workspace_prewarm::record_open(
+        &mut usage,
+        &workspace_id,
+        local_hour(),
+        Utc::now().timestamp_millis(),
+    );
+    workspace_prewarm::write_usage(&path, &cipher, &usage)
+}
@@ -0,0 +1,122 @@
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::turn_summary::{params_thread_id, params_turn_id};
+use crate::backend::workspace_turns::ActiveTurn;
+use crate::codex;
+use crate::state::AppState;
+
+/// Keeps the running turn of every thread up to date; called for every
+/// `app-server-event`, local or forwarded from a remote daemon.
+pub(crate) fn observe_app_server_event(app: &AppHandle, workspace_id: &str, message: &Value) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let method = message.get("method").and_then(|value| value.as_str());
+    if method == Some("codex/connected") {
+        // A new app-server has no turns running.
+        state.workspace_turns.finish_workspace(workspace_id);
+        return;
+    }
+    let Some(params) = message.get("params") else {
+        return;
+    };
+    let Some(thread_id) = params_thread_id(params) else {
+        return;
+    };
+    match method {
+        Some("turn/started") => {
+            if let Some(turn_id) = params_turn_id(params) {
+                state.workspace_turns.started(
+                    workspace_id,
+                    &thread_id,
+                    &turn_id,
+                    chrono::Utc::now().timestamp_millis(),
+                );
+            }
+        }
+        Some("turn/completed") => state.workspace_turns.finish(workspace_id, &thread_id),
+        _ => {}
+    }
+}
+
+/// A turn's place under `maxWorkspaceTurns`, given back on drop unless the
+/// turn was sent to the app-server.
+pub(crate) struct TurnAdmission<'a> {
+    state: &'a AppState,
+    workspace_id: String,
+    thread_id: String,
+    sent: bool,
+}
+
+impl TurnAdmission<'_> {
+    /// Keeps the place after `turn/start` was sent; from then on it is given
+    /// back when the turn completes or fails to start.
+    pub(crate) fn sent(mut self) {
+        self.sent = true;
+    }
+}
+
+impl Drop for TurnAdmission<'_> {
+    fn drop(&mut self) {
+        if !self.sent {
+            self.state
+                .workspace_turns
+                .finish(&self.workspace_id, &self.thread_id);
+        }
+    }
+}
+
+/// Takes the thread's place under `maxWorkspaceTurns` before `turn/start`.
+/// With `replace`, interrupts the thread's running turn and waits for it to
+/// complete; otherwise an overlapping turn waits or fails with a
+/// `turnActive` error, per `turnOverlapMode`.
+pub(crate) async fn admit_turn<'a>(
+    state: &'a AppState,
+    app: &AppHandle,
+    workspace_id: &str,
+    thread_id: &str,
+    replace: bool,
+) -> Result<TurnAdmission<'a>, String> {
+    if replace {
+        let running = state
+            .workspace_turns
+            .active_turn(workspace_id, thread_id)
+            .and_then(|turn| turn.turn_id);
+        if let Some(turn_id) = running {
+            codex::turn_interrupt(
+                workspace_id.to_string(),
+                thread_id.to_string(),
+                turn_id,
+                app.state(),
+                app.clone(),
+            )
+            .await?;
+        }
+    }
+    state
+        .workspace_turns
+        .enter(
+            workspace_id,
+            thread_id,
+            replace,
+            chrono::Utc::now().timestamp_millis(),
+        )
+        .await
+        .map_err(ActiveTurn::into_error)?;
+    Ok(TurnAdmission {
+        state,
+        workspace_id: workspace_id.to_string(),
+        thread_id: thread_id.to_string(),
+        sent: false,
+    })
+}
+
+/// Lists running turns, optionally of one workspace, oldest first.
+#[tauri::command]
+pub(crate) async fn list_active_turns(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ActiveTurn>, String> {
+    Ok(state.workspace_turns.active(workspace_id.as_deref()))
+}
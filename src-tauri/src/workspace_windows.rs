@@ -0,0 +1,285 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{
+    AppHandle, Emitter, EventTarget, Manager, State, WebviewUrl, WebviewWindowBuilder, Window,
+};
+
+use crate::backend::i18n::{localize, MessageCode};
+use crate::clock;
+use crate::state::AppState;
+
+const WORKSPACE_WINDOW_PREFIX: &str = "workspace-";
+
+/// Workspaces each window is scoped to, keyed by window label. Windows without
+/// an entry receive events for every workspace.
+pub(crate) type WindowScopes = HashMap<String, HashSet<String>>;
+
+fn workspace_window_label(workspace_id: &str) -> String {
+    let safe: String = workspace_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{WORKSPACE_WINDOW_PREFIX}{safe}")
+}
+
+/// Returns the windows that should receive an event for `workspace_id`, or
+/// `None` when no window is scoped and the event can be broadcast.
+fn route_workspace_event(
+    scopes: &WindowScopes,
+    open_windows: &[String],
+    workspace_id: &str,
+) -> Option<Vec<String>> {
+    if scopes.is_empty() {
+        return None;
+    }
+    Some(
+        open_windows
+            .iter()
+            .filter(|label| {
+                scopes
+                    .get(*label)
+                    .is_none_or(|workspaces| workspaces.contains(workspace_id))
+            })
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Resolves event targets using the scopes tracked in `AppState`.
+fn event_targets(app: &AppHandle, workspace_id: &str) -> Option<Vec<String>> {
+    let state = app.try_state::<AppState>()?;
+    let scopes = state.window_scopes.lock().ok()?;
+    let open_windows: Vec<String> = app.webview_windows().into_keys().collect();
+    route_workspace_event(&scopes, &open_windows, workspace_id)
+}
+
+/// Adds `workspaceIdentity` to object payloads so every window and
+/// notification can show which workspace an event belongs to.
+fn with_workspace_identity<S: Serialize>(
+    app: &AppHandle,
+    workspace_id: &str,
+    payload: S,
+) -> Value {
+    let mut value = serde_json::to_value(payload).unwrap_or(Value::Null);
+    let identity = app.try_state::<AppState>().and_then(|state| {
+        state
+            .workspace_identities
+            .lock()
+            .ok()
+            .and_then(|identities| identities.get(workspace_id).cloned())
+    });
+    if let (Some(object), Some(identity)) = (value.as_object_mut(), identity) {
+        if let Ok(identity) = serde_json::to_value(identity) {
+            object.insert("workspaceIdentity".to_string(), identity);
+        }
+    }
+    value
+}
+
+fn emit_to_targets<S: Serialize + Clone>(
+    app: &AppHandle,
+    event: &str,
+    targets: Option<Vec<String>>,
+    payload: S,
+) {
+    match targets {
+        None => {
+            let _ = app.emit(event, payload);
+        }
+        Some(labels) => {
+            for label in labels {
+                let _ = app.emit_to(EventTarget::webview_window(label), event, payload.clone());
+            }
+        }
+    }
+}
+
+/// Sends a workspace event only to the windows that should see it.
+pub(crate) fn emit_for_workspace<S: Serialize + Clone>(
+    app: &AppHandle,
+    event: &str,
+    workspace_id: &str,
+    payload: S,
+) {
+    let payload = with_workspace_identity(app, workspace_id, payload);
+    emit_to_targets(app, event, event_targets(app, workspace_id), payload);
+}
+
+/// Sends an `app-server-event`, dropping it when the thread muted its method
+/// and for windows whose `subscribe_events` filters do not match the
+/// workspace and method. Muted events are still buffered for replay.
+pub(crate) fn emit_app_server_event<S: Serialize + Clone>(
+    app: &AppHandle,
+    workspace_id: &str,
+    method: Option<&str>,
+    thread_id: Option<&str>,
+    payload: S,
+) {
+    let mut targets = event_targets(app, workspace_id);
+    if let Some(state) = app.try_state::<AppState>() {
+        if let Ok(visibility) = state.reasoning_visibility.lock() {
+            if visibility.hides(workspace_id, thread_id, method) {
+                return;
+            }
+        }
+        if let Ok(subscriptions) = state.event_subscriptions.lock() {
+            if !subscriptions.is_empty() {
+                let labels = targets
+                    .unwrap_or_else(|| app.webview_windows().into_keys().collect());
+                targets = Some(
+                    labels
+                        .into_iter()
+                        .filter(|label| subscriptions.accepts(label, workspace_id, method))
+                        .collect(),
+                );
+            }
+        }
+    }
+    let mut payload = with_workspace_identity(app, workspace_id, payload);
+    if let Some(state) = app.try_state::<AppState>() {
+        let received_at = clock::now(app, &state);
+        if let Ok(mut buffer) = state.event_buffer.lock() {
+            buffer.record(workspace_id, &mut payload, received_at);
+        }
+        let request = payload
+            .get("message")
+            .is_some_and(|message| message.get("id").is_some());
+        if let Ok(filters) = state.thread_event_filters.lock() {
+            if filters.mutes(workspace_id, thread_id, method, request) {
+                return;
+            }
+        }
+    }
+    emit_to_targets(app, "app-server-event", targets, payload);
+}
+
+pub(crate) fn forget_window(app: &AppHandle, label: &str) {
+    if let Some(state) = app.try_state::<AppState>() {
+        if let Ok(mut scopes) = state.window_scopes.lock() {
+            scopes.remove(label);
+        }
+        if let Ok(mut subscriptions) = state.event_subscriptions.lock() {
+            subscriptions.forget_window(label);
+        }
+    }
+}
+
+/// Opens (or re-focuses) a window scoped to a single workspace.
+#[tauri::command]
+pub(crate) async fn open_workspace_window(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let name = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?;
+        state.access_control.lock().await.ensure_visible(entry)?;
+        entry.name.clone()
+    };
+    let label = workspace_window_label(&workspace_id);
+    if let Ok(mut scopes) = state.window_scopes.lock() {
+        scopes.insert(label.clone(), HashSet::from([workspace_id.clone()]));
+    }
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.show();
+        let _ = window.set_focus();
+        return Ok(label);
+    }
+    let url = format!("index.html?workspaceId={workspace_id}");
+    WebviewWindowBuilder::new(&app, &label, WebviewUrl::App(url.into()))
+        .title(format!("CodexMonitor — {name}"))
+        .inner_size(900.0, 700.0)
+        .min_inner_size(360.0, 600.0)
+        .build()
+        .map_err(|err| format!("Failed to open window: {err}"))?;
+    Ok(label)
+}
+
+/// Focuses the window scoped to `workspace_id`. Returns false if none is open.
+#[tauri::command]
+pub(crate) async fn focus_workspace_window(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<bool, String> {
+    let label = {
+        let scopes = state.window_scopes.lock().map_err(|err| err.to_string())?;
+        scopes
+            .iter()
+            .filter(|(_, workspaces)| workspaces.contains(&workspace_id))
+            .map(|(label, _)| label.clone())
+            .min()
+    };
+    let Some(window) = label.and_then(|label| app.get_webview_window(&label)) else {
+        return Ok(false);
+    };
+    let _ = window.unminimize();
+    let _ = window.show();
+    window.set_focus().map_err(|err| err.to_string())?;
+    Ok(true)
+}
+
+/// Scopes the calling window to the given workspaces; `None` receives all.
+#[tauri::command]
+pub(crate) async fn set_window_workspaces(
+    workspace_ids: Option<Vec<String>>,
+    state: State<'_, AppState>,
+    window: Window,
+) -> Result<(), String> {
+    let mut scopes = state.window_scopes.lock().map_err(|err| err.to_string())?;
+    match workspace_ids {
+        Some(ids) => {
+            scopes.insert(window.label().to_string(), ids.into_iter().collect());
+        }
+        None => {
+            scopes.remove(window.label());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_to_scoped_and_unscoped_windows() {
+        let open = vec![
+            "main".to_string(),
+            "workspace-a".to_string(),
+            "workspace-b".to_string(),
+        ];
+        assert_eq!(route_workspace_event(&WindowScopes::new(), &open, "a"), None);
+
+        let mut scopes = WindowScopes::new();
+        scopes.insert("workspace-a".to_string(), HashSet::from(["a".to_string()]));
+        scopes.insert("workspace-b".to_string(), HashSet::from(["b".to_string()]));
+        assert_eq!(
+            route_workspace_event(&scopes, &open, "a"),
+            Some(vec!["main".to_string(), "workspace-a".to_string()])
+        );
+
+        scopes.insert("main".to_string(), HashSet::new());
+        assert_eq!(
+            route_workspace_event(&scopes, &open, "c"),
+            Some(Vec::new())
+        );
+    }
+
+    #[test]
+    fn window_labels_are_sanitized() {
+        assert_eq!(workspace_window_label("abc-123"), "workspace-abc-123");
+        assert_eq!(workspace_window_label("a/b c"), "workspace-a-b-c");
+    }
+}
@@ -9,6 +9,10 @@ use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 use uuid::Uuid;
 
+use crate::backend::i18n::{localize, MessageCode};
+use crate::backend::resource_limits;
+use crate::backend::workspace_identity::{normalize_color, normalize_icon};
+use crate::backend::workspace_validation;
 use crate::codex::spawn_workspace_session;
 use crate::codex_home::resolve_workspace_codex_home;
 use crate::remote_backend;
@@ -61,7 +65,7 @@ fn sanitize_clone_dir_name(name: &str) -> String {
     }
 }
 
-fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
+pub(crate) fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
     let mut results = Vec::new();
     let walker = WalkBuilder::new(root)
         // Allow hidden entries.
@@ -123,10 +127,15 @@ fn apply_workspace_settings_update(
 ) -> Result<WorkspaceEntry, String> {
     match workspaces.get_mut(id) {
         Some(entry) => {
-            entry.settings = settings.clone();
+            // Only `spawn_scratch_workspace` marks a workspace as scratch.
+            let scratch = entry.settings.scratch.take();
+            entry.settings = WorkspaceSettings {
+                scratch,
+                ..settings
+            };
             Ok(entry.clone())
         }
-        None => Err("workspace not found".to_string()),
+        None => Err(localize(MessageCode::WorkspaceNotFound)),
     }
 }
 
@@ -397,9 +406,11 @@ pub(crate) async fn list_workspaces(
 
     let workspaces = state.workspaces.lock().await;
     let sessions = state.sessions.lock().await;
+    let access = state.access_control.lock().await;
     let mut result = Vec::new();
-    for entry in workspaces.values() {
+    for entry in workspaces.values().filter(|entry| access.is_visible(entry)) {
         result.push(WorkspaceInfo {
+            identity: entry.identity(),
             id: entry.id.clone(),
             name: entry.name.clone(),
             path: entry.path.clone(),
@@ -433,20 +444,25 @@ pub(crate) async fn add_workspace(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    let name = PathBuf::from(&path)
-        .file_name()
-        .and_then(|s| s.to_str())
-        .unwrap_or("Workspace")
-        .to_string();
+    let existing: Vec<WorkspaceEntry> = state.workspaces.lock().await.values().cloned().collect();
+    let normalized = workspace_validation::validate_new_workspace(
+        &path,
+        codex_bin.as_deref(),
+        &existing,
+        std::env::var_os("PATH").as_deref(),
+    )
+    .map_err(workspace_validation::into_error)?;
     let entry = WorkspaceEntry {
         id: Uuid::new_v4().to_string(),
-        name: name.clone(),
-        path: path.clone(),
-        codex_bin,
+        name: normalized.name,
+        path: normalized.path,
+        codex_bin: normalized.codex_bin,
         kind: WorkspaceKind::Main,
         parent_id: None,
         worktree: None,
         settings: WorkspaceSettings::default(),
+        icon: None,
+        color: None,
     };
 
     let default_bin = {
@@ -459,6 +475,7 @@ pub(crate) async fn add_workspace(
     if let Err(error) = {
         let mut workspaces = state.workspaces.lock().await;
         workspaces.insert(entry.id.clone(), entry.clone());
+        state.cache_workspace_identity(&entry);
         let list: Vec<_> = workspaces.values().cloned().collect();
         write_workspaces(&state.storage_path, &list)
     } {
@@ -478,6 +495,7 @@ pub(crate) async fn add_workspace(
         .insert(entry.id.clone(), session);
 
     Ok(WorkspaceInfo {
+        identity: entry.identity(),
         id: entry.id,
         name: entry.name,
         path: entry.path,
@@ -565,6 +583,8 @@ pub(crate) async fn add_clone(
             group_id: inherited_group_id,
             ..WorkspaceSettings::default()
         },
+        icon: None,
+        color: None,
     };
 
     let default_bin = {
@@ -583,6 +603,7 @@ pub(crate) async fn add_clone(
     if let Err(error) = {
         let mut workspaces = state.workspaces.lock().await;
         workspaces.insert(entry.id.clone(), entry.clone());
+        state.cache_workspace_identity(&entry);
         let list: Vec<_> = workspaces.values().cloned().collect();
         write_workspaces(&state.storage_path, &list)
     } {
@@ -603,6 +624,7 @@ pub(crate) async fn add_clone(
         .insert(entry.id.clone(), session);
 
     Ok(WorkspaceInfo {
+        identity: entry.identity(),
         id: entry.id,
         name: entry.name,
         path: entry.path,
@@ -678,6 +700,8 @@ pub(crate) async fn add_worktree(
             branch: branch.to_string(),
         }),
         settings: WorkspaceSettings::default(),
+        icon: None,
+        color: None,
     };
 
     let default_bin = {
@@ -689,6 +713,7 @@ pub(crate) async fn add_worktree(
     {
         let mut workspaces = state.workspaces.lock().await;
         workspaces.insert(entry.id.clone(), entry.clone());
+        state.cache_workspace_identity(&entry);
         let list: Vec<_> = workspaces.values().cloned().collect();
         write_workspaces(&state.storage_path, &list)?;
     }
@@ -699,6 +724,7 @@ pub(crate) async fn add_worktree(
         .insert(entry.id.clone(), session);
 
     Ok(WorkspaceInfo {
+        identity: entry.identity(),
         id: entry.id,
         name: entry.name,
         path: entry.path,
@@ -721,7 +747,7 @@ pub(crate) async fn remove_workspace(
         let entry = workspaces
             .get(&id)
             .cloned()
-            .ok_or("workspace not found")?;
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?;
         if entry.kind.is_worktree() {
             return Err("Use remove_worktree for worktree agents.".to_string());
         }
@@ -789,7 +815,7 @@ pub(crate) async fn remove_worktree(
         let entry = workspaces
             .get(&id)
             .cloned()
-            .ok_or("workspace not found")?;
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?;
         if !entry.kind.is_worktree() {
             return Err("Not a worktree workspace.".to_string());
         }
@@ -869,7 +895,7 @@ pub(crate) async fn rename_worktree(
         let entry = workspaces
             .get(&id)
             .cloned()
-            .ok_or("workspace not found")?;
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?;
         if !entry.kind.is_worktree() {
             return Err("Not a worktree workspace.".to_string());
         }
@@ -940,7 +966,7 @@ pub(crate) async fn rename_worktree(
         let mut workspaces = state.workspaces.lock().await;
         let entry = match workspaces.get_mut(&id) {
             Some(entry) => entry,
-            None => return Err("workspace not found".to_string()),
+            None => return Err(localize(MessageCode::WorkspaceNotFound)),
         };
         entry.name = final_branch.clone();
         entry.path = next_path_string.clone();
@@ -955,6 +981,7 @@ pub(crate) async fn rename_worktree(
             }
         }
         let snapshot = entry.clone();
+        state.cache_workspace_identity(&snapshot);
         let list: Vec<_> = workspaces.values().cloned().collect();
         (snapshot, list)
     };
@@ -990,6 +1017,7 @@ pub(crate) async fn rename_worktree(
 
     let connected = state.sessions.lock().await.contains_key(&entry_snapshot.id);
     Ok(WorkspaceInfo {
+        identity: entry_snapshot.identity(),
         id: entry_snapshot.id,
         name: entry_snapshot.name,
         path: entry_snapshot.path,
@@ -1035,7 +1063,7 @@ pub(crate) async fn rename_worktree_upstream(
         let entry = workspaces
             .get(&id)
             .cloned()
-            .ok_or("workspace not found")?;
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?;
         if !entry.kind.is_worktree() {
             return Err("Not a worktree workspace.".to_string());
         }
@@ -1114,7 +1142,7 @@ pub(crate) async fn apply_worktree_changes(
         let entry = workspaces
             .get(&workspace_id)
             .cloned()
-            .ok_or("workspace not found")?;
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?;
         if !entry.kind.is_worktree() {
             return Err("Not a worktree workspace.".to_string());
         }
@@ -1237,16 +1265,31 @@ pub(crate) async fn update_workspace_settings(
     settings: WorkspaceSettings,
     state: State<'_, AppState>,
 ) -> Result<WorkspaceInfo, String> {
+    if let Some(limits) = &settings.resource_limits {
+        resource_limits::validate(limits)?;
+    }
     let (entry_snapshot, list) = {
         let mut workspaces = state.workspaces.lock().await;
+        if let Some(current) = workspaces.get(&id) {
+            let access = state.access_control.lock().await;
+            if current.settings.restricted && !settings.restricted {
+                access.ensure_unlocked(current)?;
+            } else if settings.restricted && !access.passphrase_set() {
+                return Err("Set an app passphrase before restricting a workspace.".to_string());
+            }
+        }
         let entry_snapshot = apply_workspace_settings_update(&mut workspaces, &id, settings)?;
         let list: Vec<_> = workspaces.values().cloned().collect();
         (entry_snapshot, list)
     };
     write_workspaces(&state.storage_path, &list)?;
+    if let Ok(mut visibility) = state.reasoning_visibility.lock() {
+        visibility.set_workspace(&id, entry_snapshot.settings.show_reasoning_summaries);
+    }
 
     let connected = state.sessions.lock().await.contains_key(&id);
     Ok(WorkspaceInfo {
+        identity: entry_snapshot.identity(),
         id: entry_snapshot.id,
         name: entry_snapshot.name,
         path: entry_snapshot.path,
@@ -1272,15 +1315,58 @@ pub(crate) async fn update_workspace_codex_bin(
                 entry.codex_bin = codex_bin.clone();
                 entry.clone()
             }
-            None => return Err("workspace not found".to_string()),
+            None => return Err(localize(MessageCode::WorkspaceNotFound)),
+        };
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        (entry_snapshot, list)
+    };
+    write_workspaces(&state.storage_path, &list)?;
+
+    let connected = state.sessions.lock().await.contains_key(&id);
+    Ok(WorkspaceInfo {
+        identity: entry_snapshot.identity(),
+        id: entry_snapshot.id,
+        name: entry_snapshot.name,
+        path: entry_snapshot.path,
+        codex_bin: entry_snapshot.codex_bin,
+        connected,
+        kind: entry_snapshot.kind,
+        parent_id: entry_snapshot.parent_id,
+        worktree: entry_snapshot.worktree,
+        settings: entry_snapshot.settings,
+    })
+}
+
+/// Sets the icon and color of a workspace; `None` restores the default
+/// derived from its path.
+#[tauri::command]
+pub(crate) async fn update_workspace_identity(
+    id: String,
+    icon: Option<String>,
+    color: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceInfo, String> {
+    let icon = icon.as_deref().map(normalize_icon).transpose()?;
+    let color = color.as_deref().map(normalize_color).transpose()?;
+    let (entry_snapshot, list) = {
+        let mut workspaces = state.workspaces.lock().await;
+        let entry_snapshot = match workspaces.get_mut(&id) {
+            Some(entry) => {
+                entry.icon = icon;
+                entry.color = color;
+                entry.clone()
+            }
+            None => return Err(localize(MessageCode::WorkspaceNotFound)),
         };
         let list: Vec<_> = workspaces.values().cloned().collect();
         (entry_snapshot, list)
     };
     write_workspaces(&state.storage_path, &list)?;
+    state.cache_workspace_identity(&entry_snapshot);
 
     let connected = state.sessions.lock().await.contains_key(&id);
     Ok(WorkspaceInfo {
+        identity: entry_snapshot.identity(),
         id: entry_snapshot.id,
         name: entry_snapshot.name,
         path: entry_snapshot.path,
@@ -1318,8 +1404,9 @@ pub(crate) async fn connect_workspace(
                     .map(|parent| parent.path.clone());
                 (entry, parent_path)
             })
-            .ok_or("workspace not found")?
+            .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?
     };
+    state.access_control.lock().await.ensure_visible(&entry)?;
 
     let default_bin = {
         let settings = state.app_settings.lock().await;
@@ -1351,7 +1438,7 @@ pub(crate) async fn list_workspace_files(
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
-        .ok_or("workspace not found")?;
+        .ok_or_else(|| localize(MessageCode::WorkspaceNotFound))?;
     let root = PathBuf::from(&entry.path);
     Ok(list_workspace_files_inner(&root, usize::MAX))
 }
@@ -1384,7 +1471,10 @@ mod tests {
         sanitize_worktree_name, sort_workspaces,
     };
     use crate::storage::{read_workspaces, write_workspaces};
-    use crate::types::{WorktreeInfo, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings};
+    use crate::types::{
+        WorktreeInfo, WorkspaceEntry, WorkspaceIdentity, WorkspaceInfo, WorkspaceKind,
+        WorkspaceSettings,
+    };
     use uuid::Uuid;
 
     fn workspace(name: &str, sort_order: Option<u32>) -> WorkspaceInfo {
@@ -1408,6 +1498,7 @@ mod tests {
             (None, None)
         };
         WorkspaceInfo {
+            identity: WorkspaceIdentity::default(),
             id: id.to_string(),
             name: name.to_string(),
             path: "/tmp".to_string(),
@@ -1421,6 +1512,15 @@ mod tests {
                 sort_order,
                 group_id: None,
                 git_root: None,
+                restricted: false,
+                issue_tracker: None,
+                stderr_filter: None,
+                verification: None,
+                approvals_break_through_dnd: false,
+                auto_branch: None,
+                show_reasoning_summaries: None,
+                scratch: None,
+                resource_limits: None,
             },
         }
     }
@@ -1563,6 +1663,8 @@ mod tests {
             parent_id: None,
             worktree: None,
             settings: WorkspaceSettings::default(),
+            icon: None,
+            color: None,
         };
         let mut workspaces = HashMap::from([(id.clone(), entry)]);
 
@@ -0,0 +1,11 @@
+#![cfg(feature = "protocol-harness")]
+
+use std::path::Path;
+
+#[test]
+fn sessions_survive_protocol_edge_cases() {
+    let server = Path::new(env!("CARGO_BIN_EXE_fake-app-server"));
+    if let Err(failures) = codex_monitor_lib::run_protocol_harness(server) {
+        panic!("protocol harness failures:\n{failures}");
+    }
+}
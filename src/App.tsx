@@ -13,6 +13,7 @@ import "./styles/diff-viewer.css";
 import "./styles/file-tree.css";
 import "./styles/panel-tabs.css";
 import "./styles/prompts.css";
+import "./styles/team-activity.css";
 import "./styles/debug.css";
 import "./styles/terminal.css";
 import "./styles/plan.css";
@@ -46,9 +47,11 @@ import { useWorkspaceFiles } from "./features/workspaces/hooks/useWorkspaceFiles
 import { useGitBranches } from "./features/git/hooks/useGitBranches";
 import { useDebugLog } from "./features/debug/hooks/useDebugLog";
 import { useWorkspaceRefreshOnFocus } from "./features/workspaces/hooks/useWorkspaceRefreshOnFocus";
+import { useWorkspaceLockConflicts } from "./features/workspaces/hooks/useWorkspaceLockConflicts";
 import { useWorkspaceRestore } from "./features/workspaces/hooks/useWorkspaceRestore";
 import { useRenameWorktreePrompt } from "./features/workspaces/hooks/useRenameWorktreePrompt";
 import { useLayoutController } from "./features/app/hooks/useLayoutController";
+import { useApprovalShortcuts } from "./features/app/hooks/useApprovalShortcuts";
 import { useWindowLabel } from "./features/layout/hooks/useWindowLabel";
 import { revealItemInDir } from "@tauri-apps/plugin-opener";
 import {
@@ -79,6 +82,7 @@ import { useThreadRows } from "./features/app/hooks/useThreadRows";
 import { useCopyThread } from "./features/threads/hooks/useCopyThread";
 import { useTerminalController } from "./features/terminal/hooks/useTerminalController";
 import { useGitCommitController } from "./features/app/hooks/useGitCommitController";
+import { useUserActivityReporter } from "./features/app/hooks/useUserActivityReporter";
 import { pickWorkspacePath } from "./services/tauri";
 import type {
   AccessMode,
@@ -122,6 +126,7 @@ function MainApp() {
     dictationState,
     dictationLevel,
     dictationTranscript,
+    dictationPartial,
     dictationError,
     dictationHint,
     dictationReady,
@@ -241,6 +246,10 @@ function MainApp() {
     errorSoundUrl,
   });
 
+  useUserActivityReporter({
+    isEnabled: Boolean(appSettings.autoLockMinutes),
+  });
+
   useEffect(() => {
     setAccessMode((prev) =>
       prev === "current" ? appSettings.defaultAccessMode : prev
@@ -542,7 +551,9 @@ function MainApp() {
     sendUserMessageToThread,
     startReview,
     handleApprovalDecision,
-    handleApprovalRemember
+    handleApprovalRemember,
+    handleApprovalGrantDirectory,
+    removeApproval,
   } = useThreads({
     activeWorkspace,
     onWorkspaceConnected: markWorkspaceConnected,
@@ -554,6 +565,13 @@ function MainApp() {
     customPrompts: prompts,
     onMessageActivity: queueGitStatusRefresh
   });
+  useApprovalShortcuts({
+    activeWorkspaceId,
+    acceptApprovalShortcut: appSettings.acceptApprovalShortcut,
+    declineApprovalShortcut: appSettings.declineApprovalShortcut,
+    onAnswered: removeApproval,
+    onDebug: addDebugEntry,
+  });
   const activeThreadIdRef = useRef<string | null>(activeThreadId ?? null);
   const { getThreadRows } = useThreadRows(threadParentById);
   useEffect(() => {
@@ -1038,6 +1056,8 @@ function MainApp() {
     listThreadsForWorkspace
   });
 
+  useWorkspaceLockConflicts({ workspaces });
+
   const {
     handleAddWorkspace,
     handleAddAgent,
@@ -1247,6 +1267,7 @@ function MainApp() {
     approvals,
     handleApprovalDecision,
     handleApprovalRemember,
+    handleApprovalGrantDirectory,
     onOpenSettings: () => openSettings(),
     onOpenDictationSettings: () => openSettings("dictation"),
     onOpenDebug: handleDebugClick,
@@ -1525,6 +1546,7 @@ function MainApp() {
     onDictationTranscriptHandled: (id) => {
       clearDictationTranscript(id);
     },
+    dictationPartial,
     dictationError,
     onDismissDictationError: clearDictationError,
     dictationHint,
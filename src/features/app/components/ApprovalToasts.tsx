@@ -7,6 +7,7 @@ type ApprovalToastsProps = {
   workspaces: WorkspaceInfo[];
   onDecision: (request: ApprovalRequest, decision: "accept" | "decline") => void;
   onRemember?: (request: ApprovalRequest, command: string[]) => void;
+  onGrantDirectory?: (request: ApprovalRequest, path: string) => void;
 };
 
 export function ApprovalToasts({
@@ -14,6 +15,7 @@ export function ApprovalToasts({
   workspaces,
   onDecision,
   onRemember,
+  onGrantDirectory,
 }: ApprovalToastsProps) {
   const workspaceLabels = useMemo(
     () => new Map(workspaces.map((workspace) => [workspace.id, workspace.name])),
@@ -87,6 +89,10 @@ export function ApprovalToasts({
         const params = request.params ?? {};
         const commandInfo = getApprovalCommandInfo(params);
         const entries = Object.entries(params);
+        const grantRoot =
+          typeof params.grantRoot === "string" && params.grantRoot.trim()
+            ? params.grantRoot
+            : null;
         return (
           <div
             key={`${request.workspace_id}-${request.request_id}`}
@@ -100,6 +106,11 @@ export function ApprovalToasts({
               ) : null}
             </div>
             <div className="approval-toast-method">{methodLabel(request.method)}</div>
+            {grantRoot ? (
+              <div className="approval-toast-grant">
+                Agent wants to write to <code>{grantRoot}</code>
+              </div>
+            ) : null}
             <div className="approval-toast-details">
               {entries.length ? (
                 entries.map(([key, value]) => {
@@ -143,6 +154,15 @@ export function ApprovalToasts({
                   Always allow
                 </button>
               ) : null}
+              {grantRoot && onGrantDirectory ? (
+                <button
+                  className="ghost approval-toast-remember"
+                  onClick={() => onGrantDirectory(request, grantRoot)}
+                  title={`Let later turns of this thread write to ${grantRoot}`}
+                >
+                  Allow for this thread
+                </button>
+              ) : null}
               <button
                 className="primary"
                 onClick={() => onDecision(request, "accept")}
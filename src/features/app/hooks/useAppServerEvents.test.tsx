@@ -164,4 +164,32 @@ describe("useAppServerEvents", () => {
       root.unmount();
     });
   });
+
+  it("routes plan step completions with their progress", async () => {
+    const handlers: Handlers = {
+      onPlanStepCompleted: vi.fn(),
+    };
+    const { root } = await mount(handlers);
+
+    const params = {
+      threadId: "thread-1",
+      turnId: "turn-1",
+      stepIndex: 0,
+      step: "Read code",
+      completedSteps: 1,
+      totalSteps: 3,
+    };
+    act(() => {
+      listener?.({
+        workspace_id: "ws-1",
+        message: { method: "plan/stepCompleted", params },
+      });
+    });
+
+    expect(handlers.onPlanStepCompleted).toHaveBeenCalledWith("ws-1", params);
+
+    await act(async () => {
+      root.unmount();
+    });
+  });
 });
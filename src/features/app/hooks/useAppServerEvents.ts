@@ -1,5 +1,11 @@
 import { useEffect } from "react";
-import type { AppServerEvent, ApprovalRequest } from "../../../types";
+import type {
+  AppServerEvent,
+  ApprovalRequest,
+  PlanStepCompleted,
+  ThreadBackfill,
+  TurnSummary,
+} from "../../../types";
 import { subscribeAppServerEvents } from "../../../services/events";
 
 type AgentDelta = {
@@ -23,7 +29,12 @@ type AppServerEventHandlers = {
   onAgentMessageCompleted?: (event: AgentCompleted) => void;
   onAppServerEvent?: (event: AppServerEvent) => void;
   onTurnStarted?: (workspaceId: string, threadId: string, turnId: string) => void;
-  onTurnCompleted?: (workspaceId: string, threadId: string, turnId: string) => void;
+  onTurnCompleted?: (
+    workspaceId: string,
+    threadId: string,
+    turnId: string,
+    summary: TurnSummary | null,
+  ) => void;
   onTurnError?: (
     workspaceId: string,
     threadId: string,
@@ -36,6 +47,7 @@ type AppServerEventHandlers = {
     turnId: string,
     payload: { explanation: unknown; plan: unknown },
   ) => void;
+  onPlanStepCompleted?: (workspaceId: string, event: PlanStepCompleted) => void;
   onItemStarted?: (workspaceId: string, threadId: string, item: Record<string, unknown>) => void;
   onItemCompleted?: (workspaceId: string, threadId: string, item: Record<string, unknown>) => void;
   onReasoningSummaryDelta?: (workspaceId: string, threadId: string, itemId: string, delta: string) => void;
@@ -73,7 +85,10 @@ export function useAppServerEvents(handlers: AppServerEventHandlers) {
         return;
       }
 
-      if (method.includes("requestApproval") && typeof message.id === "number") {
+      if (
+        method.includes("requestApproval") &&
+        (typeof message.id === "number" || typeof message.id === "string")
+      ) {
         handlers.onApprovalRequest?.({
           workspace_id,
           request_id: message.id,
@@ -135,8 +150,9 @@ export function useAppServerEvents(handlers: AppServerEventHandlers) {
           params.threadId ?? params.thread_id ?? turn?.threadId ?? turn?.thread_id ?? "",
         );
         const turnId = String(turn?.id ?? params.turnId ?? params.turn_id ?? "");
+        const summary = (params.summary as TurnSummary | undefined) ?? null;
         if (threadId) {
-          handlers.onTurnCompleted?.(workspace_id, threadId, turnId);
+          handlers.onTurnCompleted?.(workspace_id, threadId, turnId, summary);
         }
         return;
       }
@@ -154,6 +170,14 @@ export function useAppServerEvents(handlers: AppServerEventHandlers) {
         return;
       }
 
+      if (method === "plan/stepCompleted") {
+        const params = message.params as PlanStepCompleted;
+        if (params?.threadId) {
+          handlers.onPlanStepCompleted?.(workspace_id, params);
+        }
+        return;
+      }
+
       if (method === "turn/diff/updated") {
         const params = message.params as Record<string, unknown>;
         const threadId = String(params.threadId ?? params.thread_id ?? "");
@@ -209,6 +233,16 @@ export function useAppServerEvents(handlers: AppServerEventHandlers) {
         return;
       }
 
+      if (method === "thread/backfill") {
+        const params = message.params as ThreadBackfill;
+        if (params?.threadId) {
+          params.items.forEach(({ item }) => {
+            handlers.onItemCompleted?.(workspace_id, params.threadId, item);
+          });
+        }
+        return;
+      }
+
       if (method === "item/started") {
         const params = message.params as Record<string, unknown>;
         const threadId = String(params.threadId ?? params.thread_id ?? "");
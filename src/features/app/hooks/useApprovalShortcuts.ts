@@ -0,0 +1,61 @@
+import { useEffect } from "react";
+import type { DebugEntry, RequestId } from "../../../types";
+import { respondToNextApproval, setFocusedWorkspace } from "../../../services/tauri";
+import { matchesShortcut } from "../../../utils/shortcuts";
+
+type UseApprovalShortcutsOptions = {
+  activeWorkspaceId: string | null;
+  acceptApprovalShortcut: string | null;
+  declineApprovalShortcut: string | null;
+  onAnswered: (workspaceId: string, requestId: RequestId) => void;
+  onDebug?: (entry: DebugEntry) => void;
+};
+
+// The backend tracks pending approvals, so the shortcuts only report which
+// workspace is focused and the backend picks the oldest request.
+export function useApprovalShortcuts({
+  activeWorkspaceId,
+  acceptApprovalShortcut,
+  declineApprovalShortcut,
+  onAnswered,
+  onDebug,
+}: UseApprovalShortcutsOptions) {
+  useEffect(() => {
+    void setFocusedWorkspace(activeWorkspaceId).catch(() => {});
+  }, [activeWorkspaceId]);
+
+  useEffect(() => {
+    const respond = async (decision: "accept" | "decline") => {
+      try {
+        const answered = await respondToNextApproval(decision);
+        if (answered) {
+          onAnswered(answered.workspaceId, answered.requestId);
+        }
+      } catch (error) {
+        onDebug?.({
+          id: `${Date.now()}-client-approval-shortcut-error`,
+          timestamp: Date.now(),
+          source: "error",
+          label: "approval shortcut error",
+          payload: error instanceof Error ? error.message : String(error),
+        });
+      }
+    };
+    const handleKeyDown = (event: KeyboardEvent) => {
+      if (event.repeat || event.defaultPrevented) {
+        return;
+      }
+      if (matchesShortcut(event, acceptApprovalShortcut)) {
+        event.preventDefault();
+        void respond("accept");
+        return;
+      }
+      if (matchesShortcut(event, declineApprovalShortcut)) {
+        event.preventDefault();
+        void respond("decline");
+      }
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, [acceptApprovalShortcut, declineApprovalShortcut, onAnswered, onDebug]);
+}
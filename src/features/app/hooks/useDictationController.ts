@@ -9,6 +9,7 @@ type DictationController = {
   dictationState: ReturnType<typeof useDictation>["state"];
   dictationLevel: ReturnType<typeof useDictation>["level"];
   dictationTranscript: ReturnType<typeof useDictation>["transcript"];
+  dictationPartial: ReturnType<typeof useDictation>["partialTranscript"];
   dictationError: ReturnType<typeof useDictation>["error"];
   dictationHint: ReturnType<typeof useDictation>["hint"];
   dictationReady: boolean;
@@ -27,6 +28,7 @@ export function useDictationController(appSettings: AppSettings): DictationContr
     state: dictationState,
     level: dictationLevel,
     transcript: dictationTranscript,
+    partialTranscript: dictationPartial,
     error: dictationError,
     hint: dictationHint,
     start: startDictation,
@@ -101,6 +103,7 @@ export function useDictationController(appSettings: AppSettings): DictationContr
     dictationState,
     dictationLevel,
     dictationTranscript,
+    dictationPartial,
     dictationError,
     dictationHint,
     dictationReady,
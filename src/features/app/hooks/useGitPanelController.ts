@@ -4,6 +4,7 @@ import { useGitStatus } from "../../git/hooks/useGitStatus";
 import { useGitDiffs } from "../../git/hooks/useGitDiffs";
 import { useGitLog } from "../../git/hooks/useGitLog";
 import { useGitCommitDiffs } from "../../git/hooks/useGitCommitDiffs";
+import { recordFeatureUsage } from "../../../services/tauri";
 
 export function useGitPanelController({
   activeWorkspace,
@@ -37,7 +38,7 @@ export function useGitPanelController({
     "split" | "unified"
   >("split");
   const [filePanelMode, setFilePanelMode] = useState<
-    "git" | "files" | "prompts"
+    "git" | "files" | "prompts" | "team"
   >("git");
   const [selectedPullRequest, setSelectedPullRequest] =
     useState<GitHubPullRequest | null>(null);
@@ -59,6 +60,11 @@ export function useGitPanelController({
     activeWorkspaceIdRef.current = activeWorkspace?.id ?? null;
   }, [activeWorkspace?.id]);
 
+  useEffect(() => {
+    // Feeds the opt-in usage insights; a no-op unless enabled.
+    recordFeatureUsage(`panel.${filePanelMode}`).catch(() => {});
+  }, [filePanelMode]);
+
   useEffect(() => {
     activeWorkspaceRef.current = activeWorkspace;
   }, [activeWorkspace]);
@@ -2,6 +2,7 @@ import { useCallback, type MouseEvent } from "react";
 import { Menu, MenuItem } from "@tauri-apps/api/menu";
 import { LogicalPosition } from "@tauri-apps/api/dpi";
 import { getCurrentWindow } from "@tauri-apps/api/window";
+import { openInTerminal } from "../../../services/tauri";
 
 type SidebarMenuHandlers = {
   onDeleteThread: (workspaceId: string, threadId: string) => void;
@@ -51,6 +52,19 @@ export function useSidebarMenus({
           }
         },
       });
+      const terminalItem = await MenuItem.new({
+        text: "Open in Terminal",
+        action: async () => {
+          try {
+            const handoff = await openInTerminal(workspaceId, threadId);
+            if (!handoff.launched) {
+              await navigator.clipboard.writeText(handoff.commandLine);
+            }
+          } catch {
+            // The workspace may be disconnected; nothing to hand off.
+          }
+        },
+      });
       const items = [renameItem];
       if (canPin) {
         const isPinned = isThreadPinned(workspaceId, threadId);
@@ -67,7 +81,7 @@ export function useSidebarMenus({
           }),
         );
       }
-      items.push(copyItem, archiveItem);
+      items.push(copyItem, terminalItem, archiveItem);
       const menu = await Menu.new({ items });
       const window = getCurrentWindow();
       const position = new LogicalPosition(event.clientX, event.clientY);
@@ -0,0 +1,39 @@
+import { useEffect, useRef } from "react";
+import { reportUserActivity } from "../../../services/tauri";
+
+const REPORT_INTERVAL_MS = 30_000;
+const ACTIVITY_EVENTS = ["pointerdown", "keydown", "wheel"] as const;
+
+type UseUserActivityReporterOptions = {
+  isEnabled: boolean;
+};
+
+// The auto-lock timeout is enforced by the backend; it only learns about
+// interaction from these reports, sent at most every 30 seconds.
+export function useUserActivityReporter({
+  isEnabled,
+}: UseUserActivityReporterOptions) {
+  const lastReportedAt = useRef(0);
+
+  useEffect(() => {
+    if (!isEnabled) {
+      return;
+    }
+    function handleActivity() {
+      const now = Date.now();
+      if (now - lastReportedAt.current < REPORT_INTERVAL_MS) {
+        return;
+      }
+      lastReportedAt.current = now;
+      void reportUserActivity().catch(() => {});
+    }
+    for (const eventName of ACTIVITY_EVENTS) {
+      window.addEventListener(eventName, handleActivity, { passive: true });
+    }
+    return () => {
+      for (const eventName of ACTIVITY_EVENTS) {
+        window.removeEventListener(eventName, handleActivity);
+      }
+    };
+  }, [isEnabled]);
+}
@@ -2,6 +2,7 @@ import type { RefObject } from "react";
 import { useCallback } from "react";
 import { useNewAgentShortcut } from "./useNewAgentShortcut";
 import type { DebugEntry, WorkspaceInfo } from "../../../types";
+import { formatWorkspaceError } from "../../../utils/workspaceValidation";
 
 type Params = {
   activeWorkspace: WorkspaceInfo | null;
@@ -44,7 +45,7 @@ export function useWorkspaceActions({
         }
       }
     } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
+      const message = formatWorkspaceError(error);
       onDebug({
         id: `${Date.now()}-client-add-workspace-error`,
         timestamp: Date.now(),
@@ -57,6 +57,7 @@ type ComposerProps = {
   onOpenDictationSettings?: () => void;
   dictationTranscript?: DictationTranscript | null;
   onDictationTranscriptHandled?: (id: string) => void;
+  dictationPartial?: string | null;
   dictationError?: string | null;
   onDismissDictationError?: () => void;
   dictationHint?: string | null;
@@ -108,6 +109,7 @@ export function Composer({
   onOpenDictationSettings,
   dictationTranscript = null,
   onDictationTranscriptHandled,
+  dictationPartial = null,
   dictationError = null,
   onDismissDictationError,
   dictationHint = null,
@@ -252,6 +254,7 @@ export function Composer({
         dictationLevel={dictationLevel}
         onToggleDictation={onToggleDictation}
         onOpenDictationSettings={onOpenDictationSettings}
+        dictationPartial={dictationPartial}
         dictationError={dictationError}
         onDismissDictationError={onDismissDictationError}
         dictationHint={dictationHint}
@@ -22,6 +22,7 @@ type ComposerInputProps = {
   dictationEnabled?: boolean;
   onToggleDictation?: () => void;
   onOpenDictationSettings?: () => void;
+  dictationPartial?: string | null;
   dictationError?: string | null;
   onDismissDictationError?: () => void;
   dictationHint?: string | null;
@@ -55,6 +56,7 @@ export function ComposerInput({
   dictationEnabled = false,
   onToggleDictation,
   onOpenDictationSettings,
+  dictationPartial = null,
   dictationError = null,
   onDismissDictationError,
   dictationHint = null,
@@ -225,6 +227,11 @@ export function ComposerInput({
             level={dictationLevel}
           />
         )}
+        {isDictationBusy && dictationPartial && (
+          <div className="composer-dictation-partial" aria-live="polite">
+            {dictationPartial}
+          </div>
+        )}
         {dictationError && (
           <div className="composer-dictation-error" role="status">
             <span>{dictationError}</span>
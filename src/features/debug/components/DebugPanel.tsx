@@ -1,5 +1,7 @@
+import { useState } from "react";
 import type { MouseEvent as ReactMouseEvent } from "react";
 import type { DebugEntry } from "../../../types";
+import { useSessionOutput } from "../hooks/useSessionOutput";
 
 type DebugPanelProps = {
   entries: DebugEntry[];
@@ -8,6 +10,8 @@ type DebugPanelProps = {
   onCopy: () => void;
   onResizeStart?: (event: ReactMouseEvent) => void;
   variant?: "dock" | "full";
+  // Workspace whose raw app-server output the "Raw output" view shows.
+  workspaceId?: string | null;
 };
 
 function formatPayload(payload: unknown) {
@@ -31,8 +35,12 @@ export function DebugPanel({
   onCopy,
   onResizeStart,
   variant = "dock",
+  workspaceId = null,
 }: DebugPanelProps) {
+  const [view, setView] = useState<"events" | "output">("events");
   const isVisible = variant === "full" || isOpen;
+  const showOutput = view === "output" && Boolean(workspaceId);
+  const sessionOutput = useSessionOutput(workspaceId, isVisible && showOutput);
   if (!isVisible) {
     return null;
   }
@@ -53,15 +61,66 @@ export function DebugPanel({
       <div className="debug-header">
         <div className="debug-title">Debug</div>
         <div className="debug-actions">
-          <button className="ghost" onClick={onCopy}>
-            Copy
-          </button>
-          <button className="ghost" onClick={onClear}>
-            Clear
-          </button>
+          {workspaceId && (
+            <button
+              className="ghost"
+              onClick={() => setView(showOutput ? "events" : "output")}
+            >
+              {showOutput ? "Events" : "Raw output"}
+            </button>
+          )}
+          {showOutput ? (
+            <>
+              <label className="debug-live-toggle">
+                <input
+                  type="checkbox"
+                  checked={sessionOutput.live}
+                  onChange={(event) => sessionOutput.setLive(event.target.checked)}
+                />
+                Live
+              </label>
+              <button className="ghost" onClick={() => void sessionOutput.refresh()}>
+                Refresh
+              </button>
+            </>
+          ) : (
+            <>
+              <button className="ghost" onClick={onCopy}>
+                Copy
+              </button>
+              <button className="ghost" onClick={onClear}>
+                Clear
+              </button>
+            </>
+          )}
         </div>
       </div>
-      {isOpen && (
+      {isOpen && showOutput && (
+        <div className="debug-list debug-output">
+          {sessionOutput.error && (
+            <div className="debug-empty">{sessionOutput.error}</div>
+          )}
+          {!sessionOutput.error && sessionOutput.lines.length === 0 && (
+            <div className="debug-empty">No app-server output yet.</div>
+          )}
+          {sessionOutput.lines.map((line, index) => (
+            <div
+              key={`${line.at}-${index}`}
+              className={`debug-output-line ${line.stream}`}
+            >
+              <span className="debug-time">
+                {new Date(line.at).toLocaleTimeString()}
+              </span>
+              <span className={`debug-source ${line.stream}`}>{line.stream}</span>
+              <span className="debug-output-text">
+                {line.line}
+                {line.truncated ? " …" : ""}
+              </span>
+            </div>
+          ))}
+        </div>
+      )}
+      {isOpen && !showOutput && (
         <div className="debug-list">
           {entries.length === 0 && (
             <div className="debug-empty">No debug events yet.</div>
@@ -0,0 +1,64 @@
+import { useCallback, useEffect, useState } from "react";
+import type { SessionOutputLine } from "../../../types";
+import { subscribeSessionOutput } from "../../../services/events";
+import {
+  setSessionOutputLive,
+  tailSessionOutput,
+} from "../../../services/tauri";
+import { useTauriEvent } from "../../app/hooks/useTauriEvent";
+
+const TAIL_LINES = 200;
+const MAX_SESSION_LINES = 2000;
+
+export function useSessionOutput(workspaceId: string | null, active: boolean) {
+  const [lines, setLines] = useState<SessionOutputLine[]>([]);
+  const [live, setLive] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    if (!workspaceId) {
+      setLines([]);
+      return;
+    }
+    try {
+      setLines(await tailSessionOutput(workspaceId, TAIL_LINES));
+      setError(null);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  }, [workspaceId]);
+
+  useEffect(() => {
+    if (active) {
+      void refresh();
+    }
+  }, [active, refresh]);
+
+  const streaming = active && live && Boolean(workspaceId);
+
+  useEffect(() => {
+    if (!streaming || !workspaceId) {
+      return;
+    }
+    void setSessionOutputLive(workspaceId, true).catch((err) => {
+      setError(err instanceof Error ? err.message : String(err));
+    });
+    return () => {
+      void setSessionOutputLive(workspaceId, false).catch(() => {});
+    };
+  }, [streaming, workspaceId]);
+
+  const handleLine = useCallback(
+    (line: SessionOutputLine) => {
+      if (line.workspaceId !== workspaceId) {
+        return;
+      }
+      setLines((prev) => [...prev, line].slice(-MAX_SESSION_LINES));
+    },
+    [workspaceId],
+  );
+
+  useTauriEvent(subscribeSessionOutput, handleLine, { enabled: streaming });
+
+  return { lines, live, setLive, error, refresh };
+}
@@ -11,6 +11,7 @@ type UseDictationResult = {
   state: DictationSessionState;
   level: number;
   transcript: DictationTranscript | null;
+  partialTranscript: string | null;
   error: string | null;
   hint: string | null;
   start: (preferredLanguage: string | null) => Promise<void>;
@@ -25,6 +26,7 @@ export function useDictation(): UseDictationResult {
   const [state, setState] = useState<DictationSessionState>("idle");
   const [level, setLevel] = useState(0);
   const [transcript, setTranscript] = useState<DictationTranscript | null>(null);
+  const [partialTranscript, setPartialTranscript] = useState<string | null>(null);
   const [error, setError] = useState<string | null>(null);
   const [hint, setHint] = useState<string | null>(null);
   const hintTimeoutRef = useRef<number | null>(null);
@@ -40,13 +42,21 @@ export function useDictation(): UseDictationResult {
         if (event.state === "idle") {
           setLevel(0);
         }
+        if (event.state !== "processing") {
+          setPartialTranscript(null);
+        }
         return;
       }
       if (event.type === "level") {
         setLevel(event.value);
         return;
       }
+      if (event.type === "partial") {
+        setPartialTranscript(event.text);
+        return;
+      }
       if (event.type === "transcript") {
+        setPartialTranscript(null);
         setTranscript({
           id: `${Date.now()}-${Math.random().toString(36).slice(2, 8)}`,
           text: event.text,
@@ -117,6 +127,7 @@ export function useDictation(): UseDictationResult {
     state,
     level,
     transcript,
+    partialTranscript,
     error,
     hint,
     start,
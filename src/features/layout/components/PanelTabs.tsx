@@ -2,8 +2,9 @@ import type { ReactNode } from "react";
 import Folder from "lucide-react/dist/esm/icons/folder";
 import GitBranch from "lucide-react/dist/esm/icons/git-branch";
 import ScrollText from "lucide-react/dist/esm/icons/scroll-text";
+import Users from "lucide-react/dist/esm/icons/users";
 
-export type PanelTabId = "git" | "files" | "prompts";
+export type PanelTabId = "git" | "files" | "prompts" | "team";
 
 type PanelTab = {
   id: PanelTabId;
@@ -21,6 +22,7 @@ const defaultTabs: PanelTab[] = [
   { id: "git", label: "Git", icon: <GitBranch aria-hidden /> },
   { id: "files", label: "Files", icon: <Folder aria-hidden /> },
   { id: "prompts", label: "Prompts", icon: <ScrollText aria-hidden /> },
+  { id: "team", label: "Team", icon: <Users aria-hidden /> },
 ];
 
 export function PanelTabs({ active, onSelect, tabs = defaultTabs }: PanelTabsProps) {
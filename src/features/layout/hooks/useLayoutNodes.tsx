@@ -11,6 +11,7 @@ import { GitDiffPanel } from "../../git/components/GitDiffPanel";
 import { GitDiffViewer } from "../../git/components/GitDiffViewer";
 import { FileTreePanel } from "../../files/components/FileTreePanel";
 import { PromptPanel } from "../../prompts/components/PromptPanel";
+import { TeamActivityPanel } from "../../team/components/TeamActivityPanel";
 import { DebugPanel } from "../../debug/components/DebugPanel";
 import { PlanPanel } from "../../plan/components/PlanPanel";
 import { TabBar } from "../../app/components/TabBar";
@@ -108,6 +109,10 @@ type LayoutNodesOptions = {
     request: ApprovalRequest,
     command: string[],
   ) => void;
+  handleApprovalGrantDirectory: (
+    request: ApprovalRequest,
+    path: string,
+  ) => void;
   onOpenSettings: () => void;
   onOpenDictationSettings?: () => void;
   onOpenDebug: () => void;
@@ -181,8 +186,8 @@ type LayoutNodesOptions = {
   worktreeApplyError: string | null;
   worktreeApplySuccess: boolean;
   onApplyWorktreeChanges?: () => void | Promise<void>;
-  filePanelMode: "git" | "files" | "prompts";
-  onFilePanelModeChange: (mode: "git" | "files" | "prompts") => void;
+  filePanelMode: "git" | "files" | "prompts" | "team";
+  onFilePanelModeChange: (mode: "git" | "files" | "prompts" | "team") => void;
   fileTreeLoading: boolean;
   gitStatus: {
     branchName: string;
@@ -322,6 +327,7 @@ type LayoutNodesOptions = {
   onToggleDictation: () => void;
   dictationTranscript: DictationTranscript | null;
   onDictationTranscriptHandled: (id: string) => void;
+  dictationPartial: string | null;
   dictationError: string | null;
   onDismissDictationError: () => void;
   dictationHint: string | null;
@@ -478,6 +484,7 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       onOpenDictationSettings={options.onOpenDictationSettings}
       dictationTranscript={options.dictationTranscript}
       onDictationTranscriptHandled={options.onDictationTranscriptHandled}
+      dictationPartial={options.dictationPartial}
       dictationError={options.dictationError}
       onDismissDictationError={options.onDismissDictationError}
       dictationHint={options.dictationHint}
@@ -491,6 +498,7 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       workspaces={options.workspaces}
       onDecision={options.handleApprovalDecision}
       onRemember={options.handleApprovalRemember}
+      onGrantDirectory={options.handleApprovalGrantDirectory}
     />
   );
 
@@ -597,6 +605,13 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
         onRevealGeneralPrompts={options.onRevealGeneralPrompts}
       />
     );
+  } else if (options.filePanelMode === "team") {
+    gitDiffPanelNode = (
+      <TeamActivityPanel
+        filePanelMode={options.filePanelMode}
+        onFilePanelModeChange={options.onFilePanelModeChange}
+      />
+    );
   } else {
     gitDiffPanelNode = (
       <GitDiffPanel
@@ -724,6 +739,7 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       onClear={options.onClearDebug}
       onCopy={options.onCopyDebug}
       onResizeStart={options.onResizeDebug}
+      workspaceId={options.activeWorkspaceId}
     />
   );
 
@@ -734,6 +750,7 @@ export function useLayoutNodes(options: LayoutNodesOptions): LayoutNodesResult {
       onClear={options.onClearDebug}
       onCopy={options.onCopyDebug}
       variant="full"
+      workspaceId={options.activeWorkspaceId}
     />
   );
 
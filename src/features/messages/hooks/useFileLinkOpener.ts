@@ -4,19 +4,21 @@ import { Menu, MenuItem, PredefinedMenuItem } from "@tauri-apps/api/menu";
 import { LogicalPosition } from "@tauri-apps/api/dpi";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { revealItemInDir } from "@tauri-apps/plugin-opener";
-import { openWorkspaceIn } from "../../../services/tauri";
+import { openInEditor, openWorkspaceIn } from "../../../services/tauri";
 import { getStoredOpenAppId } from "../../app/utils/openApp";
 import type { OpenAppId } from "../../app/constants";
 
 type OpenTarget = {
   id: OpenAppId;
   appName?: string;
+  /** Editor id understood by `open_in_editor`, which can jump to a line. */
+  editorId?: string;
 };
 
 const OPEN_TARGETS: Record<OpenTarget["id"], OpenTarget> = {
-  vscode: { id: "vscode", appName: "Visual Studio Code" },
-  cursor: { id: "cursor", appName: "Cursor" },
-  zed: { id: "zed", appName: "Zed" },
+  vscode: { id: "vscode", appName: "Visual Studio Code", editorId: "vscode" },
+  cursor: { id: "cursor", appName: "Cursor", editorId: "cursor" },
+  zed: { id: "zed", appName: "Zed", editorId: "zed" },
   ghostty: { id: "ghostty", appName: "Ghostty" },
   antigravity: { id: "antigravity", appName: "Antigravity" },
   finder: { id: "finder" },
@@ -39,6 +41,11 @@ function stripLineSuffix(path: string) {
   return match ? match[1] : path;
 }
 
+function lineFromPath(path: string) {
+  const match = path.match(/:(\d+)(?::\d+)?$/);
+  return match ? Number(match[1]) : null;
+}
+
 function revealLabel() {
   const platform =
     (navigator as Navigator & { userAgentData?: { platform?: string } })
@@ -65,6 +72,15 @@ export function useFileLinkOpener(workspacePath?: string | null) {
         return;
       }
 
+      if (target.editorId) {
+        try {
+          await openInEditor(resolvedPath, lineFromPath(rawPath), target.editorId);
+          return;
+        } catch {
+          // Fall back to the app when its command line tool is missing.
+        }
+      }
+
       if (target.appName) {
         await openWorkspaceIn(resolvedPath, target.appName);
       }
@@ -1,11 +1,29 @@
 import { useCallback, useMemo, useRef } from "react";
 import errorSoundUrl from "../../../assets/error-notification.mp3";
 import successSoundUrl from "../../../assets/success-notification.mp3";
-import type { DebugEntry } from "../../../types";
+import type {
+  AuthChanged,
+  DebugEntry,
+  NotificationKind,
+  SlaBreach,
+  ThreadLink,
+  TurnSummary,
+  WorkspaceHealthReport,
+} from "../../../types";
+import {
+  subscribeAuthExpired,
+  subscribeThreadSlaBreached,
+  subscribeThreadUnblocked,
+  subscribeWorkspaceHealthReport,
+} from "../../../services/events";
+import { getNotificationRoute } from "../../../services/tauri";
 import { playNotificationSound } from "../../../utils/notificationSounds";
 import { useAppServerEvents } from "../../app/hooks/useAppServerEvents";
+import { useTauriEvent } from "../../app/hooks/useTauriEvent";
 
 const DEFAULT_MIN_DURATION_MS = 60_000; // 1 minute
+// Weekly health reports only make a sound when a workspace scores below this.
+const HEALTH_ALERT_SCORE = 70;
 
 type SoundNotificationOptions = {
   enabled: boolean;
@@ -33,8 +51,30 @@ export function useAgentSoundNotifications({
   const lastPlayedAtByThread = useRef(new Map<string, number>());
 
   const playSound = useCallback(
-    (url: string, label: "success" | "error") => {
-      playNotificationSound(url, label, onDebug);
+    (
+      url: string,
+      label: "success" | "error",
+      workspaceId: string,
+      kind: NotificationKind,
+    ) => {
+      // During OS do-not-disturb the notification only goes to the feed.
+      void getNotificationRoute(workspaceId, kind)
+        .then((route) => {
+          if (route.delivery === "system") {
+            playNotificationSound(url, label, onDebug);
+            return;
+          }
+          onDebug?.({
+            id: `${Date.now()}-notification-${label}-deferred`,
+            timestamp: Date.now(),
+            source: "client",
+            label: `notification/${label} deferred (do not disturb)`,
+            payload: { workspaceId, kind },
+          });
+        })
+        .catch(() => {
+          playNotificationSound(url, label, onDebug);
+        });
     },
     [onDebug],
   );
@@ -113,13 +153,19 @@ export function useAgentSoundNotifications({
   );
 
   const handleTurnCompleted = useCallback(
-    (workspaceId: string, threadId: string, turnId: string) => {
-      const durationMs = consumeDuration(workspaceId, threadId, turnId);
+    (
+      workspaceId: string,
+      threadId: string,
+      turnId: string,
+      summary: TurnSummary | null,
+    ) => {
+      const trackedDurationMs = consumeDuration(workspaceId, threadId, turnId);
+      const durationMs = summary?.durationMs ?? trackedDurationMs;
       const threadKey = buildThreadKey(workspaceId, threadId);
       if (!shouldPlaySound(durationMs, threadKey)) {
         return;
       }
-      playSound(successSoundUrl, "success");
+      playSound(successSoundUrl, "success", workspaceId, "turnCompleted");
     },
     [consumeDuration, playSound, shouldPlaySound],
   );
@@ -139,7 +185,7 @@ export function useAgentSoundNotifications({
       if (!shouldPlaySound(durationMs, threadKey)) {
         return;
       }
-      playSound(errorSoundUrl, "error");
+      playSound(errorSoundUrl, "error", workspaceId, "turnError");
     },
     [consumeDuration, playSound, shouldPlaySound],
   );
@@ -165,7 +211,12 @@ export function useAgentSoundNotifications({
       if (!shouldPlaySound(durationMs, threadKey)) {
         return;
       }
-      playSound(successSoundUrl, "success");
+      playSound(
+        successSoundUrl,
+        "success",
+        event.workspaceId,
+        "turnCompleted",
+      );
     },
     [consumeDuration, playSound, shouldPlaySound],
   );
@@ -190,4 +241,80 @@ export function useAgentSoundNotifications({
   );
 
   useAppServerEvents(handlers);
+
+  const handleSlaBreached = useCallback(
+    (breach: SlaBreach) => {
+      onDebug?.({
+        id: `${Date.now()}-thread-sla-breached`,
+        timestamp: Date.now(),
+        source: "event",
+        label: `thread/sla breached (${breach.slaMinutes} min)`,
+        payload: breach,
+      });
+      if (enabled) {
+        playSound(errorSoundUrl, "error", breach.workspaceId, "slaBreach");
+      }
+    },
+    [enabled, onDebug, playSound],
+  );
+
+  useTauriEvent(subscribeThreadSlaBreached, handleSlaBreached);
+
+  const handleThreadUnblocked = useCallback(
+    (link: ThreadLink) => {
+      onDebug?.({
+        id: `${Date.now()}-thread-unblocked`,
+        timestamp: Date.now(),
+        source: "event",
+        label: `thread/unblocked ${link.threadId} (${link.targetThreadId} completed)`,
+        payload: link,
+      });
+      if (enabled) {
+        playSound(successSoundUrl, "success", link.workspaceId, "threadUnblocked");
+      }
+    },
+    [enabled, onDebug, playSound],
+  );
+
+  useTauriEvent(subscribeThreadUnblocked, handleThreadUnblocked);
+
+  const handleHealthReport = useCallback(
+    (report: WorkspaceHealthReport) => {
+      const worst = report.workspaces[0];
+      onDebug?.({
+        id: `${Date.now()}-workspace-health-report`,
+        timestamp: Date.now(),
+        source: "event",
+        label: worst
+          ? `workspace/health weekly report (lowest: ${worst.name} ${worst.score})`
+          : "workspace/health weekly report",
+        payload: report,
+      });
+      if (enabled && worst && worst.score < HEALTH_ALERT_SCORE) {
+        playSound(errorSoundUrl, "error", worst.workspaceId, "healthReport");
+      }
+    },
+    [enabled, onDebug, playSound],
+  );
+
+  useTauriEvent(subscribeWorkspaceHealthReport, handleHealthReport);
+
+  const handleAuthExpired = useCallback(
+    (event: AuthChanged) => {
+      onDebug?.({
+        id: `${Date.now()}-auth-expired`,
+        timestamp: Date.now(),
+        source: "event",
+        label: `account/authExpired (${event.workspaceIds.length} workspaces paused)`,
+        payload: event,
+      });
+      const [workspaceId] = event.workspaceIds;
+      if (enabled && workspaceId) {
+        playSound(errorSoundUrl, "error", workspaceId, "authExpired");
+      }
+    },
+    [enabled, onDebug, playSound],
+  );
+
+  useTauriEvent(subscribeAuthExpired, handleAuthExpired);
 }
@@ -81,7 +81,9 @@ type ShortcutSettingKey =
   | "cycleAgentNextShortcut"
   | "cycleAgentPrevShortcut"
   | "cycleWorkspaceNextShortcut"
-  | "cycleWorkspacePrevShortcut";
+  | "cycleWorkspacePrevShortcut"
+  | "acceptApprovalShortcut"
+  | "declineApprovalShortcut";
 type ShortcutDraftKey =
   | "model"
   | "access"
@@ -96,7 +98,9 @@ type ShortcutDraftKey =
   | "cycleAgentNext"
   | "cycleAgentPrev"
   | "cycleWorkspaceNext"
-  | "cycleWorkspacePrev";
+  | "cycleWorkspacePrev"
+  | "acceptApproval"
+  | "declineApproval";
 
 const shortcutDraftKeyBySetting: Record<ShortcutSettingKey, ShortcutDraftKey> = {
   composerModelShortcut: "model",
@@ -113,6 +117,8 @@ const shortcutDraftKeyBySetting: Record<ShortcutSettingKey, ShortcutDraftKey> =
   cycleAgentPrevShortcut: "cycleAgentPrev",
   cycleWorkspaceNextShortcut: "cycleWorkspaceNext",
   cycleWorkspacePrevShortcut: "cycleWorkspacePrev",
+  acceptApprovalShortcut: "acceptApproval",
+  declineApprovalShortcut: "declineApproval",
 };
 
 export function SettingsView({
@@ -173,6 +179,8 @@ export function SettingsView({
     cycleAgentPrev: appSettings.cycleAgentPrevShortcut ?? "",
     cycleWorkspaceNext: appSettings.cycleWorkspaceNextShortcut ?? "",
     cycleWorkspacePrev: appSettings.cycleWorkspacePrevShortcut ?? "",
+    acceptApproval: appSettings.acceptApprovalShortcut ?? "",
+    declineApproval: appSettings.declineApprovalShortcut ?? "",
   });
   const dictationReady = dictationModelStatus?.state === "ready";
   const dictationProgress = dictationModelStatus?.progress ?? null;
@@ -221,6 +229,8 @@ export function SettingsView({
       cycleAgentPrev: appSettings.cycleAgentPrevShortcut ?? "",
       cycleWorkspaceNext: appSettings.cycleWorkspaceNextShortcut ?? "",
       cycleWorkspacePrev: appSettings.cycleWorkspacePrevShortcut ?? "",
+      acceptApproval: appSettings.acceptApprovalShortcut ?? "",
+      declineApproval: appSettings.declineApprovalShortcut ?? "",
     });
   }, [
     appSettings.composerAccessShortcut,
@@ -237,6 +247,8 @@ export function SettingsView({
     appSettings.cycleAgentPrevShortcut,
     appSettings.cycleWorkspaceNextShortcut,
     appSettings.cycleWorkspacePrevShortcut,
+    appSettings.acceptApprovalShortcut,
+    appSettings.declineApprovalShortcut,
   ]);
 
   useEffect(() => {
@@ -1465,6 +1477,55 @@ export function SettingsView({
                     Default: {formatShortcut("cmd+shift+up")}
                   </div>
                 </div>
+                <div className="settings-field">
+                  <div className="settings-field-label">Approve next request</div>
+                  <div className="settings-field-row">
+                    <input
+                      className="settings-input settings-input--shortcut"
+                      value={formatShortcut(shortcutDrafts.acceptApproval)}
+                      onKeyDown={(event) =>
+                        handleShortcutKeyDown(event, "acceptApprovalShortcut")
+                      }
+                      placeholder="Type shortcut"
+                      readOnly
+                    />
+                    <button
+                      type="button"
+                      className="ghost settings-button-compact"
+                      onClick={() => void updateShortcut("acceptApprovalShortcut", null)}
+                    >
+                      Clear
+                    </button>
+                  </div>
+                  <div className="settings-help">
+                    Answers the oldest pending approval of the active workspace. Default:{" "}
+                    {formatShortcut("cmd+shift+y")}
+                  </div>
+                </div>
+                <div className="settings-field">
+                  <div className="settings-field-label">Decline next request</div>
+                  <div className="settings-field-row">
+                    <input
+                      className="settings-input settings-input--shortcut"
+                      value={formatShortcut(shortcutDrafts.declineApproval)}
+                      onKeyDown={(event) =>
+                        handleShortcutKeyDown(event, "declineApprovalShortcut")
+                      }
+                      placeholder="Type shortcut"
+                      readOnly
+                    />
+                    <button
+                      type="button"
+                      className="ghost settings-button-compact"
+                      onClick={() => void updateShortcut("declineApprovalShortcut", null)}
+                    >
+                      Clear
+                    </button>
+                  </div>
+                  <div className="settings-help">
+                    Default: {formatShortcut("cmd+shift+x")}
+                  </div>
+                </div>
               </section>
             )}
             {activeSection === "codex" && (
@@ -1767,6 +1828,56 @@ export function SettingsView({
                     <span className="settings-toggle-knob" />
                   </button>
                 </div>
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Usage insights</div>
+                    <div className="settings-toggle-subtitle">
+                      Count which features you use, on this machine only.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.telemetry.enabled ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        telemetry: {
+                          ...appSettings.telemetry,
+                          enabled: !appSettings.telemetry.enabled,
+                        },
+                      })
+                    }
+                    aria-pressed={appSettings.telemetry.enabled}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Share anonymized usage</div>
+                    <div className="settings-toggle-subtitle">
+                      Upload daily feature counts, rounded and without identifiers, to
+                      the configured usage endpoint.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.telemetry.shareAggregates ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        telemetry: {
+                          ...appSettings.telemetry,
+                          shareAggregates: !appSettings.telemetry.shareAggregates,
+                        },
+                      })
+                    }
+                    aria-pressed={appSettings.telemetry.shareAggregates}
+                    disabled={!appSettings.telemetry.enabled}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
               </section>
             )}
           </div>
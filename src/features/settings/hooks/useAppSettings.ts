@@ -25,6 +25,8 @@ const defaultSettings: AppSettings = {
   cycleAgentPrevShortcut: "cmd+ctrl+up",
   cycleWorkspaceNextShortcut: "cmd+shift+down",
   cycleWorkspacePrevShortcut: "cmd+shift+up",
+  acceptApprovalShortcut: "cmd+shift+y",
+  declineApprovalShortcut: "cmd+shift+x",
   lastComposerModelId: null,
   lastComposerReasoningEffort: null,
   uiScale: UI_SCALE_DEFAULT,
@@ -38,6 +40,71 @@ const defaultSettings: AppSettings = {
   dictationPreferredLanguage: null,
   dictationHoldKey: "alt",
   workspaceGroups: [],
+  archiveRetentionDays: 7,
+  encryptLocalData: false,
+  dataRetention: {
+    journalsDays: 30,
+    analyticsDays: 90,
+    draftsDays: 14,
+    approvalLogsDays: 90,
+    reportsDays: null,
+    artifactsDays: 30,
+  },
+  updateChannel: "stable",
+  locale: null,
+  webhookIntake: {
+    enabled: false,
+    port: 4790,
+    secret: null,
+    triggerLabel: "agent",
+    autoStart: false,
+    mappings: [],
+  },
+  semanticSearch: {
+    enabled: false,
+    provider: "local",
+    apiUrl: "https://api.openai.com/v1/embeddings",
+    apiModel: "text-embedding-3-small",
+    apiKeyEnv: "OPENAI_API_KEY",
+  },
+  approvalDelegation: {
+    enabled: false,
+    approver: "",
+    forwardUrl: null,
+    secret: null,
+    requireSignoff: false,
+    inboxToken: null,
+  },
+  commandBlocklist: [],
+  queueOfflineTurns: false,
+  network: {
+    httpProxy: null,
+    httpsProxy: null,
+    noProxy: null,
+    caBundlePath: null,
+  },
+  autoLockMinutes: null,
+  maxConcurrentTurns: null,
+  maxWorkspaceTurns: null,
+  turnOverlapMode: "queue",
+  rateLimitReservePercent: 10,
+  showReasoningSummaries: true,
+  reasoningSummaryModels: {},
+  preferredEditor: null,
+  editorTemplates: [],
+  teamActivity: {
+    enabled: false,
+    serverUrl: null,
+    token: null,
+    memberName: "",
+    shareSummaries: false,
+    shareWorkspaceNames: false,
+  },
+  telemetry: {
+    enabled: false,
+    shareAggregates: false,
+    endpoint: null,
+  },
 };
 
 function normalizeAppSettings(settings: AppSettings): AppSettings {
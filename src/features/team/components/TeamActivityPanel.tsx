@@ -0,0 +1,111 @@
+import RefreshCw from "lucide-react/dist/esm/icons/refresh-cw";
+import Users from "lucide-react/dist/esm/icons/users";
+import type { TeamActivity, TeamActivityStatus } from "../../../types";
+import { PanelTabs, type PanelTabId } from "../../layout/components/PanelTabs";
+import { useTeamActivity } from "../hooks/useTeamActivity";
+
+type TeamActivityPanelProps = {
+  filePanelMode: PanelTabId;
+  onFilePanelModeChange: (mode: PanelTabId) => void;
+};
+
+const statusLabels: Record<TeamActivityStatus, string> = {
+  running: "Running",
+  completed: "Completed",
+  failed: "Failed",
+  interrupted: "Interrupted",
+};
+
+function formatAge(updatedAt: number) {
+  const minutes = Math.floor((Date.now() - updatedAt) / 60_000);
+  if (minutes < 1) {
+    return "just now";
+  }
+  if (minutes < 60) {
+    return `${minutes}m ago`;
+  }
+  const hours = Math.floor(minutes / 60);
+  return hours < 24 ? `${hours}h ago` : `${Math.floor(hours / 24)}d ago`;
+}
+
+function formatStats(activity: TeamActivity) {
+  const parts: string[] = [];
+  if (activity.filesChanged) {
+    parts.push(`${activity.filesChanged} file${activity.filesChanged === 1 ? "" : "s"}`);
+  }
+  if (activity.commandsRun) {
+    parts.push(`${activity.commandsRun} command${activity.commandsRun === 1 ? "" : "s"}`);
+  }
+  return parts.join(" · ");
+}
+
+export function TeamActivityPanel({
+  filePanelMode,
+  onFilePanelModeChange,
+}: TeamActivityPanelProps) {
+  const { activities, error, isLoading, refresh } = useTeamActivity();
+  const runningCount = activities.filter(
+    (activity) => activity.status === "running",
+  ).length;
+
+  return (
+    <aside className="diff-panel team-panel">
+      <div className="git-panel-header">
+        <PanelTabs active={filePanelMode} onSelect={onFilePanelModeChange} />
+        <div className="team-panel-meta">
+          {activities.length ? `${runningCount} running` : "No activity"}
+          <button
+            type="button"
+            className="ghost icon-button team-panel-refresh"
+            onClick={() => void refresh()}
+            aria-label="Refresh team activity"
+            title="Refresh team activity"
+          >
+            <RefreshCw aria-hidden />
+          </button>
+        </div>
+      </div>
+      <div className="team-panel-scroll">
+        {error ? (
+          <div className="prompt-empty-card">
+            <Users className="prompt-empty-icon" aria-hidden />
+            <div className="prompt-empty-title">{error}</div>
+          </div>
+        ) : !activities.length ? (
+          <div className="prompt-empty-card">
+            <Users className="prompt-empty-icon" aria-hidden />
+            <div className="prompt-empty-title">
+              {isLoading ? "Loading team activity" : "No teammate activity yet"}
+            </div>
+          </div>
+        ) : (
+          <div className="team-activity-list">
+            {activities.map((activity) => {
+              const stats = formatStats(activity);
+              return (
+                <div
+                  key={`${activity.member}:${activity.threadKey}`}
+                  className="team-activity-row"
+                >
+                  <div className="team-activity-header">
+                    <span className="team-activity-member">{activity.member}</span>
+                    <span className={`team-activity-status is-${activity.status}`}>
+                      {statusLabels[activity.status]}
+                    </span>
+                  </div>
+                  <div className="team-activity-detail">
+                    {activity.workspace} · {formatAge(activity.updatedAt)}
+                    {stats ? ` · ${stats}` : ""}
+                  </div>
+                  {activity.summary ? (
+                    <div className="team-activity-summary">{activity.summary}</div>
+                  ) : null}
+                </div>
+              );
+            })}
+          </div>
+        )}
+      </div>
+    </aside>
+  );
+}
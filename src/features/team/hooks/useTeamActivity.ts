@@ -0,0 +1,48 @@
+import { useCallback, useEffect, useRef, useState } from "react";
+import type { TeamActivity } from "../../../types";
+import { getTeamActivity } from "../../../services/tauri";
+
+const REFRESH_INTERVAL_MS = 30_000;
+
+export function useTeamActivity() {
+  const [activities, setActivities] = useState<TeamActivity[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [isLoading, setIsLoading] = useState(true);
+  const requestIdRef = useRef(0);
+
+  const refresh = useCallback(async () => {
+    const requestId = requestIdRef.current + 1;
+    requestIdRef.current = requestId;
+    try {
+      const next = await getTeamActivity();
+      if (requestIdRef.current !== requestId) {
+        return;
+      }
+      setActivities(
+        next.slice().sort((a, b) => b.updatedAt - a.updatedAt),
+      );
+      setError(null);
+    } catch (err) {
+      if (requestIdRef.current !== requestId) {
+        return;
+      }
+      setError(err instanceof Error ? err.message : String(err));
+    } finally {
+      if (requestIdRef.current === requestId) {
+        setIsLoading(false);
+      }
+    }
+  }, []);
+
+  useEffect(() => {
+    void refresh();
+    const interval = window.setInterval(() => {
+      void refresh();
+    }, REFRESH_INTERVAL_MS);
+    return () => {
+      window.clearInterval(interval);
+    };
+  }, [refresh]);
+
+  return { activities, error, isLoading, refresh };
+}
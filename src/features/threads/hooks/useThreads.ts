@@ -1,11 +1,13 @@
 import { useCallback, useEffect, useMemo, useReducer, useRef, useState } from "react";
 import type {
+  ActiveTurnError,
   ApprovalRequest,
   AppServerEvent,
   ConversationItem,
   CustomPromptOption,
   DebugEntry,
   RateLimitSnapshot,
+  RequestId,
   ThreadSummary,
   ThreadTokenUsage,
   TurnPlan,
@@ -19,6 +21,7 @@ import {
   normalizeCommandTokens,
 } from "../../../utils/approvalRules";
 import {
+  answerDirectoryAccess,
   respondToServerRequest,
   rememberApprovalRule,
   sendUserMessage as sendUserMessageService,
@@ -208,6 +211,16 @@ function extractRpcErrorMessage(response: unknown) {
   return "Request failed.";
 }
 
+function parseActiveTurnError(error: unknown): ActiveTurnError | null {
+  const raw = error instanceof Error ? error.message : String(error);
+  try {
+    const parsed = JSON.parse(raw) as ActiveTurnError;
+    return parsed?.code === "turnActive" ? parsed : null;
+  } catch {
+    return null;
+  }
+}
+
 function parseReviewTarget(input: string) {
   const trimmed = input.trim();
   const rest = trimmed.replace(/^\/review\b/i, "").trim();
@@ -1513,7 +1526,7 @@ export function useThreads({
       threadId: string,
       text: string,
       images: string[] = [],
-      options?: { skipPromptExpansion?: boolean },
+      options?: { skipPromptExpansion?: boolean; replaceActiveTurn?: boolean },
     ) => {
       const messageText = text.trim();
       if (!messageText && images.length === 0) {
@@ -1562,7 +1575,14 @@ export function useThreads({
             workspace.id,
             threadId,
             finalText,
-            { model, effort, collaborationMode, accessMode, images },
+            {
+              model,
+              effort,
+              collaborationMode,
+              accessMode,
+              images,
+              replaceActiveTurn: options?.replaceActiveTurn,
+            },
           )) as Record<string, unknown>;
         onDebug?.({
           id: `${Date.now()}-server-turn-start`,
@@ -1593,8 +1613,15 @@ export function useThreads({
         }
         dispatch({ type: "setActiveTurnId", threadId, turnId });
       } catch (error) {
-        markProcessing(threadId, false);
-        dispatch({ type: "setActiveTurnId", threadId, turnId: null });
+        const activeTurn = parseActiveTurnError(error)?.activeTurn;
+        if (activeTurn?.threadId === threadId && activeTurn.turnId) {
+          // Keep the running turn interruptible, so it can be stopped and
+          // the message sent again with `replaceActiveTurn`.
+          dispatch({ type: "setActiveTurnId", threadId, turnId: activeTurn.turnId });
+        } else {
+          markProcessing(threadId, false);
+          dispatch({ type: "setActiveTurnId", threadId, turnId: null });
+        }
         onDebug?.({
           id: `${Date.now()}-client-turn-start-error`,
           timestamp: Date.now(),
@@ -1604,7 +1631,8 @@ export function useThreads({
         });
         pushThreadErrorMessage(
           threadId,
-          error instanceof Error ? error.message : String(error),
+          parseActiveTurnError(error)?.message ??
+            (error instanceof Error ? error.message : String(error)),
         );
         safeMessageActivity();
       }
@@ -1825,6 +1853,10 @@ export function useThreads({
     [],
   );
 
+  const removeApproval = useCallback((workspaceId: string, requestId: RequestId) => {
+    dispatch({ type: "removeApproval", requestId, workspaceId });
+  }, []);
+
   const handleApprovalRemember = useCallback(
     async (request: ApprovalRequest, command: string[]) => {
       try {
@@ -1855,6 +1887,38 @@ export function useThreads({
     [onDebug, rememberApprovalPrefix],
   );
 
+  const handleApprovalGrantDirectory = useCallback(
+    async (request: ApprovalRequest, path: string) => {
+      const threadId = String(
+        request.params?.threadId ?? request.params?.thread_id ?? "",
+      );
+      try {
+        await answerDirectoryAccess(
+          request.workspace_id,
+          threadId,
+          request.request_id,
+          path,
+          true,
+        );
+      } catch (error) {
+        onDebug?.({
+          id: `${Date.now()}-client-directory-grant-error`,
+          timestamp: Date.now(),
+          source: "error",
+          label: "directory grant error",
+          payload: error instanceof Error ? error.message : String(error),
+        });
+        return;
+      }
+      dispatch({
+        type: "removeApproval",
+        requestId: request.request_id,
+        workspaceId: request.workspace_id,
+      });
+    },
+    [onDebug],
+  );
+
   const setActiveThreadId = useCallback(
     (threadId: string | null, workspaceId?: string) => {
       const targetId = workspaceId ?? activeWorkspaceId;
@@ -1941,5 +2005,7 @@ export function useThreads({
     startReview,
     handleApprovalDecision,
     handleApprovalRemember,
+    handleApprovalGrantDirectory,
+    removeApproval,
   };
 }
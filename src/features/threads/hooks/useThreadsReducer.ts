@@ -2,6 +2,7 @@ import type {
   ApprovalRequest,
   ConversationItem,
   RateLimitSnapshot,
+  RequestId,
   ThreadSummary,
   ThreadTokenUsage,
   TurnPlan,
@@ -188,7 +189,7 @@ export type ThreadAction =
       cursor: string | null;
     }
   | { type: "addApproval"; approval: ApprovalRequest }
-  | { type: "removeApproval"; requestId: number; workspaceId: string }
+  | { type: "removeApproval"; requestId: RequestId; workspaceId: string }
   | { type: "setThreadTokenUsage"; threadId: string; tokenUsage: ThreadTokenUsage }
   | {
       type: "setRateLimits";
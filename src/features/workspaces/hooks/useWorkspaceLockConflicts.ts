@@ -0,0 +1,50 @@
+import { useCallback } from "react";
+import { ask } from "@tauri-apps/plugin-dialog";
+import type { WorkspaceInfo, WorkspaceLockConflict } from "../../../types";
+import { subscribeWorkspaceLockConflict } from "../../../services/events";
+import { forceWorkspaceLock } from "../../../services/tauri";
+import { useTauriEvent } from "../../app/hooks/useTauriEvent";
+
+type WorkspaceLockConflictOptions = {
+  workspaces: WorkspaceInfo[];
+};
+
+/**
+ * Offers to take over a checkout's lock when another Codex Monitor instance
+ * holds it and a turn was refused.
+ */
+export function useWorkspaceLockConflicts({
+  workspaces,
+}: WorkspaceLockConflictOptions) {
+  const handleConflict = useCallback(
+    (conflict: WorkspaceLockConflict) => {
+      const workspace = workspaces.find(
+        (entry) => entry.id === conflict.workspaceId,
+      );
+      const name = workspace?.name ?? "this workspace";
+      const since = new Date(conflict.lock.acquiredAt).toLocaleString();
+      void (async () => {
+        const confirmed = await ask(
+          `Another Codex Monitor (pid ${conflict.lock.pid} on ${conflict.lock.host}) has been working in "${name}" since ${since}.\n\nAgents writing to the same checkout at once can overwrite each other's changes. Take over the lock and send from here anyway?`,
+          {
+            title: "Checkout In Use",
+            kind: "warning",
+            okLabel: "Take Over",
+            cancelLabel: "Cancel",
+          },
+        );
+        if (!confirmed) {
+          return;
+        }
+        try {
+          await forceWorkspaceLock(conflict.workspaceId);
+        } catch (error) {
+          console.error("Failed to take over workspace lock", error);
+        }
+      })();
+    },
+    [workspaces],
+  );
+
+  useTauriEvent(subscribeWorkspaceLockConflict, handleConflict);
+}
@@ -1,5 +1,6 @@
 import { useEffect } from "react";
 import type { WorkspaceInfo } from "../../../types";
+import { subscribeSystemResumed } from "../../../services/events";
 
 type WorkspaceRefreshOptions = {
   workspaces: WorkspaceInfo[];
@@ -39,9 +40,12 @@ export function useWorkspaceRefreshOnFocus({
 
     window.addEventListener("focus", handleFocus);
     document.addEventListener("visibilitychange", handleVisibilityChange);
+    // Sessions may have been reconnected while the machine slept.
+    const unsubscribeResumed = subscribeSystemResumed(handleFocus);
     return () => {
       window.removeEventListener("focus", handleFocus);
       document.removeEventListener("visibilitychange", handleVisibilityChange);
+      unsubscribeResumed();
     };
   }, [listThreadsForWorkspace, refreshWorkspaces, workspaces]);
 }
@@ -18,6 +18,7 @@ vi.mock("../../../services/tauri", () => ({
   addWorktree: vi.fn(),
   connectWorkspace: vi.fn(),
   pickWorkspacePath: vi.fn(),
+  recordWorkspaceOpen: vi.fn().mockResolvedValue(undefined),
   removeWorkspace: vi.fn(),
   removeWorktree: vi.fn(),
   updateWorkspaceCodexBin: vi.fn(),
@@ -14,6 +14,7 @@ import {
   connectWorkspace as connectWorkspaceService,
   listWorkspaces,
   pickWorkspacePath,
+  recordWorkspaceOpen,
   removeWorkspace as removeWorkspaceService,
   removeWorktree as removeWorktreeService,
   renameWorktree as renameWorktreeService,
@@ -21,6 +22,7 @@ import {
   updateWorkspaceCodexBin as updateWorkspaceCodexBinService,
   updateWorkspaceSettings as updateWorkspaceSettingsService,
 } from "../../../services/tauri";
+import { parseWorkspaceValidationError } from "../../../utils/workspaceValidation";
 
 const GROUP_ID_RANDOM_MODULUS = 1_000_000;
 const RESERVED_GROUP_NAME = "Ungrouped";
@@ -109,6 +111,16 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
     [activeWorkspaceId, workspaces],
   );
 
+  useEffect(() => {
+    if (!activeWorkspaceId) {
+      return;
+    }
+    // Feeds the sessions prewarmed after launch and wake.
+    recordWorkspaceOpen(activeWorkspaceId).catch((err) => {
+      console.error("Failed to record workspace open", err);
+    });
+  }, [activeWorkspaceId]);
+
   const workspaceById = useMemo(() => {
     const map = new Map<string, WorkspaceInfo>();
     workspaces.forEach((entry) => {
@@ -233,6 +245,12 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
         label: "workspace/add error",
         payload: error instanceof Error ? error.message : String(error),
       });
+      const duplicate = parseWorkspaceValidationError(error)?.errors.find(
+        (entry) => entry.reason === "duplicate",
+      );
+      if (duplicate?.existingWorkspaceId) {
+        setActiveWorkspaceId(duplicate.existingWorkspaceId);
+      }
       throw error;
     }
   }
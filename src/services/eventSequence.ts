@@ -0,0 +1,81 @@
+import type { AppServerEvent } from "../types";
+import { ackEvents, getEventsSince } from "./tauri";
+
+// Survives webview reloads, which is when events would otherwise be lost.
+const SEEN_SEQ_STORAGE_KEY = "codexmonitor.appServerEventSeq";
+const ACK_DELAY_MS = 500;
+
+function readSeenSeqs(): Record<string, number> {
+  try {
+    const stored = window.sessionStorage.getItem(SEEN_SEQ_STORAGE_KEY);
+    return stored ? (JSON.parse(stored) as Record<string, number>) : {};
+  } catch {
+    return {};
+  }
+}
+
+function writeSeenSeqs(seqs: Record<string, number>) {
+  try {
+    window.sessionStorage.setItem(SEEN_SEQ_STORAGE_KEY, JSON.stringify(seqs));
+  } catch {
+    // Storage failures only cost the backfill after a reload.
+  }
+}
+
+/**
+ * Tracks the last `app-server-event` sequence id seen per workspace: drops
+ * replayed duplicates, acks what was handled, and after a reload backfills
+ * what the webview missed.
+ */
+export function createAppServerEventSequence() {
+  const seenSeqs = readSeenSeqs();
+  const pendingAcks = new Map<string, number>();
+  let ackTimer: ReturnType<typeof setTimeout> | null = null;
+
+  const flushAcks = () => {
+    ackTimer = null;
+    for (const [workspaceId, seq] of pendingAcks) {
+      void ackEvents(workspaceId, seq).catch(() => {
+        // The next ack covers this one.
+      });
+    }
+    pendingAcks.clear();
+  };
+
+  const accept = (event: AppServerEvent) => {
+    if (typeof event.seq !== "number") {
+      return true;
+    }
+    const seen = seenSeqs[event.workspace_id] ?? 0;
+    if (event.seq <= seen) {
+      return false;
+    }
+    seenSeqs[event.workspace_id] = event.seq;
+    writeSeenSeqs(seenSeqs);
+    pendingAcks.set(event.workspace_id, event.seq);
+    if (!ackTimer) {
+      ackTimer = setTimeout(flushAcks, ACK_DELAY_MS);
+    }
+    return true;
+  };
+
+  const backfill = (deliver: (event: AppServerEvent) => void) => {
+    for (const [workspaceId, seq] of Object.entries(seenSeqs)) {
+      void getEventsSince(workspaceId, seq)
+        .then((result) => {
+          if (result.latestSeq < seq) {
+            // The app restarted and its sequence ids started over.
+            seenSeqs[workspaceId] = 0;
+          }
+          for (const event of result.events) {
+            deliver(event);
+          }
+        })
+        .catch(() => {
+          // Without a backfill the webview relies on refetching thread state.
+        });
+    }
+  };
+
+  return { accept, backfill };
+}
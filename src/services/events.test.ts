@@ -47,6 +47,31 @@ describe("events subscriptions", () => {
     expect(unlisten).toHaveBeenCalledTimes(1);
   });
 
+  it("drops app-server events whose sequence id was already delivered", async () => {
+    let listener: EventCallback<AppServerEvent> = () => {};
+    vi.mocked(listen).mockImplementation((_event, handler) => {
+      listener = handler as EventCallback<AppServerEvent>;
+      return Promise.resolve(vi.fn());
+    });
+
+    const onEvent = vi.fn();
+    const cleanup = subscribeAppServerEvents(onEvent);
+    const deliver = (seq: number) =>
+      listener({
+        event: "app-server-event",
+        id: seq,
+        payload: { workspace_id: "ws-seq", message: { method: "ping" }, seq },
+      });
+    deliver(1);
+    deliver(2);
+    deliver(2);
+    deliver(1);
+    expect(onEvent).toHaveBeenCalledTimes(2);
+
+    cleanup();
+    await Promise.resolve();
+  });
+
   it("cleans up listeners that resolve after unsubscribe", async () => {
     let resolveListener: (handler: UnlistenFn) => void = () => {};
     const unlisten = vi.fn();
@@ -1,5 +1,28 @@
 import { listen } from "@tauri-apps/api/event";
-import type { AppServerEvent, DictationEvent, DictationModelStatus } from "../types";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import type {
+  AbTestCompleted,
+  AppLockStatus,
+  AppServerEvent,
+  AppUpdateProgress,
+  AuthChanged,
+  BrokenCodexBin,
+  ClockJump,
+  ConfigChange,
+  DictationEvent,
+  DictationModelStatus,
+  DirectoryAccessRequest,
+  DoctorFixOutput,
+  NarrationEvent,
+  QueuedTask,
+  ResumeReport,
+  SessionOutputLine,
+  SlaBreach,
+  ThreadLink,
+  WorkspaceHealthReport,
+  WorkspaceLockConflict,
+} from "../types";
+import { createAppServerEventSequence } from "./eventSequence";
 
 export type Unsubscribe = () => void;
 
@@ -15,7 +38,28 @@ type SubscriptionOptions = {
 
 type Listener<T> = (payload: T) => void;
 
-function createEventHub<T>(eventName: string) {
+type EventDelivery<T> = {
+  /** Returns `false` for payloads that were already delivered. */
+  accept: (payload: T) => boolean;
+  /** Replays payloads missed before the listener was attached. */
+  backfill: (deliver: Listener<T>) => void;
+};
+
+function currentWindowLabel(): string | null {
+  try {
+    return getCurrentWindow().label;
+  } catch {
+    return null;
+  }
+}
+
+// Workspace-scoped events are routed per window by the backend, so those hubs
+// listen on the current window instead of globally.
+function createEventHub<T>(
+  eventName: string,
+  scopedToWindow = false,
+  delivery?: EventDelivery<T>,
+) {
   const listeners = new Set<Listener<T>>();
   let unlisten: Unsubscribe | null = null;
   let listenPromise: Promise<Unsubscribe> | null = null;
@@ -24,15 +68,23 @@ function createEventHub<T>(eventName: string) {
     if (unlisten || listenPromise) {
       return;
     }
-    listenPromise = listen<T>(eventName, (event) => {
+    const dispatch = (payload: T) => {
+      if (delivery && !delivery.accept(payload)) {
+        return;
+      }
       for (const listener of listeners) {
         try {
-          listener(event.payload);
+          listener(payload);
         } catch (error) {
           console.error(`[events] ${eventName} listener failed`, error);
         }
       }
-    });
+    };
+    const handler = (event: { payload: T }) => dispatch(event.payload);
+    const windowLabel = scopedToWindow ? currentWindowLabel() : null;
+    listenPromise = windowLabel
+      ? listen<T>(eventName, handler, { target: windowLabel })
+      : listen<T>(eventName, handler);
     listenPromise
       .then((handler) => {
         listenPromise = null;
@@ -41,6 +93,7 @@ function createEventHub<T>(eventName: string) {
           return;
         }
         unlisten = handler;
+        delivery?.backfill(dispatch);
       })
       .catch((error) => {
         listenPromise = null;
@@ -76,11 +129,44 @@ function createEventHub<T>(eventName: string) {
   return { subscribe };
 }
 
-const appServerHub = createEventHub<AppServerEvent>("app-server-event");
+const appServerHub = createEventHub<AppServerEvent>(
+  "app-server-event",
+  true,
+  createAppServerEventSequence(),
+);
 const dictationDownloadHub = createEventHub<DictationModelStatus>("dictation-download");
 const dictationEventHub = createEventHub<DictationEvent>("dictation-event");
-const terminalOutputHub = createEventHub<TerminalOutputEvent>("terminal-output");
+const terminalOutputHub = createEventHub<TerminalOutputEvent>(
+  "terminal-output",
+  true,
+);
+const narrationHub = createEventHub<NarrationEvent>("narration-event", true);
 const updaterCheckHub = createEventHub<void>("updater-check");
+const appUpdateProgressHub = createEventHub<AppUpdateProgress>("app-update-progress");
+const abTestCompletedHub = createEventHub<AbTestCompleted>("ab-test-completed");
+const appLockChangedHub = createEventHub<AppLockStatus>("app-lock-changed");
+const authExpiredHub = createEventHub<AuthChanged>("account/authExpired");
+const authRestoredHub = createEventHub<AuthChanged>("account/authRestored");
+const doctorFixOutputHub = createEventHub<DoctorFixOutput>("doctor-fix-output");
+const codexBinOverridesBrokenHub = createEventHub<BrokenCodexBin[]>(
+  "codex-bin-overrides-broken",
+);
+const taskQueueUpdatedHub = createEventHub<QueuedTask[]>("task-queue-updated");
+const threadSlaBreachedHub = createEventHub<SlaBreach>("thread-sla-breached");
+const threadUnblockedHub = createEventHub<ThreadLink>("thread-unblocked");
+const codexConfigChangedHub = createEventHub<ConfigChange>("codex-config-changed");
+const clockJumpedHub = createEventHub<ClockJump>("clock-jumped");
+const systemResumedHub = createEventHub<ResumeReport>("system/resumed");
+const workspaceHealthReportHub = createEventHub<WorkspaceHealthReport>(
+  "workspace-health-report",
+);
+const workspaceLockConflictHub = createEventHub<WorkspaceLockConflict>(
+  "workspace/lockConflict",
+);
+const sessionOutputHub = createEventHub<SessionOutputLine>("session-output");
+const directoryAccessRequestedHub = createEventHub<DirectoryAccessRequest>(
+  "directory-access-requested",
+);
 const menuNewAgentHub = createEventHub<void>("menu-new-agent");
 const menuNewWorktreeAgentHub = createEventHub<void>("menu-new-worktree-agent");
 const menuNewCloneAgentHub = createEventHub<void>("menu-new-clone-agent");
@@ -129,6 +215,13 @@ export function subscribeTerminalOutput(
   return terminalOutputHub.subscribe(onEvent, options);
 }
 
+export function subscribeNarrationEvents(
+  onEvent: (event: NarrationEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return narrationHub.subscribe(onEvent, options);
+}
+
 export function subscribeUpdaterCheck(
   onEvent: () => void,
   options?: SubscriptionOptions,
@@ -138,6 +231,125 @@ export function subscribeUpdaterCheck(
   }, options);
 }
 
+export function subscribeAppUpdateProgress(
+  onEvent: (event: AppUpdateProgress) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return appUpdateProgressHub.subscribe(onEvent, options);
+}
+
+export function subscribeAbTestCompleted(
+  onEvent: (event: AbTestCompleted) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return abTestCompletedHub.subscribe(onEvent, options);
+}
+
+export function subscribeAppLockChanged(
+  onEvent: (status: AppLockStatus) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return appLockChangedHub.subscribe(onEvent, options);
+}
+
+export function subscribeAuthExpired(
+  onEvent: (event: AuthChanged) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return authExpiredHub.subscribe(onEvent, options);
+}
+
+export function subscribeAuthRestored(
+  onEvent: (event: AuthChanged) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return authRestoredHub.subscribe(onEvent, options);
+}
+
+export function subscribeDoctorFixOutput(
+  onEvent: (event: DoctorFixOutput) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return doctorFixOutputHub.subscribe(onEvent, options);
+}
+
+export function subscribeWorkspaceLockConflict(
+  onEvent: (conflict: WorkspaceLockConflict) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return workspaceLockConflictHub.subscribe(onEvent, options);
+}
+
+export function subscribeCodexBinOverridesBroken(
+  onEvent: (broken: BrokenCodexBin[]) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return codexBinOverridesBrokenHub.subscribe(onEvent, options);
+}
+
+export function subscribeTaskQueueUpdated(
+  onEvent: (tasks: QueuedTask[]) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return taskQueueUpdatedHub.subscribe(onEvent, options);
+}
+
+export function subscribeThreadSlaBreached(
+  onEvent: (breach: SlaBreach) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return threadSlaBreachedHub.subscribe(onEvent, options);
+}
+
+export function subscribeThreadUnblocked(
+  onEvent: (link: ThreadLink) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return threadUnblockedHub.subscribe(onEvent, options);
+}
+
+export function subscribeCodexConfigChanged(
+  onEvent: (change: ConfigChange) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return codexConfigChangedHub.subscribe(onEvent, options);
+}
+
+export function subscribeClockJumped(
+  onEvent: (jump: ClockJump) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return clockJumpedHub.subscribe(onEvent, options);
+}
+
+export function subscribeSystemResumed(
+  onEvent: (report: ResumeReport) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return systemResumedHub.subscribe(onEvent, options);
+}
+
+export function subscribeWorkspaceHealthReport(
+  onEvent: (report: WorkspaceHealthReport) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return workspaceHealthReportHub.subscribe(onEvent, options);
+}
+
+export function subscribeSessionOutput(
+  onEvent: (line: SessionOutputLine) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return sessionOutputHub.subscribe(onEvent, options);
+}
+
+export function subscribeDirectoryAccessRequested(
+  onEvent: (request: DirectoryAccessRequest) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return directoryAccessRequestedHub.subscribe(onEvent, options);
+}
+
 export function subscribeMenuNewAgent(
   onEvent: () => void,
   options?: SubscriptionOptions,
@@ -110,6 +110,9 @@ describe("tauri invoke wrappers", () => {
       accessMode: "full-access",
       images: ["image.png"],
       collaborationMode: null,
+      dryRun: false,
+      lane: null,
+      replaceActiveTurn: null,
     });
   });
 
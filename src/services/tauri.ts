@@ -1,12 +1,111 @@
 import { invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
 import type {
+  AbTestStarted,
+  AbVariant,
+  AccessControlStatus,
+  AccountLoginStart,
+  ActionDefinition,
+  ActiveTurn,
+  Annotation,
+  AnnotationReaction,
+  AppLockStatus,
   AppSettings,
+  ApplyStagedPatchReport,
+  ApprovalAuditEntry,
+  AppUpdateInfo,
+  ArchivedThread,
+  AuditChainReport,
+  BestOfStarted,
+  BlameContext,
+  BrokenCodexBin,
+  ClockJump,
+  ClonedWorkspace,
+  CodeStatsReport,
+  CodexBinRemap,
   CodexDoctorResult,
+  CommandOutputMatch,
+  ConnectionSimulation,
+  ConnectivityProbe,
+  ContextFileSuggestion,
+  DelegatedDecision,
   DictationModelStatus,
   DictationSessionState,
+  DoctorFixResult,
+  DoctorIssue,
+  DoctorIssueId,
+  EditorInfo,
+  EffectivePolicy,
+  EventBackfill,
+  FocusState,
+  FullAccessGrant,
+  HunkRef,
+  InboxApproval,
+  ItemOutputPage,
+  LineRange,
   LocalUsageSnapshot,
+  MessageCatalog,
+  ModelComparison,
+  ModelFeedback,
+  NetworkSettings,
+  NotificationKind,
+  NotificationRoute,
+  OfflineTurn,
+  OnboardingFix,
+  OnboardingProgress,
+  OnboardingStepId,
+  PromptingInsights,
+  PromptSizeCheck,
+  ProposedPatch,
+  ProtocolCompatReport,
+  PurgeConfirmation,
+  PurgeSummary,
+  QueuedTask,
+  QuickAskAnswer,
+  QuickSearchResult,
+  RequestId,
+  ReviewPair,
+  SavedThreadFilter,
+  SelftestReport,
+  SemanticMatch,
+  SessionEnvironment,
+  SessionOutputLine,
+  SessionReload,
+  ShareBundle,
+  ShareBundleInfo,
+  StagedPatch,
+  TeamActivity,
+  TeamPack,
+  TeamPackConflictStrategy,
+  TeamPackImportReport,
+  TelemetryReport,
+  TerminalHandoff,
+  ThreadBranch,
+  ThreadHandoff,
+  ThreadIssueLink,
+  ThreadLink,
+  ThreadLinkKind,
+  ThreadPlan,
+  ThreadQueryFilters,
+  ThreadRecord,
+  ThreadSort,
+  ThreadTags,
+  ThreadTimer,
+  ThreadTurnSettings,
+  ThreadWindow,
+  TrackedIssue,
+  TurnArtifact,
+  TurnComparison,
+  TurnEstimate,
+  TurnLabels,
+  TurnLane,
+  TurnLimiterStatus,
+  TurnManifest,
+  TurnRef,
+  WorkspaceDoctorReport,
+  WorkspaceHealthReport,
   WorkspaceInfo,
+  WorkspaceLock,
   WorkspaceSettings,
 } from "../types";
 import type {
@@ -49,6 +148,8 @@ export async function listWorkspaces(): Promise<WorkspaceInfo[]> {
   return invoke<WorkspaceInfo[]>("list_workspaces");
 }
 
+// Rejects with a JSON `WorkspaceValidationError` string when the folder or
+// `codex_bin` is invalid or the folder is already a workspace.
 export async function addWorkspace(
   path: string,
   codex_bin: string | null,
@@ -89,6 +190,14 @@ export async function updateWorkspaceCodexBin(
   return invoke<WorkspaceInfo>("update_workspace_codex_bin", { id, codex_bin });
 }
 
+export async function updateWorkspaceIdentity(
+  id: string,
+  icon: string | null,
+  color: string | null,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("update_workspace_identity", { id, icon, color });
+}
+
 export async function removeWorkspace(id: string): Promise<void> {
   return invoke("remove_workspace", { id });
 }
@@ -120,6 +229,22 @@ export async function openWorkspaceIn(path: string, app: string): Promise<void>
   return invoke("open_workspace_in", { path, app });
 }
 
+export async function openInEditor(
+  path: string,
+  line?: number | null,
+  editor?: string | null,
+): Promise<void> {
+  return invoke("open_in_editor", {
+    path,
+    line: line ?? null,
+    editor: editor ?? null,
+  });
+}
+
+export async function listEditors(): Promise<EditorInfo[]> {
+  return invoke<EditorInfo[]>("list_editors");
+}
+
 export async function connectWorkspace(id: string): Promise<void> {
   return invoke("connect_workspace", { id });
 }
@@ -138,6 +263,10 @@ export async function sendUserMessage(
     accessMode?: "read-only" | "current" | "full-access";
     images?: string[];
     collaborationMode?: Record<string, unknown> | null;
+    dryRun?: boolean;
+    lane?: TurnLane;
+    // Interrupts the thread's running turn and starts this one after it.
+    replaceActiveTurn?: boolean;
   },
 ) {
   return invoke("send_user_message", {
@@ -149,6 +278,9 @@ export async function sendUserMessage(
     accessMode: options?.accessMode ?? null,
     images: options?.images ?? null,
     collaborationMode: options?.collaborationMode ?? null,
+    dryRun: options?.dryRun ?? false,
+    lane: options?.lane ?? null,
+    replaceActiveTurn: options?.replaceActiveTurn ?? null,
   });
 }
 
@@ -173,9 +305,35 @@ export async function startReview(
   return invoke("start_review", payload);
 }
 
+export async function startReviewPair(
+  workspaceId: string,
+  prompt: string,
+  options?: {
+    branch?: string | null;
+    model?: string | null;
+    effort?: string | null;
+  },
+): Promise<ReviewPair> {
+  return invoke<ReviewPair>("start_review_pair", {
+    workspaceId,
+    prompt,
+    branch: options?.branch ?? null,
+    model: options?.model ?? null,
+    effort: options?.effort ?? null,
+  });
+}
+
+export async function listReviewPairs(
+  workspaceId?: string | null,
+): Promise<ReviewPair[]> {
+  return invoke<ReviewPair[]>("list_review_pairs", {
+    workspaceId: workspaceId ?? null,
+  });
+}
+
 export async function respondToServerRequest(
   workspaceId: string,
-  requestId: number,
+  requestId: RequestId,
   decision: "accept" | "decline",
 ) {
   return invoke("respond_to_server_request", {
@@ -185,6 +343,20 @@ export async function respondToServerRequest(
   });
 }
 
+export async function respondWithDelegatedDecision(
+  workspaceId: string,
+  requestId: RequestId,
+  result: Record<string, unknown>,
+  delegation: DelegatedDecision,
+) {
+  return invoke("respond_to_server_request", {
+    workspaceId,
+    requestId,
+    result,
+    delegation,
+  });
+}
+
 export async function rememberApprovalRule(
   workspaceId: string,
   command: string[],
@@ -316,6 +488,675 @@ export async function localUsageSnapshot(
   return invoke("local_usage_snapshot", payload);
 }
 
+export async function exportWorkspaceReport(
+  workspaceId: string,
+  outputDir?: string | null,
+): Promise<string> {
+  return invoke<string>("export_workspace_report", {
+    workspaceId,
+    outputDir: outputDir ?? null,
+  });
+}
+
+export async function exportTeamPack(
+  path: string,
+  name?: string | null,
+): Promise<TeamPack> {
+  return invoke<TeamPack>("export_team_pack", { path, name: name ?? null });
+}
+
+export async function importTeamPack(
+  path: string,
+  conflictStrategy: TeamPackConflictStrategy = "skip",
+): Promise<TeamPackImportReport> {
+  return invoke<TeamPackImportReport>("import_team_pack", {
+    path,
+    conflictStrategy,
+  });
+}
+
+export async function preparePurgeLocalData(): Promise<PurgeConfirmation> {
+  return invoke<PurgeConfirmation>("prepare_purge_local_data");
+}
+
+export async function purgeAllLocalData(
+  confirmationToken: string,
+): Promise<PurgeSummary> {
+  return invoke<PurgeSummary>("purge_all_local_data", { confirmationToken });
+}
+
+export async function openWorkspaceWindow(workspaceId: string): Promise<string> {
+  return invoke<string>("open_workspace_window", { workspaceId });
+}
+
+export async function focusWorkspaceWindow(workspaceId: string): Promise<boolean> {
+  return invoke<boolean>("focus_workspace_window", { workspaceId });
+}
+
+export async function setWindowWorkspaces(
+  workspaceIds: string[] | null,
+): Promise<void> {
+  return invoke("set_window_workspaces", { workspaceIds });
+}
+
+export async function subscribeEvents(
+  workspaceIds: string[] | null,
+  methods: string[] | null,
+): Promise<number> {
+  return invoke<number>("subscribe_events", { workspaceIds, methods });
+}
+
+export async function unsubscribeEvents(id?: number | null): Promise<boolean> {
+  return invoke<boolean>("unsubscribe_events", { id: id ?? null });
+}
+
+export async function setThreadEventFilter(
+  workspaceId: string,
+  threadId: string,
+  mutedMethods: string[],
+): Promise<void> {
+  return invoke("set_thread_event_filter", { workspaceId, threadId, mutedMethods });
+}
+
+export async function quickSearch(
+  query: string,
+  limit?: number | null,
+): Promise<QuickSearchResult[]> {
+  return invoke<QuickSearchResult[]>("quick_search", { query, limit: limit ?? null });
+}
+
+export async function compareTurns(
+  turnA: TurnRef,
+  turnB: TurnRef,
+): Promise<TurnComparison> {
+  return invoke<TurnComparison>("compare_turns", { turnA, turnB });
+}
+
+export async function runAbTest(
+  workspaceId: string,
+  prompt: string,
+  variantA: AbVariant,
+  variantB: AbVariant,
+  accessMode?: "read-only" | "current" | "full-access",
+): Promise<AbTestStarted> {
+  return invoke<AbTestStarted>("run_ab_test", {
+    workspaceId,
+    prompt,
+    variantA,
+    variantB,
+    accessMode: accessMode ?? null,
+  });
+}
+
+export async function runBestOf(
+  workspaceId: string,
+  prompt: string,
+  n: number,
+  profile?: AbVariant | null,
+  verifyCommand?: string | null,
+): Promise<BestOfStarted> {
+  return invoke<BestOfStarted>("run_best_of", {
+    workspaceId,
+    prompt,
+    n,
+    profile: profile ?? null,
+    verifyCommand: verifyCommand ?? null,
+  });
+}
+
+export async function createShareBundle(
+  workspaceId: string,
+  threadId: string,
+  passphrase: string,
+  outputPath?: string | null,
+): Promise<ShareBundleInfo> {
+  return invoke<ShareBundleInfo>("create_share_bundle", {
+    workspaceId,
+    threadId,
+    passphrase,
+    outputPath: outputPath ?? null,
+  });
+}
+
+export async function openShareBundle(
+  path: string,
+  passphrase: string,
+): Promise<ShareBundle> {
+  return invoke<ShareBundle>("open_share_bundle", { path, passphrase });
+}
+
+export async function listTrackedIssues(
+  workspaceId: string,
+): Promise<TrackedIssue[]> {
+  return invoke<TrackedIssue[]>("list_tracked_issues", { workspaceId });
+}
+
+export async function startThreadFromIssue(
+  workspaceId: string,
+  issueId: string,
+  options?: {
+    model?: string | null;
+    effort?: string | null;
+    accessMode?: "read-only" | "current" | "full-access";
+  },
+): Promise<{ threadId: string; link: ThreadIssueLink }> {
+  return invoke("start_thread_from_issue", {
+    workspaceId,
+    issueId,
+    model: options?.model ?? null,
+    effort: options?.effort ?? null,
+    accessMode: options?.accessMode ?? null,
+  });
+}
+
+export async function getThreadIssue(
+  workspaceId: string,
+  threadId: string,
+): Promise<ThreadIssueLink | null> {
+  return invoke<ThreadIssueLink | null>("get_thread_issue", {
+    workspaceId,
+    threadId,
+  });
+}
+
+export async function postResultComment(
+  workspaceId: string,
+  threadId: string,
+  includeDiff = false,
+): Promise<{ tracker: "github" | "jira"; issueId: string; url: string | null }> {
+  return invoke("post_result_comment", { workspaceId, threadId, includeDiff });
+}
+
+export async function listQueuedTasks(
+  workspaceId: string | null = null,
+): Promise<QueuedTask[]> {
+  return invoke<QueuedTask[]>("list_queued_tasks", { workspaceId });
+}
+
+export async function startQueuedTask(
+  taskId: string,
+  options?: {
+    model?: string | null;
+    effort?: string | null;
+    accessMode?: "read-only" | "current" | "full-access";
+    lane?: TurnLane;
+  },
+): Promise<QueuedTask> {
+  return invoke<QueuedTask>("start_queued_task", {
+    taskId,
+    model: options?.model ?? null,
+    effort: options?.effort ?? null,
+    accessMode: options?.accessMode ?? null,
+    lane: options?.lane ?? null,
+  });
+}
+
+export async function getTurnLimiterStatus(): Promise<TurnLimiterStatus> {
+  return invoke<TurnLimiterStatus>("get_turn_limiter_status");
+}
+
+export async function dismissQueuedTask(taskId: string): Promise<void> {
+  return invoke("dismiss_queued_task", { taskId });
+}
+
+export async function getThreadPlan(
+  workspaceId: string,
+  threadId: string,
+): Promise<ThreadPlan | null> {
+  return invoke<ThreadPlan | null>("get_thread_plan", { workspaceId, threadId });
+}
+
+export async function searchCommandOutputs(
+  query: string,
+  workspaceId: string | null = null,
+  limit?: number,
+): Promise<CommandOutputMatch[]> {
+  return invoke<CommandOutputMatch[]>("search_command_outputs", {
+    query,
+    workspaceId,
+    limit: limit ?? null,
+  });
+}
+
+export async function getItemOutput(
+  workspaceId: string,
+  itemId: string,
+  offset = 0,
+  limit?: number,
+): Promise<ItemOutputPage> {
+  return invoke<ItemOutputPage>("get_item_output", {
+    workspaceId,
+    itemId,
+    offset,
+    limit: limit ?? null,
+  });
+}
+
+export async function getCodeStats(
+  workspaceId: string | null = null,
+  days?: number,
+  label?: string | null,
+): Promise<CodeStatsReport> {
+  return invoke<CodeStatsReport>("get_code_stats", {
+    workspaceId,
+    days: days ?? null,
+    label: label ?? null,
+  });
+}
+
+export async function suggestContextFiles(
+  workspaceId: string,
+  promptText: string,
+  limit?: number | null,
+): Promise<ContextFileSuggestion[]> {
+  return invoke<ContextFileSuggestion[]>("suggest_context_files", {
+    workspaceId,
+    promptText,
+    limit: limit ?? null,
+  });
+}
+
+export async function semanticSearch(
+  query: string,
+  workspaceId?: string | null,
+  limit?: number | null,
+): Promise<SemanticMatch[]> {
+  return invoke<SemanticMatch[]>("semantic_search", {
+    query,
+    workspaceId: workspaceId ?? null,
+    limit: limit ?? null,
+  });
+}
+
+export async function getEffectivePolicy(): Promise<EffectivePolicy> {
+  return invoke<EffectivePolicy>("get_effective_policy");
+}
+
+export async function getApprovalAuditLog(
+  date?: string | null,
+): Promise<ApprovalAuditEntry[]> {
+  return invoke<ApprovalAuditEntry[]>("get_approval_audit_log", { date: date ?? null });
+}
+
+export async function verifyAuditChain(): Promise<AuditChainReport> {
+  return invoke<AuditChainReport>("verify_audit_chain");
+}
+
+export async function exportApprovalAuditLog(path: string): Promise<AuditChainReport> {
+  return invoke<AuditChainReport>("export_approval_audit_log", { path });
+}
+
+export async function retryApprovalDelegation(
+  workspaceId: string,
+  requestId: RequestId,
+): Promise<void> {
+  return invoke("retry_approval_delegation", { workspaceId, requestId });
+}
+
+export async function getTurnProposedPatch(
+  workspaceId: string,
+  turnId: string,
+): Promise<ProposedPatch> {
+  return invoke<ProposedPatch>("get_turn_proposed_patch", { workspaceId, turnId });
+}
+
+export async function listStagedPatches(
+  workspaceId: string,
+): Promise<StagedPatch[]> {
+  return invoke<StagedPatch[]>("list_staged_patches", { workspaceId });
+}
+
+export async function applyStagedPatch(
+  workspaceId: string,
+  patchId: string,
+  hunks?: HunkRef[] | null,
+): Promise<ApplyStagedPatchReport> {
+  return invoke<ApplyStagedPatchReport>("apply_staged_patch", {
+    workspaceId,
+    patchId,
+    hunks: hunks ?? null,
+  });
+}
+
+export async function discardStagedPatch(workspaceId: string, patchId: string) {
+  return invoke("discard_staged_patch", { workspaceId, patchId });
+}
+
+export async function getTurnManifest(
+  workspaceId: string,
+  turnId: string,
+): Promise<TurnManifest> {
+  return invoke<TurnManifest>("get_turn_manifest", { workspaceId, turnId });
+}
+
+export async function checkPromptSize(
+  workspaceId: string,
+  threadId: string,
+  text: string,
+  images?: string[] | null,
+  model?: string | null,
+): Promise<PromptSizeCheck> {
+  return invoke<PromptSizeCheck>("check_prompt_size", {
+    workspaceId,
+    threadId,
+    text,
+    images: images ?? null,
+    model: model ?? null,
+  });
+}
+
+export async function estimateTurn(
+  workspaceId: string,
+  threadId: string,
+  text: string,
+  images?: string[] | null,
+  model?: string | null,
+  effort?: string | null,
+): Promise<TurnEstimate> {
+  return invoke<TurnEstimate>("estimate_turn", {
+    workspaceId,
+    threadId,
+    text,
+    images: images ?? null,
+    model: model ?? null,
+    effort: effort ?? null,
+  });
+}
+
+export async function getProtocolCompatReport(): Promise<ProtocolCompatReport> {
+  return invoke<ProtocolCompatReport>("get_protocol_compat_report");
+}
+
+export async function getSessionEnvironment(
+  workspaceId: string,
+): Promise<SessionEnvironment> {
+  return invoke<SessionEnvironment>("get_session_environment", { workspaceId });
+}
+
+export async function tailSessionOutput(
+  workspaceId: string,
+  lines?: number | null,
+): Promise<SessionOutputLine[]> {
+  return invoke<SessionOutputLine[]>("tail_session_output", {
+    workspaceId,
+    lines: lines ?? null,
+  });
+}
+
+export async function setSessionOutputLive(
+  workspaceId: string,
+  enabled: boolean,
+): Promise<void> {
+  return invoke("set_session_output_live", { workspaceId, enabled });
+}
+
+export async function getThreadTurnSettings(
+  workspaceId: string,
+  threadId: string,
+): Promise<ThreadTurnSettings | null> {
+  return invoke<ThreadTurnSettings | null>("get_thread_turn_settings", {
+    workspaceId,
+    threadId,
+  });
+}
+
+export async function setThreadTags(
+  workspaceId: string,
+  threadId: string,
+  tags: string[],
+): Promise<ThreadTags> {
+  return invoke<ThreadTags>("set_thread_tags", { workspaceId, threadId, tags });
+}
+
+export async function listThreadTags(
+  workspaceId?: string | null,
+): Promise<ThreadTags[]> {
+  return invoke<ThreadTags[]>("list_thread_tags", {
+    workspaceId: workspaceId ?? null,
+  });
+}
+
+export async function annotateItem(
+  annotation: Pick<Annotation, "workspaceId" | "threadId" | "turnId"> & {
+    itemId?: string | null;
+    reaction?: AnnotationReaction | null;
+    comment?: string | null;
+    needsFollowUp?: boolean;
+  },
+): Promise<Annotation> {
+  return invoke<Annotation>("annotate_item", { annotation });
+}
+
+export async function listAnnotations(
+  workspaceId?: string | null,
+  threadId?: string | null,
+): Promise<Annotation[]> {
+  return invoke<Annotation[]>("list_annotations", {
+    workspaceId: workspaceId ?? null,
+    threadId: threadId ?? null,
+  });
+}
+
+export async function getAnnotationFeedback(
+  workspaceId?: string | null,
+): Promise<ModelFeedback[]> {
+  return invoke<ModelFeedback[]>("get_annotation_feedback", {
+    workspaceId: workspaceId ?? null,
+  });
+}
+
+export async function getPromptingInsights(
+  workspaceId: string,
+  days?: number | null,
+): Promise<PromptingInsights> {
+  return invoke<PromptingInsights>("get_prompting_insights", {
+    workspaceId,
+    days: days ?? null,
+  });
+}
+
+export async function getWorkspaceHealth(
+  days?: number | null,
+): Promise<WorkspaceHealthReport> {
+  return invoke<WorkspaceHealthReport>("get_workspace_health", {
+    days: days ?? null,
+  });
+}
+
+export async function getLastHealthReport(): Promise<WorkspaceHealthReport | null> {
+  return invoke<WorkspaceHealthReport | null>("get_last_health_report");
+}
+
+export async function listThreadFilters(): Promise<SavedThreadFilter[]> {
+  return invoke<SavedThreadFilter[]>("list_thread_filters");
+}
+
+export async function saveThreadFilter(
+  filter: SavedThreadFilter,
+): Promise<SavedThreadFilter> {
+  return invoke<SavedThreadFilter>("save_thread_filter", { filter });
+}
+
+export async function deleteThreadFilter(filterId: string): Promise<void> {
+  return invoke("delete_thread_filter", { filterId });
+}
+
+export async function queryThreads(filterId: string): Promise<ThreadRecord[]> {
+  return invoke<ThreadRecord[]>("query_threads", { filterId });
+}
+
+export async function queryThreadsWindow(
+  offset: number,
+  limit: number,
+  sort?: ThreadSort,
+  filters?: ThreadQueryFilters,
+): Promise<ThreadWindow> {
+  return invoke<ThreadWindow>("query_threads_window", {
+    offset,
+    limit,
+    sort: sort ?? null,
+    filters: filters ?? null,
+  });
+}
+
+export async function listActions(): Promise<ActionDefinition[]> {
+  return invoke<ActionDefinition[]>("list_actions");
+}
+
+export async function invokeAction<T = unknown>(
+  actionId: string,
+  args?: Record<string, unknown>,
+): Promise<T> {
+  return invoke<T>("invoke_action", { actionId, args: args ?? null });
+}
+
+export async function listThreadBranches(
+  workspaceId?: string | null,
+): Promise<ThreadBranch[]> {
+  return invoke<ThreadBranch[]>("list_thread_branches", {
+    workspaceId: workspaceId ?? null,
+  });
+}
+
+export async function cloneAndAddWorkspace(
+  url: string,
+  dest: string,
+  startOnboarding?: boolean,
+): Promise<ClonedWorkspace> {
+  return invoke<ClonedWorkspace>("clone_and_add_workspace", {
+    url,
+    dest,
+    startOnboarding: startOnboarding ?? null,
+  });
+}
+
+export async function listOfflineQueue(
+  workspaceId?: string | null,
+): Promise<OfflineTurn[]> {
+  return invoke<OfflineTurn[]>("list_offline_queue", {
+    workspaceId: workspaceId ?? null,
+  });
+}
+
+export async function cancelOfflineTurn(turnId: string): Promise<void> {
+  return invoke("cancel_offline_turn", { turnId });
+}
+
+// The connection simulation commands exist only in debug builds.
+export async function getConnectionSimulation(): Promise<ConnectionSimulation> {
+  return invoke<ConnectionSimulation>("get_connection_simulation");
+}
+
+export async function setConnectionSimulation(
+  simulation: ConnectionSimulation,
+): Promise<ConnectionSimulation> {
+  return invoke<ConnectionSimulation>("set_connection_simulation", {
+    simulation,
+  });
+}
+
+export async function getFocusState(): Promise<FocusState> {
+  return invoke<FocusState>("get_focus_state");
+}
+
+export async function getNotificationRoute(
+  workspaceId: string,
+  kind: NotificationKind,
+): Promise<NotificationRoute> {
+  return invoke<NotificationRoute>("get_notification_route", {
+    workspaceId,
+    kind,
+  });
+}
+
+export async function getAppLockStatus(): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>("get_app_lock_status");
+}
+
+export async function reportUserActivity(): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>("report_user_activity");
+}
+
+export async function lockApp(): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>("lock_app");
+}
+
+export async function unlockApp(
+  passphrase?: string | null,
+): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>("unlock_app", { passphrase: passphrase ?? null });
+}
+
+export async function setLocale(locale: string | null): Promise<MessageCatalog> {
+  return invoke<MessageCatalog>("set_locale", { locale });
+}
+
+export async function getMessageCatalog(
+  locale?: string | null,
+): Promise<MessageCatalog> {
+  return invoke<MessageCatalog>("get_message_catalog", { locale: locale ?? null });
+}
+
+export async function checkAppUpdate(): Promise<AppUpdateInfo> {
+  return invoke<AppUpdateInfo>("check_app_update");
+}
+
+export async function installAppUpdate(): Promise<void> {
+  return invoke("install_app_update");
+}
+
+export async function getOnboardingProgress(): Promise<OnboardingProgress> {
+  return invoke<OnboardingProgress>("get_onboarding_progress");
+}
+
+export async function runOnboardingFix(
+  stepId: OnboardingStepId,
+): Promise<OnboardingFix> {
+  return invoke<OnboardingFix>("run_onboarding_fix", { stepId });
+}
+
+export async function getAccessControlStatus(): Promise<AccessControlStatus> {
+  return invoke<AccessControlStatus>("get_access_control_status");
+}
+
+export async function setAppPassphrase(
+  currentPassphrase: string | null,
+  passphrase: string | null,
+): Promise<AccessControlStatus> {
+  return invoke<AccessControlStatus>("set_app_passphrase", {
+    currentPassphrase,
+    passphrase,
+  });
+}
+
+export async function unlockWorkspace(
+  workspaceId: string,
+  passphrase: string,
+): Promise<AccessControlStatus> {
+  return invoke<AccessControlStatus>("unlock_workspace", {
+    workspaceId,
+    passphrase,
+  });
+}
+
+export async function lockWorkspace(
+  workspaceId?: string | null,
+): Promise<AccessControlStatus> {
+  return invoke<AccessControlStatus>("lock_workspace", {
+    workspaceId: workspaceId ?? null,
+  });
+}
+
+export async function setObserverMode(
+  enabled: boolean,
+  passphrase?: string | null,
+): Promise<AccessControlStatus> {
+  return invoke<AccessControlStatus>("set_observer_mode", {
+    enabled,
+    passphrase: passphrase ?? null,
+  });
+}
+
 export async function getModelList(workspaceId: string) {
   return invoke<any>("model_list", { workspaceId });
 }
@@ -403,8 +1244,16 @@ export async function getAppSettings(): Promise<AppSettings> {
   return invoke<AppSettings>("get_app_settings");
 }
 
-export async function updateAppSettings(settings: AppSettings): Promise<AppSettings> {
-  return invoke<AppSettings>("update_app_settings", { settings });
+// The passphrase is only needed to change approval delegation while sign-off
+// is required.
+export async function updateAppSettings(
+  settings: AppSettings,
+  passphrase?: string | null,
+): Promise<AppSettings> {
+  return invoke<AppSettings>("update_app_settings", {
+    settings,
+    passphrase: passphrase ?? null,
+  });
 }
 
 type MenuAcceleratorUpdate = {
@@ -424,6 +1273,34 @@ export async function runCodexDoctor(
   return invoke<CodexDoctorResult>("codex_doctor", { codexBin });
 }
 
+export async function probeConnectivity(
+  settings?: NetworkSettings | null,
+  urls?: string[] | null,
+): Promise<ConnectivityProbe[]> {
+  return invoke<ConnectivityProbe[]>("probe_connectivity", {
+    settings: settings ?? null,
+    urls: urls ?? null,
+  });
+}
+
+export async function listDoctorIssues(
+  codexBin: string | null,
+): Promise<DoctorIssue[]> {
+  return invoke<DoctorIssue[]>("list_doctor_issues", { codexBin });
+}
+
+export async function runDoctorFix(
+  issueId: DoctorIssueId,
+): Promise<DoctorFixResult> {
+  return invoke<DoctorFixResult>("doctor_fix", { issueId });
+}
+
+export async function runWorkspaceDoctor(
+  workspaceId: string,
+): Promise<WorkspaceDoctorReport> {
+  return invoke<WorkspaceDoctorReport>("workspace_doctor", { workspaceId });
+}
+
 export async function getWorkspaceFiles(workspaceId: string) {
   return invoke<string[]>("list_workspace_files", { workspaceId });
 }
@@ -539,8 +1416,24 @@ export async function resumeThread(workspaceId: string, threadId: string) {
   return invoke<any>("resume_thread", { workspaceId, threadId });
 }
 
-export async function archiveThread(workspaceId: string, threadId: string) {
-  return invoke<any>("archive_thread", { workspaceId, threadId });
+// Rejects with a JSON `ThreadAttentionError` string while the thread has
+// unapplied staged patches or pending approvals, unless `force` is set.
+export async function archiveThread(
+  workspaceId: string,
+  threadId: string,
+  force?: boolean,
+) {
+  return invoke<any>("archive_thread", { workspaceId, threadId, force });
+}
+
+export async function unarchiveThread(workspaceId: string, threadId: string) {
+  return invoke<any>("unarchive_thread", { workspaceId, threadId });
+}
+
+export async function listRecentlyArchived(
+  workspaceId: string | null,
+): Promise<ArchivedThread[]> {
+  return invoke<ArchivedThread[]>("list_recently_archived", { workspaceId });
 }
 
 export async function getCommitMessagePrompt(
@@ -554,3 +1447,318 @@ export async function generateCommitMessage(
 ): Promise<string> {
   return invoke("generate_commit_message", { workspaceId });
 }
+
+export async function suggestCommitMessage(
+  workspaceId: string,
+): Promise<string> {
+  return invoke("suggest_commit_message", { workspaceId });
+}
+
+export async function setThreadSla(
+  workspaceId: string,
+  threadId: string,
+  slaMinutes: number | null,
+): Promise<ThreadTimer> {
+  return invoke<ThreadTimer>("set_thread_sla", {
+    workspaceId,
+    threadId,
+    slaMinutes,
+  });
+}
+
+export async function listThreadTimers(
+  workspaceId: string,
+): Promise<ThreadTimer[]> {
+  return invoke<ThreadTimer[]>("list_thread_timers", { workspaceId });
+}
+
+export async function ackEvents(workspaceId: string, seq: number): Promise<void> {
+  return invoke("ack_events", { workspaceId, seq });
+}
+
+export async function getEventsSince(
+  workspaceId: string,
+  seq: number,
+): Promise<EventBackfill> {
+  return invoke<EventBackfill>("get_events_since", { workspaceId, seq });
+}
+
+export async function reloadSessionConfig(
+  workspaceId: string,
+  threadId?: string | null,
+  force?: boolean,
+): Promise<SessionReload> {
+  return invoke<SessionReload>("reload_session_config", {
+    workspaceId,
+    threadId: threadId ?? null,
+    force: force ?? null,
+  });
+}
+
+export async function runSelftest(): Promise<SelftestReport> {
+  return invoke<SelftestReport>("run_selftest");
+}
+
+export async function listClockJumps(since?: number | null): Promise<ClockJump[]> {
+  return invoke<ClockJump[]>("list_clock_jumps", { since: since ?? null });
+}
+
+export async function listTurnArtifacts(
+  workspaceId: string,
+  threadId: string,
+): Promise<TurnArtifact[]> {
+  return invoke<TurnArtifact[]>("list_turn_artifacts", { workspaceId, threadId });
+}
+
+export async function openArtifact(
+  workspaceId: string,
+  threadId: string,
+  path: string,
+): Promise<void> {
+  return invoke("open_artifact", { workspaceId, threadId, path });
+}
+
+export async function answerDirectoryAccess(
+  workspaceId: string,
+  threadId: string,
+  requestId: RequestId,
+  path: string,
+  allow: boolean,
+): Promise<void> {
+  return invoke("answer_directory_access", {
+    workspaceId,
+    threadId,
+    requestId,
+    path,
+    allow,
+  });
+}
+
+export async function setTurnLabels(
+  workspaceId: string,
+  threadId: string,
+  turnId: string,
+  labels: string[],
+): Promise<TurnLabels> {
+  return invoke<TurnLabels>("set_turn_labels", {
+    workspaceId,
+    threadId,
+    turnId,
+    labels,
+  });
+}
+
+export async function listTurnLabels(
+  workspaceId: string | null = null,
+): Promise<TurnLabels[]> {
+  return invoke<TurnLabels[]>("list_turn_labels", { workspaceId });
+}
+
+export async function applyTurnLabelsToPullRequest(
+  workspaceId: string,
+  prNumber: number,
+): Promise<string[]> {
+  return invoke<string[]>("apply_turn_labels_to_pull_request", {
+    workspaceId,
+    prNumber,
+  });
+}
+
+export async function listActiveTurns(
+  workspaceId: string | null = null,
+): Promise<ActiveTurn[]> {
+  return invoke<ActiveTurn[]>("list_active_turns", { workspaceId });
+}
+
+// Opens a terminal running `codex resume` for the thread; when no terminal
+// could be opened, `commandLine` is meant to be pasted into one.
+export async function openInTerminal(
+  workspaceId: string,
+  threadId: string,
+): Promise<TerminalHandoff> {
+  return invoke<TerminalHandoff>("open_in_terminal", { workspaceId, threadId });
+}
+
+export async function getModelComparison(
+  workspaceId: string | null = null,
+  days?: number,
+): Promise<ModelComparison> {
+  return invoke<ModelComparison>("get_model_comparison", {
+    workspaceId,
+    days: days ?? null,
+  });
+}
+
+export async function spawnScratchWorkspace(
+  sourceWorkspaceId?: string | null,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("spawn_scratch_workspace", {
+    sourceWorkspaceId: sourceWorkspaceId ?? null,
+  });
+}
+
+export async function stageScratchChanges(
+  workspaceId: string,
+  threadId?: string | null,
+): Promise<StagedPatch> {
+  return invoke<StagedPatch>("stage_scratch_changes", {
+    workspaceId,
+    threadId: threadId ?? null,
+  });
+}
+
+export async function discardScratchWorkspace(workspaceId: string): Promise<void> {
+  return invoke("discard_scratch_workspace", { workspaceId });
+}
+
+export async function grantFullAccess(
+  workspaceId: string,
+  threadId: string,
+  minutes: number,
+): Promise<FullAccessGrant> {
+  return invoke<FullAccessGrant>("grant_full_access", { workspaceId, threadId, minutes });
+}
+
+export async function revokeFullAccess(workspaceId: string, threadId: string): Promise<void> {
+  return invoke("revoke_full_access", { workspaceId, threadId });
+}
+
+export async function listFullAccessGrants(
+  workspaceId: string | null = null,
+): Promise<FullAccessGrant[]> {
+  return invoke<FullAccessGrant[]>("list_full_access_grants", { workspaceId });
+}
+
+export async function linkThreads(
+  workspaceId: string,
+  threadId: string,
+  kind: ThreadLinkKind,
+  targetThreadId: string,
+  targetWorkspaceId?: string | null,
+): Promise<ThreadLink> {
+  return invoke<ThreadLink>("link_threads", {
+    workspaceId,
+    threadId,
+    kind,
+    targetWorkspaceId: targetWorkspaceId ?? null,
+    targetThreadId,
+  });
+}
+
+export async function unlinkThreads(
+  workspaceId: string,
+  threadId: string,
+  kind: ThreadLinkKind,
+  targetThreadId: string,
+  targetWorkspaceId?: string | null,
+): Promise<boolean> {
+  return invoke<boolean>("unlink_threads", {
+    workspaceId,
+    threadId,
+    kind,
+    targetWorkspaceId: targetWorkspaceId ?? null,
+    targetThreadId,
+  });
+}
+
+export async function getThreadLinks(
+  workspaceId: string,
+  threadId?: string | null,
+): Promise<ThreadLink[]> {
+  return invoke<ThreadLink[]>("get_thread_links", {
+    workspaceId,
+    threadId: threadId ?? null,
+  });
+}
+
+export async function recordWorkspaceOpen(workspaceId: string): Promise<void> {
+  return invoke("record_workspace_open", { workspaceId });
+}
+
+export async function listBrokenCodexBins(): Promise<BrokenCodexBin[]> {
+  return invoke<BrokenCodexBin[]>("list_broken_codex_bins");
+}
+
+export async function remapCodexBin(
+  oldPrefix: string,
+  newPrefix: string,
+): Promise<CodexBinRemap[]> {
+  return invoke<CodexBinRemap[]>("remap_codex_bin", { oldPrefix, newPrefix });
+}
+
+export async function getAuthExpiredWorkspaces(): Promise<string[]> {
+  return invoke<string[]>("get_auth_expired_workspaces");
+}
+
+export async function startAccountLogin(
+  workspaceId: string,
+): Promise<AccountLoginStart> {
+  return invoke<AccountLoginStart>("start_account_login", { workspaceId });
+}
+
+export async function getTeamActivity(): Promise<TeamActivity[]> {
+  return invoke<TeamActivity[]>("get_team_activity");
+}
+
+export async function getWorkspaceLock(
+  workspaceId: string,
+): Promise<WorkspaceLock | null> {
+  return invoke<WorkspaceLock | null>("get_workspace_lock", { workspaceId });
+}
+
+export async function forceWorkspaceLock(workspaceId: string): Promise<void> {
+  return invoke("force_workspace_lock", { workspaceId });
+}
+
+// Counted only when usage insights are enabled in settings.
+export async function recordFeatureUsage(feature: string): Promise<void> {
+  return invoke("record_feature_usage", { feature });
+}
+
+export async function getLocalTelemetry(days?: number): Promise<TelemetryReport> {
+  return invoke<TelemetryReport>("get_local_telemetry", { days: days ?? null });
+}
+
+export async function setFocusedWorkspace(workspaceId: string | null): Promise<void> {
+  return invoke("set_focused_workspace", { workspaceId });
+}
+
+export async function respondToNextApproval(
+  decision: "accept" | "decline",
+): Promise<InboxApproval | null> {
+  return invoke<InboxApproval | null>("respond_to_next_approval", { decision });
+}
+
+export async function quickAsk(
+  workspaceId: string,
+  path: string,
+  question: string,
+): Promise<QuickAskAnswer> {
+  return invoke<QuickAskAnswer>("quick_ask", { workspaceId, path, question });
+}
+
+export async function handoffThread(
+  workspaceId: string,
+  threadId: string,
+  targetWorkspaceId: string,
+  artifacts?: string[],
+): Promise<ThreadHandoff> {
+  return invoke<ThreadHandoff>("handoff_thread", {
+    workspaceId,
+    threadId,
+    targetWorkspaceId,
+    artifacts: artifacts ?? null,
+  });
+}
+
+export async function getBlameContext(
+  workspaceId: string,
+  path: string,
+  lineRange: LineRange,
+): Promise<BlameContext> {
+  return invoke<BlameContext>("get_blame_context", {
+    workspaceId,
+    path,
+    lineRange,
+  });
+}
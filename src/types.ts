@@ -3,6 +3,87 @@ export type WorkspaceSettings = {
   sortOrder?: number | null;
   groupId?: string | null;
   gitRoot?: string | null;
+  restricted?: boolean;
+  issueTracker?: IssueTrackerConfig | null;
+  stderrFilter?: StderrFilterConfig | null;
+  verification?: VerificationConfig | null;
+  approvalsBreakThroughDnd?: boolean;
+  autoBranch?: AutoBranchConfig | null;
+  showReasoningSummaries?: boolean | null;
+  scratch?: ScratchOrigin | null;
+  resourceLimits?: ResourceLimits | null;
+};
+
+export type ResourceLimits = {
+  nice?: number | null;
+  cpuAffinity?: number[] | null;
+  memoryLimitMb?: number | null;
+};
+
+export type ScratchOrigin = {
+  sourceWorkspaceId: string | null;
+  createdAt: number;
+};
+
+export type AutoBranchConfig = {
+  prefix?: string;
+};
+
+export type VerificationConfig = {
+  command: string;
+  autoFix?: boolean;
+  maxFixAttempts?: number;
+  timeoutSecs?: number;
+};
+
+export type StderrSeverity = "debug" | "info" | "warning" | "error";
+
+export type StderrFilterConfig = {
+  allow: string[];
+  deny: string[];
+  minSeverity: StderrSeverity;
+};
+
+export type IssueTrackerConfig =
+  | { kind: "github"; repo?: string | null }
+  | { kind: "jira"; baseUrl: string; projectKey: string; email: string };
+
+export type TrackedIssue = {
+  id: string;
+  tracker: "github" | "jira";
+  title: string;
+  url: string;
+  updatedAt: string | null;
+  labels: string[];
+};
+
+export type ThreadIssueLink = {
+  workspaceId: string;
+  threadId: string;
+  tracker: "github" | "jira" | "linear";
+  issueId: string;
+  title: string;
+  url: string;
+  linkedAt: number;
+};
+
+export type QueuedTask = {
+  id: string;
+  workspaceId: string;
+  source: "linear" | "jira";
+  externalId: string;
+  title: string;
+  prompt: string;
+  url: string;
+  createdAt: number;
+  status: "queued" | "started";
+  threadId: string | null;
+};
+
+export type AccessControlStatus = {
+  passphraseSet: boolean;
+  observerMode: boolean;
+  unlockedWorkspaceIds: string[];
 };
 
 export type WorkspaceGroup = {
@@ -28,11 +109,22 @@ export type WorkspaceInfo = {
   parentId?: string | null;
   worktree?: WorktreeInfo | null;
   settings: WorkspaceSettings;
+  icon?: string;
+  color?: string;
+};
+
+export type WorkspaceIdentity = {
+  icon: string;
+  color: string;
 };
 
 export type AppServerEvent = {
   workspace_id: string;
   message: Record<string, unknown>;
+  workspaceIdentity?: WorkspaceIdentity;
+  /** Per-workspace delivery sequence id, used to ack and backfill. */
+  seq?: number;
+  receivedAt?: ClockTimestamp;
 };
 
 export type Message = {
@@ -72,6 +164,58 @@ export type ReviewTarget =
 export type AccessMode = "read-only" | "current" | "full-access";
 export type BackendMode = "local" | "remote";
 export type ThemePreference = "system" | "light" | "dark";
+export type UpdateChannel = "stable" | "beta";
+
+export type TurnOverlapMode = "queue" | "reject";
+
+export type DataRetentionSettings = {
+  journalsDays: number | null;
+  analyticsDays: number | null;
+  draftsDays: number | null;
+  approvalLogsDays: number | null;
+  reportsDays: number | null;
+  artifactsDays: number | null;
+};
+
+export type WebhookWorkspaceMapping = {
+  projectKey: string;
+  workspaceId: string;
+};
+
+export type WebhookIntakeSettings = {
+  enabled: boolean;
+  port: number;
+  secret: string | null;
+  triggerLabel: string;
+  autoStart: boolean;
+  mappings: WebhookWorkspaceMapping[];
+};
+
+export type EmbeddingProvider = "local" | "api";
+
+export type SemanticSearchSettings = {
+  enabled: boolean;
+  provider: EmbeddingProvider;
+  apiUrl: string;
+  apiModel: string;
+  apiKeyEnv: string;
+};
+
+export type ApprovalDelegationSettings = {
+  enabled: boolean;
+  approver: string;
+  forwardUrl: string | null;
+  secret: string | null;
+  requireSignoff: boolean;
+  inboxToken: string | null;
+};
+
+export type NetworkSettings = {
+  httpProxy: string | null;
+  httpsProxy: string | null;
+  noProxy: string | null;
+  caBundlePath: string | null;
+};
 
 export type AppSettings = {
   codexBin: string | null;
@@ -93,6 +237,8 @@ export type AppSettings = {
   cycleAgentPrevShortcut: string | null;
   cycleWorkspaceNextShortcut: string | null;
   cycleWorkspacePrevShortcut: string | null;
+  acceptApprovalShortcut: string | null;
+  declineApprovalShortcut: string | null;
   lastComposerModelId: string | null;
   lastComposerReasoningEffort: string | null;
   uiScale: number;
@@ -106,6 +252,187 @@ export type AppSettings = {
   dictationPreferredLanguage: string | null;
   dictationHoldKey: string | null;
   workspaceGroups: WorkspaceGroup[];
+  archiveRetentionDays: number;
+  encryptLocalData: boolean;
+  dataRetention: DataRetentionSettings;
+  updateChannel: UpdateChannel;
+  locale: string | null;
+  webhookIntake: WebhookIntakeSettings;
+  semanticSearch: SemanticSearchSettings;
+  approvalDelegation: ApprovalDelegationSettings;
+  commandBlocklist: string[];
+  queueOfflineTurns: boolean;
+  network: NetworkSettings;
+  autoLockMinutes: number | null;
+  maxConcurrentTurns: number | null;
+  maxWorkspaceTurns: number | null;
+  turnOverlapMode: TurnOverlapMode;
+  rateLimitReservePercent: number;
+  showReasoningSummaries: boolean;
+  reasoningSummaryModels: Record<string, boolean>;
+  preferredEditor: string | null;
+  editorTemplates: EditorTemplate[];
+  teamActivity: TeamActivitySettings;
+  telemetry: TelemetrySettings;
+};
+
+export type EditorTemplate = {
+  id: string;
+  label: string;
+  command: string;
+};
+
+export type OnboardingStepId =
+  | "codexInstalled"
+  | "authenticated"
+  | "nodePresent"
+  | "workspaceAdded"
+  | "threadStarted";
+
+export type OnboardingFix =
+  | { kind: "command"; label: string; command: string }
+  | { kind: "login"; label: string }
+  | { kind: "action"; label: string; action: string };
+
+export type OnboardingStep = {
+  id: OnboardingStepId;
+  label: string;
+  status: "done" | "pending" | "blocked";
+  detail: string | null;
+  fix: OnboardingFix | null;
+};
+
+export type OnboardingProgress = {
+  steps: OnboardingStep[];
+  completed: number;
+  total: number;
+  nextStep: OnboardingStepId | null;
+};
+
+export type QuickSearchResult = {
+  kind: "workspace" | "thread" | "prompt";
+  id: string;
+  title: string;
+  subtitle: string | null;
+  workspaceId: string | null;
+  score: number;
+};
+
+export type TurnRef = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string;
+};
+
+export type TurnSideStats = {
+  status: string | null;
+  filesChanged: number;
+  additions: number;
+  deletions: number;
+  commandsRun: number;
+};
+
+export type FileComparison = {
+  path: string;
+  status: "onlyA" | "onlyB" | "identical" | "different";
+  kindA: string | null;
+  kindB: string | null;
+  diffA: string | null;
+  diffB: string | null;
+};
+
+export type TurnComparison = {
+  turnA: TurnRef;
+  turnB: TurnRef;
+  statsA: TurnSideStats;
+  statsB: TurnSideStats;
+  files: FileComparison[];
+  finalMessageA: string | null;
+  finalMessageB: string | null;
+  messageDiff: { change: "same" | "onlyA" | "onlyB"; text: string }[];
+};
+
+export type AbVariant = {
+  label?: string | null;
+  model?: string | null;
+  effort?: string | null;
+};
+
+export type AbVariantRun = {
+  label: string;
+  model: string | null;
+  effort: string | null;
+  threadId: string;
+  turnId: string | null;
+};
+
+export type AbTestStarted = {
+  testId: string;
+  variants: AbVariantRun[];
+};
+
+export type AbTestCompleted = {
+  testId: string;
+  workspaceId: string;
+  prompt: string;
+  variants: (AbVariantRun & {
+    status: string | null;
+    durationMs: number | null;
+    totalTokens: number;
+    filesChanged: number;
+    commandsRun: number;
+    lastAgentMessage: string | null;
+  })[];
+};
+
+export type ShareEntry = {
+  turnId: string | null;
+  kind: "user" | "assistant" | "reasoning" | "command" | "fileChange";
+  text: string;
+};
+
+export type ShareBundle = {
+  format: string;
+  createdAt: number;
+  workspaceName: string;
+  threadId: string;
+  title: string | null;
+  entries: ShareEntry[];
+  manifests: TurnManifest[];
+  annotations: Annotation[];
+};
+
+export type ShareBundleInfo = {
+  path: string;
+  entries: number;
+};
+
+export type NarrationEvent = {
+  workspaceId: string;
+  threadId: string | null;
+  text: string;
+  urgent: boolean;
+};
+
+export type AppUpdateInfo = {
+  available: boolean;
+  channel: UpdateChannel;
+  currentVersion: string;
+  version: string | null;
+  notes: string | null;
+  date: string | null;
+  heldBack: boolean;
+};
+
+export type AppUpdateProgress =
+  | { event: "started"; contentLength: number | null }
+  | { event: "progress"; downloaded: number; contentLength: number | null }
+  | { event: "finished" }
+  | { event: "installed" };
+
+export type MessageCatalog = {
+  locale: string;
+  messages: Record<string, string>;
 };
 
 export type CodexDoctorResult = {
@@ -120,9 +447,12 @@ export type CodexDoctorResult = {
   nodeDetails: string | null;
 };
 
+// JSON-RPC allows numeric and string request ids.
+export type RequestId = number | string;
+
 export type ApprovalRequest = {
   workspace_id: string;
-  request_id: number;
+  request_id: RequestId;
   method: string;
   params: Record<string, unknown>;
 };
@@ -269,6 +599,40 @@ export type TurnPlan = {
   steps: TurnPlanStep[];
 };
 
+export type ThreadPlan = TurnPlan & {
+  threadId: string;
+};
+
+export type CommandOutputMatch = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string | null;
+  itemId: string;
+  command: string;
+  exitCode: number | null;
+  capturedAt: number;
+  lineNumber: number;
+  line: string;
+};
+
+export type ItemOutputPage = {
+  itemId: string;
+  offset: number;
+  content: string;
+  totalChars: number;
+  hasMore: boolean;
+  nextOffset: number | null;
+};
+
+export type PlanStepCompleted = {
+  threadId: string;
+  turnId: string;
+  stepIndex: number;
+  step: string;
+  completedSteps: number;
+  totalSteps: number;
+};
+
 export type RateLimitWindow = {
   usedPercent: number;
   windowDurationMins: number | null;
@@ -345,6 +709,91 @@ export type DebugEntry = {
 
 export type TerminalStatus = "idle" | "connecting" | "ready" | "error";
 
+export type TeamPackPrompt = {
+  name: string;
+  description: string | null;
+  argumentHint: string | null;
+  content: string;
+};
+
+export type TeamPackProfile = {
+  defaultAccessMode: string | null;
+  model: string | null;
+  reasoningEffort: string | null;
+};
+
+export type TeamPack = {
+  version: number;
+  name: string | null;
+  exportedAt: number;
+  approvalRules: string[][];
+  prompts: TeamPackPrompt[];
+  profile: TeamPackProfile | null;
+};
+
+export type TeamPackConflictStrategy = "skip" | "overwrite" | "rename";
+
+export type TeamPackImportReport = {
+  rulesAdded: number;
+  promptsAdded: string[];
+  promptsUpdated: string[];
+  promptsUnchanged: string[];
+  conflicts: string[];
+  profileApplied: boolean;
+};
+
+export type PurgeSummary = {
+  removedEntries: number;
+  removedBytes: number;
+};
+
+export type PurgeConfirmation = {
+  token: string;
+  expiresInMs: number;
+  pending: PurgeSummary;
+};
+
+export type ArchivedThread = {
+  workspaceId: string;
+  threadId: string;
+  archivedAt: number;
+  expiresAt: number;
+  thread: Record<string, unknown> | null;
+};
+
+export type LanguageStats = {
+  files: number;
+  added: number;
+  removed: number;
+};
+
+export type CodeStats = {
+  byLanguage: Record<string, LanguageStats>;
+  added: number;
+  removed: number;
+  testFiles: number;
+  testLinesAdded: number;
+  nonTestLinesAdded: number;
+};
+
+export type CodeStatsReport = {
+  days: number;
+  turns: number;
+  stats: CodeStats;
+};
+
+export type TurnSummary = {
+  threadId: string;
+  turnId: string | null;
+  status: string | null;
+  lastAgentMessage: string | null;
+  filesChanged: number;
+  commandsRun: number;
+  durationMs: number | null;
+  totalTokens: number;
+  codeStats?: CodeStats;
+};
+
 export type DictationModelState = "missing" | "downloading" | "ready" | "error";
 
 export type DictationDownloadProgress = {
@@ -365,6 +814,7 @@ export type DictationSessionState = "idle" | "listening" | "processing";
 export type DictationEvent =
   | { type: "state"; state: DictationSessionState }
   | { type: "level"; value: number }
+  | { type: "partial"; text: string }
   | { type: "transcript"; text: string }
   | { type: "error"; message: string }
   | { type: "canceled"; message: string };
@@ -373,3 +823,962 @@ export type DictationTranscript = {
   id: string;
   text: string;
 };
+
+export type ContextReason =
+  | "pathMention"
+  | "fileName"
+  | "pathSegment"
+  | "symbol"
+  | "currentDiff"
+  | "recentlyChanged";
+
+export type ContextFileSuggestion = {
+  path: string;
+  score: number;
+  reasons: ContextReason[];
+  symbols: string[];
+};
+
+export type SemanticPassageSource = "message" | "commandOutput";
+
+export type SemanticMatch = {
+  workspaceId: string;
+  threadId: string | null;
+  itemId: string;
+  source: SemanticPassageSource;
+  role: string | null;
+  text: string;
+  createdAt: number;
+  score: number;
+};
+
+export type EffectivePolicy = {
+  source: string | null;
+  error: string | null;
+  disallowFullAccess: boolean;
+  approvalPolicy: string | null;
+  writableRoots: string[] | null;
+  codexBin: string | null;
+};
+
+export type DelegatedDecision = {
+  delegationId: string;
+  approver: string;
+  signedAt: number;
+  signature: string;
+};
+
+export type ApprovalAuditAction =
+  | "forwarded"
+  | "delegated"
+  | "answeredLocally"
+  | "rejected"
+  | "directoryGranted"
+  | "directoryDenied"
+  | "fullAccessGranted"
+  | "fullAccessReverted"
+  | "answeredByShortcut"
+  | "answeredFromInbox";
+
+export type ApprovalAuditEntry = {
+  recordedAt: number;
+  workspaceId: string;
+  // Null for entries with no app-server request behind them.
+  requestId: RequestId | null;
+  delegationId: string | null;
+  action: ApprovalAuditAction;
+  actor: string;
+  method: string | null;
+  decision: Record<string, unknown> | null;
+  detail: string | null;
+  // Null for the first entry and for entries written before chaining.
+  prevHash: string | null;
+  hash: string | null;
+};
+
+export type AuditChainBreak = {
+  day: string;
+  index: number;
+  reason: string;
+};
+
+export type AuditChainReport = {
+  entries: number;
+  unchained: number;
+  anchor: string | null;
+  head: string | null;
+  intact: boolean;
+  brokenAt: AuditChainBreak | null;
+};
+
+export type ProposedFileChange = {
+  itemId: string;
+  path: string;
+  kind: string | null;
+  diff: string;
+};
+
+export type ProposedPatch = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string;
+  files: ProposedFileChange[];
+  completed: boolean;
+  patch: string;
+};
+
+export type StagedPatchSource = "dryRun" | "declinedApproval" | "scratch";
+
+export type PatchHunk = {
+  header: string;
+  oldStart: number;
+  lines: string[];
+  applied: boolean;
+};
+
+export type StagedFile = {
+  path: string;
+  kind: string | null;
+  hunks: PatchHunk[];
+};
+
+export type StagedPatch = {
+  id: string;
+  workspaceId: string;
+  threadId: string;
+  turnId: string | null;
+  source: StagedPatchSource;
+  createdAt: number;
+  files: StagedFile[];
+};
+
+export type HunkRef = {
+  path: string;
+  hunk: number;
+};
+
+export type HunkConflict = HunkRef & {
+  reason: string;
+};
+
+export type FileWriteError = {
+  path: string;
+  stage: "backup" | "write" | "remove" | "restore";
+  message: string;
+};
+
+export type ApplyStagedPatchReport = {
+  applied: HunkRef[];
+  conflicts: HunkConflict[];
+  errors: FileWriteError[];
+  patchCompleted: boolean;
+};
+
+export type DirtyFile = {
+  path: string;
+  sha256: string | null;
+};
+
+export type TurnManifest = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string;
+  recordedAt: number;
+  gitSha: string | null;
+  gitBranch: string | null;
+  dirtyFiles: DirtyFile[];
+  codexVersion: string | null;
+  model: string | null;
+  effort: string | null;
+  accessMode: string;
+  approvalPolicy: string;
+  sandboxPolicy: Record<string, unknown>;
+  collaborationMode: Record<string, unknown> | null;
+  configOverrides: Record<string, unknown>;
+};
+
+export type BestOfAttempt = {
+  index: number;
+  threadId: string;
+  turnId: string | null;
+};
+
+export type BestOfStarted = {
+  runId: string;
+  attempts: BestOfAttempt[];
+};
+
+export type VerificationOutcome = {
+  passed: boolean;
+  exitCode: number | null;
+  timedOut: boolean;
+  output: string;
+};
+
+export type TurnVerification = {
+  threadId: string;
+  turnId: string | null;
+  command: string;
+  verification: VerificationOutcome;
+  fixAttempt: number;
+  willFix: boolean;
+};
+
+export type BestOfResult = BestOfAttempt & {
+  rank: number;
+  filesChanged: number;
+  linesAdded: number;
+  linesRemoved: number;
+  patch: string;
+  verification: VerificationOutcome | null;
+};
+
+export type BestOfCompleted = {
+  runId: string;
+  workspaceId: string;
+  prompt: string;
+  verifyCommand: string | null;
+  results: BestOfResult[];
+};
+
+export type PromptSizeCheck = {
+  level: "ok" | "warning" | "error";
+  model: string | null;
+  estimatedTokens: number;
+  contextTokens: number;
+  contextWindow: number | null;
+  suggestions: string[];
+};
+
+export type TokenRange = {
+  low: number;
+  high: number;
+};
+
+export type ModelPricing = {
+  inputPerMillion: number;
+  cachedInputPerMillion: number;
+  outputPerMillion: number;
+};
+
+export type TurnEstimate = {
+  model: string | null;
+  effort: string | null;
+  promptTokens: number;
+  contextTokens: number;
+  contextWindow: number | null;
+  inputTokens: TokenRange;
+  outputTokens: TokenRange;
+  costUsd: TokenRange | null;
+  pricing: ModelPricing | null;
+};
+
+export type OfflineTurn = {
+  id: string;
+  workspaceId: string;
+  threadId: string;
+  text: string;
+  model: string | null;
+  effort: string | null;
+  accessMode: string | null;
+  images: string[] | null;
+  collaborationMode: Record<string, unknown> | null;
+  dryRun: boolean;
+  queuedAt: number;
+};
+
+export type WorkspaceCheckId =
+  | "pathExists"
+  | "gitRepository"
+  | "writable"
+  | "syncedFolder"
+  | "codexConfig";
+
+export type WorkspaceFinding = {
+  id: WorkspaceCheckId;
+  severity: "ok" | "info" | "warning" | "error";
+  message: string;
+  detail: string | null;
+};
+
+export type WorkspaceDoctorReport = {
+  workspaceId: string;
+  path: string;
+  ok: boolean;
+  findings: WorkspaceFinding[];
+};
+
+export type DoctorIssueId =
+  | "codexNotOnPath"
+  | "nodeMissingNvm"
+  | "codexHomePermissions";
+
+export type DoctorIssue = {
+  id: DoctorIssueId;
+  label: string;
+  detail: string | null;
+};
+
+export type DoctorFixOutput = {
+  issueId: DoctorIssueId;
+  stream: "stdout" | "stderr" | "info";
+  line: string;
+};
+
+export type DoctorFixResult = {
+  issueId: DoctorIssueId;
+  ok: boolean;
+  message: string;
+};
+
+export type ConnectivityProbe = {
+  url: string;
+  ok: boolean;
+  status: number | null;
+  elapsedMs: number;
+  proxy: string | null;
+  error: string | null;
+  hint: string | null;
+};
+
+export type ConnectionSimulation = {
+  enabled: boolean;
+  latencyMs: number;
+  jitterMs: number;
+  dropPercent: number;
+  bandwidthKbps: number | null;
+};
+
+export type FocusState = {
+  doNotDisturb: boolean;
+  source: string | null;
+};
+
+export type NotificationKind =
+  | "approval"
+  | "turnCompleted"
+  | "turnError"
+  | "slaBreach"
+  | "healthReport"
+  | "threadUnblocked"
+  | "authExpired";
+
+export type NotificationRoute = {
+  delivery: "system" | "activityFeed";
+  doNotDisturb: boolean;
+  brokeThrough: boolean;
+};
+
+export type AppLockStatus = {
+  locked: boolean;
+  autoLockMinutes: number | null;
+  passphraseRequired: boolean;
+};
+
+export type TurnLane = "interactive" | "scheduled" | "batch";
+
+export type TurnSlot = {
+  workspaceId: string;
+  threadId: string;
+  lane: TurnLane;
+};
+
+export type TurnLimiterStatus = {
+  limit: number | null;
+  running: TurnSlot[];
+  waiting: TurnSlot[];
+};
+
+export type MethodCount = {
+  method: string;
+  count: number;
+  firstSeenAt: number;
+  replacement: string | null;
+};
+
+export type SessionProtocolReport = {
+  workspaceId: string;
+  codexVersion: string | null;
+  knownCount: number;
+  deprecated: MethodCount[];
+  unknown: MethodCount[];
+};
+
+export type ProtocolCompatReport = {
+  sessions: SessionProtocolReport[];
+  cliNewerThanApp: boolean;
+  cliOlderThanApp: boolean;
+};
+
+export type ThreadTurnSettings = {
+  workspaceId: string;
+  threadId: string;
+  model: string | null;
+  effort: string | null;
+  accessMode: string | null;
+  updatedAt: number;
+};
+
+export type ThreadTags = {
+  workspaceId: string;
+  threadId: string;
+  tags: string[];
+  updatedAt: number;
+};
+
+export type ThreadStatus = "running" | "idle" | "archived";
+
+export type SavedThreadFilter = {
+  id: string;
+  name: string;
+  tags: string[];
+  workspaceIds: string[];
+  statuses: ThreadStatus[];
+  updatedWithinDays: number | null;
+  updatedAfter: number | null;
+  updatedBefore: number | null;
+};
+
+export type ThreadRecord = {
+  workspaceId: string;
+  threadId: string;
+  title: string;
+  updatedAt: number | null;
+  status: ThreadStatus;
+  tags: string[];
+};
+
+export type ThreadSort = "updatedDesc" | "updatedAsc" | "titleAsc";
+
+export type ThreadQueryFilters = {
+  workspaceIds?: string[];
+  statuses?: ThreadStatus[];
+  tags?: string[];
+  query?: string | null;
+  updatedAfter?: number | null;
+  updatedBefore?: number | null;
+};
+
+export type ThreadWindow = {
+  offset: number;
+  total: number;
+  indexed: number;
+  refreshedAt: number | null;
+  threads: ThreadRecord[];
+};
+
+export type ActionArgumentKind = "workspaceId" | "threadId" | "string" | "json";
+
+export type ActionArgument = {
+  name: string;
+  kind: ActionArgumentKind;
+  required: boolean;
+};
+
+export type ActionDefinition = {
+  id: string;
+  title: string;
+  category: string;
+  arguments: ActionArgument[];
+};
+
+export type ThreadBranch = {
+  workspaceId: string;
+  threadId: string;
+  branch: string;
+  baseBranch: string;
+  createdAt: number;
+};
+
+export type QuotaDeferral = {
+  window: string;
+  remainingPercent: number;
+  reservePercent: number;
+  resumesAt: number;
+};
+
+export type TurnDeferredEvent = {
+  workspaceId: string;
+  threadId: string;
+  lane: TurnLane;
+  deferral: QuotaDeferral | null;
+};
+
+export type CloneProgressEvent = {
+  url: string;
+  destination: string;
+  stage: string;
+  percent: number | null;
+  message: string;
+};
+
+export type ClonedWorkspace = {
+  workspace: WorkspaceInfo;
+  doctor: WorkspaceDoctorReport | null;
+  onboardingThreadId: string | null;
+};
+
+export type UnappliedPatch = {
+  id: string;
+  turnId: string | null;
+  files: string[];
+  unappliedHunks: number;
+};
+
+export type PendingApproval = {
+  requestId: RequestId;
+  method: string;
+  turnId: string | null;
+};
+
+export type ThreadAttention = {
+  workspaceId: string;
+  threadId: string;
+  stagedPatches: UnappliedPatch[];
+  pendingApprovals: PendingApproval[];
+};
+
+export type ThreadAttentionError = {
+  code: "needsAttention";
+  message: string;
+  attention: ThreadAttention;
+};
+
+export type BackfillItem = {
+  turnId: string | null;
+  item: Record<string, unknown>;
+};
+
+export type ThreadBackfill = {
+  threadId: string;
+  items: BackfillItem[];
+};
+
+export type SessionEnvironment = {
+  program: string;
+  args: string[];
+  commandLine: string;
+  cwd: string | null;
+  path: string[];
+  env: Record<string, string>;
+  overridden: string[];
+  launchedAt: number;
+};
+
+export type ReviewPairStatus = "building" | "reviewing" | "failed";
+
+export type ReviewPair = {
+  pairId: string;
+  workspaceId: string;
+  worktreeId: string;
+  branch: string;
+  prompt: string;
+  builderThreadId: string;
+  builderTurnId: string | null;
+  reviewerThreadId: string;
+  status: ReviewPairStatus;
+  error: string | null;
+  createdAt: number;
+};
+
+export type AnnotationReaction = "thumbsUp" | "thumbsDown";
+
+export type Annotation = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string;
+  itemId: string | null;
+  reaction: AnnotationReaction | null;
+  comment: string | null;
+  needsFollowUp: boolean;
+  updatedAt: number;
+};
+
+export type ModelFeedback = {
+  model: string;
+  thumbsUp: number;
+  thumbsDown: number;
+  successRate: number | null;
+  followUps: number;
+};
+
+export type PromptFeature =
+  | "fileReferences"
+  | "snippets"
+  | "testExpectations"
+  | "detail";
+
+export type OutcomeMetric = "interrupted" | "retried" | "disliked";
+
+export type PromptInsight = {
+  feature: PromptFeature;
+  metric: OutcomeMetric;
+  worseWithFeature: boolean;
+  withRate: number;
+  withoutRate: number;
+  withTurns: number;
+  withoutTurns: number;
+  ratio: number;
+  message: string;
+};
+
+export type PromptingInsights = {
+  days: number;
+  turns: number;
+  interruptRate: number;
+  retryRate: number;
+  insights: PromptInsight[];
+};
+
+export type EditorInfo = {
+  id: string;
+  label: string;
+  command: string;
+  available: boolean;
+};
+
+export type ThreadTimer = {
+  workspaceId: string;
+  threadId: string;
+  activeMs: number;
+  turns: number;
+  clockJumpTurns: number;
+  slaMinutes: number | null;
+  breachedAt: number | null;
+  updatedAt: number;
+};
+
+export type SlaBreach = {
+  workspaceId: string;
+  threadId: string;
+  slaMinutes: number;
+  activeMs: number;
+};
+
+export type EventBackfill = {
+  events: AppServerEvent[];
+  latestSeq: number;
+  complete: boolean;
+};
+
+export type ConfigChange = {
+  workspaceId: string;
+  paths: string[];
+};
+
+export type SessionReload = {
+  workspaceId: string;
+  resumedThreadId: string | null;
+};
+
+export type SelftestScenario = {
+  name: string;
+  passed: boolean;
+  detail: string | null;
+  durationMs: number;
+};
+
+export type SelftestReport = {
+  appVersion: string;
+  os: string;
+  passed: boolean;
+  scenarios: SelftestScenario[];
+};
+
+/** UTC time plus monotonic ms since app start, which ignores clock jumps. */
+export type ClockTimestamp = {
+  utcMs: number;
+  monoMs: number;
+  offsetMinutes: number;
+};
+
+export type ClockJump = {
+  before: ClockTimestamp;
+  after: ClockTimestamp;
+  kind: "forward" | "backward";
+  skewMs: number;
+};
+
+export type WorkspaceRecovery = {
+  workspaceId: string;
+  status: "healthy" | "reconnected" | "failed";
+  error: string | null;
+};
+
+export type ResumeReport = {
+  sleptMs: number;
+  resumedAt: number;
+  workspaces: WorkspaceRecovery[];
+};
+
+export type TurnArtifact = {
+  path: string;
+  absolutePath: string;
+  size: number;
+  modifiedAt: number | null;
+};
+
+export type DirectoryAccessRequest = {
+  workspaceId: string;
+  threadId: string;
+  requestId: RequestId;
+  path: string;
+  reason: string | null;
+};
+
+export type WorkspaceHealthSignals = {
+  sessionsStarted: number;
+  sessionsLost: number;
+  turns: number;
+  failedTurns: number;
+  approvals: number;
+  averageApprovalMs: number | null;
+  doctorErrors: number;
+  doctorWarnings: number;
+};
+
+export type WorkspaceHealthPenalties = {
+  sessionStability: number;
+  failedTurns: number;
+  approvalLatency: number;
+  doctorFindings: number;
+};
+
+export type WorkspaceHealth = {
+  workspaceId: string;
+  name: string;
+  score: number;
+  signals: WorkspaceHealthSignals;
+  penalties: WorkspaceHealthPenalties;
+};
+
+export type WorkspaceHealthReport = {
+  generatedAt: number;
+  days: number;
+  workspaces: WorkspaceHealth[];
+};
+
+export type SessionOutputLine = {
+  workspaceId: string;
+  stream: "stdout" | "stderr";
+  line: string;
+  truncated: boolean;
+  at: number;
+};
+
+export type TurnLabels = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string;
+  labels: string[];
+  updatedAt: number;
+};
+
+export type ActiveTurn = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string | null;
+  startedAt: number;
+};
+
+export type ActiveTurnError = {
+  code: "turnActive";
+  message: string;
+  activeTurn: ActiveTurn;
+};
+
+export type TerminalHandoff = {
+  commandLine: string;
+  launched: boolean;
+};
+
+export type ModelStats = {
+  model: string | null;
+  effort: string | null;
+  turns: number;
+  averageDurationMs: number | null;
+  interruptRate: number;
+  verifiedTurns: number;
+  verificationPassRate: number | null;
+  acceptedChanges: number;
+  tokensPerAcceptedChange: number | null;
+};
+
+export type ModelComparison = {
+  days: number;
+  models: ModelStats[];
+};
+
+export type FullAccessGrant = {
+  workspaceId: string;
+  threadId: string;
+  grantedAt: number;
+  expiresAt: number;
+};
+
+export type ThreadLinkKind = "blockedOn" | "followUpOf";
+
+export type ThreadLink = {
+  workspaceId: string;
+  threadId: string;
+  kind: ThreadLinkKind;
+  targetWorkspaceId: string;
+  targetThreadId: string;
+  createdAt: number;
+  // Set once a turn of the blocking thread completed.
+  unblockedAt: number | null;
+};
+
+export type BrokenCodexBin = {
+  workspaceId: string;
+  workspaceName: string;
+  codexBin: string;
+  suggestion: string | null;
+};
+
+export type CodexBinRemap = {
+  workspaceId: string;
+  from: string;
+  to: string;
+};
+
+export type AuthChanged = {
+  workspaceIds: string[];
+};
+
+export type AccountLoginStart = {
+  type: "chatgpt";
+  loginId: string;
+  authUrl: string;
+};
+
+export type TeamActivitySettings = {
+  enabled: boolean;
+  serverUrl: string | null;
+  token: string | null;
+  memberName: string;
+  shareSummaries: boolean;
+  shareWorkspaceNames: boolean;
+};
+
+export type TeamActivityStatus = "running" | "completed" | "failed" | "interrupted";
+
+export type TeamActivity = {
+  member: string;
+  workspace: string;
+  threadKey: string;
+  status: TeamActivityStatus;
+  summary?: string | null;
+  filesChanged?: number | null;
+  commandsRun?: number | null;
+  durationMs?: number | null;
+  updatedAt: number;
+};
+
+export type WorkspaceLock = {
+  pid: number;
+  host: string;
+  acquiredAt: number;
+  refreshedAt: number;
+};
+
+export type WorkspaceLockConflict = {
+  workspaceId: string;
+  lock: WorkspaceLock;
+};
+
+export type WorkspaceField = "path" | "codexBin";
+
+export type WorkspaceFieldErrorReason =
+  | "empty"
+  | "notFound"
+  | "notADirectory"
+  | "duplicate"
+  | "notExecutable";
+
+export type WorkspaceFieldError = {
+  field: WorkspaceField;
+  reason: WorkspaceFieldErrorReason;
+  message: string;
+  existingWorkspaceId?: string;
+};
+
+export type WorkspaceValidationError = {
+  code: "invalidWorkspace";
+  message: string;
+  errors: WorkspaceFieldError[];
+};
+
+export type TelemetrySettings = {
+  enabled: boolean;
+  shareAggregates: boolean;
+  endpoint: string | null;
+};
+
+export type FeatureUsage = {
+  feature: string;
+  count: number;
+  daysUsed: number;
+};
+
+export type TelemetryReport = {
+  days: number;
+  total: number;
+  features: FeatureUsage[];
+  daily: { day: string; total: number }[];
+};
+
+export type InboxApproval = {
+  id: string;
+  workspaceId: string;
+  requestId: RequestId;
+  threadId: string;
+  turnId: string | null;
+  method: string;
+  command: string | null;
+  cwd: string | null;
+  reason: string | null;
+  receivedAt: number;
+};
+
+export type QuickAskAnswer = {
+  threadId: string;
+  answer: string;
+  truncated: boolean;
+};
+
+export type ThreadHandoff = {
+  workspaceId: string;
+  threadId: string;
+  summary: string;
+  // Artifacts copied to the new thread, by their path from listTurnArtifacts.
+  artifacts: string[];
+  link: ThreadLink;
+};
+
+// 1-based and inclusive.
+export type LineRange = {
+  start: number;
+  end: number;
+};
+
+export type BlameCommit = {
+  sha: string;
+  author: string;
+  timestamp: number;
+  summary: string;
+  message: string;
+  // Lines of the requested region this commit last changed.
+  lines: LineRange[];
+};
+
+export type BlameContext = {
+  path: string;
+  range: LineRange;
+  head: string | null;
+  commits: BlameCommit[];
+  uncommittedLines: LineRange[];
+  cached: boolean;
+  // The history as plain text, ready to add to a prompt.
+  text: string;
+};
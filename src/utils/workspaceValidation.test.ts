@@ -0,0 +1,34 @@
+import { describe, expect, it } from "vitest";
+import {
+  formatWorkspaceError,
+  parseWorkspaceValidationError,
+} from "./workspaceValidation";
+
+const structured = JSON.stringify({
+  code: "invalidWorkspace",
+  message: "/tmp/a.txt is not a folder. Codex binary /x/codex was not found.",
+  errors: [
+    { field: "path", reason: "notADirectory", message: "/tmp/a.txt is not a folder." },
+    { field: "codexBin", reason: "notFound", message: "Codex binary /x/codex was not found." },
+  ],
+});
+
+describe("parseWorkspaceValidationError", () => {
+  it("parses structured errors", () => {
+    const parsed = parseWorkspaceValidationError(structured);
+    expect(parsed?.errors.map((entry) => entry.field)).toEqual(["path", "codexBin"]);
+  });
+
+  it("ignores plain errors", () => {
+    expect(parseWorkspaceValidationError(new Error("boom"))).toBeNull();
+  });
+});
+
+describe("formatWorkspaceError", () => {
+  it("labels each field", () => {
+    expect(formatWorkspaceError(structured)).toBe(
+      "Folder: /tmp/a.txt is not a folder.\nCodex binary: Codex binary /x/codex was not found.",
+    );
+    expect(formatWorkspaceError(new Error("boom"))).toBe("boom");
+  });
+});
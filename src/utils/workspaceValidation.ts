@@ -0,0 +1,31 @@
+import type { WorkspaceField, WorkspaceValidationError } from "../types";
+
+const FIELD_LABELS: Record<WorkspaceField, string> = {
+  path: "Folder",
+  codexBin: "Codex binary",
+};
+
+export function parseWorkspaceValidationError(
+  error: unknown,
+): WorkspaceValidationError | null {
+  const raw = error instanceof Error ? error.message : String(error);
+  try {
+    const parsed = JSON.parse(raw) as Partial<WorkspaceValidationError>;
+    if (parsed?.code === "invalidWorkspace" && Array.isArray(parsed.errors)) {
+      return parsed as WorkspaceValidationError;
+    }
+  } catch {
+    // Not a structured validation error.
+  }
+  return null;
+}
+
+export function formatWorkspaceError(error: unknown): string {
+  const validation = parseWorkspaceValidationError(error);
+  if (!validation) {
+    return error instanceof Error ? error.message : String(error);
+  }
+  return validation.errors
+    .map((entry) => `${FIELD_LABELS[entry.field]}: ${entry.message}`)
+    .join("\n");
+}